@@ -0,0 +1,258 @@
+use std::path::{Path, PathBuf};
+
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::app_clipboard::{
+    copy_key_as_command_to_clipboard, copy_key_as_json_to_clipboard, copy_key_name_to_clipboard,
+    copy_selected_key_value_to_clipboard,
+};
+use super::notifications::Severity;
+use super::state_action_menu::{
+    actions_for, export_file_name, prompt_command, value_file_name, value_file_path, write_value_file, KeyAction,
+};
+use super::value_fetch::ValuePurpose;
+use super::worker::WorkerRequest;
+use super::App;
+use crate::headless::key_document;
+
+impl App {
+    /// `a` or right-click: opens the action menu for the selected key, loading it into the
+    /// value panel first when the key list points at another key.
+    pub fn open_action_menu(&mut self) {
+        let selected = self
+            .is_key_view_focused
+            .then(|| {
+                self.visible_keys_in_current_view
+                    .get(self.selected_visible_key_index)
+                    .filter(|(_, is_folder)| !*is_folder)
+                    .and_then(|(name, _)| self.leaf_full_name(name))
+            })
+            .flatten()
+            .filter(|_| !self.search_state.is_active);
+        if let Some(key) = selected {
+            if self.value_viewer.active_leaf_key_name.as_deref() != Some(key.as_str()) {
+                // The menu opens once the value is in, as its entries depend on the type
+                self.open_leaf_key(&key, ValuePurpose::ActionMenu);
+                return;
+            }
+        }
+        self.show_action_menu();
+    }
+
+    /// Opens the action menu for the key in the value panel.
+    pub(super) fn show_action_menu(&mut self) {
+        let Some(key) = self.value_viewer.active_leaf_key_name.clone() else {
+            self.notifications.info("Select a key to act on first.".to_string());
+            return;
+        };
+        let key_type = self.value_viewer.selected_key_type.as_deref().unwrap_or_default().to_lowercase();
+        if key_type == "none" {
+            self.notifications.error(format!("Key not found: {}", key));
+            return;
+        }
+        let actions = actions_for(&key_type, self.value_viewer.hyperloglog.is_some(), self.is_read_only());
+        self.action_menu.open(key, key_type, actions);
+    }
+
+    /// Runs the selected entry: shortcuts go through their own handlers, input-taking
+    /// entries open the menu's prompt.
+    pub fn choose_key_action(&mut self) {
+        let Some(action) = self.action_menu.selected_action() else {
+            return;
+        };
+        let key = self.action_menu.key.clone();
+        if action.prompt(&self.action_menu.key_type).is_some() {
+            let initial = match action {
+                KeyAction::EditValue => self.editable_value(),
+                KeyAction::Rename => key,
+                KeyAction::SaveValue => value_file_name(&key, &self.action_menu.key_type),
+                KeyAction::SetTtl => match self.ttl_map.get(&key) {
+                    Some(ttl) if *ttl > 0 => ttl.to_string(),
+                    _ => String::new(),
+                },
+                _ => String::new(),
+            };
+            self.action_menu.open_prompt(action, initial);
+            return;
+        }
+        self.action_menu.close();
+        match action {
+            KeyAction::CopyName => copy_key_name_to_clipboard(self, key),
+            KeyAction::Export => self.export_key(key),
+            KeyAction::CopyValue => copy_selected_key_value_to_clipboard(self),
+            KeyAction::CopyJson => copy_key_as_json_to_clipboard(self),
+            KeyAction::CopyCommand => copy_key_as_command_to_clipboard(self),
+            KeyAction::PfAdd => self.open_pfadd_prompt(),
+            KeyAction::Probe => self.open_probe_prompt(),
+            KeyAction::FollowStream => self.toggle_stream_follow(),
+            KeyAction::ConsumerGroups => self.open_consumer_groups(),
+            KeyAction::KeyInfo => self.open_key_info(),
+            KeyAction::Bookmark => self.toggle_bookmark(),
+            KeyAction::Delete => self.delete_dialog.initiate_delete_key(&key),
+            KeyAction::EditValue | KeyAction::AddItem | KeyAction::SetTtl | KeyAction::Rename | KeyAction::SaveValue => {}
+        }
+    }
+
+    /// The shown value as a one-line starting point for "Edit value"; empty when it is
+    /// binary, decoded, chunked or spans several lines.
+    fn editable_value(&self) -> String {
+        let viewer = &self.value_viewer;
+        if let Some(json) = &viewer.selected_key_value_json {
+            return serde_json::from_str::<serde_json::Value>(json)
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+        }
+        if viewer.string_chunk.is_some() || viewer.decoded_as.is_some() || viewer.decompressed.is_some() {
+            return String::new();
+        }
+        viewer
+            .selected_key_value
+            .clone()
+            .filter(|value| !value.contains('\n'))
+            .unwrap_or_default()
+    }
+
+    /// Runs the typed prompt against the menu's key.
+    pub fn submit_key_action_prompt(&mut self) {
+        let Some(action) = self.action_menu.prompt else {
+            return;
+        };
+        let key = self.action_menu.key.clone();
+        if action == KeyAction::SaveValue {
+            self.save_value_to_file(key);
+            return;
+        }
+        // Invalid input keeps the prompt open for correction
+        let cmd = match prompt_command(action, &self.action_menu.key_type, &key, &self.action_menu.input) {
+            Ok(cmd) => cmd,
+            Err(message) => {
+                self.notifications.error(message);
+                return;
+            }
+        };
+        let input = std::mem::take(&mut self.action_menu.input);
+        self.action_menu.close();
+        let Some(con) = self.writer("key changes") else {
+            return;
+        };
+        let label = action.label(&self.action_menu.key_type).to_string();
+        self.submit(WorkerRequest::KeyCommand {
+            con,
+            cmd,
+            key,
+            action,
+            label,
+            input,
+        });
+    }
+
+    /// Reports a prompt command; a rename then goes to the renamed key.
+    pub(super) fn apply_key_command(
+        &mut self,
+        key: String,
+        action: KeyAction,
+        label: String,
+        input: String,
+        reply: Result<Value, String>,
+    ) {
+        match reply {
+            Ok(Value::Int(0)) if action == KeyAction::Rename => {
+                self.notifications.error(format!("Rename failed: '{}' already exists.", input.trim()));
+            }
+            Ok(_) if action == KeyAction::Rename => {
+                let new_name = input.trim().to_string();
+                self.notifications.success(format!("Renamed '{}' to '{}'.", key, new_name));
+                self.type_map_mut().remove(&key);
+                self.refresh_key_tree();
+                self.goto_key_prompt = Some(new_name);
+                self.goto_key();
+            }
+            Ok(_) => {
+                self.notifications.success(format!("{}: done on '{}'.", label, key));
+                self.key_cache.invalidate(&key);
+                if self.value_viewer.active_leaf_key_name.as_deref() == Some(key.as_str()) {
+                    self.refresh_active_key();
+                }
+            }
+            Err(e) => self.notifications.error(format!("{} failed: {}", label, e)),
+        }
+    }
+
+    /// Writes the stored bytes of a string, or the document `JSON.GET` returns, to the
+    /// typed path. The value is fetched whole, not from the shown (possibly chunked or
+    /// decoded) display.
+    fn save_value_to_file(&mut self, key: String) {
+        let path = match value_file_path(&self.action_menu.input) {
+            Ok(path) => path,
+            Err(message) => {
+                self.notifications.error(message);
+                return;
+            }
+        };
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected.");
+            return;
+        };
+        let command = if self.action_menu.key_type == "rejson-rl" { "JSON.GET" } else { "GET" };
+        self.submit(WorkerRequest::SaveValue { con, key, command, path });
+    }
+
+    /// Closes the prompt once the value is saved; a failed save keeps it open to pick
+    /// another path.
+    pub(super) fn apply_saved_value(&mut self, key: String, path: PathBuf, saved: Result<usize, String>) {
+        let len = match saved {
+            Ok(len) => len,
+            Err(message) => {
+                self.notifications.error(message);
+                return;
+            }
+        };
+        if self.action_menu.prompt == Some(KeyAction::SaveValue) && self.action_menu.key == key {
+            self.action_menu.input.clear();
+            self.action_menu.close();
+        }
+        self.notifications.success(format!("Saved {} bytes of '{}' to {}", len, key, path.display()));
+    }
+
+    /// Writes the key as `get --json` prints it to a file in the working directory.
+    fn export_key(&mut self, key: String) {
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected.");
+            return;
+        };
+        self.submit(WorkerRequest::ExportKey { con, key });
+    }
+}
+
+/// Reads the value `save_value_to_file` asked for with `command` and writes it to `path`.
+/// Returns the bytes written, or the error toast.
+pub(super) async fn save_value(
+    mut con: MultiplexedConnection,
+    key: &str,
+    command: &str,
+    path: &Path,
+) -> Result<usize, String> {
+    let bytes = match redis::cmd(command).arg(key).query_async::<Option<Vec<u8>>>(&mut con).await {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => return Err(format!("Key not found: {}", key)),
+        Err(e) => return Err(format!("Failed to read '{}': {}", key, e)),
+    };
+    write_value_file(path, &bytes).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(bytes.len())
+}
+
+pub(super) async fn export_key(mut con: MultiplexedConnection, key: &str) -> (Severity, String) {
+    let document = match key_document(&mut con, key).await {
+        Ok(document) => document,
+        Err(e) => return (Severity::Error, format!("Failed to export '{}': {}", key, e)),
+    };
+    let path = Path::new(".").join(export_file_name(key));
+    let written = serde_json::to_string_pretty(&document)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+    match written {
+        Ok(()) => (Severity::Success, format!("Exported '{}' to {}", key, path.display())),
+        Err(e) => (Severity::Error, format!("Failed to export '{}': {}", key, e)),
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::time::Instant;
+
+use super::key_sampler::{KeySampler, KeySamplerEvent};
+use super::monitor::{MonitorEvent, MonitorSession};
+use super::state_analysis::AnalysisView;
+use super::App;
+
+impl App {
+    /// Opens keyspace analysis on `view`, walking the keyspace unless earlier results exist.
+    pub fn open_analysis(&mut self, view: AnalysisView) {
+        self.analysis.is_active = true;
+        self.analysis.view = view;
+        if self.analysis.sampler.is_none() && self.analysis.scanned == 0 {
+            self.restart_analysis();
+        }
+    }
+
+    pub fn restart_analysis(&mut self) {
+        self.analysis.reset();
+        let Some(con) = self.redis.reader() else {
+            self.analysis.error = Some("Not connected".to_string());
+            return;
+        };
+        self.analysis.sampler = Some(KeySampler::spawn(con, self.scan_pattern.clone()));
+    }
+
+    pub fn poll_analysis(&mut self) {
+        self.poll_hot_key_monitor();
+        let state = &mut self.analysis;
+        let Some(sampler) = &mut state.sampler else {
+            return;
+        };
+        for event in sampler.drain() {
+            match event {
+                KeySamplerEvent::Samples { samples, .. } => state.record(samples, self.key_delimiters.primary()),
+                KeySamplerEvent::Done => {
+                    state.sampler = None;
+                    break;
+                }
+                KeySamplerEvent::Error(message) => {
+                    state.error = Some(message);
+                    state.sampler = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Counts key accesses through MONITOR for a few seconds, for servers
+    /// whose maxmemory-policy keeps no LFU counters.
+    pub fn start_hot_key_monitor(&mut self) {
+        let Some(client) = self.redis.client.clone() else {
+            self.analysis.error = Some("Not connected".to_string());
+            return;
+        };
+        self.analysis.hot_keys.start_monitor(MonitorSession::spawn(client));
+    }
+
+    fn poll_hot_key_monitor(&mut self) {
+        let hot_keys = &mut self.analysis.hot_keys;
+        let Some(session) = &mut hot_keys.monitor else {
+            return;
+        };
+        let mut entries = Vec::new();
+        for event in session.drain() {
+            match event {
+                MonitorEvent::Entry(entry) => entries.push(entry),
+                MonitorEvent::Error(message) => {
+                    self.analysis.error = Some(message);
+                    hot_keys.stop_monitor();
+                    break;
+                }
+            }
+        }
+        hot_keys.record_accesses(&entries);
+        if hot_keys.monitor_until.is_some_and(|until| Instant::now() >= until) {
+            hot_keys.stop_monitor();
+        }
+    }
+
+    /// Jumps to the selected big or hot key in the tree, like opening a bookmark.
+    pub fn open_selected_analysis_key(&mut self) {
+        let Some(key) = self.analysis.selected_key().map(str::to_string) else {
+            return;
+        };
+        self.analysis.is_active = false;
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+
+    pub fn export_prefix_memory(&mut self) {
+        match self.analysis.prefix_memory.export_csv(Path::new("."), self.analysis.prefix_depth) {
+            Ok(path) => self.notifications.success(format!("Exported memory report to {}", path.display())),
+            Err(e) => self.notifications.error(format!("Failed to export memory report: {}", e)),
+        }
+    }
+}
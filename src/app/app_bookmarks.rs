@@ -0,0 +1,80 @@
+use super::App;
+
+impl App {
+    pub(crate) fn current_profile_name(&self) -> &str {
+        self.profiles
+            .get(self.current_profile_index)
+            .map_or("Default", |profile| profile.name.as_str())
+    }
+
+    pub fn current_bookmarks(&self) -> &[String] {
+        self.bookmarks.for_profile(self.current_profile_name())
+    }
+
+    /// Bookmarks the opened key, or the selected leaf in the key list, for the current profile.
+    pub fn toggle_bookmark(&mut self) {
+        let key = self.value_viewer.active_leaf_key_name.clone().or_else(|| {
+            self.visible_keys_in_current_view
+                .get(self.selected_visible_key_index)
+                .filter(|(_, is_folder)| !*is_folder)
+                .and_then(|(name, _)| self.leaf_full_name(name))
+        });
+        let Some(key) = key else {
+            self.notifications.info("Select a key to bookmark first.".to_string());
+            return;
+        };
+        let profile = self.current_profile_name().to_string();
+        let added = self.bookmarks.toggle(&profile, &key);
+        match self.bookmarks.save() {
+            Err(e) => self.notifications.error(format!("Failed to save bookmarks: {}", e)),
+            Ok(()) if added => self.notifications.success(format!("Bookmarked '{}'", key)),
+            Ok(()) => self.notifications.success(format!("Removed bookmark '{}'", key)),
+        }
+    }
+
+    pub fn toggle_bookmarks_panel(&mut self) {
+        self.bookmarks_panel.is_active = !self.bookmarks_panel.is_active;
+        self.bookmarks_panel.selected_index = 0;
+    }
+
+    pub fn next_bookmark(&mut self) {
+        let len = self.current_bookmarks().len();
+        self.bookmarks_panel.next(len);
+    }
+
+    pub fn previous_bookmark(&mut self) {
+        let len = self.current_bookmarks().len();
+        self.bookmarks_panel.previous(len);
+    }
+
+    /// Closes the panel and opens the selected bookmark through the goto-key path.
+    pub fn open_selected_bookmark(&mut self) {
+        let Some(key) = self
+            .current_bookmarks()
+            .get(self.bookmarks_panel.selected_index)
+            .cloned()
+        else {
+            return;
+        };
+        self.bookmarks_panel.is_active = false;
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+
+    pub fn remove_selected_bookmark(&mut self) {
+        let Some(key) = self
+            .current_bookmarks()
+            .get(self.bookmarks_panel.selected_index)
+            .cloned()
+        else {
+            return;
+        };
+        let profile = self.current_profile_name().to_string();
+        self.bookmarks.toggle(&profile, &key);
+        if let Err(e) = self.bookmarks.save() {
+            self.notifications.error(format!("Failed to save bookmarks: {}", e));
+        }
+        let len = self.current_bookmarks().len();
+        self.bookmarks_panel.selected_index = self.bookmarks_panel.selected_index.min(len.saturating_sub(1));
+    }
+}
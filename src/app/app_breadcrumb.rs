@@ -0,0 +1,51 @@
+use super::App;
+
+/// Label of the key tree root in the breadcrumb bar.
+const BREADCRUMB_ROOT: &str = "⌂";
+
+impl App {
+    /// Root followed by each folder of the current breadcrumb, as drawn in the bar.
+    pub fn breadcrumb_labels(&self) -> Vec<String> {
+        std::iter::once(BREADCRUMB_ROOT.to_string())
+            .chain(self.current_breadcrumb.iter().cloned())
+            .collect()
+    }
+
+    /// Left/Right: moves the bar's selection one level, starting from the current level.
+    pub fn move_breadcrumb_cursor(&mut self, delta: isize) {
+        let current = self.current_breadcrumb.len();
+        let from = self.breadcrumb_cursor.unwrap_or(current).min(current);
+        self.breadcrumb_cursor = Some(from.saturating_add_signed(delta).min(current));
+    }
+
+    /// Enter on the bar: opens the selected level. Returns false when no level is selected.
+    pub fn jump_to_breadcrumb_cursor(&mut self) -> bool {
+        let Some(level) = self.breadcrumb_cursor.take() else {
+            return false;
+        };
+        self.jump_to_breadcrumb_level(level);
+        true
+    }
+
+    /// Shows the tree level `level` folders deep in the current breadcrumb (0 is the root),
+    /// with the folder it was entered through selected.
+    pub fn jump_to_breadcrumb_level(&mut self, level: usize) {
+        self.breadcrumb_cursor = None;
+        if level >= self.current_breadcrumb.len() {
+            return;
+        }
+        let entered = self.current_breadcrumb[level].clone();
+        self.current_breadcrumb.truncate(level);
+        self.update_visible_keys();
+        self.start_level_scan();
+        self.clear_selected_key_info();
+        let folder = format!("{}/", entered);
+        if let Some(idx) = self
+            .visible_keys_in_current_view
+            .iter()
+            .position(|(name, is_folder)| *is_folder && *name == folder)
+        {
+            self.selected_visible_key_index = idx;
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use super::bulk_delete::{BulkDelete, BulkDeleteEvent};
+use super::App;
+
+impl App {
+    /// Starts deleting the folder or the marked items of the confirmed delete dialog in
+    /// the background.
+    pub(super) fn start_bulk_delete(&mut self) {
+        if let Some(running) = &self.bulk_delete {
+            self.notifications.error(format!(
+                "Still deleting {}; Esc in the key list stops it.",
+                running.label
+            ));
+            return;
+        }
+        let Some(con) = self.writer("deletes") else {
+            return;
+        };
+        let dialog = &self.delete_dialog;
+        let (keys, prefixes, label) = if dialog.is_multi_delete {
+            let mut keys = Vec::new();
+            let mut prefixes = Vec::new();
+            for item in &dialog.keys_to_delete {
+                match item.strip_prefix("folder:") {
+                    Some(prefix) => prefixes.push(prefix.to_string()),
+                    None => keys.push(item.clone()),
+                }
+            }
+            (keys, prefixes, "items".to_string())
+        } else {
+            let Some(prefix) = dialog.prefix_to_delete.clone() else {
+                self.notifications.error("Error deleting: Prefix to delete was None".to_string());
+                return;
+            };
+            let label = format!("keys matching prefix '{}'", prefix);
+            (Vec::new(), vec![prefix], label)
+        };
+        let delimiters = self.key_delimiters.clone();
+        self.bulk_delete = Some(BulkDelete::spawn(con, delimiters, label, keys, prefixes));
+    }
+
+    /// Counts deleted batches and reloads the key tree once the delete is done.
+    pub fn poll_bulk_delete(&mut self) {
+        let Some(delete) = &mut self.bulk_delete else {
+            return;
+        };
+        let mut finished = false;
+        for event in delete.drain() {
+            match event {
+                BulkDeleteEvent::Deleted(count) => delete.deleted += count,
+                BulkDeleteEvent::Error(e) => delete.errors.push(e),
+                BulkDeleteEvent::Done => finished = true,
+            }
+        }
+        if !finished {
+            return;
+        }
+        let Some(delete) = self.bulk_delete.take() else {
+            return;
+        };
+        if delete.errors.is_empty() {
+            self.notifications.success(format!("Deleted {} {}.", delete.deleted, delete.label));
+        } else {
+            self.notifications.error(format!(
+                "Error deleting: Deleted {} {}, but encountered errors: {}",
+                delete.deleted,
+                delete.label,
+                delete.errors.join("; ")
+            ));
+        }
+        self.reload_after_bulk_delete();
+    }
+
+    /// Esc: stops the running delete. Keys removed so far stay removed.
+    pub fn cancel_bulk_delete(&mut self) {
+        let Some(mut delete) = self.bulk_delete.take() else {
+            return;
+        };
+        for event in delete.drain() {
+            if let BulkDeleteEvent::Deleted(count) = event {
+                delete.deleted += count;
+            }
+        }
+        self.notifications.info(format!(
+            "Delete stopped after removing {} {}; the rest are kept.",
+            delete.deleted, delete.label
+        ));
+        self.reload_after_bulk_delete();
+    }
+
+    fn reload_after_bulk_delete(&mut self) {
+        self.fetch_keys_and_build_tree();
+        self.update_visible_keys();
+    }
+}
@@ -1,4 +1,12 @@
+use crate::app::notifications::Severity;
+use crate::app::osc52;
+use crate::app::prefix_walk::WalkPurpose;
+use crate::app::reproduce::{fetch_key_contents, reproduce_script};
+use crate::app::state_folder_copy::{key_name_list, FolderCopy};
+use crate::app::worker::WorkerRequest;
 use crate::app::App;
+use crate::headless::key_document;
+use redis::aio::MultiplexedConnection;
 use tokio::task;
 use crossclip::{Clipboard, SystemClipboard, ClipboardError};
 
@@ -11,49 +19,99 @@ fn ellipsize(text: &str, max_len: usize) -> String {
     }
 }
 
-pub async fn copy_selected_key_name_to_clipboard(app: &mut App) {
-    app.clipboard_status = None; // Clear previous status
+pub fn copy_selected_key_name_to_clipboard(app: &mut App) {
+    if !app.selected_indices.is_empty() && !app.search_state.is_active {
+        copy_multi_selection_names_to_clipboard(app);
+        return;
+    }
     let mut key_to_copy: Option<String> = None;
 
     // Prioritize the currently selected item in the visible key list
     if app.selected_visible_key_index < app.visible_keys_in_current_view.len() {
-        let (display_name, _is_folder) = app.visible_keys_in_current_view[app.selected_visible_key_index].clone();
+        let (display_name, is_folder) = app.visible_keys_in_current_view[app.selected_visible_key_index].clone();
+        if is_folder && !app.search_state.is_active {
+            // Folders offer their full prefix or every key beneath it
+            let prefix = format!(
+                "{}{}{}",
+                app.current_level_prefix(),
+                display_name.trim_end_matches('/'),
+                app.key_delimiters.primary()
+            );
+            app.folder_copy.open(prefix);
+            return;
+        }
         // For folders, display_name often ends with '/'. We might want to trim that.
         key_to_copy = Some(display_name.trim_end_matches('/').to_string());
     }
     
     if let Some(name) = key_to_copy {
-        let name_clone_for_closure = name.clone();
-        let result: Result<Result<String, ClipboardError>, tokio::task::JoinError> = task::spawn_blocking(move || {
-            let clipboard = SystemClipboard::new()?; // Propagate error if SystemClipboard::new() fails
-            clipboard.set_string_contents(name_clone_for_closure.clone())?;
-            Ok(name_clone_for_closure)
-        }).await;
-
-        match result {
-            Ok(Ok(copied_name)) => app.clipboard_status = Some(format!("Copied key name '{}' to clipboard!", copied_name)),
-            Ok(Err(e)) => app.clipboard_status = Some(format!("Failed to access clipboard: {}", e)),
-            Err(e) => app.clipboard_status = Some(format!("Clipboard task failed: {}", e)),
-        }
+        copy_key_name_to_clipboard(app, name);
     } else {
-        app.clipboard_status = Some("No key selected to copy".to_string());
+        app.notifications.info("No key selected to copy".to_string());
     }
 }
 
-pub async fn copy_selected_key_value_to_clipboard(app: &mut App) {
-    app.clipboard_status = None; // Clear previous status
+pub fn copy_key_name_to_clipboard(app: &mut App, name: String) {
+    let message = format!("Copied key name '{}' to clipboard!", name);
+    copy_to_clipboard(app, name, message);
+}
+
+/// Copies what was chosen in the folder menu opened by `y` on a folder.
+pub fn copy_folder_to_clipboard(app: &mut App) {
+    let menu = std::mem::take(&mut app.folder_copy);
+    if !menu.is_active {
+        return;
+    }
+    let (text, what) = match menu.selected_choice() {
+        FolderCopy::Prefix => (menu.prefix.clone(), format!("prefix '{}'", menu.prefix)),
+        FolderCopy::KeyNames => {
+            let label = format!("key name(s) under '{}'", menu.prefix);
+            let purpose = WalkPurpose::CopyKeyNames { keys: Vec::new() };
+            app.start_prefix_walk(label, vec![menu.prefix], purpose);
+            return;
+        }
+    };
+    copy_to_clipboard(app, text, format!("Copied {} to clipboard!", what));
+}
+
+/// `y` with a multi-selection: every selected key's full name, one per line, with the
+/// keys under selected folders listed in the background like multi-delete would remove them.
+fn copy_multi_selection_names_to_clipboard(app: &mut App) {
+    let (keys, prefixes) = app.multi_selection_items();
+    if !prefixes.is_empty() {
+        let purpose = WalkPurpose::CopyKeyNames { keys };
+        app.start_prefix_walk("selected key name(s)".to_string(), prefixes, purpose);
+        return;
+    }
+    if keys.is_empty() {
+        app.notifications.info("No keys in the selection to copy.".to_string());
+        return;
+    }
+    let list = key_name_list(keys);
+    let count = list.lines().count();
+    copy_to_clipboard(app, list, format!("Copied {} selected key name(s) to clipboard!", count));
+}
+
+pub fn copy_selected_key_value_to_clipboard(app: &mut App) {
     let mut value_to_copy: Option<String> = None;
 
-    if app.is_value_view_focused {
+    let hex_view = &app.value_viewer.hex_view;
+    if hex_view.enabled && app.value_viewer.raw_value.is_some() {
+        let raw = app.value_viewer.raw_value.as_deref().unwrap_or_default();
+        value_to_copy = hex_view.selected_hex(raw);
+        if value_to_copy.is_none() {
+            app.notifications.info("No bytes selected to copy.".to_string());
+        }
+    } else if app.is_value_view_focused {
         // Value view is focused: copy the selected sub-item
         if let Some(lines) = &app.value_viewer.displayed_value_lines {
             if !lines.is_empty() && app.value_viewer.selected_value_sub_index < lines.len() {
                 value_to_copy = Some(lines[app.value_viewer.selected_value_sub_index].clone());
             } else {
-                app.clipboard_status = Some("No specific value item selected to copy.".to_string());
+                app.notifications.info("No specific value item selected to copy.".to_string());
             }
         } else {
-            app.clipboard_status = Some("No multi-line value items to select from.".to_string());
+            app.notifications.info("No multi-line value items to select from.".to_string());
         }
     } else {
         // Key view is focused (or no specific sub-item focus): copy the whole value representation
@@ -65,36 +123,147 @@ pub async fn copy_selected_key_value_to_clipboard(app: &mut App) {
                     if !cvd.starts_with("(") || !cvd.ends_with(")") {
                         value_to_copy = Some(cvd.clone());
                     } else {
-                        app.clipboard_status =
-                            Some(format!("Value is an empty placeholder: {}", cvd));
+                        app.notifications.info(format!("Value is an empty placeholder: {}", cvd));
                     }
                 } else {
-                    app.clipboard_status = Some(
+                    app.notifications.info(
                         "No value content to copy (displayed_value_lines is empty).".to_string(),
                     );
                 }
-            } else if let Some(s_val) = &app.value_viewer.current_display_value {
+            } else if let Some(s_val) = app
+                .value_viewer
+                .selected_key_value
+                .as_ref()
+                .or(app.value_viewer.current_display_value.as_ref())
+            {
+                // Prefer the raw string so display-only annotations are not copied
                 value_to_copy = Some(s_val.clone());
             } else {
-                app.clipboard_status = Some("No value available to copy for the selected key.".to_string());
+                app.notifications.info("No value available to copy for the selected key.".to_string());
             }
         } else {
-            app.clipboard_status = Some("No active key selected to copy value from.".to_string());
+            app.notifications.info("No active key selected to copy value from.".to_string());
         }
     }
 
     if let Some(value_str) = value_to_copy {
-        let value_str_clone_for_closure = value_str.clone();
-        let result: Result<Result<String, ClipboardError>, tokio::task::JoinError> = task::spawn_blocking(move || {
-            let clipboard = SystemClipboard::new()?; // Propagate error
-            clipboard.set_string_contents(value_str_clone_for_closure.clone())?;
-            Ok(value_str_clone_for_closure)
-        }).await;
-
-        match result {
-            Ok(Ok(copied_value)) => app.clipboard_status = Some(format!("Copied to clipboard: {}", ellipsize(&copied_value, 50))),
-            Ok(Err(e)) => app.clipboard_status = Some(format!("Failed to access clipboard: {}", e)),
-            Err(e) => app.clipboard_status = Some(format!("Clipboard task failed: {}", e)),
+        let message = format!("Copied to clipboard: {}", ellipsize(&value_str, 50));
+        copy_to_clipboard(app, value_str, message);
+    }
+}
+
+/// Copies the viewed key as the document `get --json` prints, on one line with its
+/// fields sorted: `{"key":...,"ttl":...,"type":"hash","value":{...}}`.
+pub fn copy_key_as_json_to_clipboard(app: &mut App) {
+    let Some(key) = app.value_viewer.active_leaf_key_name.clone() else {
+        app.notifications.info("No active key selected to copy.".to_string());
+        return;
+    };
+    let Some(con) = app.redis.reader() else {
+        app.notifications.error("Not connected.");
+        return;
+    };
+    app.submit(WorkerRequest::CopyKeyJson { con, key });
+}
+
+/// Copies redis-cli commands that recreate the viewed key with its TTL, for pasting
+/// into redis-cli or piping to it.
+pub fn copy_key_as_command_to_clipboard(app: &mut App) {
+    let Some(key) = app.value_viewer.active_leaf_key_name.clone() else {
+        app.notifications.info("No active key selected to copy.".to_string());
+        return;
+    };
+    let Some(con) = app.redis.reader() else {
+        app.notifications.error("Not connected.");
+        return;
+    };
+    app.submit(WorkerRequest::CopyKeyCommand { con, key });
+}
+
+/// Has the worker put `text` on the clipboard; `message` is the toast once it is there.
+pub(crate) fn copy_to_clipboard(app: &mut App, text: String, message: String) {
+    app.submit(WorkerRequest::CopyText { text, message });
+}
+
+/// Puts `text` on the clipboard, returning the toast to show: `message` with where the
+/// text went, or the error.
+pub(super) async fn copy_text(text: String, message: String) -> (Severity, String) {
+    match set_clipboard(text).await {
+        Ok(via) => (Severity::Success, format!("{}{}", message, via.note())),
+        Err(e) => (Severity::Error, e),
+    }
+}
+
+pub(super) async fn copy_key_json(mut con: MultiplexedConnection, key: String) -> (Severity, String) {
+    let json = match key_document(&mut con, &key).await {
+        Ok(document) => document.to_string(),
+        Err(e) => return (Severity::Error, format!("Failed to read '{}': {}", key, e)),
+    };
+    let message = format!("Copied '{}' as JSON ({} bytes) to clipboard", key, json.len());
+    copy_text(json, message).await
+}
+
+pub(super) async fn copy_key_command(mut con: MultiplexedConnection, key: String) -> (Severity, String) {
+    let script = match fetch_key_contents(&mut con, &key).await {
+        Ok(Some((contents, ttl))) => reproduce_script(&key, &contents, ttl),
+        Ok(None) => return (Severity::Error, format!("Key not found: {}", key)),
+        Err(e) => return (Severity::Error, format!("Failed to read '{}': {}", key, e)),
+    };
+    let message = format!("Copied {} redis-cli commands recreating '{}' to clipboard", script.lines().count(), key);
+    copy_text(script, message).await
+}
+
+/// Where copied text was sent.
+enum CopiedVia {
+    System,
+    /// An OSC 52 sequence for the terminal, when there is no system clipboard (SSH,
+    /// headless servers).
+    Terminal,
+}
+
+impl CopiedVia {
+    /// Suffix for the success message.
+    fn note(&self) -> &'static str {
+        match self {
+            CopiedVia::System => "",
+            CopiedVia::Terminal => " (via the terminal, OSC 52)",
+        }
+    }
+}
+
+/// Puts `text` on the system clipboard, or hands it to the terminal with OSC 52 when
+/// there is none to reach.
+async fn set_clipboard(text: String) -> Result<CopiedVia, String> {
+    let outcome: Result<Result<(), ClipboardError>, tokio::task::JoinError> = task::spawn_blocking({
+        let text = text.clone();
+        move || {
+            let clipboard = SystemClipboard::new()?;
+            clipboard.set_string_contents(text)
         }
+    }).await;
+    let error = match outcome {
+        Ok(Ok(())) => return Ok(CopiedVia::System),
+        Ok(Err(e)) => format!("Failed to access clipboard: {}", e),
+        Err(e) => format!("Clipboard task failed: {}", e),
+    };
+    tracing::info!("{}; falling back to OSC 52", error);
+    osc52::copy(&text).map(|()| CopiedVia::Terminal).map_err(|e| format!("{}; OSC 52 failed: {}", error, e))
+}
+
+pub fn copy_command_result_to_clipboard(app: &mut App) {
+    let Some(result) = app.command_state.last_result.clone() else {
+        app.notifications.info("No command result to copy.".to_string());
+        return;
+    };
+    let mut detail = format!("{} lines", result.lines().count());
+    if let Some(form) = app.command_state.result_form() {
+        detail.push_str(&format!(", {}", form));
     }
-} 
+    copy_to_clipboard(app, result, format!("Copied command result ({}) to clipboard", detail));
+}
+
+pub fn copy_stats_report_to_clipboard(app: &mut App) {
+    let report = app.stats_report.report.clone();
+    let line_count = report.lines().count();
+    copy_to_clipboard(app, report, format!("Copied stats report ({} lines) to clipboard", line_count));
+}
@@ -0,0 +1,174 @@
+use redis::aio::MultiplexedConnection;
+use redis::Client;
+
+use super::keyspace_compare::{fetch_values, value_lines, CompareEvent, KeyspaceCompare};
+use super::state_compare::{CompareDetail, CompareStage};
+use super::worker::WorkerRequest;
+use super::App;
+use crate::config::ConnectionProfile;
+
+impl App {
+    pub fn open_compare(&mut self) {
+        let state = &mut self.compare;
+        state.is_active = true;
+        state.stage = CompareStage::PickProfile;
+        state.reset_results();
+        state.right = None;
+        state.profile_selected = (0..self.profiles.len())
+            .find(|idx| *idx != self.current_profile_index)
+            .unwrap_or(0);
+    }
+
+    pub fn select_compare_profile(&mut self, forward: bool) {
+        let count = self.profiles.len();
+        if count == 0 {
+            return;
+        }
+        let state = &mut self.compare;
+        state.profile_selected = if forward {
+            (state.profile_selected + 1) % count
+        } else {
+            (state.profile_selected + count - 1) % count
+        };
+    }
+
+    pub fn confirm_compare_profile(&mut self) {
+        if self.profiles.get(self.compare.profile_selected).is_none() {
+            return;
+        }
+        self.compare.pattern = self.scan_pattern.clone();
+        self.compare.pattern_cursor = self.compare.pattern.len();
+        self.compare.stage = CompareStage::Pattern;
+    }
+
+    pub fn submit_compare_pattern(&mut self) {
+        if self.compare.pattern.trim().is_empty() {
+            self.compare.pattern = "*".to_string();
+        }
+        self.compare.reset_results();
+        self.compare.stage = CompareStage::Results;
+        self.start_compare();
+    }
+
+    /// Connects to the other profile (its configured DB, or 0) and starts comparing.
+    pub fn start_compare(&mut self) {
+        let Some(left) = self.redis.reader() else {
+            self.compare.error = Some("Not connected".to_string());
+            return;
+        };
+        let Some(profile) = self.profiles.get(self.compare.profile_selected).cloned() else {
+            return;
+        };
+        self.submit(WorkerRequest::StartCompare { left, profile });
+    }
+
+    pub(super) fn apply_compare_connection(
+        &mut self,
+        left: MultiplexedConnection,
+        profile: ConnectionProfile,
+        right: Result<MultiplexedConnection, String>,
+    ) {
+        if !self.compare.is_active {
+            return;
+        }
+        let right = match right {
+            Ok(con) => con,
+            Err(e) => {
+                self.compare.error = Some(format!("Failed to connect to {}: {}", profile.name, e));
+                return;
+            }
+        };
+        self.compare.left_label = format!("{} DB {}", self.current_profile_name(), self.selected_db_index);
+        self.compare.right_label = format!("{} DB {}", profile.name, profile.db.unwrap_or(0));
+        self.compare.job = Some(KeyspaceCompare::spawn(left, right.clone(), self.compare.pattern.clone()));
+        self.compare.right = Some(right);
+    }
+
+    pub fn poll_compare(&mut self) {
+        let state = &mut self.compare;
+        let Some(job) = &mut state.job else {
+            return;
+        };
+        for event in job.drain() {
+            match event {
+                CompareEvent::Scanned { left, right } => state.scanned = Some((left, right)),
+                CompareEvent::Compared { rows, identical, compared } => {
+                    state.identical += identical;
+                    state.compared += compared;
+                    state.add_rows(rows);
+                }
+                CompareEvent::Done => {
+                    state.job = None;
+                    break;
+                }
+                CompareEvent::Error(message) => {
+                    state.error = Some(message);
+                    state.job = None;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Reads the selected key from both sides for a side-by-side view.
+    pub fn request_compare_detail(&mut self) {
+        let Some(key) = self.compare.selected_row().map(|row| row.key.clone()) else {
+            return;
+        };
+        let (Some(left), Some(right)) = (self.redis.reader(), self.compare.right.clone()) else {
+            self.compare.error = Some("Not connected".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::FetchCompareDetail { left, right, key });
+    }
+
+    pub(super) fn apply_compare_detail(&mut self, detail: CompareDetail) {
+        if self.compare.is_active {
+            self.compare.detail = Some(detail);
+        }
+    }
+}
+
+/// Connects to `profile` on its configured DB, or 0, for the right side of a compare.
+pub(super) async fn connect_right(profile: &ConnectionProfile) -> Result<MultiplexedConnection, String> {
+    let connected = async {
+        let client = Client::open(profile.url.as_str())?;
+        let mut con = client.get_multiplexed_async_connection().await?;
+        redis::cmd("SELECT").arg(profile.db.unwrap_or(0)).query_async::<()>(&mut con).await?;
+        Ok::<_, redis::RedisError>(con)
+    };
+    connected.await.map_err(|e| e.to_string())
+}
+
+pub(super) async fn compare_detail(
+    mut left: MultiplexedConnection,
+    mut right: MultiplexedConnection,
+    key: String,
+) -> CompareDetail {
+    let left_lines = read_key(&mut left, &key).await;
+    let right_lines = read_key(&mut right, &key).await;
+    CompareDetail {
+        key,
+        left: left_lines,
+        right: right_lines,
+        scroll: 0,
+    }
+}
+
+/// `type: <type>` followed by the value lines; errors become a single `(error)` line.
+async fn read_key(con: &mut MultiplexedConnection, key: &str) -> Vec<String> {
+    let key_type: String = match redis::cmd("TYPE").arg(key).query_async(con).await {
+        Ok(key_type) => key_type,
+        Err(e) => return vec![format!("(error) {}", e)],
+    };
+    if key_type == "none" {
+        return vec!["(missing)".to_string()];
+    }
+    let key = key.to_string();
+    let mut lines = vec![format!("type: {}", key_type)];
+    match fetch_values(con, &[(&key, &key_type)]).await {
+        Ok(values) => lines.extend(values.first().map(|value| value_lines(&key_type, value)).unwrap_or_default()),
+        Err(e) => lines.push(format!("(error) {}", e)),
+    }
+    lines
+}
@@ -0,0 +1,82 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::state_config_editor::{parse_config_reply, ConfigParam};
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn open_config_editor(&mut self) {
+        self.config_editor.is_active = true;
+        self.config_editor.edit = None;
+        self.fetch_config();
+    }
+
+    pub fn fetch_config(&mut self) {
+        let Some(con) = self.redis.connection.clone() else {
+            self.config_editor.status = Some("Not connected".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::FetchConfig(con));
+    }
+
+    pub(super) fn apply_config(&mut self, params: Result<Vec<ConfigParam>, String>) {
+        match params {
+            Ok(params) => self.config_editor.set_params(params),
+            Err(e) => self.config_editor.status = Some(format!("(error) CONFIG GET: {}", e)),
+        }
+    }
+
+    /// Opens the value editor unless the profile is read-only.
+    pub fn start_config_edit(&mut self) {
+        if self.refuse_write("configuration changes") {
+            return;
+        }
+        self.config_editor.start_edit();
+    }
+
+    pub fn submit_config_edit(&mut self) {
+        let Some(value) = self.config_editor.edit.take() else {
+            return;
+        };
+        let Some(name) = self.config_editor.selected_param().map(|param| param.name.clone()) else {
+            return;
+        };
+        let Some(con) = self.writer("configuration changes") else {
+            return;
+        };
+        self.submit(WorkerRequest::SetConfig { con, name, value });
+    }
+
+    pub(super) fn apply_config_set(&mut self, name: String, value: String, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                self.config_editor.status = Some(format!("Set {} = {}", name, value));
+                self.fetch_config();
+            }
+            Err(e) => self.config_editor.status = Some(format!("(error) CONFIG SET {}: {}", name, e)),
+        }
+    }
+
+    pub fn request_config_rewrite(&mut self) {
+        if self.refuse_write("configuration changes") {
+            return;
+        }
+        let Some(con) = self.writer("configuration changes") else {
+            return;
+        };
+        self.submit(WorkerRequest::RewriteConfig(con));
+    }
+
+    pub(super) fn apply_config_rewrite(&mut self, result: Result<(), String>) {
+        self.config_editor.status = Some(match result {
+            Ok(()) => "CONFIG REWRITE saved the running configuration to the server's config file.".to_string(),
+            Err(e) => format!("(error) CONFIG REWRITE: {}", e),
+        });
+    }
+}
+
+pub(super) async fn read_config(mut con: MultiplexedConnection) -> Result<Vec<ConfigParam>, String> {
+    let reply = redis::cmd("CONFIG").arg("GET").arg("*").query_async::<Value>(&mut con).await;
+    reply.map(|reply| parse_config_reply(&reply)).map_err(|e| e.to_string())
+}
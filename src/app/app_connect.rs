@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::cluster::{self, ClusterTopology};
+use super::key_scan::{DEFAULT_SCAN_COUNT, DEFAULT_SCAN_PATTERN};
+use super::redis_client::RedisClient;
+use super::redis_stats::{parse_keyspace, KeyspaceCounts};
+use super::worker::{Worker, WorkerRequest};
+use super::App;
+use crate::command::{fetch_command_catalog, CommandSpec};
+use crate::config::ConnectionProfile;
+use crate::session::SessionState;
+
+/// A connection the worker opened, with what the app reads right after connecting.
+pub struct OpenedConnection {
+    pub redis: RedisClient,
+    pub db_size: Option<u64>,
+    pub catalog: Vec<CommandSpec>,
+    pub cluster: Option<ClusterTopology>,
+    pub keyspace: Option<HashMap<usize, KeyspaceCounts>>,
+}
+
+/// Connects to `profile` on its own DB, on `db_override`, or else on `db_index` (the DB
+/// of the previous connection), then reads the DB size, command docs, cluster layout
+/// and keyspace.
+pub(super) async fn open_connection(
+    profile: &ConnectionProfile,
+    use_profile_db: bool,
+    db_override: Option<usize>,
+    db_index: usize,
+) -> Result<Box<OpenedConnection>, String> {
+    let mut redis = RedisClient::new();
+    redis.db_index = db_index;
+    redis
+        .connect_to_profile(profile, use_profile_db, db_override)
+        .await
+        .map_err(|e| e.to_string())?;
+    let (mut db_size, mut catalog, mut cluster) = (None, Vec::new(), None);
+    if let Some(con) = redis.connection.as_mut() {
+        db_size = redis::cmd("DBSIZE").query_async::<u64>(&mut *con).await.ok();
+        catalog = fetch_command_catalog(con).await;
+        cluster = cluster::fetch_topology(con).await;
+    }
+    let keyspace = redis.get_info_section("keyspace").await.ok().map(|info| parse_keyspace(&info));
+    Ok(Box::new(OpenedConnection {
+        redis,
+        db_size,
+        catalog,
+        cluster,
+        keyspace,
+    }))
+}
+
+impl App {
+    /// Connects to the selected profile, on the DB of the saved session when it belongs
+    /// to that profile; the session's level and key are restored once connected.
+    pub fn trigger_initial_connect(&mut self) {
+        let session = self.take_session_for_current_profile();
+        self.connect_to_profile(self.current_profile_index, session.is_none(), session);
+    }
+
+    /// Starts connecting in the background; the previous connection stays in use until
+    /// the new one is open.
+    fn connect_to_profile(&mut self, profile_index: usize, use_profile_db: bool, session: Option<SessionState>) {
+        let Some(profile) = self.profiles.get(profile_index).cloned() else {
+            self.connection_status = format!("Error: Profile index {} out of bounds.", profile_index);
+            return;
+        };
+
+        // A delete still running belongs to the server being left
+        self.cancel_bulk_delete();
+        self.prefix_walk = None;
+        self.connection_status = format!("Connecting to {} ({})...", profile.name, profile.url);
+
+        // A manual DB switch connects to the DB selected in the app instead of the profile's
+        let db_override = (!use_profile_db).then_some(self.selected_db_index);
+        // Whatever the worker of the previous connection still had in flight goes with it
+        self.worker = Some(Worker::spawn());
+        self.submit(WorkerRequest::Connect {
+            profile,
+            profile_index,
+            use_profile_db,
+            db_override,
+            db_index: self.redis.db_index,
+            session,
+        });
+    }
+
+    pub(super) fn apply_connection(
+        &mut self,
+        profile_index: usize,
+        session: Option<SessionState>,
+        result: Result<Box<OpenedConnection>, String>,
+    ) {
+        let opened = match result {
+            Ok(opened) => *opened,
+            Err(e) => {
+                if let Some(profile) = self.profiles.get(profile_index) {
+                    tracing::error!(profile = %profile.name, "failed to connect: {}", e);
+                }
+                self.worker = None;
+                self.connection_status = format!("Failed to connect: {}", e);
+                self.db_size = None;
+                self.cluster = None;
+                return;
+            }
+        };
+        self.redis = opened.redis;
+        self.latency.clear();
+        self.selected_db_index = self.redis.db_index;
+        self.connection_status = self.redis.connection_status.clone();
+        if let Some(profile) = self.profiles.get(profile_index) {
+            self.scan_pattern = profile
+                .scan_pattern
+                .clone()
+                .unwrap_or_else(|| DEFAULT_SCAN_PATTERN.to_string());
+            self.scan_count = profile.scan_count.unwrap_or(DEFAULT_SCAN_COUNT);
+        }
+        self.scan_type = None;
+        self.db_size = opened.db_size;
+        self.command_state.catalog = opened.catalog;
+        self.cluster = opened.cluster;
+        self.db_key_counts = opened.keyspace;
+        self.fetch_keys_and_build_tree();
+        if let Some(session) = session {
+            self.apply_session(session);
+        }
+    }
+
+    pub fn toggle_profile_selector(&mut self) {
+        self.profile_state.toggle(self.current_profile_index);
+    }
+
+    pub fn next_profile_in_list(&mut self) {
+        self.profile_state.next(self.profiles.len());
+    }
+
+    pub fn previous_profile_in_list(&mut self) {
+        self.profile_state.previous(self.profiles.len());
+    }
+
+    pub fn select_profile_and_connect(&mut self) {
+        if self.profile_state.selected_index < self.profiles.len() {
+            self.current_profile_index = self.profile_state.selected_index;
+            self.profile_state.is_active = false;
+            self.connect_to_profile(self.current_profile_index, true, None);
+        }
+    }
+
+    pub fn next_db(&mut self) {
+        if self.db_count > 0 {
+            self.selected_db_index = (self.selected_db_index + 1) % (self.db_count as usize);
+        }
+    }
+
+    pub fn previous_db(&mut self) {
+        if self.db_count > 0 {
+            if self.selected_db_index > 0 {
+                self.selected_db_index -= 1;
+            } else {
+                self.selected_db_index = (self.db_count as usize).saturating_sub(1);
+            }
+        }
+    }
+
+    pub fn trigger_apply_selected_db(&mut self) {
+        self.clear_selected_key_info();
+        self.current_breadcrumb.clear();
+        self.raw_keys = Arc::new(Vec::new());
+        self.key_tree.clear();
+        self.folder_key_counts.clear();
+        self.loaded_prefixes.clear();
+        self.key_scan = None;
+        self.tree_refresh = None;
+        self.visible_keys_in_current_view.clear();
+        self.selected_visible_key_index = 0;
+        self.connect_to_profile(self.current_profile_index, false, None);
+    }
+}
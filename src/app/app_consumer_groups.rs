@@ -0,0 +1,395 @@
+use super::app_fetch::{expect_array, value_to_bytes};
+use super::state_consumer_groups::{ConsumerGroupInfo, GroupPrompt, PendingEntry};
+use super::worker::WorkerRequest;
+use super::{value_format, App};
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+/// Pending entries fetched per XPENDING / XAUTOCLAIM call.
+const PENDING_PAGE_SIZE: usize = 100;
+
+impl App {
+    pub fn open_consumer_groups(&mut self) {
+        let Some(key_name) = self.value_viewer.active_leaf_key_name.clone() else {
+            return;
+        };
+        if self.value_viewer.selected_key_type.as_deref() != Some("STREAM") {
+            self.notifications.info("Consumer groups are available for stream keys only.".to_string());
+            return;
+        }
+        self.consumer_groups.open(key_name);
+        self.fetch_consumer_groups();
+    }
+
+    /// Runs the XCLAIM or XAUTOCLAIM call for the open prompt.
+    pub fn submit_consumer_group_prompt(&mut self) {
+        match self.consumer_groups.prompt {
+            Some(GroupPrompt::Claim) => self.claim_pending_entries(),
+            Some(GroupPrompt::AutoClaim) => self.auto_claim_pending_entries(),
+            None => {}
+        }
+    }
+
+    /// Reads the groups of the stream, then the pending entries of the selected one.
+    pub fn fetch_consumer_groups(&mut self) {
+        let Some(key) = self.consumer_groups.stream_key.clone() else {
+            return;
+        };
+        let Some(con) = self.redis.connection.clone() else {
+            self.consumer_groups.status = Some("Not connected.".to_string());
+            return;
+        };
+        let selected = self.consumer_groups.selected_group;
+        self.submit(WorkerRequest::FetchConsumerGroups { con, key, selected });
+    }
+
+    pub(super) fn apply_consumer_groups(&mut self, key: String, read: Result<GroupsRead, String>) {
+        if self.consumer_groups.stream_key.as_deref() != Some(key.as_str()) {
+            return;
+        }
+        let read = match read {
+            Ok(read) => read,
+            Err(message) => {
+                self.consumer_groups.status = Some(format!("XINFO GROUPS failed: {}", message));
+                return;
+            }
+        };
+        let state = &mut self.consumer_groups;
+        state.selected_group = read.selected;
+        state.groups = read.groups;
+        match read.pending {
+            Ok(pending) => state.set_pending(pending),
+            Err(message) => state.status = Some(format!("XPENDING failed: {}", message)),
+        }
+    }
+
+    pub fn ack_pending_entries(&mut self) {
+        let ids = self.consumer_groups.target_ids();
+        if ids.is_empty() {
+            return;
+        }
+        self.run_group_command(GroupCommand::Ack { ids });
+    }
+
+    fn claim_pending_entries(&mut self) {
+        let Some((consumer, min_idle)) = self.take_prompt_input() else {
+            return;
+        };
+        let ids = self.consumer_groups.target_ids();
+        if ids.is_empty() {
+            return;
+        }
+        self.run_group_command(GroupCommand::Claim { consumer, min_idle, ids });
+    }
+
+    fn auto_claim_pending_entries(&mut self) {
+        let Some((consumer, min_idle)) = self.take_prompt_input() else {
+            return;
+        };
+        self.run_group_command(GroupCommand::AutoClaim { consumer, min_idle });
+    }
+
+    fn run_group_command(&mut self, command: GroupCommand) {
+        let (Some(key), Some(group)) = self.group_target() else {
+            return;
+        };
+        let Some(con) = self.writer("acknowledgements and claims") else {
+            return;
+        };
+        self.submit(WorkerRequest::GroupCommand { con, key, group, command });
+    }
+
+    /// Shows how a group command went and reads the groups again.
+    pub(super) fn apply_group_command(&mut self, key: String, status: String, clears_marks: bool) {
+        if self.consumer_groups.stream_key.as_deref() != Some(key.as_str()) {
+            return;
+        }
+        self.consumer_groups.status = Some(status);
+        if clears_marks {
+            self.consumer_groups.marked.clear();
+        }
+        self.fetch_consumer_groups();
+    }
+
+    fn group_target(&self) -> (Option<String>, Option<String>) {
+        (
+            self.consumer_groups.stream_key.clone(),
+            self.consumer_groups.current_group().map(|g| g.name.clone()),
+        )
+    }
+
+    /// Closes the prompt and returns its parsed input, reporting parse errors as status.
+    fn take_prompt_input(&mut self) -> Option<(String, u64)> {
+        let parsed = self.consumer_groups.parse_prompt_input();
+        self.consumer_groups.prompt = None;
+        match parsed {
+            Ok(parsed) => Some(parsed),
+            Err(message) => {
+                self.consumer_groups.status = Some(message);
+                None
+            }
+        }
+    }
+}
+
+/// Groups of a stream, with the pending entries of the selected one.
+pub struct GroupsRead {
+    pub groups: Vec<ConsumerGroupInfo>,
+    /// The selection, kept within the groups read.
+    pub selected: usize,
+    pub pending: Result<Vec<PendingEntry>, String>,
+}
+
+/// A write to the pending entries of the selected group.
+pub enum GroupCommand {
+    Ack {
+        ids: Vec<String>,
+    },
+    Claim {
+        consumer: String,
+        min_idle: u64,
+        ids: Vec<String>,
+    },
+    AutoClaim {
+        consumer: String,
+        min_idle: u64,
+    },
+}
+
+impl GroupCommand {
+    /// Whether the marked entries are done with afterwards; an autoclaim does not use them.
+    pub fn clears_marks(&self) -> bool {
+        !matches!(self, GroupCommand::AutoClaim { .. })
+    }
+}
+
+pub(super) async fn read_consumer_groups(
+    mut con: MultiplexedConnection,
+    key: &str,
+    selected: usize,
+) -> Result<GroupsRead, String> {
+    let groups = redis::cmd("XINFO")
+        .arg("GROUPS")
+        .arg(key)
+        .query_async::<Value>(&mut con)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(parse_groups)?;
+    let selected = selected.min(groups.len().saturating_sub(1));
+    let Some(group) = groups.get(selected).map(|g| g.name.clone()) else {
+        return Ok(GroupsRead {
+            groups,
+            selected,
+            pending: Ok(Vec::new()),
+        });
+    };
+    let pending = redis::cmd("XPENDING")
+        .arg(key)
+        .arg(&group)
+        .arg("-")
+        .arg("+")
+        .arg(PENDING_PAGE_SIZE)
+        .query_async::<Value>(&mut con)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(parse_pending);
+    Ok(GroupsRead {
+        groups,
+        selected,
+        pending,
+    })
+}
+
+/// Runs `command` on `group` of the stream at `key`, returning the status line.
+pub(super) async fn run_group_command(
+    mut con: MultiplexedConnection,
+    key: &str,
+    group: &str,
+    command: GroupCommand,
+) -> String {
+    match command {
+        GroupCommand::Ack { ids } => {
+            let result = redis::cmd("XACK").arg(key).arg(group).arg(&ids).query_async::<u64>(&mut con).await;
+            match result {
+                Ok(acked) => format!("XACK acknowledged {} of {} entries.", acked, ids.len()),
+                Err(e) => format!("XACK failed: {}", e),
+            }
+        }
+        GroupCommand::Claim { consumer, min_idle, ids } => {
+            let result = redis::cmd("XCLAIM")
+                .arg(key)
+                .arg(group)
+                .arg(&consumer)
+                .arg(min_idle)
+                .arg(&ids)
+                .arg("JUSTID")
+                .query_async::<Value>(&mut con)
+                .await;
+            match result.map(count_ids) {
+                Ok(claimed) => format!("XCLAIM moved {} of {} entries to '{}'.", claimed, ids.len(), consumer),
+                Err(e) => format!("XCLAIM failed: {}", e),
+            }
+        }
+        GroupCommand::AutoClaim { consumer, min_idle } => {
+            let result = redis::cmd("XAUTOCLAIM")
+                .arg(key)
+                .arg(group)
+                .arg(&consumer)
+                .arg(min_idle)
+                .arg("0-0")
+                .arg("COUNT")
+                .arg(PENDING_PAGE_SIZE)
+                .arg("JUSTID")
+                .query_async::<Value>(&mut con)
+                .await;
+            match result.map(parse_autoclaim_count) {
+                Ok(claimed) => format!(
+                    "XAUTOCLAIM moved {} entries idle for {}ms+ to '{}'.",
+                    claimed, min_idle, consumer
+                ),
+                Err(e) => format!("XAUTOCLAIM failed: {}", e),
+            }
+        }
+    }
+}
+
+fn value_to_string(value: &Value) -> String {
+    value_format::format_bytes_inline(&value_to_bytes(value).unwrap_or_default())
+}
+
+fn value_to_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Int(num) => u64::try_from(*num).ok(),
+        Value::Nil => None,
+        other => value_to_string(other).parse().ok(),
+    }
+}
+
+/// Field/value pairs of an XINFO record, which is a flat array in RESP2 and a map in RESP3.
+fn record_fields(value: Value) -> Vec<(String, Value)> {
+    match value {
+        Value::Map(pairs) => pairs
+            .into_iter()
+            .map(|(field, value)| (value_to_string(&field), value))
+            .collect(),
+        Value::Array(items) => {
+            let mut items = items.into_iter();
+            let mut pairs = Vec::new();
+            while let (Some(field), Some(value)) = (items.next(), items.next()) {
+                pairs.push((value_to_string(&field), value));
+            }
+            pairs
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn parse_groups(value: Value) -> Result<Vec<ConsumerGroupInfo>, String> {
+    let records = expect_array(value, "XINFO GROUPS")?;
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let mut group = ConsumerGroupInfo {
+                name: String::new(),
+                consumers: 0,
+                pending: 0,
+                last_delivered_id: String::new(),
+                lag: None,
+            };
+            for (field, value) in record_fields(record) {
+                match field.as_str() {
+                    "name" => group.name = value_to_string(&value),
+                    "consumers" => group.consumers = value_to_u64(&value).unwrap_or(0),
+                    "pending" => group.pending = value_to_u64(&value).unwrap_or(0),
+                    "last-delivered-id" => group.last_delivered_id = value_to_string(&value),
+                    "lag" => group.lag = value_to_u64(&value),
+                    _ => {}
+                }
+            }
+            group
+        })
+        .collect())
+}
+
+fn parse_pending(value: Value) -> Result<Vec<PendingEntry>, String> {
+    let rows = expect_array(value, "XPENDING")?;
+    rows.into_iter()
+        .map(|row| match row {
+            Value::Array(parts) if parts.len() == 4 => Ok(PendingEntry {
+                id: value_to_string(&parts[0]),
+                consumer: value_to_string(&parts[1]),
+                idle_ms: value_to_u64(&parts[2]).unwrap_or(0),
+                deliveries: value_to_u64(&parts[3]).unwrap_or(0),
+            }),
+            other => Err(format!("unexpected entry {:?}", other)),
+        })
+        .collect()
+}
+
+fn count_ids(value: Value) -> usize {
+    match value {
+        Value::Array(ids) => ids.len(),
+        _ => 0,
+    }
+}
+
+/// XAUTOCLAIM replies with `[next-cursor, claimed, (deleted)]`.
+fn parse_autoclaim_count(value: Value) -> usize {
+    match value {
+        Value::Array(mut parts) if parts.len() >= 2 => count_ids(parts.swap_remove(1)),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parse_groups_reads_flat_and_map_records() {
+        let flat = Value::Array(vec![
+            bulk("name"),
+            bulk("workers"),
+            bulk("consumers"),
+            Value::Int(2),
+            bulk("pending"),
+            Value::Int(5),
+            bulk("last-delivered-id"),
+            bulk("7-0"),
+            bulk("lag"),
+            Value::Nil,
+        ]);
+        let map = Value::Map(vec![(bulk("name"), bulk("audit")), (bulk("lag"), Value::Int(3))]);
+        let groups = parse_groups(Value::Array(vec![flat, map])).expect("parse");
+        assert_eq!(groups[0].name, "workers");
+        assert_eq!((groups[0].consumers, groups[0].pending), (2, 5));
+        assert_eq!(groups[0].last_delivered_id, "7-0");
+        assert_eq!(groups[0].lag, None);
+        assert_eq!(groups[1].name, "audit");
+        assert_eq!(groups[1].lag, Some(3));
+    }
+
+    #[test]
+    fn parse_pending_and_autoclaim_replies() {
+        let pending = parse_pending(Value::Array(vec![Value::Array(vec![
+            bulk("1-0"),
+            bulk("worker-1"),
+            Value::Int(1500),
+            Value::Int(2),
+        ])]))
+        .expect("parse");
+        assert_eq!(pending[0].consumer, "worker-1");
+        assert_eq!((pending[0].idle_ms, pending[0].deliveries), (1500, 2));
+
+        let reply = Value::Array(vec![
+            bulk("0-0"),
+            Value::Array(vec![bulk("1-0"), bulk("2-0")]),
+            Value::Array(vec![]),
+        ]);
+        assert_eq!(parse_autoclaim_count(reply), 2);
+    }
+}
@@ -0,0 +1,116 @@
+use super::bulk_delete;
+use super::worker::WorkerRequest;
+use super::{App, MultiplexedConnection};
+
+impl App {
+    pub fn initiate_delete_selected_item(&mut self) {
+        if self.refuse_write("deletes") {
+            return;
+        }
+        // Taken out so the dialog can resolve leaf names through the key tree
+        let mut dialog = std::mem::take(&mut self.delete_dialog);
+        let leaf_key = |name: &str| self.leaf_full_name(name);
+        if !self.selected_indices.is_empty() {
+            // Multi-select delete
+            dialog.initiate_delete_multiple_items(
+                &self.selected_indices,
+                &self.visible_keys_in_current_view,
+                &self.current_breadcrumb,
+                self.key_delimiters.primary(),
+                leaf_key,
+                self.search_state.is_active,
+            );
+        } else {
+            // Single item delete
+            dialog.initiate_delete_selected_item(
+                self.selected_visible_key_index,
+                &self.visible_keys_in_current_view,
+                &self.current_breadcrumb,
+                self.key_delimiters.primary(),
+                leaf_key,
+                self.search_state.is_active,
+            );
+        }
+        self.delete_dialog = dialog;
+    }
+
+    pub fn cancel_delete_item(&mut self) {
+        self.drop_delete_preview_walk();
+        self.delete_dialog.preview = None;
+        self.delete_dialog.show_confirmation_dialog = false;
+        self.delete_dialog.key_to_delete_display_name = None;
+        self.delete_dialog.key_to_delete_full_path = None;
+        self.delete_dialog.prefix_to_delete = None;
+        self.delete_dialog.deletion_is_folder = false;
+    }
+
+    pub fn confirm_delete_item(&mut self) {
+        self.drop_delete_preview_walk();
+        let mut submitted = false;
+        if self.delete_dialog.is_multi_delete || self.delete_dialog.deletion_is_folder {
+            // Folders can hold millions of keys, so they are deleted in the background
+            self.start_bulk_delete();
+        } else if let Some(con) = self.writer("deletes") {
+            match self.delete_dialog.key_to_delete_full_path.clone() {
+                Some(key) => {
+                    self.submit(WorkerRequest::DeleteKey { con, key });
+                    submitted = true;
+                }
+                None => self.notifications.error("Error deleting: Key path to delete was None"),
+            }
+        }
+
+        self.delete_dialog.show_confirmation_dialog = false;
+        self.delete_dialog.key_to_delete_display_name = None;
+        self.delete_dialog.key_to_delete_full_path = None;
+        self.delete_dialog.prefix_to_delete = None;
+        self.delete_dialog.deletion_is_folder = false;
+        self.delete_dialog.keys_to_delete.clear();
+        self.delete_dialog.is_multi_delete = false;
+        self.delete_dialog.preview = None;
+
+        // Clear multi-selection after deletion
+        self.clear_multi_selection();
+        if self.bulk_delete.is_some() || submitted {
+            // The tree is reloaded once the delete finishes
+            return;
+        }
+        self.reload_after_delete();
+    }
+
+    pub(super) fn apply_deleted_key(&mut self, key: String, result: Result<String, String>) {
+        match result {
+            Ok(msg) => self.notifications.success(msg),
+            Err(e) => {
+                self.notifications.error(format!("Error deleting: {}", e));
+                return;
+            }
+        }
+        self.type_map_mut().remove(&key);
+        self.key_cache.invalidate(&key);
+        if self.value_viewer.active_leaf_key_name.as_deref() == Some(key.as_str()) {
+            self.reload_after_delete();
+        } else {
+            // The value panel has moved on to another key meanwhile
+            self.fetch_keys_and_build_tree();
+            self.update_visible_keys();
+        }
+    }
+
+    fn reload_after_delete(&mut self) {
+        self.fetch_keys_and_build_tree();
+        self.update_visible_keys();
+        self.value_viewer.active_leaf_key_name = None;
+        self.clear_selected_key_info();
+    }
+}
+
+pub(super) async fn delete_redis_key(mut con: MultiplexedConnection, full_key: &str) -> Result<String, String> {
+    let mut prefer_unlink = true;
+    let count = bulk_delete::delete_batch(&mut con, &[full_key.to_string()], &mut prefer_unlink).await?;
+    if count > 0 {
+        Ok(format!("Deleted key '{}'.", full_key))
+    } else {
+        Ok(format!("Key '{}' not found or already deleted.", full_key))
+    }
+}
@@ -0,0 +1,48 @@
+use super::delete_preview::DeletePreview;
+use super::prefix_walk::WalkPurpose;
+use super::App;
+
+impl App {
+    /// Whether the open delete dialog removes folders, so `p` has something to scan.
+    pub fn delete_can_preview(&self) -> bool {
+        let dialog = &self.delete_dialog;
+        dialog.deletion_is_folder
+            || (dialog.is_multi_delete && dialog.keys_to_delete.iter().any(|item| item.starts_with("folder:")))
+    }
+
+    /// `p` in the delete dialog: scans the keys the delete would remove in the
+    /// background and counts them per folder as pages arrive, deleting nothing.
+    pub fn preview_delete_item(&mut self) {
+        if !self.delete_can_preview() || self.delete_dialog.preview.is_some() {
+            return;
+        }
+
+        let dialog = &self.delete_dialog;
+        let (mut preview, prefixes, keys): (_, Vec<String>, Vec<String>) = if dialog.is_multi_delete {
+            let (folders, keys): (Vec<&String>, Vec<&String>) =
+                dialog.keys_to_delete.iter().partition(|item| item.starts_with("folder:"));
+            let prefixes = folders.iter().map(|item| item["folder:".len()..].to_string()).collect();
+            (DeletePreview::new(self.current_level_prefix()), prefixes, keys.into_iter().cloned().collect())
+        } else {
+            let prefix = dialog.prefix_to_delete.clone().unwrap_or_default();
+            (DeletePreview::new(prefix.clone()), vec![prefix], Vec::new())
+        };
+        keys.iter().for_each(|key| preview.add(key, &self.key_delimiters));
+
+        if self.start_prefix_walk("keys to delete".to_string(), prefixes, WalkPurpose::DeletePreview) {
+            self.delete_dialog.preview = Some(preview);
+        }
+    }
+
+    /// Whether the delete preview is still scanning.
+    pub fn delete_preview_running(&self) -> bool {
+        self.prefix_walk.as_ref().is_some_and(|walk| walk.purpose == WalkPurpose::DeletePreview)
+    }
+
+    /// Stops a delete preview scan when the dialog closes.
+    pub(super) fn drop_delete_preview_walk(&mut self) {
+        if self.delete_preview_running() {
+            self.prefix_walk = None;
+        }
+    }
+}
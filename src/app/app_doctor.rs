@@ -0,0 +1,38 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::state_doctor::build_report;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn open_doctor_report(&mut self) {
+        self.doctor_report.is_active = true;
+        self.doctor_report.scroll = 0;
+        self.doctor_report.lines = vec!["Running MEMORY DOCTOR, LATENCY DOCTOR and MEMORY STATS...".to_string()];
+        let Some(con) = self.redis.connection.clone() else {
+            self.doctor_report.lines = vec!["Not connected".to_string()];
+            return;
+        };
+        self.submit(WorkerRequest::FetchDoctorReport(con));
+    }
+}
+
+pub(super) async fn doctor_report(mut con: MultiplexedConnection) -> Vec<String> {
+    let memory_doctor = redis::cmd("MEMORY")
+        .arg("DOCTOR")
+        .query_async::<String>(&mut con)
+        .await
+        .map_err(|e| e.to_string());
+    let latency_doctor = redis::cmd("LATENCY")
+        .arg("DOCTOR")
+        .query_async::<String>(&mut con)
+        .await
+        .map_err(|e| e.to_string());
+    let memory_stats = redis::cmd("MEMORY")
+        .arg("STATS")
+        .query_async::<Value>(&mut con)
+        .await
+        .map_err(|e| e.to_string());
+    build_report(memory_doctor, latency_doctor, memory_stats)
+}
@@ -1,8 +1,13 @@
-use super::{value_format, App, StreamEntry};
+use super::latency_tracker::OperationClass;
+use super::value_fetch::ValueFetch;
+use super::value_viewer::{page_end, CollectionPage};
+use super::worker::WorkerRequest;
+use super::{value_decoder, value_format, App, StreamEntry};
 use redis::{aio::MultiplexedConnection, Value};
 use std::future::Future;
+use std::time::{Duration, Instant};
 
-impl App {
+impl ValueFetch {
     async fn run_fetch<T, Fut, OkF, ErrF>(
         &mut self,
         fut: Fut,
@@ -14,20 +19,23 @@ impl App {
         OkF: FnOnce(&mut Self, T) -> Result<(), String>,
         ErrF: FnOnce(&mut Self),
     {
-        match fut.await {
+        let started = Instant::now();
+        let result = fut.await;
+        self.record(OperationClass::Collection, started.elapsed());
+        match result {
             Ok(val) => {
                 if let Err(message) = on_ok(self, val) {
-                    self.value_viewer.selected_key_value = Some(message);
+                    self.viewer.selected_key_value = Some(message);
                 }
             }
             Err(e) => {
-                self.value_viewer.selected_key_value = Some(format!("{}: {}", err_msg, e));
+                self.viewer.selected_key_value = Some(format!("{}: {}", err_msg, e));
                 on_err(self);
             }
         }
     }
 
-    pub async fn fetch_and_set_hash_value(
+    pub(super) async fn fetch_and_set_hash_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
@@ -38,61 +46,70 @@ impl App {
         let err_context = format!("Failed to HGETALL for '{}' (hash)", key_name);
         self.run_fetch(
             fut,
-            |app, value| parse_hash_value(app, key_name, value),
-            |app| {
-                app.value_viewer.selected_key_value_hash = None;
+            |fetch, value| parse_hash_value(fetch, key_name, value),
+            |fetch| {
+                fetch.viewer.selected_key_value_hash = None;
             },
             err_context,
         )
         .await;
     }
 
-    pub async fn fetch_and_set_zset_value(
+    pub(super) async fn fetch_and_set_zset_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
     ) {
-        let mut owned_cmd = redis::cmd("ZRANGE");
-        owned_cmd.arg(key_name);
-        owned_cmd.arg(0);
-        owned_cmd.arg(-1);
-        owned_cmd.arg("WITHSCORES");
-        let fut = owned_cmd.query_async::<Value>(con);
+        let page_size = self.options.collection_page_size;
+        let mut pipe = redis::pipe();
+        pipe.cmd("ZCARD").arg(key_name);
+        pipe.cmd("ZRANGE").arg(key_name).arg(0).arg(page_end(0, page_size)).arg("WITHSCORES");
+        let fut = pipe.query_async::<(usize, Value)>(con);
         let err_context = format!("Failed to ZRANGE for '{}' (zset)", key_name);
         self.run_fetch(
             fut,
-            |app, value| parse_zset_value(app, key_name, value),
-            |app| {
-                app.value_viewer.selected_key_value_zset = None;
+            |fetch, (total, value)| {
+                parse_zset_value(fetch, key_name, value)?;
+                let loaded = fetch.viewer.selected_key_value_zset.as_ref().map_or(0, Vec::len);
+                fetch.viewer.collection_page = CollectionPage::after(loaded, total, page_size);
+                Ok(())
+            },
+            |fetch| {
+                fetch.viewer.selected_key_value_zset = None;
             },
             err_context,
         )
         .await;
     }
 
-    pub async fn fetch_and_set_list_value(
+    pub(super) async fn fetch_and_set_list_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
     ) {
-        let mut owned_cmd = redis::cmd("LRANGE");
-        owned_cmd.arg(key_name);
-        owned_cmd.arg(0);
-        owned_cmd.arg(-1);
-        let fut = owned_cmd.query_async::<Value>(con);
+        let page_size = self.options.collection_page_size;
+        let mut pipe = redis::pipe();
+        pipe.cmd("LLEN").arg(key_name);
+        pipe.cmd("LRANGE").arg(key_name).arg(0).arg(page_end(0, page_size));
+        let fut = pipe.query_async::<(usize, Value)>(con);
         let err_context = format!("Failed to LRANGE for '{}' (list)", key_name);
         self.run_fetch(
             fut,
-            |app, value| parse_list_value(app, key_name, value),
-            |app| {
-                app.value_viewer.selected_key_value_list = None;
+            |fetch, (total, value)| {
+                parse_list_value(fetch, key_name, value)?;
+                let loaded = fetch.viewer.selected_key_value_list.as_ref().map_or(0, Vec::len);
+                fetch.viewer.collection_page = CollectionPage::after(loaded, total, page_size);
+                Ok(())
+            },
+            |fetch| {
+                fetch.viewer.selected_key_value_list = None;
             },
             err_context,
         )
         .await;
     }
 
-    pub async fn fetch_and_set_set_value(
+    pub(super) async fn fetch_and_set_set_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
@@ -103,16 +120,16 @@ impl App {
         let err_context = format!("Failed to SMEMBERS for '{}' (set)", key_name);
         self.run_fetch(
             fut,
-            |app, value| parse_set_value(app, key_name, value),
-            |app| {
-                app.value_viewer.selected_key_value_set = None;
+            |fetch, value| parse_set_value(fetch, key_name, value),
+            |fetch| {
+                fetch.viewer.selected_key_value_set = None;
             },
             err_context,
         )
         .await;
     }
 
-    pub async fn fetch_and_set_json_value(
+    pub(super) async fn fetch_and_set_json_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
@@ -123,69 +140,218 @@ impl App {
         let err_context = format!("Failed to JSON.GET for '{}' (json)", key_name);
         self.run_fetch(
             fut,
-            |app, value| {
-                app.value_viewer.selected_key_value_json =
-                    Some(value_format::format_json_pretty(&value));
+            |fetch, value| {
+                fetch.viewer.selected_key_value_json = Some(value_format::format_json_pretty(&value));
                 Ok(())
             },
-            |app| {
-                app.value_viewer.selected_key_value_json = None;
+            |fetch| {
+                fetch.viewer.selected_key_value_json = None;
             },
             err_context,
         )
         .await;
     }
 
-    pub async fn fetch_and_set_stream_value(
+    pub(super) async fn fetch_and_set_stream_value(
         &mut self,
         key_name: &str,
         con: &mut MultiplexedConnection,
     ) {
-        let result = redis::cmd("XREVRANGE")
+        // The newest entries first, shown oldest to newest
+        let page_size = self.options.collection_page_size;
+        let mut range = redis::cmd("XREVRANGE");
+        range.arg(key_name).arg("+").arg("-");
+        if page_size > 0 {
+            range.arg("COUNT").arg(page_size);
+        }
+        let started = Instant::now();
+        let result = redis::pipe()
+            .cmd("XLEN")
             .arg(key_name)
-            .arg("+")
-            .arg("-")
-            .arg("COUNT")
-            .arg(100)
-            .query_async::<Value>(con)
+            .add_command(range)
+            .query_async::<(usize, Value)>(con)
             .await;
+        self.record(OperationClass::Collection, started.elapsed());
 
         match result {
-            Ok(value) => match parse_stream_entries(value) {
+            Ok((total, value)) => match parse_stream_entries(value) {
                 Ok(mut entries) => {
                     entries.reverse();
-                    self.value_viewer.selected_key_value_stream = Some(entries);
-                    self.value_viewer.selected_key_value = None;
-                    self.value_viewer.update_current_display_value();
+                    self.viewer.collection_page = CollectionPage::after(entries.len(), total, page_size);
+                    self.viewer.selected_key_value_stream = Some(entries);
+                    self.viewer.selected_key_value = None;
                 }
                 Err(message) => {
-                    self.value_viewer.selected_key_value_stream = None;
-                    self.value_viewer.selected_key_value = Some(message);
-                    self.value_viewer.update_current_display_value();
+                    self.viewer.selected_key_value_stream = None;
+                    self.viewer.selected_key_value = Some(message);
                 }
             },
             Err(e) => {
-                self.value_viewer.selected_key_value_stream = None;
-                self.value_viewer.selected_key_value =
-                    Some(format!("Error fetching stream: {}", e));
-                self.value_viewer.update_current_display_value();
+                self.viewer.selected_key_value_stream = None;
+                self.viewer.selected_key_value = Some(format!("Error fetching stream: {}", e));
             }
         }
     }
 }
 
-fn parse_hash_value(app: &mut App, key_name: &str, value: Value) -> Result<(), String> {
+/// Rows of the next page of a list, sorted set or stream.
+pub enum MoreRows {
+    List(Vec<String>),
+    Zset(Vec<(String, f64)>),
+    /// Entries older than the loaded ones, oldest first.
+    Stream(Vec<StreamEntry>),
+}
+
+impl MoreRows {
+    fn len(&self) -> usize {
+        match self {
+            MoreRows::List(items) => items.len(),
+            MoreRows::Zset(members) => members.len(),
+            MoreRows::Stream(entries) => entries.len(),
+        }
+    }
+}
+
+/// Reads the page after `page` of a list or sorted set, or the entries older than
+/// `oldest` (an exclusive stream ID bound) of a stream.
+pub(super) async fn fetch_more(
+    mut con: MultiplexedConnection,
+    key_name: &str,
+    key_type: &str,
+    page: CollectionPage,
+    oldest: Option<String>,
+) -> Result<MoreRows, String> {
+    let start = page.loaded;
+    let end = page_end(start, page.next_len());
+    match key_type {
+        "LIST" => {
+            let result = redis::cmd("LRANGE").arg(key_name).arg(start).arg(end).query_async(&mut con).await;
+            let values = expect_array(result.map_err(|e| e.to_string())?, "LRANGE")?;
+            Ok(MoreRows::List(inline_items(&values)))
+        }
+        "ZSET" => {
+            let result = redis::cmd("ZRANGE")
+                .arg(key_name)
+                .arg(start)
+                .arg(end)
+                .arg("WITHSCORES")
+                .query_async(&mut con)
+                .await;
+            let values = expect_array(result.map_err(|e| e.to_string())?, "ZRANGE")?;
+            zset_members(key_name, &values).map(MoreRows::Zset)
+        }
+        "STREAM" => {
+            let oldest = oldest.ok_or_else(|| "no entries loaded yet".to_string())?;
+            let result = redis::cmd("XREVRANGE")
+                .arg(key_name)
+                .arg(oldest)
+                .arg("-")
+                .arg("COUNT")
+                .arg(page.next_len())
+                .query_async(&mut con)
+                .await;
+            let mut older = parse_stream_entries(result.map_err(|e| e.to_string())?)?;
+            older.reverse();
+            Ok(MoreRows::Stream(older))
+        }
+        other => Err(format!("{} values are not paged", other.to_lowercase())),
+    }
+}
+
+impl App {
+    /// `n`: asks for the next page of the list or sorted set in the value panel, or the
+    /// next older stream entries.
+    pub fn load_more_collection(&mut self) {
+        let (Some(key), Some(page)) =
+            (self.value_viewer.active_leaf_key_name.clone(), self.value_viewer.collection_page)
+        else {
+            return;
+        };
+        let key_type = self.value_viewer.selected_key_type.clone().unwrap_or_default();
+        if !matches!(key_type.as_str(), "LIST" | "ZSET" | "STREAM") {
+            return;
+        }
+        let oldest = self
+            .value_viewer
+            .selected_key_value_stream
+            .as_ref()
+            .and_then(|entries| entries.first())
+            .map(|entry| format!("({}", entry.id));
+        if key_type == "STREAM" && oldest.is_none() {
+            return;
+        }
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected. Cannot load more.".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::LoadMoreValues { con, key, key_type, page, oldest });
+    }
+
+    /// Appends a page fetched by `load_more_collection`, or puts older stream entries
+    /// above the loaded ones, keeping the selected row.
+    pub(super) fn apply_more_values(
+        &mut self,
+        key: String,
+        page: CollectionPage,
+        rows: Result<MoreRows, String>,
+        elapsed: Duration,
+    ) {
+        self.latency.record(OperationClass::Collection, elapsed);
+        // The panel moved on to another key, or reloaded this one, since the page was asked for
+        if self.value_viewer.active_leaf_key_name.as_deref() != Some(key.as_str())
+            || self.value_viewer.collection_page != Some(page)
+        {
+            return;
+        }
+        let rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                self.notifications.error(format!("Failed to load more of '{}': {}", key, e));
+                return;
+            }
+        };
+        let added = rows.len();
+        let is_stream = matches!(rows, MoreRows::Stream(_));
+        let viewer = &mut self.value_viewer;
+        match rows {
+            MoreRows::List(items) => viewer.selected_key_value_list.get_or_insert_default().extend(items),
+            MoreRows::Zset(members) => viewer.selected_key_value_zset.get_or_insert_default().extend(members),
+            MoreRows::Stream(mut older) => {
+                let entries = viewer.selected_key_value_stream.get_or_insert_default();
+                older.append(entries);
+                *entries = older;
+            }
+        }
+        let loaded = page.loaded + added;
+        // An empty page means the collection shrank since it was opened
+        viewer.collection_page = CollectionPage::after(loaded, page.total, page.page_size).filter(|_| added > 0);
+        let rows_before = viewer.displayed_value_lines.as_ref().map_or(0, Vec::len);
+        let selected = viewer.selected_value_sub_index;
+        let scroll = viewer.value_view_scroll;
+        viewer.update_current_display_value();
+        let rows_after = viewer.displayed_value_lines.as_ref().map_or(0, Vec::len);
+        viewer.selected_value_sub_index = if is_stream {
+            selected + rows_after.saturating_sub(rows_before)
+        } else {
+            selected
+        };
+        viewer.value_view_scroll = scroll;
+        self.notifications.info(format!("Loaded {} more: {} of {}", added, loaded.min(page.total), page.total));
+    }
+}
+
+fn parse_hash_value(fetch: &mut ValueFetch, key_name: &str, value: Value) -> Result<(), String> {
     let values = expect_array(value, "HGETALL")?;
     if values.is_empty() {
-        app.value_viewer.selected_key_value_hash = Some(Vec::new());
-        app.value_viewer.selected_key_value = None;
+        fetch.viewer.selected_key_value_hash = Some(Vec::new());
+        fetch.viewer.selected_key_value = None;
         return Ok(());
     }
 
     let mut hash_data = Vec::new();
     for chunk in values.chunks(2) {
         if chunk.len() != 2 {
-            app.value_viewer.selected_key_value_hash = None;
+            fetch.viewer.selected_key_value_hash = None;
             return Err(format!(
                 "HGETALL for '{}' (hash) returned malformed pair data.",
                 key_name
@@ -193,28 +359,44 @@ fn parse_hash_value(app: &mut App, key_name: &str, value: Value) -> Result<(), S
         }
         let field = value_to_bytes(&chunk[0]).unwrap_or_default();
         let value_bytes = value_to_bytes(&chunk[1]).unwrap_or_default();
-        hash_data.push((
-            value_format::format_bytes_inline(&field),
-            value_format::format_bytes_inline(&value_bytes),
-        ));
+        let display_value = match value_decoder::decode(&value_bytes, fetch.options.decoder) {
+            Some(decoded) => {
+                fetch.viewer.decoded_as = Some(decoded.label.to_string());
+                decoded.compact()
+            }
+            None => value_format::format_bytes_inline(&value_bytes),
+        };
+        hash_data.push((value_format::format_bytes_inline(&field), display_value));
     }
-    app.value_viewer.selected_key_value_hash = Some(hash_data);
-    app.value_viewer.selected_key_value = None;
+    fetch.viewer.selected_key_value_hash = Some(hash_data);
+    fetch.viewer.selected_key_value = None;
     Ok(())
 }
 
-fn parse_zset_value(app: &mut App, key_name: &str, value: Value) -> Result<(), String> {
+fn parse_zset_value(fetch: &mut ValueFetch, key_name: &str, value: Value) -> Result<(), String> {
     let values = expect_array(value, "ZRANGE")?;
     if values.is_empty() {
-        app.value_viewer.selected_key_value_zset = Some(Vec::new());
-        app.value_viewer.selected_key_value = None;
+        fetch.viewer.selected_key_value_zset = Some(Vec::new());
+        fetch.viewer.selected_key_value = None;
         return Ok(());
     }
 
+    match zset_members(key_name, &values) {
+        Ok(members) => fetch.viewer.selected_key_value_zset = Some(members),
+        Err(message) => {
+            fetch.viewer.selected_key_value_zset = None;
+            return Err(message);
+        }
+    }
+    fetch.viewer.selected_key_value = None;
+    Ok(())
+}
+
+/// Member and score pairs of a `ZRANGE ... WITHSCORES` reply.
+fn zset_members(key_name: &str, values: &[Value]) -> Result<Vec<(String, f64)>, String> {
     let mut zset_data = Vec::new();
     for chunk in values.chunks(2) {
         if chunk.len() != 2 {
-            app.value_viewer.selected_key_value_zset = None;
             return Err(format!(
                 "ZRANGE for '{}' (zset) returned malformed pair data.",
                 key_name
@@ -230,40 +412,35 @@ fn parse_zset_value(app: &mut App, key_name: &str, value: Value) -> Result<(), S
         })?;
         zset_data.push((member, score));
     }
-    app.value_viewer.selected_key_value_zset = Some(zset_data);
-    app.value_viewer.selected_key_value = None;
-    Ok(())
+    Ok(zset_data)
 }
 
-fn parse_list_value(app: &mut App, _key_name: &str, value: Value) -> Result<(), String> {
+fn parse_list_value(fetch: &mut ValueFetch, _key_name: &str, value: Value) -> Result<(), String> {
     let values = expect_array(value, "LRANGE")?;
-    let list = values
-        .iter()
-        .map(|entry| {
-            let bytes = value_to_bytes(entry).unwrap_or_default();
-            value_format::format_bytes_inline(&bytes)
-        })
-        .collect::<Vec<String>>();
-    app.value_viewer.selected_key_value_list = Some(list);
-    app.value_viewer.selected_key_value = None;
+    fetch.viewer.selected_key_value_list = Some(inline_items(&values));
+    fetch.viewer.selected_key_value = None;
     Ok(())
 }
 
-fn parse_set_value(app: &mut App, _key_name: &str, value: Value) -> Result<(), String> {
+fn parse_set_value(fetch: &mut ValueFetch, _key_name: &str, value: Value) -> Result<(), String> {
     let values = expect_array(value, "SMEMBERS")?;
-    let set = values
+    fetch.viewer.selected_key_value_set = Some(inline_items(&values));
+    fetch.viewer.selected_key_value = None;
+    Ok(())
+}
+
+/// List elements or set members, each on one line.
+fn inline_items(values: &[Value]) -> Vec<String> {
+    values
         .iter()
         .map(|entry| {
             let bytes = value_to_bytes(entry).unwrap_or_default();
             value_format::format_bytes_inline(&bytes)
         })
-        .collect::<Vec<String>>();
-    app.value_viewer.selected_key_value_set = Some(set);
-    app.value_viewer.selected_key_value = None;
-    Ok(())
+        .collect()
 }
 
-fn parse_stream_entries(value: Value) -> Result<Vec<StreamEntry>, String> {
+pub(super) fn parse_stream_entries(value: Value) -> Result<Vec<StreamEntry>, String> {
     let values = expect_array(value, "XREVRANGE")?;
     let mut parsed_streams = Vec::new();
     for entry in values {
@@ -301,7 +478,7 @@ fn parse_stream_entries(value: Value) -> Result<Vec<StreamEntry>, String> {
     Ok(parsed_streams)
 }
 
-fn expect_array(value: Value, command: &str) -> Result<Vec<Value>, String> {
+pub(super) fn expect_array(value: Value, command: &str) -> Result<Vec<Value>, String> {
     match value {
         Value::Nil => Ok(Vec::new()),
         Value::Array(values) => Ok(values),
@@ -312,7 +489,7 @@ fn expect_array(value: Value, command: &str) -> Result<Vec<Value>, String> {
     }
 }
 
-fn value_to_bytes(value: &Value) -> Option<Vec<u8>> {
+pub(super) fn value_to_bytes(value: &Value) -> Option<Vec<u8>> {
     match value {
         Value::BulkString(bytes) => Some(bytes.clone()),
         Value::SimpleString(text) => Some(text.as_bytes().to_vec()),
@@ -337,6 +514,29 @@ fn value_to_f64(value: &Value) -> Option<f64> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn zset_members_pair_scores_and_pages_count_down() {
+        let values = vec![
+            Value::BulkString(b"alice".to_vec()),
+            Value::BulkString(b"1.5".to_vec()),
+            Value::BulkString(b"bob".to_vec()),
+            Value::Double(2.0),
+        ];
+        assert_eq!(
+            zset_members("scores", &values).unwrap(),
+            vec![("alice".to_string(), 1.5), ("bob".to_string(), 2.0)]
+        );
+        assert!(zset_members("scores", &values[..3]).is_err());
+
+        assert_eq!(page_end(0, 200), 199);
+        assert_eq!(page_end(400, 0), -1);
+        let page = CollectionPage::after(200, 450, 200).unwrap();
+        assert_eq!(page.next_len(), 200);
+        assert_eq!(CollectionPage::after(400, 450, 200).unwrap().next_len(), 50);
+        assert_eq!(CollectionPage::after(450, 450, 200), None);
+        assert_eq!(CollectionPage::after(10, 450, 0), None);
+    }
+
     #[test]
     fn parse_stream_entries_handles_basic_entry() {
         let value = Value::Array(vec![Value::Array(vec![
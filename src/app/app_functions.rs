@@ -0,0 +1,132 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::state_functions::{parse_call_input, parse_function_list, FunctionLibrary, FunctionPrompt};
+use super::worker::WorkerRequest;
+use super::App;
+use crate::command::render_reply;
+
+/// What the functions prompt runs.
+pub enum FunctionCall {
+    Call {
+        name: String,
+        keys: Vec<String>,
+        args: Vec<String>,
+    },
+    /// FUNCTION LOAD of the library in the file at `path`.
+    Load { path: String, replace: bool },
+}
+
+impl App {
+    pub fn open_functions(&mut self) {
+        self.functions.open();
+        self.fetch_functions();
+    }
+
+    pub fn fetch_functions(&mut self) {
+        let Some(con) = self.redis.connection.clone() else {
+            self.functions.result = Some("Not connected".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::FetchFunctions(con));
+    }
+
+    pub(super) fn apply_functions(&mut self, libraries: Result<Vec<FunctionLibrary>, String>) {
+        match libraries {
+            Ok(libraries) => {
+                self.functions.set_libraries(libraries);
+                if self.functions.libraries.is_empty() {
+                    self.functions.result =
+                        Some("No function libraries loaded (press l to FUNCTION LOAD one).".to_string());
+                }
+            }
+            Err(e) => self.functions.result = Some(format!("(error) FUNCTION LIST: {} (Redis 7+ required)", e)),
+        }
+    }
+
+    /// Runs the open prompt: FCALL the selected function or FUNCTION LOAD a file.
+    pub fn submit_function_prompt(&mut self) {
+        let Some(prompt) = self.functions.prompt.take() else {
+            return;
+        };
+        let input = std::mem::take(&mut self.functions.input);
+        let Some(con) = self.writer("function loads and calls") else {
+            return;
+        };
+        let call = match prompt {
+            FunctionPrompt::Call => {
+                let Some(function) = self.functions.selected_function() else {
+                    return;
+                };
+                let (keys, args) = parse_call_input(&input);
+                FunctionCall::Call {
+                    name: function.name.clone(),
+                    keys: keys.into_iter().map(str::to_string).collect(),
+                    args: args.into_iter().map(str::to_string).collect(),
+                }
+            }
+            FunctionPrompt::Load => {
+                let input = input.trim();
+                let (path, replace) = match input.strip_suffix("--replace") {
+                    Some(path) => (path.trim_end(), true),
+                    None => (input, false),
+                };
+                FunctionCall::Load {
+                    path: path.to_string(),
+                    replace,
+                }
+            }
+        };
+        self.submit(WorkerRequest::RunFunction { con, call });
+    }
+
+    /// Shows what the prompt returned; a loaded library is then listed.
+    pub(super) fn apply_function_result(&mut self, result: String, loaded: bool) {
+        self.functions.result = Some(result);
+        // Functions may write any key
+        self.key_cache.clear();
+        if loaded {
+            self.fetch_functions();
+        }
+    }
+}
+
+pub(super) async fn list_functions(mut con: MultiplexedConnection) -> Result<Vec<FunctionLibrary>, String> {
+    let reply = redis::cmd("FUNCTION").arg("LIST").query_async::<Value>(&mut con).await;
+    reply.map(|reply| parse_function_list(&reply)).map_err(|e| e.to_string())
+}
+
+/// Runs `call`, returning what to show and whether a library was loaded.
+pub(super) async fn run_function(mut con: MultiplexedConnection, call: FunctionCall) -> (String, bool) {
+    match call {
+        FunctionCall::Call { name, keys, args } => {
+            let reply = redis::cmd("FCALL")
+                .arg(&name)
+                .arg(keys.len())
+                .arg(&keys)
+                .arg(&args)
+                .query_async::<Value>(&mut con)
+                .await;
+            let result = match reply {
+                Ok(value) => render_reply(&value).join("\n"),
+                Err(e) => format!("(error) {}", e),
+            };
+            (result, false)
+        }
+        FunctionCall::Load { path, replace } => {
+            let code = match tokio::fs::read_to_string(&path).await {
+                Ok(code) => code,
+                Err(e) => return (format!("Failed to read '{}': {}", path, e), false),
+            };
+            let mut cmd = redis::cmd("FUNCTION");
+            cmd.arg("LOAD");
+            if replace {
+                cmd.arg("REPLACE");
+            }
+            match cmd.arg(code).query_async::<String>(&mut con).await {
+                Ok(library) => (format!("Loaded library '{}'", library), true),
+                Err(e) => (format!("(error) {}", e), false),
+            }
+        }
+    }
+}
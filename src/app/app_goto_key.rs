@@ -0,0 +1,83 @@
+use redis::aio::MultiplexedConnection;
+
+use super::value_fetch::ValuePurpose;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn open_goto_key_prompt(&mut self) {
+        self.goto_key_prompt = Some(String::new());
+    }
+
+    pub fn submit_goto_key_prompt(&mut self) {
+        if self
+            .goto_key_prompt
+            .as_deref()
+            .is_some_and(|input| !input.trim().is_empty())
+        {
+            self.goto_key();
+        } else {
+            self.goto_key_prompt = None;
+        }
+    }
+
+    /// Opens the key typed into the goto prompt by exact name, even if its level
+    /// has not been scanned yet, once the worker has confirmed it exists.
+    pub fn goto_key(&mut self) {
+        let Some(input) = self.goto_key_prompt.take() else {
+            return;
+        };
+        let key = input.trim().to_string();
+        let Some(con) = self.redis.connection.clone() else {
+            self.notifications.error("Goto key failed: No Redis connection available for key lookup.".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::GotoKey { con, key });
+    }
+
+    /// Shows the key looked up by `goto_key`. The breadcrumb is rebuilt from the key
+    /// delimiters.
+    pub(super) fn apply_goto_key(&mut self, key: String, found: Result<Option<String>, String>) {
+        let key_type = match found {
+            Ok(Some(key_type)) => key_type,
+            Ok(None) => {
+                self.notifications.error(format!("Key not found: {}", key));
+                return;
+            }
+            Err(e) => {
+                self.notifications.error(format!("Goto key failed: {}", e));
+                return;
+            }
+        };
+        if !key_type.is_empty() {
+            self.type_map_mut().insert(key.clone(), key_type);
+        }
+
+        if self.search_state.is_active {
+            self.exit_search_mode();
+        }
+        let path_segments: Vec<String> = self
+            .key_delimiters
+            .split(&key)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.reveal_key_in_tree(&path_segments);
+        self.clear_selected_key_info();
+        self.open_leaf_key(&key, ValuePurpose::Open);
+        self.value_is_pinned = true;
+        self.is_key_view_focused = true;
+        self.is_value_view_focused = false;
+    }
+}
+
+/// `EXISTS`, then `TYPE`, of a key to go to; None when it does not exist. The type is
+/// left empty when only it could not be read.
+pub(super) async fn look_up_key(mut con: MultiplexedConnection, key: &str) -> Result<Option<String>, String> {
+    let exists = redis::cmd("EXISTS").arg(key).query_async::<bool>(&mut con).await.map_err(|e| e.to_string())?;
+    if !exists {
+        return Ok(None);
+    }
+    let key_type = redis::cmd("TYPE").arg(key).query_async::<String>(&mut con).await.unwrap_or_default();
+    Ok(Some(key_type))
+}
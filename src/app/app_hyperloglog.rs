@@ -0,0 +1,63 @@
+use redis::aio::MultiplexedConnection;
+
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    /// `A` on a HyperLogLog: prompts for elements to `PFADD`, on dev profiles only.
+    pub fn open_pfadd_prompt(&mut self) {
+        if self.value_viewer.hyperloglog.is_none() {
+            self.notifications.info("PFADD works on HyperLogLog values.");
+            return;
+        }
+        let dev = self
+            .profiles
+            .get(self.current_profile_index)
+            .and_then(|profile| profile.dev)
+            .unwrap_or(false);
+        if !dev || self.is_read_only() {
+            self.notifications
+                .error(format!("PFADD is only available on dev profiles; '{}' is not one.", self.current_profile_name()));
+            return;
+        }
+        self.value_viewer.pfadd_input = Some(String::new());
+        self.value_viewer.pfadd_cursor = 0;
+    }
+
+    /// Adds the whitespace-separated elements typed in the prompt.
+    pub fn submit_pfadd(&mut self) {
+        let Some(input) = self.value_viewer.pfadd_input.take() else {
+            return;
+        };
+        let Some(key) = self.value_viewer.active_leaf_key_name.clone() else {
+            return;
+        };
+        let elements: Vec<String> = input.split_whitespace().map(str::to_string).collect();
+        if elements.is_empty() {
+            return;
+        }
+        let Some(con) = self.writer("PFADD writes") else {
+            return;
+        };
+        self.submit(WorkerRequest::PfAdd { con, key, elements });
+    }
+
+    /// Reports a PFADD and reloads the key so the new estimate and encoding show.
+    pub(super) fn apply_pfadd(&mut self, key: String, count: usize, changed: Result<i64, String>) {
+        match changed {
+            Ok(changed) => {
+                let registers = if changed == 1 { "registers changed" } else { "no register changed" };
+                self.notifications.success(format!("PFADD {} element(s) to '{}': {}.", count, key, registers));
+                self.key_cache.invalidate(&key);
+                if self.value_viewer.active_leaf_key_name.as_deref() == Some(key.as_str()) {
+                    self.refresh_active_key();
+                }
+            }
+            Err(e) => self.notifications.error(format!("PFADD failed: {}", e)),
+        }
+    }
+}
+
+pub(super) async fn pfadd(mut con: MultiplexedConnection, key: &str, elements: &[String]) -> Result<i64, String> {
+    redis::cmd("PFADD").arg(key).arg(elements).query_async::<i64>(&mut con).await.map_err(|e| e.to_string())
+}
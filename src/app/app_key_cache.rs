@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+use super::key_cache::CachedKey;
+use super::App;
+
+impl App {
+    /// How long a viewed key is shown from the cache; None when `key_cache_secs` is 0.
+    pub(crate) fn key_cache_max_age(&self) -> Option<Duration> {
+        (self.settings.key_cache_secs > 0).then(|| Duration::from_secs(self.settings.key_cache_secs))
+    }
+
+    /// Remembers the value panel just fetched for `key`.
+    pub(crate) fn cache_viewed_key(&mut self, key: &str, key_type: &str, ttl: i64) {
+        if self.key_cache_max_age().is_none() {
+            return;
+        }
+        let entry = CachedKey {
+            viewer: self.value_viewer.clone(),
+            key_type: key_type.to_string(),
+            ttl,
+            stored_at: Instant::now(),
+        };
+        self.key_cache.insert(key.to_string(), entry);
+    }
+
+    /// Shows `key` from the cache when a fresh copy is there, keeping the view preferences
+    /// that survive key changes. Returns whether it did.
+    pub(crate) fn restore_cached_key(&mut self, key: &str) -> bool {
+        let Some(max_age) = self.key_cache_max_age() else {
+            return false;
+        };
+        let Some(entry) = self.key_cache.get(key, max_age, Instant::now()) else {
+            return false;
+        };
+        let mut viewer = entry.viewer.clone();
+        viewer.wrap_disabled = self.value_viewer.wrap_disabled;
+        viewer.line_numbers = self.value_viewer.line_numbers;
+        viewer.timestamps_hidden = self.value_viewer.timestamps_hidden;
+        let (key_type, ttl, stored_at) = (entry.key_type.clone(), entry.ttl, entry.stored_at);
+        self.value_viewer = viewer;
+        self.value_viewer.update_current_display_value();
+        self.type_map_mut().insert(key.to_string(), key_type);
+        self.ttl_map.insert(key.to_string(), ttl);
+        self.ttl_read_at.insert(key.to_string(), stored_at);
+        true
+    }
+}
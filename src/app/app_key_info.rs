@@ -0,0 +1,52 @@
+use redis::aio::MultiplexedConnection;
+
+use super::state_key_info::KeyInfo;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn open_key_info(&mut self) {
+        let Some(key) = self.value_viewer.active_leaf_key_name.clone() else {
+            self.notifications.info("Select a key to inspect first.".to_string());
+            return;
+        };
+        let Some(con) = self.redis.connection.clone() else {
+            self.notifications.error("Not connected.");
+            return;
+        };
+        self.submit(WorkerRequest::FetchKeyInfo { con, key });
+    }
+
+    pub fn close_key_info(&mut self) {
+        self.key_info = None;
+    }
+
+    /// Shows the inspected key, with the MEMORY USAGE the value panel read for it.
+    pub(super) fn apply_key_info(&mut self, mut info: KeyInfo) {
+        if self.value_viewer.active_leaf_key_name.as_deref() == Some(info.key.as_str()) {
+            info.memory_usage = self.value_viewer.memory_usage;
+        }
+        self.key_info = Some(info);
+    }
+}
+
+pub(super) async fn read_key_info(mut con: MultiplexedConnection, key: String) -> KeyInfo {
+    let con = &mut con;
+    let key_type = redis::cmd("TYPE").arg(&key).query_async::<String>(con).await;
+    let ttl = redis::cmd("TTL").arg(&key).query_async::<i64>(con).await;
+    let encoding = redis::cmd("OBJECT").arg("ENCODING").arg(&key).query_async::<Option<String>>(con).await;
+    // IDLETIME errors under an LFU maxmemory policy, FREQ under any other
+    let idle_seconds = redis::cmd("OBJECT").arg("IDLETIME").arg(&key).query_async::<Option<i64>>(con).await;
+    let freq = redis::cmd("OBJECT").arg("FREQ").arg(&key).query_async::<Option<i64>>(con).await;
+    let dump = redis::cmd("DUMP").arg(&key).query_async::<Option<Vec<u8>>>(con).await;
+    KeyInfo {
+        key,
+        key_type: key_type.map_err(|e| e.to_string()),
+        ttl: ttl.map_err(|e| e.to_string()),
+        encoding: encoding.map_err(|e| e.to_string()),
+        idle_seconds: idle_seconds.map_err(|e| e.to_string()),
+        freq: freq.map_err(|e| e.to_string()),
+        serialized_len: dump.map(|dump| dump.map(|bytes| bytes.len())).map_err(|e| e.to_string()),
+        memory_usage: None,
+    }
+}
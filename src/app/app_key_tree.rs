@@ -0,0 +1,449 @@
+use super::key_scan::{KeyScan, KeyScanEvent, DEFAULT_SCAN_PATTERN};
+use super::latency_tracker::OperationClass;
+use super::{App, KeyTreeNode};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+impl App {
+    /// Key prefix of the level shown for the current breadcrumb, including the trailing delimiter.
+    pub(crate) fn current_level_prefix(&self) -> String {
+        let delimiter = self.key_delimiters.primary().to_string();
+        let mut prefix = self.current_breadcrumb.join(&delimiter);
+        if !prefix.is_empty() {
+            prefix.push_str(&delimiter);
+        }
+        prefix
+    }
+
+    /// Starts a background `SCAN MATCH <prefix>*` for the level being viewed unless it is
+    /// already loaded. Only immediate children are kept; deeper levels load when entered.
+    pub(crate) fn start_level_scan(&mut self) {
+        self.scan_level(self.current_level_prefix());
+    }
+
+    /// Starts the background scan of the level at `prefix`, replacing any other level scan.
+    pub(crate) fn scan_level(&mut self, prefix: String) {
+        if self.loaded_prefixes.contains(&prefix)
+            || self.key_scan.as_ref().is_some_and(|scan| scan.prefix == prefix)
+        {
+            return;
+        }
+        let Some(con) = self.redis.reader() else {
+            self.connection_status = "Not connected. Cannot fetch keys.".to_string();
+            return;
+        };
+        self.reset_level(&prefix);
+        if self.current_level_prefix().starts_with(&prefix) {
+            self.update_visible_keys();
+        }
+        let pattern = self.level_scan_pattern(&prefix);
+        let count_total = prefix.is_empty() && self.scan_pattern == DEFAULT_SCAN_PATTERN && self.scan_type.is_none();
+        self.scan_cursor = 0;
+        self.keys_fully_loaded = false;
+        self.key_scan = Some(KeyScan::spawn(con, prefix, pattern, self.scan_count, count_total, self.scan_type));
+    }
+
+    /// `SCAN MATCH` pattern for the level at `prefix`. Deeper levels can only MATCH their
+    /// own prefix; the profile pattern is applied client-side with [`App::keep_scan_matches`].
+    pub(crate) fn level_scan_pattern(&self, prefix: &str) -> String {
+        if prefix.is_empty() {
+            self.scan_pattern.clone()
+        } else {
+            self.key_delimiters.prefix_pattern(prefix)
+        }
+    }
+
+    /// Drops keys of a deeper level that the profile pattern excludes, and keys another
+    /// delimiter places elsewhere in the tree.
+    pub(crate) fn keep_scan_matches(&self, prefix: &str, keys: &mut Vec<String>) {
+        if !prefix.is_empty() && self.scan_pattern != DEFAULT_SCAN_PATTERN {
+            keys.retain(|key| glob_match(&self.scan_pattern, key));
+        }
+        if !self.key_delimiters.is_single() {
+            keys.retain(|key| self.key_delimiters.canonical(key).starts_with(prefix));
+        }
+    }
+
+    pub fn open_scan_prompt(&mut self) {
+        self.scan_prompt = Some(format!("{} {}", self.scan_pattern, self.scan_count));
+    }
+
+    /// Applies `<pattern> [count]` from the scan prompt and reloads the key tree.
+    pub fn submit_scan_prompt(&mut self) {
+        let Some(input) = self.scan_prompt.take() else {
+            return;
+        };
+        match parse_scan_prompt(&input) {
+            Ok((pattern, count)) => {
+                self.scan_pattern = pattern;
+                if let Some(count) = count {
+                    self.scan_count = count;
+                }
+                self.fetch_keys_and_build_tree();
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+
+    /// Stops the running scan; its level stays partially loaded until entered again.
+    pub fn cancel_key_scan(&mut self) {
+        if let Some(scan) = self.key_scan.take() {
+            self.connection_status = format!(
+                "Scan of '{}' cancelled after {} keys.",
+                scan.prefix, scan.found
+            );
+        }
+    }
+
+    /// Applies batches received from the background scan.
+    pub fn poll_key_scan(&mut self) {
+        let Some(scan) = &mut self.key_scan else {
+            return;
+        };
+        let events = scan.drain();
+        if events.is_empty() {
+            return;
+        }
+        let prefix = scan.prefix.clone();
+        let mut finished = false;
+        for event in events {
+            match event {
+                KeyScanEvent::Total(total) => {
+                    self.db_size = Some(total);
+                    if let Some(scan) = &mut self.key_scan {
+                        scan.total = Some(total);
+                    }
+                }
+                KeyScanEvent::Batch {
+                    mut keys,
+                    cursor,
+                    elapsed,
+                } => {
+                    self.latency.record(OperationClass::Scan, elapsed);
+                    self.keep_scan_matches(&prefix, &mut keys);
+                    self.insert_scanned_keys(&prefix, &keys);
+                    self.scan_cursor = cursor;
+                    if let Some(scan) = &mut self.key_scan {
+                        scan.found += keys.len();
+                        scan.cursor = cursor;
+                    }
+                }
+                KeyScanEvent::Done => {
+                    self.keys_fully_loaded = true;
+                    self.loaded_prefixes.insert(prefix.clone());
+                    finished = true;
+                }
+                KeyScanEvent::Error(e) => {
+                    self.connection_status = format!("Failed during SCAN: {}", e);
+                    self.key_scan = None;
+                    break;
+                }
+            }
+        }
+
+        if prefix == self.current_level_prefix() {
+            let selected = self.selected_visible_key_index;
+            self.update_visible_keys();
+            self.selected_visible_key_index =
+                selected.min(self.visible_keys_in_current_view.len().saturating_sub(1));
+            let pending = self.pending_key_selection.as_deref();
+            if let Some(idx) = pending.and_then(|name| self.leaf_index_in_view(name)) {
+                self.selected_visible_key_index = idx;
+                self.pending_key_selection = None;
+            }
+        }
+        if finished {
+            self.pending_key_selection = None;
+        }
+        let Some(scan) = &self.key_scan else {
+            return;
+        };
+        let found = scan.found;
+        self.connection_status = if !finished {
+            format!(
+                "Connected to DB {}. Found {} keys under '{}' (cursor {}).",
+                self.selected_db_index, found, prefix, scan.cursor
+            )
+        } else if found == 0 {
+            format!("Connected to DB {}. No keys found.", self.selected_db_index)
+        } else {
+            format!(
+                "Connected to DB {}. Found {} keys under '{}'. Displaying {} items.",
+                self.selected_db_index,
+                found,
+                prefix,
+                self.visible_keys_in_current_view.len()
+            )
+        };
+        if finished {
+            self.key_scan = None;
+        }
+    }
+
+    /// Forgets everything loaded beneath `prefix` so the level can be scanned afresh.
+    fn reset_level(&mut self, prefix: &str) {
+        if let Some(level) = level_mut(&mut self.key_tree, self.key_delimiters.primary(), prefix) {
+            level.clear();
+        }
+        let beneath = |key: &str| key.len() > prefix.len() && key.starts_with(prefix);
+        let delimiters = &self.key_delimiters;
+        Arc::make_mut(&mut self.raw_keys).retain(|key| !delimiters.canonical(key).starts_with(prefix));
+        self.folder_key_counts.retain(|key, _| !beneath(key));
+        self.loaded_prefixes.retain(|loaded| !loaded.starts_with(prefix));
+    }
+
+    /// Records keys returned by a SCAN of the level rooted at `prefix`: direct leaves become
+    /// tree leaves, deeper keys only create their child folder and bump its key count.
+    pub(crate) fn insert_scanned_keys(&mut self, prefix: &str, keys: &[String]) {
+        let delimiter = self.key_delimiters.primary();
+        let Some(level) = level_mut(&mut self.key_tree, delimiter, prefix) else {
+            return;
+        };
+        for key in keys {
+            let canonical = self.key_delimiters.canonical(key);
+            let Some(rest) = canonical.strip_prefix(prefix) else {
+                continue;
+            };
+            match rest.find(delimiter) {
+                Some(idx) => {
+                    let child = &rest[..idx];
+                    folder_entry(level, child);
+                    let child_prefix = &canonical[..prefix.len() + idx + delimiter.len_utf8()];
+                    *self
+                        .folder_key_counts
+                        .entry(child_prefix.to_string())
+                        .or_insert(0) += 1;
+                }
+                None => {
+                    if let Entry::Vacant(entry) = level.entry(rest.to_string()) {
+                        entry.insert(KeyTreeNode::Leaf {
+                            full_key_name: key.clone(),
+                        });
+                    }
+                    Arc::make_mut(&mut self.raw_keys).push(key.clone());
+                }
+            }
+        }
+    }
+}
+
+impl App {
+    /// Loaded levels whose prefix `key` falls under, shallowest first.
+    fn loaded_levels_above(&self, key: &str) -> Vec<String> {
+        let delimiter = self.key_delimiters.primary();
+        let key = self.key_delimiters.canonical(key);
+        std::iter::once(String::new())
+            .chain(key.match_indices(delimiter).map(|(idx, _)| key[..idx + delimiter.len_utf8()].to_string()))
+            .filter(|prefix| self.loaded_prefixes.contains(prefix))
+            .collect()
+    }
+
+    /// Adds a key reported by a keyspace notification to every loaded level above it.
+    ///
+    /// Beneath a folder whose level is not loaded a write cannot be told apart from a
+    /// new key, so counts there only grow on the `new` event (`created`).
+    pub(crate) fn insert_live_key(&mut self, key: &str, created: bool) {
+        let levels = self.loaded_levels_above(key);
+        let Some(deepest) = levels.last() else {
+            return;
+        };
+        let delimiter = self.key_delimiters.primary();
+        let Some(level) = level_mut(&mut self.key_tree, delimiter, deepest) else {
+            return;
+        };
+        let canonical = self.key_delimiters.canonical(key);
+        let rest = &canonical[deepest.len()..];
+        let is_new = match rest.find(delimiter) {
+            None => !level.contains_key(rest),
+            Some(idx) => created || !level.contains_key(&rest[..idx]),
+        };
+        if !is_new {
+            return;
+        }
+        let keys = [key.to_string()];
+        for prefix in &levels {
+            self.insert_scanned_keys(prefix, &keys);
+        }
+    }
+
+    /// Removes a deleted or expired key from every loaded level above it, dropping
+    /// folders that become empty.
+    pub(crate) fn remove_live_key(&mut self, key: &str) {
+        let delimiter = self.key_delimiters.primary();
+        let canonical = self.key_delimiters.canonical(key).into_owned();
+        for prefix in self.loaded_levels_above(key) {
+            let Some(level) = level_mut(&mut self.key_tree, delimiter, &prefix) else {
+                continue;
+            };
+            let rest = &canonical[prefix.len()..];
+            let Some(idx) = rest.find(delimiter) else {
+                if matches!(level.get(rest), Some(KeyTreeNode::Leaf { full_key_name }) if full_key_name == key) {
+                    level.remove(rest);
+                    Arc::make_mut(&mut self.raw_keys).retain(|raw| raw != key);
+                }
+                continue;
+            };
+            let child_prefix = &canonical[..prefix.len() + idx + delimiter.len_utf8()];
+            let Some(count) = self.folder_key_counts.get_mut(child_prefix) else {
+                continue;
+            };
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                self.folder_key_counts.remove(child_prefix);
+                level.remove(&rest[..idx]);
+                self.loaded_prefixes.retain(|loaded| !loaded.starts_with(child_prefix));
+            }
+        }
+    }
+}
+
+/// Tree level for a prefix ending in `delimiter` (or the root for `""`), creating folders
+/// along the way.
+pub(super) fn level_mut<'a>(
+    tree: &'a mut HashMap<String, KeyTreeNode>,
+    delimiter: char,
+    prefix: &str,
+) -> Option<&'a mut HashMap<String, KeyTreeNode>> {
+    let Some(path) = prefix.strip_suffix(delimiter) else {
+        return prefix.is_empty().then_some(tree);
+    };
+    let mut level = tree;
+    for segment in path.split(delimiter) {
+        level = folder_entry(level, segment);
+    }
+    Some(level)
+}
+
+/// Returns the folder called `name` in `level`, creating it or replacing a leaf of that name.
+pub(super) fn folder_entry<'a>(
+    level: &'a mut HashMap<String, KeyTreeNode>,
+    name: &str,
+) -> &'a mut HashMap<String, KeyTreeNode> {
+    let node = level
+        .entry(name.to_string())
+        .or_insert_with(|| KeyTreeNode::Folder(HashMap::new()));
+    if let KeyTreeNode::Leaf { .. } = node {
+        *node = KeyTreeNode::Folder(HashMap::new());
+    }
+    match node {
+        KeyTreeNode::Folder(sub_map) => sub_map,
+        KeyTreeNode::Leaf { .. } => unreachable!("Leaf was just replaced by a Folder"),
+    }
+}
+
+/// Escapes glob metacharacters so a key prefix matches literally in `SCAN MATCH`.
+pub(crate) fn glob_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Redis `stringmatch` semantics: `*`, `?`, `[...]` classes with ranges and `^` negation,
+/// and `\` escapes.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_from(&pattern, &text)
+}
+
+fn glob_match_from(pattern: &[char], text: &[char]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return text.is_empty();
+    };
+    match first {
+        '*' => (0..=text.len()).any(|skip| glob_match_from(rest, &text[skip..])),
+        '?' => !text.is_empty() && glob_match_from(rest, &text[1..]),
+        '[' => {
+            let Some((&ch, text_rest)) = text.split_first() else {
+                return false;
+            };
+            let (matched, pattern_rest) = match_class(rest, ch);
+            matched && glob_match_from(pattern_rest, text_rest)
+        }
+        '\\' if !rest.is_empty() => {
+            text.first() == Some(&rest[0]) && glob_match_from(&rest[1..], &text[1..])
+        }
+        literal => text.first() == Some(&literal) && glob_match_from(rest, &text[1..]),
+    }
+}
+
+/// Matches `ch` against the class body following `[`, returning the pattern after the closing `]`.
+fn match_class(pattern: &[char], ch: char) -> (bool, &[char]) {
+    let (negated, mut idx) = match pattern.first() {
+        Some('^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut matched = false;
+    while idx < pattern.len() && pattern[idx] != ']' {
+        if pattern[idx] == '\\' && idx + 1 < pattern.len() {
+            matched |= pattern[idx + 1] == ch;
+            idx += 2;
+        } else if idx + 2 < pattern.len() && pattern[idx + 1] == '-' && pattern[idx + 2] != ']' {
+            let (low, high) = (pattern[idx].min(pattern[idx + 2]), pattern[idx].max(pattern[idx + 2]));
+            matched |= (low..=high).contains(&ch);
+            idx += 3;
+        } else {
+            matched |= pattern[idx] == ch;
+            idx += 1;
+        }
+    }
+    let rest = pattern.get(idx + 1..).unwrap_or(&[]);
+    (matched != negated, rest)
+}
+
+/// Parses `<pattern> [count]`; a trailing number is taken as the COUNT hint.
+fn parse_scan_prompt(input: &str) -> Result<(String, Option<usize>), String> {
+    let input = input.trim();
+    let (pattern, count) = match input.rsplit_once(' ') {
+        Some((pattern, count)) if count.chars().all(|c| c.is_ascii_digit()) => {
+            let count = count
+                .parse::<usize>()
+                .ok()
+                .filter(|count| *count > 0)
+                .ok_or_else(|| format!("Invalid SCAN COUNT '{}'", count))?;
+            (pattern.trim(), Some(count))
+        }
+        _ => (input, None),
+    };
+    if pattern.is_empty() {
+        return Err("Scan pattern cannot be empty".to_string());
+    }
+    Ok((pattern.to_string(), count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_escape, glob_match, parse_scan_prompt};
+
+    #[test]
+    fn glob_escape_quotes_metacharacters() {
+        assert_eq!(glob_escape("users:"), "users:");
+        assert_eq!(glob_escape("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+
+    #[test]
+    fn glob_match_follows_redis_semantics() {
+        assert!(glob_match("myapp:*", "myapp:users:1"));
+        assert!(!glob_match("myapp:*", "other:1"));
+        assert!(glob_match("user:?", "user:7"));
+        assert!(glob_match("user:[0-3]", "user:2"));
+        assert!(!glob_match("user:[^0-3]", "user:2"));
+        assert!(glob_match("a\\*b", "a*b"));
+        assert!(!glob_match("a\\*b", "axb"));
+        assert!(glob_match(&glob_escape("we[i]rd*"), "we[i]rd*"));
+    }
+
+    #[test]
+    fn parse_scan_prompt_splits_trailing_count() {
+        assert_eq!(parse_scan_prompt("myapp:* 500"), Ok(("myapp:*".to_string(), Some(500))));
+        assert_eq!(parse_scan_prompt("my app:*"), Ok(("my app:*".to_string(), None)));
+        assert!(parse_scan_prompt("* 0").is_err());
+        assert!(parse_scan_prompt("  ").is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use super::latency_monitor::{LatencyMonitor, LatencyMonitorEvent};
+use super::App;
+
+impl App {
+    /// Opens the latency panel and starts sampling until it is closed.
+    pub fn open_latency_panel(&mut self) {
+        let Some(con) = self.redis.connection.clone() else {
+            self.notifications.error("Not connected. Cannot sample latency.".to_string());
+            return;
+        };
+        let panel = &mut self.latency_panel;
+        panel.is_active = true;
+        panel.ping_samples.clear();
+        panel.error = None;
+        panel.monitor = Some(LatencyMonitor::spawn(con));
+    }
+
+    pub fn poll_latency_panel(&mut self) {
+        let panel = &mut self.latency_panel;
+        let Some(monitor) = &mut panel.monitor else {
+            return;
+        };
+        for event in monitor.drain() {
+            match event {
+                LatencyMonitorEvent::Ping(rtt) => panel.record_ping(rtt),
+                LatencyMonitorEvent::Events(events) => {
+                    panel.events = events;
+                    panel.error = None;
+                }
+                // Keep sampling; LATENCY may be renamed or blocked while PING still works
+                LatencyMonitorEvent::Error(message) => panel.error = Some(message),
+            }
+        }
+    }
+}
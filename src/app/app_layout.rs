@@ -0,0 +1,37 @@
+use crate::config::{Config, KEY_PANEL_PERCENT_RANGE};
+
+use super::App;
+
+/// Percentage points the key list grows or shrinks per Ctrl+h / Ctrl+l.
+const KEY_PANEL_STEP: u16 = 5;
+
+impl App {
+    /// Key list width in percent, kept within [`KEY_PANEL_PERCENT_RANGE`] whatever the config says.
+    pub fn key_panel_percent(&self) -> u16 {
+        self.settings
+            .key_panel_percent
+            .clamp(*KEY_PANEL_PERCENT_RANGE.start(), *KEY_PANEL_PERCENT_RANGE.end())
+    }
+
+    /// Widens or narrows the key list by one step and saves the width to the config.
+    pub fn resize_key_panel(&mut self, wider: bool) {
+        let current = self.key_panel_percent();
+        let resized = if wider {
+            current + KEY_PANEL_STEP
+        } else {
+            current.saturating_sub(KEY_PANEL_STEP)
+        };
+        let percent = resized.clamp(
+            *KEY_PANEL_PERCENT_RANGE.start(),
+            *KEY_PANEL_PERCENT_RANGE.end(),
+        );
+        if percent == self.settings.key_panel_percent {
+            return;
+        }
+        self.settings.key_panel_percent = percent;
+        match Config::persist_setting(None, "key_panel_percent", &percent.to_string()) {
+            Ok(()) => self.notifications.success(format!("Key panel width {}% (saved).", percent)),
+            Err(e) => self.notifications.error(format!("Key panel width {}%, not saved: {}", percent, e)),
+        }
+    }
+}
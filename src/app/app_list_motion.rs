@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+
+use super::mouse::PanelAreas;
+use super::App;
+
+impl App {
+    /// Previews the selected key once the selection has rested for `preview_delay_ms`;
+    /// moving again pushes the preview back.
+    pub fn schedule_auto_preview(&mut self) {
+        match self.settings.preview_delay_ms {
+            // Due straight away, so the preview runs on the next pass of the event loop
+            0 => self.preview_due = Some(Instant::now()),
+            delay => self.preview_due = Some(Instant::now() + Duration::from_millis(delay)),
+        }
+    }
+
+    /// True once a scheduled preview is due; it is then no longer scheduled.
+    pub fn take_due_auto_preview(&mut self) -> bool {
+        if self.preview_due.is_none_or(|due| Instant::now() < due) {
+            return false;
+        }
+        self.preview_due = None;
+        true
+    }
+
+    /// Selected row and length of the key list as drawn: search matches, tree rows or
+    /// the current level.
+    pub fn key_list_position(&self) -> (usize, usize) {
+        if self.search_state.is_active {
+            return (self.search_state.selected_index, self.search_state.filtered_keys.len());
+        }
+        if self.tree_view.is_active {
+            let rows = self.tree_rows();
+            return (self.tree_selected_row(&rows), rows.len());
+        }
+        (self.selected_visible_key_index, self.visible_keys_in_current_view.len())
+    }
+
+    /// Selected (or top) row and line count of the value view.
+    fn value_list_position(&self) -> (usize, usize) {
+        let viewer = &self.value_viewer;
+        match &viewer.displayed_value_lines {
+            Some(lines) => (viewer.selected_value_sub_index, lines.len()),
+            None => (viewer.value_view_scroll.0 as usize, viewer.line_count()),
+        }
+    }
+
+    /// Keeps the key and value list offsets following their selections in the list areas
+    /// about to be drawn.
+    pub fn sync_list_scroll(&mut self, areas: &PanelAreas) {
+        let (selected, len) = self.key_list_position();
+        self.key_list_scroll.follow(selected, len, areas.key_list.height as usize);
+        let (selected, len) = self.value_list_position();
+        let value_rows = areas.values_pane.height.saturating_sub(2) as usize;
+        self.value_list_scroll.follow(selected, len, value_rows);
+    }
+
+    /// `gg`: first row of the focused list.
+    pub fn select_list_top(&mut self) {
+        self.select_focused_list_row(0);
+    }
+
+    /// `G`: last row of the focused list, or row `count` (1-based, as in the gutter).
+    pub fn select_list_bottom(&mut self, count: Option<usize>) {
+        if let Some(line) = count.filter(|_| self.is_value_view_focused) {
+            self.value_viewer.goto_line(line);
+            return;
+        }
+        let Some((_, len)) = self.focused_list_position() else {
+            return;
+        };
+        let row = count.map_or(len.saturating_sub(1), |row| row.saturating_sub(1));
+        self.select_focused_list_row(row.min(len.saturating_sub(1)));
+    }
+
+    /// `Ctrl+d` / `Ctrl+u`: half the visible rows down or up.
+    pub fn move_list_half_page(&mut self, down: bool) {
+        let Some((selected, len)) = self.focused_list_position() else {
+            return;
+        };
+        let scroll = if self.is_value_view_focused { self.value_list_scroll } else { self.key_list_scroll };
+        let row = if down {
+            (selected + scroll.half_page()).min(len.saturating_sub(1))
+        } else {
+            selected.saturating_sub(scroll.half_page())
+        };
+        self.select_focused_list_row(row);
+    }
+
+    /// `zz`: scrolls the focused list so its selection is in the middle.
+    pub fn center_list_selection(&mut self) {
+        if self.is_value_view_focused {
+            if self.value_viewer.displayed_value_lines.is_some() {
+                let (selected, len) = self.value_list_position();
+                self.value_list_scroll.center(selected, len);
+            }
+        } else if self.is_key_view_focused {
+            let (selected, len) = self.key_list_position();
+            self.key_list_scroll.center(selected, len);
+        }
+    }
+
+    fn focused_list_position(&self) -> Option<(usize, usize)> {
+        if self.is_value_view_focused {
+            Some(self.value_list_position())
+        } else if self.is_key_view_focused {
+            Some(self.key_list_position())
+        } else {
+            None
+        }
+    }
+
+    fn select_focused_list_row(&mut self, row: usize) {
+        if self.is_value_view_focused {
+            match self.value_viewer.displayed_value_lines {
+                Some(_) => self.value_viewer.selected_value_sub_index = row,
+                None => self.value_viewer.value_view_scroll.0 = row.min(u16::MAX as usize) as u16,
+            }
+            return;
+        }
+        if !self.is_key_view_focused || self.key_list_position().1 == 0 {
+            return;
+        }
+        if self.search_state.is_active {
+            self.search_state.selected_index = row;
+            return;
+        }
+        if self.tree_view.is_active {
+            if let Some(tree_row) = self.tree_rows().get(row) {
+                self.select_tree_row(tree_row);
+            }
+        } else if row != self.selected_visible_key_index {
+            self.selected_visible_key_index = row;
+            self.clear_selected_key_info_if_not_pinned();
+        }
+        self.schedule_auto_preview();
+    }
+}
@@ -0,0 +1,186 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::pubsub::{PubSubEvent, PubSubListener};
+use super::notifications::Severity;
+use super::worker::WorkerRequest;
+use super::App;
+
+/// How a keyspace event affects the key it names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyChange {
+    /// The `new` event, only sent when `n` is in notify-keyspace-events (Redis 7+).
+    Created,
+    Written,
+    Removed,
+}
+
+pub(crate) fn classify_event(event: &str) -> KeyChange {
+    match event {
+        "new" => KeyChange::Created,
+        "del" | "expired" | "evicted" | "rename_from" | "move_from" => KeyChange::Removed,
+        _ => KeyChange::Written,
+    }
+}
+
+/// Key named by a `__keyspace@<db>__:<key>` channel.
+pub(crate) fn keyspace_key(channel: &str) -> Option<&str> {
+    channel
+        .strip_prefix("__keyspace@")?
+        .split_once("__:")
+        .map(|(_, key)| key)
+}
+
+impl App {
+    pub fn toggle_live_updates(&mut self) {
+        self.settings.live_updates = !self.settings.live_updates;
+        if !self.settings.live_updates {
+            self.live_updates = None;
+            self.notifications.info("Live key updates off.".to_string());
+            return;
+        }
+        self.start_live_updates();
+        if self.live_updates.is_some() {
+            self.check_keyspace_notifications();
+        }
+    }
+
+    /// (Re)subscribes to keyspace events of the selected DB.
+    pub(crate) fn start_live_updates(&mut self) {
+        let Some(client) = self.redis.client.clone() else {
+            self.notifications.error("Not connected. Cannot follow keyspace events.".to_string());
+            return;
+        };
+        let pattern = format!("__keyspace@{}__:*", self.redis.db_index);
+        self.live_updates = Some(PubSubListener::spawn(client, Vec::new(), vec![pattern]));
+    }
+
+    /// Warns when the server is not configured to publish keyspace events.
+    fn check_keyspace_notifications(&mut self) {
+        if let Some(con) = self.redis.connection.clone() {
+            self.submit(WorkerRequest::CheckKeyspaceNotifications(con));
+        }
+    }
+
+    /// Applies queued keyspace events to the key tree and refreshes the viewed key
+    /// when it changed.
+    pub fn poll_live_updates(&mut self) {
+        let Some(listener) = &mut self.live_updates else {
+            return;
+        };
+        let mut changes = Vec::new();
+        for event in listener.drain() {
+            match event {
+                PubSubEvent::Message(message) => {
+                    if let Some(key) = keyspace_key(&message.channel) {
+                        changes.push((key.to_string(), classify_event(&message.payload)));
+                    }
+                }
+                PubSubEvent::Error(message) => {
+                    self.notifications.error(format!("Live key updates stopped: {}", message));
+                    self.live_updates = None;
+                    self.settings.live_updates = false;
+                    break;
+                }
+            }
+        }
+        if changes.is_empty() {
+            return;
+        }
+
+        let mut active_change = None;
+        for (key, change) in &changes {
+            self.apply_key_change(key, *change);
+            if self.value_viewer.active_leaf_key_name.as_deref() == Some(key.as_str()) {
+                active_change = Some(*change);
+            }
+        }
+
+        let selected_name = self
+            .visible_keys_in_current_view
+            .get(self.selected_visible_key_index)
+            .map(|(name, _)| name.clone());
+        self.update_visible_keys();
+        self.selected_visible_key_index = selected_name
+            .and_then(|name| {
+                self.visible_keys_in_current_view
+                    .iter()
+                    .position(|(visible, _)| *visible == name)
+            })
+            .unwrap_or(0)
+            .min(self.visible_keys_in_current_view.len().saturating_sub(1));
+
+        match active_change {
+            Some(KeyChange::Removed) => {
+                self.notifications.info("The viewed key was deleted or expired.".to_string());
+            }
+            Some(_) => self.refresh_active_key(),
+            None => {}
+        }
+    }
+
+    pub(crate) fn apply_key_change(&mut self, key: &str, change: KeyChange) {
+        self.type_map_mut().remove(key);
+        self.ttl_map.remove(key);
+        self.ttl_read_at.remove(key);
+        self.key_cache.invalidate(key);
+        self.key_sizes.sizes.remove(key);
+        if change == KeyChange::Removed {
+            self.remove_live_key(key);
+            return;
+        }
+        // The type of a new key is unknown here, so a TYPE filter leaves it to the next rescan
+        if self.scan_type.is_some() || !super::app_key_tree::glob_match(&self.scan_pattern, key) {
+            return;
+        }
+        self.insert_live_key(key, change == KeyChange::Created);
+    }
+}
+
+/// Reads notify-keyspace-events and words the toast that follows turning live updates on.
+pub(super) async fn check_keyspace_notifications(mut con: MultiplexedConnection) -> (Severity, String) {
+    let reply = redis::cmd("CONFIG")
+        .arg("GET")
+        .arg("notify-keyspace-events")
+        .query_async::<Value>(&mut con)
+        .await;
+    let flags = match reply {
+        Ok(Value::Array(items)) => items.get(1).and_then(crate::command::resp::value_text),
+        Ok(Value::Map(entries)) => entries.first().and_then(|(_, value)| crate::command::resp::value_text(value)),
+        Ok(_) => None,
+        Err(e) => {
+            return (
+                Severity::Info,
+                format!("Live key updates on; could not read notify-keyspace-events: {}", e),
+            );
+        }
+    }
+    .unwrap_or_default();
+    let has_events = flags.contains('A') || flags.chars().any(|c| "g$lshzxetd".contains(c));
+    if flags.contains('K') && has_events {
+        (Severity::Success, format!("Live key updates on (notify-keyspace-events '{}').", flags))
+    } else {
+        (
+            Severity::Error,
+            format!(
+                "Live key updates on, but notify-keyspace-events is '{}'. Run CONFIG SET notify-keyspace-events KA to receive events.",
+                flags
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyspace_channels_name_the_changed_key() {
+        assert_eq!(keyspace_key("__keyspace@0__:users:1"), Some("users:1"));
+        assert_eq!(keyspace_key("__keyspace@12__:a__:b"), Some("a__:b"));
+        assert_eq!(keyspace_key("orders"), None);
+        assert_eq!(classify_event("expired"), KeyChange::Removed);
+        assert_eq!(classify_event("hset"), KeyChange::Written);
+        assert_eq!(classify_event("new"), KeyChange::Created);
+    }
+}
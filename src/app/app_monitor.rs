@@ -0,0 +1,41 @@
+use super::monitor::{MonitorEvent, MonitorSession};
+use super::App;
+
+impl App {
+    /// Opens the MONITOR panel. Streaming only starts on request because
+    /// MONITOR slows the server down.
+    pub fn open_monitor(&mut self) {
+        self.monitor.is_active = true;
+    }
+
+    pub fn toggle_monitor_session(&mut self) {
+        if self.monitor.session.take().is_some() {
+            return;
+        }
+        let Some(client) = self.redis.client.clone() else {
+            self.notifications.error("Not connected. Cannot start MONITOR.".to_string());
+            return;
+        };
+        self.monitor.session = Some(MonitorSession::spawn(client));
+    }
+
+    pub fn poll_monitor(&mut self) {
+        let Some(session) = &mut self.monitor.session else {
+            return;
+        };
+        let mut entries = Vec::new();
+        for event in session.drain() {
+            match event {
+                MonitorEvent::Entry(entry) => entries.push(entry),
+                MonitorEvent::Error(message) => {
+                    self.notifications.info(message);
+                    self.monitor.session = None;
+                    break;
+                }
+            }
+        }
+        if !entries.is_empty() {
+            self.monitor.push_entries(entries);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::Instant;
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+
+use super::mouse::{breadcrumb_level_at, list_row_at, list_row_item, Pane, PanelAreas};
+use super::App;
+
+impl App {
+    /// Whether a modal that owns the keyboard is drawn over the main view.
+    fn modal_open(&self) -> bool {
+        self.help.is_active
+            || self.bookmarks_panel.is_active
+            || self.action_menu.is_active
+            || self.scan_presets.is_active
+            || self.paste_preview.is_active
+            || self.folder_copy.is_active
+            || self.delete_dialog.show_confirmation_dialog
+            || self.key_info.is_some()
+            || self.script_runner.is_active
+            || self.functions.is_active
+            || self.redisearch.is_active
+            || self.pubsub.is_active
+            || self.monitor.is_active
+            || self.config_editor.is_active
+            || self.doctor_report.is_active
+            || self.stats_report.is_active
+            || self.analysis.is_active
+            || self.compare.is_active
+            || self.snapshots.is_active
+            || self.random_sample.is_active
+            || self.latency_panel.is_active
+            || self.consumer_groups.is_active
+            || self.command_state.is_active
+    }
+
+    /// Click selects (and focuses the pane), double-click activates, right-click opens the
+    /// key action menu, the wheel moves the focused list. Ignored while a modal other than the profile selector is open.
+    pub fn handle_mouse(&mut self, mouse: MouseEvent, areas: &PanelAreas) {
+        if self.modal_open() {
+            return;
+        }
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.scroll_focused_list(true),
+            MouseEventKind::ScrollUp => self.scroll_focused_list(false),
+            MouseEventKind::Down(MouseButton::Left) => self.click(mouse.column, mouse.row, areas),
+            MouseEventKind::Down(MouseButton::Right) => self.right_click(mouse.column, mouse.row, areas),
+            _ => {}
+        }
+    }
+
+    fn scroll_focused_list(&mut self, down: bool) {
+        if self.profile_state.is_active {
+            if down {
+                self.next_profile_in_list();
+            } else {
+                self.previous_profile_in_list();
+            }
+        } else if self.search_state.is_active && self.is_key_view_focused {
+            if down {
+                self.select_next_filtered_key();
+            } else {
+                self.select_previous_filtered_key();
+            }
+        } else if self.is_value_view_focused {
+            if down {
+                self.select_next_value_item();
+            } else {
+                self.select_previous_value_item();
+            }
+        } else if self.is_key_view_focused {
+            if self.tree_view.is_active {
+                self.move_tree_selection(if down { 1 } else { -1 });
+            } else if down {
+                self.next_key_in_view();
+            } else {
+                self.previous_key_in_view();
+            }
+            self.schedule_auto_preview();
+        } else if down {
+            self.next_db();
+        } else {
+            self.previous_db();
+        }
+    }
+
+    fn click(&mut self, column: u16, row: u16, areas: &PanelAreas) {
+        let Some(pane) = areas.pane_at(column, row) else {
+            return;
+        };
+        match pane {
+            Pane::Profiles => {
+                let Some(list) = areas.profile_list else {
+                    return;
+                };
+                let selected = self.profile_state.selected_index;
+                let Some(index) = list_row_item(list, selected, self.profiles.len(), column, row) else {
+                    return;
+                };
+                self.profile_state.selected_index = index;
+                if self.clicks.register(pane, index, Instant::now()) {
+                    self.select_profile_and_connect();
+                }
+            }
+            Pane::Databases => {
+                self.focus_db();
+                let len = self.db_count as usize;
+                let Some(index) = list_row_item(areas.db_list, self.selected_db_index, len, column, row) else {
+                    return;
+                };
+                self.selected_db_index = index;
+                if self.clicks.register(pane, index, Instant::now()) {
+                    self.trigger_apply_selected_db();
+                }
+            }
+            Pane::Keys => {
+                self.focus_keys();
+                if let Some(bar) = areas.breadcrumb_bar.filter(|bar| bar.contains(Position::new(column, row))) {
+                    let offset = (column - bar.x) as usize;
+                    if let Some(level) = breadcrumb_level_at(&self.breadcrumb_labels(), offset) {
+                        self.jump_to_breadcrumb_level(level);
+                    }
+                    return;
+                }
+                self.click_key(areas, column, row);
+            }
+            Pane::Values => self.focus_values(),
+            Pane::Stats => {}
+        }
+    }
+
+    /// Opens the action menu for the key under the pointer, or for the key in the value
+    /// panel.
+    fn right_click(&mut self, column: u16, row: u16, areas: &PanelAreas) {
+        match areas.pane_at(column, row) {
+            Some(Pane::Keys) if !self.search_state.is_active => {
+                self.focus_keys();
+                if self.tree_view.is_active {
+                    let rows = self.tree_rows();
+                    let Some(index) = list_row_at(areas.key_list, self.key_list_scroll.offset, rows.len(), column, row)
+                    else {
+                        return;
+                    };
+                    self.select_tree_row(&rows[index]);
+                } else {
+                    let (_, len) = self.key_list_position();
+                    let Some(index) = list_row_at(areas.key_list, self.key_list_scroll.offset, len, column, row) else {
+                        return;
+                    };
+                    self.selected_visible_key_index = index;
+                }
+                self.open_action_menu();
+            }
+            Some(Pane::Values) => {
+                self.focus_values();
+                self.open_action_menu();
+            }
+            _ => {}
+        }
+    }
+
+    fn click_key(&mut self, areas: &PanelAreas, column: u16, row: u16) {
+        let search = self.search_state.is_active;
+        if !search && self.tree_view.is_active {
+            self.click_tree_row(areas, column, row);
+            return;
+        }
+        let (_, len) = self.key_list_position();
+        let Some(index) = list_row_at(areas.key_list, self.key_list_scroll.offset, len, column, row) else {
+            return;
+        };
+        let double = self.clicks.register(Pane::Keys, index, Instant::now());
+        if search {
+            self.search_state.selected_index = index;
+            if double {
+                self.activate_selected_filtered_key();
+            }
+            return;
+        }
+        self.selected_visible_key_index = index;
+        if double {
+            self.activate_selected_key();
+        } else {
+            self.auto_preview_current_key();
+        }
+    }
+
+    /// Selects the clicked tree row; a double click toggles a folder or opens a key.
+    fn click_tree_row(&mut self, areas: &PanelAreas, column: u16, row: u16) {
+        let rows = self.tree_rows();
+        let Some(index) = list_row_at(areas.key_list, self.key_list_scroll.offset, rows.len(), column, row) else {
+            return;
+        };
+        let double = self.clicks.register(Pane::Keys, index, Instant::now());
+        self.select_tree_row(&rows[index]);
+        if double && self.toggle_tree_folder() {
+            return;
+        }
+        if double {
+            self.activate_selected_key();
+        } else {
+            self.auto_preview_current_key();
+        }
+    }
+}
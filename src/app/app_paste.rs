@@ -0,0 +1,91 @@
+use crossclip::{Clipboard, SystemClipboard};
+use redis::aio::MultiplexedConnection;
+use tokio::task;
+
+use super::reproduce::{parse_paste, CommandArgs};
+use super::worker::WorkerRequest;
+use super::App;
+
+/// The key a clipboard paste recreates, its commands and the type of the key it would
+/// overwrite.
+pub type PastedKey = (String, Vec<CommandArgs>, Option<String>);
+
+impl App {
+    /// `V`: previews the key held in the clipboard, as copied with `J` or `c`.
+    pub fn open_paste(&mut self) {
+        if self.refuse_write("pastes") {
+            return;
+        }
+        let Some(con) = self.redis.connection.clone() else {
+            self.notifications.error("Not connected.");
+            return;
+        };
+        self.submit(WorkerRequest::ReadPaste(con));
+    }
+
+    pub(super) fn apply_read_paste(&mut self, read: Result<PastedKey, String>) {
+        match read {
+            Ok((key, commands, existing_type)) => self.paste_preview.open(key, commands, existing_type),
+            Err(e) => self.notifications.error(e),
+        }
+    }
+
+    /// Runs the previewed commands in one MULTI/EXEC and jumps to the created key.
+    pub fn confirm_paste(&mut self) {
+        if !self.paste_preview.is_active {
+            return;
+        }
+        let preview = std::mem::take(&mut self.paste_preview);
+        let Some(con) = self.writer("pastes") else {
+            return;
+        };
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for args in &preview.commands {
+            let mut cmd = redis::cmd(&String::from_utf8_lossy(&args[0]));
+            for arg in &args[1..] {
+                cmd.arg(arg.as_slice());
+            }
+            pipe.add_command(cmd);
+        }
+        self.submit(WorkerRequest::ApplyPaste {
+            con,
+            pipe,
+            key: preview.key,
+            overwrites: preview.existing_type.is_some(),
+        });
+    }
+
+    pub(super) fn apply_paste(&mut self, key: String, overwrites: bool, result: Result<(), String>) {
+        if let Err(e) = result {
+            self.notifications.error(format!("Paste failed: {}", e));
+            return;
+        }
+        let verb = if overwrites { "Overwrote" } else { "Created" };
+        self.notifications.success(format!("{} '{}' from the clipboard.", verb, key));
+        self.type_map_mut().remove(&key);
+        self.key_cache.invalidate(&key);
+        self.refresh_key_tree();
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+}
+
+/// Parses the clipboard and looks up the type of the key it would overwrite.
+pub(super) async fn read_paste(mut con: MultiplexedConnection) -> Result<PastedKey, String> {
+    let text = match task::spawn_blocking(|| SystemClipboard::new()?.get_string_contents()).await {
+        Ok(Ok(text)) => text,
+        Ok(Err(e)) => return Err(format!("Failed to access clipboard: {}", e)),
+        Err(e) => return Err(format!("Clipboard task failed: {}", e)),
+    };
+    let (key, commands) = parse_paste(&text)?;
+    let existing_type = match redis::cmd("TYPE").arg(&key).query_async::<String>(&mut con).await {
+        Ok(key_type) if key_type != "none" => Some(key_type),
+        _ => None,
+    };
+    Ok((key, commands, existing_type))
+}
+
+pub(super) async fn run_paste(mut con: MultiplexedConnection, pipe: redis::Pipeline) -> Result<(), String> {
+    pipe.query_async::<redis::Value>(&mut con).await.map(drop).map_err(|e| e.to_string())
+}
@@ -0,0 +1,99 @@
+use super::app_clipboard::copy_to_clipboard;
+use super::prefix_walk::{PrefixWalk, PrefixWalkEvent, WalkPurpose};
+use super::state_folder_copy::key_name_list;
+use super::App;
+
+impl App {
+    /// Starts listing the keys beneath `prefixes` on the read connection. Only one walk
+    /// runs at a time; returns false after an error toast when it cannot start.
+    pub(super) fn start_prefix_walk(&mut self, label: String, prefixes: Vec<String>, purpose: WalkPurpose) -> bool {
+        if let Some(running) = &self.prefix_walk {
+            self.notifications.error(format!("Still listing {}; Esc stops it.", running.label));
+            return false;
+        }
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("No Redis connection available.");
+            return false;
+        };
+        self.prefix_walk = Some(PrefixWalk::spawn(con, self.key_delimiters.clone(), label, prefixes, purpose));
+        true
+    }
+
+    /// Counts the pages found since the last frame into the delete preview or the names
+    /// to copy, and finishes the walk once it is done.
+    pub fn poll_prefix_walk(&mut self) {
+        let Some(walk) = &mut self.prefix_walk else {
+            return;
+        };
+        let mut outcome = None;
+        for event in walk.drain() {
+            match event {
+                PrefixWalkEvent::Keys(keys) => {
+                    walk.found += keys.len();
+                    match &mut walk.purpose {
+                        WalkPurpose::DeletePreview => {
+                            if let Some(preview) = &mut self.delete_dialog.preview {
+                                keys.iter().for_each(|key| preview.add(key, &self.key_delimiters));
+                            }
+                        }
+                        WalkPurpose::CopyKeyNames { keys: names } => names.extend(keys),
+                    }
+                }
+                PrefixWalkEvent::Error(e) => outcome = Some(Err(e)),
+                PrefixWalkEvent::Done => outcome = Some(Ok(())),
+            }
+        }
+        let Some(outcome) = outcome else {
+            return;
+        };
+        let Some(mut walk) = self.prefix_walk.take() else {
+            return;
+        };
+        let purpose = std::mem::replace(&mut walk.purpose, WalkPurpose::DeletePreview);
+        match (outcome, purpose) {
+            (Err(e), WalkPurpose::DeletePreview) => {
+                self.mark_delete_preview_stopped();
+                self.notifications.error(e);
+            }
+            (Err(e), WalkPurpose::CopyKeyNames { .. }) => {
+                self.notifications.error(format!("{}; nothing was copied.", e));
+            }
+            (Ok(()), WalkPurpose::DeletePreview) => {}
+            (Ok(()), WalkPurpose::CopyKeyNames { keys }) => {
+                if keys.is_empty() {
+                    self.notifications.info(format!("No {} to copy.", walk.label));
+                    return;
+                }
+                let list = key_name_list(keys);
+                let what = format!("{} {}", list.lines().count(), walk.label);
+                copy_to_clipboard(self, list, format!("Copied {} to clipboard!", what));
+            }
+        }
+    }
+
+    /// Esc while keys are listed: a delete preview keeps what it counted so far, a copy
+    /// is abandoned. Returns whether a walk was running.
+    pub fn stop_prefix_walk(&mut self) -> bool {
+        if self.prefix_walk.is_none() {
+            return false;
+        }
+        self.poll_prefix_walk();
+        let Some(walk) = self.prefix_walk.take() else {
+            return true;
+        };
+        match walk.purpose {
+            WalkPurpose::DeletePreview => self.mark_delete_preview_stopped(),
+            WalkPurpose::CopyKeyNames { .. } => self.notifications.info(format!(
+                "Stopped listing {} after {} key(s); nothing was copied.",
+                walk.label, walk.found
+            )),
+        }
+        true
+    }
+
+    fn mark_delete_preview_stopped(&mut self) {
+        if let Some(preview) = &mut self.delete_dialog.preview {
+            preview.stopped = true;
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::probabilistic::ProbabilisticKind;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    /// `e` on a RedisBloom key: prompts for inputs to check. All the checks are reads.
+    pub fn open_probe_prompt(&mut self) {
+        if self.value_viewer.probabilistic.is_none() {
+            self.notifications
+                .info("Existence checks work on Bloom, Cuckoo, Top-K and t-digest values.");
+            return;
+        }
+        self.value_viewer.probe_input = Some(String::new());
+        self.value_viewer.probe_cursor = 0;
+    }
+
+    /// Runs the check command of the shown key on the whitespace-separated inputs.
+    pub fn submit_probe(&mut self) {
+        let Some(input) = self.value_viewer.probe_input.take() else {
+            return;
+        };
+        let Some(key) = self.value_viewer.active_leaf_key_name.clone() else {
+            return;
+        };
+        let inputs: Vec<String> = input.split_whitespace().map(str::to_string).collect();
+        if inputs.is_empty() {
+            return;
+        }
+        let Some(kind) = self.value_viewer.probabilistic.as_ref().map(|view| view.kind) else {
+            return;
+        };
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected.");
+            return;
+        };
+        self.submit(WorkerRequest::Probe { con, key, kind, inputs });
+    }
+
+    /// Lists the answers of a check under the INFO of the key it was run on.
+    pub(super) fn apply_probe(
+        &mut self,
+        key: String,
+        kind: ProbabilisticKind,
+        inputs: Vec<String>,
+        reply: Result<Value, String>,
+    ) {
+        let reply = match reply {
+            Ok(reply) => reply,
+            Err(e) => {
+                self.notifications.error(format!("{} failed: {}", kind.check_command(), e));
+                return;
+            }
+        };
+        if self.value_viewer.active_leaf_key_name.as_deref() != Some(key.as_str()) {
+            return;
+        }
+        let Some(view) = self.value_viewer.probabilistic.as_mut() else {
+            return;
+        };
+        let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+        view.set_check(&inputs, &reply);
+        self.value_viewer.selected_key_value = Some(view.text());
+        self.value_viewer.update_current_display_value();
+    }
+}
+
+/// Asks a RedisBloom key whether it holds each of `inputs`.
+pub(super) async fn probe(
+    mut con: MultiplexedConnection,
+    key: &str,
+    kind: ProbabilisticKind,
+    inputs: &[String],
+) -> Result<Value, String> {
+    redis::cmd(kind.check_command())
+        .arg(key)
+        .arg(inputs)
+        .query_async::<Value>(&mut con)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,66 @@
+use super::pubsub::{split_subscriptions, PubSubEvent, PubSubListener};
+use super::state_pubsub::PubSubPrompt;
+use super::App;
+
+impl App {
+    pub fn open_pubsub(&mut self) {
+        self.pubsub.is_active = true;
+        if self.pubsub.listener.is_none() {
+            self.pubsub.open_prompt(PubSubPrompt::Subscribe);
+        }
+    }
+
+    pub fn submit_pubsub_prompt(&mut self) {
+        let Some(prompt) = self.pubsub.prompt.take() else {
+            return;
+        };
+        let input = std::mem::take(&mut self.pubsub.input);
+        match prompt {
+            PubSubPrompt::Filter => {
+                self.pubsub.filter = input.trim().to_string();
+                self.pubsub.scroll = 0;
+            }
+            PubSubPrompt::Subscribe => self.subscribe(&input),
+        }
+    }
+
+    /// Replaces the current subscriptions with the channels and patterns in `input`.
+    fn subscribe(&mut self, input: &str) {
+        let (channels, patterns) = split_subscriptions(input);
+        if channels.is_empty() && patterns.is_empty() {
+            return;
+        }
+        let Some(client) = self.redis.client.clone() else {
+            self.notifications.error("Not connected. Cannot subscribe.".to_string());
+            return;
+        };
+        self.pubsub.listener = Some(PubSubListener::spawn(client, channels, patterns));
+    }
+
+    pub fn unsubscribe(&mut self) {
+        if self.pubsub.listener.take().is_some() {
+            self.notifications.success("Unsubscribed from all channels.".to_string());
+        }
+    }
+
+    /// Collects messages from the subscriber, even while the panel is closed.
+    pub fn poll_pubsub(&mut self) {
+        let Some(listener) = &mut self.pubsub.listener else {
+            return;
+        };
+        let mut messages = Vec::new();
+        for event in listener.drain() {
+            match event {
+                PubSubEvent::Message(message) => messages.push(message),
+                PubSubEvent::Error(message) => {
+                    self.notifications.info(message);
+                    self.pubsub.listener = None;
+                    break;
+                }
+            }
+        }
+        if !messages.is_empty() {
+            self.pubsub.push_messages(messages);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use redis::aio::MultiplexedConnection;
+
+use super::key_sampler::{describe_keys, KeySample};
+use super::state_random_sample::distinct_keys;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn open_random_sample(&mut self) {
+        self.random_sample.is_active = true;
+        self.request_random_sample();
+    }
+
+    pub fn request_random_sample(&mut self) {
+        let Some(con) = self.redis.reader() else {
+            self.random_sample.loading = false;
+            self.random_sample.error = Some("Not connected".to_string());
+            return;
+        };
+        self.random_sample.loading = true;
+        let size = self.random_sample.size;
+        self.submit(WorkerRequest::SampleRandomKeys { con, size });
+    }
+
+    pub(super) fn apply_random_sample(&mut self, sampled: Result<Vec<KeySample>, String>) {
+        match sampled {
+            Ok(samples) => self.random_sample.set_samples(samples),
+            Err(e) => {
+                self.random_sample.loading = false;
+                self.random_sample.error = Some(format!("RANDOMKEY failed: {}", e));
+            }
+        }
+    }
+
+    /// Jumps to the selected sampled key in the tree.
+    pub fn open_selected_random_key(&mut self) {
+        let Some(key) = self.random_sample.selected_key().map(str::to_string) else {
+            return;
+        };
+        self.random_sample.is_active = false;
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+}
+
+/// Draws `size` RANDOMKEYs in one pipeline, then describes them like the keyspace analysis does.
+pub(super) async fn sample_random_keys(mut con: MultiplexedConnection, size: usize) -> Result<Vec<KeySample>, String> {
+    let mut pipe = redis::pipe();
+    for _ in 0..size {
+        pipe.cmd("RANDOMKEY");
+    }
+    let replies = pipe.query_async::<Vec<Option<String>>>(&mut con).await.map_err(|e| e.to_string())?;
+    describe_keys(&mut con, distinct_keys(replies)).await.map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use redis::aio::MultiplexedConnection;
+
+use super::App;
+
+impl App {
+    /// Whether the current profile has `read_only = true`.
+    pub fn is_read_only(&self) -> bool {
+        self.profiles
+            .get(self.current_profile_index)
+            .and_then(|profile| profile.read_only)
+            .unwrap_or(false)
+    }
+
+    /// On a read-only profile, shows that `changes` are disabled and returns true.
+    pub(super) fn refuse_write(&mut self, changes: &str) -> bool {
+        if !self.is_read_only() {
+            return false;
+        }
+        self.notifications.error(format!(
+            "Profile '{}' is read-only; {} are disabled.",
+            self.current_profile_name(),
+            changes
+        ));
+        true
+    }
+
+    /// Primary connection for a change made from the UI. Every write takes its connection
+    /// from here, so a read-only profile refuses it with an error naming `changes`.
+    pub(super) fn writer(&mut self, changes: &str) -> Option<MultiplexedConnection> {
+        if self.refuse_write(changes) {
+            return None;
+        }
+        let connection = self.redis.connection.clone();
+        if connection.is_none() {
+            self.notifications.error("No Redis connection available.");
+        }
+        connection
+    }
+}
@@ -0,0 +1,183 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::state_redisearch::{
+    parse_index_info, parse_index_list, parse_query_input, parse_search_reply, IndexInfo, SearchResults,
+    SEARCH_PAGE_SIZE,
+};
+use super::worker::WorkerRequest;
+use super::App;
+
+/// A query page the worker runs: `command` on `index`, for the rows from `offset`.
+pub struct SearchQuery {
+    pub command: &'static str,
+    pub index: String,
+    pub query: String,
+    pub args: Vec<String>,
+    pub offset: usize,
+}
+
+impl App {
+    pub fn open_redisearch(&mut self) {
+        self.redisearch.open();
+        self.fetch_search_indexes();
+    }
+
+    /// Lists the indexes with FT._LIST, then loads FT.INFO for the selected one. An error
+    /// here usually means the search module is not loaded.
+    pub fn fetch_search_indexes(&mut self) {
+        let Some(con) = self.redis.reader() else {
+            self.redisearch.message = Some("Not connected".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::FetchSearchIndexes(con));
+    }
+
+    pub(super) fn apply_search_indexes(&mut self, indexes: Result<Vec<String>, String>) {
+        match indexes {
+            Ok(indexes) => {
+                self.redisearch.set_indexes(indexes);
+                if self.redisearch.indexes.is_empty() {
+                    self.redisearch.message = Some("No search indexes (create one with FT.CREATE).".to_string());
+                    return;
+                }
+                self.redisearch.message = None;
+                self.fetch_search_index_info();
+            }
+            Err(e) => {
+                self.redisearch.set_indexes(Vec::new());
+                self.redisearch.message = Some(format!("(error) FT._LIST: {} (is the search module loaded?)", e));
+            }
+        }
+    }
+
+    pub fn fetch_search_index_info(&mut self) {
+        let Some(index) = self.redisearch.selected_index_name().map(str::to_string) else {
+            return;
+        };
+        let Some(con) = self.redis.reader() else {
+            return;
+        };
+        self.submit(WorkerRequest::FetchSearchIndexInfo { con, index });
+    }
+
+    pub(super) fn apply_search_index_info(&mut self, index: String, info: Result<IndexInfo, String>) {
+        if self.redisearch.selected_index_name() != Some(index.as_str()) {
+            return;
+        }
+        match info {
+            Ok(info) => self.redisearch.info = Some(info),
+            Err(e) => self.redisearch.message = Some(format!("(error) FT.INFO {}: {}", index, e)),
+        }
+    }
+
+    pub fn select_search_index(&mut self, forward: bool) {
+        if self.redisearch.select_index(forward) {
+            self.fetch_search_index_info();
+        }
+    }
+
+    /// Runs the typed query from the first page.
+    pub fn submit_search_query(&mut self) {
+        let state = &mut self.redisearch;
+        state.editing = false;
+        state.query = Some((state.mode, std::mem::take(&mut state.input)));
+        state.offset = 0;
+        self.run_search_query();
+    }
+
+    pub fn search_page(&mut self, forward: bool) {
+        let state = &mut self.redisearch;
+        if forward {
+            if !state.has_next_page() {
+                return;
+            }
+            state.offset += SEARCH_PAGE_SIZE;
+        } else {
+            if state.offset == 0 {
+                return;
+            }
+            state.offset = state.offset.saturating_sub(SEARCH_PAGE_SIZE);
+        }
+        self.run_search_query();
+    }
+
+    /// Runs the current query for the page at `offset`, appending `LIMIT offset count`
+    /// after the options or pipeline steps.
+    pub fn run_search_query(&mut self) {
+        let Some((mode, input)) = self.redisearch.query.clone() else {
+            return;
+        };
+        let Some(index) = self.redisearch.selected_index_name().map(str::to_string) else {
+            return;
+        };
+        let Some(con) = self.redis.reader() else {
+            self.redisearch.message = Some("Not connected".to_string());
+            return;
+        };
+        let (query, args) = parse_query_input(&input);
+        let query = SearchQuery {
+            command: mode.command(),
+            index,
+            query: query.to_string(),
+            args: args.into_iter().map(str::to_string).collect(),
+            offset: self.redisearch.offset,
+        };
+        self.submit(WorkerRequest::RunSearchQuery { con, query });
+    }
+
+    pub(super) fn apply_search_results(&mut self, query: SearchQuery, results: Result<SearchResults, String>) {
+        // The index or the page changed while the query ran
+        if self.redisearch.selected_index_name() != Some(query.index.as_str()) || self.redisearch.offset != query.offset
+        {
+            return;
+        }
+        match results {
+            Ok(results) => {
+                self.redisearch.message = results.rows.is_empty().then(|| "No results.".to_string());
+                self.redisearch.results_focused = !results.rows.is_empty();
+                self.redisearch.results = Some(results);
+                self.redisearch.selected_row = 0;
+            }
+            Err(e) => {
+                self.redisearch.results = None;
+                self.redisearch.message = Some(format!("(error) {}: {}", query.command, e));
+            }
+        }
+    }
+
+    /// Closes the panel and opens the selected FT.SEARCH document in the key and value
+    /// panels, the way a bookmark does.
+    pub fn open_search_document(&mut self) {
+        let Some(key) = self.redisearch.selected_document().map(str::to_string) else {
+            self.redisearch.message = Some("Aggregated rows have no document to open.".to_string());
+            return;
+        };
+        self.redisearch.close();
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+}
+
+pub(super) async fn list_indexes(mut con: MultiplexedConnection) -> Result<Vec<String>, String> {
+    let reply = redis::cmd("FT._LIST").query_async::<Value>(&mut con).await;
+    reply.map(|reply| parse_index_list(&reply)).map_err(|e| e.to_string())
+}
+
+pub(super) async fn index_info(mut con: MultiplexedConnection, index: &str) -> Result<IndexInfo, String> {
+    let reply = redis::cmd("FT.INFO").arg(index).query_async::<Value>(&mut con).await;
+    reply.map(|reply| parse_index_info(&reply)).map_err(|e| e.to_string())
+}
+
+pub(super) async fn search(mut con: MultiplexedConnection, query: &SearchQuery) -> Result<SearchResults, String> {
+    let reply = redis::cmd(query.command)
+        .arg(&query.index)
+        .arg(&query.query)
+        .arg(&query.args)
+        .arg("LIMIT")
+        .arg(query.offset)
+        .arg(SEARCH_PAGE_SIZE)
+        .query_async::<Value>(&mut con)
+        .await;
+    reply.map(|reply| parse_search_reply(&reply)).map_err(|e| e.to_string())
+}
@@ -0,0 +1,50 @@
+use super::key_scan::DEFAULT_SCAN_COUNT;
+use super::state_scan_presets::{picker_presets, ScanFilter};
+use super::App;
+
+impl App {
+    /// COUNT of presets that leave it unset: the connected profile's.
+    fn profile_scan_count(&self) -> usize {
+        self.profiles
+            .get(self.current_profile_index)
+            .and_then(|profile| profile.scan_count)
+            .unwrap_or(DEFAULT_SCAN_COUNT)
+    }
+
+    /// `v`: lists the scan presets of the connected profile.
+    pub fn open_scan_presets(&mut self) {
+        let configured = self
+            .profiles
+            .get(self.current_profile_index)
+            .map(|profile| profile.scan_presets.as_slice())
+            .unwrap_or_default();
+        let current = ScanFilter {
+            pattern: self.scan_pattern.clone(),
+            key_type: self.scan_type,
+            count: self.scan_count,
+        };
+        self.scan_presets.open(picker_presets(configured), self.profile_scan_count(), &current);
+    }
+
+    /// Applies the preset at `index` (the selected one when `None`) and reloads the key tree.
+    pub fn apply_scan_preset(&mut self, index: Option<usize>) {
+        let index = index.unwrap_or(self.scan_presets.selected);
+        let Some(preset) = self.scan_presets.presets.get(index) else {
+            return;
+        };
+        let filter = match ScanFilter::from_preset(preset, self.profile_scan_count()) {
+            Ok(filter) => filter,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        let name = preset.name.clone();
+        self.scan_presets.close();
+        self.scan_pattern = filter.pattern;
+        self.scan_type = filter.key_type;
+        self.scan_count = filter.count;
+        self.fetch_keys_and_build_tree();
+        self.notifications.info(format!("Scan filter: {}", name));
+    }
+}
@@ -0,0 +1,86 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::state_script_runner::ScriptMode;
+use super::worker::WorkerRequest;
+use super::App;
+use crate::command::render_reply;
+
+impl App {
+    pub fn open_script_runner(&mut self) {
+        self.script_runner.open();
+    }
+
+    /// Runs the script with EVAL, or SCRIPT LOAD + EVALSHA, and renders the reply.
+    pub fn run_script(&mut self) {
+        let Some(con) = self.writer("scripts") else {
+            return;
+        };
+        let state = &mut self.script_runner;
+        state.result_scroll = 0;
+        if state.script.trim().is_empty() {
+            state.result = Some("Script is empty.".to_string());
+            return;
+        }
+        let request = WorkerRequest::RunScript {
+            con,
+            script: state.script.clone(),
+            mode: state.mode,
+            keys: state.key_names(),
+            args: state.arg_values(),
+        };
+        self.submit(request);
+    }
+
+    pub(super) fn apply_script_result(&mut self, result: String, sha: Option<String>) {
+        let state = &mut self.script_runner;
+        state.result = Some(result);
+        state.result_scroll = 0;
+        if sha.is_some() {
+            state.sha = sha;
+        }
+        // The script may have written any key
+        self.key_cache.clear();
+    }
+}
+
+/// Runs a script and renders its reply, with the SHA1 `SCRIPT LOAD` returned for EVALSHA.
+pub(super) async fn run_script(
+    mut con: MultiplexedConnection,
+    script: &str,
+    mode: ScriptMode,
+    keys: &[String],
+    args: &[String],
+) -> (String, Option<String>) {
+    let mut lines = Vec::new();
+    let mut sha = None;
+    let mut cmd = match mode {
+        ScriptMode::Eval => {
+            let mut cmd = redis::cmd("EVAL");
+            cmd.arg(script);
+            cmd
+        }
+        ScriptMode::EvalSha => {
+            let loaded = redis::cmd("SCRIPT")
+                .arg("LOAD")
+                .arg(script)
+                .query_async::<String>(&mut con)
+                .await;
+            let loaded = match loaded {
+                Ok(loaded) => loaded,
+                Err(e) => return (format!("(error) SCRIPT LOAD: {}", e), None),
+            };
+            lines.push(format!("SHA1 {}", loaded));
+            let mut cmd = redis::cmd("EVALSHA");
+            cmd.arg(&loaded);
+            sha = Some(loaded);
+            cmd
+        }
+    };
+    cmd.arg(keys.len()).arg(keys).arg(args);
+    match cmd.query_async::<Value>(&mut con).await {
+        Ok(value) => lines.extend(render_reply(&value)),
+        Err(e) => lines.push(format!("(error) {}", e)),
+    }
+    (lines.join("\n"), sha)
+}
@@ -0,0 +1,238 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::key_scan::{KeyScan, KeyScanEvent};
+use super::latency_tracker::OperationClass;
+use super::value_fetch::ValuePurpose;
+use super::value_grep::{ValueGrep, ValueGrepEvent};
+use super::{App, MultiplexedConnection};
+use crate::search::{redis_type_name, split_prefixed_token, split_type_filter, SearchJob, SearchMode};
+
+/// Server-side search stops scanning once this many keys matched.
+const MAX_SERVER_SEARCH_RESULTS: usize = 10_000;
+/// Below this many loaded keys, searches run inline on every keystroke.
+const BACKGROUND_SEARCH_THRESHOLD: usize = 20_000;
+/// Quiet period after a keystroke before a background search starts.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+impl App {
+    pub fn enter_search_mode(&mut self) {
+        self.search_state.enter();
+        self.is_key_view_focused = true;
+        self.is_value_view_focused = false;
+        self.search_state
+            .update_filtered_keys(&self.raw_keys, &self.type_map);
+    }
+
+    pub fn exit_search_mode(&mut self) {
+        self.search_state.exit();
+        self.search_scan = None;
+        self.value_grep = None;
+        self.search_job = None;
+        self.search_due = None;
+    }
+
+    /// Re-runs the local search. Large keyspaces are searched on a background task
+    /// after a short debounce, cancelling any search for an older query.
+    pub fn update_filtered_keys(&mut self) {
+        if !self.search_state.matches_locally() {
+            return;
+        }
+        self.search_job = None;
+        if self.raw_keys.len() < BACKGROUND_SEARCH_THRESHOLD || self.search_state.query.is_empty() {
+            self.search_due = None;
+            self.search_state
+                .update_filtered_keys(&self.raw_keys, &self.type_map);
+            return;
+        }
+        self.search_due = Some(Instant::now() + SEARCH_DEBOUNCE);
+    }
+
+    /// Starts a debounced search once it is due and applies finished results.
+    pub fn poll_search_job(&mut self) {
+        if self.search_due.is_some_and(|due| Instant::now() >= due) {
+            self.search_due = None;
+            self.search_job = Some(SearchJob::spawn(
+                self.search_state.query.clone(),
+                self.search_state.mode,
+                Arc::clone(&self.raw_keys),
+                Arc::clone(&self.type_map),
+            ));
+        }
+        let Some(job) = &mut self.search_job else {
+            return;
+        };
+        if let Some(outcome) = job.poll() {
+            self.search_state.apply_outcome(outcome);
+            self.search_job = None;
+        }
+    }
+
+    pub fn search_pending(&self) -> bool {
+        self.search_job.is_some() || self.search_due.is_some()
+    }
+
+    /// Switches between fuzzy matching of loaded keys and server-side `SCAN MATCH`.
+    pub fn toggle_search_mode(&mut self) {
+        self.search_state.toggle_mode();
+        self.search_scan = None;
+        self.value_grep = None;
+        self.update_filtered_keys();
+    }
+
+    /// Runs the search query as a `SCAN MATCH` glob in the background.
+    pub fn start_server_search(&mut self) {
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected. Cannot search the server.".to_string());
+            return;
+        };
+        if self.search_state.mode == SearchMode::Values {
+            self.start_value_grep(con);
+            return;
+        }
+        let query = self.search_state.query.clone();
+        // `type:<name>` is passed to SCAN's TYPE option
+        let (type_name, pattern) = split_type_filter(&query);
+        let key_type = match type_name.map(redis_type_name).transpose() {
+            Ok(key_type) => key_type,
+            Err(e) => {
+                self.search_state.query_error = Some(e);
+                return;
+            }
+        };
+        let pattern = if pattern.is_empty() { "*".to_string() } else { pattern };
+        self.search_state.query_error = None;
+        self.search_state.begin_server_results(query);
+        self.search_scan = Some(KeyScan::spawn(
+            con,
+            pattern.clone(),
+            pattern,
+            self.scan_count,
+            false,
+            key_type,
+        ));
+    }
+
+    /// Searches values for the query text; a `match:<glob>` token limits which keys are read.
+    fn start_value_grep(&mut self, con: MultiplexedConnection) {
+        let query = self.search_state.query.clone();
+        let (pattern, needle) = split_prefixed_token(&query, "match:");
+        if needle.is_empty() {
+            self.search_state.query_error = Some("nothing to search for".to_string());
+            return;
+        }
+        let pattern = pattern.filter(|p| !p.is_empty()).unwrap_or("*").to_string();
+        self.search_state.query_error = None;
+        self.search_state.begin_server_results(query.clone());
+        self.value_grep = Some(ValueGrep::spawn(con, pattern, needle, self.scan_count));
+    }
+
+    /// Collects hits from the running value search.
+    pub fn poll_value_grep(&mut self) {
+        let Some(grep) = &mut self.value_grep else {
+            return;
+        };
+        for event in grep.drain() {
+            match event {
+                ValueGrepEvent::Progress { scanned, cursor } => {
+                    grep.scanned = scanned;
+                    grep.cursor = cursor;
+                }
+                ValueGrepEvent::Matches(hits) => {
+                    self.search_state.add_value_results(hits, MAX_SERVER_SEARCH_RESULTS);
+                    if self.search_state.filtered_keys.len() >= MAX_SERVER_SEARCH_RESULTS {
+                        self.value_grep = None;
+                        return;
+                    }
+                }
+                ValueGrepEvent::Done => {
+                    self.notifications.info(format!(
+                        "Value search finished: {} matching keys.",
+                        self.search_state.filtered_keys.len()
+                    ));
+                    self.value_grep = None;
+                    return;
+                }
+                ValueGrepEvent::Error(e) => {
+                    self.notifications.error(format!("Value search failed: {}", e));
+                    self.value_grep = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Collects matches from the server-side search scan.
+    pub fn poll_search_scan(&mut self) {
+        let Some(scan) = &mut self.search_scan else {
+            return;
+        };
+        for event in scan.drain() {
+            match event {
+                KeyScanEvent::Total(_) => {}
+                KeyScanEvent::Batch {
+                    keys,
+                    cursor,
+                    elapsed,
+                } => {
+                    self.latency.record(OperationClass::Scan, elapsed);
+                    scan.found += keys.len();
+                    scan.cursor = cursor;
+                    self.search_state.add_server_results(keys, MAX_SERVER_SEARCH_RESULTS);
+                    if self.search_state.filtered_keys.len() >= MAX_SERVER_SEARCH_RESULTS {
+                        self.notifications.info(format!(
+                            "Showing the first {} matches; refine the pattern to see more.",
+                            MAX_SERVER_SEARCH_RESULTS
+                        ));
+                        self.search_scan = None;
+                        return;
+                    }
+                }
+                KeyScanEvent::Done => {
+                    self.search_scan = None;
+                    return;
+                }
+                KeyScanEvent::Error(e) => {
+                    self.notifications.error(format!("Server search failed: {}", e));
+                    self.search_scan = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    pub fn select_next_filtered_key(&mut self) {
+        self.search_state.select_next_filtered();
+    }
+
+    pub fn select_previous_filtered_key(&mut self) {
+        self.search_state.select_previous_filtered();
+    }
+
+    pub fn activate_selected_filtered_key(&mut self) {
+        let activation_info_opt = self.search_state.activate_selected_filtered(
+            &self.key_delimiters,
+            &self.key_tree,
+            &self.raw_keys,
+        );
+
+        if let Some(info) = activation_info_opt {
+            if info.is_folder {
+                self.current_breadcrumb = info.path_segments;
+                self.update_visible_keys();
+                self.start_level_scan();
+                self.clear_selected_key_info_if_not_pinned();
+            } else {
+                self.reveal_key_in_tree(&info.path_segments);
+                self.clear_selected_key_info();
+                self.open_leaf_key(&info.full_key_path, ValuePurpose::Open);
+                self.value_is_pinned = true;
+            }
+            self.exit_search_mode();
+            self.is_key_view_focused = true;
+            self.is_value_view_focused = false;
+        } else {
+            self.exit_search_mode();
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::session::SessionState;
+
+use super::App;
+
+impl App {
+    /// Profile, DB, tree level and selected key to restore on the next launch.
+    pub fn session_state(&self) -> SessionState {
+        let selected_key = self
+            .visible_keys_in_current_view
+            .get(self.selected_visible_key_index)
+            .filter(|(_, is_folder)| !*is_folder)
+            .and_then(|(name, _)| self.leaf_full_name(name));
+        SessionState {
+            profile: Some(self.current_profile_name().to_string()),
+            db: self.selected_db_index,
+            breadcrumb: self.current_breadcrumb.clone(),
+            selected_key,
+        }
+    }
+
+    /// The saved session when it belongs to the profile being connected to; its DB then
+    /// replaces the profile's default one.
+    pub(crate) fn take_session_for_current_profile(&mut self) -> Option<SessionState> {
+        let session = self.restore_session.take()?;
+        if session.profile.as_deref() != Some(self.current_profile_name()) {
+            return None;
+        }
+        self.selected_db_index = session.db;
+        Some(session)
+    }
+
+    /// Goes back to the saved tree level and selects the saved key once its level has
+    /// been scanned.
+    pub(crate) fn apply_session(&mut self, session: SessionState) {
+        self.current_breadcrumb = session.breadcrumb;
+        self.update_visible_keys();
+        self.start_level_scan();
+        let Some(key) = session.selected_key else {
+            return;
+        };
+        let segments = self.key_delimiters.split(&key);
+        let Some((leaf_name, parents)) = segments.split_last() else {
+            return;
+        };
+        if parents != self.current_breadcrumb.as_slice() {
+            return;
+        }
+        let leaf_name = leaf_name.to_string();
+        match self.leaf_index_in_view(&leaf_name) {
+            Some(idx) => self.selected_visible_key_index = idx,
+            None => self.pending_key_selection = Some(leaf_name),
+        }
+    }
+}
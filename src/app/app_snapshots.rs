@@ -0,0 +1,140 @@
+use super::key_sampler::{KeySampler, KeySamplerEvent};
+use super::state_snapshots::{SnapshotDiff, SnapshotJob, SnapshotPurpose};
+use super::timestamp::now_millis;
+use super::App;
+use crate::snapshot::{diff, list_snapshots, DiffKind, KeyspaceSnapshot};
+
+impl App {
+    pub fn open_snapshots(&mut self) {
+        self.snapshots.is_active = true;
+        self.snapshots.diff = None;
+        self.refresh_snapshot_list();
+    }
+
+    fn refresh_snapshot_list(&mut self) {
+        self.snapshots.snapshots = self.snapshots.dir.as_deref().map(list_snapshots).unwrap_or_default();
+        self.snapshots.selected = self.snapshots.selected.min(self.snapshots.snapshots.len().saturating_sub(1));
+    }
+
+    pub fn start_snapshot_name(&mut self) {
+        let name = format!("{}-db{}", self.current_profile_name(), self.selected_db_index);
+        self.snapshots.name_cursor = name.len();
+        self.snapshots.name_input = Some(name);
+    }
+
+    pub fn submit_snapshot_name(&mut self) {
+        let Some(name) = self.snapshots.name_input.take() else {
+            return;
+        };
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            self.snapshots.status = Some("A snapshot needs a name".to_string());
+            return;
+        }
+        let pattern = self.scan_pattern.clone();
+        self.start_snapshot_walk(SnapshotPurpose::Capture(name), pattern);
+    }
+
+    /// Walks the live keyspace with the selected snapshot's pattern and diffs the two.
+    pub fn compare_selected_snapshot(&mut self) {
+        let Some(summary) = self.snapshots.selected_snapshot() else {
+            return;
+        };
+        match KeyspaceSnapshot::load(&summary.path) {
+            Ok(snapshot) => {
+                let pattern = snapshot.pattern.clone();
+                self.start_snapshot_walk(SnapshotPurpose::Compare(snapshot), pattern);
+            }
+            Err(e) => self.snapshots.status = Some(format!("Failed to load snapshot: {}", e)),
+        }
+    }
+
+    fn start_snapshot_walk(&mut self, purpose: SnapshotPurpose, pattern: String) {
+        let Some(con) = self.redis.reader() else {
+            self.snapshots.status = Some("Not connected".to_string());
+            return;
+        };
+        self.snapshots.status = None;
+        self.snapshots.job = Some(SnapshotJob {
+            purpose,
+            sampler: KeySampler::spawn(con, pattern),
+            keys: Default::default(),
+        });
+    }
+
+    pub fn poll_snapshots(&mut self) {
+        let Some(job) = &mut self.snapshots.job else {
+            return;
+        };
+        for event in job.sampler.drain() {
+            match event {
+                KeySamplerEvent::Samples { samples, .. } => job.record(&samples),
+                KeySamplerEvent::Done => {
+                    if let Some(job) = self.snapshots.job.take() {
+                        self.finish_snapshot_job(job);
+                    }
+                    return;
+                }
+                KeySamplerEvent::Error(message) => {
+                    self.snapshots.status = Some(message);
+                    self.snapshots.job = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn finish_snapshot_job(&mut self, job: SnapshotJob) {
+        match job.purpose {
+            SnapshotPurpose::Capture(name) => {
+                let snapshot = KeyspaceSnapshot {
+                    name,
+                    profile: self.current_profile_name().to_string(),
+                    db: self.selected_db_index,
+                    pattern: self.scan_pattern.clone(),
+                    taken_at: now_millis(),
+                    keys: job.keys,
+                };
+                let Some(dir) = self.snapshots.dir.clone() else {
+                    self.snapshots.status = Some("No config directory to save snapshots in".to_string());
+                    return;
+                };
+                self.snapshots.status = Some(match snapshot.save(&dir) {
+                    Ok(path) => format!("Saved {} keys to {}", snapshot.keys.len(), path.display()),
+                    Err(e) => format!("Failed to save snapshot: {}", e),
+                });
+                self.refresh_snapshot_list();
+            }
+            SnapshotPurpose::Compare(snapshot) => {
+                let rows = diff(&snapshot.keys, &job.keys);
+                let count = |kind: DiffKind| rows.iter().filter(|row| row.kind == kind).count();
+                let mut status = format!(
+                    "{} added, {} removed, {} changed since {}",
+                    count(DiffKind::Added),
+                    count(DiffKind::Removed),
+                    count(DiffKind::Changed),
+                    snapshot.name
+                );
+                if snapshot.profile != self.current_profile_name() || snapshot.db != self.selected_db_index {
+                    status.push_str(&format!(" (taken on {} DB {})", snapshot.profile, snapshot.db));
+                }
+                self.snapshots.status = Some(status);
+                self.snapshots.diff = Some(SnapshotDiff {
+                    against: snapshot.name,
+                    rows,
+                    selected: 0,
+                });
+            }
+        }
+    }
+
+    /// Jumps to the selected added or changed key in the tree.
+    pub fn open_selected_diff_key(&mut self) {
+        let Some(key) = self.snapshots.selected_diff_key().map(str::to_string) else {
+            return;
+        };
+        self.snapshots.is_active = false;
+        self.goto_key_prompt = Some(key);
+        self.goto_key();
+    }
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+use std::time::Duration;
+
+use super::latency_tracker::OperationClass;
+use super::redis_client::redacted_url;
+use super::redis_stats::RedisStats;
+use super::state_stats_report::build_stats_report;
+use super::timestamp::now_millis;
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    /// `I` with the stats panel open: reads INFO afresh and shows it as a markdown report.
+    pub fn open_stats_report(&mut self) {
+        self.stats_report.is_active = true;
+        self.stats_report.scroll = 0;
+        self.stats_report.report = "Reading INFO...".to_string();
+        let Some(con) = self.redis.connection.clone() else {
+            self.stats_report.report = "(error) No Redis connection available for INFO command.".to_string();
+            return;
+        };
+        self.submit(WorkerRequest::FetchStatsReport(con));
+    }
+
+    pub(super) fn apply_stats_report(&mut self, result: Result<String, String>, elapsed: Duration) {
+        self.latency.record(OperationClass::Info, elapsed);
+        let info = match result {
+            Ok(info) => info,
+            Err(e) => {
+                self.stats_report.report = format!("(error) {}", e);
+                return;
+            }
+        };
+        let stats = RedisStats::from_info_string(&info);
+        let target = match self.profiles.get(self.current_profile_index) {
+            Some(profile) => format!("{} ({}, db {})", profile.name, redacted_url(&profile.url), self.redis.db_index),
+            None => format!("db {}", self.redis.db_index),
+        };
+        self.stats_report.report = build_stats_report(&target, now_millis() / 1000, &stats, &self.latency, &info);
+        self.db_key_counts = Some(stats.keyspace.clone());
+        self.redis_stats = Some(stats);
+    }
+
+    /// `s` in the stats report: writes it to a file in the working directory.
+    pub fn save_stats_report(&mut self) {
+        match self.stats_report.save(Path::new("."), now_millis()) {
+            Ok(path) => self.notifications.success(format!("Saved stats report to {}", path.display())),
+            Err(e) => self.notifications.error(format!("Failed to save stats report: {}", e)),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use super::stream_tail::{StreamTail, StreamTailEvent};
+use super::App;
+
+/// Oldest tailed stream entries are dropped beyond this many to bound memory.
+const MAX_TAILED_STREAM_ENTRIES: usize = 5000;
+
+impl App {
+    pub fn toggle_stream_follow(&mut self) {
+        if self.stream_tail.take().is_some() {
+            self.notifications.info("Stopped following stream.".to_string());
+            return;
+        }
+        let Some(key_name) = self.value_viewer.active_leaf_key_name.clone() else {
+            self.notifications.info("Select a stream key to follow.".to_string());
+            return;
+        };
+        if self.value_viewer.selected_key_type.as_deref() != Some("STREAM") {
+            self.notifications.info("Follow mode is available for stream keys only.".to_string());
+            return;
+        }
+        let Some(client) = self.redis.client.clone() else {
+            self.notifications.error("Not connected. Cannot follow stream.".to_string());
+            return;
+        };
+        let last_id = self
+            .value_viewer
+            .selected_key_value_stream
+            .as_ref()
+            .and_then(|entries| entries.last())
+            .map_or_else(|| "$".to_string(), |entry| entry.id.clone());
+        self.stream_tail = Some(StreamTail::spawn(client, self.redis.db_index, key_name, last_id));
+    }
+
+    pub fn toggle_stream_pause(&mut self) {
+        if let Some(tail) = &mut self.stream_tail {
+            tail.paused = !tail.paused;
+        }
+    }
+
+    /// Appends entries delivered by the stream tail. Stops tailing once the
+    /// viewed key changes or the background read fails.
+    pub fn poll_stream_tail(&mut self) {
+        let Some(tail) = &mut self.stream_tail else {
+            return;
+        };
+        if self.value_viewer.active_leaf_key_name.as_deref() != Some(tail.key.as_str()) {
+            self.stream_tail = None;
+            return;
+        }
+        let mut new_entries = Vec::new();
+        for event in tail.drain() {
+            match event {
+                StreamTailEvent::Entries(entries) => new_entries.extend(entries),
+                StreamTailEvent::Error(message) => {
+                    self.notifications.info(message);
+                    self.stream_tail = None;
+                    break;
+                }
+            }
+        }
+        if new_entries.is_empty() {
+            return;
+        }
+
+        let viewer = &mut self.value_viewer;
+        let row_count = viewer.displayed_value_lines.as_ref().map_or(0, Vec::len);
+        let was_at_end = viewer.selected_value_sub_index + 1 >= row_count;
+        let selected = viewer.selected_value_sub_index;
+        let entries = viewer.selected_key_value_stream.get_or_insert_with(Vec::new);
+        entries.extend(new_entries);
+        let overflow = entries.len().saturating_sub(MAX_TAILED_STREAM_ENTRIES);
+        entries.drain(..overflow);
+        viewer.update_current_display_value();
+        let row_count = viewer.displayed_value_lines.as_ref().map_or(0, Vec::len);
+        viewer.selected_value_sub_index = if was_at_end {
+            row_count.saturating_sub(1)
+        } else {
+            selected.min(row_count.saturating_sub(1))
+        };
+    }
+}
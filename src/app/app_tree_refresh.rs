@@ -0,0 +1,188 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::app_key_tree::{folder_entry, level_mut};
+use super::key_scan::{KeyScan, KeyScanEvent, TreeRefresh};
+use super::{App, KeyTreeNode};
+
+impl App {
+    /// F5: rescans the level being viewed in the background and forgets cached values.
+    pub fn refresh_key_tree(&mut self) {
+        self.key_cache.clear();
+        self.start_tree_refresh(true);
+    }
+
+    fn start_tree_refresh(&mut self, manual: bool) {
+        self.last_tree_refresh = Instant::now();
+        let prefix = self.current_level_prefix();
+        if self.key_scan.is_some() {
+            if manual {
+                self.notifications.info("Keys are still loading.".to_string());
+            }
+            return;
+        }
+        if !self.loaded_prefixes.contains(&prefix) {
+            // A cancelled scan left the level partial; load it from scratch instead
+            if manual {
+                self.start_level_scan();
+            }
+            return;
+        }
+        let Some(con) = self.redis.reader() else {
+            if manual {
+                self.notifications.error("Not connected. Cannot refresh keys.".to_string());
+            }
+            return;
+        };
+        let pattern = self.level_scan_pattern(&prefix);
+        let scan = KeyScan::spawn(con, prefix, pattern, self.scan_count, false, self.scan_type);
+        self.tree_refresh = Some(TreeRefresh {
+            scan,
+            keys: Vec::new(),
+            manual,
+        });
+        if manual {
+            self.notifications.info("Refreshing keys...".to_string());
+        }
+    }
+
+    /// Starts the periodic rescan when it is due and merges a finished one into the tree.
+    pub fn poll_tree_refresh(&mut self) {
+        let Some(refresh) = &mut self.tree_refresh else {
+            let interval = self.settings.auto_refresh_secs;
+            if interval > 0
+                && self.redis.connection.is_some()
+                && self.last_tree_refresh.elapsed() >= Duration::from_secs(interval)
+            {
+                self.start_tree_refresh(false);
+            }
+            return;
+        };
+        let mut finished = false;
+        for event in refresh.scan.drain() {
+            match event {
+                KeyScanEvent::Batch { mut keys, .. } => refresh.keys.append(&mut keys),
+                KeyScanEvent::Done => finished = true,
+                KeyScanEvent::Total(_) => {}
+                KeyScanEvent::Error(e) => {
+                    self.notifications.error(format!("Refresh failed: {}", e));
+                    self.tree_refresh = None;
+                    return;
+                }
+            }
+        }
+        if !finished {
+            return;
+        }
+        let Some(TreeRefresh { scan, mut keys, manual }) = self.tree_refresh.take() else {
+            return;
+        };
+        self.last_tree_refresh = Instant::now();
+        let prefix = scan.prefix.clone();
+        // The level may have been reset by a DB switch or a new scan pattern meanwhile
+        if !self.loaded_prefixes.contains(&prefix) {
+            return;
+        }
+        self.keep_scan_matches(&prefix, &mut keys);
+        let (added, removed) = self.merge_refreshed_level(&prefix, &keys);
+        if prefix == self.current_level_prefix() {
+            self.update_visible_keys_keeping_selection();
+        }
+        if manual || added + removed > 0 {
+            self.notifications.success(format!("Keys refreshed: {} added, {} removed.", added, removed));
+        }
+    }
+
+    /// Replaces the level at `prefix` with the keys of a fresh SCAN, keeping the subtrees
+    /// of folders that still exist. Returns how many entries were added and removed.
+    pub(crate) fn merge_refreshed_level(&mut self, prefix: &str, keys: &[String]) -> (usize, usize) {
+        let delimiter = self.key_delimiters.primary();
+        let mut leaves: HashMap<String, &str> = HashMap::new();
+        let mut folders: HashMap<String, usize> = HashMap::new();
+        let unique: HashSet<&str> = keys.iter().map(String::as_str).collect();
+        for key in unique {
+            let canonical = self.key_delimiters.canonical(key);
+            let Some(rest) = canonical.strip_prefix(prefix) else {
+                continue;
+            };
+            match rest.find(delimiter) {
+                Some(idx) => *folders.entry(rest[..idx].to_string()).or_insert(0) += 1,
+                None => {
+                    leaves.insert(rest.to_string(), key);
+                }
+            }
+        }
+        let Some(level) = level_mut(&mut self.key_tree, delimiter, prefix) else {
+            return (0, 0);
+        };
+
+        let mut gone_leaves = HashSet::new();
+        let mut gone_folders = Vec::new();
+        level.retain(|name, node| {
+            let keep = match node {
+                KeyTreeNode::Leaf { .. } => leaves.contains_key(name.as_str()) && !folders.contains_key(name.as_str()),
+                KeyTreeNode::Folder(_) => folders.contains_key(name.as_str()),
+            };
+            if !keep {
+                match node {
+                    KeyTreeNode::Leaf { full_key_name } => {
+                        gone_leaves.insert(full_key_name.clone());
+                    }
+                    KeyTreeNode::Folder(_) => gone_folders.push(format!("{}{}{}", prefix, name, delimiter)),
+                }
+            }
+            keep
+        });
+        let removed = gone_leaves.len() + gone_folders.len();
+
+        let mut added = 0;
+        let mut new_leaves = Vec::new();
+        for (child, count) in folders {
+            if !matches!(level.get(&child), Some(KeyTreeNode::Folder(_))) {
+                added += 1;
+            }
+            folder_entry(level, &child);
+            self.folder_key_counts.insert(format!("{}{}{}", prefix, child, delimiter), count);
+        }
+        for (rest, key) in leaves {
+            if let Entry::Vacant(entry) = level.entry(rest) {
+                let full_key_name = key.to_string();
+                new_leaves.push(full_key_name.clone());
+                entry.insert(KeyTreeNode::Leaf { full_key_name });
+                added += 1;
+            }
+        }
+
+        let under_gone_folder = |key: &str| gone_folders.iter().any(|folder| key.starts_with(folder.as_str()));
+        let delimiters = &self.key_delimiters;
+        let raw_keys = Arc::make_mut(&mut self.raw_keys);
+        raw_keys.retain(|key| !gone_leaves.contains(key) && !under_gone_folder(&delimiters.canonical(key)));
+        raw_keys.extend(new_leaves);
+        self.folder_key_counts.retain(|key, _| !under_gone_folder(key));
+        self.loaded_prefixes.retain(|loaded| !under_gone_folder(loaded));
+        (added, removed)
+    }
+
+    /// Rebuilds the visible list while keeping the cursor and multi-selection on the same
+    /// entries, wherever they moved to.
+    pub(crate) fn update_visible_keys_keeping_selection(&mut self) {
+        let entry_at = |app: &App, idx: usize| app.visible_keys_in_current_view.get(idx).cloned();
+        let selected = entry_at(self, self.selected_visible_key_index);
+        let marked: Vec<_> = self
+            .selected_indices
+            .iter()
+            .filter_map(|idx| entry_at(self, *idx))
+            .collect();
+        self.update_visible_keys();
+        let position = |app: &App, entry: &(String, bool)| {
+            app.visible_keys_in_current_view.iter().position(|visible| visible == entry)
+        };
+        self.selected_visible_key_index = selected
+            .and_then(|entry| position(self, &entry))
+            .unwrap_or(self.selected_visible_key_index)
+            .min(self.visible_keys_in_current_view.len().saturating_sub(1));
+        self.selected_indices = marked.iter().filter_map(|entry| position(self, entry)).collect();
+    }
+}
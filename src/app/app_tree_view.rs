@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use super::state_tree_view::TreeRow;
+use super::{level_entries, App, KeyTreeNode};
+
+impl App {
+    /// `t`: switches the key panel between drilling into one folder at a time and the
+    /// expandable tree.
+    pub fn toggle_tree_view(&mut self) {
+        self.tree_view.is_active = !self.tree_view.is_active;
+        self.expand_tree_to_current_level();
+    }
+
+    /// Opens the folders above the current level so the selection is on screen.
+    pub(crate) fn expand_tree_to_current_level(&mut self) {
+        if !self.tree_view.is_active {
+            return;
+        }
+        let delimiter = self.key_delimiters.primary();
+        let mut prefix = String::new();
+        for segment in &self.current_breadcrumb {
+            prefix.push_str(segment);
+            prefix.push(delimiter);
+            self.tree_view.expanded.insert(prefix.clone());
+        }
+    }
+
+    /// The root level with the level of every expanded folder nested under its row.
+    pub fn tree_rows(&self) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        self.push_tree_rows(&self.key_tree, &mut Vec::new(), "", &mut rows);
+        rows
+    }
+
+    fn push_tree_rows(
+        &self,
+        level: &HashMap<String, KeyTreeNode>,
+        parents: &mut Vec<String>,
+        prefix: &str,
+        rows: &mut Vec<TreeRow>,
+    ) {
+        let delimiter = self.key_delimiters.primary();
+        for (name, is_folder) in level_entries(level) {
+            let mut row = TreeRow {
+                parents: parents.clone(),
+                name,
+                is_folder,
+                expanded: false,
+            };
+            if !is_folder {
+                rows.push(row);
+                continue;
+            }
+            let folder = row.folder_name().to_string();
+            let child_prefix = format!("{}{}{}", prefix, folder, delimiter);
+            row.expanded = self.tree_view.expanded.contains(&child_prefix);
+            let expanded = row.expanded;
+            rows.push(row);
+            if let Some(KeyTreeNode::Folder(children)) = level.get(&folder).filter(|_| expanded) {
+                parents.push(folder);
+                self.push_tree_rows(children, parents, &child_prefix, rows);
+                parents.pop();
+            }
+        }
+    }
+
+    /// Canonical prefix of the level a folder row opens.
+    fn tree_folder_prefix(&self, row: &TreeRow) -> String {
+        let delimiter = self.key_delimiters.primary();
+        let mut prefix = String::new();
+        for segment in row.parents.iter().map(String::as_str).chain([row.folder_name()]) {
+            prefix.push_str(segment);
+            prefix.push(delimiter);
+        }
+        prefix
+    }
+
+    pub fn tree_folder_key_count(&self, row: &TreeRow) -> usize {
+        self.folder_key_counts
+            .get(&self.tree_folder_prefix(row))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Full key name of a leaf row.
+    pub fn tree_leaf_full_name(&self, row: &TreeRow) -> Option<String> {
+        self.leaf_full_name_in(&row.parents, &row.name)
+    }
+
+    /// Index of the selected entry among `rows`; while its level is still empty, the row
+    /// of the folder being loaded.
+    pub fn tree_selected_row(&self, rows: &[TreeRow]) -> usize {
+        let breadcrumb = &self.current_breadcrumb;
+        let position = match self.visible_keys_in_current_view.get(self.selected_visible_key_index) {
+            Some((name, _)) => rows.iter().position(|row| row.parents == *breadcrumb && row.name == *name),
+            None => breadcrumb.split_last().and_then(|(folder, parents)| {
+                rows.iter()
+                    .position(|row| row.is_folder && row.parents == parents && row.folder_name() == folder)
+            }),
+        };
+        position.unwrap_or(0)
+    }
+
+    /// Makes `row` the selection by moving the breadcrumb to its level.
+    pub fn select_tree_row(&mut self, row: &TreeRow) {
+        if row.parents != self.current_breadcrumb {
+            self.current_breadcrumb = row.parents.clone();
+            self.update_visible_keys();
+            self.clear_multi_selection();
+        }
+        if let Some(idx) = self
+            .visible_keys_in_current_view
+            .iter()
+            .position(|(name, _)| *name == row.name)
+        {
+            self.selected_visible_key_index = idx;
+        }
+        self.clear_selected_key_info_if_not_pinned();
+    }
+
+    /// Up/Down in the tree, wrapping around like the drill-down list.
+    pub fn move_tree_selection(&mut self, delta: isize) {
+        let rows = self.tree_rows();
+        if rows.is_empty() {
+            return;
+        }
+        let current = self.tree_selected_row(&rows) as isize;
+        let next = (current + delta).rem_euclid(rows.len() as isize) as usize;
+        self.select_tree_row(&rows[next]);
+    }
+
+    fn selected_tree_row(&self) -> Option<TreeRow> {
+        let rows = self.tree_rows();
+        let idx = self.tree_selected_row(&rows);
+        rows.into_iter().nth(idx)
+    }
+
+    /// Enter on a folder row opens or closes it in place. Returns false for leaves.
+    pub fn toggle_tree_folder(&mut self) -> bool {
+        let Some(row) = self.selected_tree_row().filter(|row| row.is_folder) else {
+            return false;
+        };
+        if row.expanded {
+            self.tree_view.expanded.remove(&self.tree_folder_prefix(&row));
+        } else {
+            self.expand_tree_folder_row(&row);
+        }
+        true
+    }
+
+    fn expand_tree_folder_row(&mut self, row: &TreeRow) {
+        let prefix = self.tree_folder_prefix(row);
+        self.tree_view.expanded.insert(prefix.clone());
+        self.scan_level(prefix);
+    }
+
+    /// `l`: opens a closed folder, steps into an open one, or opens a key.
+    pub fn tree_expand(&mut self) {
+        let Some(row) = self.selected_tree_row() else {
+            return;
+        };
+        if !row.is_folder {
+            self.activate_selected_key();
+        } else if row.expanded {
+            self.move_tree_selection(1);
+        } else {
+            self.expand_tree_folder_row(&row);
+        }
+    }
+
+    /// `h`: closes an open folder, otherwise selects the folder the entry is in.
+    pub fn tree_collapse(&mut self) {
+        let Some(row) = self.selected_tree_row() else {
+            return;
+        };
+        if row.is_folder && row.expanded {
+            self.tree_view.expanded.remove(&self.tree_folder_prefix(&row));
+            return;
+        }
+        self.select_tree_parent();
+    }
+
+    /// Selects the row of the folder holding the current level.
+    pub fn select_tree_parent(&mut self) {
+        let Some((folder, parents)) = self.current_breadcrumb.split_last() else {
+            return;
+        };
+        let row = TreeRow {
+            parents: parents.to_vec(),
+            name: format!("{}/", folder),
+            is_folder: true,
+            expanded: true,
+        };
+        self.select_tree_row(&row);
+    }
+}
@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use super::App;
+
+impl App {
+    /// TTL of the viewed key counted down from when it was fetched. The `-1` (no expiry)
+    /// and `-2` (missing) replies pass through unchanged.
+    pub fn remaining_ttl(&self) -> Option<i64> {
+        let name = self.value_viewer.active_leaf_key_name.as_ref()?;
+        let ttl = self.ttl_map.get(name).copied()?;
+        Some(match self.value_viewer.ttl_fetched_at {
+            Some(fetched_at) => countdown(ttl, fetched_at, Instant::now()),
+            None => ttl,
+        })
+    }
+
+    /// Stores a `TTL` reply for `key` as read now.
+    pub(crate) fn record_ttl(&mut self, key: &str, ttl: i64) {
+        self.ttl_map.insert(key.to_string(), ttl);
+        self.ttl_read_at.insert(key.to_string(), Instant::now());
+    }
+
+    /// TTL of any key whose TTL was read, counted down like `remaining_ttl`, for the key list.
+    pub fn listed_ttl(&self, key: &str) -> Option<i64> {
+        let ttl = self.ttl_map.get(key).copied()?;
+        Some(match self.ttl_read_at.get(key) {
+            Some(read_at) => countdown(ttl, *read_at, Instant::now()),
+            None => ttl,
+        })
+    }
+
+    /// Re-fetches the viewed key once its countdown reaches zero, so an expired key shows
+    /// as missing and a refreshed TTL starts counting again.
+    pub fn poll_ttl_countdown(&mut self) {
+        let Some(fetched_at) = self.value_viewer.ttl_fetched_at else {
+            return;
+        };
+        let Some(name) = self.value_viewer.active_leaf_key_name.clone() else {
+            return;
+        };
+        let ttl = self.ttl_map.get(&name).copied().unwrap_or(-2);
+        // A TTL of 0 is the server rounding down, so give it a second before asking again
+        let expires_after = Duration::from_secs(ttl.max(1) as u64);
+        if ttl < 0 || fetched_at.elapsed() < expires_after || self.value_fetch_in_flight() {
+            return;
+        }
+        self.value_viewer.ttl_fetched_at = None;
+        self.ttl_map.insert(name, 0);
+        self.refresh_active_key();
+    }
+}
+
+/// Seconds left of a `ttl` read at `fetched_at`, never below zero.
+fn countdown(ttl: i64, fetched_at: Instant, now: Instant) -> i64 {
+    if ttl < 0 {
+        return ttl;
+    }
+    let elapsed = now.saturating_duration_since(fetched_at).as_secs() as i64;
+    (ttl - elapsed).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttl_counts_down_to_zero() {
+        let fetched_at = Instant::now();
+        assert_eq!(countdown(252, fetched_at, fetched_at + Duration::from_millis(1500)), 251);
+        assert_eq!(countdown(3, fetched_at, fetched_at + Duration::from_secs(10)), 0);
+        assert_eq!(countdown(-1, fetched_at, fetched_at + Duration::from_secs(10)), -1);
+    }
+}
@@ -0,0 +1,104 @@
+use super::value_fetch::{FetchOptions, FetchedValue, ValuePurpose};
+use super::value_viewer::ValueSort;
+use super::worker::{WorkerJob, WorkerRequest};
+use super::App;
+
+impl App {
+    /// Loads `full_key_name` into the value panel, from the cache when a fresh copy is
+    /// there, else by asking the worker; the panel shows the key as being fetched meanwhile.
+    pub(crate) fn open_leaf_key(&mut self, full_key_name: &str, purpose: ValuePurpose) {
+        self.preview_due = None;
+        self.value_viewer.clear();
+        self.value_viewer.active_leaf_key_name = Some(full_key_name.to_string());
+        if self.restore_cached_key(full_key_name) {
+            self.value_loaded(purpose);
+            return;
+        }
+        let Some(con) = self.redis.reader() else {
+            self.value_viewer.selected_key_type = Some("error".to_string());
+            self.value_viewer.selected_key_value = Some("Error: No Redis connection to fetch key value.".to_string());
+            self.value_viewer.update_current_display_value();
+            return;
+        };
+        self.value_viewer.selected_key_type = Some("fetching...".to_string());
+        self.value_viewer.update_current_display_value();
+        self.request_value(con, full_key_name.to_string(), purpose);
+    }
+
+    /// Re-fetches the key currently shown in the value panel, keeping it pinned.
+    pub fn refresh_active_key(&mut self) {
+        self.reload_active_key(ValuePurpose::Refresh);
+    }
+
+    pub(super) fn reload_active_key(&mut self, purpose: ValuePurpose) {
+        let Some(key) = self.value_viewer.active_leaf_key_name.clone() else {
+            return;
+        };
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected. Cannot refresh value.".to_string());
+            return;
+        };
+        self.request_value(con, key, purpose);
+    }
+
+    /// Whether a value fetch, page or chunk is still on its way to the value panel.
+    pub(crate) fn value_fetch_in_flight(&self) -> bool {
+        self.worker.as_ref().is_some_and(|worker| worker.is_busy(WorkerJob::Value))
+    }
+
+    fn request_value(&mut self, con: super::MultiplexedConnection, key: String, purpose: ValuePurpose) {
+        let options = FetchOptions::new(&self.settings, self.value_decoder);
+        self.submit(WorkerRequest::FetchValue { con, key, options, purpose });
+    }
+
+    /// Puts a fetched key into the value panel, unless the panel has moved on to another
+    /// key since it was asked for.
+    pub(super) fn apply_fetched_value(&mut self, purpose: ValuePurpose, fetched: FetchedValue) {
+        for (class, elapsed) in &fetched.timings {
+            self.latency.record(*class, *elapsed);
+        }
+        let FetchedValue { key, viewer, key_type, ttl, .. } = fetched;
+        self.record_ttl(&key, ttl);
+        if let Some(key_type) = &key_type {
+            self.type_map_mut().insert(key.clone(), key_type.clone());
+        }
+        if self.value_viewer.active_leaf_key_name.as_deref() != Some(key.as_str()) {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.value_viewer, viewer);
+        let viewer = &mut self.value_viewer;
+        viewer.wrap_disabled = previous.wrap_disabled;
+        viewer.line_numbers = previous.line_numbers;
+        viewer.timestamps_hidden = previous.timestamps_hidden;
+        viewer.update_current_display_value();
+        if let Some(key_type) = key_type.filter(|key_type| key_type != "none") {
+            self.cache_viewed_key(&key, &key_type, ttl);
+        }
+        if matches!(purpose, ValuePurpose::Refresh | ValuePurpose::Watch) {
+            let viewer = &mut self.value_viewer;
+            // Prompts typed into while the value was reloading stay open
+            viewer.goto_line_input = previous.goto_line_input.clone();
+            viewer.pfadd_input = previous.pfadd_input.clone().filter(|_| viewer.hyperloglog.is_some());
+            viewer.pfadd_cursor = previous.pfadd_cursor;
+            viewer.probe_input = previous.probe_input.clone().filter(|_| viewer.probabilistic.is_some());
+            viewer.probe_cursor = previous.probe_cursor;
+            viewer.filter_input_active = previous.filter_input_active;
+            if previous.sort != ValueSort::Natural || !previous.line_filter.is_empty() {
+                viewer.sort = previous.sort;
+                viewer.set_line_filter(previous.line_filter.clone());
+            }
+            viewer.hex_view.enabled = previous.hex_view.enabled && viewer.raw_value.is_some();
+        }
+        if purpose == ValuePurpose::Watch {
+            self.record_watch_tick(&previous);
+        }
+        self.value_loaded(purpose);
+    }
+
+    /// What follows once the value is in the panel.
+    fn value_loaded(&mut self, purpose: ValuePurpose) {
+        if purpose == ValuePurpose::ActionMenu {
+            self.show_action_menu();
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::time::{Duration, Instant};
+
+use super::hex_view;
+use super::value_fetch::ValuePurpose;
+use super::value_viewer::{StringChunk, ValueViewer};
+use super::worker::WorkerRequest;
+use super::App;
+
+impl App {
+    pub fn select_next_value_item(&mut self) {
+        let Some(lines) = &self.value_viewer.displayed_value_lines else {
+            self.value_viewer.scroll(1, 0);
+            return;
+        };
+        if !lines.is_empty() {
+            self.value_viewer.selected_value_sub_index =
+                (self.value_viewer.selected_value_sub_index + 1) % lines.len();
+        }
+    }
+
+    pub fn select_previous_value_item(&mut self) {
+        let Some(lines) = &self.value_viewer.displayed_value_lines else {
+            self.value_viewer.scroll(-1, 0);
+            return;
+        };
+        if !lines.is_empty() {
+            if self.value_viewer.selected_value_sub_index > 0 {
+                self.value_viewer.selected_value_sub_index -= 1;
+            } else {
+                self.value_viewer.selected_value_sub_index = lines.len() - 1;
+            }
+        }
+    }
+
+    pub fn select_page_down_value_item(&mut self, page_size: usize) {
+        let Some(lines) = &self.value_viewer.displayed_value_lines else {
+            self.value_viewer.scroll(page_size as i32, 0);
+            return;
+        };
+        if !lines.is_empty() {
+            self.value_viewer.selected_value_sub_index =
+                (self.value_viewer.selected_value_sub_index + page_size).min(lines.len() - 1);
+        }
+    }
+
+    pub fn select_page_up_value_item(&mut self, page_size: usize) {
+        let Some(lines) = &self.value_viewer.displayed_value_lines else {
+            self.value_viewer.scroll(-(page_size as i32), 0);
+            return;
+        };
+        if !lines.is_empty() {
+            self.value_viewer.selected_value_sub_index = self
+                .value_viewer
+                .selected_value_sub_index
+                .saturating_sub(page_size);
+        }
+    }
+
+    pub fn page_string_chunk(&mut self, forward: bool) {
+        let (Some(key), Some(chunk)) = (
+            self.value_viewer.active_leaf_key_name.clone(),
+            self.value_viewer.string_chunk,
+        ) else {
+            return;
+        };
+        let offset = if forward {
+            if !chunk.has_next() {
+                return;
+            }
+            chunk.offset + chunk.chunk_size
+        } else {
+            if !chunk.has_previous() {
+                return;
+            }
+            chunk.offset.saturating_sub(chunk.chunk_size)
+        };
+
+        let Some(con) = self.redis.reader() else {
+            self.notifications.error("Not connected. Cannot page value.".to_string());
+            return;
+        };
+        let chunk = StringChunk { offset, ..chunk };
+        self.submit(WorkerRequest::FetchStringChunk { con, key, chunk });
+    }
+
+    pub(super) fn apply_string_chunk(&mut self, key: String, chunk: StringChunk, bytes: Result<Vec<u8>, String>) {
+        if self.value_viewer.active_leaf_key_name.as_deref() != Some(key.as_str())
+            || self.value_viewer.string_chunk.is_none()
+        {
+            return;
+        }
+        self.value_viewer.set_string_chunk(&key, chunk, bytes);
+        self.value_viewer.update_current_display_value();
+    }
+
+    pub fn cycle_value_decoder(&mut self) {
+        self.value_decoder = self.value_decoder.next();
+        // Cached values were decoded with the previous decoder
+        self.key_cache.clear();
+        self.notifications.info(format!("Value decoder: {}", self.value_decoder.label()));
+        if self.value_viewer.active_leaf_key_name.is_some() {
+            self.refresh_active_key();
+        }
+    }
+
+    pub fn toggle_watch(&mut self) {
+        if !self.watch.enabled && self.value_viewer.active_leaf_key_name.is_none() {
+            self.notifications.info("Select a key to watch first.".to_string());
+            return;
+        }
+        self.watch.toggle();
+    }
+
+    pub fn should_refresh_watch(&self) -> bool {
+        self.value_viewer.active_leaf_key_name.is_some()
+            && self
+                .watch
+                .is_due(Duration::from_millis(self.settings.watch_interval_ms))
+    }
+
+    /// Re-fetches the watched key in place, unless the previous tick is still being read.
+    pub fn refresh_watched_key(&mut self) {
+        self.watch.last_refresh = Some(Instant::now());
+        if !self.value_fetch_in_flight() {
+            self.reload_active_key(ValuePurpose::Watch);
+        }
+    }
+
+    /// Keeps the scroll position of the watched key across a tick and remembers which
+    /// rows changed since the previous one.
+    pub(super) fn record_watch_tick(&mut self, previous: &ValueViewer) {
+        let key = self.value_viewer.active_leaf_key_name.clone();
+        let same_key = self.watch.key == key;
+        let previous_rows = previous.rows();
+        let current_rows = self.value_viewer.rows();
+        self.value_viewer.selected_value_sub_index =
+            previous.selected_value_sub_index.min(current_rows.len().saturating_sub(1));
+        self.value_viewer.value_view_scroll = previous.value_view_scroll;
+        let baseline = if same_key { &previous_rows } else { &current_rows };
+        self.watch.record_refresh(baseline, &current_rows);
+        self.watch.key = key;
+    }
+
+    pub fn toggle_hex_view(&mut self) {
+        if !self.value_viewer.toggle_hex_view() {
+            self.notifications.info("Hex view is available for string values only.".to_string());
+        }
+    }
+
+    pub fn submit_hex_goto(&mut self) {
+        let Some(input) = self.value_viewer.hex_view.goto_input.take() else {
+            return;
+        };
+        let len = self.value_viewer.raw_value.as_ref().map_or(0, Vec::len);
+        match hex_view::parse_offset(&input) {
+            Some(offset) => {
+                let relative = offset.saturating_sub(self.value_viewer.raw_base_offset());
+                self.value_viewer.hex_view.jump_to(relative, len);
+            }
+            None => {
+                self.notifications.error(format!("Invalid offset '{}'.", input));
+            }
+        }
+    }
+
+    pub fn open_goto_line_prompt(&mut self) {
+        if self.value_viewer.line_count() > 0 {
+            self.value_viewer.goto_line_input = Some(String::new());
+        }
+    }
+
+    pub fn submit_goto_line(&mut self) {
+        let Some(input) = self.value_viewer.goto_line_input.take() else {
+            return;
+        };
+        match input.trim().parse::<usize>() {
+            Ok(line) => self.value_viewer.goto_line(line),
+            Err(_) => self.notifications.error(format!("Invalid line '{}'.", input)),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::key_sizes::{visible_window, VISIBLE_KEY_BATCH};
+use super::latency_tracker::OperationClass;
+use super::{App, KeyTreeNode};
+use crate::command::resp::value_text;
+use redis::Value;
+
+/// How long keys whose `TYPE`/`TTL` read failed wait before they are read again.
+const TYPE_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+impl App {
+    pub fn toggle_key_sizes(&mut self) {
+        self.key_sizes.toggle();
+    }
+
+    /// Full names of the leaf keys currently within the visible window.
+    fn visible_leaf_keys(&self) -> Vec<String> {
+        if self.search_state.is_active {
+            let keys = &self.search_state.filtered_keys;
+            return keys[visible_window(self.search_state.selected_index, keys.len())].to_vec();
+        }
+        if self.tree_view.is_active {
+            let rows = self.tree_rows();
+            let window = visible_window(self.tree_selected_row(&rows), rows.len());
+            return rows[window]
+                .iter()
+                .filter(|row| !row.is_folder)
+                .filter_map(|row| self.tree_leaf_full_name(row))
+                .collect();
+        }
+        let visible = &self.visible_keys_in_current_view;
+        visible[visible_window(self.selected_visible_key_index, visible.len())]
+            .iter()
+            .filter(|(_, is_folder)| !is_folder)
+            .filter_map(|(name, _)| self.leaf_full_name(name))
+            .collect()
+    }
+
+    /// Visible keys whose type or TTL is unknown, or was read longer than `key_cache_secs` ago.
+    /// Keys whose read just failed wait `TYPE_RETRY_DELAY` first.
+    pub(super) fn missing_key_types(&self) -> Vec<String> {
+        let type_pending = self.search_state.is_active.then_some(&self.search_state.type_pending);
+        let max_age = self.key_cache_max_age();
+        let is_stale = |key: &String| match (self.ttl_read_at.get(key), max_age) {
+            (Some(read_at), Some(max_age)) => read_at.elapsed() >= max_age,
+            _ => false,
+        };
+        self.visible_leaf_keys()
+            .into_iter()
+            .chain(type_pending.into_iter().flatten().cloned())
+            .filter(|key| !self.type_map.contains_key(key) || !self.ttl_map.contains_key(key) || is_stale(key))
+            .filter(|key| {
+                let failed_at = self.key_type_failures.get(key);
+                failed_at.is_none_or(|failed_at| failed_at.elapsed() >= TYPE_RETRY_DELAY)
+            })
+            .take(VISIBLE_KEY_BATCH)
+            .collect()
+    }
+
+    /// Records the pipelined `TYPE` and `TTL` replies of one batch of visible keys, for the
+    /// list badges and countdowns and so an opened key's metadata is already known.
+    /// The type map for writing; copied first only if a background search still holds it.
+    pub(crate) fn type_map_mut(&mut self) -> &mut HashMap<String, String> {
+        Arc::make_mut(&mut self.type_map)
+    }
+
+    /// Keys whose replies failed keep no type or TTL, rather than look missing.
+    pub(super) fn apply_key_types(
+        &mut self,
+        keys: Vec<String>,
+        replies: Result<Vec<Value>, String>,
+        elapsed: Duration,
+    ) {
+        self.latency.record(OperationClass::Metadata, elapsed);
+        let (replies, error) = match replies {
+            Ok(replies) => (replies, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        let failing_already = !self.key_type_failures.is_empty();
+        let mut failed = 0;
+        for (idx, key) in keys.into_iter().enumerate() {
+            let Some((key_type, ttl)) = type_and_ttl(replies.get(2 * idx..2 * idx + 2)) else {
+                self.key_type_failures.insert(key, Instant::now());
+                failed += 1;
+                continue;
+            };
+            self.key_type_failures.remove(&key);
+            self.record_ttl(&key, ttl);
+            self.type_map_mut().insert(key, key_type);
+        }
+        if failed > 0 && !failing_already {
+            let reason = error.unwrap_or_else(|| "unexpected reply".to_string());
+            self.notifications.error(format!("Reading TYPE/TTL of {} key(s) failed: {}", failed, reason));
+        }
+        if !self.search_state.type_pending.is_empty() {
+            self.update_filtered_keys();
+        }
+    }
+
+    /// Visible keys not sized yet, while the size column is on.
+    pub(super) fn missing_key_sizes(&self) -> Vec<String> {
+        if !self.key_sizes.enabled {
+            return Vec::new();
+        }
+        self.key_sizes.missing(&self.visible_leaf_keys())
+    }
+
+    /// Resolves a leaf in the current breadcrumb level to its full key name.
+    pub(crate) fn leaf_full_name(&self, display_name: &str) -> Option<String> {
+        self.leaf_full_name_in(&self.current_breadcrumb, display_name)
+    }
+
+    /// The multi-selection as full key names and folder prefixes, in list order, as
+    /// multi-delete resolves it.
+    pub(crate) fn multi_selection_items(&self) -> (Vec<String>, Vec<String>) {
+        let mut indices: Vec<usize> = self.selected_indices.iter().copied().collect();
+        indices.sort_unstable();
+        let level_prefix = self.current_level_prefix();
+        let (mut keys, mut prefixes) = (Vec::new(), Vec::new());
+        for (name, is_folder) in indices.into_iter().filter_map(|idx| self.visible_keys_in_current_view.get(idx)) {
+            if *is_folder {
+                prefixes.push(format!("{}{}{}", level_prefix, name.trim_end_matches('/'), self.key_delimiters.primary()));
+            } else if let Some(full_key) = self.leaf_full_name(name) {
+                keys.push(full_key);
+            }
+        }
+        (keys, prefixes)
+    }
+
+    /// Resolves a leaf in the level below the folders `parents` to its full key name.
+    pub(crate) fn leaf_full_name_in(&self, parents: &[String], display_name: &str) -> Option<String> {
+        let mut level = &self.key_tree;
+        for segment in parents {
+            match level.get(segment) {
+                Some(KeyTreeNode::Folder(sub_map)) => level = sub_map,
+                _ => return None,
+            }
+        }
+        match level.get(display_name) {
+            Some(KeyTreeNode::Leaf { full_key_name }) => Some(full_key_name.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The `TYPE` and `TTL` replies of one key, or None when either failed or is absent.
+fn type_and_ttl(replies: Option<&[Value]>) -> Option<(String, i64)> {
+    match replies? {
+        [key_type, Value::Int(ttl)] => Some((value_text(key_type)?, *ttl)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::type_and_ttl;
+    use redis::Value;
+
+    #[test]
+    fn pipelined_type_and_ttl_replies_pair_up() {
+        let replies = [
+            Value::SimpleString("hash".to_string()),
+            Value::Int(90),
+            Value::SimpleString("string".to_string()),
+            Value::Nil,
+        ];
+        assert_eq!(type_and_ttl(replies.get(0..2)), Some(("hash".to_string(), 90)));
+        assert_eq!(type_and_ttl(replies.get(2..4)), None);
+        assert_eq!(type_and_ttl(replies.get(4..6)), None);
+    }
+}
@@ -0,0 +1,135 @@
+use super::latency_tracker::OperationClass;
+use super::redis_stats::RedisStats;
+use super::worker::{AppEvent, WorkerJob, WorkerRequest};
+use super::App;
+
+impl App {
+    /// Hands `request` to the worker of the current connection attempt; without one
+    /// there is no server to ask, so it is dropped.
+    pub(super) fn submit(&mut self, request: WorkerRequest) {
+        if let Some(worker) = &mut self.worker {
+            worker.submit(request);
+        }
+    }
+
+    /// Hands the worker the reads the screen is missing: stale stats, and the types, TTLs
+    /// and sizes of visible keys. Kinds already in flight are skipped.
+    pub fn request_background_reads(&mut self) {
+        let Some(worker) = &self.worker else {
+            return;
+        };
+        let types_idle = !worker.is_busy(WorkerJob::KeyTypes);
+        let sizes_idle = !worker.is_busy(WorkerJob::KeySizes);
+        if self.should_refresh_stats() {
+            self.trigger_fetch_redis_stats();
+        }
+        let types = if types_idle { self.missing_key_types() } else { Vec::new() };
+        let sizes = if sizes_idle { self.missing_key_sizes() } else { Vec::new() };
+        let Some(con) = self.redis.reader() else {
+            return;
+        };
+        let Some(worker) = &mut self.worker else {
+            return;
+        };
+        if !types.is_empty() {
+            worker.send(WorkerRequest::FetchKeyTypes { con: con.clone(), keys: types });
+        }
+        if !sizes.is_empty() {
+            worker.send(WorkerRequest::FetchKeySizes { con, keys: sizes });
+        }
+    }
+
+    /// INFO goes to the primary so the stats describe the server written to.
+    pub fn trigger_fetch_redis_stats(&mut self) {
+        let (Some(worker), Some(con)) = (&mut self.worker, self.redis.connection.clone()) else {
+            return;
+        };
+        worker.send(WorkerRequest::FetchStats(con));
+    }
+
+    /// Applies every request the worker finished since the last frame.
+    pub fn poll_worker(&mut self) {
+        let Some(worker) = &mut self.worker else {
+            return;
+        };
+        for event in worker.drain() {
+            self.apply_event(event);
+        }
+    }
+
+    fn apply_event(&mut self, event: AppEvent) {
+        match event {
+            AppEvent::Stats { info, elapsed } => {
+                self.latency.record(OperationClass::Info, elapsed);
+                match info {
+                    Ok(info) => {
+                        let stats = RedisStats::from_info_string(&info);
+                        self.db_key_counts = Some(stats.keyspace.clone());
+                        self.redis_stats = Some(stats);
+                    }
+                    Err(e) => tracing::warn!("failed to fetch Redis stats: {}", e),
+                }
+            }
+            AppEvent::KeyTypes { keys, replies, elapsed } => self.apply_key_types(keys, replies, elapsed),
+            AppEvent::KeySizes { keys, sizes } => {
+                for (key, size) in keys.into_iter().zip(sizes) {
+                    self.key_sizes.sizes.insert(key, size);
+                }
+            }
+            AppEvent::Connected {
+                profile_index,
+                session,
+                result,
+            } => self.apply_connection(profile_index, session, result),
+            AppEvent::Value { purpose, fetched } => self.apply_fetched_value(purpose, *fetched),
+            AppEvent::MoreValues {
+                key,
+                page,
+                rows,
+                elapsed,
+            } => self.apply_more_values(key, page, rows, elapsed),
+            AppEvent::StringChunk { key, chunk, bytes } => self.apply_string_chunk(key, chunk, bytes),
+            AppEvent::Probed { key, kind, inputs, reply } => self.apply_probe(key, kind, inputs, reply),
+            AppEvent::PfAdded { key, count, changed } => self.apply_pfadd(key, count, changed),
+            AppEvent::GotoKey { key, found } => self.apply_goto_key(key, found),
+            AppEvent::Toast(severity, message) => self.notifications.push(severity, message),
+            AppEvent::KeyCommand {
+                key,
+                action,
+                label,
+                input,
+                reply,
+            } => self.apply_key_command(key, action, label, input, reply),
+            AppEvent::SavedValue { key, path, saved } => self.apply_saved_value(key, path, saved),
+            AppEvent::DeletedKey { key, result } => self.apply_deleted_key(key, result),
+            AppEvent::PasteRead(read) => self.apply_read_paste(read),
+            AppEvent::Pasted {
+                key,
+                overwrites,
+                result,
+            } => self.apply_paste(key, overwrites, result),
+            AppEvent::ScriptRan { result, sha } => self.apply_script_result(result, sha),
+            AppEvent::DoctorReport(lines) => self.doctor_report.lines = lines,
+            AppEvent::StatsReport { info, elapsed } => self.apply_stats_report(info, elapsed),
+            AppEvent::RandomSample(sampled) => self.apply_random_sample(sampled),
+            AppEvent::KeyInfo(info) => self.apply_key_info(*info),
+            AppEvent::Config(params) => self.apply_config(params),
+            AppEvent::ConfigSet { name, value, result } => self.apply_config_set(name, value, result),
+            AppEvent::ConfigRewritten(result) => self.apply_config_rewrite(result),
+            AppEvent::Functions(libraries) => self.apply_functions(libraries),
+            AppEvent::FunctionRan { result, loaded } => self.apply_function_result(result, loaded),
+            AppEvent::SearchIndexes(indexes) => self.apply_search_indexes(indexes),
+            AppEvent::SearchIndexInfo { index, info } => self.apply_search_index_info(index, info),
+            AppEvent::SearchResults { query, results } => self.apply_search_results(query, results),
+            AppEvent::CompareConnected { left, profile, right } => self.apply_compare_connection(left, *profile, right),
+            AppEvent::CompareDetail(detail) => self.apply_compare_detail(*detail),
+            AppEvent::ConsumerGroups { key, read } => self.apply_consumer_groups(key, read),
+            AppEvent::GroupCommandRan {
+                key,
+                status,
+                clears_marks,
+            } => self.apply_group_command(key, status, clears_marks),
+            AppEvent::CommandRan(outcome) => self.apply_command_outcome(outcome),
+        }
+    }
+}
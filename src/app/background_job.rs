@@ -0,0 +1,60 @@
+use std::future::Future;
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+/// A task streaming its events back over a channel, so the UI keeps drawing while it runs
+/// and picks the events up between frames. Dropping the job aborts the task.
+pub struct BackgroundJob<T> {
+    receiver: UnboundedReceiver<T>,
+    handle: JoinHandle<()>,
+}
+
+impl<T: Send + 'static> BackgroundJob<T> {
+    /// Spawns the task `start` builds around the sender of the job's events.
+    pub fn spawn<F>(start: impl FnOnce(UnboundedSender<T>) -> F) -> Self
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(start(sender));
+        Self { receiver, handle }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut events = Vec::new();
+        while let Ok(event) = self.receiver.try_recv() {
+            events.push(event);
+        }
+        events
+    }
+}
+
+impl<T> Drop for BackgroundJob<T> {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn drains_what_the_task_sent_and_aborts_on_drop() {
+        let mut job = BackgroundJob::spawn(|sender| async move {
+            let _ = sender.send(1);
+            let _ = sender.send(2);
+        });
+        tokio::task::yield_now().await;
+        assert_eq!(job.drain(), [1, 2]);
+        assert!(job.drain().is_empty());
+
+        let job = BackgroundJob::<()>::spawn(|_| std::future::pending());
+        let handle = job.handle.abort_handle();
+        drop(job);
+        tokio::task::yield_now().await;
+        assert!(handle.is_finished());
+    }
+}
@@ -0,0 +1,167 @@
+use redis::aio::MultiplexedConnection;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+use super::key_delimiters::KeyDelimiters;
+
+/// Keys removed per UNLINK/DEL.
+pub const DELETE_BATCH_SIZE: usize = 500;
+
+#[derive(Debug)]
+pub enum BulkDeleteEvent {
+    /// Keys removed by one batch.
+    Deleted(i64),
+    /// A key batch or prefix that failed; the delete goes on with the next one.
+    Error(String),
+    Done,
+}
+
+/// Background delete of folders and marked keys, so a huge prefix can be stopped with
+/// Esc. Keys already removed stay removed. Dropping the delete cancels it.
+pub struct BulkDelete {
+    /// What is being deleted, for progress and the final notification.
+    pub label: String,
+    pub deleted: i64,
+    pub errors: Vec<String>,
+    job: BackgroundJob<BulkDeleteEvent>,
+}
+
+impl BulkDelete {
+    /// Deletes `keys` in batches, then every key beneath each canonical prefix in `prefixes`.
+    pub fn spawn(
+        con: MultiplexedConnection,
+        delimiters: KeyDelimiters,
+        label: String,
+        keys: Vec<String>,
+        prefixes: Vec<String>,
+    ) -> Self {
+        let job = BackgroundJob::spawn(|sender| delete_all(con, delimiters, keys, prefixes, sender));
+        Self {
+            label,
+            deleted: 0,
+            errors: Vec::new(),
+            job,
+        }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<BulkDeleteEvent> {
+        self.job.drain()
+    }
+}
+
+async fn delete_all(
+    mut con: MultiplexedConnection,
+    delimiters: KeyDelimiters,
+    keys: Vec<String>,
+    prefixes: Vec<String>,
+    sender: UnboundedSender<BulkDeleteEvent>,
+) {
+    let mut prefer_unlink = true;
+    for batch in keys.chunks(DELETE_BATCH_SIZE) {
+        let event = match delete_batch(&mut con, batch, &mut prefer_unlink).await {
+            Ok(count) => BulkDeleteEvent::Deleted(count),
+            Err(e) => BulkDeleteEvent::Error(e),
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+    }
+    for prefix in &prefixes {
+        if let Err(e) = delete_prefix(&mut con, &delimiters, prefix, &mut prefer_unlink, &sender).await {
+            if sender.send(BulkDeleteEvent::Error(e)).is_err() {
+                return;
+            }
+        }
+    }
+    let _ = sender.send(BulkDeleteEvent::Done);
+}
+
+/// Scans `prefix` and deletes what it finds batch by batch, reporting each batch.
+async fn delete_prefix(
+    con: &mut MultiplexedConnection,
+    delimiters: &KeyDelimiters,
+    prefix: &str,
+    prefer_unlink: &mut bool,
+    sender: &UnboundedSender<BulkDeleteEvent>,
+) -> Result<(), String> {
+    let mut cursor: u64 = 0;
+    let mut batch = Vec::new();
+    loop {
+        let (next_cursor, keys) = scan_prefix_page(con, delimiters, prefix, cursor).await?;
+        batch.extend(keys);
+        if batch.len() >= DELETE_BATCH_SIZE || (next_cursor == 0 && !batch.is_empty()) {
+            let count = delete_batch(con, &batch, prefer_unlink).await?;
+            batch.clear();
+            sender
+                .send(BulkDeleteEvent::Deleted(count))
+                .map_err(|_| "Delete was cancelled".to_string())?;
+        }
+        if next_cursor == 0 {
+            return Ok(());
+        }
+        cursor = next_cursor;
+    }
+}
+
+/// One `SCAN` page of the keys beneath the canonical `prefix`, with the cursor to
+/// continue from.
+pub(super) async fn scan_prefix_page(
+    con: &mut MultiplexedConnection,
+    delimiters: &KeyDelimiters,
+    prefix: &str,
+    cursor: u64,
+) -> Result<(u64, Vec<String>), String> {
+    let (next_cursor, keys) = redis::cmd("SCAN")
+        .arg(cursor)
+        .arg("MATCH")
+        .arg(delimiters.prefix_pattern(prefix))
+        .arg("COUNT")
+        .arg(1000)
+        .query_async::<(u64, Vec<String>)>(con)
+        .await
+        .map_err(|e| format!("Error scanning keys for prefix {}: {}", prefix, e))?;
+    let keys = keys
+        .into_iter()
+        .filter(|key| delimiters.canonical(key).starts_with(prefix))
+        .collect();
+    Ok((next_cursor, keys))
+}
+
+/// Removes `keys` with UNLINK, falling back to DEL for good on servers without it.
+pub(super) async fn delete_batch(
+    con: &mut MultiplexedConnection,
+    keys: &[String],
+    prefer_unlink: &mut bool,
+) -> Result<i64, String> {
+    if keys.is_empty() {
+        return Ok(0);
+    }
+
+    let result = if *prefer_unlink {
+        redis::cmd("UNLINK").arg(keys).query_async::<i64>(con).await
+    } else {
+        redis::cmd("DEL").arg(keys).query_async::<i64>(con).await
+    };
+
+    match result {
+        Ok(count) => Ok(count),
+        Err(e) => {
+            if *prefer_unlink && is_unknown_command_error(&e) {
+                *prefer_unlink = false;
+                redis::cmd("DEL")
+                    .arg(keys)
+                    .query_async::<i64>(con)
+                    .await
+                    .map_err(|err| format!("Error deleting keys: {}", err))
+            } else {
+                Err(format!("Error deleting keys: {}", e))
+            }
+        }
+    }
+}
+
+fn is_unknown_command_error(err: &redis::RedisError) -> bool {
+    err.kind() == redis::ErrorKind::Extension
+        && err.to_string().to_lowercase().contains("unknown command")
+}
@@ -0,0 +1,126 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use crate::command::resp::value_text;
+
+pub const CLUSTER_SLOTS: u16 = 16384;
+
+/// Hash slots `start..=end` served by the master at `node` (`host:port`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotRange {
+    pub start: u16,
+    pub end: u16,
+    pub node: String,
+}
+
+/// Slot ownership reported by CLUSTER SLOTS when connected to a cluster node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClusterTopology {
+    pub ranges: Vec<SlotRange>,
+}
+
+impl ClusterTopology {
+    /// Master node serving `slot`, if the slot is assigned.
+    pub fn owner(&self, slot: u16) -> Option<&str> {
+        self.ranges
+            .iter()
+            .find(|range| (range.start..=range.end).contains(&slot))
+            .map(|range| range.node.as_str())
+    }
+}
+
+/// Reads the slot map when the server runs in cluster mode; `None` otherwise.
+pub async fn fetch_topology(con: &mut MultiplexedConnection) -> Option<ClusterTopology> {
+    let info: String = redis::cmd("INFO").arg("cluster").query_async(con).await.ok()?;
+    if !info.lines().any(|line| line.trim() == "cluster_enabled:1") {
+        return None;
+    }
+    let slots: Value = redis::cmd("CLUSTER").arg("SLOTS").query_async(con).await.ok()?;
+    Some(parse_cluster_slots(&slots))
+}
+
+/// Parses `[[start, end, [host, port, id, ...], replicas...], ...]`.
+pub fn parse_cluster_slots(reply: &Value) -> ClusterTopology {
+    let Value::Array(entries) = reply else {
+        return ClusterTopology::default();
+    };
+    let ranges = entries
+        .iter()
+        .filter_map(|entry| {
+            let Value::Array(fields) = entry else {
+                return None;
+            };
+            let (Value::Int(start), Value::Int(end), Value::Array(master)) = (fields.first()?, fields.get(1)?, fields.get(2)?)
+            else {
+                return None;
+            };
+            let host = master.first().and_then(value_text)?;
+            let port = match master.get(1)? {
+                Value::Int(port) => *port,
+                _ => return None,
+            };
+            Some(SlotRange {
+                start: u16::try_from(*start).ok()?,
+                end: u16::try_from(*end).ok()?,
+                node: format!("{}:{}", host, port),
+            })
+        })
+        .collect();
+    ClusterTopology { ranges }
+}
+
+/// Hash slot of `key`, as CLUSTER KEYSLOT computes it: CRC16 of the key, or
+/// of the first non-empty `{hash tag}` in it, modulo 16384.
+pub fn key_slot(key: &[u8]) -> u16 {
+    let hashed = match key.iter().position(|b| *b == b'{') {
+        Some(open) => match key[open + 1..].iter().position(|b| *b == b'}') {
+            Some(len) if len > 0 => &key[open + 1..open + 1 + len],
+            _ => key,
+        },
+        None => key,
+    };
+    crc16(hashed) % CLUSTER_SLOTS
+}
+
+/// CRC16-CCITT (XMODEM), the variant Redis Cluster uses.
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_slots_match_redis_and_honor_hash_tags() {
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+        assert_eq!(key_slot(b"foo"), 12182);
+        assert_eq!(key_slot(b"bar"), 5061);
+        assert_eq!(key_slot(b"{user1000}.following"), key_slot(b"user1000"));
+        assert_eq!(key_slot(b"{}.following"), crc16(b"{}.following") % CLUSTER_SLOTS);
+
+        let reply = Value::Array(vec![
+            Value::Array(vec![
+                Value::Int(0),
+                Value::Int(5460),
+                Value::Array(vec![Value::BulkString(b"10.0.0.1".to_vec()), Value::Int(7000)]),
+            ]),
+            Value::Array(vec![
+                Value::Int(5461),
+                Value::Int(16383),
+                Value::Array(vec![Value::BulkString(b"10.0.0.2".to_vec()), Value::Int(7001)]),
+            ]),
+        ]);
+        let topology = parse_cluster_slots(&reply);
+        assert_eq!(topology.owner(5061), Some("10.0.0.1:7000"));
+        assert_eq!(topology.owner(12182), Some("10.0.0.2:7001"));
+    }
+}
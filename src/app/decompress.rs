@@ -0,0 +1,131 @@
+use std::io::Read;
+
+/// Upper bound on inflated output so a hostile or corrupt value cannot exhaust memory.
+const MAX_DECOMPRESSED_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Lz4,
+}
+
+impl Compression {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Lz4 => "lz4",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecompressionInfo {
+    pub compression: Compression,
+    pub original_len: usize,
+    pub decompressed_len: usize,
+    /// Inflation stopped at the size cap, so only the start of the value is shown.
+    pub truncated: bool,
+}
+
+/// A payload inflated by `decompress`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inflated {
+    pub compression: Compression,
+    pub bytes: Vec<u8>,
+    /// The payload inflates past `MAX_DECOMPRESSED_BYTES`; `bytes` holds only that much.
+    pub truncated: bool,
+}
+
+/// Identifies a compressed payload by its frame magic bytes.
+pub fn detect(bytes: &[u8]) -> Option<Compression> {
+    match bytes {
+        [0x1F, 0x8B, ..] => Some(Compression::Gzip),
+        [0x28, 0xB5, 0x2F, 0xFD, ..] => Some(Compression::Zstd),
+        [0x04, 0x22, 0x4D, 0x18, ..] => Some(Compression::Lz4),
+        _ => None,
+    }
+}
+
+/// Inflates `bytes` when they carry a recognised magic header and decode cleanly.
+pub fn decompress(bytes: &[u8]) -> Option<Inflated> {
+    decompress_capped(bytes, MAX_DECOMPRESSED_BYTES)
+}
+
+fn decompress_capped(bytes: &[u8], cap: u64) -> Option<Inflated> {
+    let compression = detect(bytes)?;
+    let reader: Box<dyn Read + '_> = match compression {
+        Compression::Gzip => Box::new(flate2::read::GzDecoder::new(bytes)),
+        Compression::Zstd => Box::new(ruzstd::decoding::StreamingDecoder::new(bytes).ok()?),
+        Compression::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(bytes)),
+    };
+    let mut out = Vec::new();
+    // One byte past the cap tells a payload that fits from one that was cut
+    reader.take(cap + 1).read_to_end(&mut out).ok()?;
+    let truncated = out.len() as u64 > cap;
+    out.truncate(cap as usize);
+    Some(Inflated {
+        compression,
+        bytes: out,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const PAYLOAD: &[u8] = b"hello hello hello hello compressed world";
+
+    fn inflated(compression: Compression) -> Option<Inflated> {
+        Some(Inflated {
+            compression,
+            bytes: PAYLOAD.to_vec(),
+            truncated: false,
+        })
+    }
+
+    fn gzip(payload: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(payload).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn decompress_gzip() {
+        assert_eq!(decompress(&gzip(PAYLOAD)), inflated(Compression::Gzip));
+    }
+
+    #[test]
+    fn decompress_zstd() {
+        let compressed =
+            ruzstd::encoding::compress_to_vec(PAYLOAD, ruzstd::encoding::CompressionLevel::Fastest);
+        assert_eq!(decompress(&compressed), inflated(Compression::Zstd));
+    }
+
+    #[test]
+    fn decompress_lz4_frame() {
+        let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+        encoder.write_all(PAYLOAD).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert_eq!(decompress(&compressed), inflated(Compression::Lz4));
+    }
+
+    #[test]
+    fn decompress_rejects_plain_and_corrupt_values() {
+        assert_eq!(decompress(b"plain text"), None);
+        assert_eq!(decompress(&[0x1F, 0x8B, 0x00, 0x01]), None);
+    }
+
+    #[test]
+    fn payloads_past_the_cap_are_cut_and_flagged() {
+        let compressed = gzip(PAYLOAD);
+        let cut = decompress_capped(&compressed, 5).unwrap();
+        assert_eq!(cut.bytes, b"hello");
+        assert!(cut.truncated);
+        let exact = decompress_capped(&compressed, PAYLOAD.len() as u64).unwrap();
+        assert!(!exact.truncated);
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use super::key_delimiters::KeyDelimiters;
+
+/// What a delete would remove, without removing it: the matching keys counted per
+/// folder one level below `base`, the level being deleted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeletePreview {
+    base: String,
+    pub total: usize,
+    /// The scan was stopped early, so `total` is only a lower bound.
+    pub stopped: bool,
+    /// Keys per child folder prefix; `""` holds the keys directly in `base`.
+    counts: HashMap<String, usize>,
+}
+
+impl DeletePreview {
+    pub fn new(base: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Counts `key` under its folder below `base`.
+    pub fn add(&mut self, key: &str, delimiters: &KeyDelimiters) {
+        let canonical = delimiters.canonical(key);
+        let rest = canonical.strip_prefix(self.base.as_str()).unwrap_or(&canonical);
+        let group = match rest.find(delimiters.primary()) {
+            Some(end) => format!("{}{}", self.base, &rest[..=end]),
+            None => String::new(),
+        };
+        *self.counts.entry(group).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// Folder labels with their key counts, largest first.
+    pub fn groups(&self) -> Vec<(String, usize)> {
+        let mut groups: Vec<(String, usize)> = self
+            .counts
+            .iter()
+            .map(|(group, &count)| (self.label(group), count))
+            .collect();
+        groups.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        groups
+    }
+
+    fn label(&self, group: &str) -> String {
+        match (group.is_empty(), self.base.is_empty()) {
+            (false, _) => group.to_string(),
+            (true, true) => "(no prefix)".to_string(),
+            (true, false) => format!("(directly in {})", self.base),
+        }
+    }
+
+    /// The `max_groups` largest groups as aligned `count  folder` lines, plus a line
+    /// for the rest.
+    pub fn lines(&self, max_groups: usize) -> Vec<String> {
+        let groups = self.groups();
+        let width = groups.first().map_or(1, |(_, count)| count.to_string().len());
+        let mut lines: Vec<String> = groups
+            .iter()
+            .take(max_groups)
+            .map(|(label, count)| format!("{:>width$}  {}", count, label, width = width))
+            .collect();
+        if groups.len() > max_groups {
+            let rest: usize = groups[max_groups..].iter().map(|(_, count)| count).sum();
+            lines.push(format!("{:>width$}  in {} more folder(s)", rest, groups.len() - max_groups, width = width));
+        }
+        lines
+    }
+}
+
+/// The folder a `SCAN MATCH` glob is confined to: its literal start, cut after the last
+/// delimiter, e.g. `cache:user*` gives `cache:`.
+pub fn glob_base(pattern: &str, delimiters: &KeyDelimiters) -> String {
+    let literal_end = pattern.find(['*', '?', '[', '\\']).unwrap_or(pattern.len());
+    let literal = delimiters.canonical(&pattern[..literal_end]).into_owned();
+    match literal.rfind(delimiters.primary()) {
+        Some(end) => literal[..=end].to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_counted_per_folder_below_the_base() {
+        let delimiters = KeyDelimiters::new(":/");
+        let mut preview = DeletePreview::new("users:");
+        for key in ["users:1:name", "users:1:email", "users/2/name", "users:orphan"] {
+            preview.add(key, &delimiters);
+        }
+        assert_eq!(preview.total, 4);
+        assert_eq!(
+            preview.groups(),
+            [
+                ("users:1:".to_string(), 2),
+                ("(directly in users:)".to_string(), 1),
+                ("users:2:".to_string(), 1),
+            ]
+        );
+        assert_eq!(preview.lines(1), ["2  users:1:", "2  in 2 more folder(s)"]);
+
+        let mut root = DeletePreview::new("");
+        root.add("plain", &delimiters);
+        assert_eq!(root.groups(), [("(no prefix)".to_string(), 1)]);
+    }
+
+    #[test]
+    fn glob_base_stops_at_the_first_wildcard() {
+        let delimiters = KeyDelimiters::default();
+        assert_eq!(glob_base("cache:*", &delimiters), "cache:");
+        assert_eq!(glob_base("cache:user:4*", &delimiters), "cache:user:");
+        assert_eq!(glob_base("*:session", &delimiters), "");
+        assert_eq!(glob_base("plain", &delimiters), "");
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+pub const HEX_ROW_BYTES: usize = 16;
+
+/// Cursor, selection, and goto prompt for the byte-level hex viewer.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HexViewState {
+    pub enabled: bool,
+    pub cursor: usize,
+    pub selection_anchor: Option<usize>,
+    pub goto_input: Option<String>,
+}
+
+impl HexViewState {
+    pub fn reset(&mut self) {
+        self.cursor = 0;
+        self.selection_anchor = None;
+        self.goto_input = None;
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.reset();
+    }
+
+    pub fn move_cursor(&mut self, delta: isize, len: usize) {
+        if len == 0 {
+            self.cursor = 0;
+            return;
+        }
+        let target = self.cursor as isize + delta;
+        self.cursor = target.clamp(0, len as isize - 1) as usize;
+    }
+
+    pub fn jump_to(&mut self, position: usize, len: usize) {
+        self.cursor = position.min(len.saturating_sub(1));
+    }
+
+    pub fn toggle_selection(&mut self) {
+        self.selection_anchor = match self.selection_anchor {
+            Some(_) => None,
+            None => Some(self.cursor),
+        };
+    }
+
+    /// Inclusive byte range covered by the selection, or just the cursor byte.
+    pub fn selected_range(&self) -> (usize, usize) {
+        let anchor = self.selection_anchor.unwrap_or(self.cursor);
+        (anchor.min(self.cursor), anchor.max(self.cursor))
+    }
+
+    pub fn is_selected(&self, position: usize) -> bool {
+        let (start, end) = self.selected_range();
+        self.selection_anchor.is_some() && position >= start && position <= end
+    }
+
+    /// Space separated hex of the selected bytes, ready for the clipboard.
+    pub fn selected_hex(&self, bytes: &[u8]) -> Option<String> {
+        let (start, end) = self.selected_range();
+        let slice = bytes.get(start..=end)?;
+        let mut out = String::with_capacity(slice.len() * 3);
+        for (idx, byte) in slice.iter().enumerate() {
+            if idx > 0 {
+                out.push(' ');
+            }
+            write!(out, "{:02X}", byte).ok();
+        }
+        Some(out)
+    }
+}
+
+/// Parses a goto target as `0x`-prefixed hex or plain decimal.
+pub fn parse_offset(input: &str) -> Option<usize> {
+    let input = input.trim();
+    match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => input.parse().ok(),
+    }
+}
+
+pub fn ascii_char(byte: u8) -> char {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte as char
+    } else {
+        '.'
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_cursor_clamps_to_bounds() {
+        let mut state = HexViewState::default();
+        state.move_cursor(-5, 40);
+        assert_eq!(state.cursor, 0);
+        state.move_cursor(HEX_ROW_BYTES as isize * 3, 40);
+        assert_eq!(state.cursor, 39);
+    }
+
+    #[test]
+    fn selected_hex_covers_range_in_either_direction() {
+        let mut state = HexViewState::default();
+        state.jump_to(3, 5);
+        state.toggle_selection();
+        state.move_cursor(-2, 5);
+        assert_eq!(state.selected_range(), (1, 3));
+        assert_eq!(
+            state.selected_hex(&[0x00, 0x0A, 0xFF, 0x10, 0x20]).as_deref(),
+            Some("0A FF 10")
+        );
+    }
+
+    #[test]
+    fn parse_offset_accepts_hex_and_decimal() {
+        assert_eq!(parse_offset("0x1F"), Some(31));
+        assert_eq!(parse_offset(" 42 "), Some(42));
+        assert_eq!(parse_offset("zz"), None);
+    }
+}
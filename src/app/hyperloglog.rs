@@ -0,0 +1,106 @@
+/// Magic bytes every HyperLogLog string starts with.
+const HLL_MAGIC: &[u8] = b"HYLL";
+
+/// Header bytes before the registers: magic, encoding, 3 unused, 8 cardinality cache.
+const HLL_HEADER_LEN: usize = 16;
+
+/// Registers of a HyperLogLog, 6 bits each when dense.
+const HLL_REGISTERS: usize = 16384;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HllEncoding {
+    /// Run-length encoded registers, used while most are zero.
+    Sparse,
+    /// 16384 packed 6-bit registers.
+    Dense,
+}
+
+impl HllEncoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            HllEncoding::Sparse => "sparse",
+            HllEncoding::Dense => "dense",
+        }
+    }
+}
+
+/// The header of a HyperLogLog string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HllHeader {
+    pub encoding: HllEncoding,
+    /// Cardinality cached by the last `PFCOUNT`; `None` once a `PFADD` invalidated it.
+    pub cached_cardinality: Option<u64>,
+}
+
+/// Reads the header of `bytes` if they hold a HyperLogLog.
+pub fn parse(bytes: &[u8]) -> Option<HllHeader> {
+    if bytes.len() < HLL_HEADER_LEN || !bytes.starts_with(HLL_MAGIC) {
+        return None;
+    }
+    let encoding = match bytes[4] {
+        0 => HllEncoding::Dense,
+        1 => HllEncoding::Sparse,
+        _ => return None,
+    };
+    let mut card = [0u8; 8];
+    card.copy_from_slice(&bytes[8..HLL_HEADER_LEN]);
+    // The most significant bit of the last byte marks the cache as stale
+    let cached_cardinality = (card[7] & 0x80 == 0).then(|| u64::from_le_bytes(card));
+    Some(HllHeader {
+        encoding,
+        cached_cardinality,
+    })
+}
+
+/// Text shown in the value panel for a HyperLogLog of `len` bytes, with the `PFCOUNT`
+/// estimate when the server answered it.
+pub fn describe(header: &HllHeader, len: usize, count: Result<u64, String>) -> String {
+    let mut lines = vec!["HyperLogLog".to_string(), String::new()];
+    lines.push(match count {
+        Ok(count) => format!("Estimated cardinality (PFCOUNT): {}", count),
+        Err(e) => format!("Estimated cardinality (PFCOUNT): unavailable ({})", e),
+    });
+    lines.push(match header.encoding {
+        HllEncoding::Sparse => format!(
+            "Encoding: sparse, {} bytes (converted to dense past hll-sparse-max-bytes)",
+            len
+        ),
+        HllEncoding::Dense => format!("Encoding: dense, {} bytes ({} 6-bit registers)", len, HLL_REGISTERS),
+    });
+    lines.push(match header.cached_cardinality {
+        Some(cached) => format!("Cached cardinality in the header: {}", cached),
+        None => "Cached cardinality in the header: stale (recomputed by the next PFCOUNT)".to_string(),
+    });
+    lines.push(String::new());
+    lines.push("Press D for the raw bytes, or A to PFADD elements (dev profiles).".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_give_the_encoding_and_cached_cardinality() {
+        let mut sparse = b"HYLL\x01\x00\x00\x00".to_vec();
+        sparse.extend(3u64.to_le_bytes());
+        sparse.extend([0x5a, 0x7f]);
+        let header = parse(&sparse).unwrap();
+        assert_eq!(header.encoding, HllEncoding::Sparse);
+        assert_eq!(header.cached_cardinality, Some(3));
+
+        let mut dense = b"HYLL\x00\x00\x00\x00".to_vec();
+        dense.extend([0, 0, 0, 0, 0, 0, 0, 0x80]);
+        let header = parse(&dense).unwrap();
+        assert_eq!(header.encoding, HllEncoding::Dense);
+        assert_eq!(header.cached_cardinality, None);
+
+        assert_eq!(parse(b"HYLL"), None);
+        assert_eq!(parse(b"hello, this is not a hyperloglog"), None);
+
+        let text = describe(&header, dense.len(), Ok(42));
+        assert!(text.contains("PFCOUNT): 42"), "{}", text);
+        assert!(text.contains("Encoding: dense, 16 bytes"), "{}", text);
+        assert!(text.contains("stale"), "{}", text);
+    }
+}
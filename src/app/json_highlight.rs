@@ -0,0 +1,126 @@
+/// Lexical category of a piece of JSON text, used to pick a display color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonTokenKind {
+    Key,
+    String,
+    Number,
+    Boolean,
+    Null,
+    Punctuation,
+    Whitespace,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonToken {
+    pub kind: JsonTokenKind,
+    pub text: String,
+}
+
+/// Returns highlighted lines when `text` is a JSON object or array, `None` otherwise.
+/// Scalars are left alone so plain strings such as `"42"` keep their normal rendering.
+pub fn highlight_json(text: &str) -> Option<Vec<Vec<JsonToken>>> {
+    let trimmed = text.trim_start();
+    if !(trimmed.starts_with('{') || trimmed.starts_with('[')) {
+        return None;
+    }
+    serde_json::from_str::<serde_json::Value>(text).ok()?;
+    Some(text.lines().map(tokenize_line).collect())
+}
+
+// JSON strings cannot contain raw newlines, so every token fits on one line.
+fn tokenize_line(line: &str) -> Vec<JsonToken> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let start = idx;
+        let ch = chars[idx];
+        let kind = if ch == '"' {
+            idx += 1;
+            while idx < chars.len() && chars[idx] != '"' {
+                if chars[idx] == '\\' {
+                    idx += 1;
+                }
+                idx += 1;
+            }
+            idx = (idx + 1).min(chars.len());
+            let next_significant = chars[idx..].iter().find(|c| !c.is_whitespace());
+            if next_significant == Some(&':') {
+                JsonTokenKind::Key
+            } else {
+                JsonTokenKind::String
+            }
+        } else if ch.is_whitespace() {
+            while idx < chars.len() && chars[idx].is_whitespace() {
+                idx += 1;
+            }
+            JsonTokenKind::Whitespace
+        } else if ch == '-' || ch.is_ascii_digit() {
+            while idx < chars.len() && is_number_char(chars[idx]) {
+                idx += 1;
+            }
+            JsonTokenKind::Number
+        } else if ch.is_ascii_alphabetic() {
+            while idx < chars.len() && chars[idx].is_ascii_alphabetic() {
+                idx += 1;
+            }
+            match chars[start..idx].iter().collect::<String>().as_str() {
+                "true" | "false" => JsonTokenKind::Boolean,
+                "null" => JsonTokenKind::Null,
+                _ => JsonTokenKind::Punctuation,
+            }
+        } else {
+            idx += 1;
+            JsonTokenKind::Punctuation
+        };
+
+        tokens.push(JsonToken {
+            kind,
+            text: chars[start..idx].iter().collect(),
+        });
+    }
+
+    tokens
+}
+
+fn is_number_char(ch: char) -> bool {
+    ch.is_ascii_digit() || matches!(ch, '-' | '+' | '.' | 'e' | 'E')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(line: &[JsonToken]) -> Vec<JsonTokenKind> {
+        line.iter()
+            .filter(|token| token.kind != JsonTokenKind::Whitespace)
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn highlight_json_classifies_tokens() {
+        let lines = highlight_json(r#"{"name": "a\"b", "n": -1.5e3, "ok": true, "x": null}"#)
+            .expect("json");
+        assert_eq!(lines.len(), 1);
+        use JsonTokenKind::*;
+        assert_eq!(
+            kinds(&lines[0]),
+            vec![
+                Punctuation, Key, Punctuation, String, Punctuation, Key, Punctuation, Number,
+                Punctuation, Key, Punctuation, Boolean, Punctuation, Key, Punctuation, Null,
+                Punctuation
+            ]
+        );
+        let rebuilt: std::string::String = lines[0].iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(rebuilt, r#"{"name": "a\"b", "n": -1.5e3, "ok": true, "x": null}"#);
+    }
+
+    #[test]
+    fn highlight_json_skips_scalars_and_invalid_text() {
+        assert!(highlight_json("42").is_none());
+        assert!(highlight_json("\"text\"").is_none());
+        assert!(highlight_json("{not json").is_none());
+    }
+}
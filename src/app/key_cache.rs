@@ -0,0 +1,119 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use super::value_viewer::ValueViewer;
+
+/// Recently viewed keys kept at most.
+const CACHE_CAPACITY: usize = 32;
+
+/// A key as the value panel last fetched it.
+#[derive(Debug, Clone)]
+pub struct CachedKey {
+    /// The value panel right after the fetch, before any sorting or filtering.
+    pub viewer: ValueViewer,
+    /// `TYPE` reply, as kept in `type_map`.
+    pub key_type: String,
+    /// `TTL` reply, as kept in `ttl_map`.
+    pub ttl: i64,
+    pub stored_at: Instant,
+}
+
+impl CachedKey {
+    /// Still worth showing: younger than `max_age` and not past its own expiry.
+    fn is_fresh(&self, max_age: Duration, now: Instant) -> bool {
+        let age = now.saturating_duration_since(self.stored_at);
+        age < max_age && (self.ttl < 0 || age < Duration::from_secs(self.ttl as u64))
+    }
+}
+
+/// Values, types, TTLs and sizes of recently viewed keys, so selecting one again skips
+/// the server. Entries age out and are dropped when the key changes.
+#[derive(Debug, Default, Clone)]
+pub struct KeyCache {
+    entries: HashMap<String, CachedKey>,
+    /// Keys from least to most recently stored.
+    order: VecDeque<String>,
+}
+
+impl KeyCache {
+    /// The cached copy of `key`, unless it is older than `max_age` or its TTL ran out.
+    pub fn get(&self, key: &str, max_age: Duration, now: Instant) -> Option<&CachedKey> {
+        self.entries.get(key).filter(|entry| entry.is_fresh(max_age, now))
+    }
+
+    pub fn insert(&mut self, key: String, entry: CachedKey) {
+        self.order.retain(|cached| *cached != key);
+        self.order.push_back(key.clone());
+        self.entries.insert(key, entry);
+        while self.order.len() > CACHE_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    pub fn invalidate(&mut self, key: &str) {
+        if self.entries.remove(key).is_some() {
+            self.order.retain(|cached| cached != key);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(ttl: i64, stored_at: Instant) -> CachedKey {
+        CachedKey {
+            viewer: ValueViewer::default(),
+            key_type: "string".to_string(),
+            ttl,
+            stored_at,
+        }
+    }
+
+    #[test]
+    fn entries_age_out_expire_and_evict_oldest() {
+        let start = Instant::now();
+        let max_age = Duration::from_secs(30);
+        let mut cache = KeyCache::default();
+        cache.insert("a".to_string(), entry(-1, start));
+        cache.insert("b".to_string(), entry(5, start));
+        assert!(cache.get("a", max_age, start + Duration::from_secs(29)).is_some());
+        assert!(cache.get("a", max_age, start + Duration::from_secs(30)).is_none());
+        assert!(cache.get("b", max_age, start + Duration::from_secs(4)).is_some());
+        assert!(cache.get("b", max_age, start + Duration::from_secs(5)).is_none());
+
+        cache.invalidate("a");
+        assert!(cache.get("a", max_age, start).is_none());
+        assert_eq!(cache.len(), 1);
+
+        for idx in 0..CACHE_CAPACITY {
+            cache.insert(format!("k{}", idx), entry(-1, start));
+        }
+        assert_eq!(cache.len(), CACHE_CAPACITY);
+        assert!(cache.get("b", max_age, start).is_none());
+        assert!(cache.get("k0", max_age, start).is_some());
+
+        // Storing a key again makes it the most recent
+        cache.insert("k0".to_string(), entry(-1, start));
+        cache.insert("new".to_string(), entry(-1, start));
+        assert!(cache.get("k0", max_age, start).is_some());
+        assert!(cache.get("k1", max_age, start).is_none());
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
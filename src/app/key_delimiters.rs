@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use super::app_key_tree::glob_escape;
+
+/// Characters that separate key tree levels, highest priority first.
+///
+/// Each key is split on the highest-priority delimiter it contains, so `user:jane.doe`
+/// stays one level deep while `seed.dot.num_1` splits on the dots. Levels are identified
+/// by their canonical prefix: the path joined with the primary (first) delimiter, which
+/// for a single delimiter is exactly the literal key prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyDelimiters {
+    chars: Vec<char>,
+}
+
+impl Default for KeyDelimiters {
+    fn default() -> Self {
+        Self { chars: vec![':'] }
+    }
+}
+
+impl KeyDelimiters {
+    /// Delimiters from a settings string such as `":/."`; duplicates are dropped and an
+    /// empty string falls back to `:`.
+    pub fn new(chars: &str) -> Self {
+        let mut unique = Vec::new();
+        for ch in chars.chars().filter(|ch| !ch.is_whitespace()) {
+            if !unique.contains(&ch) {
+                unique.push(ch);
+            }
+        }
+        if unique.is_empty() {
+            return Self::default();
+        }
+        Self { chars: unique }
+    }
+
+    pub fn primary(&self) -> char {
+        self.chars[0]
+    }
+
+    pub fn is_single(&self) -> bool {
+        self.chars.len() == 1
+    }
+
+    /// The delimiter `key` is split on, if it contains any.
+    pub fn for_key(&self, key: &str) -> Option<char> {
+        self.chars.iter().copied().find(|&ch| key.contains(ch))
+    }
+
+    /// Path segments of `key`.
+    pub fn split<'a>(&self, key: &'a str) -> Vec<&'a str> {
+        match self.for_key(key) {
+            Some(delimiter) => key.split(delimiter).collect(),
+            None => vec![key],
+        }
+    }
+
+    /// `key` with its delimiter replaced by the primary one, so level prefixes can be
+    /// compared with `starts_with` regardless of the convention a key follows.
+    pub fn canonical<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        match self.for_key(key) {
+            Some(delimiter) if delimiter != self.primary() => Cow::Owned(key.replace(delimiter, &self.primary().to_string())),
+            _ => Cow::Borrowed(key),
+        }
+    }
+
+    /// `SCAN MATCH` pattern for every key beneath the canonical `prefix`. With several
+    /// delimiters each separator becomes a class, so results still need a canonical check.
+    pub fn prefix_pattern(&self, prefix: &str) -> String {
+        if self.is_single() {
+            return format!("{}*", glob_escape(prefix));
+        }
+        let class = self.class_pattern();
+        let mut pattern = String::new();
+        for segment in prefix.split_terminator(self.primary()) {
+            pattern.push_str(&glob_escape(segment));
+            pattern.push_str(&class);
+        }
+        pattern.push('*');
+        pattern
+    }
+
+    fn class_pattern(&self) -> String {
+        let mut class = String::from("[");
+        for &ch in &self.chars {
+            if matches!(ch, '^' | ']' | '-' | '\\') {
+                class.push('\\');
+            }
+            class.push(ch);
+        }
+        class.push(']');
+        class
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::app_key_tree::glob_match;
+
+    #[test]
+    fn keys_split_on_their_highest_priority_delimiter() {
+        let delimiters = KeyDelimiters::new(":/.");
+        assert_eq!(delimiters.split("seed:level1:3"), ["seed", "level1", "3"]);
+        assert_eq!(delimiters.split("seed/path/num_1"), ["seed", "path", "num_1"]);
+        assert_eq!(delimiters.split("user:jane.doe"), ["user", "jane.doe"]);
+        assert_eq!(delimiters.split("plain"), ["plain"]);
+        assert_eq!(delimiters.canonical("seed.dot.num_1"), "seed:dot:num_1");
+        assert_eq!(delimiters.canonical("user:jane.doe"), "user:jane.doe");
+    }
+
+    #[test]
+    fn prefix_pattern_matches_every_convention() {
+        let delimiters = KeyDelimiters::new(":/.");
+        let pattern = delimiters.prefix_pattern("seed:path:");
+        assert_eq!(pattern, "seed[:/.]path[:/.]*");
+        assert!(glob_match(&pattern, "seed/path/num_1"));
+        assert!(glob_match(&pattern, "seed:path:1"));
+        assert!(!glob_match(&pattern, "seed/paths/1"));
+
+        assert_eq!(KeyDelimiters::default().prefix_pattern("a*b:"), "a\\*b:*");
+        assert_eq!(KeyDelimiters::new(""), KeyDelimiters::default());
+    }
+}
@@ -0,0 +1,161 @@
+use redis::aio::MultiplexedConnection;
+use redis::{RedisResult, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+
+/// SCAN COUNT hint used while walking the keyspace for an analysis.
+pub const SAMPLER_SCAN_COUNT: usize = 500;
+
+/// What an analyzer learned about one key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySample {
+    pub key: String,
+    /// Lowercase TYPE reply, e.g. `hash`.
+    pub key_type: String,
+    /// MEMORY USAGE in bytes, when the server allows it.
+    pub memory: Option<u64>,
+    /// Bytes for strings, elements for collections.
+    pub length: Option<u64>,
+    /// Seconds until expiry; `None` for keys without one.
+    pub ttl: Option<u64>,
+    /// OBJECT FREQ access counter, only available under an LFU maxmemory-policy.
+    pub freq: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum KeySamplerEvent {
+    Samples { samples: Vec<KeySample>, cursor: u64 },
+    Done,
+    Error(String),
+}
+
+/// Background SCAN over the whole keyspace that describes each key it finds.
+/// Dropping the sampler stops the walk.
+pub struct KeySampler {
+    pub scanned: usize,
+    pub done: bool,
+    job: BackgroundJob<KeySamplerEvent>,
+}
+
+impl KeySampler {
+    pub fn spawn(con: MultiplexedConnection, pattern: String) -> Self {
+        let job = BackgroundJob::spawn(|sender| sample_keys(con, pattern, sender));
+        Self {
+            scanned: 0,
+            done: false,
+            job,
+        }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<KeySamplerEvent> {
+        self.job.drain()
+    }
+}
+
+async fn sample_keys(mut con: MultiplexedConnection, pattern: String, sender: UnboundedSender<KeySamplerEvent>) {
+    let mut cursor: u64 = 0;
+    loop {
+        let scanned = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(SAMPLER_SCAN_COUNT)
+            .query_async::<(u64, Vec<String>)>(&mut con)
+            .await;
+        let (next_cursor, keys) = match scanned {
+            Ok(reply) => reply,
+            Err(e) => {
+                let _ = sender.send(KeySamplerEvent::Error(format!("SCAN failed: {}", e)));
+                return;
+            }
+        };
+        cursor = next_cursor;
+        let samples = match describe_keys(&mut con, keys).await {
+            Ok(samples) => samples,
+            Err(e) => {
+                let _ = sender.send(KeySamplerEvent::Error(e.to_string()));
+                return;
+            }
+        };
+        if sender.send(KeySamplerEvent::Samples { samples, cursor }).is_err() {
+            return;
+        }
+        if cursor == 0 {
+            let _ = sender.send(KeySamplerEvent::Done);
+            return;
+        }
+    }
+}
+
+/// Two pipelined round-trips per batch: TYPE, MEMORY USAGE, TTL and OBJECT
+/// FREQ, then the type-specific length.
+pub async fn describe_keys(con: &mut MultiplexedConnection, keys: Vec<String>) -> RedisResult<Vec<KeySample>> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut pipe = redis::pipe();
+    pipe.ignore_errors();
+    for key in &keys {
+        pipe.cmd("TYPE").arg(key);
+        pipe.cmd("MEMORY").arg("USAGE").arg(key);
+        pipe.cmd("TTL").arg(key);
+        pipe.cmd("OBJECT").arg("FREQ").arg(key);
+    }
+    let replies = pipe.query_async::<Vec<RedisResult<Value>>>(con).await?;
+    let mut samples: Vec<KeySample> = keys
+        .into_iter()
+        .zip(replies.chunks(4))
+        .map(|(key, replies)| KeySample {
+            key,
+            key_type: match &replies[0] {
+                Ok(Value::SimpleString(key_type)) => key_type.to_lowercase(),
+                Ok(Value::Okay) => "ok".to_string(),
+                _ => "unknown".to_string(),
+            },
+            memory: as_u64(&replies[1]),
+            length: None,
+            ttl: as_u64(&replies[2]),
+            freq: as_u64(&replies[3]),
+        })
+        .collect();
+
+    let mut pipe = redis::pipe();
+    pipe.ignore_errors();
+    let mut measured = Vec::new();
+    for (idx, sample) in samples.iter().enumerate() {
+        if let Some(command) = length_command(&sample.key_type) {
+            pipe.cmd(command).arg(&sample.key);
+            measured.push(idx);
+        }
+    }
+    if !measured.is_empty() {
+        let lengths = pipe.query_async::<Vec<RedisResult<Value>>>(con).await?;
+        for (idx, length) in measured.into_iter().zip(lengths) {
+            samples[idx].length = as_u64(&length);
+        }
+    }
+    Ok(samples)
+}
+
+fn as_u64(reply: &RedisResult<Value>) -> Option<u64> {
+    match reply {
+        Ok(Value::Int(n)) => u64::try_from(*n).ok(),
+        _ => None,
+    }
+}
+
+/// Command returning the size of a key of `key_type`.
+pub fn length_command(key_type: &str) -> Option<&'static str> {
+    match key_type {
+        "string" => Some("STRLEN"),
+        "list" => Some("LLEN"),
+        "set" => Some("SCARD"),
+        "zset" => Some("ZCARD"),
+        "hash" => Some("HLEN"),
+        "stream" => Some("XLEN"),
+        _ => None,
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+
+pub const DEFAULT_SCAN_PATTERN: &str = "*";
+pub const DEFAULT_SCAN_COUNT: usize = 1000;
+
+#[derive(Debug)]
+pub enum KeyScanEvent {
+    /// `DBSIZE`, sent before the first batch of a root scan.
+    Total(u64),
+    Batch {
+        keys: Vec<String>,
+        cursor: u64,
+        elapsed: Duration,
+    },
+    Done,
+    Error(String),
+}
+
+/// Background `SCAN MATCH <pattern>` loop for one level of the key tree.
+///
+/// Batches are streamed over a channel so the UI keeps drawing and handling
+/// keys while a large keyspace is scanned. Dropping the scan cancels it.
+pub struct KeyScan {
+    /// Level prefix being scanned, as stored in `App::loaded_prefixes`.
+    pub prefix: String,
+    pub found: usize,
+    pub cursor: u64,
+    pub total: Option<u64>,
+    job: BackgroundJob<KeyScanEvent>,
+}
+
+impl KeyScan {
+    /// Scans `pattern` in batches of `count`, optionally only keys of `key_type`. `DBSIZE` is
+    /// fetched first when `count_total` is set so the gauge can show a ratio.
+    pub fn spawn(
+        con: MultiplexedConnection,
+        prefix: String,
+        pattern: String,
+        count: usize,
+        count_total: bool,
+        key_type: Option<&'static str>,
+    ) -> Self {
+        let job = BackgroundJob::spawn(|sender| scan_keys(con, pattern, count, count_total, key_type, sender));
+        Self {
+            prefix,
+            found: 0,
+            cursor: 0,
+            total: None,
+            job,
+        }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<KeyScanEvent> {
+        self.job.drain()
+    }
+
+    /// Share of the keyspace found so far, when the total is known.
+    pub fn progress(&self) -> Option<f64> {
+        let total = self.total.filter(|total| *total > 0)?;
+        Some((self.found as f64 / total as f64).min(1.0))
+    }
+}
+
+async fn scan_keys(
+    mut con: MultiplexedConnection,
+    pattern: String,
+    count: usize,
+    count_total: bool,
+    key_type: Option<&'static str>,
+    sender: UnboundedSender<KeyScanEvent>,
+) {
+    if count_total {
+        if let Ok(total) = redis::cmd("DBSIZE").query_async::<u64>(&mut con).await {
+            let _ = sender.send(KeyScanEvent::Total(total));
+        }
+    }
+
+    let mut cursor: u64 = 0;
+    loop {
+        let started = Instant::now();
+        let mut cmd = redis::cmd("SCAN");
+        cmd.arg(cursor).arg("MATCH").arg(&pattern).arg("COUNT").arg(count);
+        if let Some(key_type) = key_type {
+            cmd.arg("TYPE").arg(key_type);
+        }
+        let result = cmd
+            .query_async::<(u64, Vec<String>)>(&mut con)
+            .await;
+        let event = match result {
+            Ok((next_cursor, keys)) => {
+                cursor = next_cursor;
+                KeyScanEvent::Batch {
+                    keys,
+                    cursor,
+                    elapsed: started.elapsed(),
+                }
+            }
+            Err(e) => {
+                let _ = sender.send(KeyScanEvent::Error(e.to_string()));
+                return;
+            }
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+        if cursor == 0 {
+            let _ = sender.send(KeyScanEvent::Done);
+            return;
+        }
+    }
+}
+
+/// Background rescan of a level that is already loaded. Keys are collected until the
+/// scan finishes and then merged into the tree in one go, so the view never empties.
+pub struct TreeRefresh {
+    pub scan: KeyScan,
+    pub keys: Vec<String>,
+    /// Started with F5 rather than by the auto-refresh timer.
+    pub manual: bool,
+}
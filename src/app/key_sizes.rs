@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+
+/// Rows on either side of the selection whose types and sizes are fetched.
+pub const VISIBLE_KEY_WINDOW: usize = 30;
+/// Keys typed or sized per pipelined round-trip.
+pub const VISIBLE_KEY_BATCH: usize = 50;
+
+/// Lazily populated `MEMORY USAGE` results for the key list size column.
+#[derive(Debug, Default, Clone)]
+pub struct KeySizes {
+    pub enabled: bool,
+    /// `None` records keys whose size could not be read so they are not retried.
+    pub sizes: HashMap<String, Option<u64>>,
+}
+
+impl KeySizes {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    pub fn clear(&mut self) {
+        self.sizes.clear();
+    }
+
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.sizes.get(key).copied().flatten()
+    }
+
+    /// Keys from `candidates` that have not been sized yet, up to one batch.
+    pub fn missing<'a>(&self, candidates: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+        candidates
+            .into_iter()
+            .filter(|key| !self.sizes.contains_key(*key))
+            .take(VISIBLE_KEY_BATCH)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Index range of rows around `selected` that count as visible.
+pub fn visible_window(selected: usize, len: usize) -> std::ops::Range<usize> {
+    let start = selected.saturating_sub(VISIBLE_KEY_WINDOW);
+    let end = (selected + VISIBLE_KEY_WINDOW + 1).min(len);
+    start..end.max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_skips_known_and_failed_keys() {
+        let mut sizes = KeySizes::default();
+        sizes.sizes.insert("a".to_string(), Some(10));
+        sizes.sizes.insert("b".to_string(), None);
+        let keys = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(sizes.missing(&keys), vec!["c".to_string()]);
+        assert_eq!(sizes.get("a"), Some(10));
+        assert_eq!(sizes.get("b"), None);
+    }
+
+    #[test]
+    fn visible_window_clamps_to_list() {
+        assert_eq!(visible_window(0, 5), 0..5);
+        assert_eq!(visible_window(100, 200), 70..131);
+    }
+}
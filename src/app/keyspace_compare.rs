@@ -0,0 +1,295 @@
+use std::collections::BTreeSet;
+
+use redis::aio::MultiplexedConnection;
+use redis::{RedisResult, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+use super::key_sampler::SAMPLER_SCAN_COUNT;
+use super::value_format::{format_bytes_block, format_bytes_inline};
+
+/// Common keys whose values are fetched and compared per pipelined round-trip.
+const COMPARE_BATCH: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompareStatus {
+    OnlyLeft,
+    OnlyRight,
+    TypeMismatch { left: String, right: String },
+    ValueMismatch,
+}
+
+/// A key that differs between the two sides.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareRow {
+    pub key: String,
+    pub status: CompareStatus,
+}
+
+#[derive(Debug)]
+pub enum CompareEvent {
+    Scanned { left: usize, right: usize },
+    Compared { rows: Vec<CompareRow>, identical: usize, compared: usize },
+    Done,
+    Error(String),
+}
+
+/// Background comparison of `pattern` on two connections: both sides are
+/// scanned fully, then common keys are compared type by type and value by value.
+/// Dropping it stops the comparison.
+pub struct KeyspaceCompare {
+    job: BackgroundJob<CompareEvent>,
+}
+
+impl KeyspaceCompare {
+    pub fn spawn(left: MultiplexedConnection, right: MultiplexedConnection, pattern: String) -> Self {
+        let job = BackgroundJob::spawn(|sender| compare(left, right, pattern, sender));
+        Self { job }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<CompareEvent> {
+        self.job.drain()
+    }
+}
+
+async fn compare(
+    mut left: MultiplexedConnection,
+    mut right: MultiplexedConnection,
+    pattern: String,
+    sender: UnboundedSender<CompareEvent>,
+) {
+    let (left_keys, right_keys) = match tokio::try_join!(scan_all(&mut left, &pattern), scan_all(&mut right, &pattern)) {
+        Ok(keys) => keys,
+        Err(e) => {
+            let _ = sender.send(CompareEvent::Error(format!("SCAN failed: {}", e)));
+            return;
+        }
+    };
+    let _ = sender.send(CompareEvent::Scanned {
+        left: left_keys.len(),
+        right: right_keys.len(),
+    });
+
+    let mut rows: Vec<CompareRow> = left_keys
+        .difference(&right_keys)
+        .map(|key| CompareRow { key: key.clone(), status: CompareStatus::OnlyLeft })
+        .chain(right_keys.difference(&left_keys).map(|key| CompareRow {
+            key: key.clone(),
+            status: CompareStatus::OnlyRight,
+        }))
+        .collect();
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    if sender.send(CompareEvent::Compared { rows, identical: 0, compared: 0 }).is_err() {
+        return;
+    }
+
+    let common: Vec<String> = left_keys.intersection(&right_keys).cloned().collect();
+    for batch in common.chunks(COMPARE_BATCH) {
+        let event = match compare_batch(&mut left, &mut right, batch).await {
+            Ok(rows) => CompareEvent::Compared {
+                identical: batch.len() - rows.len(),
+                compared: batch.len(),
+                rows,
+            },
+            Err(e) => CompareEvent::Error(e.to_string()),
+        };
+        let failed = matches!(event, CompareEvent::Error(_));
+        if sender.send(event).is_err() || failed {
+            return;
+        }
+    }
+    let _ = sender.send(CompareEvent::Done);
+}
+
+async fn scan_all(con: &mut MultiplexedConnection, pattern: &str) -> RedisResult<BTreeSet<String>> {
+    let mut keys = BTreeSet::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let (next_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(SAMPLER_SCAN_COUNT)
+            .query_async(con)
+            .await?;
+        keys.extend(batch);
+        cursor = next_cursor;
+        if cursor == 0 {
+            return Ok(keys);
+        }
+    }
+}
+
+async fn compare_batch(
+    left: &mut MultiplexedConnection,
+    right: &mut MultiplexedConnection,
+    keys: &[String],
+) -> RedisResult<Vec<CompareRow>> {
+    let (left_types, right_types) = tokio::try_join!(key_types(left, keys), key_types(right, keys))?;
+    let same_type: Vec<(&String, &String)> = keys
+        .iter()
+        .zip(&left_types)
+        .zip(&right_types)
+        .filter(|((_, l), r)| l == r)
+        .map(|((key, key_type), _)| (key, key_type))
+        .collect();
+    let (left_values, right_values) =
+        tokio::try_join!(fetch_values(left, &same_type), fetch_values(right, &same_type))?;
+
+    let mut rows: Vec<CompareRow> = keys
+        .iter()
+        .zip(left_types.iter().zip(&right_types))
+        .filter(|(_, (l, r))| l != r)
+        .map(|(key, (l, r))| CompareRow {
+            key: key.clone(),
+            status: CompareStatus::TypeMismatch { left: l.clone(), right: r.clone() },
+        })
+        .collect();
+    for (((key, _), l), r) in same_type.iter().zip(left_values).zip(right_values) {
+        if l != r {
+            rows.push(CompareRow { key: (*key).clone(), status: CompareStatus::ValueMismatch });
+        }
+    }
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(rows)
+}
+
+async fn key_types(con: &mut MultiplexedConnection, keys: &[String]) -> RedisResult<Vec<String>> {
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.cmd("TYPE").arg(key);
+    }
+    pipe.query_async(con).await
+}
+
+/// Values of `(key, type)` pairs in a form that compares equal when the contents do.
+pub async fn fetch_values(con: &mut MultiplexedConnection, keys: &[(&String, &String)]) -> RedisResult<Vec<Value>> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut pipe = redis::pipe();
+    pipe.ignore_errors();
+    for (key, key_type) in keys {
+        let (command, args) = read_command(key_type);
+        pipe.cmd(command).arg(*key).arg(args);
+    }
+    let replies: Vec<RedisResult<Value>> = pipe.query_async(con).await?;
+    Ok(keys
+        .iter()
+        .zip(replies)
+        .map(|((_, key_type), reply)| match reply {
+            Ok(value) => canonical(key_type, value),
+            Err(e) => Value::SimpleString(format!("(error) {}", e)),
+        })
+        .collect())
+}
+
+/// Command and trailing arguments reading the whole value of a key of `key_type`.
+fn read_command(key_type: &str) -> (&'static str, &'static [&'static str]) {
+    match key_type {
+        "string" => ("GET", &[]),
+        "list" => ("LRANGE", &["0", "-1"]),
+        "set" => ("SMEMBERS", &[]),
+        "zset" => ("ZRANGE", &["0", "-1", "WITHSCORES"]),
+        "hash" => ("HGETALL", &[]),
+        "stream" => ("XRANGE", &["-", "+"]),
+        _ => ("DUMP", &[]),
+    }
+}
+
+/// Orders unordered collections so equal sets and hashes compare equal.
+pub fn canonical(key_type: &str, value: Value) -> Value {
+    match (key_type, value) {
+        ("set", Value::Array(mut members)) | ("set", Value::Set(mut members)) => {
+            members.sort_by_key(sort_key);
+            Value::Array(members)
+        }
+        ("hash", Value::Map(pairs)) => sorted_pairs(pairs),
+        ("hash", Value::Array(flat)) => {
+            let mut flat = flat.into_iter();
+            let mut pairs = Vec::new();
+            while let (Some(field), Some(value)) = (flat.next(), flat.next()) {
+                pairs.push((field, value));
+            }
+            sorted_pairs(pairs)
+        }
+        (_, value) => value,
+    }
+}
+
+fn sorted_pairs(mut pairs: Vec<(Value, Value)>) -> Value {
+    pairs.sort_by_key(|(field, _)| sort_key(field));
+    Value::Array(pairs.into_iter().flat_map(|(field, value)| [field, value]).collect())
+}
+
+fn sort_key(value: &Value) -> Vec<u8> {
+    match value {
+        Value::BulkString(bytes) => bytes.clone(),
+        Value::SimpleString(text) => text.as_bytes().to_vec(),
+        other => format!("{:?}", other).into_bytes(),
+    }
+}
+
+/// Display lines of a value read by `fetch_values`, for the side-by-side drill-down.
+pub fn value_lines(key_type: &str, value: &Value) -> Vec<String> {
+    match (key_type, value) {
+        (_, Value::Nil) => vec!["(missing)".to_string()],
+        ("hash", Value::Array(flat)) | ("zset", Value::Array(flat)) => flat
+            .chunks(2)
+            .map(|pair| {
+                let first = inline(&pair[0]);
+                let second = pair.get(1).map(inline).unwrap_or_default();
+                if key_type == "hash" {
+                    format!("{} => {}", first, second)
+                } else {
+                    format!("{} ({})", first, second)
+                }
+            })
+            .collect(),
+        (_, Value::Array(items)) => items.iter().map(inline).collect(),
+        (_, Value::BulkString(bytes)) => format_bytes_block(bytes).lines().map(str::to_string).collect(),
+        (_, other) => vec![inline(other)],
+    }
+}
+
+fn inline(value: &Value) -> String {
+    match value {
+        Value::BulkString(bytes) => format_bytes_inline(bytes),
+        Value::SimpleString(text) => text.clone(),
+        Value::Int(n) => n.to_string(),
+        Value::Double(n) => n.to_string(),
+        Value::Nil => "(nil)".to_string(),
+        Value::Array(items) => format!("[{}]", items.iter().map(inline).collect::<Vec<_>>().join(", ")),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn unordered_collections_compare_by_content() {
+        let left = canonical("set", Value::Array(vec![bulk("b"), bulk("a")]));
+        let right = canonical("set", Value::Array(vec![bulk("a"), bulk("b")]));
+        assert_eq!(left, right);
+
+        let left = canonical("hash", Value::Array(vec![bulk("f2"), bulk("2"), bulk("f1"), bulk("1")]));
+        let right = canonical("hash", Value::Map(vec![(bulk("f1"), bulk("1")), (bulk("f2"), bulk("2"))]));
+        assert_eq!(left, right);
+
+        let left = canonical("list", Value::Array(vec![bulk("b"), bulk("a")]));
+        let right = canonical("list", Value::Array(vec![bulk("a"), bulk("b")]));
+        assert_ne!(left, right);
+
+        let hash = canonical("hash", Value::Array(vec![bulk("f"), bulk("1")]));
+        assert_eq!(value_lines("hash", &hash), vec!["f => 1"]);
+        assert_eq!(value_lines("string", &Value::Nil), vec!["(missing)"]);
+    }
+}
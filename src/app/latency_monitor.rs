@@ -0,0 +1,160 @@
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+
+/// Time between PING round-trip samples.
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+/// LATENCY LATEST/HISTORY are re-read every this many pings.
+const EVENTS_EVERY_PINGS: u32 = 5;
+
+/// A latency event tracked by the server's latency monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyEvent {
+    pub name: String,
+    pub latest_ms: u64,
+    pub max_ms: u64,
+    /// `(unix seconds, milliseconds)` samples from LATENCY HISTORY, oldest first.
+    pub history: Vec<(i64, u64)>,
+}
+
+#[derive(Debug)]
+pub enum LatencyMonitorEvent {
+    Ping(Duration),
+    Events(Vec<LatencyEvent>),
+    Error(String),
+}
+
+/// Background sampler of PING round-trips and the server's latency monitor.
+/// Dropping it stops sampling.
+pub struct LatencyMonitor {
+    job: BackgroundJob<LatencyMonitorEvent>,
+}
+
+impl LatencyMonitor {
+    pub fn spawn(con: MultiplexedConnection) -> Self {
+        let job = BackgroundJob::spawn(|sender| sample(con, sender));
+        Self { job }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<LatencyMonitorEvent> {
+        self.job.drain()
+    }
+}
+
+async fn sample(mut con: MultiplexedConnection, sender: UnboundedSender<LatencyMonitorEvent>) {
+    let mut interval = tokio::time::interval(PING_INTERVAL);
+    let mut pings: u32 = 0;
+    loop {
+        interval.tick().await;
+        let started = Instant::now();
+        let event = match redis::cmd("PING").query_async::<()>(&mut con).await {
+            Ok(()) => LatencyMonitorEvent::Ping(started.elapsed()),
+            Err(e) => LatencyMonitorEvent::Error(format!("PING failed: {}", e)),
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+        if pings.is_multiple_of(EVENTS_EVERY_PINGS) {
+            let event = match fetch_events(&mut con).await {
+                Ok(events) => LatencyMonitorEvent::Events(events),
+                Err(e) => LatencyMonitorEvent::Error(format!("LATENCY LATEST failed: {}", e)),
+            };
+            if sender.send(event).is_err() {
+                return;
+            }
+        }
+        pings = pings.wrapping_add(1);
+    }
+}
+
+async fn fetch_events(con: &mut MultiplexedConnection) -> redis::RedisResult<Vec<LatencyEvent>> {
+    let latest = redis::cmd("LATENCY").arg("LATEST").query_async::<Value>(con).await?;
+    let mut events = parse_latency_latest(&latest);
+    for event in &mut events {
+        let history = redis::cmd("LATENCY")
+            .arg("HISTORY")
+            .arg(&event.name)
+            .query_async::<Value>(con)
+            .await?;
+        event.history = parse_latency_history(&history);
+    }
+    Ok(events)
+}
+
+fn int(value: &Value) -> Option<i64> {
+    match value {
+        Value::Int(n) => Some(*n),
+        other => crate::command::resp::value_text(other)?.parse().ok(),
+    }
+}
+
+/// Parses LATENCY LATEST: `[[name, timestamp, latest-ms, max-ms, ...], ...]`.
+pub fn parse_latency_latest(reply: &Value) -> Vec<LatencyEvent> {
+    let Value::Array(rows) = reply else {
+        return Vec::new();
+    };
+    let mut events: Vec<LatencyEvent> = rows
+        .iter()
+        .filter_map(|row| {
+            let Value::Array(fields) = row else {
+                return None;
+            };
+            Some(LatencyEvent {
+                name: crate::command::resp::value_text(fields.first()?)?,
+                latest_ms: int(fields.get(2)?)?.max(0) as u64,
+                max_ms: int(fields.get(3)?)?.max(0) as u64,
+                history: Vec::new(),
+            })
+        })
+        .collect();
+    events.sort_by(|a, b| a.name.cmp(&b.name));
+    events
+}
+
+/// Parses LATENCY HISTORY: `[[timestamp, ms], ...]`.
+pub fn parse_latency_history(reply: &Value) -> Vec<(i64, u64)> {
+    let Value::Array(rows) = reply else {
+        return Vec::new();
+    };
+    let mut samples: Vec<(i64, u64)> = rows
+        .iter()
+        .filter_map(|row| match row {
+            Value::Array(pair) if pair.len() >= 2 => Some((int(&pair[0])?, int(&pair[1])?.max(0) as u64)),
+            _ => None,
+        })
+        .collect();
+    samples.sort_by_key(|(timestamp, _)| *timestamp);
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn latest_and_history_replies_are_parsed() {
+        let latest = Value::Array(vec![Value::Array(vec![
+            Value::BulkString(b"command".to_vec()),
+            Value::Int(1_700_000_100),
+            Value::Int(12),
+            Value::Int(250),
+        ])]);
+        let events = parse_latency_latest(&latest);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].name.as_str(), events[0].latest_ms, events[0].max_ms), ("command", 12, 250));
+
+        let history = Value::Array(vec![
+            Value::Array(vec![Value::Int(1_700_000_100), Value::Int(12)]),
+            Value::Array(vec![Value::Int(1_700_000_050), Value::Int(250)]),
+        ]);
+        assert_eq!(
+            parse_latency_history(&history),
+            vec![(1_700_000_050, 250), (1_700_000_100, 12)]
+        );
+    }
+}
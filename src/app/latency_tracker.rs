@@ -0,0 +1,125 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+const DEFAULT_WINDOW: usize = 256;
+
+/// Classes of round-trips the UI performs, as seen from the client side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationClass {
+    Scan,
+    Metadata,
+    Get,
+    Collection,
+    Info,
+}
+
+impl OperationClass {
+    pub const ALL: [OperationClass; 5] = [
+        OperationClass::Scan,
+        OperationClass::Metadata,
+        OperationClass::Get,
+        OperationClass::Collection,
+        OperationClass::Info,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OperationClass::Scan => "SCAN",
+            OperationClass::Metadata => "TYPE/TTL",
+            OperationClass::Get => "GET",
+            OperationClass::Collection => "COLLECTION",
+            OperationClass::Info => "INFO",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencySummary {
+    pub p50: Duration,
+    pub p99: Duration,
+    pub samples: usize,
+}
+
+/// Rolling window of observed latencies per operation class.
+#[derive(Debug, Clone)]
+pub struct LatencyTracker {
+    window: usize,
+    samples: HashMap<OperationClass, VecDeque<Duration>>,
+}
+
+impl LatencyTracker {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            samples: HashMap::new(),
+        }
+    }
+
+    pub fn record(&mut self, class: OperationClass, elapsed: Duration) {
+        let window = self.window;
+        let samples = self.samples.entry(class).or_default();
+        if samples.len() == window {
+            samples.pop_front();
+        }
+        samples.push_back(elapsed);
+    }
+
+    pub fn summary(&self, class: OperationClass) -> Option<LatencySummary> {
+        let samples = self.samples.get(&class)?;
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<Duration> = samples.iter().copied().collect();
+        sorted.sort_unstable();
+        Some(LatencySummary {
+            p50: percentile(&sorted, 50.0),
+            p99: percentile(&sorted, 99.0),
+            samples: sorted.len(),
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+/// Nearest-rank percentile over an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], pct: f64) -> Duration {
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_reports_nearest_rank_percentiles() {
+        let mut tracker = LatencyTracker::new(100);
+        for ms in 1..=100 {
+            tracker.record(OperationClass::Get, Duration::from_millis(ms));
+        }
+        let summary = tracker.summary(OperationClass::Get).unwrap();
+        assert_eq!(summary.p50, Duration::from_millis(50));
+        assert_eq!(summary.p99, Duration::from_millis(99));
+        assert_eq!(summary.samples, 100);
+        assert!(tracker.summary(OperationClass::Scan).is_none());
+    }
+
+    #[test]
+    fn record_evicts_oldest_sample_when_window_is_full() {
+        let mut tracker = LatencyTracker::new(2);
+        tracker.record(OperationClass::Info, Duration::from_millis(500));
+        tracker.record(OperationClass::Info, Duration::from_millis(1));
+        tracker.record(OperationClass::Info, Duration::from_millis(2));
+        let summary = tracker.summary(OperationClass::Info).unwrap();
+        assert_eq!(summary.samples, 2);
+        assert_eq!(summary.p99, Duration::from_millis(2));
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::Range;
+
+/// Items built beyond each edge of the viewport, so rows taller than one line and small
+/// scrolls between frames still find their items.
+const OVERSCAN: usize = 16;
+
+/// First row drawn and visible height of a list whose scroll position is kept between
+/// frames, so `zz` can center the selection and half-page jumps know the page size.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ListScroll {
+    pub offset: usize,
+    pub rows: usize,
+}
+
+impl ListScroll {
+    /// Scrolls as little as needed to keep `selected` in view, as ratatui would.
+    pub fn follow(&mut self, selected: usize, len: usize, rows: usize) {
+        self.rows = rows;
+        if rows == 0 {
+            return;
+        }
+        if selected < self.offset {
+            self.offset = selected;
+        } else if selected >= self.offset + rows {
+            self.offset = selected + 1 - rows;
+        }
+        self.offset = self.offset.min(len.saturating_sub(rows));
+    }
+
+    /// Scrolls so `selected` sits in the middle row.
+    pub fn center(&mut self, selected: usize, len: usize) {
+        self.offset = selected
+            .saturating_sub(self.rows / 2)
+            .min(len.saturating_sub(self.rows));
+    }
+
+    pub fn half_page(&self) -> usize {
+        (self.rows / 2).max(1)
+    }
+
+    /// Items of a `len` long list worth building for a frame `rows` high: the page that
+    /// will show `selected`, plus overscan on both sides.
+    pub fn window(&self, selected: usize, len: usize, rows: usize) -> Range<usize> {
+        let mut scroll = *self;
+        scroll.follow(selected, len, rows);
+        let start = scroll.offset.saturating_sub(OVERSCAN);
+        let end = (scroll.offset + rows + OVERSCAN).min(len);
+        start.min(end)..end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follow_keeps_selection_visible_and_center_moves_it_to_the_middle() {
+        let mut scroll = ListScroll::default();
+        scroll.follow(25, 100, 10);
+        assert_eq!(scroll.offset, 16);
+        // Moving back up inside the page does not scroll
+        scroll.follow(20, 100, 10);
+        assert_eq!(scroll.offset, 16);
+        scroll.center(20, 100);
+        assert_eq!(scroll.offset, 15);
+        scroll.center(98, 100);
+        assert_eq!(scroll.offset, 90);
+        scroll.follow(3, 5, 10);
+        assert_eq!(scroll.offset, 0);
+        assert_eq!(scroll.half_page(), 5);
+    }
+
+    #[test]
+    fn window_covers_the_page_of_the_selection_plus_overscan() {
+        let scroll = ListScroll { offset: 1_000, rows: 10 };
+        assert_eq!(scroll.window(1_005, 500_000, 10), 984..1_026);
+        // A selection outside the page moves the window with it
+        assert_eq!(scroll.window(5_000, 500_000, 10), 4_975..5_017);
+        assert_eq!(scroll.window(0, 500_000, 10), 0..26);
+        assert_eq!(scroll.window(3, 5, 10), 0..5);
+        assert_eq!(ListScroll::default().window(0, 0, 10), 0..0);
+    }
+}
@@ -1,29 +1,175 @@
+mod app_action_menu;
+mod app_analysis;
+mod app_bookmarks;
+mod app_breadcrumb;
+mod app_bulk_delete;
+mod app_compare;
 pub mod app_clipboard;
+mod app_config_editor;
+mod app_connect;
+mod app_delete;
+mod app_delete_preview;
+mod app_consumer_groups;
+mod app_doctor;
+mod app_functions;
+mod app_goto_key;
+mod app_hyperloglog;
+mod app_key_cache;
+mod app_key_info;
+mod app_key_tree;
+mod app_latency_panel;
+mod app_layout;
+mod app_list_motion;
+mod app_live_updates;
+mod app_monitor;
+mod app_mouse;
+mod app_paste;
+mod app_prefix_walk;
+mod app_probabilistic;
+mod app_pubsub;
+mod app_random_sample;
+mod app_read_only;
+mod app_redisearch;
+mod app_scan_presets;
+mod app_search;
+mod app_script_runner;
+mod app_session;
+mod app_snapshots;
+mod app_stats_report;
+mod app_stream_follow;
+mod app_tree_refresh;
+mod app_ttl;
+mod app_tree_view;
+mod app_value_fetch;
+mod app_value_nav;
+mod app_visible_keys;
+mod app_worker;
 mod app_fetch;
-mod value_format;
+pub mod background_job;
+pub mod bulk_delete;
+pub mod cluster;
+pub mod decompress;
+pub mod delete_preview;
+pub mod hex_view;
+pub mod hyperloglog;
+pub mod json_highlight;
+pub mod key_cache;
+pub mod key_delimiters;
+pub mod key_sampler;
+pub mod keyspace_compare;
+pub mod key_scan;
+pub mod key_sizes;
+pub mod latency_monitor;
+pub mod latency_tracker;
+pub mod list_scroll;
+pub mod monitor;
+pub mod mouse;
+pub mod notifications;
+pub mod osc52;
+pub mod prefix_walk;
+pub mod probabilistic;
+pub mod pubsub;
+pub mod value_format;
 pub mod redis_client;
 pub mod redis_stats;
+pub mod reproduce;
+pub mod replication;
+pub mod state_action_menu;
+pub mod state_analysis;
+pub mod state_bookmarks;
+pub mod state_compare;
+pub mod state_config_editor;
+pub mod state_consumer_groups;
 pub mod state_delete_dialog;
+pub mod state_doctor;
+pub mod state_folder_copy;
+pub mod state_functions;
+pub mod state_help;
+pub mod state_key_info;
+pub mod state_latency_panel;
+pub mod state_monitor;
+pub mod state_paste;
 pub mod state_profile_selector;
+pub mod state_pubsub;
+pub mod state_random_sample;
+pub mod state_redisearch;
+pub mod state_scan_presets;
+pub mod state_script_runner;
+pub mod state_snapshots;
+pub mod state_stats_report;
+pub mod state_tree_view;
+pub mod stream_tail;
+pub mod timestamp;
+pub mod value_decoder;
+pub mod value_fetch;
+pub mod value_grep;
 pub mod value_viewer;
+pub mod watch;
+pub mod worker;
 
 // use crate::search::SearchState;
 
 // REMOVE: pub mod app;
 
-use crate::command::CommandState;
-use crate::config::ConnectionProfile;
-use crate::search::SearchState;
+use crate::command::{CommandOutcome, CommandState};
+use crate::bookmarks::Bookmarks;
+use crate::app::list_scroll::ListScroll;
+use crate::keymap::{Keymap, PendingKeys};
+use crate::session::SessionState;
+use crate::app::state_help::HelpState;
+use crate::config::{ConnectionProfile, Settings};
+use crate::search::{SearchJob, SearchState};
 // REMOVE: use redis::{Client};
 pub use redis::aio::MultiplexedConnection; // Re-export for other modules
                                            // use tokio::task; // Moved to app_clipboard.rs, check if needed elsewhere here.
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
 // use crossclip::{Clipboard, SystemClipboard}; // Moved to app_clipboard.rs
+use crate::app::cluster::ClusterTopology;
+use crate::app::key_scan::{KeyScan, TreeRefresh, DEFAULT_SCAN_COUNT, DEFAULT_SCAN_PATTERN};
+use crate::app::key_delimiters::KeyDelimiters;
+use crate::app::key_cache::KeyCache;
+use crate::app::bulk_delete::BulkDelete;
+use crate::app::prefix_walk::PrefixWalk;
+use crate::app::worker::{Worker, WorkerRequest};
+use crate::app::key_sizes::KeySizes;
+use crate::app::mouse::ClickTracker;
+use crate::app::notifications::Notifications;
+use crate::app::latency_tracker::LatencyTracker;
 use crate::app::redis_client::RedisClient;
-use crate::app::redis_stats::RedisStats;
+use crate::app::redis_stats::{KeyspaceCounts, RedisStats};
+use crate::app::state_consumer_groups::ConsumerGroupState;
 use crate::app::state_delete_dialog::DeleteDialogState;
+use crate::app::state_key_info::KeyInfo;
+use crate::app::state_bookmarks::BookmarksPanelState;
 use crate::app::state_profile_selector::ProfileSelectorState;
+use crate::app::state_action_menu::ActionMenuState;
+use crate::app::state_functions::FunctionsState;
+use crate::app::state_redisearch::RediSearchState;
+use crate::app::state_scan_presets::ScanPresetPicker;
+use crate::app::state_paste::PastePreviewState;
+use crate::app::state_folder_copy::FolderCopyMenu;
+use crate::app::state_analysis::AnalysisState;
+use crate::app::state_random_sample::RandomSampleState;
+use crate::app::state_snapshots::SnapshotsState;
+use crate::app::state_compare::CompareState;
+use crate::app::state_tree_view::TreeViewState;
+use crate::app::state_config_editor::ConfigEditorState;
+use crate::app::state_doctor::DoctorReportState;
+use crate::app::state_stats_report::StatsReportState;
+use crate::app::state_latency_panel::LatencyPanelState;
+use crate::app::state_monitor::MonitorState;
+use crate::app::state_pubsub::PubSubState;
+use crate::app::state_script_runner::ScriptRunnerState;
+use crate::app::pubsub::PubSubListener;
+use crate::app::stream_tail::StreamTail;
+use crate::app::value_decoder::ValueDecoder;
+use crate::app::value_fetch::ValuePurpose;
+use crate::app::value_grep::ValueGrep;
 use crate::app::value_viewer::ValueViewer;
+use crate::app::watch::WatchState;
 // REMOVE: use crate::app::app_fetch::{
 //     fetch_and_set_hash_value,
 //     fetch_and_set_zset_value,
@@ -46,23 +192,6 @@ pub enum KeyTreeNode {
     Leaf { full_key_name: String },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum PendingOperation {
-    InitialConnect,
-    ApplySelectedDb,
-    SelectProfileAndConnect,
-    ConfirmDeleteItem,
-    ExecuteCommand,
-    ActivateSelectedKey,
-    ActivateSelectedFilteredKey,
-    CopyKeyNameToClipboard,
-    CopyKeyValueToClipboard,
-    FetchRedisStats,
-    AutoPreviewCurrentKey,
-}
-
-const DELETE_BATCH_SIZE: usize = 500;
-
 pub struct App {
     pub selected_db_index: usize,
     pub db_count: u8,
@@ -71,23 +200,103 @@ pub struct App {
     pub profiles: Vec<ConnectionProfile>,
     pub current_profile_index: usize,
     pub profile_state: ProfileSelectorState,
-    pub raw_keys: Vec<String>,
+    /// Leaf keys of the loaded levels, shared with background searches.
+    pub raw_keys: Arc<Vec<String>>,
     pub key_tree: HashMap<String, KeyTreeNode>,
+    /// Keys beneath each folder, keyed by its prefix including the trailing delimiter.
+    pub folder_key_counts: HashMap<String, usize>,
+    /// Level prefixes (as in `folder_key_counts`) whose SCAN has completed; `""` is the root.
+    pub loaded_prefixes: HashSet<String>,
     pub current_breadcrumb: Vec<String>,
+    /// Level selected in the breadcrumb bar with Left/Right, opened with Enter.
+    pub breadcrumb_cursor: Option<usize>,
+    pub tree_view: TreeViewState,
     pub visible_keys_in_current_view: Vec<(String, bool)>,
     pub ttl_map: HashMap<String, i64>,
-    pub type_map: HashMap<String, String>,
+    /// When each `ttl_map` entry was read, so listed TTLs count down.
+    pub ttl_read_at: HashMap<String, Instant>,
+    pub key_sizes: KeySizes,
+    pub key_cache: KeyCache,
+    /// Shared with background searches, which filter by type.
+    pub type_map: Arc<HashMap<String, String>>,
+    /// Keys whose `TYPE`/`TTL` read failed, and when, so they are retried after a pause
+    /// rather than on every frame.
+    pub key_type_failures: HashMap<String, Instant>,
     pub selected_visible_key_index: usize,
     pub selected_indices: std::collections::HashSet<usize>,
     pub multi_select_anchor: Option<usize>,
-    pub key_delimiter: char,
+    /// Separators that split keys into tree levels, from the `key_delimiters` setting.
+    pub key_delimiters: KeyDelimiters,
     pub is_key_view_focused: bool,
     pub value_viewer: ValueViewer,
     pub is_value_view_focused: bool,
     pub value_is_pinned: bool,
+    pub value_decoder: ValueDecoder,
+    pub watch: WatchState,
+    pub stream_tail: Option<StreamTail>,
+    /// Keyspace notification subscriber while live key updates are on.
+    pub live_updates: Option<PubSubListener>,
+    pub key_scan: Option<KeyScan>,
+    /// Folder or multi-key delete running in the background.
+    pub bulk_delete: Option<BulkDelete>,
+    /// Background listing of the keys under folders, for the delete preview or a copy.
+    pub prefix_walk: Option<PrefixWalk>,
+    /// Runs Redis work and clipboard copies off the event loop; replaced on each connect.
+    pub worker: Option<Worker>,
+    /// Rescan of the viewed level (F5 or `auto_refresh_secs`), and when the last one started.
+    pub tree_refresh: Option<TreeRefresh>,
+    pub last_tree_refresh: Instant,
+    /// Last mouse click, to recognise double-clicks.
+    pub clicks: ClickTracker,
+    pub keymap: Keymap,
+    /// Count and leader typed so far towards a vim-style command.
+    pub pending_keys: PendingKeys,
+    pub key_list_scroll: ListScroll,
+    pub value_list_scroll: ListScroll,
+    pub help: HelpState,
+    /// Server-side `SCAN MATCH` backing the search panel in server mode.
+    pub search_scan: Option<KeyScan>,
+    /// Value search backing the search panel in values mode.
+    pub value_grep: Option<ValueGrep>,
+    /// Fuzzy/regex search running in the background, and when the next one is due.
+    pub search_job: Option<SearchJob>,
+    pub search_due: Option<Instant>,
+    /// When the selected key gets previewed, once the selection stops moving.
+    pub preview_due: Option<Instant>,
+    /// Leaf to select once the level being loaded contains it.
+    pub pending_key_selection: Option<String>,
+    /// State saved on the last exit, applied by the initial connect.
+    pub restore_session: Option<SessionState>,
+    /// Runtime `SCAN MATCH` pattern and COUNT, seeded from the connected profile.
+    pub scan_pattern: String,
+    pub scan_count: usize,
+    /// `SCAN TYPE` set by a scan preset.
+    pub scan_type: Option<&'static str>,
+    pub scan_prompt: Option<String>,
+    pub scan_presets: ScanPresetPicker,
+    /// Input of the jump-to-key prompt (Ctrl+G) while it is open.
+    pub goto_key_prompt: Option<String>,
+    pub bookmarks: Bookmarks,
+    pub bookmarks_panel: BookmarksPanelState,
+    pub script_runner: ScriptRunnerState,
+    pub action_menu: ActionMenuState,
+    pub paste_preview: PastePreviewState,
+    pub folder_copy: FolderCopyMenu,
+    pub functions: FunctionsState,
+    pub redisearch: RediSearchState,
+    pub pubsub: PubSubState,
+    pub monitor: MonitorState,
+    pub config_editor: ConfigEditorState,
+    pub latency_panel: LatencyPanelState,
+    pub doctor_report: DoctorReportState,
+    pub stats_report: StatsReportState,
+    pub analysis: AnalysisState,
+    pub random_sample: RandomSampleState,
+    pub snapshots: SnapshotsState,
+    pub compare: CompareState,
     pub scan_cursor: u64,
     pub keys_fully_loaded: bool,
-    pub clipboard_status: Option<String>,
+    pub notifications: Notifications,
 
     // Fuzzy Search State
     pub search_state: SearchState,
@@ -95,25 +304,37 @@ pub struct App {
     // Delete Confirmation State
     pub delete_dialog: DeleteDialogState,
 
+    // Stream consumer group inspector
+    pub consumer_groups: ConsumerGroupState,
+
+    // Key metadata popup
+    pub key_info: Option<KeyInfo>,
+
     // Command prompt state
     pub command_state: CommandState,
-    pub pending_operation: Option<PendingOperation>,
 
     // Redis stats state
     pub redis_stats: Option<RedisStats>,
+    /// Keys per DB index from INFO keyspace; `None` until it has been read.
+    pub db_key_counts: Option<HashMap<usize, KeyspaceCounts>>,
+    /// DBSIZE of the selected DB, fetched right after connecting.
+    pub db_size: Option<u64>,
+    /// Slot map of the connected cluster node; `None` outside cluster mode.
+    pub cluster: Option<ClusterTopology>,
     pub show_stats: bool,
     pub stats_auto_refresh: bool,
+    pub latency: LatencyTracker,
+
+    pub settings: Settings,
 }
 
 impl App {
-    // Clipboard functions are now in app::app_clipboard
-    // Calls would be: crate::app::app_clipboard::copy_selected_key_name_to_clipboard(self).await;
-    // And: crate::app::app_clipboard::copy_selected_key_value_to_clipboard(self).await;
-
     pub fn new(
         initial_url: &str,
         initial_profile_name: &str,
         profiles: Vec<ConnectionProfile>,
+        settings: Settings,
+        bookmarks: Bookmarks,
     ) -> App {
         let mut app = App {
             selected_db_index: 0,
@@ -127,23 +348,84 @@ impl App {
             current_profile_index: 0,
             profile_state: ProfileSelectorState::default(),
 
-            raw_keys: Vec::new(),
+            raw_keys: Arc::new(Vec::new()),
             key_tree: HashMap::new(),
+            folder_key_counts: HashMap::new(),
+            loaded_prefixes: HashSet::new(),
             current_breadcrumb: Vec::new(),
+            breadcrumb_cursor: None,
+            tree_view: TreeViewState {
+                is_active: settings.tree_view,
+                ..TreeViewState::default()
+            },
             visible_keys_in_current_view: Vec::new(),
             ttl_map: HashMap::new(),
-            type_map: HashMap::new(),
+            ttl_read_at: HashMap::new(),
+            key_sizes: KeySizes::default(),
+            key_cache: KeyCache::default(),
+            type_map: Arc::default(),
+            key_type_failures: HashMap::new(),
             selected_visible_key_index: 0,
             selected_indices: std::collections::HashSet::new(),
             multi_select_anchor: None,
-            key_delimiter: ':',
+            key_delimiters: KeyDelimiters::new(&settings.key_delimiters),
             is_key_view_focused: false,
             value_viewer: ValueViewer::default(),
             is_value_view_focused: false,
             value_is_pinned: false,
+            value_decoder: ValueDecoder::default(),
+            watch: WatchState::default(),
+            stream_tail: None,
+            live_updates: None,
+            key_scan: None,
+            bulk_delete: None,
+            prefix_walk: None,
+            worker: None,
+            tree_refresh: None,
+            last_tree_refresh: Instant::now(),
+            clicks: ClickTracker::default(),
+            keymap: Keymap::new(settings.keymap),
+            pending_keys: PendingKeys::default(),
+            key_list_scroll: ListScroll::default(),
+            value_list_scroll: ListScroll::default(),
+            help: HelpState::default(),
+            search_scan: None,
+            value_grep: None,
+            search_job: None,
+            search_due: None,
+            preview_due: None,
+            pending_key_selection: None,
+            restore_session: None,
+            scan_pattern: DEFAULT_SCAN_PATTERN.to_string(),
+            scan_count: DEFAULT_SCAN_COUNT,
+            scan_type: None,
+            scan_prompt: None,
+            scan_presets: ScanPresetPicker::default(),
+            goto_key_prompt: None,
+            bookmarks,
+            bookmarks_panel: BookmarksPanelState::default(),
+            script_runner: ScriptRunnerState::default(),
+            action_menu: ActionMenuState::default(),
+            paste_preview: PastePreviewState::default(),
+            folder_copy: FolderCopyMenu::default(),
+            functions: FunctionsState::default(),
+            redisearch: RediSearchState::default(),
+            pubsub: PubSubState::default(),
+            monitor: MonitorState::default(),
+            config_editor: ConfigEditorState::default(),
+            latency_panel: LatencyPanelState::default(),
+            doctor_report: DoctorReportState::default(),
+            stats_report: StatsReportState::default(),
+            analysis: AnalysisState::default(),
+            random_sample: RandomSampleState::default(),
+            snapshots: SnapshotsState {
+                dir: crate::snapshot::snapshot_dir(None),
+                ..SnapshotsState::default()
+            },
+            compare: CompareState::default(),
             scan_cursor: 0,
             keys_fully_loaded: false,
-            clipboard_status: None,
+            notifications: Notifications::default(),
 
             // Fuzzy Search State
             search_state: SearchState::new(),
@@ -151,14 +433,25 @@ impl App {
             // Delete Confirmation State
             delete_dialog: DeleteDialogState::default(),
 
+            // Stream consumer group inspector
+            consumer_groups: ConsumerGroupState::default(),
+
+            // Key metadata popup
+            key_info: None,
+
             // Command prompt state
             command_state: CommandState::new(),
-            pending_operation: None,
 
             // Redis stats state
             redis_stats: None,
+            db_key_counts: None,
+            db_size: None,
+            cluster: None,
             show_stats: false,
             stats_auto_refresh: true,
+            latency: LatencyTracker::default(),
+
+            settings,
         };
 
         if !app.profiles.is_empty() {
@@ -175,52 +468,6 @@ impl App {
         app
     }
 
-    pub fn trigger_initial_connect(&mut self) {
-        self.connection_status = "Preparing initial connection...".to_string();
-        self.pending_operation = Some(PendingOperation::InitialConnect);
-    }
-
-    pub async fn execute_initial_connect(&mut self) {
-        self.connect_to_profile(self.current_profile_index, true)
-            .await;
-        self.pending_operation = None;
-    }
-
-    async fn connect_to_profile(&mut self, profile_index: usize, use_profile_db: bool) {
-        if profile_index >= self.profiles.len() {
-            self.connection_status =
-                format!("Error: Profile index {} out of bounds.", profile_index);
-            return;
-        }
-
-        let profile = &self.profiles[profile_index];
-        self.connection_status = format!("Connecting to {} ({})...", profile.name, profile.url);
-        tokio::task::yield_now().await;
-
-        // Determine the target_db_index_override based on use_profile_db
-        let target_db_override = if use_profile_db {
-            None // When using profile_db, no override is needed
-        } else {
-            Some(self.selected_db_index) // When not using profile_db (i.e. manual DB select), pass current app selection
-        };
-
-        // Use the new RedisClient abstraction
-        match self
-            .redis
-            .connect_to_profile(profile, use_profile_db, target_db_override)
-            .await
-        {
-            Ok(()) => {
-                self.selected_db_index = self.redis.db_index;
-                self.connection_status = self.redis.connection_status.clone();
-                self.fetch_keys_and_build_tree().await;
-            }
-            Err(e) => {
-                self.connection_status = format!("Failed to connect: {}", e);
-            }
-        }
-    }
-
     pub fn clear_selected_key_info(&mut self) {
         self.value_viewer.clear();
         self.is_value_view_focused = false;
@@ -234,205 +481,63 @@ impl App {
         }
     }
 
-    async fn fetch_value_for_key(
-        &mut self,
-        full_key_name: &str,
-        con: &mut MultiplexedConnection,
-    ) {
-        let ttl = redis::cmd("TTL")
-            .arg(full_key_name)
-            .query_async::<i64>(con)
-            .await
-            .unwrap_or(-2);
-        self.ttl_map.insert(full_key_name.to_string(), ttl);
-
-        let key_type = match redis::cmd("TYPE")
-            .arg(full_key_name)
-            .query_async::<String>(con)
-            .await
-        {
-            Ok(key_type) => key_type,
-            Err(e) => {
-                self.value_viewer.selected_key_type = Some("error".to_string());
-                self.value_viewer.selected_key_value = Some(format!(
-                    "Failed to TYPE key '{}': {}",
-                    full_key_name, e
-                ));
-                self.value_viewer.update_current_display_value();
-                return;
-            }
-        };
-
-        self.type_map
-            .insert(full_key_name.to_string(), key_type.clone());
-        let key_type_upper = key_type.to_uppercase();
-        self.value_viewer.selected_key_type = Some(key_type_upper.clone());
-
-        match key_type_upper.as_str() {
-            "STRING" => self.fetch_string_value(full_key_name, con).await,
-            "NONE" => {
-                self.value_viewer.selected_key_value =
-                    Some("(nil)".to_string());
-            }
-            "HASH" => {
-                self.fetch_and_set_hash_value(full_key_name, con).await;
-            }
-            "ZSET" => {
-                self.fetch_and_set_zset_value(full_key_name, con).await;
-            }
-            "LIST" => {
-                self.fetch_and_set_list_value(full_key_name, con).await;
-            }
-            "SET" => {
-                self.fetch_and_set_set_value(full_key_name, con).await;
-            }
-            "STREAM" => {
-                self.fetch_and_set_stream_value(full_key_name, con).await;
-            }
-            "REJSON-RL" | "JSON" => {
-                self.fetch_and_set_json_value(full_key_name, con).await;
-            }
-            _ => {
-                self.value_viewer.selected_key_value = Some(format!(
-                    "Key is of type '{}'. Value view for this type not yet implemented.",
-                    key_type
-                ));
-            }
-        }
-
-        self.value_viewer.update_current_display_value();
-    }
-
-    async fn fetch_string_value(
-        &mut self,
-        full_key_name: &str,
-        con: &mut MultiplexedConnection,
-    ) {
-        match redis::cmd("GET")
-            .arg(full_key_name)
-            .query_async::<Option<Vec<u8>>>(con)
-            .await
-        {
-            Ok(Some(bytes)) => {
-                self.value_viewer.selected_key_value =
-                    Some(value_format::format_bytes_block(&bytes));
-            }
-            Ok(None) => {
-                self.value_viewer.selected_key_value =
-                    Some("(nil)".to_string());
-            }
-            Err(e) => {
-                self.value_viewer.selected_key_type = Some("error".to_string());
-                self.value_viewer.selected_key_value = Some(format!(
-                    "Failed to GET key '{}': {}",
-                    full_key_name, e
-                ));
-            }
-        }
-    }
-
-    async fn fetch_keys_and_build_tree(&mut self) {
-        self.raw_keys.clear();
+    pub(crate) fn fetch_keys_and_build_tree(&mut self) {
+        self.key_cache.clear();
+        self.raw_keys = Arc::new(Vec::new());
         self.key_tree.clear();
+        self.folder_key_counts.clear();
+        self.loaded_prefixes.clear();
         self.current_breadcrumb.clear();
+        self.tree_view.expanded.clear();
         self.visible_keys_in_current_view.clear();
         self.selected_visible_key_index = 0;
         self.clear_selected_key_info();
-
-        self.scan_cursor = 0;
-        self.keys_fully_loaded = false;
-
-        let mut cursor: u64 = self.scan_cursor;
-        let mut con = match self.redis.connection.take() {
-            Some(con) => con,
-            None => {
-                self.connection_status = "Not connected. Cannot fetch keys.".to_string();
-                return;
-            }
-        };
-        loop {
-            match redis::cmd("SCAN")
-                .arg(cursor)
-                .arg("MATCH")
-                .arg("*")
-                .arg("COUNT")
-                .arg(1000)
-                .query_async::<(u64, Vec<String>)>(&mut con)
-                .await
-            {
-                Ok((next_cursor, batch)) => {
-                    cursor = next_cursor;
-                    for key in &batch {
-                        self.insert_key_into_tree(key);
-                    }
-                    self.raw_keys.extend(batch);
-                    if !self.raw_keys.is_empty() {
-                        self.update_visible_keys();
-                    }
-                    self.connection_status = format!(
-                        "Connected to DB {}. Found {} keys (cursor {}).",
-                        self.selected_db_index,
-                        self.raw_keys.len(),
-                        cursor
-                    );
-                    self.scan_cursor = cursor;
-                    if cursor == 0 {
-                        self.keys_fully_loaded = true;
-                        break;
-                    }
-                    tokio::task::yield_now().await;
-                }
-                Err(e) => {
-                    self.connection_status = format!("Failed during SCAN: {}", e);
-                    break;
-                }
-            }
-        }
-        self.redis.connection = Some(con);
-        if self.raw_keys.is_empty() {
-            self.connection_status =
-                format!("Connected to DB {}. No keys found.", self.selected_db_index);
-        } else if !self.keys_fully_loaded {
-            self.connection_status = format!(
-                "Connected to DB {}. Loaded {} keys so far...",
-                self.selected_db_index,
-                self.raw_keys.len()
-            );
-        } else {
-            self.connection_status = format!(
-                "Connected to DB {}. Found {} keys. Displaying {} top-level items.",
-                self.selected_db_index,
-                self.raw_keys.len(),
-                self.visible_keys_in_current_view.len()
-            );
+        self.key_sizes.clear();
+        self.type_map = Arc::default();
+        self.key_type_failures.clear();
+        self.ttl_map.clear();
+        self.ttl_read_at.clear();
+        self.key_scan = None;
+        self.tree_refresh = None;
+        self.start_level_scan();
+        if self.settings.live_updates {
+            self.start_live_updates();
         }
     }
 
     #[cfg(test)]
     fn parse_keys_to_tree(&mut self) {
         self.key_tree.clear();
-        let raw_keys = self.raw_keys.clone();
-        for full_key_name in &raw_keys {
+        self.folder_key_counts.clear();
+        let raw_keys = Arc::clone(&self.raw_keys);
+        for full_key_name in raw_keys.iter() {
             self.insert_key_into_tree(full_key_name);
         }
     }
 
+    #[cfg(test)]
     fn insert_key_into_tree(&mut self, full_key_name: &str) {
-        let parts: Vec<&str> = full_key_name.split(self.key_delimiter).collect();
+        use std::collections::hash_map::Entry;
+
+        let parts = self.key_delimiters.split(full_key_name);
         let mut current_level = &mut self.key_tree;
+        let mut inserted = false;
+        let mut displaced_leaf = None;
         for (i, part) in parts.iter().enumerate() {
             if i == parts.len() - 1 {
-                current_level
-                    .entry(part.to_string())
-                    .or_insert_with(|| KeyTreeNode::Leaf {
+                if let Entry::Vacant(entry) = current_level.entry(part.to_string()) {
+                    entry.insert(KeyTreeNode::Leaf {
                         full_key_name: full_key_name.to_string(),
                     });
+                    inserted = true;
+                }
             } else {
                 let node = current_level
                     .entry(part.to_string())
                     .or_insert_with(|| KeyTreeNode::Folder(HashMap::new()));
 
-                if matches!(node, KeyTreeNode::Leaf { .. }) {
+                if let KeyTreeNode::Leaf { full_key_name } = node {
+                    displaced_leaf = Some(std::mem::take(full_key_name));
                     *node = KeyTreeNode::Folder(HashMap::new());
                 }
 
@@ -445,6 +550,35 @@ impl App {
                 }
             }
         }
+        if let Some(displaced) = displaced_leaf {
+            self.adjust_folder_key_counts(&displaced, false);
+        }
+        if inserted {
+            self.adjust_folder_key_counts(full_key_name, true);
+        }
+    }
+
+    /// Updates the descendant count of every folder above `full_key_name`.
+    #[cfg(test)]
+    fn adjust_folder_key_counts(&mut self, full_key_name: &str, added: bool) {
+        let canonical = self.key_delimiters.canonical(full_key_name).into_owned();
+        let delimiter = self.key_delimiters.primary();
+        for (idx, _) in canonical.match_indices(delimiter) {
+            let prefix = &canonical[..idx + delimiter.len_utf8()];
+            if added {
+                *self.folder_key_counts.entry(prefix.to_string()).or_insert(0) += 1;
+            } else if let Some(count) = self.folder_key_counts.get_mut(prefix) {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Number of keys under a folder shown in the current view (`name` without the trailing `/`).
+    pub fn folder_key_count(&self, name: &str) -> usize {
+        let mut prefix = self.current_level_prefix();
+        prefix.push_str(name);
+        prefix.push(self.key_delimiters.primary());
+        self.folder_key_counts.get(&prefix).copied().unwrap_or(0)
     }
 
     pub fn previous_key_in_view(&mut self) {
@@ -461,7 +595,7 @@ impl App {
         }
     }
 
-    pub async fn activate_selected_key(&mut self) {
+    pub fn activate_selected_key(&mut self) {
         if self.selected_visible_key_index < self.visible_keys_in_current_view.len() {
             let (display_name, is_folder) =
                 self.visible_keys_in_current_view[self.selected_visible_key_index].clone();
@@ -470,6 +604,7 @@ impl App {
                 let folder_name = display_name.trim_end_matches('/').to_string();
                 self.current_breadcrumb.push(folder_name);
                 self.update_visible_keys();
+                self.start_level_scan();
             } else {
                 let mut current_node_map_for_leaf = &self.key_tree;
                 for segment in &self.current_breadcrumb {
@@ -491,29 +626,14 @@ impl App {
                         _ => None,
                     });
                 if let Some(actual_full_key_name) = actual_full_key_name_opt {
-                    self.value_viewer.clear();
-                    self.value_viewer.active_leaf_key_name = Some(actual_full_key_name.clone());
-                    self.value_viewer.selected_key_type = Some("fetching...".to_string());
-                    let mut con = match self.redis.connection.take() {
-                        Some(con) => con,
-                        None => {
-                            self.value_viewer.selected_key_type = Some("error".to_string());
-                            self.value_viewer.selected_key_value =
-                                Some("Error: No Redis connection to fetch key value.".to_string());
-                            self.value_viewer.update_current_display_value();
-                            return;
-                        }
-                    };
-                    self.fetch_value_for_key(&actual_full_key_name, &mut con)
-                        .await;
-                    self.redis.connection = Some(con);
+                    self.open_leaf_key(&actual_full_key_name, ValuePurpose::Open);
                 } else {
                     self.value_viewer.selected_key_type = Some("error".to_string());
                     self.value_viewer.selected_key_value = Some(format!("Error: Key '{}' not found as leaf in tree at current level after traversal.", display_name));
+                    self.value_viewer.update_current_display_value();
                 }
             }
         }
-        self.value_viewer.update_current_display_value();
         // Mark value as pinned when explicitly activated with Enter
         if self.value_viewer.active_leaf_key_name.is_some() {
             self.value_is_pinned = true;
@@ -524,6 +644,7 @@ impl App {
         if !self.current_breadcrumb.is_empty() {
             self.current_breadcrumb.pop();
             self.update_visible_keys();
+            self.start_level_scan();
             self.clear_selected_key_info();
         }
     }
@@ -540,30 +661,11 @@ impl App {
             }
         }
 
-        self.visible_keys_in_current_view = current_level
-            .iter()
-            .map(|(name, node)| {
-                let display_name = match node {
-                    KeyTreeNode::Folder(_) => format!("{}/", name),
-                    KeyTreeNode::Leaf { .. } => name.clone(),
-                };
-                (display_name, matches!(node, KeyTreeNode::Folder(_)))
-            })
-            .collect();
-
-        self.visible_keys_in_current_view
-            .sort_by(
-                |(a_name, a_folder), (b_name, b_folder)| match (a_folder, b_folder) {
-                    (true, false) => std::cmp::Ordering::Less,
-                    (false, true) => std::cmp::Ordering::Greater,
-                    _ => a_name.cmp(b_name),
-                },
-            );
+        self.visible_keys_in_current_view = level_entries(current_level);
         self.selected_visible_key_index = 0;
-    }
-
-    pub fn toggle_profile_selector(&mut self) {
-        self.profile_state.toggle(self.current_profile_index);
+        // A preview scheduled on the previous level would land on this one's first key
+        self.preview_due = None;
+        self.expand_tree_to_current_level();
     }
 
     pub fn focus_db(&mut self) {
@@ -581,23 +683,6 @@ impl App {
         self.is_value_view_focused = true;
     }
 
-    pub fn next_profile_in_list(&mut self) {
-        self.profile_state.next(self.profiles.len());
-    }
-
-    pub fn previous_profile_in_list(&mut self) {
-        self.profile_state.previous(self.profiles.len());
-    }
-
-    pub async fn select_profile_and_connect(&mut self) {
-        if self.profile_state.selected_index < self.profiles.len() {
-            self.current_profile_index = self.profile_state.selected_index;
-            self.profile_state.is_active = false;
-            self.connect_to_profile(self.current_profile_index, true)
-                .await;
-        }
-    }
-
     pub fn cycle_focus_backward(&mut self) {
         if self.is_value_view_focused {
             self.is_value_view_focused = false;
@@ -699,7 +784,7 @@ impl App {
         self.multi_select_anchor = Some(self.selected_visible_key_index);
     }
 
-    pub async fn auto_preview_current_key(&mut self) {
+    pub fn auto_preview_current_key(&mut self) {
         if !self.value_is_pinned
             && self.selected_visible_key_index < self.visible_keys_in_current_view.len()
         {
@@ -726,444 +811,82 @@ impl App {
                     });
 
                 if let Some(actual_full_key_name) = actual_full_key_name_opt {
-                    self.value_viewer.clear();
-                    self.value_viewer.active_leaf_key_name = Some(actual_full_key_name.clone());
-                    self.value_viewer.selected_key_type = Some("fetching...".to_string());
-
-                    let mut con = match self.redis.connection.take() {
-                        Some(con) => con,
-                        None => return,
-                    };
-
-                    self.fetch_value_for_key(&actual_full_key_name, &mut con)
-                        .await;
-                    self.redis.connection = Some(con);
+                    self.open_leaf_key(&actual_full_key_name, ValuePurpose::Preview);
                 }
             }
         }
     }
 
-    pub fn next_db(&mut self) {
-        if self.db_count > 0 {
-            self.selected_db_index = (self.selected_db_index + 1) % (self.db_count as usize);
-        }
-    }
-
-    pub fn previous_db(&mut self) {
-        if self.db_count > 0 {
-            if self.selected_db_index > 0 {
-                self.selected_db_index -= 1;
-            } else {
-                self.selected_db_index = (self.db_count as usize).saturating_sub(1);
-            }
-        }
-    }
-
-    pub fn trigger_apply_selected_db(&mut self) {
-        self.connection_status = format!("Preparing to switch to DB {}...", self.selected_db_index);
-        self.pending_operation = Some(PendingOperation::ApplySelectedDb);
-    }
-
-    pub async fn execute_apply_selected_db(&mut self) {
-        self.clear_selected_key_info();
-        self.current_breadcrumb.clear();
-        self.raw_keys.clear();
-        self.key_tree.clear();
-        self.visible_keys_in_current_view.clear();
-        self.selected_visible_key_index = 0;
-        self.connect_to_profile(self.current_profile_index, false)
-            .await;
-        self.pending_operation = None;
-    }
-
     pub fn navigate_to_key_tree_root(&mut self) {
         self.current_breadcrumb.clear();
         self.update_visible_keys();
+        self.start_level_scan();
         self.clear_selected_key_info();
     }
 
-    pub fn initiate_delete_selected_item(&mut self) {
-        if !self.selected_indices.is_empty() {
-            // Multi-select delete
-            self.delete_dialog.initiate_delete_multiple_items(
-                &self.selected_indices,
-                &self.visible_keys_in_current_view,
-                &self.current_breadcrumb,
-                self.key_delimiter,
-                self.search_state.is_active,
-            );
-        } else {
-            // Single item delete
-            self.delete_dialog.initiate_delete_selected_item(
-                self.selected_visible_key_index,
-                &self.visible_keys_in_current_view,
-                &self.current_breadcrumb,
-                self.key_delimiter,
-                self.search_state.is_active,
-            );
-        }
-    }
-
-    pub fn cancel_delete_item(&mut self) {
-        self.delete_dialog.show_confirmation_dialog = false;
-        self.delete_dialog.key_to_delete_display_name = None;
-        self.delete_dialog.key_to_delete_full_path = None;
-        self.delete_dialog.prefix_to_delete = None;
-        self.delete_dialog.deletion_is_folder = false;
-    }
-
-    pub async fn confirm_delete_item(&mut self) {
-        let result = if self.delete_dialog.is_multi_delete {
-            self.delete_multiple_items_async().await
-        } else if self.delete_dialog.deletion_is_folder {
-            if let Some(prefix) = self.delete_dialog.prefix_to_delete.clone() {
-                self.delete_redis_prefix_async(&prefix).await
-            } else {
-                Err("Prefix to delete was None".to_string())
-            }
-        } else if let Some(key_path) = self.delete_dialog.key_to_delete_full_path.clone() {
-            self.delete_redis_key_async(&key_path).await
-        } else {
-            Err("Key path to delete was None".to_string())
+    /// Shows the level containing the key split into `path_segments` and selects it,
+    /// deferring the selection when the level is still loading (e.g. a server-side hit).
+    pub(crate) fn reveal_key_in_tree(&mut self, path_segments: &[String]) {
+        let Some((leaf_name, parents)) = path_segments.split_last() else {
+            return;
         };
-
-        match result {
-            Ok(msg) => self.clipboard_status = Some(msg),
-            Err(e) => self.clipboard_status = Some(format!("Error deleting: {}", e)),
-        }
-
-        self.delete_dialog.show_confirmation_dialog = false;
-        self.delete_dialog.key_to_delete_display_name = None;
-        self.delete_dialog.key_to_delete_full_path = None;
-        self.delete_dialog.prefix_to_delete = None;
-        self.delete_dialog.deletion_is_folder = false;
-        self.delete_dialog.keys_to_delete.clear();
-        self.delete_dialog.is_multi_delete = false;
-
-        // Clear multi-selection after deletion
-        self.clear_multi_selection();
-
-        self.fetch_keys_and_build_tree().await;
+        self.current_breadcrumb = parents.to_vec();
         self.update_visible_keys();
-        self.value_viewer.active_leaf_key_name = None;
-        self.clear_selected_key_info();
-    }
-
-    async fn delete_keys_batch(
-        &self,
-        con: &mut MultiplexedConnection,
-        keys: &[String],
-        prefer_unlink: &mut bool,
-    ) -> Result<i64, String> {
-        if keys.is_empty() {
-            return Ok(0);
-        }
-
-        let result = if *prefer_unlink {
-            redis::cmd("UNLINK").arg(keys).query_async::<i64>(con).await
-        } else {
-            redis::cmd("DEL").arg(keys).query_async::<i64>(con).await
-        };
-
-        match result {
-            Ok(count) => Ok(count),
-            Err(e) => {
-                if *prefer_unlink && is_unknown_command_error(&e) {
-                    *prefer_unlink = false;
-                    redis::cmd("DEL")
-                        .arg(keys)
-                        .query_async::<i64>(con)
-                        .await
-                        .map_err(|err| format!("Error deleting keys: {}", err))
-                } else {
-                    Err(format!("Error deleting keys: {}", e))
-                }
-            }
+        self.start_level_scan();
+        match self.leaf_index_in_view(leaf_name) {
+            Some(idx) => self.selected_visible_key_index = idx,
+            None => self.pending_key_selection = Some(leaf_name.clone()),
         }
     }
 
-    async fn delete_prefix_keys(
-        &self,
-        con: &mut MultiplexedConnection,
-        prefix: &str,
-        prefer_unlink: &mut bool,
-    ) -> Result<i64, String> {
-        let pattern = format!("{}*", prefix);
-        let mut cursor: u64 = 0;
-        let mut batch = Vec::new();
-        let mut total_deleted: i64 = 0;
-
-        loop {
-            let (next_cursor, keys) = redis::cmd("SCAN")
-                .arg(cursor)
-                .arg("MATCH")
-                .arg(&pattern)
-                .arg("COUNT")
-                .arg(1000)
-                .query_async::<(u64, Vec<String>)>(con)
-                .await
-                .map_err(|e| format!("Error scanning keys for prefix {}: {}", prefix, e))?;
-
-            for key in keys {
-                batch.push(key);
-                if batch.len() >= DELETE_BATCH_SIZE {
-                    total_deleted += self.delete_keys_batch(con, &batch, prefer_unlink).await?;
-                    batch.clear();
-                }
-            }
-
-            if next_cursor == 0 {
-                break;
-            }
-            cursor = next_cursor;
-        }
-
-        if !batch.is_empty() {
-            total_deleted += self.delete_keys_batch(con, &batch, prefer_unlink).await?;
-        }
-
-        Ok(total_deleted)
-    }
-
-    async fn delete_redis_prefix_async(&mut self, prefix: &str) -> Result<String, String> {
-        let mut con = match self.redis.connection.take() {
-            Some(con) => con,
-            None => return Err("No Redis connection available for deleting prefix.".to_string()),
-        };
-
-        let mut prefer_unlink = true;
-        let result = match self.delete_prefix_keys(&mut con, prefix, &mut prefer_unlink).await {
-            Ok(0) => Ok(format!("No keys found matching prefix '{}'.", prefix)),
-            Ok(count) => Ok(format!("Deleted {} keys matching prefix '{}'.", count, prefix)),
-            Err(e) => Err(e),
-        };
-
-        self.redis.connection = Some(con);
-        result
-    }
-
-    async fn delete_redis_key_async(&mut self, full_key: &str) -> Result<String, String> {
-        let mut con = match self.redis.connection.take() {
-            Some(con) => con,
-            None => return Err("No Redis connection available for deleting key.".to_string()),
-        };
-
-        let mut prefer_unlink = true;
-        let result = match self
-            .delete_keys_batch(&mut con, &[full_key.to_string()], &mut prefer_unlink)
-            .await
-        {
-            Ok(count) => {
-                if count > 0 {
-                    Ok(format!("Deleted key '{}'.", full_key))
-                } else {
-                    Ok(format!("Key '{}' not found or already deleted.", full_key))
-                }
-            }
-            Err(e) => Err(e),
-        };
-
-        self.redis.connection = Some(con);
-        result
-    }
-
-    async fn delete_multiple_items_async(&mut self) -> Result<String, String> {
-        let mut con = match self.redis.connection.take() {
-            Some(con) => con,
-            None => return Err("No Redis connection available for multi-delete.".to_string()),
-        };
-
-        let mut total_deleted: i64 = 0;
-        let mut errors = Vec::new();
-        let mut prefer_unlink = true;
-        let mut key_batch = Vec::new();
-
-        for item in &self.delete_dialog.keys_to_delete {
-            if let Some(prefix) = item.strip_prefix("folder:") {
-                if !key_batch.is_empty() {
-                    match self
-                        .delete_keys_batch(&mut con, &key_batch, &mut prefer_unlink)
-                        .await
-                    {
-                        Ok(count) => total_deleted += count,
-                        Err(e) => errors.push(e),
-                    }
-                    key_batch.clear();
-                }
-                // Handle folder deletion
-                match self
-                    .delete_prefix_keys(&mut con, prefix, &mut prefer_unlink)
-                    .await
-                {
-                    Ok(count) => total_deleted += count,
-                    Err(e) => errors.push(e),
-                }
-            } else {
-                // Handle single key deletion
-                key_batch.push(item.clone());
-                if key_batch.len() >= DELETE_BATCH_SIZE {
-                    match self
-                        .delete_keys_batch(&mut con, &key_batch, &mut prefer_unlink)
-                        .await
-                    {
-                        Ok(count) => total_deleted += count,
-                        Err(e) => errors.push(e),
-                    }
-                    key_batch.clear();
-                }
-            }
-        }
-
-        if !key_batch.is_empty() {
-            match self
-                .delete_keys_batch(&mut con, &key_batch, &mut prefer_unlink)
-                .await
-            {
-                Ok(count) => total_deleted += count,
-                Err(e) => errors.push(e),
-            }
-        }
-
-        self.redis.connection = Some(con);
-
-        if errors.is_empty() {
-            Ok(format!("Deleted {} items.", total_deleted))
-        } else {
-            Err(format!(
-                "Deleted {} items, but encountered errors: {}",
-                total_deleted,
-                errors.join("; ")
-            ))
-        }
-    }
-
-    pub fn enter_search_mode(&mut self) {
-        self.search_state.enter();
-        self.is_key_view_focused = true;
-        self.is_value_view_focused = false;
-        self.search_state
-            .update_filtered_keys(&self.raw_keys);
-    }
-
-    pub fn exit_search_mode(&mut self) {
-        self.search_state.exit();
-    }
-
-    pub fn update_filtered_keys(&mut self) {
-        self.search_state
-            .update_filtered_keys(&self.raw_keys);
-    }
-
-    pub fn select_next_filtered_key(&mut self) {
-        self.search_state.select_next_filtered();
-    }
-
-    pub fn select_previous_filtered_key(&mut self) {
-        self.search_state.select_previous_filtered();
+    pub(crate) fn leaf_index_in_view(&self, leaf_name: &str) -> Option<usize> {
+        self.visible_keys_in_current_view
+            .iter()
+            .position(|(name, is_folder)| name == leaf_name && !*is_folder)
     }
 
-    pub async fn activate_selected_filtered_key(&mut self) {
-        let activation_info_opt = self.search_state.activate_selected_filtered(
-            self.key_delimiter,
-            &self.key_tree,
-            &self.raw_keys,
-        );
-
-        if let Some(info) = activation_info_opt {
-            if info.is_folder {
-                self.current_breadcrumb = info.path_segments;
-                self.update_visible_keys();
-                self.clear_selected_key_info_if_not_pinned();
-            } else {
-                self.current_breadcrumb = if info.path_segments.len() > 1 {
-                    info.path_segments[0..info.path_segments.len() - 1].to_vec()
-                } else {
-                    Vec::new()
-                };
-                self.update_visible_keys();
-
-                if let Some(leaf_name) = info.path_segments.last() {
-                    if let Some(idx) = self
-                        .visible_keys_in_current_view
-                        .iter()
-                        .position(|(name, is_folder)| name == leaf_name && !*is_folder)
-                    {
-                        self.selected_visible_key_index = idx;
-                        self.activate_selected_key().await;
-                    } else {
-                        self.clear_selected_key_info_if_not_pinned();
-                    }
-                } else {
-                    self.clear_selected_key_info_if_not_pinned();
-                }
-            }
-            self.search_state.exit();
-            self.is_key_view_focused = true;
-            self.is_value_view_focused = false;
-        } else {
-            self.search_state.exit();
-        }
+    pub fn open_command_prompt(&mut self) {
+        self.command_state.open();
     }
 
-    pub fn select_next_value_item(&mut self) {
-        if let Some(lines) = &self.value_viewer.displayed_value_lines {
-            if !lines.is_empty() {
-                self.value_viewer.selected_value_sub_index =
-                    (self.value_viewer.selected_value_sub_index + 1) % lines.len();
-            }
-        }
+    pub fn close_command_prompt(&mut self) {
+        self.command_state.close();
     }
 
-    pub fn select_previous_value_item(&mut self) {
-        if let Some(lines) = &self.value_viewer.displayed_value_lines {
-            if !lines.is_empty() {
-                if self.value_viewer.selected_value_sub_index > 0 {
-                    self.value_viewer.selected_value_sub_index -= 1;
-                } else {
-                    self.value_viewer.selected_value_sub_index = lines.len() - 1;
-                }
-            }
+    pub fn execute_command_input(&mut self) {
+        let refusal = self.is_read_only().then(|| self.command_state.read_only_refusal()).flatten();
+        if let Some(refused) = refusal {
+            self.refuse_write(&refused);
+            return;
         }
+        let Some(input) = self.command_state.take_input() else {
+            return;
+        };
+        let Some(con) = self.redis.connection.clone() else {
+            self.command_state.last_result = Some("Not connected".to_string());
+            return;
+        };
+        self.submit(WorkerRequest::RunCommand { con, input });
     }
 
-    pub fn select_page_down_value_item(&mut self, page_size: usize) {
-        if let Some(lines) = &self.value_viewer.displayed_value_lines {
-            if !lines.is_empty() {
-                self.value_viewer.selected_value_sub_index =
-                    (self.value_viewer.selected_value_sub_index + page_size).min(lines.len() - 1);
-            }
-        }
+    pub(super) fn apply_command_outcome(&mut self, outcome: CommandOutcome) {
+        // Typed commands can write any key
+        self.key_cache.clear();
+        self.command_state.show_outcome(outcome);
     }
 
-    pub fn select_page_up_value_item(&mut self, page_size: usize) {
-        if let Some(lines) = &self.value_viewer.displayed_value_lines {
-            if !lines.is_empty() {
-                self.value_viewer.selected_value_sub_index = self
-                    .value_viewer
-                    .selected_value_sub_index
-                    .saturating_sub(page_size);
-            }
+    /// Dumps the command prompt result to a file in the working directory.
+    pub fn save_command_result(&mut self) {
+        match self.command_state.save_result(Path::new(".")) {
+            Ok(path) => self.notifications.success(format!("Saved result to {}", path.display())),
+            Err(e) => self.notifications.error(format!("Failed to save result: {}", e)),
         }
     }
 
-    pub fn open_command_prompt(&mut self) {
-        self.command_state.open();
-    }
-
-    pub fn close_command_prompt(&mut self) {
-        self.command_state.close();
-    }
-
-    pub async fn execute_command_input(&mut self) {
-        self.command_state
-            .execute_command(&mut self.redis.connection)
-            .await;
-    }
-
     pub fn toggle_stats_view(&mut self) {
         self.show_stats = !self.show_stats;
         if self.show_stats && self.redis_stats.is_none() {
-            self.pending_operation = Some(PendingOperation::FetchRedisStats);
+            self.trigger_fetch_redis_stats();
         }
     }
 
@@ -1171,23 +894,6 @@ impl App {
         self.stats_auto_refresh = !self.stats_auto_refresh;
     }
 
-    pub fn trigger_fetch_redis_stats(&mut self) {
-        self.pending_operation = Some(PendingOperation::FetchRedisStats);
-    }
-
-    pub async fn execute_fetch_redis_stats(&mut self) {
-        match self.redis.get_info().await {
-            Ok(info_string) => {
-                self.redis_stats = Some(RedisStats::from_info_string(&info_string));
-            }
-            Err(e) => {
-                // Could set an error state here if needed
-                eprintln!("Failed to fetch Redis stats: {}", e);
-            }
-        }
-        self.pending_operation = None;
-    }
-
     pub fn should_refresh_stats(&self) -> bool {
         if !self.show_stats || !self.stats_auto_refresh {
             return false;
@@ -1200,9 +906,18 @@ impl App {
     }
 }
 
-fn is_unknown_command_error(err: &redis::RedisError) -> bool {
-    err.kind() == redis::ErrorKind::Extension
-        && err.to_string().to_lowercase().contains("unknown command")
+/// Entries of a tree level as listed in the key panel: folders (with a trailing `/`) first,
+/// then leaves, each sorted by name.
+pub(crate) fn level_entries(level: &HashMap<String, KeyTreeNode>) -> Vec<(String, bool)> {
+    let mut entries: Vec<(String, bool)> = level
+        .iter()
+        .map(|(name, node)| match node {
+            KeyTreeNode::Folder(_) => (format!("{}/", name), true),
+            KeyTreeNode::Leaf { .. } => (name.clone(), false),
+        })
+        .collect();
+    entries.sort_by(|(a_name, a_folder), (b_name, b_folder)| b_folder.cmp(a_folder).then_with(|| a_name.cmp(b_name)));
+    entries
 }
 
 #[cfg(test)]
@@ -0,0 +1,146 @@
+use futures_util::StreamExt;
+use redis::Client;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+
+/// One command reported by MONITOR.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorEntry {
+    /// Server time in Unix milliseconds.
+    pub timestamp: i64,
+    pub db: String,
+    /// `ip:port`, `unix:<path>` or `lua`.
+    pub client: String,
+    /// Command name in upper case, for filtering.
+    pub command: String,
+    /// Command and arguments as quoted by the server.
+    pub arguments: String,
+}
+
+#[derive(Debug)]
+pub enum MonitorEvent {
+    Entry(MonitorEntry),
+    Error(String),
+}
+
+/// Background MONITOR stream on its own connection. While paused, entries
+/// stay queued and are delivered once resumed.
+pub struct MonitorSession {
+    pub paused: bool,
+    job: BackgroundJob<MonitorEvent>,
+}
+
+impl MonitorSession {
+    pub fn spawn(client: Client) -> Self {
+        let job = BackgroundJob::spawn(|sender| monitor(client, sender));
+        Self {
+            paused: false,
+            job,
+        }
+    }
+
+    /// Drains every queued event unless the session is paused.
+    pub fn drain(&mut self) -> Vec<MonitorEvent> {
+        if self.paused {
+            return Vec::new();
+        }
+        self.job.drain()
+    }
+}
+
+async fn monitor(client: Client, sender: UnboundedSender<MonitorEvent>) {
+    let monitor = match client.get_async_monitor().await {
+        Ok(monitor) => monitor,
+        Err(e) => {
+            let _ = sender.send(MonitorEvent::Error(format!("MONITOR failed: {}", e)));
+            return;
+        }
+    };
+    let mut lines = monitor.into_on_message::<String>();
+    while let Some(line) = lines.next().await {
+        let Some(entry) = parse_monitor_line(&line) else {
+            continue;
+        };
+        if sender.send(MonitorEvent::Entry(entry)).is_err() {
+            return;
+        }
+    }
+    let _ = sender.send(MonitorEvent::Error("MONITOR connection closed".to_string()));
+}
+
+/// Parses `1339518083.107412 [0 127.0.0.1:60866] "keys" "*"`.
+pub fn parse_monitor_line(line: &str) -> Option<MonitorEntry> {
+    let (timestamp, rest) = line.split_once(' ')?;
+    let (source, arguments) = rest.strip_prefix('[')?.split_once("] ")?;
+    let (db, client) = source.split_once(' ')?;
+    let seconds: f64 = timestamp.parse().ok()?;
+    let command = arguments
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.split('"').next())
+        .unwrap_or(arguments)
+        .to_uppercase();
+    Some(MonitorEntry {
+        timestamp: (seconds * 1000.0) as i64,
+        db: db.to_string(),
+        client: client.to_string(),
+        command,
+        arguments: arguments.to_string(),
+    })
+}
+
+/// Splits the server-quoted argument list back into the individual arguments.
+pub fn split_arguments(arguments: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut chars = arguments.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+        let mut part = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => part.push('\n'),
+                    Some('r') => part.push('\r'),
+                    Some('t') => part.push('\t'),
+                    Some('x') => part.push_str("\\x"),
+                    Some(escaped) => part.push(escaped),
+                    None => break,
+                },
+                c => part.push(c),
+            }
+        }
+        parts.push(part);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitor_lines_are_split_into_fields() {
+        let entry = parse_monitor_line(r#"1339518083.107412 [0 127.0.0.1:60866] "set" "user:1" "a \"b\"""#).unwrap();
+        assert_eq!(entry.timestamp, 1_339_518_083_107);
+        assert_eq!(entry.db, "0");
+        assert_eq!(entry.client, "127.0.0.1:60866");
+        assert_eq!(entry.command, "SET");
+        assert_eq!(entry.arguments, r#""set" "user:1" "a \"b\"""#);
+
+        let lua = parse_monitor_line(r#"1339518083.2 [3 lua] "get" "k""#).unwrap();
+        assert_eq!((lua.db.as_str(), lua.client.as_str()), ("3", "lua"));
+        assert!(parse_monitor_line("OK").is_none());
+    }
+
+    #[test]
+    fn quoted_arguments_are_unescaped() {
+        assert_eq!(
+            split_arguments(r#""set" "user:1" "a \"b\"" "\xff""#),
+            vec!["set", "user:1", "a \"b\"", "\\xff"]
+        );
+        assert!(split_arguments("").is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+
+use ratatui::layout::{Position, Rect};
+
+/// Two clicks on the same row within this window count as a double-click.
+pub const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(400);
+
+/// Drawn between the levels of the breadcrumb bar.
+pub const BREADCRUMB_SEPARATOR: &str = " › ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Profiles,
+    Databases,
+    Keys,
+    Values,
+    Stats,
+}
+
+/// Screen areas of the last drawn frame that react to the mouse. List areas are the rows
+/// inside the borders, so a click's row maps straight onto an item.
+#[derive(Debug, Clone, Default)]
+pub struct PanelAreas {
+    /// Profile selector list, while it covers the main view.
+    pub profile_list: Option<Rect>,
+    pub db_pane: Rect,
+    pub db_list: Rect,
+    pub keys_pane: Rect,
+    pub key_list: Rect,
+    /// Breadcrumb bar above the key list, hidden while searching.
+    pub breadcrumb_bar: Option<Rect>,
+    pub values_pane: Rect,
+    pub stats_pane: Option<Rect>,
+}
+
+impl PanelAreas {
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<Pane> {
+        let position = Position::new(column, row);
+        if let Some(list) = self.profile_list {
+            return list.contains(position).then_some(Pane::Profiles);
+        }
+        [
+            (Some(self.db_pane), Pane::Databases),
+            (Some(self.keys_pane), Pane::Keys),
+            (Some(self.values_pane), Pane::Values),
+            (self.stats_pane, Pane::Stats),
+        ]
+        .into_iter()
+        .find(|(area, _)| area.is_some_and(|area| area.contains(position)))
+        .map(|(_, pane)| pane)
+    }
+}
+
+/// Item under `row` in a list of `len` single-line items drawn in `list` with `selected`
+/// highlighted. Mirrors how ratatui scrolls a fresh `ListState` to keep the selection visible.
+pub fn list_row_item(list: Rect, selected: usize, len: usize, column: u16, row: u16) -> Option<usize> {
+    let offset = (selected + 1).saturating_sub(list.height as usize);
+    list_row_at(list, offset, len, column, row)
+}
+
+/// Item under `row` in a list of `len` single-line items drawn in `list` from `offset`.
+pub fn list_row_at(list: Rect, offset: usize, len: usize, column: u16, row: u16) -> Option<usize> {
+    if !list.contains(Position::new(column, row)) {
+        return None;
+    }
+    let index = offset + (row - list.y) as usize;
+    (index < len).then_some(index)
+}
+
+/// Level of the breadcrumb bar drawn from `labels` at `offset` columns into the bar.
+pub fn breadcrumb_level_at(labels: &[String], offset: usize) -> Option<usize> {
+    let mut start = 0;
+    for (level, label) in labels.iter().enumerate() {
+        let end = start + label.chars().count();
+        if (start..end).contains(&offset) {
+            return Some(level);
+        }
+        start = end + BREADCRUMB_SEPARATOR.chars().count();
+    }
+    None
+}
+
+/// Remembers the last click to recognise double-clicks.
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last: Option<(Instant, Pane, usize)>,
+}
+
+impl ClickTracker {
+    /// Records a click on `index` of `pane` and reports whether it completes a double-click.
+    pub fn register(&mut self, pane: Pane, index: usize, now: Instant) -> bool {
+        let double = self.last.is_some_and(|(at, last_pane, last_index)| {
+            last_pane == pane && last_index == index && now.duration_since(at) <= DOUBLE_CLICK_WINDOW
+        });
+        self.last = if double { None } else { Some((now, pane, index)) };
+        double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_rows_onto_scrolled_list_items() {
+        let list = Rect::new(1, 5, 20, 4);
+        assert_eq!(list_row_item(list, 0, 10, 3, 5), Some(0));
+        assert_eq!(list_row_item(list, 0, 2, 3, 8), None);
+        // Selecting item 6 scrolls the list so it sits on the last row
+        assert_eq!(list_row_item(list, 6, 10, 3, 5), Some(3));
+        assert_eq!(list_row_item(list, 6, 10, 3, 8), Some(6));
+        assert_eq!(list_row_item(list, 6, 10, 30, 8), None);
+    }
+
+    #[test]
+    fn maps_bar_columns_onto_breadcrumb_levels() {
+        let labels: Vec<String> = ["⌂", "users", "42"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(breadcrumb_level_at(&labels, 0), Some(0));
+        // Separators between labels do not belong to a level
+        assert_eq!(breadcrumb_level_at(&labels, 1), None);
+        assert_eq!(breadcrumb_level_at(&labels, 4), Some(1));
+        assert_eq!(breadcrumb_level_at(&labels, 8), Some(1));
+        assert_eq!(breadcrumb_level_at(&labels, 12), Some(2));
+        assert_eq!(breadcrumb_level_at(&labels, 14), None);
+    }
+
+    #[test]
+    fn double_click_needs_same_row_within_window() {
+        let mut clicks = ClickTracker::default();
+        let start = Instant::now();
+        assert!(!clicks.register(Pane::Keys, 2, start));
+        assert!(clicks.register(Pane::Keys, 2, start + Duration::from_millis(200)));
+        // A third click starts over instead of chaining
+        assert!(!clicks.register(Pane::Keys, 2, start + Duration::from_millis(300)));
+        assert!(!clicks.register(Pane::Keys, 3, start + Duration::from_millis(350)));
+        assert!(!clicks.register(Pane::Keys, 3, start + Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Most toasts on screen at once; older ones make room for new ones.
+pub const MAX_TOASTS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Error,
+}
+
+impl Severity {
+    /// How long a toast stays up; errors linger so they are not missed.
+    pub fn lifetime(self) -> Duration {
+        match self {
+            Severity::Info | Severity::Success => Duration::from_secs(4),
+            Severity::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub severity: Severity,
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+/// Timed status messages shown bottom-right, newest last.
+#[derive(Debug, Default, Clone)]
+pub struct Notifications {
+    toasts: VecDeque<Toast>,
+}
+
+impl Notifications {
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        let message = message.into();
+        // Repeating the same message just restarts its timer
+        self.toasts.retain(|toast| toast.message != message);
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            severity,
+            message,
+            shown_at: Instant::now(),
+        });
+    }
+
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Severity::Info, message);
+    }
+
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(Severity::Success, message);
+    }
+
+    /// Error toasts are also written to the log file.
+    pub fn error(&mut self, message: impl Into<String>) {
+        let message = message.into();
+        tracing::error!("{}", message);
+        self.push(Severity::Error, message);
+    }
+
+    /// Drops toasts that have been up for their whole lifetime.
+    pub fn expire(&mut self, now: Instant) {
+        self.toasts
+            .retain(|toast| now.duration_since(toast.shown_at) < toast.severity.lifetime());
+    }
+
+    pub fn toasts(&self) -> impl Iterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_queue_dedupe_and_expire() {
+        let mut notifications = Notifications::default();
+        for idx in 0..MAX_TOASTS + 1 {
+            notifications.info(format!("message {}", idx));
+        }
+        assert_eq!(notifications.toasts().count(), MAX_TOASTS);
+        assert_eq!(notifications.toasts().next().unwrap().message, "message 1");
+
+        notifications.error("message 2");
+        assert_eq!(notifications.toasts().count(), MAX_TOASTS);
+        assert_eq!(notifications.toasts().last().unwrap().message, "message 2");
+
+        let later = Instant::now() + Duration::from_secs(5);
+        notifications.expire(later);
+        let left: Vec<_> = notifications.toasts().map(|toast| toast.severity).collect();
+        assert_eq!(left, vec![Severity::Error]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::io::{self, Write};
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Asks the terminal to put `text` on its clipboard with an OSC 52 sequence. This reaches
+/// the local clipboard over SSH when the terminal supports it; nothing reports whether it
+/// did.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+    stdout.flush()
+}
+
+/// `ESC ] 52 ; c ; <base64> BEL`, wrapped in a DCS passthrough inside tmux.
+pub fn sequence(text: &str, in_tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (idx, &byte)| group | (byte as u32) << (16 - 8 * idx));
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(group >> (18 - 6 * idx) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequences_carry_base64_text() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("user:1 ✓".as_bytes()), "dXNlcjoxIOKckw==");
+        assert_eq!(sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(sequence("hi", true), "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\");
+    }
+}
@@ -0,0 +1,87 @@
+use redis::aio::MultiplexedConnection;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+use super::bulk_delete::scan_prefix_page;
+use super::key_delimiters::KeyDelimiters;
+
+#[derive(Debug)]
+pub enum PrefixWalkEvent {
+    /// Keys of one `SCAN` page.
+    Keys(Vec<String>),
+    /// The walk stopped on a failed page.
+    Error(String),
+    Done,
+}
+
+/// What the keys of a walk are gathered for.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WalkPurpose {
+    /// Counted into the preview of the open delete dialog.
+    DeletePreview,
+    /// Copied as a name list once the walk is done, along with `keys` named up front.
+    CopyKeyNames { keys: Vec<String> },
+}
+
+/// Background `SCAN` of every key beneath canonical prefixes, for the delete preview and
+/// for copying a folder's key names. Pages stream in so a folder of millions of keys
+/// neither freezes the UI nor holds the connection, and Esc can stop it with what was
+/// found so far. Dropping the walk cancels it.
+pub struct PrefixWalk {
+    /// What is being walked, for progress and notifications.
+    pub label: String,
+    pub purpose: WalkPurpose,
+    pub found: usize,
+    job: BackgroundJob<PrefixWalkEvent>,
+}
+
+impl PrefixWalk {
+    pub fn spawn(
+        con: MultiplexedConnection,
+        delimiters: KeyDelimiters,
+        label: String,
+        prefixes: Vec<String>,
+        purpose: WalkPurpose,
+    ) -> Self {
+        let job = BackgroundJob::spawn(|sender| walk(con, delimiters, prefixes, sender));
+        Self {
+            label,
+            purpose,
+            found: 0,
+            job,
+        }
+    }
+
+    /// Drains every queued event without blocking.
+    pub fn drain(&mut self) -> Vec<PrefixWalkEvent> {
+        self.job.drain()
+    }
+}
+
+async fn walk(
+    mut con: MultiplexedConnection,
+    delimiters: KeyDelimiters,
+    prefixes: Vec<String>,
+    sender: UnboundedSender<PrefixWalkEvent>,
+) {
+    for prefix in &prefixes {
+        let mut cursor = 0;
+        loop {
+            let (next_cursor, keys) = match scan_prefix_page(&mut con, &delimiters, prefix, cursor).await {
+                Ok(page) => page,
+                Err(e) => {
+                    let _ = sender.send(PrefixWalkEvent::Error(e));
+                    return;
+                }
+            };
+            if !keys.is_empty() && sender.send(PrefixWalkEvent::Keys(keys)).is_err() {
+                return;
+            }
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+    }
+    let _ = sender.send(PrefixWalkEvent::Done);
+}
@@ -0,0 +1,183 @@
+use redis::Value;
+
+use crate::command::resp::{pairs, value_text};
+
+/// RedisBloom key types the value panel can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbabilisticKind {
+    Bloom,
+    Cuckoo,
+    TopK,
+    TDigest,
+}
+
+impl ProbabilisticKind {
+    /// Kind of a key from its `TYPE` reply, in upper case.
+    pub fn from_type(key_type: &str) -> Option<Self> {
+        match key_type {
+            "MBBLOOM--" => Some(ProbabilisticKind::Bloom),
+            "MBBLOOMCF" => Some(ProbabilisticKind::Cuckoo),
+            "TOPK-TYPE" => Some(ProbabilisticKind::TopK),
+            "TDIS-TYPE" => Some(ProbabilisticKind::TDigest),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProbabilisticKind::Bloom => "Bloom filter",
+            ProbabilisticKind::Cuckoo => "Cuckoo filter",
+            ProbabilisticKind::TopK => "Top-K",
+            ProbabilisticKind::TDigest => "t-digest",
+        }
+    }
+
+    pub fn info_command(self) -> &'static str {
+        match self {
+            ProbabilisticKind::Bloom => "BF.INFO",
+            ProbabilisticKind::Cuckoo => "CF.INFO",
+            ProbabilisticKind::TopK => "TOPK.INFO",
+            ProbabilisticKind::TDigest => "TDIGEST.INFO",
+        }
+    }
+
+    /// Read-only command run on the words typed after `e`, one reply per word.
+    pub fn check_command(self) -> &'static str {
+        match self {
+            ProbabilisticKind::Bloom => "BF.MEXISTS",
+            ProbabilisticKind::Cuckoo => "CF.MEXISTS",
+            ProbabilisticKind::TopK => "TOPK.QUERY",
+            ProbabilisticKind::TDigest => "TDIGEST.QUANTILE",
+        }
+    }
+
+    fn answer(self, reply: &Value) -> String {
+        match (self, reply) {
+            (ProbabilisticKind::Bloom | ProbabilisticKind::Cuckoo, Value::Int(1) | Value::Boolean(true)) => {
+                "may exist".to_string()
+            }
+            (ProbabilisticKind::Bloom | ProbabilisticKind::Cuckoo, _) => "does not exist".to_string(),
+            (ProbabilisticKind::TopK, Value::Int(1) | Value::Boolean(true)) => "in the top-k".to_string(),
+            (ProbabilisticKind::TopK, _) => "not in the top-k".to_string(),
+            (ProbabilisticKind::TDigest, reply) => reply_text(reply).unwrap_or_else(|| "nan".to_string()),
+        }
+    }
+}
+
+/// What the value panel shows for a RedisBloom key: its INFO fields, extra detail for
+/// the kind, and the answers of the last existence check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbabilisticView {
+    pub kind: ProbabilisticKind,
+    pub info: Vec<(String, String)>,
+    /// Heavy hitters of a Top-K or quantiles of a t-digest.
+    pub details: Vec<(String, String)>,
+    pub last_check: Option<Vec<(String, String)>>,
+}
+
+impl ProbabilisticView {
+    pub fn new(kind: ProbabilisticKind, info: &Value) -> Self {
+        Self {
+            kind,
+            info: info_fields(info),
+            details: Vec::new(),
+            last_check: None,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        let mut lines = vec![self.kind.label().to_string(), String::new()];
+        lines.extend(aligned(&self.info));
+        if !self.details.is_empty() {
+            lines.push(String::new());
+            lines.push(
+                match self.kind {
+                    ProbabilisticKind::TopK => "Top items (TOPK.LIST WITHCOUNT):",
+                    _ => "Quantiles (TDIGEST.QUANTILE):",
+                }
+                .to_string(),
+            );
+            lines.extend(aligned(&self.details).into_iter().map(|line| format!("  {}", line)));
+        }
+        if let Some(check) = &self.last_check {
+            lines.push(String::new());
+            lines.push(format!("{}:", self.kind.check_command()));
+            lines.extend(aligned(check).into_iter().map(|line| format!("  {}", line)));
+        }
+        lines.push(String::new());
+        lines.push(format!("Press e to run {} on space-separated inputs.", self.kind.check_command()));
+        lines.join("\n")
+    }
+
+    /// Pairs each checked input with the answer in `reply`.
+    pub fn set_check(&mut self, inputs: &[&str], reply: &Value) {
+        let answers = match reply {
+            Value::Array(answers) => answers.iter().map(|answer| self.kind.answer(answer)).collect(),
+            other => vec![self.kind.answer(other)],
+        };
+        self.last_check = Some(inputs.iter().map(|input| input.to_string()).zip(answers).collect());
+    }
+}
+
+fn reply_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Int(n) => Some(n.to_string()),
+        Value::Double(n) => Some(n.to_string()),
+        Value::Nil => Some("(nil)".to_string()),
+        _ => value_text(value),
+    }
+}
+
+/// Name/value pairs of an INFO reply, which is flat in RESP2 and a map in RESP3. Also
+/// reads the item/count pairs of `TOPK.LIST ... WITHCOUNT`.
+pub fn info_fields(reply: &Value) -> Vec<(String, String)> {
+    pairs(reply)
+        .into_iter()
+        .filter_map(|(name, value)| Some((value_text(name)?, reply_text(value).unwrap_or_default())))
+        .collect()
+}
+
+fn aligned(fields: &[(String, String)]) -> Vec<String> {
+    let width = fields.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    fields
+        .iter()
+        .map(|(name, value)| format!("{:<width$}  {}", format!("{}:", name), value, width = width + 1))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn bloom_info_and_checks_read_as_text() {
+        assert_eq!(ProbabilisticKind::from_type("MBBLOOM--"), Some(ProbabilisticKind::Bloom));
+        assert_eq!(ProbabilisticKind::from_type("TDIS-TYPE"), Some(ProbabilisticKind::TDigest));
+        assert_eq!(ProbabilisticKind::from_type("HASH"), None);
+
+        let info = Value::Array(vec![
+            bulk("Capacity"),
+            Value::Int(1000),
+            bulk("Number of items inserted"),
+            Value::Int(500),
+        ]);
+        let mut view = ProbabilisticView::new(ProbabilisticKind::Bloom, &info);
+        view.set_check(&["alice", "bob"], &Value::Array(vec![Value::Int(1), Value::Int(0)]));
+        let text = view.text();
+        assert!(text.contains("Capacity:                  1000"), "{}", text);
+        assert!(text.contains("  alice:  may exist"), "{}", text);
+        assert!(text.contains("  bob:    does not exist"), "{}", text);
+
+        let mut digest = ProbabilisticView::new(ProbabilisticKind::TDigest, &Value::Map(vec![]));
+        digest.set_check(&["0.5"], &Value::Array(vec![bulk("12.5")]));
+        assert_eq!(digest.last_check, Some(vec![("0.5".to_string(), "12.5".to_string())]));
+        assert_eq!(
+            info_fields(&Value::Array(vec![bulk("home"), Value::Int(42)])),
+            [("home".to_string(), "42".to_string())]
+        );
+    }
+}
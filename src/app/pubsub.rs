@@ -0,0 +1,120 @@
+use futures_util::StreamExt;
+use redis::{Client, Msg};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+use super::timestamp::now_millis;
+use super::value_format::format_bytes_inline;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PubSubMessage {
+    /// Unix milliseconds at which the message arrived.
+    pub received_at: i64,
+    pub channel: String,
+    /// Pattern that matched, for PSUBSCRIBE deliveries.
+    pub pattern: Option<String>,
+    pub payload: String,
+}
+
+#[derive(Debug)]
+pub enum PubSubEvent {
+    Message(PubSubMessage),
+    Error(String),
+}
+
+/// Background subscriber for a set of channels and patterns.
+///
+/// A subscribed connection cannot run other commands, so it gets its own
+/// connection. While paused, messages stay queued in the channel and are
+/// delivered once the listener is resumed.
+pub struct PubSubListener {
+    pub channels: Vec<String>,
+    pub patterns: Vec<String>,
+    pub paused: bool,
+    job: BackgroundJob<PubSubEvent>,
+}
+
+impl PubSubListener {
+    pub fn spawn(client: Client, channels: Vec<String>, patterns: Vec<String>) -> Self {
+        let job = BackgroundJob::spawn(|sender| listen(client, channels.clone(), patterns.clone(), sender));
+        Self {
+            channels,
+            patterns,
+            paused: false,
+            job,
+        }
+    }
+
+    /// Drains every queued event unless the listener is paused.
+    pub fn drain(&mut self) -> Vec<PubSubEvent> {
+        if self.paused {
+            return Vec::new();
+        }
+        self.job.drain()
+    }
+}
+
+/// Splits subscription input into plain channels and glob patterns, which
+/// need PSUBSCRIBE.
+pub fn split_subscriptions(input: &str) -> (Vec<String>, Vec<String>) {
+    input
+        .split_whitespace()
+        .map(str::to_string)
+        .partition(|name| !name.contains(['*', '?', '[']))
+}
+
+async fn listen(
+    client: Client,
+    channels: Vec<String>,
+    patterns: Vec<String>,
+    sender: UnboundedSender<PubSubEvent>,
+) {
+    let mut pubsub = match client.get_async_pubsub().await {
+        Ok(pubsub) => pubsub,
+        Err(e) => {
+            let _ = sender.send(PubSubEvent::Error(format!("Pub/Sub connect failed: {}", e)));
+            return;
+        }
+    };
+    if !channels.is_empty() {
+        if let Err(e) = pubsub.subscribe(&channels).await {
+            let _ = sender.send(PubSubEvent::Error(format!("SUBSCRIBE failed: {}", e)));
+            return;
+        }
+    }
+    if !patterns.is_empty() {
+        if let Err(e) = pubsub.psubscribe(&patterns).await {
+            let _ = sender.send(PubSubEvent::Error(format!("PSUBSCRIBE failed: {}", e)));
+            return;
+        }
+    }
+
+    let mut messages = pubsub.into_on_message();
+    while let Some(msg) = messages.next().await {
+        if sender.send(PubSubEvent::Message(to_message(&msg))).is_err() {
+            return;
+        }
+    }
+    let _ = sender.send(PubSubEvent::Error("Pub/Sub connection closed".to_string()));
+}
+
+fn to_message(msg: &Msg) -> PubSubMessage {
+    PubSubMessage {
+        received_at: now_millis(),
+        channel: msg.get_channel_name().to_string(),
+        pattern: msg.get_pattern::<Option<String>>().ok().flatten(),
+        payload: format_bytes_inline(msg.get_payload_bytes()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_are_subscribed_as_patterns() {
+        let (channels, patterns) = split_subscriptions("orders news.* user:[12] alerts");
+        assert_eq!(channels, vec!["orders".to_string(), "alerts".to_string()]);
+        assert_eq!(patterns, vec!["news.*".to_string(), "user:[12]".to_string()]);
+    }
+}
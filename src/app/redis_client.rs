@@ -38,6 +38,10 @@ impl From<redis::RedisError> for RedisError {
 pub struct RedisClient {
     pub client: Option<Client>,
     pub connection: Option<MultiplexedConnection>,
+    /// Replica connection for browsing reads, when the profile sets `replica_url`.
+    pub read_connection: Option<MultiplexedConnection>,
+    /// `host:port` of the replica serving reads; `None` while reads use the primary.
+    pub read_target: Option<String>,
     pub db_index: usize,
     pub connection_status: String,
 }
@@ -47,6 +51,8 @@ impl RedisClient {
         Self {
             client: None,
             connection: None,
+            read_connection: None,
+            read_target: None,
             db_index: 0,
             connection_status: String::from("Not connected"),
         }
@@ -59,6 +65,8 @@ impl RedisClient {
         target_db_index_override: Option<usize>,
     ) -> Result<(), RedisError> {
         self.connection_status = format!("Connecting to {} ({})...", profile.name, profile.url);
+        self.read_connection = None;
+        self.read_target = None;
         let client = Client::open(profile.url.as_str())?;
         self.client = Some(client);
         let mut connection = self
@@ -82,161 +90,75 @@ impl RedisClient {
             "Connected to {} ({}), DB {}",
             profile.name, profile.url, self.db_index
         );
-        Ok(())
-    }
-
-    pub async fn fetch_keys(&mut self) -> Result<Vec<String>, RedisError> {
-        let mut keys = Vec::new();
-        if let Some(mut con) = self.connection.take() {
-            let mut cursor: u64 = 0;
-            loop {
-                match redis::cmd("SCAN")
-                    .arg(cursor)
-                    .arg("MATCH")
-                    .arg("*")
-                    .arg("COUNT")
-                    .arg(1000)
-                    .query_async::<(u64, Vec<String>)>(&mut con)
-                    .await
-                {
-                    Ok((next_cursor, batch)) => {
-                        cursor = next_cursor;
-                        keys.extend(batch);
-                        if cursor == 0 {
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        self.connection = Some(con);
-                        return Err(RedisError::Client(e));
-                    }
+        tracing::info!(profile = %profile.name, db = self.db_index, "connected to {}", redacted_url(&profile.url));
+        if let Some(replica_url) = &profile.replica_url {
+            match connect_replica(replica_url, db_to_select).await {
+                Ok((connection, target)) => {
+                    tracing::info!(profile = %profile.name, "reading from replica {}", target);
+                    self.read_connection = Some(connection);
+                    self.read_target = Some(target);
                 }
-            }
-            self.connection = Some(con);
-            Ok(keys)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available to fetch keys.".to_string(),
-            ))
-        }
-    }
-
-    pub async fn delete_prefix(
-        &mut self,
-        prefix: &str,
-        _delimiter: char,
-    ) -> Result<usize, RedisError> {
-        if let Some(con) = self.connection.as_mut() {
-            let pattern = format!("{}*", prefix);
-            let mut keys_to_delete: Vec<String> = Vec::new();
-            let mut cursor: u64 = 0;
-            loop {
-                match redis::cmd("SCAN")
-                    .arg(cursor)
-                    .arg("MATCH")
-                    .arg(&pattern)
-                    .arg("COUNT")
-                    .arg(100)
-                    .query_async::<(u64, Vec<String>)>(con)
-                    .await
-                {
-                    Ok((next_cursor, batch)) => {
-                        keys_to_delete.extend(batch);
-                        if next_cursor == 0 {
-                            break;
-                        }
-                        cursor = next_cursor;
-                    }
-                    Err(e) => return Err(RedisError::Client(e)),
+                Err(e) => {
+                    tracing::warn!(profile = %profile.name, "replica unavailable, reading from the primary: {}", e);
+                    self.connection_status.push_str(&format!(" | replica unavailable ({})", e));
                 }
             }
-            if keys_to_delete.is_empty() {
-                return Ok(0);
-            }
-            let count = redis::cmd("DEL")
-                .arg(keys_to_delete.as_slice())
-                .query_async::<i32>(con)
-                .await?;
-            Ok(count as usize)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for deleting prefix.".to_string(),
-            ))
-        }
-    }
-
-    pub async fn delete_key(&mut self, key: &str) -> Result<bool, RedisError> {
-        if let Some(con) = self.connection.as_mut() {
-            let count = redis::cmd("DEL")
-                .arg(key)
-                .query_async::<i32>(con)
-                .await?;
-            Ok(count > 0)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for deleting key.".to_string(),
-            ))
         }
+        Ok(())
     }
 
-    pub async fn get_key_type(&mut self, key: &str) -> Result<String, RedisError> {
-        if let Some(con) = self.connection.as_mut() {
-            let key_type = redis::cmd("TYPE")
-                .arg(key)
-                .query_async::<String>(con)
-                .await?;
-            Ok(key_type)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for key type.".to_string(),
-            ))
-        }
+    /// Connection for read-only browsing: the replica when one is configured, else the primary.
+    pub fn reader(&self) -> Option<MultiplexedConnection> {
+        self.read_connection.clone().or_else(|| self.connection.clone())
     }
 
-    pub async fn get_ttl(&mut self, key: &str) -> Result<i64, RedisError> {
+    pub async fn get_info_section(&mut self, section: &str) -> Result<String, RedisError> {
         if let Some(con) = self.connection.as_mut() {
-            let ttl = redis::cmd("TTL")
-                .arg(key)
-                .query_async::<i64>(con)
-                .await?;
-            Ok(ttl)
+            let info = redis::cmd("INFO").arg(section).query_async::<String>(con).await?;
+            Ok(info)
         } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for TTL.".to_string(),
-            ))
+            Err(RedisError::Connection(format!(
+                "No Redis connection available for INFO {}.",
+                section
+            )))
         }
     }
+}
 
-    pub async fn get_string(&mut self, key: &str) -> Result<Option<String>, RedisError> {
-        if let Some(con) = self.connection.as_mut() {
-            let value = redis::cmd("GET")
-                .arg(key)
-                .query_async::<Option<String>>(con)
-                .await?;
-            Ok(value)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for getting string.".to_string(),
-            ))
-        }
+impl Default for RedisClient {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    pub async fn get_info(&mut self) -> Result<String, RedisError> {
-        if let Some(con) = self.connection.as_mut() {
-            let info = redis::cmd("INFO").query_async::<String>(con).await?;
-            Ok(info)
-        } else {
-            Err(RedisError::Connection(
-                "No Redis connection available for INFO command.".to_string(),
-            ))
+/// Opens `url` on `db` and checks that it really is a replica, so a typo
+/// cannot silently send reads to another primary.
+async fn connect_replica(url: &str, db: u8) -> Result<(MultiplexedConnection, String), RedisError> {
+    let client = Client::open(url)?;
+    let target = match client.get_connection_info().addr() {
+        redis::ConnectionAddr::Tcp(host, port) | redis::ConnectionAddr::TcpTls { host, port, .. } => {
+            format!("{}:{}", host, port)
         }
+        addr => addr.to_string(),
+    };
+    let mut connection = client.get_multiplexed_async_connection().await?;
+    redis::cmd("SELECT").arg(db).query_async::<()>(&mut connection).await?;
+    let role: redis::Value = redis::cmd("ROLE").query_async(&mut connection).await?;
+    let is_replica = matches!(&role, redis::Value::Array(fields)
+        if fields.first().and_then(crate::command::resp::value_text).as_deref() == Some("slave"));
+    if !is_replica {
+        return Err(RedisError::Other(format!("{} is not a replica", target)));
     }
-
-    // Add more methods for hash, list, set, zset, stream as needed
+    Ok((connection, target))
 }
 
-impl Default for RedisClient {
-    fn default() -> Self {
-        Self::new()
+/// `url` with its password masked, for the log file and stats reports.
+pub(crate) fn redacted_url(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(mut parsed) if parsed.password().is_some() => {
+            let _ = parsed.set_password(Some("***"));
+            parsed.to_string()
+        }
+        _ => url.to_string(),
     }
 }
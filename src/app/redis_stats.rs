@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use super::replication::{parse_replication, ReplicationInfo};
+
 #[derive(Debug, Clone)]
 pub struct RedisStats {
     pub memory_used: u64,
@@ -24,9 +26,19 @@ pub struct RedisStats {
     pub connected_slaves: u32,
     pub used_cpu_sys: f64,
     pub used_cpu_user: f64,
+    /// Key counts per DB index from the `keyspace` section.
+    pub keyspace: HashMap<usize, KeyspaceCounts>,
+    pub replication: ReplicationInfo,
     pub last_updated: Instant,
 }
 
+/// One `dbN:keys=..,expires=..` line of INFO keyspace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KeyspaceCounts {
+    pub keys: u64,
+    pub expires: u64,
+}
+
 impl Default for RedisStats {
     fn default() -> Self {
         Self {
@@ -51,6 +63,8 @@ impl Default for RedisStats {
             connected_slaves: 0,
             used_cpu_sys: 0.0,
             used_cpu_user: 0.0,
+            keyspace: HashMap::new(),
+            replication: ReplicationInfo::default(),
             last_updated: Instant::now(),
         }
     }
@@ -142,6 +156,8 @@ impl RedisStats {
             stats.used_cpu_user = cpu_user.parse().unwrap_or(0.0);
         }
 
+        stats.keyspace = parse_keyspace(info);
+        stats.replication = parse_replication(info);
         stats.last_updated = Instant::now();
         stats
     }
@@ -155,7 +171,25 @@ impl RedisStats {
     }
 }
 
-fn format_bytes(bytes: u64) -> String {
+/// Parses `db3:keys=12405,expires=301,avg_ttl=0` lines; DBs without keys are absent.
+pub fn parse_keyspace(info: &str) -> HashMap<usize, KeyspaceCounts> {
+    info.lines()
+        .filter_map(|line| {
+            let (db, fields) = line.trim().strip_prefix("db")?.split_once(':')?;
+            let mut counts = KeyspaceCounts::default();
+            for (name, value) in fields.split(',').filter_map(|field| field.split_once('=')) {
+                match name {
+                    "keys" => counts.keys = value.parse().ok()?,
+                    "expires" => counts.expires = value.parse().ok()?,
+                    _ => {}
+                }
+            }
+            Some((db.parse().ok()?, counts))
+        })
+        .collect()
+}
+
+pub fn format_bytes(bytes: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
     let mut size = bytes as f64;
     let mut unit_index = 0;
@@ -187,4 +221,17 @@ fn format_duration(seconds: u64) -> String {
     } else {
         format!("{}s", secs)
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyspace_section_is_parsed_per_db() {
+        let info = "# Keyspace\r\ndb0:keys=12405,expires=301,avg_ttl=0\r\ndb3:keys=7,expires=0,avg_ttl=0,subexpiry=0\r\n";
+        let keyspace = RedisStats::from_info_string(info).keyspace;
+        assert_eq!(keyspace.len(), 2);
+        assert_eq!(keyspace[&0], KeyspaceCounts { keys: 12405, expires: 301 });
+        assert_eq!(keyspace[&3], KeyspaceCounts { keys: 7, expires: 0 });
+    }
+}
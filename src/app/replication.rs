@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+
+/// Topology and lag from the `replication` section of INFO.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplicationInfo {
+    /// `master` or `slave`.
+    pub role: String,
+    /// Bytes of replication stream this server has produced (or applied, on a replica).
+    pub master_repl_offset: u64,
+    /// Set when this server replicates from a master.
+    pub master: Option<MasterLink>,
+    pub replicas: Vec<ReplicaInfo>,
+}
+
+/// A replica's view of its master.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MasterLink {
+    pub host: String,
+    pub port: u16,
+    /// `up` or `down`.
+    pub link_status: String,
+    pub last_io_seconds_ago: Option<i64>,
+    pub link_down_since_seconds: Option<i64>,
+    pub sync_in_progress: bool,
+}
+
+impl MasterLink {
+    pub fn is_up(&self) -> bool {
+        self.link_status == "up"
+    }
+}
+
+/// One `slaveN:ip=..,port=..,state=..,offset=..,lag=..` line on a master.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplicaInfo {
+    pub ip: String,
+    pub port: u16,
+    /// `online`, `wait_bgsave`, `send_bulk`, ...
+    pub state: String,
+    pub offset: u64,
+    /// Seconds since the replica last acknowledged.
+    pub lag: u64,
+}
+
+impl ReplicaInfo {
+    pub fn is_online(&self) -> bool {
+        self.state == "online"
+    }
+}
+
+impl ReplicationInfo {
+    /// Bytes the replica still has to apply to catch up with this master.
+    pub fn offset_lag(&self, replica: &ReplicaInfo) -> u64 {
+        self.master_repl_offset.saturating_sub(replica.offset)
+    }
+
+    pub fn is_replica(&self) -> bool {
+        self.master.is_some()
+    }
+}
+
+pub fn parse_replication(info: &str) -> ReplicationInfo {
+    let fields: HashMap<&str, &str> = info
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(':'))
+        .collect();
+    let number = |name: &str| fields.get(name).and_then(|value| value.parse::<i64>().ok());
+
+    let master = fields.get("master_host").map(|host| MasterLink {
+        host: host.to_string(),
+        port: number("master_port").and_then(|port| u16::try_from(port).ok()).unwrap_or(0),
+        link_status: fields.get("master_link_status").unwrap_or(&"unknown").to_string(),
+        last_io_seconds_ago: number("master_last_io_seconds_ago"),
+        link_down_since_seconds: number("master_link_down_since_seconds"),
+        sync_in_progress: number("master_sync_in_progress") == Some(1),
+    });
+
+    let mut replicas: Vec<(usize, ReplicaInfo)> = fields
+        .iter()
+        .filter_map(|(name, value)| {
+            let index = name.strip_prefix("slave")?.parse().ok()?;
+            Some((index, parse_replica(value)))
+        })
+        .collect();
+    replicas.sort_by_key(|(index, _)| *index);
+
+    ReplicationInfo {
+        role: fields.get("role").unwrap_or(&"unknown").to_string(),
+        master_repl_offset: number("master_repl_offset").unwrap_or(0).max(0) as u64,
+        master,
+        replicas: replicas.into_iter().map(|(_, replica)| replica).collect(),
+    }
+}
+
+/// Parses `ip=10.0.0.2,port=6380,state=online,offset=1234,lag=0`.
+fn parse_replica(fields: &str) -> ReplicaInfo {
+    let mut replica = ReplicaInfo::default();
+    for (name, value) in fields.split(',').filter_map(|field| field.split_once('=')) {
+        match name {
+            "ip" => replica.ip = value.to_string(),
+            "port" => replica.port = value.parse().unwrap_or(0),
+            "state" => replica.state = value.to_string(),
+            "offset" => replica.offset = value.parse().unwrap_or(0),
+            "lag" => replica.lag = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    replica
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn master_and_replica_sections_are_parsed() {
+        let master = parse_replication(
+            "# Replication\r\nrole:master\r\nconnected_slaves:2\r\n\
+             slave1:ip=10.0.0.3,port=6381,state=wait_bgsave,offset=0,lag=12\r\n\
+             slave0:ip=10.0.0.2,port=6380,state=online,offset=1000,lag=0\r\n\
+             master_repl_offset:1500\r\n",
+        );
+        assert_eq!(master.role, "master");
+        assert!(!master.is_replica());
+        assert_eq!(master.replicas.len(), 2);
+        assert_eq!(master.replicas[0].ip, "10.0.0.2");
+        assert!(master.replicas[0].is_online());
+        assert_eq!(master.offset_lag(&master.replicas[0]), 500);
+        assert_eq!(master.replicas[1].lag, 12);
+
+        let replica = parse_replication(
+            "role:slave\r\nmaster_host:10.0.0.1\r\nmaster_port:6379\r\nmaster_link_status:down\r\n\
+             master_last_io_seconds_ago:-1\r\nmaster_sync_in_progress:0\r\nmaster_link_down_since_seconds:42\r\n\
+             connected_slaves:0\r\nmaster_repl_offset:900\r\n",
+        );
+        let link = replica.master.unwrap();
+        assert_eq!((link.host.as_str(), link.port), ("10.0.0.1", 6379));
+        assert!(!link.is_up());
+        assert_eq!(link.link_down_since_seconds, Some(42));
+        assert!(replica.replicas.is_empty());
+    }
+}
@@ -0,0 +1,410 @@
+use redis::aio::MultiplexedConnection;
+use redis::RedisResult;
+use serde_json::Value as JsonValue;
+
+/// Field/value pairs of a hash or a stream entry.
+type FieldValues = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A command name and its arguments.
+pub type CommandArgs = Vec<Vec<u8>>;
+
+/// Commands a pasted script may run; each must target the same key.
+const PASTE_COMMANDS: [&str; 10] =
+    ["DEL", "SET", "HSET", "RPUSH", "SADD", "ZADD", "XADD", "JSON.SET", "RESTORE", "EXPIRE"];
+
+/// Contents of a key, with the raw bytes the commands recreating it need.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyContents {
+    String(Vec<u8>),
+    Hash(FieldValues),
+    List(Vec<Vec<u8>>),
+    Set(Vec<Vec<u8>>),
+    ZSet(Vec<(Vec<u8>, f64)>),
+    Stream(Vec<(String, FieldValues)>),
+    Json(String),
+    /// `DUMP` payload of any other type, or of an empty stream.
+    Dump(Vec<u8>),
+}
+
+/// Reads `key` for [`reproduce_script`]; `None` when it does not exist.
+pub async fn fetch_key_contents(
+    con: &mut MultiplexedConnection,
+    key: &str,
+) -> RedisResult<Option<(KeyContents, i64)>> {
+    let key_type: String = redis::cmd("TYPE").arg(key).query_async(con).await?;
+    let contents = match key_type.as_str() {
+        "none" => return Ok(None),
+        "string" => KeyContents::String(redis::cmd("GET").arg(key).query_async(con).await?),
+        "hash" => KeyContents::Hash(redis::cmd("HGETALL").arg(key).query_async(con).await?),
+        "list" => KeyContents::List(redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query_async(con).await?),
+        "set" => {
+            let mut members: Vec<Vec<u8>> = redis::cmd("SMEMBERS").arg(key).query_async(con).await?;
+            members.sort();
+            KeyContents::Set(members)
+        }
+        "zset" => KeyContents::ZSet(
+            redis::cmd("ZRANGE").arg(key).arg(0).arg(-1).arg("WITHSCORES").query_async(con).await?,
+        ),
+        "stream" => {
+            let entries: Vec<(String, FieldValues)> =
+                redis::cmd("XRANGE").arg(key).arg("-").arg("+").query_async(con).await?;
+            if entries.is_empty() {
+                KeyContents::Dump(redis::cmd("DUMP").arg(key).query_async(con).await?)
+            } else {
+                KeyContents::Stream(entries)
+            }
+        }
+        "ReJSON-RL" => KeyContents::Json(redis::cmd("JSON.GET").arg(key).query_async(con).await?),
+        _ => KeyContents::Dump(redis::cmd("DUMP").arg(key).query_async(con).await?),
+    };
+    let ttl: i64 = redis::cmd("TTL").arg(key).query_async(con).await?;
+    Ok(Some((contents, ttl)))
+}
+
+/// [`reproduce_commands`] one per line in redis-cli syntax.
+pub fn reproduce_script(key: &str, contents: &KeyContents, ttl: i64) -> String {
+    format_script(&reproduce_commands(key, contents, ttl))
+}
+
+/// Commands that recreate `key` from scratch: a `DEL`, the write for its type, and an
+/// `EXPIRE` when `ttl` is positive.
+pub fn reproduce_commands(key: &str, contents: &KeyContents, ttl: i64) -> Vec<CommandArgs> {
+    let key = key.as_bytes().to_vec();
+    let mut commands = vec![vec![b"DEL".to_vec(), key.clone()]];
+    let command = |name: &str| vec![name.as_bytes().to_vec(), key.clone()];
+    match contents {
+        KeyContents::String(value) => {
+            let mut set = command("SET");
+            set.push(value.clone());
+            commands.push(set);
+        }
+        KeyContents::Hash(fields) => {
+            let mut hset = command("HSET");
+            hset.extend(fields.iter().flat_map(|(field, value)| [field.clone(), value.clone()]));
+            commands.push(hset);
+        }
+        KeyContents::List(items) => {
+            let mut rpush = command("RPUSH");
+            rpush.extend(items.iter().cloned());
+            commands.push(rpush);
+        }
+        KeyContents::Set(members) => {
+            let mut sadd = command("SADD");
+            sadd.extend(members.iter().cloned());
+            commands.push(sadd);
+        }
+        KeyContents::ZSet(members) => {
+            let mut zadd = command("ZADD");
+            zadd.extend(
+                members
+                    .iter()
+                    .flat_map(|(member, score)| [score_arg(*score).into_bytes(), member.clone()]),
+            );
+            commands.push(zadd);
+        }
+        KeyContents::Stream(entries) => {
+            for (id, fields) in entries {
+                let mut xadd = command("XADD");
+                xadd.push(id.as_bytes().to_vec());
+                xadd.extend(fields.iter().flat_map(|(field, value)| [field.clone(), value.clone()]));
+                commands.push(xadd);
+            }
+        }
+        KeyContents::Json(json) => {
+            let mut json_set = command("JSON.SET");
+            json_set.push(b"$".to_vec());
+            json_set.push(json.as_bytes().to_vec());
+            commands.push(json_set);
+        }
+        KeyContents::Dump(payload) => {
+            let mut restore = command("RESTORE");
+            restore.push(b"0".to_vec());
+            restore.push(payload.clone());
+            commands.push(restore);
+        }
+    }
+    if ttl > 0 {
+        let mut expire = command("EXPIRE");
+        expire.push(ttl.to_string().into_bytes());
+        commands.push(expire);
+    }
+    commands
+}
+
+pub fn format_script(commands: &[CommandArgs]) -> String {
+    commands
+        .iter()
+        .map(|args| args.iter().map(|arg| cli_quote(arg)).collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads pasted text back into the key and the commands creating it: either the JSON
+/// document `J` copies or the commands `c` copies.
+pub fn parse_paste(text: &str) -> Result<(String, Vec<CommandArgs>), String> {
+    let text = text.trim();
+    if text.starts_with('{') {
+        let document: JsonValue = serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+        let (key, contents, ttl) = contents_from_document(&document)?;
+        let commands = reproduce_commands(&key, &contents, ttl);
+        return Ok((key, commands));
+    }
+    let commands = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(split_cli_args)
+        .collect::<Result<Vec<_>, _>>()?;
+    let Some(key) = commands.first().and_then(|args| args.get(1)).cloned() else {
+        return Err("The clipboard holds neither a key as JSON nor redis-cli commands.".to_string());
+    };
+    for args in &commands {
+        let name = String::from_utf8_lossy(&args[0]).to_uppercase();
+        if !PASTE_COMMANDS.contains(&name.as_str()) {
+            return Err(format!("'{}' is not a command that creates a key.", name));
+        }
+        if args.get(1) != Some(&key) {
+            return Err(format!("Every command must write the same key; {} writes another.", name));
+        }
+    }
+    if commands.iter().all(|args| args[0].eq_ignore_ascii_case(b"DEL")) {
+        return Err("The pasted commands only delete the key.".to_string());
+    }
+    Ok((String::from_utf8_lossy(&key).into_owned(), commands))
+}
+
+/// The key, contents and TTL of a `get --json` document. Values are taken as the text
+/// they were copied as, so binary strings come back in their displayed form.
+fn contents_from_document(document: &JsonValue) -> Result<(String, KeyContents, i64), String> {
+    let key = document["key"].as_str().ok_or("The JSON has no \"key\" field.")?;
+    let key_type = document["type"].as_str().ok_or("The JSON has no \"type\" field.")?;
+    let ttl = document["ttl"].as_i64().unwrap_or(-1);
+    let value = &document["value"];
+    let malformed = || format!("The \"value\" does not look like a {} value.", key_type);
+    let items = || value.as_array().ok_or_else(malformed);
+    let contents = match key_type {
+        "string" => KeyContents::String(json_bytes(value)),
+        "hash" => KeyContents::Hash(
+            value
+                .as_object()
+                .ok_or_else(malformed)?
+                .iter()
+                .map(|(field, value)| (field.as_bytes().to_vec(), json_bytes(value)))
+                .collect(),
+        ),
+        "list" => KeyContents::List(items()?.iter().map(json_bytes).collect()),
+        "set" => KeyContents::Set(items()?.iter().map(json_bytes).collect()),
+        "zset" => KeyContents::ZSet(
+            items()?
+                .iter()
+                .map(|row| Some((json_bytes(&row["member"]), row["score"].as_f64()?)))
+                .collect::<Option<_>>()
+                .ok_or_else(malformed)?,
+        ),
+        "stream" => KeyContents::Stream(
+            items()?
+                .iter()
+                .map(|entry| {
+                    let fields = entry["fields"].as_object()?;
+                    let fields = fields.iter().map(|(field, value)| (field.as_bytes().to_vec(), json_bytes(value)));
+                    Some((entry["id"].as_str()?.to_string(), fields.collect()))
+                })
+                .collect::<Option<_>>()
+                .ok_or_else(malformed)?,
+        ),
+        "ReJSON-RL" => KeyContents::Json(value.to_string()),
+        other => return Err(format!("A {} key cannot be recreated from JSON; copy it as commands (c).", other)),
+    };
+    Ok((key.to_string(), contents, ttl))
+}
+
+/// A JSON string as its text, anything else (a decoded MessagePack value) as JSON.
+fn json_bytes(value: &JsonValue) -> Vec<u8> {
+    match value {
+        JsonValue::String(text) => text.as_bytes().to_vec(),
+        other => other.to_string().into_bytes(),
+    }
+}
+
+/// Splits a line the way redis-cli does: whitespace-separated words, double quotes with
+/// `\xHH`, `\n`, `\r`, `\t`, `\b`, `\a` and `\<char>` escapes, and single quotes with `\'`.
+pub fn split_cli_args(line: &str) -> Result<CommandArgs, String> {
+    let unbalanced = || format!("Unbalanced quotes in: {}", line);
+    let bytes = line.as_bytes();
+    let mut args = Vec::new();
+    let mut idx = 0;
+    loop {
+        while idx < bytes.len() && bytes[idx].is_ascii_whitespace() {
+            idx += 1;
+        }
+        if idx == bytes.len() {
+            return Ok(args);
+        }
+        let mut arg = Vec::new();
+        let mut quote = None;
+        loop {
+            let Some(&byte) = bytes.get(idx) else {
+                if quote.is_some() {
+                    return Err(unbalanced());
+                }
+                break;
+            };
+            match quote {
+                Some(b'"') if byte == b'\\' && idx + 3 < bytes.len() && bytes[idx + 1] == b'x' => {
+                    let hex = &bytes[idx + 2..idx + 4];
+                    if hex.iter().all(u8::is_ascii_hexdigit) {
+                        let hex = std::str::from_utf8(hex).unwrap_or_default();
+                        arg.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                        idx += 3;
+                    } else {
+                        arg.push(b'x');
+                        idx += 1;
+                    }
+                }
+                Some(b'"') if byte == b'\\' && idx + 1 < bytes.len() => {
+                    idx += 1;
+                    arg.push(match bytes[idx] {
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        b'b' => 0x08,
+                        b'a' => 0x07,
+                        other => other,
+                    });
+                }
+                Some(b'\'') if byte == b'\\' && bytes.get(idx + 1) == Some(&b'\'') => {
+                    idx += 1;
+                    arg.push(b'\'');
+                }
+                Some(open) if byte == open => {
+                    // A closing quote must end the argument
+                    if bytes.get(idx + 1).is_some_and(|next| !next.is_ascii_whitespace()) {
+                        return Err(unbalanced());
+                    }
+                    idx += 1;
+                    break;
+                }
+                Some(_) => arg.push(byte),
+                None if byte.is_ascii_whitespace() => break,
+                None if byte == b'"' || byte == b'\'' => quote = Some(byte),
+                None => arg.push(byte),
+            }
+            idx += 1;
+        }
+        args.push(arg);
+    }
+}
+
+/// ZADD score as redis-cli accepts it, including the infinities.
+fn score_arg(score: f64) -> String {
+    match score {
+        f64::INFINITY => "+inf".to_string(),
+        f64::NEG_INFINITY => "-inf".to_string(),
+        score => score.to_string(),
+    }
+}
+
+/// Quotes an argument the way redis-cli splits its input: bare when it is plain ASCII,
+/// otherwise in double quotes with `\"`, `\\`, `\n`, `\r`, `\t` and `\xHH` escapes.
+/// UTF-8 text keeps its characters; other bytes are escaped.
+pub fn cli_quote(arg: &[u8]) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() && !matches!(byte, b'"' | b'\'' | b'\\'));
+    if plain {
+        return String::from_utf8_lossy(arg).into_owned();
+    }
+    let mut quoted = String::from("\"");
+    let escape_byte = |quoted: &mut String, byte: u8| quoted.push_str(&format!("\\x{:02x}", byte));
+    match std::str::from_utf8(arg) {
+        Ok(text) => {
+            for ch in text.chars() {
+                match ch {
+                    '"' => quoted.push_str("\\\""),
+                    '\\' => quoted.push_str("\\\\"),
+                    '\n' => quoted.push_str("\\n"),
+                    '\r' => quoted.push_str("\\r"),
+                    '\t' => quoted.push_str("\\t"),
+                    ch if ch.is_control() => {
+                        ch.encode_utf8(&mut [0; 4]).bytes().for_each(|byte| escape_byte(&mut quoted, byte))
+                    }
+                    ch => quoted.push(ch),
+                }
+            }
+        }
+        Err(_) => {
+            for &byte in arg {
+                match byte {
+                    b'"' => quoted.push_str("\\\""),
+                    b'\\' => quoted.push_str("\\\\"),
+                    b' ' => quoted.push(' '),
+                    byte if byte.is_ascii_graphic() => quoted.push(byte as char),
+                    byte => escape_byte(&mut quoted, byte),
+                }
+            }
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arguments_are_quoted_for_redis_cli() {
+        assert_eq!(cli_quote(b"user:1"), "user:1");
+        assert_eq!(cli_quote(b""), "\"\"");
+        assert_eq!(cli_quote(b"Ada Lovelace"), "\"Ada Lovelace\"");
+        assert_eq!(cli_quote("say \"hi\"\n".as_bytes()), "\"say \\\"hi\\\"\\n\"");
+        assert_eq!(cli_quote("café".as_bytes()), "\"café\"");
+        assert_eq!(cli_quote(b"it's"), "\"it's\"");
+        assert_eq!(cli_quote(&[0x00, b'a', 0xff]), "\"\\x00a\\xff\"");
+    }
+
+    #[test]
+    fn scripts_recreate_the_key_and_its_ttl() {
+        let hash = KeyContents::Hash(vec![(b"name".to_vec(), b"Ada Lovelace".to_vec())]);
+        assert_eq!(
+            reproduce_script("user:1", &hash, 60),
+            "DEL user:1\nHSET user:1 name \"Ada Lovelace\"\nEXPIRE user:1 60"
+        );
+        let zset = KeyContents::ZSet(vec![(b"a".to_vec(), 1.5), (b"b".to_vec(), f64::NEG_INFINITY)]);
+        assert_eq!(reproduce_script("z", &zset, -1), "DEL z\nZADD z 1.5 a -inf b");
+        let stream = KeyContents::Stream(vec![
+            ("1-0".to_string(), vec![(b"k".to_vec(), b"v".to_vec())]),
+            ("2-0".to_string(), vec![(b"k".to_vec(), b"w".to_vec())]),
+        ]);
+        assert_eq!(reproduce_script("s", &stream, -1), "DEL s\nXADD s 1-0 k v\nXADD s 2-0 k w");
+        assert_eq!(
+            reproduce_script("j", &KeyContents::Json("{\"a\":1}".to_string()), -1),
+            "DEL j\nJSON.SET j $ \"{\\\"a\\\":1}\""
+        );
+    }
+
+    #[test]
+    fn pasted_scripts_and_documents_parse_back() {
+        assert_eq!(
+            split_cli_args(r#"SET k "a \"b\"\x00\n" 'it\'s'"#).unwrap(),
+            [b"SET".to_vec(), b"k".to_vec(), b"a \"b\"\x00\n".to_vec(), b"it's".to_vec()]
+        );
+        assert!(split_cli_args(r#"SET k "open"#).is_err());
+
+        let hash = KeyContents::Hash(vec![(b"name".to_vec(), vec![b'A', 0xff, b'\n'])]);
+        let commands = reproduce_commands("user:1", &hash, 60);
+        let (key, parsed) = parse_paste(&format_script(&commands)).unwrap();
+        assert_eq!((key.as_str(), parsed), ("user:1", commands));
+
+        let document = r#"{"key":"z","ttl":-1,"type":"zset","value":[{"member":"a","score":2.0}]}"#;
+        let (key, parsed) = parse_paste(document).unwrap();
+        assert_eq!(key, "z");
+        assert_eq!(format_script(&parsed), "DEL z\nZADD z 2 a");
+
+        assert!(parse_paste("FLUSHALL").is_err());
+        assert!(parse_paste("SET a 1\nSET b 2").is_err());
+        assert!(parse_paste("DEL a").is_err());
+        assert!(parse_paste(r#"{"key":"b","type":"MBbloom--","value":{}}"#).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use redis::Cmd;
+
+use crate::keymap::Action;
+
+/// An entry of the key action menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAction {
+    EditValue,
+    AddItem,
+    SetTtl,
+    Rename,
+    CopyName,
+    CopyValue,
+    CopyJson,
+    CopyCommand,
+    Export,
+    SaveValue,
+    PfAdd,
+    Probe,
+    FollowStream,
+    ConsumerGroups,
+    KeyInfo,
+    Bookmark,
+    Delete,
+}
+
+impl KeyAction {
+    pub fn label(self, key_type: &str) -> &'static str {
+        match self {
+            KeyAction::EditValue => "Edit value",
+            KeyAction::AddItem => match key_type {
+                "hash" => "Add field",
+                "list" => "Push element",
+                "set" => "Add members",
+                "zset" => "Add member",
+                _ => "Add entry",
+            },
+            KeyAction::SetTtl => "Set TTL",
+            KeyAction::Rename => "Rename",
+            KeyAction::CopyName => "Copy key name",
+            KeyAction::CopyValue => "Copy value",
+            KeyAction::CopyJson => "Copy as JSON",
+            KeyAction::CopyCommand => "Copy as redis-cli commands",
+            KeyAction::Export => "Export to JSON file",
+            KeyAction::SaveValue => "Save value to file",
+            KeyAction::PfAdd => "PFADD elements",
+            KeyAction::Probe => "Check items",
+            KeyAction::FollowStream => "Follow stream",
+            KeyAction::ConsumerGroups => "Consumer groups",
+            KeyAction::KeyInfo => "Key details",
+            KeyAction::Bookmark => "Toggle bookmark",
+            KeyAction::Delete => "Delete",
+        }
+    }
+
+    /// The keymap action doing the same, whose key is shown next to the entry.
+    pub fn shortcut(self) -> Option<Action> {
+        match self {
+            KeyAction::CopyName => Some(Action::CopyKeyName),
+            KeyAction::CopyValue => Some(Action::CopyValue),
+            KeyAction::CopyJson => Some(Action::CopyJson),
+            KeyAction::CopyCommand => Some(Action::CopyCommand),
+            KeyAction::PfAdd => Some(Action::PfAdd),
+            KeyAction::Probe => Some(Action::Probe),
+            KeyAction::FollowStream => Some(Action::StreamFollow),
+            KeyAction::ConsumerGroups => Some(Action::ConsumerGroups),
+            KeyAction::KeyInfo => Some(Action::KeyInfo),
+            KeyAction::Bookmark => Some(Action::ToggleBookmark),
+            KeyAction::Delete => Some(Action::Delete),
+            _ => None,
+        }
+    }
+
+    /// What to type for actions that take input in the menu.
+    pub fn prompt(self, key_type: &str) -> Option<&'static str> {
+        match (self, key_type) {
+            (KeyAction::EditValue, "rejson-rl") => Some("JSON.SET $ <json>"),
+            (KeyAction::EditValue, _) => Some("SET <value> (keeps the TTL)"),
+            (KeyAction::AddItem, "hash") => Some("HSET <field> <value>"),
+            (KeyAction::AddItem, "list") => Some("RPUSH <element>"),
+            (KeyAction::AddItem, "set") => Some("SADD <member...>"),
+            (KeyAction::AddItem, "zset") => Some("ZADD <score> <member>"),
+            (KeyAction::AddItem, _) => Some("XADD * <field> <value> ..."),
+            (KeyAction::SetTtl, _) => Some("EXPIRE <seconds> (empty or -1 to PERSIST)"),
+            (KeyAction::Rename, _) => Some("RENAMENX <new name>"),
+            (KeyAction::SaveValue, _) => Some("Write the raw bytes to <path> (must not exist)"),
+            _ => None,
+        }
+    }
+
+    fn writes(self) -> bool {
+        matches!(
+            self,
+            KeyAction::EditValue
+                | KeyAction::AddItem
+                | KeyAction::SetTtl
+                | KeyAction::Rename
+                | KeyAction::PfAdd
+                | KeyAction::Delete
+        )
+    }
+}
+
+/// The actions valid for a key of `key_type` (the lower-cased `TYPE` reply). Writes are
+/// left out on read-only profiles.
+pub fn actions_for(key_type: &str, hyperloglog: bool, read_only: bool) -> Vec<KeyAction> {
+    use KeyAction::*;
+    let mut actions = match key_type {
+        "string" if hyperloglog => vec![PfAdd],
+        "string" | "rejson-rl" => vec![EditValue],
+        "hash" | "list" | "set" | "zset" => vec![AddItem],
+        "stream" => vec![AddItem, FollowStream, ConsumerGroups],
+        "mbbloom--" | "mbbloomcf" | "topk-type" | "tdis-type" => vec![Probe],
+        _ => Vec::new(),
+    };
+    actions.extend([SetTtl, Rename, CopyName, CopyValue, CopyJson, CopyCommand, Export]);
+    if matches!(key_type, "string" | "rejson-rl") {
+        actions.push(SaveValue);
+    }
+    actions.extend([KeyInfo, Bookmark, Delete]);
+    actions.retain(|action| !(read_only && action.writes()));
+    actions
+}
+
+/// The command a prompted action runs on `key` with the typed `input`.
+pub fn prompt_command(action: KeyAction, key_type: &str, key: &str, input: &str) -> Result<Cmd, String> {
+    let cmd = match (action, key_type) {
+        (KeyAction::EditValue, "rejson-rl") => {
+            serde_json::from_str::<serde_json::Value>(input).map_err(|e| format!("Invalid JSON: {}", e))?;
+            redis::cmd("JSON.SET").arg(key).arg("$").arg(input).clone()
+        }
+        (KeyAction::EditValue, _) => redis::cmd("SET").arg(key).arg(input).arg("KEEPTTL").clone(),
+        (KeyAction::AddItem, "hash") => {
+            let Some((field, value)) = input.trim_start().split_once(' ') else {
+                return Err("Type a field and a value.".to_string());
+            };
+            redis::cmd("HSET").arg(key).arg(field).arg(value.trim_start()).clone()
+        }
+        (KeyAction::AddItem, "list") => redis::cmd("RPUSH").arg(key).arg(input).clone(),
+        (KeyAction::AddItem, "set") => {
+            let members: Vec<&str> = input.split_whitespace().collect();
+            if members.is_empty() {
+                return Err("Type at least one member.".to_string());
+            }
+            redis::cmd("SADD").arg(key).arg(members).clone()
+        }
+        (KeyAction::AddItem, "zset") => {
+            let (score, member) = input.trim_start().split_once(' ').unwrap_or((input.trim(), ""));
+            let score: f64 = score.parse().map_err(|_| format!("'{}' is not a score.", score))?;
+            if member.trim().is_empty() {
+                return Err("Type a score and a member.".to_string());
+            }
+            redis::cmd("ZADD").arg(key).arg(score).arg(member.trim_start()).clone()
+        }
+        (KeyAction::AddItem, _) => {
+            let words: Vec<&str> = input.split_whitespace().collect();
+            if words.is_empty() || !words.len().is_multiple_of(2) {
+                return Err("Type field and value pairs.".to_string());
+            }
+            redis::cmd("XADD").arg(key).arg("*").arg(words).clone()
+        }
+        (KeyAction::SetTtl, _) => match input.trim() {
+            "" | "-1" => redis::cmd("PERSIST").arg(key).clone(),
+            seconds => {
+                let seconds: u64 = seconds.parse().map_err(|_| format!("'{}' is not a number of seconds.", seconds))?;
+                redis::cmd("EXPIRE").arg(key).arg(seconds).clone()
+            }
+        },
+        (KeyAction::Rename, _) => {
+            let new_name = input.trim();
+            if new_name.is_empty() || new_name == key {
+                return Err("Type a new name for the key.".to_string());
+            }
+            redis::cmd("RENAMENX").arg(key).arg(new_name).clone()
+        }
+        _ => return Err("This action takes no input.".to_string()),
+    };
+    Ok(cmd)
+}
+
+/// File a key is exported to: its name with anything but letters, digits, `.`, `-` and
+/// `_` replaced by `_`.
+pub fn export_file_name(key: &str) -> String {
+    format!("{}.json", file_stem(key))
+}
+
+/// Suggested path for "Save value to file": `.json` for JSON documents, `.bin` otherwise.
+pub fn value_file_name(key: &str, key_type: &str) -> String {
+    let extension = if key_type == "rejson-rl" { "json" } else { "bin" };
+    format!("{}.{}", file_stem(key), extension)
+}
+
+fn file_stem(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') { c } else { '_' })
+        .collect();
+    name.trim_start_matches('.').to_string()
+}
+
+/// The typed path with a leading `~/` resolved against `$HOME`.
+pub fn value_file_path(input: &str) -> Result<PathBuf, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Enter a file path.".to_string());
+    }
+    match (input.strip_prefix("~/"), std::env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Ok(Path::new(&home).join(rest)),
+        _ => Ok(PathBuf::from(input)),
+    }
+}
+
+/// Writes `bytes` exactly as given to a new file, refusing to replace an existing one.
+pub fn write_value_file(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let mut file = fs::OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+/// Popup menu of the actions for the key shown in the value panel (`a` or right-click).
+#[derive(Debug, Default, Clone)]
+pub struct ActionMenuState {
+    pub is_active: bool,
+    pub key: String,
+    /// Lower-cased `TYPE` of the key.
+    pub key_type: String,
+    pub actions: Vec<KeyAction>,
+    pub selected: usize,
+    /// Action whose input is being typed.
+    pub prompt: Option<KeyAction>,
+    pub input: String,
+    pub cursor: usize,
+}
+
+impl ActionMenuState {
+    pub fn open(&mut self, key: String, key_type: String, actions: Vec<KeyAction>) {
+        *self = Self {
+            is_active: true,
+            key,
+            key_type,
+            actions,
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+        self.prompt = None;
+    }
+
+    pub fn select(&mut self, forward: bool) {
+        let count = self.actions.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    pub fn selected_action(&self) -> Option<KeyAction> {
+        self.actions.get(self.selected).copied()
+    }
+
+    pub fn open_prompt(&mut self, action: KeyAction, initial: String) {
+        self.prompt = Some(action);
+        self.cursor = initial.len();
+        self.input = initial;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packed(cmd: Result<Cmd, String>) -> String {
+        String::from_utf8_lossy(&cmd.unwrap().get_packed_command()).replace("\r\n", " ")
+    }
+
+    #[test]
+    fn actions_depend_on_the_type_and_profile() {
+        let hash = actions_for("hash", false, false);
+        assert_eq!(hash[0], KeyAction::AddItem);
+        assert!(hash.contains(&KeyAction::Rename));
+        assert!(!hash.contains(&KeyAction::EditValue));
+        assert_eq!(actions_for("string", true, false)[0], KeyAction::PfAdd);
+        assert!(actions_for("stream", false, false).contains(&KeyAction::ConsumerGroups));
+        assert_eq!(actions_for("mbbloom--", false, false)[0], KeyAction::Probe);
+
+        let read_only = actions_for("string", false, true);
+        assert!(!read_only.iter().any(|action| action.writes()));
+        assert!(read_only.contains(&KeyAction::Export));
+        assert!(read_only.contains(&KeyAction::CopyJson));
+        assert!(read_only.contains(&KeyAction::SaveValue));
+        assert!(actions_for("rejson-rl", false, false).contains(&KeyAction::SaveValue));
+        assert!(!hash.contains(&KeyAction::SaveValue));
+    }
+
+    #[test]
+    fn values_save_byte_exact_to_new_files() {
+        assert_eq!(value_file_name("img:1", "string"), "img_1.bin");
+        assert_eq!(value_file_name("doc:1", "rejson-rl"), "doc_1.json");
+        assert!(value_file_path("  ").is_err());
+        assert_eq!(value_file_path(" out.bin ").unwrap(), PathBuf::from("out.bin"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blob.bin");
+        let bytes = [0x89, b'P', b'N', b'G', 0x00, 0xff, b'\r', b'\n'];
+        write_value_file(&path, &bytes).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        assert!(write_value_file(&path, b"other").is_err());
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+    }
+
+    #[test]
+    fn prompts_become_commands() {
+        let hset = packed(prompt_command(KeyAction::AddItem, "hash", "user:1", "name Ada Lovelace"));
+        assert!(hset.ends_with("$4 name $12 Ada Lovelace "), "{}", hset);
+        assert!(packed(prompt_command(KeyAction::SetTtl, "list", "k", "")).contains("PERSIST"));
+        assert!(packed(prompt_command(KeyAction::SetTtl, "list", "k", "60")).contains("EXPIRE $1 k $2 60"));
+        assert!(prompt_command(KeyAction::SetTtl, "list", "k", "soon").is_err());
+        assert!(prompt_command(KeyAction::AddItem, "zset", "k", "high score").is_err());
+        assert!(prompt_command(KeyAction::AddItem, "stream", "k", "a 1 b").is_err());
+        assert!(prompt_command(KeyAction::EditValue, "rejson-rl", "k", "{oops").is_err());
+        assert!(prompt_command(KeyAction::Rename, "set", "k", " k ").is_err());
+        assert_eq!(export_file_name("user:1/profile"), "user_1_profile.json");
+        assert_eq!(export_file_name("../etc"), "_etc.json");
+    }
+}
@@ -0,0 +1,588 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use super::key_sampler::{KeySample, KeySampler};
+use super::monitor::{split_arguments, MonitorEntry, MonitorSession};
+use super::timestamp::now_millis;
+
+/// Largest keys kept per type.
+pub const BIG_KEYS_PER_TYPE: usize = 10;
+/// Deepest prefix level the memory report can group by.
+pub const MAX_PREFIX_DEPTH: usize = 4;
+/// Length of the hot key ranking.
+pub const HOT_KEYS_LIMIT: usize = 50;
+/// How long MONITOR is sampled when LFU counters are unavailable.
+pub const HOT_KEY_MONITOR_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnalysisView {
+    #[default]
+    BigKeys,
+    MemoryByPrefix,
+    TtlDistribution,
+    HotKeys,
+}
+
+impl AnalysisView {
+    pub const ALL: [AnalysisView; 4] = [
+        AnalysisView::BigKeys,
+        AnalysisView::MemoryByPrefix,
+        AnalysisView::TtlDistribution,
+        AnalysisView::HotKeys,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnalysisView::BigKeys => "Big keys",
+            AnalysisView::MemoryByPrefix => "Memory by prefix",
+            AnalysisView::TtlDistribution => "TTL distribution",
+            AnalysisView::HotKeys => "Hot keys",
+        }
+    }
+
+    pub fn next(&self) -> AnalysisView {
+        let idx = Self::ALL.iter().position(|view| view == self).unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+}
+
+/// Keyspace analysis: one SCAN walk feeding several reports.
+pub struct AnalysisState {
+    pub is_active: bool,
+    pub view: AnalysisView,
+    pub sampler: Option<KeySampler>,
+    /// Keys examined by the last (or running) walk.
+    pub scanned: usize,
+    /// The walk was stopped with Esc, so the reports only cover the keys scanned so far.
+    pub stopped: bool,
+    pub error: Option<String>,
+    /// Number of leading key segments the per-prefix reports group by.
+    pub prefix_depth: usize,
+    pub big_keys: BigKeys,
+    pub prefix_memory: PrefixMemory,
+    pub ttl_distribution: TtlDistribution,
+    pub hot_keys: HotKeys,
+}
+
+impl Default for AnalysisState {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            view: AnalysisView::default(),
+            sampler: None,
+            scanned: 0,
+            stopped: false,
+            error: None,
+            prefix_depth: 1,
+            big_keys: BigKeys::default(),
+            prefix_memory: PrefixMemory::default(),
+            ttl_distribution: TtlDistribution::default(),
+            hot_keys: HotKeys::default(),
+        }
+    }
+}
+
+impl AnalysisState {
+    pub fn reset(&mut self) {
+        self.sampler = None;
+        self.scanned = 0;
+        self.stopped = false;
+        self.error = None;
+        self.big_keys = BigKeys::default();
+        self.prefix_memory = PrefixMemory::default();
+        self.ttl_distribution = TtlDistribution::default();
+        self.hot_keys = HotKeys::default();
+    }
+
+    /// Stops a running walk and keeps what it found; false when none is running.
+    pub fn stop(&mut self) -> bool {
+        if self.sampler.take().is_none() {
+            return false;
+        }
+        self.stopped = true;
+        true
+    }
+
+    pub fn record(&mut self, samples: Vec<KeySample>, delimiter: char) {
+        self.scanned += samples.len();
+        self.prefix_memory.record(&samples, delimiter);
+        self.ttl_distribution.record(&samples, delimiter);
+        self.hot_keys.record(&samples);
+        self.big_keys.record(samples);
+    }
+
+    pub fn change_depth(&mut self, delta: isize) {
+        self.prefix_depth = self.prefix_depth.saturating_add_signed(delta).clamp(1, MAX_PREFIX_DEPTH);
+        self.prefix_memory.selected = 0;
+        self.ttl_distribution.selected = 0;
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let depth = self.prefix_depth;
+        match self.view {
+            AnalysisView::BigKeys => self.big_keys.select(delta),
+            AnalysisView::MemoryByPrefix => self.prefix_memory.select(delta, depth),
+            AnalysisView::TtlDistribution => self.ttl_distribution.select(delta, depth),
+            AnalysisView::HotKeys => self.hot_keys.select(delta),
+        }
+    }
+
+    /// Key under the cursor in the views that list individual keys.
+    pub fn selected_key(&self) -> Option<&str> {
+        match self.view {
+            AnalysisView::BigKeys => self.big_keys.selected_key(),
+            AnalysisView::HotKeys => self.hot_keys.selected_key(),
+            AnalysisView::MemoryByPrefix | AnalysisView::TtlDistribution => None,
+        }
+    }
+}
+
+/// Interactive `--bigkeys`: the largest keys of each type seen so far.
+#[derive(Debug, Default)]
+pub struct BigKeys {
+    pub by_type: BTreeMap<String, Vec<KeySample>>,
+    /// Index into `rows()`.
+    pub selected: usize,
+}
+
+/// Keys are ranked by MEMORY USAGE, falling back to their length when it is unavailable.
+fn size_rank(sample: &KeySample) -> (u64, u64) {
+    (sample.memory.unwrap_or(0), sample.length.unwrap_or(0))
+}
+
+impl BigKeys {
+    pub fn record(&mut self, samples: Vec<KeySample>) {
+        for sample in samples {
+            let top = self.by_type.entry(sample.key_type.clone()).or_default();
+            if top.len() == BIG_KEYS_PER_TYPE && top.last().is_some_and(|smallest| size_rank(smallest) >= size_rank(&sample)) {
+                continue;
+            }
+            let position = top.partition_point(|kept| size_rank(kept) >= size_rank(&sample));
+            top.insert(position, sample);
+            top.truncate(BIG_KEYS_PER_TYPE);
+        }
+    }
+
+    /// Every listed key, grouped by type in display order.
+    pub fn rows(&self) -> Vec<&KeySample> {
+        self.by_type.values().flatten().collect()
+    }
+
+    pub fn selected_key(&self) -> Option<&str> {
+        self.rows().get(self.selected).map(|sample| sample.key.as_str())
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let last = self.rows().len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefixUsage {
+    pub keys: u64,
+    pub bytes: u64,
+}
+
+/// MEMORY USAGE summed per key tree prefix, at every depth up to `MAX_PREFIX_DEPTH`.
+#[derive(Debug)]
+pub struct PrefixMemory {
+    totals: Vec<HashMap<String, PrefixUsage>>,
+    pub selected: usize,
+}
+
+impl Default for PrefixMemory {
+    fn default() -> Self {
+        Self {
+            totals: vec![HashMap::new(); MAX_PREFIX_DEPTH],
+            selected: 0,
+        }
+    }
+}
+
+impl PrefixMemory {
+    pub fn record(&mut self, samples: &[KeySample], delimiter: char) {
+        for sample in samples {
+            let bytes = sample.memory.unwrap_or(0);
+            for (depth, totals) in self.totals.iter_mut().enumerate() {
+                let usage = totals.entry(group_name(&sample.key, delimiter, depth + 1)).or_default();
+                usage.keys += 1;
+                usage.bytes += bytes;
+            }
+        }
+    }
+
+    /// Groups at `depth`, largest first.
+    pub fn ranked(&self, depth: usize) -> Vec<(&str, PrefixUsage)> {
+        let mut ranked: Vec<(&str, PrefixUsage)> = self.totals[depth - 1]
+            .iter()
+            .map(|(name, usage)| (name.as_str(), *usage))
+            .collect();
+        ranked.sort_by(|(a_name, a), (b_name, b)| b.bytes.cmp(&a.bytes).then_with(|| a_name.cmp(b_name)));
+        ranked
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.totals[0].values().map(|usage| usage.bytes).sum()
+    }
+
+    pub fn select(&mut self, delta: isize, depth: usize) {
+        let last = self.totals[depth - 1].len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn to_csv(&self, depth: usize) -> String {
+        let mut csv = String::from("prefix,keys,bytes\n");
+        for (name, usage) in self.ranked(depth) {
+            csv.push_str(&format!("{},{},{}\n", csv_field(name), usage.keys, usage.bytes));
+        }
+        csv
+    }
+
+    /// Writes the breakdown at `depth` to `lazyredis-memory-<millis>.csv` in `dir`.
+    pub fn export_csv(&self, dir: &Path, depth: usize) -> io::Result<PathBuf> {
+        let path = dir.join(format!("lazyredis-memory-{}.csv", now_millis()));
+        fs::write(&path, self.to_csv(depth))?;
+        Ok(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TtlBucket {
+    NoExpiry,
+    UnderMinute,
+    UnderHour,
+    UnderDay,
+    OverDay,
+}
+
+impl TtlBucket {
+    pub const ALL: [TtlBucket; 5] = [
+        TtlBucket::NoExpiry,
+        TtlBucket::UnderMinute,
+        TtlBucket::UnderHour,
+        TtlBucket::UnderDay,
+        TtlBucket::OverDay,
+    ];
+
+    pub fn of(ttl: Option<u64>) -> TtlBucket {
+        match ttl {
+            None => TtlBucket::NoExpiry,
+            Some(secs) if secs < 60 => TtlBucket::UnderMinute,
+            Some(secs) if secs < 3600 => TtlBucket::UnderHour,
+            Some(secs) if secs < 86_400 => TtlBucket::UnderDay,
+            Some(_) => TtlBucket::OverDay,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TtlBucket::NoExpiry => "no expiry",
+            TtlBucket::UnderMinute => "<1m",
+            TtlBucket::UnderHour => "<1h",
+            TtlBucket::UnderDay => "<1d",
+            TtlBucket::OverDay => ">1d",
+        }
+    }
+}
+
+/// Key counts per `TtlBucket`, indexed like `TtlBucket::ALL`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TtlHistogram(pub [u64; 5]);
+
+impl TtlHistogram {
+    pub fn add(&mut self, bucket: TtlBucket) {
+        self.0[bucket as usize] += 1;
+    }
+
+    pub fn count(&self, bucket: TtlBucket) -> u64 {
+        self.0[bucket as usize]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.iter().sum()
+    }
+}
+
+/// TTL histogram of every sampled key, overall and per key tree prefix.
+#[derive(Debug)]
+pub struct TtlDistribution {
+    pub overall: TtlHistogram,
+    by_prefix: Vec<HashMap<String, TtlHistogram>>,
+    pub selected: usize,
+}
+
+impl Default for TtlDistribution {
+    fn default() -> Self {
+        Self {
+            overall: TtlHistogram::default(),
+            by_prefix: vec![HashMap::new(); MAX_PREFIX_DEPTH],
+            selected: 0,
+        }
+    }
+}
+
+impl TtlDistribution {
+    pub fn record(&mut self, samples: &[KeySample], delimiter: char) {
+        for sample in samples {
+            let bucket = TtlBucket::of(sample.ttl);
+            self.overall.add(bucket);
+            for (depth, histograms) in self.by_prefix.iter_mut().enumerate() {
+                histograms.entry(group_name(&sample.key, delimiter, depth + 1)).or_default().add(bucket);
+            }
+        }
+    }
+
+    /// Groups at `depth`, the most keys without an expiry first.
+    pub fn ranked(&self, depth: usize) -> Vec<(&str, TtlHistogram)> {
+        let mut ranked: Vec<(&str, TtlHistogram)> = self.by_prefix[depth - 1]
+            .iter()
+            .map(|(name, histogram)| (name.as_str(), *histogram))
+            .collect();
+        ranked.sort_by(|(a_name, a), (b_name, b)| {
+            b.count(TtlBucket::NoExpiry)
+                .cmp(&a.count(TtlBucket::NoExpiry))
+                .then_with(|| b.total().cmp(&a.total()))
+                .then_with(|| a_name.cmp(b_name))
+        });
+        ranked
+    }
+
+    pub fn select(&mut self, delta: isize, depth: usize) {
+        let last = self.by_prefix[depth - 1].len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+}
+
+/// Where the hot key ranking comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotKeySource {
+    /// OBJECT FREQ, reported during the keyspace walk under an LFU policy.
+    Lfu,
+    /// Accesses counted from a short MONITOR sample.
+    Monitor,
+}
+
+/// Most frequently accessed keys.
+#[derive(Default)]
+pub struct HotKeys {
+    /// Hottest keys by LFU counter, largest first.
+    by_freq: Vec<(String, u64)>,
+    /// Running MONITOR sample, used when no LFU counters are reported.
+    pub monitor: Option<MonitorSession>,
+    pub monitor_until: Option<Instant>,
+    by_access: HashMap<String, u64>,
+    pub selected: usize,
+}
+
+impl HotKeys {
+    pub fn record(&mut self, samples: &[KeySample]) {
+        for sample in samples {
+            let Some(freq) = sample.freq else {
+                continue;
+            };
+            if self.by_freq.len() == HOT_KEYS_LIMIT && self.by_freq.last().is_some_and(|(_, coldest)| *coldest >= freq) {
+                continue;
+            }
+            let position = self.by_freq.partition_point(|(_, kept)| *kept >= freq);
+            self.by_freq.insert(position, (sample.key.clone(), freq));
+            self.by_freq.truncate(HOT_KEYS_LIMIT);
+        }
+    }
+
+    pub fn source(&self) -> Option<HotKeySource> {
+        if !self.by_freq.is_empty() {
+            Some(HotKeySource::Lfu)
+        } else if self.monitor.is_some() || !self.by_access.is_empty() {
+            Some(HotKeySource::Monitor)
+        } else {
+            None
+        }
+    }
+
+    pub fn start_monitor(&mut self, session: MonitorSession) {
+        self.by_access.clear();
+        self.selected = 0;
+        self.monitor = Some(session);
+        self.monitor_until = Some(Instant::now() + HOT_KEY_MONITOR_WINDOW);
+    }
+
+    pub fn stop_monitor(&mut self) {
+        self.monitor = None;
+        self.monitor_until = None;
+    }
+
+    pub fn record_accesses(&mut self, entries: &[MonitorEntry]) {
+        for entry in entries {
+            for key in accessed_keys(&split_arguments(&entry.arguments)) {
+                *self.by_access.entry(key.to_string()).or_default() += 1;
+            }
+        }
+    }
+
+    /// Ranked keys with their access counts, the hottest first.
+    pub fn rows(&self) -> Vec<(&str, u64)> {
+        if !self.by_freq.is_empty() {
+            return self.by_freq.iter().map(|(key, freq)| (key.as_str(), *freq)).collect();
+        }
+        let mut rows: Vec<(&str, u64)> = self.by_access.iter().map(|(key, count)| (key.as_str(), *count)).collect();
+        rows.sort_by(|(a_key, a), (b_key, b)| b.cmp(a).then_with(|| a_key.cmp(b_key)));
+        rows.truncate(HOT_KEYS_LIMIT);
+        rows
+    }
+
+    pub fn selected_key(&self) -> Option<&str> {
+        self.rows().get(self.selected).map(|(key, _)| *key)
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let last = self.rows().len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+}
+
+/// Keys touched by a command seen through MONITOR. Commands without keys,
+/// or whose keys cannot be told apart from their arguments, count for none.
+fn accessed_keys(arguments: &[String]) -> &[String] {
+    let Some((command, rest)) = arguments.split_first() else {
+        return &[];
+    };
+    match command.to_uppercase().as_str() {
+        "MGET" | "DEL" | "UNLINK" | "EXISTS" | "TOUCH" | "WATCH" | "SINTER" | "SUNION" | "SDIFF" => rest,
+        "PING" | "ECHO" | "INFO" | "SELECT" | "AUTH" | "HELLO" | "CLIENT" | "CONFIG" | "COMMAND" | "MONITOR"
+        | "SCAN" | "KEYS" | "DBSIZE" | "FLUSHDB" | "FLUSHALL" | "MULTI" | "EXEC" | "DISCARD" | "PUBLISH"
+        | "SUBSCRIBE" | "PSUBSCRIBE" | "UNSUBSCRIBE" | "PUNSUBSCRIBE" | "EVAL" | "EVALSHA" | "FCALL" | "SCRIPT"
+        | "FUNCTION" | "CLUSTER" | "SLOWLOG" | "LATENCY" | "MEMORY" | "OBJECT" | "TIME" | "LASTSAVE" | "SAVE"
+        | "BGSAVE" | "BGREWRITEAOF" | "ROLE" | "REPLICAOF" | "SLAVEOF" | "WAIT" | "QUIT" | "RESET" => &[],
+        _ => &rest[..rest.len().min(1)],
+    }
+}
+
+/// `sessions:*` for keys below a folder at `depth`, or the key itself when it is shallower.
+fn group_name(key: &str, delimiter: char, depth: usize) -> String {
+    match key.match_indices(delimiter).nth(depth - 1) {
+        Some((idx, _)) => format!("{}*", &key[..idx + delimiter.len_utf8()]),
+        None => key.to_string(),
+    }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(key: &str, key_type: &str, memory: Option<u64>, length: u64) -> KeySample {
+        KeySample {
+            key: key.to_string(),
+            key_type: key_type.to_string(),
+            memory,
+            length: Some(length),
+            ttl: None,
+            freq: None,
+        }
+    }
+
+    #[test]
+    fn keeps_the_largest_keys_per_type() {
+        let mut state = AnalysisState::default();
+        let mut samples: Vec<KeySample> = (0..15)
+            .map(|n| sample(&format!("s:{}", n), "string", Some(n * 100), n))
+            .collect();
+        samples.push(sample("h:1", "hash", None, 40));
+        samples.push(sample("h:2", "hash", None, 400));
+        state.record(samples, ':');
+
+        assert_eq!(state.scanned, 17);
+        let strings = &state.big_keys.by_type["string"];
+        assert_eq!(strings.len(), BIG_KEYS_PER_TYPE);
+        assert_eq!(strings[0].key, "s:14");
+        assert_eq!(strings[9].key, "s:5");
+        let rows: Vec<&str> = state.big_keys.rows().iter().map(|sample| sample.key.as_str()).collect();
+        assert_eq!(&rows[..2], ["h:2", "h:1"]);
+    }
+
+    #[test]
+    fn memory_is_grouped_by_prefix_depth() {
+        let mut memory = PrefixMemory::default();
+        memory.record(
+            &[
+                sample("sessions:web:1", "string", Some(300), 1),
+                sample("sessions:api:1", "string", Some(200), 1),
+                sample("cache:a", "string", Some(100), 1),
+                sample("config", "hash", Some(50), 1),
+            ],
+            ':',
+        );
+        assert_eq!(memory.total_bytes(), 650);
+        assert_eq!(
+            memory.ranked(1),
+            vec![
+                ("sessions:*", PrefixUsage { keys: 2, bytes: 500 }),
+                ("cache:*", PrefixUsage { keys: 1, bytes: 100 }),
+                ("config", PrefixUsage { keys: 1, bytes: 50 }),
+            ]
+        );
+        assert_eq!(memory.ranked(2)[0], ("sessions:web:*", PrefixUsage { keys: 1, bytes: 300 }));
+        assert_eq!(memory.ranked(2)[2].0, "cache:a");
+        assert!(memory.to_csv(2).starts_with("prefix,keys,bytes\nsessions:web:*,1,300\n"));
+    }
+
+    #[test]
+    fn ttls_are_bucketed_overall_and_per_prefix() {
+        let ttls = [None, Some(30), Some(600), Some(7200), Some(200_000), None];
+        let keys = ["cache:a", "cache:b", "cache:c", "session:a", "session:b", "session:c"];
+        let samples: Vec<KeySample> = keys
+            .iter()
+            .zip(ttls)
+            .map(|(key, ttl)| KeySample { ttl, ..sample(key, "string", None, 1) })
+            .collect();
+        let mut distribution = TtlDistribution::default();
+        distribution.record(&samples, ':');
+
+        assert_eq!(distribution.overall, TtlHistogram([2, 1, 1, 1, 1]));
+        let ranked = distribution.ranked(1);
+        assert_eq!(ranked[0], ("cache:*", TtlHistogram([1, 1, 1, 0, 0])));
+        assert_eq!(ranked[1], ("session:*", TtlHistogram([1, 0, 0, 1, 1])));
+        assert_eq!(TtlBucket::of(Some(59)), TtlBucket::UnderMinute);
+        assert_eq!(TtlBucket::of(Some(86_400)), TtlBucket::OverDay);
+    }
+
+    #[test]
+    fn hot_keys_rank_lfu_counters_before_monitor_samples() {
+        let mut hot = HotKeys::default();
+        let entry = |arguments: &str| MonitorEntry {
+            timestamp: 0,
+            db: "0".to_string(),
+            client: "lua".to_string(),
+            command: String::new(),
+            arguments: arguments.to_string(),
+        };
+        hot.record_accesses(&[
+            entry(r#""get" "a""#),
+            entry(r#""mget" "a" "b""#),
+            entry(r#""ping""#),
+            entry(r#""set" "b" "a""#),
+            entry(r#""get" "a""#),
+        ]);
+        assert_eq!(hot.rows(), vec![("a", 3), ("b", 2)]);
+
+        let samples: Vec<KeySample> = [("x", 4), ("y", 200)]
+            .iter()
+            .map(|(key, freq)| KeySample { freq: Some(*freq), ..sample(key, "string", None, 1) })
+            .collect();
+        hot.record(&samples);
+        assert_eq!(hot.source(), Some(HotKeySource::Lfu));
+        assert_eq!(hot.rows(), vec![("y", 200), ("x", 4)]);
+        assert_eq!(hot.selected_key(), Some("y"));
+    }
+}
@@ -0,0 +1,19 @@
+#[derive(Debug, Default, Clone)]
+pub struct BookmarksPanelState {
+    pub is_active: bool,
+    pub selected_index: usize,
+}
+
+impl BookmarksPanelState {
+    pub fn next(&mut self, len: usize) {
+        if len > 0 {
+            self.selected_index = (self.selected_index + 1) % len;
+        }
+    }
+
+    pub fn previous(&mut self, len: usize) {
+        if len > 0 {
+            self.selected_index = (self.selected_index + len - 1) % len;
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use redis::aio::MultiplexedConnection;
+
+use super::keyspace_compare::{CompareRow, CompareStatus, KeyspaceCompare};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareStage {
+    /// Choosing the profile to compare the current one with.
+    #[default]
+    PickProfile,
+    /// Editing the SCAN pattern compared on both sides.
+    Pattern,
+    Results,
+}
+
+/// Both sides of one key, for the drill-down.
+#[derive(Debug, Clone, Default)]
+pub struct CompareDetail {
+    pub key: String,
+    pub left: Vec<String>,
+    pub right: Vec<String>,
+    pub scroll: usize,
+}
+
+/// Compares the current profile's keyspace with another profile's.
+#[derive(Default)]
+pub struct CompareState {
+    pub is_active: bool,
+    pub stage: CompareStage,
+    /// Index into `App::profiles` of the other side.
+    pub profile_selected: usize,
+    pub pattern: String,
+    pub pattern_cursor: usize,
+    /// `Profile DB n` of each side, for titles.
+    pub left_label: String,
+    pub right_label: String,
+    pub right: Option<MultiplexedConnection>,
+    pub job: Option<KeyspaceCompare>,
+    /// Keys found on each side, once both scans finished.
+    pub scanned: Option<(usize, usize)>,
+    pub rows: Vec<CompareRow>,
+    pub identical: usize,
+    pub compared: usize,
+    pub selected: usize,
+    pub detail: Option<CompareDetail>,
+    pub error: Option<String>,
+}
+
+impl CompareState {
+    pub fn reset_results(&mut self) {
+        self.job = None;
+        self.scanned = None;
+        self.rows.clear();
+        self.identical = 0;
+        self.compared = 0;
+        self.selected = 0;
+        self.detail = None;
+        self.error = None;
+    }
+
+    pub fn add_rows(&mut self, rows: Vec<CompareRow>) {
+        self.rows.extend(rows);
+        self.rows.sort_by(|a, b| a.key.cmp(&b.key));
+    }
+
+    pub fn count(&self, matches: impl Fn(&CompareStatus) -> bool) -> usize {
+        self.rows.iter().filter(|row| matches(&row.status)).count()
+    }
+
+    pub fn selected_row(&self) -> Option<&CompareRow> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        if let Some(detail) = &mut self.detail {
+            let last = detail.left.len().max(detail.right.len()).saturating_sub(1);
+            detail.scroll = detail.scroll.saturating_add_signed(delta).min(last);
+            return;
+        }
+        let last = self.rows.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+}
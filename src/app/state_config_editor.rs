@@ -0,0 +1,181 @@
+use fuzzy_matcher::FuzzyMatcher;
+use redis::Value;
+
+use crate::command::resp::{pairs, value_text};
+
+/// Defaults of commonly tuned parameters on Redis 7, for spotting changed values.
+const KNOWN_DEFAULTS: &[(&str, &str)] = &[
+    ("activedefrag", "no"),
+    ("active-expire-effort", "1"),
+    ("appendfsync", "everysec"),
+    ("appendonly", "no"),
+    ("busy-reply-threshold", "5000"),
+    ("databases", "16"),
+    ("dynamic-hz", "yes"),
+    ("hash-max-listpack-entries", "128"),
+    ("hash-max-listpack-value", "64"),
+    ("hz", "10"),
+    ("io-threads", "1"),
+    ("latency-monitor-threshold", "0"),
+    ("lazyfree-lazy-eviction", "no"),
+    ("lazyfree-lazy-expire", "no"),
+    ("lazyfree-lazy-server-del", "no"),
+    ("lazyfree-lazy-user-del", "no"),
+    ("list-max-listpack-size", "-2"),
+    ("loglevel", "notice"),
+    ("maxclients", "10000"),
+    ("maxmemory", "0"),
+    ("maxmemory-policy", "noeviction"),
+    ("maxmemory-samples", "5"),
+    ("notify-keyspace-events", ""),
+    ("port", "6379"),
+    ("proto-max-bulk-len", "536870912"),
+    ("protected-mode", "yes"),
+    ("rdbcompression", "yes"),
+    ("repl-backlog-size", "1048576"),
+    ("repl-timeout", "60"),
+    ("replica-read-only", "yes"),
+    ("save", "3600 1 300 100 60 10000"),
+    ("set-max-intset-entries", "512"),
+    ("slowlog-log-slower-than", "10000"),
+    ("slowlog-max-len", "128"),
+    ("stop-writes-on-bgsave-error", "yes"),
+    ("stream-node-max-bytes", "4096"),
+    ("stream-node-max-entries", "100"),
+    ("tcp-backlog", "511"),
+    ("tcp-keepalive", "300"),
+    ("timeout", "0"),
+    ("zset-max-listpack-entries", "128"),
+    ("zset-max-listpack-value", "64"),
+];
+
+pub fn known_default(name: &str) -> Option<&'static str> {
+    KNOWN_DEFAULTS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, value)| *value)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigParam {
+    pub name: String,
+    pub value: String,
+}
+
+impl ConfigParam {
+    /// Whether the value differs from the known default; `None` when the default is unknown.
+    pub fn is_modified(&self) -> Option<bool> {
+        known_default(&self.name).map(|default| default != self.value)
+    }
+}
+
+/// CONFIG GET * listing with a fuzzy filter and an inline CONFIG SET editor.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigEditorState {
+    pub is_active: bool,
+    pub params: Vec<ConfigParam>,
+    pub filter: String,
+    pub filter_cursor: usize,
+    /// Indices into `params` matching the filter, best match first.
+    pub filtered: Vec<usize>,
+    pub selected: usize,
+    /// New value being typed for the selected parameter.
+    pub edit: Option<String>,
+    pub edit_cursor: usize,
+    pub status: Option<String>,
+}
+
+impl ConfigEditorState {
+    pub fn set_params(&mut self, mut params: Vec<ConfigParam>) {
+        params.sort_by(|a, b| a.name.cmp(&b.name));
+        self.params = params;
+        self.apply_filter();
+    }
+
+    pub fn apply_filter(&mut self) {
+        let selected_name = self.selected_param().map(|param| param.name.clone());
+        if self.filter.is_empty() {
+            self.filtered = (0..self.params.len()).collect();
+        } else {
+            let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+            let mut scored: Vec<(i64, usize)> = self
+                .params
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, param)| matcher.fuzzy_match(&param.name, &self.filter).map(|score| (score, idx)))
+                .collect();
+            scored.sort_by(|(a_score, a_idx), (b_score, b_idx)| b_score.cmp(a_score).then(a_idx.cmp(b_idx)));
+            self.filtered = scored.into_iter().map(|(_, idx)| idx).collect();
+        }
+        self.selected = selected_name
+            .and_then(|name| self.filtered.iter().position(|&idx| self.params[idx].name == name))
+            .unwrap_or(0);
+    }
+
+    pub fn selected_param(&self) -> Option<&ConfigParam> {
+        self.filtered.get(self.selected).map(|&idx| &self.params[idx])
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let last = self.filtered.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn start_edit(&mut self) {
+        let Some(value) = self.selected_param().map(|param| param.value.clone()) else {
+            return;
+        };
+        self.edit_cursor = value.len();
+        self.edit = Some(value);
+    }
+}
+
+/// Parses a `CONFIG GET *` reply (flat array or map).
+pub fn parse_config_reply(reply: &Value) -> Vec<ConfigParam> {
+    pairs(reply)
+        .into_iter()
+        .filter_map(|(name, value)| {
+            Some(ConfigParam {
+                name: value_text(name)?,
+                value: value_text(value).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn filter_keeps_selection_and_flags_modified_values() {
+        let reply = Value::Array(vec![
+            bulk("maxmemory"),
+            bulk("100mb"),
+            bulk("hz"),
+            bulk("10"),
+            bulk("maxmemory-policy"),
+            bulk("allkeys-lru"),
+            bulk("some-module-setting"),
+            bulk("x"),
+        ]);
+        let mut state = ConfigEditorState::default();
+        state.set_params(parse_config_reply(&reply));
+        assert_eq!(state.filtered.len(), 4);
+        assert_eq!(state.selected_param().unwrap().name, "hz");
+        assert_eq!(state.selected_param().unwrap().is_modified(), Some(false));
+
+        state.select(1);
+        state.filter = "mxpol".to_string();
+        state.apply_filter();
+        assert_eq!(state.filtered.len(), 1);
+        let param = state.selected_param().unwrap();
+        assert_eq!(param.name, "maxmemory-policy");
+        assert_eq!(param.is_modified(), Some(true));
+        assert_eq!(known_default("some-module-setting"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumerGroupInfo {
+    pub name: String,
+    pub consumers: u64,
+    pub pending: u64,
+    pub last_delivered_id: String,
+    /// Entries not yet delivered to the group; reported by Redis 7+ only.
+    pub lag: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingEntry {
+    pub id: String,
+    pub consumer: String,
+    pub idle_ms: u64,
+    pub deliveries: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupPrompt {
+    /// XCLAIM the targeted entries: `<consumer> [min-idle-ms]`.
+    Claim,
+    /// XAUTOCLAIM idle entries: `<consumer> <min-idle-ms>`.
+    AutoClaim,
+}
+
+impl GroupPrompt {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GroupPrompt::Claim => "XCLAIM to <consumer> [min-idle-ms]",
+            GroupPrompt::AutoClaim => "XAUTOCLAIM to <consumer> <min-idle-ms>",
+        }
+    }
+}
+
+/// Consumer group inspector for the stream in the value panel.
+#[derive(Debug, Default, Clone)]
+pub struct ConsumerGroupState {
+    pub is_active: bool,
+    pub stream_key: Option<String>,
+    pub groups: Vec<ConsumerGroupInfo>,
+    pub selected_group: usize,
+    pub pending: Vec<PendingEntry>,
+    pub selected_pending: usize,
+    pub marked: BTreeSet<String>,
+    pub prompt: Option<GroupPrompt>,
+    pub input: String,
+    pub status: Option<String>,
+}
+
+impl ConsumerGroupState {
+    pub fn open(&mut self, stream_key: String) {
+        *self = Self {
+            is_active: true,
+            stream_key: Some(stream_key),
+            ..Self::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn current_group(&self) -> Option<&ConsumerGroupInfo> {
+        self.groups.get(self.selected_group)
+    }
+
+    pub fn select_group(&mut self, forward: bool) {
+        if self.groups.is_empty() {
+            return;
+        }
+        let len = self.groups.len();
+        self.selected_group = if forward {
+            (self.selected_group + 1) % len
+        } else {
+            (self.selected_group + len - 1) % len
+        };
+        self.pending.clear();
+        self.selected_pending = 0;
+        self.marked.clear();
+    }
+
+    pub fn select_pending(&mut self, forward: bool) {
+        if self.pending.is_empty() {
+            return;
+        }
+        self.selected_pending = if forward {
+            (self.selected_pending + 1).min(self.pending.len() - 1)
+        } else {
+            self.selected_pending.saturating_sub(1)
+        };
+    }
+
+    pub fn toggle_mark(&mut self) {
+        let Some(entry) = self.pending.get(self.selected_pending) else {
+            return;
+        };
+        if !self.marked.remove(&entry.id) {
+            self.marked.insert(entry.id.clone());
+        }
+    }
+
+    /// Marked entry ids, or the highlighted entry when nothing is marked.
+    pub fn target_ids(&self) -> Vec<String> {
+        if !self.marked.is_empty() {
+            return self.marked.iter().cloned().collect();
+        }
+        self.pending
+            .get(self.selected_pending)
+            .map(|entry| vec![entry.id.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Replaces the pending list after a refresh, keeping marks that still exist.
+    pub fn set_pending(&mut self, pending: Vec<PendingEntry>) {
+        self.marked
+            .retain(|id| pending.iter().any(|entry| &entry.id == id));
+        self.selected_pending = self.selected_pending.min(pending.len().saturating_sub(1));
+        self.pending = pending;
+    }
+
+    pub fn open_prompt(&mut self, prompt: GroupPrompt) {
+        self.prompt = Some(prompt);
+        self.input.clear();
+    }
+
+    /// Consumer name and min-idle-time from the prompt; XCLAIM defaults the idle time to 0.
+    pub fn parse_prompt_input(&self) -> Result<(String, u64), String> {
+        let mut parts = self.input.split_whitespace();
+        let consumer = parts
+            .next()
+            .ok_or_else(|| "A consumer name is required.".to_string())?;
+        let min_idle = match (parts.next(), self.prompt) {
+            (Some(raw), _) => raw
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid min-idle-time '{}'.", raw))?,
+            (None, Some(GroupPrompt::AutoClaim)) => {
+                return Err("XAUTOCLAIM needs a min-idle-time in milliseconds.".to_string())
+            }
+            (None, _) => 0,
+        };
+        Ok((consumer.to_string(), min_idle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str) -> PendingEntry {
+        PendingEntry {
+            id: id.to_string(),
+            consumer: "worker".to_string(),
+            idle_ms: 0,
+            deliveries: 1,
+        }
+    }
+
+    #[test]
+    fn target_ids_prefer_marked_entries() {
+        let mut state = ConsumerGroupState::default();
+        state.set_pending(vec![entry("1-0"), entry("2-0"), entry("3-0")]);
+        assert_eq!(state.target_ids(), vec!["1-0"]);
+        state.select_pending(true);
+        state.toggle_mark();
+        state.select_pending(true);
+        state.toggle_mark();
+        assert_eq!(state.target_ids(), vec!["2-0", "3-0"]);
+        state.set_pending(vec![entry("3-0")]);
+        assert_eq!(state.target_ids(), vec!["3-0"]);
+    }
+
+    #[test]
+    fn parse_prompt_input_requires_idle_time_for_autoclaim() {
+        let mut state = ConsumerGroupState::default();
+        state.open_prompt(GroupPrompt::Claim);
+        state.input = "worker-2".to_string();
+        assert_eq!(state.parse_prompt_input(), Ok(("worker-2".to_string(), 0)));
+        state.open_prompt(GroupPrompt::AutoClaim);
+        state.input = "worker-2".to_string();
+        assert!(state.parse_prompt_input().is_err());
+        state.input = "worker-2 60000".to_string();
+        assert_eq!(state.parse_prompt_input(), Ok(("worker-2".to_string(), 60000)));
+    }
+}
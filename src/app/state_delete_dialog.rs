@@ -1,3 +1,5 @@
+use super::delete_preview::DeletePreview;
+
 #[derive(Debug, Default, Clone)]
 pub struct DeleteDialogState {
     pub show_confirmation_dialog: bool,
@@ -7,6 +9,8 @@ pub struct DeleteDialogState {
     pub deletion_is_folder: bool,
     pub keys_to_delete: Vec<String>,
     pub is_multi_delete: bool,
+    /// Dry-run result shown in the dialog after `p`.
+    pub preview: Option<DeletePreview>,
 }
 
 impl DeleteDialogState {
@@ -16,12 +20,14 @@ impl DeleteDialogState {
         visible_keys: &[(String, bool)],
         current_breadcrumb: &[String],
         key_delimiter: char,
+        leaf_key: impl Fn(&str) -> Option<String>,
         search_active: bool,
     ) {
         if search_active || selected_index >= visible_keys.len() {
             return;
         }
         let (display_name, is_folder) = visible_keys[selected_index].clone();
+        self.preview = None;
         self.key_to_delete_display_name = Some(display_name.clone());
         self.deletion_is_folder = is_folder;
         if is_folder {
@@ -31,20 +37,29 @@ impl DeleteDialogState {
             self.prefix_to_delete = Some(format!("{}{}", prefix_parts.join(&key_delimiter.to_string()), key_delimiter));
             self.key_to_delete_full_path = None;
         } else {
-            let mut full_key_parts = current_breadcrumb.to_vec();
-            full_key_parts.push(display_name);
-            self.key_to_delete_full_path = Some(full_key_parts.join(&key_delimiter.to_string()));
+            self.key_to_delete_full_path = leaf_key(&display_name);
             self.prefix_to_delete = None;
         }
         self.show_confirmation_dialog = true;
     }
 
+    /// Asks to delete the single key `full_key_name`, wherever it is in the tree.
+    pub fn initiate_delete_key(&mut self, full_key_name: &str) {
+        *self = Self {
+            show_confirmation_dialog: true,
+            key_to_delete_display_name: Some(full_key_name.to_string()),
+            key_to_delete_full_path: Some(full_key_name.to_string()),
+            ..Self::default()
+        };
+    }
+
     pub fn initiate_delete_multiple_items(
         &mut self,
         selected_indices: &std::collections::HashSet<usize>,
         visible_keys: &[(String, bool)],
         current_breadcrumb: &[String],
         key_delimiter: char,
+        leaf_key: impl Fn(&str) -> Option<String>,
         search_active: bool,
     ) {
         if search_active || selected_indices.is_empty() {
@@ -52,6 +67,7 @@ impl DeleteDialogState {
         }
         
         self.keys_to_delete.clear();
+        self.preview = None;
         self.is_multi_delete = true;
         self.deletion_is_folder = false;
         
@@ -63,10 +79,8 @@ impl DeleteDialogState {
                     prefix_parts.push(display_name.trim_end_matches('/').to_string());
                     let prefix = format!("{}{}", prefix_parts.join(&key_delimiter.to_string()), key_delimiter);
                     self.keys_to_delete.push(format!("folder:{}", prefix));
-                } else {
-                    let mut full_key_parts = current_breadcrumb.to_vec();
-                    full_key_parts.push(display_name.clone());
-                    self.keys_to_delete.push(full_key_parts.join(&key_delimiter.to_string()));
+                } else if let Some(full_key) = leaf_key(display_name) {
+                    self.keys_to_delete.push(full_key);
                 }
             }
         }
@@ -85,5 +99,6 @@ impl DeleteDialogState {
         self.deletion_is_folder = false;
         self.keys_to_delete.clear();
         self.is_multi_delete = false;
+        self.preview = None;
     }
 }
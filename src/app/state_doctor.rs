@@ -0,0 +1,117 @@
+use redis::Value;
+
+use super::redis_stats::format_bytes;
+use crate::command::resp::{pairs, value_text};
+
+/// Advisory report built from MEMORY DOCTOR, LATENCY DOCTOR and MEMORY STATS.
+#[derive(Debug, Default, Clone)]
+pub struct DoctorReportState {
+    pub is_active: bool,
+    pub lines: Vec<String>,
+    pub scroll: usize,
+}
+
+impl DoctorReportState {
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+/// Lays out the three replies as titled sections; each failed command shows its error instead.
+pub fn build_report(
+    memory_doctor: Result<String, String>,
+    latency_doctor: Result<String, String>,
+    memory_stats: Result<Value, String>,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut section = |title: &str, body: Result<Vec<String>, String>| {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("== {} ==", title));
+        match body {
+            Ok(body) => lines.extend(body),
+            Err(e) => lines.push(format!("(error) {}", e)),
+        }
+    };
+    let text = |text: String| text.lines().map(|line| line.trim_end().to_string()).collect();
+    section("MEMORY DOCTOR", memory_doctor.map(text));
+    section("LATENCY DOCTOR", latency_doctor.map(text));
+    section("MEMORY STATS", memory_stats.map(|stats| stats_lines(&stats, 0)));
+    lines
+}
+
+fn stats_lines(value: &Value, depth: usize) -> Vec<String> {
+    let indent = "  ".repeat(depth);
+    let mut lines = Vec::new();
+    for (name, value) in pairs(value) {
+        let name = value_text(name).unwrap_or_else(|| "?".to_string());
+        match value {
+            Value::Array(_) | Value::Map(_) => {
+                lines.push(format!("{}{}:", indent, name));
+                lines.extend(stats_lines(value, depth + 1));
+            }
+            _ => lines.push(format!("{}{}: {}", indent, name, stat_value(&name, value))),
+        }
+    }
+    lines
+}
+
+/// Byte counts are shown with a readable size next to the raw number.
+fn stat_value(name: &str, value: &Value) -> String {
+    match value {
+        Value::Int(n) if *n >= 1024 && !name.contains("count") => {
+            format!("{} ({})", n, format_bytes(*n as u64))
+        }
+        Value::Int(n) => n.to_string(),
+        Value::Double(d) => format!("{:.2}", d),
+        Value::Nil => "(nil)".to_string(),
+        other => value_text(other).unwrap_or_else(|| format!("{:?}", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn report_has_a_section_per_command() {
+        let stats = Value::Array(vec![
+            bulk("peak.allocated"),
+            Value::Int(2_097_152),
+            bulk("keys.count"),
+            Value::Int(5000),
+            bulk("db.0"),
+            Value::Array(vec![bulk("overhead.hashtable.main"), Value::Int(72)]),
+            bulk("fragmentation"),
+            bulk("1.25"),
+        ]);
+        let report = build_report(
+            Ok("Hi Sam, I can't find any memory issue.\r\n".to_string()),
+            Err("ERR unknown command".to_string()),
+            Ok(stats),
+        );
+        assert_eq!(
+            report,
+            vec![
+                "== MEMORY DOCTOR ==",
+                "Hi Sam, I can't find any memory issue.",
+                "",
+                "== LATENCY DOCTOR ==",
+                "(error) ERR unknown command",
+                "",
+                "== MEMORY STATS ==",
+                "peak.allocated: 2097152 (2.0 MB)",
+                "keys.count: 5000",
+                "db.0:",
+                "  overhead.hashtable.main: 72",
+                "fragmentation: 1.25",
+            ]
+        );
+    }
+}
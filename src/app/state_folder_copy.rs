@@ -0,0 +1,77 @@
+/// What `y` on a folder copies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FolderCopy {
+    /// The folder's full prefix, ending with the delimiter.
+    Prefix,
+    /// Every key under the prefix, one per line.
+    KeyNames,
+}
+
+impl FolderCopy {
+    pub const ALL: [FolderCopy; 2] = [FolderCopy::Prefix, FolderCopy::KeyNames];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FolderCopy::Prefix => "Prefix",
+            FolderCopy::KeyNames => "Key names",
+        }
+    }
+}
+
+/// Choice opened by `y` on a folder between copying its prefix and its keys.
+#[derive(Debug, Default, Clone)]
+pub struct FolderCopyMenu {
+    pub is_active: bool,
+    /// Canonical prefix of the folder, delimiter included.
+    pub prefix: String,
+    pub selected: usize,
+}
+
+impl FolderCopyMenu {
+    pub fn open(&mut self, prefix: String) {
+        *self = Self {
+            is_active: true,
+            prefix,
+            selected: 0,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn select(&mut self, down: bool) {
+        let len = FolderCopy::ALL.len();
+        self.selected = if down { (self.selected + 1) % len } else { (self.selected + len - 1) % len };
+    }
+
+    pub fn selected_choice(&self) -> FolderCopy {
+        FolderCopy::ALL[self.selected]
+    }
+}
+
+/// Key names sorted and deduplicated, since `SCAN` may return a key more than once.
+pub fn key_name_list(mut keys: Vec<String>) -> String {
+    keys.sort_unstable();
+    keys.dedup();
+    keys.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_and_keys_list_once_each() {
+        let mut menu = FolderCopyMenu::default();
+        menu.open("users:".to_string());
+        assert_eq!(menu.selected_choice(), FolderCopy::Prefix);
+        menu.select(false);
+        assert_eq!(menu.selected_choice(), FolderCopy::KeyNames);
+        menu.select(true);
+        assert_eq!(menu.selected_choice(), FolderCopy::Prefix);
+
+        let keys = ["users:2", "users:1", "users:2"].map(String::from).to_vec();
+        assert_eq!(key_name_list(keys), "users:1\nusers:2");
+    }
+}
@@ -0,0 +1,183 @@
+use redis::Value;
+
+use crate::command::resp::{map_field, pairs, value_text};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub description: String,
+    pub flags: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionLibrary {
+    pub name: String,
+    pub engine: String,
+    pub functions: Vec<FunctionInfo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionPrompt {
+    /// FCALL the selected function: `<keys...> | <args...>`.
+    Call,
+    /// FUNCTION LOAD a local file: `<path> [--replace]`.
+    Load,
+}
+
+impl FunctionPrompt {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FunctionPrompt::Call => "FCALL with <keys...> | <args...>",
+            FunctionPrompt::Load => "FUNCTION LOAD from <path> [--replace]",
+        }
+    }
+}
+
+/// Redis 7 functions view: libraries from FUNCTION LIST, FCALL and FUNCTION LOAD.
+#[derive(Debug, Default, Clone)]
+pub struct FunctionsState {
+    pub is_active: bool,
+    pub libraries: Vec<FunctionLibrary>,
+    /// Index into all functions, in library order.
+    pub selected: usize,
+    pub prompt: Option<FunctionPrompt>,
+    pub input: String,
+    pub cursor: usize,
+    pub result: Option<String>,
+}
+
+impl FunctionsState {
+    pub fn open(&mut self) {
+        self.is_active = true;
+        self.prompt = None;
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+        self.prompt = None;
+    }
+
+    /// Every function with the library it belongs to, in display order.
+    pub fn functions(&self) -> impl Iterator<Item = (&FunctionLibrary, &FunctionInfo)> {
+        self.libraries
+            .iter()
+            .flat_map(|library| library.functions.iter().map(move |function| (library, function)))
+    }
+
+    pub fn selected_function(&self) -> Option<&FunctionInfo> {
+        self.functions().nth(self.selected).map(|(_, function)| function)
+    }
+
+    pub fn select(&mut self, forward: bool) {
+        let count = self.functions().count();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    pub fn open_prompt(&mut self, prompt: FunctionPrompt) {
+        if prompt == FunctionPrompt::Call && self.selected_function().is_none() {
+            self.result = Some("No function selected.".to_string());
+            return;
+        }
+        self.prompt = Some(prompt);
+        self.input.clear();
+        self.cursor = 0;
+    }
+
+    pub fn set_libraries(&mut self, libraries: Vec<FunctionLibrary>) {
+        self.libraries = libraries;
+        let count = self.functions().count();
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+/// Parses a `FUNCTION LIST` reply.
+pub fn parse_function_list(reply: &Value) -> Vec<FunctionLibrary> {
+    let Value::Array(libraries) = reply else {
+        return Vec::new();
+    };
+    libraries
+        .iter()
+        .map(|library| {
+            let fields = pairs(library);
+            let text = |name: &str| map_field(&fields, name).and_then(value_text).unwrap_or_default();
+            let functions = match map_field(&fields, "functions") {
+                Some(Value::Array(functions)) => functions.iter().map(parse_function).collect(),
+                _ => Vec::new(),
+            };
+            FunctionLibrary {
+                name: text("library_name"),
+                engine: text("engine"),
+                functions,
+            }
+        })
+        .collect()
+}
+
+fn parse_function(function: &Value) -> FunctionInfo {
+    let fields = pairs(function);
+    let text = |name: &str| map_field(&fields, name).and_then(value_text).unwrap_or_default();
+    let flags = match map_field(&fields, "flags") {
+        Some(Value::Array(flags)) | Some(Value::Set(flags)) => flags.iter().filter_map(value_text).collect(),
+        _ => Vec::new(),
+    };
+    FunctionInfo {
+        name: text("name"),
+        description: text("description"),
+        flags,
+    }
+}
+
+/// Splits FCALL input `<keys...> | <args...>`; without `|` every word is a key.
+pub fn parse_call_input(input: &str) -> (Vec<&str>, Vec<&str>) {
+    let (keys, args) = input.split_once('|').unwrap_or((input, ""));
+    (keys.split_whitespace().collect(), args.split_whitespace().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn function_list_reply_is_parsed_per_library() {
+        let reply = Value::Array(vec![Value::Array(vec![
+            bulk("library_name"),
+            bulk("mylib"),
+            bulk("engine"),
+            bulk("LUA"),
+            bulk("functions"),
+            Value::Array(vec![Value::Array(vec![
+                bulk("name"),
+                bulk("knockknock"),
+                bulk("description"),
+                Value::Nil,
+                bulk("flags"),
+                Value::Array(vec![bulk("no-writes")]),
+            ])]),
+        ])]);
+        let mut state = FunctionsState::default();
+        state.set_libraries(parse_function_list(&reply));
+        assert_eq!(state.libraries[0].name, "mylib");
+        assert_eq!(state.libraries[0].engine, "LUA");
+        let function = state.selected_function().unwrap();
+        assert_eq!(function.name, "knockknock");
+        assert_eq!(function.flags, vec!["no-writes".to_string()]);
+    }
+
+    #[test]
+    fn call_input_splits_keys_from_args() {
+        assert_eq!(parse_call_input("user:1 user:2 | 10 x"), (vec!["user:1", "user:2"], vec!["10", "x"]));
+        assert_eq!(parse_call_input("user:1"), (vec!["user:1"], vec![]));
+        assert_eq!(parse_call_input("| 5"), (vec![], vec!["5"]));
+    }
+}
@@ -0,0 +1,22 @@
+/// Full-screen cheat sheet of the keymap, opened with `?`.
+#[derive(Debug, Default, Clone)]
+pub struct HelpState {
+    pub is_active: bool,
+    /// First line shown; clamped to the content when drawn.
+    pub scroll: usize,
+}
+
+impl HelpState {
+    pub fn open(&mut self) {
+        self.is_active = true;
+        self.scroll = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+    }
+
+    pub fn scroll(&mut self, delta: isize) {
+        self.scroll = self.scroll.saturating_add_signed(delta);
+    }
+}
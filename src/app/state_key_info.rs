@@ -0,0 +1,74 @@
+/// Snapshot shown by the key metadata popup. Each probe keeps its own error
+/// because OBJECT IDLETIME/FREQ depend on the server's maxmemory policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyInfo {
+    pub key: String,
+    pub key_type: Result<String, String>,
+    pub ttl: Result<i64, String>,
+    pub encoding: Result<Option<String>, String>,
+    pub idle_seconds: Result<Option<i64>, String>,
+    pub freq: Result<Option<i64>, String>,
+    pub serialized_len: Result<Option<usize>, String>,
+    pub memory_usage: Option<u64>,
+}
+
+impl KeyInfo {
+    /// Label/value rows in display order.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Key", self.key.clone()),
+            ("Type", show(&self.key_type, |t| t.clone())),
+            ("TTL", show(&self.ttl, |ttl| format_ttl_seconds(*ttl))),
+            ("Encoding", show(&self.encoding, |e| e.clone().unwrap_or_else(|| "n/a".to_string()))),
+            ("Idle time", show(&self.idle_seconds, |idle| idle.map_or("n/a".to_string(), |s| format!("{}s", s)))),
+            ("LFU freq", show(&self.freq, |freq| freq.map_or("n/a".to_string(), |f| f.to_string()))),
+            (
+                "Serialized length",
+                show(&self.serialized_len, |len| len.map_or("n/a".to_string(), |l| format!("{} bytes", l))),
+            ),
+            (
+                "Memory usage",
+                self.memory_usage.map_or("n/a".to_string(), |bytes| format!("{} bytes", bytes)),
+            ),
+        ]
+    }
+}
+
+fn show<T>(value: &Result<T, String>, format: impl Fn(&T) -> String) -> String {
+    match value {
+        Ok(value) => format(value),
+        Err(message) => format!("unavailable ({})", message),
+    }
+}
+
+fn format_ttl_seconds(ttl: i64) -> String {
+    match ttl {
+        -2 => "key does not exist".to_string(),
+        -1 => "no expiry".to_string(),
+        secs => format!("{}s", secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_report_unavailable_probes() {
+        let info = KeyInfo {
+            key: "session:1".to_string(),
+            key_type: Ok("hash".to_string()),
+            ttl: Ok(-1),
+            encoding: Ok(Some("listpack".to_string())),
+            idle_seconds: Ok(Some(12)),
+            freq: Err("An LFU maxmemory policy is not selected".to_string()),
+            serialized_len: Ok(Some(48)),
+            memory_usage: None,
+        };
+        let rows = info.rows();
+        assert_eq!(rows[2], ("TTL", "no expiry".to_string()));
+        assert_eq!(rows[4], ("Idle time", "12s".to_string()));
+        assert!(rows[5].1.starts_with("unavailable ("));
+        assert_eq!(rows[7], ("Memory usage", "n/a".to_string()));
+    }
+}
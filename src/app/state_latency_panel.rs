@@ -0,0 +1,31 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::latency_monitor::{LatencyEvent, LatencyMonitor};
+
+/// PING round-trips kept for the RTT sparkline.
+pub const MAX_PING_SAMPLES: usize = 300;
+
+/// Latency panel: local PING RTT samples plus the server's latency events.
+#[derive(Default)]
+pub struct LatencyPanelState {
+    pub is_active: bool,
+    pub monitor: Option<LatencyMonitor>,
+    pub ping_samples: VecDeque<Duration>,
+    pub events: Vec<LatencyEvent>,
+    pub error: Option<String>,
+}
+
+impl LatencyPanelState {
+    pub fn record_ping(&mut self, rtt: Duration) {
+        if self.ping_samples.len() == MAX_PING_SAMPLES {
+            self.ping_samples.pop_front();
+        }
+        self.ping_samples.push_back(rtt);
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+        self.monitor = None;
+    }
+}
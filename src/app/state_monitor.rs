@@ -0,0 +1,114 @@
+use std::collections::VecDeque;
+
+use super::monitor::{MonitorEntry, MonitorSession};
+
+/// Commands kept in the MONITOR ring buffer; older ones are dropped first.
+pub const MAX_MONITOR_ENTRIES: usize = 2_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonitorPrompt {
+    Client,
+    Command,
+}
+
+impl MonitorPrompt {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MonitorPrompt::Client => "Filter by client address",
+            MonitorPrompt::Command => "Filter by command name",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MonitorState {
+    pub is_active: bool,
+    pub session: Option<MonitorSession>,
+    pub entries: VecDeque<MonitorEntry>,
+    pub prompt: Option<MonitorPrompt>,
+    pub input: String,
+    pub cursor: usize,
+    pub client_filter: String,
+    pub command_filter: String,
+    /// Lines scrolled up from the newest command; 0 follows new commands.
+    pub scroll: usize,
+}
+
+impl MonitorState {
+    pub fn open_prompt(&mut self, prompt: MonitorPrompt) {
+        self.input = match prompt {
+            MonitorPrompt::Client => self.client_filter.clone(),
+            MonitorPrompt::Command => self.command_filter.clone(),
+        };
+        self.cursor = self.input.len();
+        self.prompt = Some(prompt);
+    }
+
+    pub fn submit_prompt(&mut self) {
+        let Some(prompt) = self.prompt.take() else {
+            return;
+        };
+        let value = std::mem::take(&mut self.input).trim().to_string();
+        match prompt {
+            MonitorPrompt::Client => self.client_filter = value,
+            MonitorPrompt::Command => self.command_filter = value,
+        }
+        self.scroll = 0;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.session.as_ref().is_some_and(|session| session.paused)
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if let Some(session) = &mut self.session {
+            session.paused = !session.paused;
+        }
+    }
+
+    pub fn push_entries(&mut self, entries: Vec<MonitorEntry>) {
+        // Keep a scrolled-back view anchored on the same commands
+        if self.scroll > 0 {
+            self.scroll += entries.iter().filter(|entry| self.matches_filter(entry)).count();
+        }
+        self.entries.extend(entries);
+        let overflow = self.entries.len().saturating_sub(MAX_MONITOR_ENTRIES);
+        self.entries.drain(..overflow);
+    }
+
+    pub fn matches_filter(&self, entry: &MonitorEntry) -> bool {
+        entry.client.contains(&self.client_filter)
+            && entry.command.contains(&self.command_filter.to_uppercase())
+    }
+
+    pub fn visible_entries(&self) -> impl Iterator<Item = &MonitorEntry> {
+        self.entries.iter().filter(|entry| self.matches_filter(entry))
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.visible_entries().count().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::monitor::parse_monitor_line;
+
+    #[test]
+    fn filters_combine_client_and_command() {
+        let mut state = MonitorState::default();
+        let lines = [
+            r#"1.0 [0 10.0.0.1:5000] "get" "a""#,
+            r#"2.0 [0 10.0.0.2:5000] "get" "b""#,
+            r#"3.0 [0 10.0.0.1:5000] "hset" "c" "f" "v""#,
+        ];
+        state.push_entries(lines.iter().filter_map(|line| parse_monitor_line(line)).collect());
+        state.client_filter = "10.0.0.1".to_string();
+        assert_eq!(state.visible_entries().count(), 2);
+        state.command_filter = "get".to_string();
+        let visible: Vec<_> = state.visible_entries().map(|entry| entry.arguments.as_str()).collect();
+        assert_eq!(visible, vec![r#""get" "a""#]);
+    }
+}
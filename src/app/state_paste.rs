@@ -0,0 +1,37 @@
+use super::reproduce::{format_script, CommandArgs};
+
+/// Preview of a key pasted from the clipboard (`V`), run only once confirmed.
+#[derive(Debug, Default, Clone)]
+pub struct PastePreviewState {
+    pub is_active: bool,
+    pub key: String,
+    pub commands: Vec<CommandArgs>,
+    /// The commands in redis-cli syntax, as shown.
+    pub lines: Vec<String>,
+    /// `TYPE` of the key when it already exists and would be overwritten.
+    pub existing_type: Option<String>,
+    pub scroll: usize,
+}
+
+impl PastePreviewState {
+    pub fn open(&mut self, key: String, commands: Vec<CommandArgs>, existing_type: Option<String>) {
+        let lines = format_script(&commands).lines().map(str::to_string).collect();
+        *self = Self {
+            is_active: true,
+            key,
+            commands,
+            lines,
+            existing_type,
+            scroll: 0,
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
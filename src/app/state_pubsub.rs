@@ -0,0 +1,120 @@
+use std::collections::VecDeque;
+
+use super::pubsub::{PubSubListener, PubSubMessage};
+
+/// Messages kept in the panel; older ones are dropped first.
+pub const MAX_PUBSUB_MESSAGES: usize = 5_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PubSubPrompt {
+    /// Channels and glob patterns to subscribe to.
+    Subscribe,
+    /// Only show messages whose channel or pattern contains this text.
+    Filter,
+}
+
+impl PubSubPrompt {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PubSubPrompt::Subscribe => "Subscribe to channels/patterns (space separated, globs use PSUBSCRIBE)",
+            PubSubPrompt::Filter => "Filter by channel",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PubSubState {
+    pub is_active: bool,
+    pub listener: Option<PubSubListener>,
+    pub messages: VecDeque<PubSubMessage>,
+    pub prompt: Option<PubSubPrompt>,
+    pub input: String,
+    pub cursor: usize,
+    pub filter: String,
+    /// Lines scrolled up from the newest message; 0 follows new messages.
+    pub scroll: usize,
+}
+
+impl PubSubState {
+    pub fn open_prompt(&mut self, prompt: PubSubPrompt) {
+        self.input = match prompt {
+            PubSubPrompt::Subscribe => self.listener.as_ref().map_or_else(String::new, |listener| {
+                [listener.channels.as_slice(), listener.patterns.as_slice()].concat().join(" ")
+            }),
+            PubSubPrompt::Filter => self.filter.clone(),
+        };
+        self.cursor = self.input.len();
+        self.prompt = Some(prompt);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.listener.as_ref().is_some_and(|listener| listener.paused)
+    }
+
+    pub fn toggle_pause(&mut self) {
+        if let Some(listener) = &mut self.listener {
+            listener.paused = !listener.paused;
+        }
+    }
+
+    pub fn push_messages(&mut self, messages: Vec<PubSubMessage>) {
+        // Keep a scrolled-back view anchored on the same messages
+        if self.scroll > 0 {
+            self.scroll += messages.iter().filter(|message| self.matches_filter(message)).count();
+        }
+        self.messages.extend(messages);
+        let overflow = self.messages.len().saturating_sub(MAX_PUBSUB_MESSAGES);
+        self.messages.drain(..overflow);
+    }
+
+    pub fn matches_filter(&self, message: &PubSubMessage) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        let filter = self.filter.to_lowercase();
+        message.channel.to_lowercase().contains(&filter)
+            || message
+                .pattern
+                .as_ref()
+                .is_some_and(|pattern| pattern.to_lowercase().contains(&filter))
+    }
+
+    pub fn visible_messages(&self) -> impl Iterator<Item = &PubSubMessage> {
+        self.messages.iter().filter(|message| self.matches_filter(message))
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.visible_messages().count().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(channel: &str, payload: &str) -> PubSubMessage {
+        PubSubMessage {
+            received_at: 0,
+            channel: channel.to_string(),
+            pattern: None,
+            payload: payload.to_string(),
+        }
+    }
+
+    #[test]
+    fn filter_and_scroll_track_matching_messages() {
+        let mut state = PubSubState {
+            filter: "ORDERS".to_string(),
+            ..PubSubState::default()
+        };
+        state.push_messages(vec![message("orders", "1"), message("news", "2"), message("orders", "3")]);
+        let payloads: Vec<&str> = state.visible_messages().map(|m| m.payload.as_str()).collect();
+        assert_eq!(payloads, vec!["1", "3"]);
+
+        state.scroll_by(5);
+        assert_eq!(state.scroll, 1);
+        state.push_messages(vec![message("news", "4"), message("orders", "5")]);
+        assert_eq!(state.scroll, 2);
+    }
+}
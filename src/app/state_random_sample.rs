@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use super::key_sampler::KeySample;
+
+pub const DEFAULT_RANDOM_SAMPLE_SIZE: usize = 50;
+pub const MAX_RANDOM_SAMPLE_SIZE: usize = 500;
+const SAMPLE_SIZE_STEP: usize = 10;
+
+/// Keys picked with RANDOMKEY, to get a feel for a database without scanning it.
+#[derive(Debug, Clone)]
+pub struct RandomSampleState {
+    pub is_active: bool,
+    /// Number of RANDOMKEY calls per sample; repeats are dropped.
+    pub size: usize,
+    pub samples: Vec<KeySample>,
+    pub selected: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+impl Default for RandomSampleState {
+    fn default() -> Self {
+        Self {
+            is_active: false,
+            size: DEFAULT_RANDOM_SAMPLE_SIZE,
+            samples: Vec::new(),
+            selected: 0,
+            loading: false,
+            error: None,
+        }
+    }
+}
+
+impl RandomSampleState {
+    pub fn change_size(&mut self, steps: isize) {
+        self.size = self
+            .size
+            .saturating_add_signed(steps * SAMPLE_SIZE_STEP as isize)
+            .clamp(SAMPLE_SIZE_STEP, MAX_RANDOM_SAMPLE_SIZE);
+    }
+
+    pub fn set_samples(&mut self, samples: Vec<KeySample>) {
+        self.samples = samples;
+        self.selected = 0;
+        self.loading = false;
+        self.error = None;
+    }
+
+    pub fn selected_key(&self) -> Option<&str> {
+        self.samples.get(self.selected).map(|sample| sample.key.as_str())
+    }
+
+    pub fn select(&mut self, delta: isize) {
+        let last = self.samples.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+}
+
+/// RANDOMKEY replies without repeats, in the order they were drawn. An empty
+/// database answers nil.
+pub fn distinct_keys(replies: Vec<Option<String>>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    replies
+        .into_iter()
+        .flatten()
+        .filter(|key| seen.insert(key.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_keys_are_deduplicated_in_order() {
+        let replies = vec![Some("b".to_string()), None, Some("a".to_string()), Some("b".to_string())];
+        assert_eq!(distinct_keys(replies), vec!["b", "a"]);
+
+        let mut state = RandomSampleState::default();
+        state.change_size(-10);
+        assert_eq!(state.size, SAMPLE_SIZE_STEP);
+        state.change_size(100);
+        assert_eq!(state.size, MAX_RANDOM_SAMPLE_SIZE);
+    }
+}
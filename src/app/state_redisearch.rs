@@ -0,0 +1,456 @@
+use redis::Value;
+
+use crate::command::resp::{map_field, pairs, value_text};
+
+/// Rows fetched per FT.SEARCH / FT.AGGREGATE page.
+pub const SEARCH_PAGE_SIZE: usize = 10;
+
+/// Widest a result column is drawn; longer values are cut with `…`.
+const MAX_COLUMN_WIDTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchMode {
+    /// FT.SEARCH: `<query> ; <options...>`, rows are documents.
+    #[default]
+    Search,
+    /// FT.AGGREGATE: `<query> ; <pipeline...>`, rows are aggregated records.
+    Aggregate,
+}
+
+impl SearchMode {
+    pub fn command(self) -> &'static str {
+        match self {
+            SearchMode::Search => "FT.SEARCH",
+            SearchMode::Aggregate => "FT.AGGREGATE",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchMode::Search => "FT.SEARCH <query> ; <options...>",
+            SearchMode::Aggregate => "FT.AGGREGATE <query> ; <pipeline...>",
+        }
+    }
+}
+
+/// The parts of FT.INFO shown next to the index list.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexInfo {
+    pub key_type: String,
+    pub prefixes: Vec<String>,
+    pub num_docs: String,
+    /// `(attribute, type)` of each indexed field.
+    pub attributes: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchRow {
+    /// Document key; `None` for FT.AGGREGATE rows.
+    pub id: Option<String>,
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub total: u64,
+    pub rows: Vec<SearchRow>,
+}
+
+impl SearchResults {
+    /// Field names in the order they first appear, after the `id` column of FT.SEARCH.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns: Vec<String> = Vec::new();
+        if self.rows.iter().any(|row| row.id.is_some()) {
+            columns.push("id".to_string());
+        }
+        for (name, _) in self.rows.iter().flat_map(|row| &row.fields) {
+            if !columns.contains(name) {
+                columns.push(name.clone());
+            }
+        }
+        columns
+    }
+
+    /// The cells of every row under `columns`, each cut to its column width, with the
+    /// widths themselves.
+    pub fn table(&self) -> (Vec<String>, Vec<usize>, Vec<Vec<String>>) {
+        let columns = self.columns();
+        let cells: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| match (column.as_str(), &row.id) {
+                        ("id", Some(id)) => id.clone(),
+                        _ => row
+                            .fields
+                            .iter()
+                            .find(|(name, _)| name == column)
+                            .map(|(_, value)| value.replace('\n', " "))
+                            .unwrap_or_default(),
+                    })
+                    .map(|cell| truncate(&cell, MAX_COLUMN_WIDTH))
+                    .collect()
+            })
+            .collect();
+        let widths = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([column.chars().count().min(MAX_COLUMN_WIDTH)])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        (columns, widths, cells)
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// RediSearch panel: indexes from FT._LIST with their FT.INFO, and paged FT.SEARCH /
+/// FT.AGGREGATE results.
+#[derive(Debug, Default, Clone)]
+pub struct RediSearchState {
+    pub is_active: bool,
+    pub indexes: Vec<String>,
+    pub selected_index: usize,
+    pub info: Option<IndexInfo>,
+    pub mode: SearchMode,
+    pub editing: bool,
+    pub input: String,
+    pub cursor: usize,
+    /// The query the results belong to, re-run when paging.
+    pub query: Option<(SearchMode, String)>,
+    pub offset: usize,
+    pub results: Option<SearchResults>,
+    pub selected_row: usize,
+    /// Whether j/k move through the results rather than the indexes.
+    pub results_focused: bool,
+    pub message: Option<String>,
+}
+
+impl RediSearchState {
+    pub fn open(&mut self) {
+        self.is_active = true;
+        self.editing = false;
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+        self.editing = false;
+    }
+
+    pub fn selected_index_name(&self) -> Option<&str> {
+        self.indexes.get(self.selected_index).map(String::as_str)
+    }
+
+    pub fn set_indexes(&mut self, indexes: Vec<String>) {
+        self.indexes = indexes;
+        self.selected_index = self.selected_index.min(self.indexes.len().saturating_sub(1));
+    }
+
+    /// Moves the index selection, clearing results that belonged to the old index.
+    pub fn select_index(&mut self, forward: bool) -> bool {
+        let count = self.indexes.len();
+        if count < 2 {
+            return false;
+        }
+        self.selected_index = if forward {
+            (self.selected_index + 1) % count
+        } else {
+            (self.selected_index + count - 1) % count
+        };
+        self.info = None;
+        self.query = None;
+        self.results = None;
+        self.results_focused = false;
+        true
+    }
+
+    pub fn select_row(&mut self, forward: bool) {
+        let count = self.results.as_ref().map_or(0, |results| results.rows.len());
+        if count == 0 {
+            return;
+        }
+        self.selected_row = if forward {
+            (self.selected_row + 1).min(count - 1)
+        } else {
+            self.selected_row.saturating_sub(1)
+        };
+    }
+
+    pub fn open_prompt(&mut self, mode: SearchMode) {
+        if self.selected_index_name().is_none() {
+            self.message = Some("No index selected.".to_string());
+            return;
+        }
+        self.mode = mode;
+        self.editing = true;
+        if let Some((query_mode, query)) = &self.query {
+            if *query_mode == mode {
+                self.input = query.clone();
+            }
+        }
+        self.cursor = self.input.len();
+    }
+
+    /// Document key of the selected result row, for FT.SEARCH results.
+    pub fn selected_document(&self) -> Option<&str> {
+        self.results
+            .as_ref()?
+            .rows
+            .get(self.selected_row)?
+            .id
+            .as_deref()
+    }
+
+    /// Whether another page follows the one shown. FT.AGGREGATE does not report a
+    /// reliable total, so a full page is taken to mean there may be more.
+    pub fn has_next_page(&self) -> bool {
+        let (Some((mode, _)), Some(results)) = (&self.query, &self.results) else {
+            return false;
+        };
+        match mode {
+            SearchMode::Search => ((self.offset + results.rows.len()) as u64) < results.total,
+            SearchMode::Aggregate => results.rows.len() == SEARCH_PAGE_SIZE,
+        }
+    }
+}
+
+/// Splits `<query> ; <arguments...>`. The query stays one argument since it may hold
+/// spaces; `;` is not part of the query syntax.
+pub fn parse_query_input(input: &str) -> (&str, Vec<&str>) {
+    let (query, args) = input.split_once(';').unwrap_or((input, ""));
+    let query = query.trim();
+    (if query.is_empty() { "*" } else { query }, args.split_whitespace().collect())
+}
+
+/// Parses an FT._LIST reply.
+pub fn parse_index_list(reply: &Value) -> Vec<String> {
+    match reply {
+        Value::Array(names) | Value::Set(names) => {
+            let mut names: Vec<String> = names.iter().filter_map(value_text).collect();
+            names.sort();
+            names
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Text of a bulk, simple, integer or double reply.
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Int(n) => Some(n.to_string()),
+        Value::Double(n) => Some(n.to_string()),
+        _ => value_text(value),
+    }
+}
+
+/// Parses the index definition, document count and attributes out of an FT.INFO reply.
+pub fn parse_index_info(reply: &Value) -> IndexInfo {
+    let fields = pairs(reply);
+    let definition = map_field(&fields, "index_definition").map(pairs).unwrap_or_default();
+    let prefixes = match map_field(&definition, "prefixes") {
+        Some(Value::Array(prefixes)) | Some(Value::Set(prefixes)) => prefixes.iter().filter_map(value_text).collect(),
+        _ => Vec::new(),
+    };
+    let attributes = match map_field(&fields, "attributes") {
+        Some(Value::Array(attributes)) => attributes
+            .iter()
+            .map(|attribute| {
+                // Trailing flags such as SORTABLE break the pairing, but come last
+                let attribute = pairs(attribute);
+                let text = |name: &str| map_field(&attribute, name).and_then(value_text).unwrap_or_default();
+                (text("attribute"), text("type"))
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    IndexInfo {
+        key_type: map_field(&definition, "key_type").and_then(value_text).unwrap_or_default(),
+        prefixes,
+        num_docs: map_field(&fields, "num_docs").and_then(scalar_text).unwrap_or_default(),
+        attributes,
+    }
+}
+
+fn field_pairs(value: &Value) -> Vec<(String, String)> {
+    pairs(value)
+        .into_iter()
+        .filter_map(|(name, value)| Some((value_text(name)?, scalar_text(value).unwrap_or_default())))
+        .collect()
+}
+
+/// Parses an FT.SEARCH or FT.AGGREGATE reply. RESP2 replies are `[total, id, fields, ...]`
+/// for searches and `[total, fields, ...]` for aggregations; RESP3 ones are a map with
+/// `total_results` and `results`.
+pub fn parse_search_reply(reply: &Value) -> SearchResults {
+    if let Value::Map(_) = reply {
+        let fields = pairs(reply);
+        let rows = match map_field(&fields, "results") {
+            Some(Value::Array(results)) => results
+                .iter()
+                .map(|result| {
+                    let result = pairs(result);
+                    SearchRow {
+                        id: map_field(&result, "id").and_then(value_text),
+                        fields: map_field(&result, "extra_attributes").map(field_pairs).unwrap_or_default(),
+                    }
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let total = match map_field(&fields, "total_results") {
+            Some(Value::Int(total)) => *total as u64,
+            _ => rows.len() as u64,
+        };
+        return SearchResults { total, rows };
+    }
+    let Value::Array(items) = reply else {
+        return SearchResults::default();
+    };
+    let total = match items.first() {
+        Some(Value::Int(total)) => *total as u64,
+        _ => 0,
+    };
+    let mut rows: Vec<SearchRow> = Vec::new();
+    // Whether the last row is a document id still waiting for its fields
+    let mut awaiting_fields = false;
+    for item in items.iter().skip(1) {
+        match item {
+            Value::Array(_) | Value::Map(_) if awaiting_fields => {
+                if let Some(row) = rows.last_mut() {
+                    row.fields = field_pairs(item);
+                }
+                awaiting_fields = false;
+            }
+            Value::Array(_) | Value::Map(_) => rows.push(SearchRow {
+                id: None,
+                fields: field_pairs(item),
+            }),
+            other => {
+                rows.push(SearchRow {
+                    id: value_text(other),
+                    fields: Vec::new(),
+                });
+                awaiting_fields = true;
+            }
+        }
+    }
+    SearchResults { total, rows }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn search_and_aggregate_replies_become_rows() {
+        let search = Value::Array(vec![
+            Value::Int(57),
+            bulk("product:1"),
+            Value::Array(vec![bulk("name"), bulk("Lamp"), bulk("price"), bulk("20")]),
+            bulk("product:2"),
+            Value::Array(vec![bulk("name"), bulk("A very long product name that will not fit")]),
+        ]);
+        let results = parse_search_reply(&search);
+        assert_eq!(results.total, 57);
+        assert_eq!(results.rows[1].id.as_deref(), Some("product:2"));
+        let (columns, widths, cells) = results.table();
+        assert_eq!(columns, ["id", "name", "price"]);
+        assert_eq!(cells[0], ["product:1", "Lamp", "20"]);
+        assert_eq!(cells[1][2], "");
+        assert_eq!(widths, [9, MAX_COLUMN_WIDTH, 5]);
+        assert!(cells[1][1].ends_with('…'));
+
+        let aggregate = Value::Array(vec![
+            Value::Int(2),
+            Value::Array(vec![bulk("city"), bulk("Paris"), bulk("n"), bulk("3")]),
+            Value::Array(vec![bulk("city"), bulk("Oslo"), bulk("n"), bulk("1")]),
+        ]);
+        let results = parse_search_reply(&aggregate);
+        assert_eq!(results.columns(), ["city", "n"]);
+        assert!(results.rows.iter().all(|row| row.id.is_none()));
+
+        let resp3 = Value::Map(vec![
+            (bulk("total_results"), Value::Int(1)),
+            (
+                bulk("results"),
+                Value::Array(vec![Value::Map(vec![
+                    (bulk("id"), bulk("product:9")),
+                    (bulk("extra_attributes"), Value::Map(vec![(bulk("name"), bulk("Desk"))])),
+                ])]),
+            ),
+        ]);
+        let results = parse_search_reply(&resp3);
+        assert_eq!(results.total, 1);
+        assert_eq!(results.rows[0].fields, [("name".to_string(), "Desk".to_string())]);
+    }
+
+    #[test]
+    fn index_info_and_paging() {
+        let info = Value::Array(vec![
+            bulk("index_name"),
+            bulk("idx:products"),
+            bulk("index_definition"),
+            Value::Array(vec![
+                bulk("key_type"),
+                bulk("HASH"),
+                bulk("prefixes"),
+                Value::Array(vec![bulk("product:")]),
+            ]),
+            bulk("attributes"),
+            Value::Array(vec![Value::Array(vec![
+                bulk("identifier"),
+                bulk("price"),
+                bulk("attribute"),
+                bulk("price"),
+                bulk("type"),
+                bulk("NUMERIC"),
+                bulk("SORTABLE"),
+            ])]),
+            bulk("num_docs"),
+            Value::Int(57),
+        ]);
+        let info = parse_index_info(&info);
+        assert_eq!(info.key_type, "HASH");
+        assert_eq!(info.prefixes, ["product:"]);
+        assert_eq!(info.num_docs, "57");
+        assert_eq!(info.attributes, [("price".to_string(), "NUMERIC".to_string())]);
+
+        assert_eq!(
+            parse_query_input("@name:(lamp|desk) ; SORTBY price DESC"),
+            ("@name:(lamp|desk)", vec!["SORTBY", "price", "DESC"])
+        );
+        assert_eq!(parse_query_input("  "), ("*", vec![]));
+
+        let mut state = RediSearchState {
+            query: Some((SearchMode::Search, "*".to_string())),
+            offset: 50,
+            results: Some(SearchResults {
+                total: 57,
+                rows: vec![SearchRow::default(); 7],
+            }),
+            ..Default::default()
+        };
+        assert!(!state.has_next_page());
+        state.offset = 40;
+        assert!(state.has_next_page());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::config::ScanPreset;
+use crate::search::redis_type_name;
+
+use super::key_scan::DEFAULT_SCAN_PATTERN;
+
+/// A preset resolved into the `SCAN` arguments the key tree loads with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanFilter {
+    pub pattern: String,
+    pub key_type: Option<&'static str>,
+    pub count: usize,
+}
+
+impl ScanFilter {
+    /// Resolves `preset`, taking `*`, any type and `default_count` for the unset fields.
+    pub fn from_preset(preset: &ScanPreset, default_count: usize) -> Result<Self, String> {
+        let key_type = preset
+            .key_type
+            .as_deref()
+            .map(redis_type_name)
+            .transpose()
+            .map_err(|e| format!("Scan preset '{}': {}", preset.name, e))?;
+        Ok(Self {
+            pattern: preset.pattern.clone().unwrap_or_else(|| DEFAULT_SCAN_PATTERN.to_string()),
+            key_type,
+            count: preset.count.unwrap_or(default_count),
+        })
+    }
+}
+
+/// The presets of a profile, plus an `everything` entry when none of them clears the
+/// filter.
+pub fn picker_presets(configured: &[ScanPreset]) -> Vec<ScanPreset> {
+    let mut presets = configured.to_vec();
+    let clears = |preset: &ScanPreset| {
+        preset.pattern.as_deref().unwrap_or(DEFAULT_SCAN_PATTERN) == DEFAULT_SCAN_PATTERN && preset.key_type.is_none()
+    };
+    if !presets.iter().any(clears) {
+        presets.push(ScanPreset {
+            name: "everything".to_string(),
+            pattern: None,
+            key_type: None,
+            count: None,
+        });
+    }
+    presets
+}
+
+/// Quick picker of the saved scan filters (`v`); digits apply an entry directly.
+#[derive(Debug, Default, Clone)]
+pub struct ScanPresetPicker {
+    pub is_active: bool,
+    pub presets: Vec<ScanPreset>,
+    pub selected: usize,
+    /// COUNT of presets that set none, for display.
+    pub default_count: usize,
+}
+
+impl ScanPresetPicker {
+    /// Opens on `presets`, selecting the one that matches the `current` filter.
+    pub fn open(&mut self, presets: Vec<ScanPreset>, default_count: usize, current: &ScanFilter) {
+        let selected = presets
+            .iter()
+            .position(|preset| ScanFilter::from_preset(preset, default_count).as_ref() == Ok(current))
+            .unwrap_or(0);
+        *self = Self {
+            is_active: true,
+            presets,
+            selected,
+            default_count,
+        };
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+    }
+
+    pub fn select(&mut self, forward: bool) {
+        let count = self.presets.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+    }
+
+    pub fn selected_preset(&self) -> Option<&ScanPreset> {
+        self.presets.get(self.selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str, pattern: Option<&str>, key_type: Option<&str>, count: Option<usize>) -> ScanPreset {
+        ScanPreset {
+            name: name.to_string(),
+            pattern: pattern.map(str::to_string),
+            key_type: key_type.map(str::to_string),
+            count,
+        }
+    }
+
+    #[test]
+    fn presets_resolve_against_profile_defaults() {
+        let sessions = preset("sessions", Some("session:*"), Some("hash"), None);
+        let filter = ScanFilter::from_preset(&sessions, 500).unwrap();
+        assert_eq!(
+            filter,
+            ScanFilter {
+                pattern: "session:*".to_string(),
+                key_type: Some("hash"),
+                count: 500,
+            }
+        );
+        let json = ScanFilter::from_preset(&preset("json", None, Some("JSON"), Some(50)), 500).unwrap();
+        assert_eq!((json.key_type, json.count), (Some("ReJSON-RL"), 50));
+        assert!(ScanFilter::from_preset(&preset("bad", None, Some("blob"), None), 500).is_err());
+
+        let presets = picker_presets(std::slice::from_ref(&sessions));
+        assert_eq!(presets.len(), 2);
+        assert_eq!(presets[1].name, "everything");
+        assert_eq!(picker_presets(&[sessions, preset("all", Some("*"), None, Some(10))]).len(), 2);
+
+        let mut picker = ScanPresetPicker::default();
+        let everything = ScanFilter::from_preset(&presets[1], 500).unwrap();
+        picker.open(presets, 500, &everything);
+        assert_eq!(picker.selected, 1);
+        picker.select(true);
+        assert_eq!(picker.selected_preset().map(|preset| preset.name.as_str()), Some("sessions"));
+    }
+}
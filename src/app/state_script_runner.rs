@@ -0,0 +1,153 @@
+use std::path::Path;
+
+use crate::line_edit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScriptField {
+    #[default]
+    Script,
+    Keys,
+    Args,
+    File,
+}
+
+impl ScriptField {
+    pub fn next(self) -> Self {
+        match self {
+            ScriptField::Script => ScriptField::Keys,
+            ScriptField::Keys => ScriptField::Args,
+            ScriptField::Args => ScriptField::File,
+            ScriptField::File => ScriptField::Script,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ScriptField::Script => "Script",
+            ScriptField::Keys => "KEYS (space separated)",
+            ScriptField::Args => "ARGV (space separated)",
+            ScriptField::File => "Load .lua file (Enter)",
+        }
+    }
+}
+
+/// How the script is sent to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScriptMode {
+    #[default]
+    Eval,
+    /// `SCRIPT LOAD` followed by `EVALSHA`, as applications usually run scripts.
+    EvalSha,
+}
+
+/// Lua script runner. Its inputs survive closing the panel so a script can be
+/// tweaked and re-run against the keys it touches.
+#[derive(Debug, Default, Clone)]
+pub struct ScriptRunnerState {
+    pub is_active: bool,
+    pub field: ScriptField,
+    pub script: String,
+    pub keys: String,
+    pub args: String,
+    pub file: String,
+    /// Byte offset of the cursor in the focused field.
+    pub cursor: usize,
+    pub mode: ScriptMode,
+    /// SHA1 returned by the last `SCRIPT LOAD`.
+    pub sha: Option<String>,
+    pub result: Option<String>,
+    pub result_scroll: usize,
+}
+
+impl ScriptRunnerState {
+    pub fn open(&mut self) {
+        self.is_active = true;
+        self.cursor = self.focused().len();
+    }
+
+    pub fn focused(&self) -> &str {
+        match self.field {
+            ScriptField::Script => &self.script,
+            ScriptField::Keys => &self.keys,
+            ScriptField::Args => &self.args,
+            ScriptField::File => &self.file,
+        }
+    }
+
+    /// The focused field and the cursor inside it, for line editing.
+    pub fn focused_mut(&mut self) -> (&mut String, &mut usize) {
+        let text = match self.field {
+            ScriptField::Script => &mut self.script,
+            ScriptField::Keys => &mut self.keys,
+            ScriptField::Args => &mut self.args,
+            ScriptField::File => &mut self.file,
+        };
+        (text, &mut self.cursor)
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = self.field.next();
+        self.cursor = self.focused().len();
+    }
+
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            ScriptMode::Eval => ScriptMode::EvalSha,
+            ScriptMode::EvalSha => ScriptMode::Eval,
+        };
+    }
+
+    pub fn insert_newline(&mut self) {
+        let (text, cursor) = self.focused_mut();
+        line_edit::insert(text, cursor, '\n');
+    }
+
+    /// Replaces the script with the contents of the file named in the file field.
+    pub fn load_file(&mut self) {
+        let path = self.file.trim();
+        match std::fs::read_to_string(Path::new(path)) {
+            Ok(contents) => {
+                self.script = contents;
+                self.sha = None;
+                self.result = Some(format!("Loaded {}", path));
+                self.field = ScriptField::Script;
+                self.cursor = self.script.len();
+            }
+            Err(e) => self.result = Some(format!("Failed to read '{}': {}", path, e)),
+        }
+    }
+
+    pub fn key_names(&self) -> Vec<String> {
+        self.keys.split_whitespace().map(str::to_string).collect()
+    }
+
+    pub fn arg_values(&self) -> Vec<String> {
+        self.args.split_whitespace().map(str::to_string).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ScriptField, ScriptRunnerState};
+
+    #[test]
+    fn loading_a_file_replaces_the_script_and_focuses_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("touch.lua");
+        std::fs::write(&path, "return redis.call('GET', KEYS[1])\n").unwrap();
+
+        let mut state = ScriptRunnerState::default();
+        state.open();
+        state.next_field();
+        state.keys = "user:1  user:2".to_string();
+        assert_eq!(state.key_names(), vec!["user:1", "user:2"]);
+        while state.field != ScriptField::File {
+            state.next_field();
+        }
+        state.file = path.display().to_string();
+        state.load_file();
+        assert_eq!(state.field, ScriptField::Script);
+        assert!(state.script.starts_with("return redis.call"));
+        assert_eq!(state.cursor, state.script.len());
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use super::key_sampler::{KeySample, KeySampler};
+use crate::snapshot::{DiffRow, KeyspaceSnapshot, SnapshotEntry, SnapshotSummary};
+
+/// What a running keyspace walk is for.
+pub enum SnapshotPurpose {
+    /// Save the walk under this name.
+    Capture(String),
+    /// Diff the walk against a saved snapshot.
+    Compare(KeyspaceSnapshot),
+}
+
+/// Background walk collecting the live keyspace for a snapshot or a diff.
+pub struct SnapshotJob {
+    pub purpose: SnapshotPurpose,
+    pub sampler: KeySampler,
+    pub keys: BTreeMap<String, SnapshotEntry>,
+}
+
+impl SnapshotJob {
+    pub fn record(&mut self, samples: &[KeySample]) {
+        self.keys
+            .extend(samples.iter().map(|sample| (sample.key.clone(), SnapshotEntry::from(sample))));
+    }
+}
+
+/// Result of diffing the live keyspace against a snapshot.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotDiff {
+    pub against: String,
+    pub rows: Vec<DiffRow>,
+    pub selected: usize,
+}
+
+/// Saved keyspace snapshots and the diff view.
+#[derive(Default)]
+pub struct SnapshotsState {
+    pub is_active: bool,
+    /// `None` when no config directory could be determined.
+    pub dir: Option<PathBuf>,
+    pub snapshots: Vec<SnapshotSummary>,
+    pub selected: usize,
+    /// Name being typed for a new snapshot.
+    pub name_input: Option<String>,
+    pub name_cursor: usize,
+    pub job: Option<SnapshotJob>,
+    pub diff: Option<SnapshotDiff>,
+    pub status: Option<String>,
+}
+
+impl SnapshotsState {
+    pub fn select(&mut self, delta: isize) {
+        if let Some(diff) = &mut self.diff {
+            let last = diff.rows.len().saturating_sub(1);
+            diff.selected = diff.selected.saturating_add_signed(delta).min(last);
+            return;
+        }
+        let last = self.snapshots.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn selected_snapshot(&self) -> Option<&SnapshotSummary> {
+        self.snapshots.get(self.selected)
+    }
+
+    /// Live key under the cursor in the diff view; removed keys cannot be opened.
+    pub fn selected_diff_key(&self) -> Option<&str> {
+        let diff = self.diff.as_ref()?;
+        let row = diff.rows.get(diff.selected)?;
+        row.after.as_ref().map(|_| row.key.as_str())
+    }
+}
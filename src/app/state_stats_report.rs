@@ -0,0 +1,155 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::latency_tracker::{LatencyTracker, OperationClass};
+use super::redis_stats::RedisStats;
+use super::timestamp::format_utc;
+
+/// Markdown report of the stats panel and the INFO reply it was read from (`I`), to
+/// copy or save for an incident ticket.
+#[derive(Debug, Default, Clone)]
+pub struct StatsReportState {
+    pub is_active: bool,
+    pub report: String,
+    pub scroll: usize,
+}
+
+impl StatsReportState {
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.report.lines().count().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Writes the report to `lazyredis-stats-<millis>.md` in `dir`.
+    pub fn save(&self, dir: &Path, millis: i64) -> io::Result<PathBuf> {
+        let path = dir.join(format!("lazyredis-stats-{}.md", millis));
+        fs::write(&path, &self.report)?;
+        Ok(path)
+    }
+}
+
+/// Lays out `stats` as summary tables followed by the raw `info` it was parsed from.
+/// Latency classes without samples are left out.
+pub fn build_stats_report(
+    target: &str,
+    generated_at_secs: i64,
+    stats: &RedisStats,
+    latency: &LatencyTracker,
+    info: &str,
+) -> String {
+    let mut lines = vec![
+        format!("# Redis stats: {}", target),
+        String::new(),
+        format!("Generated {}", format_utc(generated_at_secs)),
+        String::new(),
+        "## Summary".to_string(),
+        String::new(),
+        "| Metric | Value |".to_string(),
+        "| --- | --- |".to_string(),
+    ];
+    let rows = [
+        ("Server", format!("Redis {} ({})", stats.redis_version, stats.redis_mode)),
+        ("Role", format!("{} ({} replicas)", stats.role, stats.connected_slaves)),
+        ("Uptime", stats.uptime_human.clone()),
+        (
+            "Memory used",
+            format!(
+                "{} (peak {}, RSS {})",
+                stats.memory_used_human, stats.memory_peak_human, stats.memory_rss_human
+            ),
+        ),
+        (
+            "Clients",
+            format!("{} connected, {} blocked", stats.connected_clients, stats.blocked_clients),
+        ),
+        ("Ops/sec", stats.instantaneous_ops_per_sec.to_string()),
+        ("Commands processed", stats.total_commands_processed.to_string()),
+        (
+            "Keyspace hit rate",
+            format!(
+                "{:.1}% ({} hits, {} misses)",
+                stats.hit_rate, stats.keyspace_hits, stats.keyspace_misses
+            ),
+        ),
+        ("CPU", format!("sys {:.2}s, user {:.2}s", stats.used_cpu_sys, stats.used_cpu_user)),
+    ];
+    lines.extend(rows.iter().map(|(metric, value)| format!("| {} | {} |", metric, value)));
+
+    lines.extend([String::new(), "## Keyspace".to_string(), String::new()]);
+    if stats.keyspace.is_empty() {
+        lines.push("No keys.".to_string());
+    } else {
+        lines.push("| DB | Keys | Expires |".to_string());
+        lines.push("| --- | --- | --- |".to_string());
+        let mut dbs: Vec<_> = stats.keyspace.iter().collect();
+        dbs.sort_unstable_by_key(|(db, _)| **db);
+        lines.extend(dbs.into_iter().map(|(db, counts)| format!("| db{} | {} | {} |", db, counts.keys, counts.expires)));
+    }
+
+    let latency_rows: Vec<String> = OperationClass::ALL
+        .iter()
+        .filter_map(|class| {
+            let summary = latency.summary(*class)?;
+            Some(format!(
+                "| {} | {} | {} | {} |",
+                class.label(),
+                millis(summary.p50),
+                millis(summary.p99),
+                summary.samples
+            ))
+        })
+        .collect();
+    if !latency_rows.is_empty() {
+        lines.extend([
+            String::new(),
+            "## Client latency".to_string(),
+            String::new(),
+            "| Operation | p50 | p99 | Samples |".to_string(),
+            "| --- | --- | --- | --- |".to_string(),
+        ]);
+        lines.extend(latency_rows);
+    }
+
+    lines.extend([String::new(), "## INFO".to_string(), String::new(), "```text".to_string()]);
+    lines.extend(info.trim().lines().map(|line| line.trim_end().to_string()));
+    lines.push("```".to_string());
+    let mut report = lines.join("\n");
+    report.push('\n');
+    report
+}
+
+fn millis(latency: Duration) -> String {
+    format!("{:.2} ms", latency.as_secs_f64() * 1_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_has_summary_keyspace_and_info() {
+        let info = "# Server\r\nredis_version:7.2.4\r\nredis_mode:standalone\r\n\r\n# Keyspace\r\n\
+                    db3:keys=7,expires=0,avg_ttl=0\r\ndb0:keys=10,expires=2,avg_ttl=0\r\n";
+        let stats = RedisStats::from_info_string(info);
+        let mut latency = LatencyTracker::default();
+        latency.record(OperationClass::Get, Duration::from_micros(1_500));
+
+        let report = build_stats_report("prod (redis://cache:6379)", 0, &stats, &latency, info);
+        assert!(report.starts_with("# Redis stats: prod (redis://cache:6379)\n\nGenerated 1970-01-01 00:00:00 UTC\n"));
+        assert!(report.contains("| Server | Redis 7.2.4 (standalone) |"));
+        assert!(report.contains("| db0 | 10 | 2 |\n| db3 | 7 | 0 |"));
+        assert!(report.contains("| GET | 1.50 ms | 1.50 ms | 1 |"));
+        assert!(!report.contains("| SCAN |"));
+        assert!(report.ends_with("```text\n# Server\nredis_version:7.2.4\nredis_mode:standalone\n\n# Keyspace\n\
+                                  db3:keys=7,expires=0,avg_ttl=0\ndb0:keys=10,expires=2,avg_ttl=0\n```\n"));
+        assert!(!report.contains('\r'));
+
+        let state = StatsReportState { is_active: true, report, scroll: 0 };
+        let dir = tempfile::tempdir().unwrap();
+        let path = state.save(dir.path(), 42).unwrap();
+        assert!(path.ends_with("lazyredis-stats-42.md"));
+        assert_eq!(fs::read_to_string(path).unwrap(), state.report);
+    }
+}
@@ -0,0 +1,33 @@
+use std::collections::HashSet;
+
+/// Expandable tree mode of the key panel, toggled with `t`. The selection is still the
+/// breadcrumb plus the selected entry of that level, so every key action keeps working;
+/// the tree only decides which levels are drawn.
+#[derive(Debug, Default, Clone)]
+pub struct TreeViewState {
+    pub is_active: bool,
+    /// Canonical prefixes (with the trailing delimiter) of the folders shown open.
+    pub expanded: HashSet<String>,
+}
+
+/// One line of the expanded tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeRow {
+    /// Folders above the entry, i.e. the breadcrumb of the level it belongs to.
+    pub parents: Vec<String>,
+    /// Entry name as listed in its level, folders with a trailing `/`.
+    pub name: String,
+    pub is_folder: bool,
+    pub expanded: bool,
+}
+
+impl TreeRow {
+    pub fn depth(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Folder name without the trailing `/`.
+    pub fn folder_name(&self) -> &str {
+        self.name.strip_suffix('/').unwrap_or(&self.name)
+    }
+}
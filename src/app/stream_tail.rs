@@ -0,0 +1,140 @@
+use redis::{AsyncConnectionConfig, Client, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::app_fetch::parse_stream_entries;
+use super::background_job::BackgroundJob;
+use super::StreamEntry;
+
+/// How long each XREAD blocks server-side before the task loops again.
+const BLOCK_MILLIS: usize = 1000;
+const READ_COUNT: usize = 100;
+
+#[derive(Debug)]
+pub enum StreamTailEvent {
+    Entries(Vec<StreamEntry>),
+    Error(String),
+}
+
+/// Background `XREAD BLOCK` loop for one stream key.
+///
+/// Blocking reads run on a dedicated connection so they never stall the shared
+/// multiplexed connection used by the rest of the UI. While paused, new entries
+/// stay queued in the channel and are appended once the tail is resumed.
+pub struct StreamTail {
+    pub key: String,
+    pub paused: bool,
+    job: BackgroundJob<StreamTailEvent>,
+}
+
+impl StreamTail {
+    /// Starts tailing `key` after `last_id` (`$` for only new entries).
+    pub fn spawn(client: Client, db_index: usize, key: String, last_id: String) -> Self {
+        let job = BackgroundJob::spawn(|sender| tail_stream(client, db_index, key.clone(), last_id, sender));
+        Self {
+            key,
+            paused: false,
+            job,
+        }
+    }
+
+    /// Drains every queued event unless the tail is paused.
+    pub fn drain(&mut self) -> Vec<StreamTailEvent> {
+        if self.paused {
+            return Vec::new();
+        }
+        self.job.drain()
+    }
+}
+
+async fn tail_stream(
+    client: Client,
+    db_index: usize,
+    key: String,
+    mut last_id: String,
+    sender: UnboundedSender<StreamTailEvent>,
+) {
+    let config = AsyncConnectionConfig::new().set_response_timeout(None);
+    let mut con = match client.get_multiplexed_async_connection_with_config(&config).await {
+        Ok(con) => con,
+        Err(e) => {
+            let _ = sender.send(StreamTailEvent::Error(format!("Stream tail connect failed: {}", e)));
+            return;
+        }
+    };
+    if let Err(e) = redis::cmd("SELECT").arg(db_index).query_async::<()>(&mut con).await {
+        let _ = sender.send(StreamTailEvent::Error(format!("Stream tail SELECT failed: {}", e)));
+        return;
+    }
+
+    loop {
+        let result = redis::cmd("XREAD")
+            .arg("COUNT")
+            .arg(READ_COUNT)
+            .arg("BLOCK")
+            .arg(BLOCK_MILLIS)
+            .arg("STREAMS")
+            .arg(&key)
+            .arg(&last_id)
+            .query_async::<Value>(&mut con)
+            .await;
+        let event = match result.map_err(|e| e.to_string()).and_then(parse_xread_reply) {
+            Ok(entries) if entries.is_empty() => continue,
+            Ok(entries) => {
+                if let Some(last) = entries.last() {
+                    last_id = last.id.clone();
+                }
+                StreamTailEvent::Entries(entries)
+            }
+            Err(message) => StreamTailEvent::Error(format!("XREAD failed: {}", message)),
+        };
+        let is_error = matches!(event, StreamTailEvent::Error(_));
+        if sender.send(event).is_err() || is_error {
+            return;
+        }
+    }
+}
+
+/// Extracts the entries of the single requested stream from an XREAD reply,
+/// which is an array of `[key, entries]` pairs in RESP2 and a map in RESP3.
+fn parse_xread_reply(value: Value) -> Result<Vec<StreamEntry>, String> {
+    let entries = match value {
+        Value::Nil => return Ok(Vec::new()),
+        Value::Array(streams) => streams.into_iter().find_map(|stream| match stream {
+            Value::Array(mut pair) if pair.len() == 2 => pair.pop(),
+            _ => None,
+        }),
+        Value::Map(streams) => streams.into_iter().next().map(|(_, entries)| entries),
+        other => return Err(format!("unexpected reply {:?}", other)),
+    };
+    match entries {
+        Some(entries) => parse_stream_entries(entries),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn parse_xread_reply_reads_resp2_and_resp3_shapes() {
+        let entries = Value::Array(vec![Value::Array(vec![
+            bulk("1-0"),
+            Value::Array(vec![bulk("field"), bulk("value")]),
+        ])]);
+        let resp2 = Value::Array(vec![Value::Array(vec![bulk("events"), entries.clone()])]);
+        let resp3 = Value::Map(vec![(bulk("events"), entries)]);
+
+        for reply in [resp2, resp3] {
+            let parsed = parse_xread_reply(reply).expect("parse");
+            assert_eq!(parsed.len(), 1);
+            assert_eq!(parsed[0].id, "1-0");
+            assert_eq!(parsed[0].fields, vec![("field".to_string(), "value".to_string())]);
+        }
+        assert!(parse_xread_reply(Value::Nil).unwrap().is_empty());
+    }
+}
@@ -1,11 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
+use crate::app::key_delimiters::KeyDelimiters;
 use crate::app::state_delete_dialog::DeleteDialogState;
 use crate::app::state_profile_selector::ProfileSelectorState;
 use crate::app::value_viewer::ValueViewer;
 use crate::app::{App, KeyTreeNode};
 use crate::command::CommandState;
-use crate::config::ConnectionProfile;
+use crate::config::{ConnectionProfile, Settings};
 use crate::search::SearchState;
 
 fn empty_app() -> App {
@@ -17,43 +19,104 @@ fn empty_app() -> App {
         profiles: Vec::new(),
         current_profile_index: 0,
         profile_state: ProfileSelectorState::default(),
-        raw_keys: Vec::new(),
+        raw_keys: Arc::new(Vec::new()),
         key_tree: HashMap::new(),
+        folder_key_counts: HashMap::new(),
+        loaded_prefixes: HashSet::new(),
         current_breadcrumb: Vec::new(),
+        breadcrumb_cursor: None,
+        tree_view: crate::app::state_tree_view::TreeViewState::default(),
         visible_keys_in_current_view: Vec::new(),
         ttl_map: HashMap::new(),
-        type_map: HashMap::new(),
+        ttl_read_at: HashMap::new(),
+        key_sizes: crate::app::key_sizes::KeySizes::default(),
+        key_cache: crate::app::key_cache::KeyCache::default(),
+        type_map: Arc::default(),
+        key_type_failures: HashMap::new(),
         selected_visible_key_index: 0,
         selected_indices: std::collections::HashSet::new(),
         multi_select_anchor: None,
-        key_delimiter: ':',
+        key_delimiters: KeyDelimiters::default(),
         is_key_view_focused: false,
         value_viewer: ValueViewer::default(),
         is_value_view_focused: false,
         value_is_pinned: false,
+        value_decoder: crate::app::value_decoder::ValueDecoder::default(),
+        watch: crate::app::watch::WatchState::default(),
+        stream_tail: None,
+        live_updates: None,
+        key_scan: None,
+        bulk_delete: None,
+        prefix_walk: None,
+        worker: None,
+        tree_refresh: None,
+        last_tree_refresh: std::time::Instant::now(),
+        clicks: crate::app::mouse::ClickTracker::default(),
+        keymap: crate::keymap::Keymap::default(),
+        pending_keys: crate::keymap::PendingKeys::default(),
+        key_list_scroll: crate::app::list_scroll::ListScroll::default(),
+        value_list_scroll: crate::app::list_scroll::ListScroll::default(),
+        help: crate::app::state_help::HelpState::default(),
+        search_scan: None,
+        value_grep: None,
+        search_job: None,
+        search_due: None,
+        preview_due: None,
+        pending_key_selection: None,
+        restore_session: None,
+        scan_pattern: "*".to_string(),
+        scan_count: 1000,
+        scan_type: None,
+        scan_prompt: None,
+        scan_presets: crate::app::state_scan_presets::ScanPresetPicker::default(),
+        goto_key_prompt: None,
+        bookmarks: crate::bookmarks::Bookmarks::default(),
+        bookmarks_panel: crate::app::state_bookmarks::BookmarksPanelState::default(),
+        script_runner: crate::app::state_script_runner::ScriptRunnerState::default(),
+        action_menu: crate::app::state_action_menu::ActionMenuState::default(),
+        paste_preview: crate::app::state_paste::PastePreviewState::default(),
+        folder_copy: crate::app::state_folder_copy::FolderCopyMenu::default(),
+        functions: crate::app::state_functions::FunctionsState::default(),
+        redisearch: crate::app::state_redisearch::RediSearchState::default(),
+        pubsub: crate::app::state_pubsub::PubSubState::default(),
+        monitor: crate::app::state_monitor::MonitorState::default(),
+        config_editor: crate::app::state_config_editor::ConfigEditorState::default(),
+        latency_panel: crate::app::state_latency_panel::LatencyPanelState::default(),
+        doctor_report: crate::app::state_doctor::DoctorReportState::default(),
+        stats_report: crate::app::state_stats_report::StatsReportState::default(),
+        analysis: crate::app::state_analysis::AnalysisState::default(),
+        random_sample: crate::app::state_random_sample::RandomSampleState::default(),
+        snapshots: crate::app::state_snapshots::SnapshotsState::default(),
+        compare: crate::app::state_compare::CompareState::default(),
         scan_cursor: 0,
         keys_fully_loaded: false,
-        clipboard_status: None,
+        notifications: crate::app::notifications::Notifications::default(),
         search_state: SearchState::new(),
         delete_dialog: DeleteDialogState::default(),
+        consumer_groups: crate::app::state_consumer_groups::ConsumerGroupState::default(),
+        key_info: None,
         command_state: CommandState::new(),
-        pending_operation: None,
         redis_stats: None,
+        db_key_counts: None,
+        db_size: None,
+        cluster: None,
         show_stats: false,
         stats_auto_refresh: true,
+        latency: crate::app::latency_tracker::LatencyTracker::default(),
+        settings: Settings::default(),
     }
 }
 
 #[test]
 fn builds_tree_with_nested_keys() {
     let mut app = empty_app();
-    app.raw_keys = vec![
+    app.raw_keys = Arc::new(vec![
         "foo:bar".to_string(),
         "foo:baz".to_string(),
         "foo:qux:1".to_string(),
         "alpha".to_string(),
         "beta:g1:h1".to_string(),
-    ];
+    ]);
     app.parse_keys_to_tree();
 
     assert!(matches!(
@@ -82,7 +145,7 @@ fn builds_tree_with_nested_keys() {
 #[test]
 fn promotes_leaf_to_folder_when_needed() {
     let mut app = empty_app();
-    app.raw_keys = vec!["foo".to_string(), "foo:bar".to_string()];
+    app.raw_keys = Arc::new(vec!["foo".to_string(), "foo:bar".to_string()]);
     app.parse_keys_to_tree();
     if let KeyTreeNode::Folder(map) = app.key_tree.get("foo").unwrap() {
         assert!(matches!(
@@ -103,6 +166,11 @@ fn seed_and_purge_only_allowed_on_dev_profiles() {
         db: Some(0),
         dev: Some(true),
         color: None,
+        scan_pattern: None,
+        scan_count: None,
+        read_only: None,
+        replica_url: None,
+        scan_presets: Vec::new(),
     };
     let prod_profile = ConnectionProfile {
         name: "Prod".to_string(),
@@ -110,6 +178,11 @@ fn seed_and_purge_only_allowed_on_dev_profiles() {
         db: Some(0),
         dev: Some(false),
         color: None,
+        scan_pattern: None,
+        scan_count: None,
+        read_only: None,
+        replica_url: None,
+        scan_presets: Vec::new(),
     };
 
     fn can_seed_or_purge(profile: &ConnectionProfile) -> bool {
@@ -128,9 +201,461 @@ fn seed_and_purge_only_allowed_on_dev_profiles() {
         db: Some(0),
         dev: None,
         color: None,
+        scan_pattern: None,
+        scan_count: None,
+        read_only: None,
+        replica_url: None,
+        scan_presets: Vec::new(),
     };
     assert!(
         !can_seed_or_purge(&no_dev_field),
         "Should NOT allow if dev field is missing"
     );
 }
+
+#[test]
+fn goto_line_uses_element_index_for_lists() {
+    let mut app = empty_app();
+    app.value_viewer.selected_key_type = Some("list".to_string());
+    app.value_viewer.selected_key_value_list = Some(vec!["a".into(), "b".into(), "c".into()]);
+    app.value_viewer.update_current_display_value();
+    app.value_viewer.goto_line_input = Some("1".to_string());
+    app.submit_goto_line();
+    assert_eq!(app.value_viewer.selected_value_sub_index, 1);
+    assert_eq!(app.value_viewer.line_label(2), "2");
+
+    app.value_viewer.selected_key_type = Some("string".to_string());
+    app.value_viewer.selected_key_value = Some("one\ntwo\nthree".to_string());
+    app.value_viewer.update_current_display_value();
+    app.value_viewer.goto_line(3);
+    assert_eq!(app.value_viewer.value_view_scroll.0, 2);
+    assert_eq!(app.value_viewer.line_label(0), "1");
+}
+
+#[test]
+fn hash_rows_sort_and_filter_in_place() {
+    let mut viewer = ValueViewer {
+        selected_key_type: Some("HASH".to_string()),
+        selected_key_value_hash: Some(vec![
+            ("b".to_string(), "1".to_string()),
+            ("a".to_string(), "3".to_string()),
+            ("c".to_string(), "2".to_string()),
+        ]),
+        ..ValueViewer::default()
+    };
+    viewer.update_current_display_value();
+    viewer.cycle_sort();
+    assert_eq!(
+        viewer.displayed_value_lines.as_deref(),
+        Some(&["a: 3".to_string(), "b: 1".to_string(), "c: 2".to_string()][..])
+    );
+    viewer.cycle_sort();
+    assert_eq!(viewer.displayed_value_lines.as_ref().unwrap()[0], "b: 1");
+    viewer.set_line_filter("C".to_string());
+    assert_eq!(viewer.displayed_value_lines.as_deref(), Some(&["c: 2".to_string()][..]));
+}
+
+#[test]
+fn counts_keys_beneath_each_folder() {
+    let mut app = empty_app();
+    app.raw_keys = Arc::new(vec![
+        "users:1".to_string(),
+        "users:2:profile".to_string(),
+        "users:2:settings".to_string(),
+        "users:2:settings".to_string(),
+        "orders".to_string(),
+        "orders:9".to_string(),
+    ]);
+    app.parse_keys_to_tree();
+    assert_eq!(app.folder_key_count("users"), 3);
+    // "orders" was displaced by the "orders:" folder, leaving one key beneath it
+    assert_eq!(app.folder_key_count("orders"), 1);
+    app.current_breadcrumb = vec!["users".to_string()];
+    assert_eq!(app.folder_key_count("2"), 2);
+}
+
+#[test]
+fn scanned_level_keeps_only_immediate_children() {
+    let mut app = empty_app();
+    let root_scan = vec![
+        "users:1".to_string(),
+        "users:2:profile".to_string(),
+        "config".to_string(),
+    ];
+    app.insert_scanned_keys("", &root_scan);
+    app.update_visible_keys();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![("users/".to_string(), true), ("config".to_string(), false)]
+    );
+    assert_eq!(app.folder_key_count("users"), 2);
+    assert_eq!(*app.raw_keys, vec!["config".to_string()]);
+
+    app.current_breadcrumb = vec!["users".to_string()];
+    let users_scan = vec!["users:1".to_string(), "users:2:profile".to_string()];
+    app.insert_scanned_keys(&app.current_level_prefix(), &users_scan);
+    app.update_visible_keys();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![("2/".to_string(), true), ("1".to_string(), false)]
+    );
+    assert_eq!(app.folder_key_count("2"), 1);
+}
+
+#[test]
+fn mixed_delimiters_share_one_tree() {
+    let mut app = empty_app();
+    app.key_delimiters = KeyDelimiters::new(":/.");
+    let root_scan = vec![
+        "seed:simple:1".to_string(),
+        "seed/path/num_1".to_string(),
+        "seed.dot.num_1".to_string(),
+        "user:jane.doe".to_string(),
+    ];
+    app.insert_scanned_keys("", &root_scan);
+    app.update_visible_keys();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![("seed/".to_string(), true), ("user/".to_string(), true)]
+    );
+    assert_eq!(app.folder_key_count("seed"), 3);
+
+    app.current_breadcrumb = vec!["seed".to_string()];
+    let prefix = app.current_level_prefix();
+    // The level scan also returns keys another delimiter places elsewhere
+    let mut seed_scan = vec![
+        "seed:simple:1".to_string(),
+        "seed/path/num_1".to_string(),
+        "seed.dot.num_1".to_string(),
+        "seed/x:y".to_string(),
+    ];
+    app.keep_scan_matches(&prefix, &mut seed_scan);
+    app.insert_scanned_keys(&prefix, &seed_scan);
+    app.update_visible_keys();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![
+            ("dot/".to_string(), true),
+            ("path/".to_string(), true),
+            ("simple/".to_string(), true)
+        ]
+    );
+
+    app.current_breadcrumb = vec!["seed".to_string(), "path".to_string()];
+    app.insert_scanned_keys(&app.current_level_prefix(), &["seed/path/num_1".to_string()]);
+    app.update_visible_keys();
+    assert_eq!(app.leaf_full_name("num_1").as_deref(), Some("seed/path/num_1"));
+}
+
+#[test]
+fn folder_delete_needs_a_connection_and_esc_without_one_running_is_a_no_op() {
+    let mut app = empty_app();
+    app.delete_dialog.deletion_is_folder = true;
+    app.delete_dialog.prefix_to_delete = Some("sessions:".to_string());
+    app.start_bulk_delete();
+    assert!(app.bulk_delete.is_none());
+    let toast = app.notifications.toasts().last().unwrap();
+    assert!(toast.message.contains("No Redis connection"));
+
+    app.cancel_bulk_delete();
+    assert_eq!(app.notifications.toasts().count(), 1);
+}
+
+#[test]
+fn delete_preview_needs_a_connection_and_esc_without_a_walk_is_a_no_op() {
+    let mut app = empty_app();
+    app.delete_dialog.show_confirmation_dialog = true;
+    app.delete_dialog.deletion_is_folder = true;
+    app.delete_dialog.prefix_to_delete = Some("sessions:".to_string());
+    assert!(app.delete_can_preview());
+    app.preview_delete_item();
+    assert!(app.prefix_walk.is_none());
+    assert!(app.delete_dialog.preview.is_none());
+    let toast = app.notifications.toasts().last().unwrap();
+    assert!(toast.message.contains("No Redis connection"));
+
+    assert!(!app.delete_preview_running());
+    assert!(!app.stop_prefix_walk());
+    assert_eq!(app.notifications.toasts().count(), 1);
+}
+
+#[test]
+fn read_only_profiles_refuse_writes_before_touching_the_server() {
+    let mut app = empty_app();
+    app.profiles = vec![ConnectionProfile {
+        read_only: Some(true),
+        ..ConnectionProfile::from_url("redis://prod.example.com:6379").unwrap()
+    }];
+    app.visible_keys_in_current_view = vec![("session:1".to_string(), false)];
+    app.initiate_delete_selected_item();
+    assert!(!app.delete_dialog.show_confirmation_dialog);
+    let toast = app.notifications.toasts().last().unwrap();
+    assert_eq!(toast.message, "Profile 'prod.example.com:6379' is read-only; deletes are disabled.");
+
+    app.delete_dialog.deletion_is_folder = true;
+    app.delete_dialog.prefix_to_delete = Some("session:".to_string());
+    app.start_bulk_delete();
+    assert!(app.bulk_delete.is_none());
+    assert!(app.writer("scripts").is_none());
+    let toast = app.notifications.toasts().last().unwrap();
+    assert!(toast.message.ends_with("read-only; scripts are disabled."));
+}
+
+#[test]
+fn auto_preview_waits_for_the_selection_to_rest() {
+    use std::time::{Duration, Instant};
+
+    let mut app = empty_app();
+    app.schedule_auto_preview();
+    assert!(!app.take_due_auto_preview());
+
+    app.preview_due = Some(Instant::now() - Duration::from_millis(1));
+    assert!(app.take_due_auto_preview());
+    assert!(app.preview_due.is_none());
+    assert!(!app.take_due_auto_preview());
+
+    app.settings.preview_delay_ms = 0;
+    app.schedule_auto_preview();
+    assert!(app.take_due_auto_preview());
+}
+
+#[test]
+fn viewed_keys_come_back_from_the_cache_until_they_change() {
+    use crate::app::app_live_updates::KeyChange;
+
+    let mut app = empty_app();
+    app.value_viewer.active_leaf_key_name = Some("greeting".to_string());
+    app.value_viewer.selected_key_type = Some("STRING".to_string());
+    app.value_viewer.selected_key_value = Some("hello".to_string());
+    app.value_viewer.memory_usage = Some(56);
+    app.cache_viewed_key("greeting", "string", -1);
+
+    app.value_viewer.clear();
+    app.value_viewer.line_numbers = true;
+    assert!(app.restore_cached_key("greeting"));
+    assert_eq!(app.value_viewer.selected_key_value.as_deref(), Some("hello"));
+    assert_eq!(app.value_viewer.memory_usage, Some(56));
+    assert!(app.value_viewer.line_numbers);
+    assert_eq!(app.type_map.get("greeting").map(String::as_str), Some("string"));
+    assert_eq!(app.ttl_map.get("greeting"), Some(&-1));
+
+    app.apply_key_change("greeting", KeyChange::Written);
+    assert!(!app.restore_cached_key("greeting"));
+
+    app.settings.key_cache_secs = 0;
+    app.cache_viewed_key("greeting", "string", -1);
+    assert!(app.key_cache.is_empty());
+}
+
+#[test]
+fn keyspace_events_update_loaded_levels() {
+    use crate::app::app_live_updates::KeyChange;
+
+    let mut app = empty_app();
+    app.insert_scanned_keys("", &["users:1".to_string(), "config".to_string()]);
+    app.loaded_prefixes.insert(String::new());
+
+    app.apply_key_change("session", KeyChange::Written);
+    app.apply_key_change("config", KeyChange::Removed);
+    app.update_visible_keys();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![("users/".to_string(), true), ("session".to_string(), false)]
+    );
+
+    // An unloaded folder only counts keys announced by the `new` event
+    app.apply_key_change("users:2", KeyChange::Written);
+    assert_eq!(app.folder_key_count("users"), 1);
+    app.apply_key_change("users:2", KeyChange::Created);
+    assert_eq!(app.folder_key_count("users"), 2);
+
+    app.apply_key_change("users:1", KeyChange::Removed);
+    app.apply_key_change("users:2", KeyChange::Removed);
+    app.update_visible_keys();
+    assert_eq!(app.visible_keys_in_current_view, vec![("session".to_string(), false)]);
+}
+
+#[test]
+fn reveal_key_defers_selection_until_level_loads() {
+    let mut app = empty_app();
+    app.insert_scanned_keys("", &["users:7".to_string(), "config".to_string()]);
+    app.loaded_prefixes.insert(String::new());
+
+    app.reveal_key_in_tree(&["config".to_string()]);
+    assert!(app.current_breadcrumb.is_empty());
+    assert_eq!(app.selected_visible_key_index, 1);
+
+    app.reveal_key_in_tree(&["users".to_string(), "7".to_string()]);
+    assert_eq!(app.current_breadcrumb, vec!["users".to_string()]);
+    assert_eq!(app.pending_key_selection.as_deref(), Some("7"));
+}
+
+#[test]
+fn session_state_restores_level_and_selected_key() {
+    let mut app = empty_app();
+    app.insert_scanned_keys("", &["users:7".to_string(), "config".to_string()]);
+    app.insert_scanned_keys("users:", &["users:1".to_string(), "users:7".to_string()]);
+    app.loaded_prefixes.extend([String::new(), "users:".to_string()]);
+    app.reveal_key_in_tree(&["users".to_string(), "7".to_string()]);
+
+    let session = app.session_state();
+    assert_eq!(session.breadcrumb, vec!["users".to_string()]);
+    assert_eq!(session.selected_key.as_deref(), Some("users:7"));
+
+    app.navigate_to_key_tree_root();
+    app.apply_session(session);
+    assert_eq!(app.current_breadcrumb, vec!["users".to_string()]);
+    assert_eq!(
+        app.visible_keys_in_current_view[app.selected_visible_key_index],
+        ("7".to_string(), false)
+    );
+}
+
+#[test]
+fn multi_selection_resolves_full_names_and_folder_prefixes() {
+    let mut app = empty_app();
+    app.insert_scanned_keys("users:", &["users:7:name".to_string(), "users:1".to_string(), "users:2".to_string()]);
+    app.loaded_prefixes.extend([String::new(), "users:".to_string()]);
+    app.current_breadcrumb = vec!["users".to_string()];
+    app.update_visible_keys();
+    let index_of = |app: &App, name: &str| {
+        app.visible_keys_in_current_view.iter().position(|(entry, _)| entry == name).unwrap()
+    };
+    app.selected_indices.extend([index_of(&app, "2"), index_of(&app, "7/"), index_of(&app, "1")]);
+
+    let (keys, prefixes) = app.multi_selection_items();
+    assert_eq!(keys.len(), 2);
+    assert!(keys.contains(&"users:1".to_string()) && keys.contains(&"users:2".to_string()));
+    assert_eq!(prefixes, vec!["users:7:".to_string()]);
+}
+
+#[test]
+fn breadcrumb_cursor_jumps_to_selected_level() {
+    let mut app = empty_app();
+    app.insert_scanned_keys("", &["users:7:name".to_string(), "config".to_string()]);
+    app.loaded_prefixes.extend([String::new(), "users:".to_string(), "users:7:".to_string()]);
+    app.current_breadcrumb = vec!["users".to_string(), "7".to_string()];
+
+    app.move_breadcrumb_cursor(1);
+    assert_eq!(app.breadcrumb_cursor, Some(2));
+    app.move_breadcrumb_cursor(-1);
+    app.move_breadcrumb_cursor(-1);
+    app.move_breadcrumb_cursor(-1);
+    assert_eq!(app.breadcrumb_cursor, Some(0));
+
+    assert!(app.jump_to_breadcrumb_cursor());
+    assert!(app.current_breadcrumb.is_empty());
+    assert_eq!(app.breadcrumb_cursor, None);
+    // The folder the jump came out of stays selected
+    assert_eq!(
+        app.visible_keys_in_current_view[app.selected_visible_key_index],
+        ("users/".to_string(), true)
+    );
+    assert!(!app.jump_to_breadcrumb_cursor());
+}
+
+#[test]
+fn tree_view_expands_folders_in_place() {
+    let mut app = empty_app();
+    let keys = ["users:7".to_string(), "users:8".to_string(), "config".to_string()];
+    app.insert_scanned_keys("", &keys);
+    app.insert_scanned_keys("users:", &keys[..2]);
+    app.loaded_prefixes.extend([String::new(), "users:".to_string()]);
+    app.update_visible_keys();
+    app.toggle_tree_view();
+
+    let names = |app: &App| app.tree_rows().into_iter().map(|row| row.name).collect::<Vec<_>>();
+    assert_eq!(names(&app), ["users/", "config"]);
+
+    app.tree_expand();
+    assert_eq!(names(&app), ["users/", "7", "8", "config"]);
+    // The breadcrumb follows the selection into the opened folder
+    app.move_tree_selection(1);
+    app.move_tree_selection(1);
+    assert_eq!(app.current_breadcrumb, ["users"]);
+    assert_eq!(app.visible_keys_in_current_view[app.selected_visible_key_index].0, "8");
+    app.move_tree_selection(1);
+    assert!(app.current_breadcrumb.is_empty());
+    assert_eq!(app.visible_keys_in_current_view[app.selected_visible_key_index].0, "config");
+
+    app.move_tree_selection(-2);
+    app.tree_collapse();
+    assert!(app.current_breadcrumb.is_empty());
+    assert_eq!(app.tree_selected_row(&app.tree_rows()), 0);
+    app.tree_collapse();
+    assert_eq!(names(&app), ["users/", "config"]);
+}
+
+#[test]
+fn list_motions_jump_within_the_focused_list() {
+    let mut app = empty_app();
+    let keys: Vec<String> = (0..30).map(|n| format!("key{:02}", n)).collect();
+    app.insert_scanned_keys("", &keys);
+    app.update_visible_keys();
+    app.is_key_view_focused = true;
+    app.key_list_scroll.follow(0, keys.len(), 10);
+
+    app.select_list_bottom(None);
+    assert_eq!(app.selected_visible_key_index, 29);
+    app.select_list_bottom(Some(12));
+    assert_eq!(app.selected_visible_key_index, 11);
+    app.move_list_half_page(true);
+    assert_eq!(app.selected_visible_key_index, 16);
+    app.center_list_selection();
+    assert_eq!(app.key_list_scroll.offset, 11);
+    app.select_list_top();
+    assert_eq!(app.selected_visible_key_index, 0);
+}
+
+#[test]
+fn refreshed_level_merges_changes_and_keeps_selection() {
+    let mut app = empty_app();
+    app.insert_scanned_keys("", &["users:1".to_string(), "orders:1".to_string(), "config".to_string()]);
+    app.insert_scanned_keys("users:", &["users:1".to_string()]);
+    app.loaded_prefixes.extend([String::new(), "users:".to_string()]);
+    app.update_visible_keys();
+    app.selected_visible_key_index = app.leaf_index_in_view("config").unwrap();
+
+    let fresh = [
+        "users:1".to_string(),
+        "users:2".to_string(),
+        "config".to_string(),
+        "session".to_string(),
+        "config".to_string(),
+    ];
+    assert_eq!(app.merge_refreshed_level("", &fresh), (1, 1));
+    app.update_visible_keys_keeping_selection();
+    assert_eq!(
+        app.visible_keys_in_current_view,
+        vec![
+            ("users/".to_string(), true),
+            ("config".to_string(), false),
+            ("session".to_string(), false),
+        ]
+    );
+    assert_eq!(app.folder_key_count("users"), 2);
+    // The users level stays loaded beneath the surviving folder
+    assert!(app.loaded_prefixes.contains("users:"));
+    assert_eq!(app.selected_visible_key_index, 1);
+}
+
+#[test]
+fn failed_type_reads_leave_keys_unset_and_notify_once() {
+    use std::time::Duration;
+
+    let mut app = empty_app();
+    let keys = vec!["a".to_string(), "b".to_string()];
+    app.apply_key_types(keys.clone(), Err("timed out".to_string()), Duration::ZERO);
+    assert!(app.type_map.is_empty() && app.ttl_map.is_empty());
+    assert_eq!(app.key_type_failures.len(), 2);
+    let toast = app.notifications.toasts().last().unwrap();
+    assert!(toast.message.contains("timed out"));
+
+    app.apply_key_types(keys, Err("timed out".to_string()), Duration::ZERO);
+    assert_eq!(app.notifications.toasts().count(), 1);
+
+    let replies = vec![redis::Value::SimpleString("hash".to_string()), redis::Value::Int(-1)];
+    app.apply_key_types(vec!["a".to_string()], Ok(replies), Duration::ZERO);
+    assert_eq!(app.type_map.get("a").map(String::as_str), Some("hash"));
+    assert!(!app.key_type_failures.contains_key("a"));
+}
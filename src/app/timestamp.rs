@@ -0,0 +1,114 @@
+/// Epochs outside 2001-09-09..2100-01-01 are treated as ordinary numbers, so
+/// counters and IDs are not mistaken for dates.
+const MIN_EPOCH_SECS: i64 = 1_000_000_000;
+const MAX_EPOCH_SECS: i64 = 4_102_444_800;
+
+/// Interprets `text` as Unix seconds (10 digits) or milliseconds (13 digits).
+pub fn epoch_seconds(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let secs = match text.len() {
+        10 => text.parse::<i64>().ok()?,
+        13 => text.parse::<i64>().ok()? / 1000,
+        _ => return None,
+    };
+    (MIN_EPOCH_SECS..MAX_EPOCH_SECS).contains(&secs).then_some(secs)
+}
+
+/// Formats a Unix timestamp as an ISO-8601 style UTC datetime.
+pub fn format_utc(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let day_secs = secs.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        day_secs / 3600,
+        (day_secs % 3600) / 60,
+        day_secs % 60
+    )
+}
+
+/// Appends a readable datetime to `value` when it looks like an epoch.
+pub fn annotate(value: &str) -> String {
+    match epoch_seconds(value) {
+        Some(secs) => format!("{}  ({})", value, format_utc(secs)),
+        None => value.to_string(),
+    }
+}
+
+/// Stream IDs are `<millis>-<seq>`; annotate them by their millisecond part.
+pub fn annotate_stream_id(id: &str) -> String {
+    let millis = id.split('-').next().unwrap_or("");
+    match epoch_seconds(millis) {
+        Some(secs) => format!("{}  ({})", id, format_utc(secs)),
+        None => id.to_string(),
+    }
+}
+
+/// Formats Unix milliseconds as a UTC time of day, `HH:MM:SS.mmm`.
+pub fn format_clock(millis: i64) -> String {
+    let day_millis = millis.rem_euclid(86_400_000);
+    let secs = day_millis / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        day_millis % 1000
+    )
+}
+
+/// Current time as Unix milliseconds.
+pub fn now_millis() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as i64)
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotate_recognises_seconds_and_millis() {
+        assert_eq!(annotate("1700000000"), "1700000000  (2023-11-14 22:13:20 UTC)");
+        assert_eq!(annotate("1709251199999"), "1709251199999  (2024-02-29 23:59:59 UTC)");
+        assert_eq!(
+            annotate_stream_id("1700000000000-3"),
+            "1700000000000-3  (2023-11-14 22:13:20 UTC)"
+        );
+    }
+
+    #[test]
+    fn annotate_ignores_other_numbers() {
+        assert_eq!(annotate("42"), "42");
+        assert_eq!(annotate("0999999999"), "0999999999");
+        assert_eq!(annotate("17000000001"), "17000000001");
+        assert_eq!(annotate("hello"), "hello");
+    }
+
+    #[test]
+    fn clock_shows_utc_time_of_day_with_millis() {
+        assert_eq!(format_clock(1_700_000_000_123), "22:13:20.123");
+        assert_eq!(format_clock(0), "00:00:00.000");
+    }
+}
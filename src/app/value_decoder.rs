@@ -0,0 +1,160 @@
+use super::value_format;
+use serde_json::{Map, Number, Value as JsonValue};
+
+/// How raw string/hash bytes are interpreted before display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValueDecoder {
+    /// Inflate compressed payloads and decode only when the bytes are
+    /// unambiguously in a known encoding.
+    #[default]
+    Auto,
+    /// Never decompress or decode; show bytes as text or hex.
+    Raw,
+    /// Always try MessagePack, even for bytes that also look like text.
+    MessagePack,
+}
+
+impl ValueDecoder {
+    pub fn next(self) -> Self {
+        match self {
+            ValueDecoder::Auto => ValueDecoder::MessagePack,
+            ValueDecoder::MessagePack => ValueDecoder::Raw,
+            ValueDecoder::Raw => ValueDecoder::Auto,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ValueDecoder::Auto => "auto",
+            ValueDecoder::Raw => "raw",
+            ValueDecoder::MessagePack => "msgpack",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedValue {
+    pub label: &'static str,
+    pub json: JsonValue,
+}
+
+impl DecodedValue {
+    pub fn pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.json).unwrap_or_default()
+    }
+
+    pub fn compact(&self) -> String {
+        serde_json::to_string(&self.json).unwrap_or_default()
+    }
+}
+
+pub fn decode(bytes: &[u8], decoder: ValueDecoder) -> Option<DecodedValue> {
+    match decoder {
+        ValueDecoder::Raw => None,
+        ValueDecoder::MessagePack => decode_msgpack(bytes),
+        ValueDecoder::Auto if looks_like_msgpack_container(bytes) => decode_msgpack(bytes),
+        ValueDecoder::Auto => None,
+    }
+}
+
+/// Auto-detection is limited to maps and arrays: their marker bytes are never
+/// printable ASCII, whereas msgpack scalars collide with ordinary text.
+fn looks_like_msgpack_container(bytes: &[u8]) -> bool {
+    matches!(bytes.first(), Some(0x80..=0x9f | 0xdc..=0xdf))
+}
+
+fn decode_msgpack(bytes: &[u8]) -> Option<DecodedValue> {
+    let mut cursor = bytes;
+    let value = rmpv::decode::read_value(&mut cursor).ok()?;
+    if !cursor.is_empty() {
+        return None;
+    }
+    Some(DecodedValue {
+        label: "msgpack",
+        json: msgpack_to_json(value),
+    })
+}
+
+fn msgpack_to_json(value: rmpv::Value) -> JsonValue {
+    match value {
+        rmpv::Value::Nil => JsonValue::Null,
+        rmpv::Value::Boolean(b) => JsonValue::Bool(b),
+        rmpv::Value::Integer(i) => match (i.as_i64(), i.as_u64()) {
+            (Some(n), _) => JsonValue::from(n),
+            (None, Some(n)) => JsonValue::from(n),
+            _ => JsonValue::Null,
+        },
+        rmpv::Value::F32(f) => float_to_json(f as f64),
+        rmpv::Value::F64(f) => float_to_json(f),
+        rmpv::Value::String(s) => match s.into_str() {
+            Some(text) => JsonValue::String(text),
+            None => JsonValue::Null,
+        },
+        rmpv::Value::Binary(bytes) => JsonValue::String(value_format::format_bytes_inline(&bytes)),
+        rmpv::Value::Array(items) => JsonValue::Array(items.into_iter().map(msgpack_to_json).collect()),
+        rmpv::Value::Map(entries) => {
+            let mut map = Map::new();
+            for (key, value) in entries {
+                let key = match msgpack_to_json(key) {
+                    JsonValue::String(text) => text,
+                    other => other.to_string(),
+                };
+                map.insert(key, msgpack_to_json(value));
+            }
+            JsonValue::Object(map)
+        }
+        rmpv::Value::Ext(kind, bytes) => {
+            let mut map = Map::new();
+            map.insert("ext_type".to_string(), JsonValue::from(kind));
+            map.insert(
+                "data".to_string(),
+                JsonValue::String(value_format::format_bytes_inline(&bytes)),
+            );
+            JsonValue::Object(map)
+        }
+    }
+}
+
+fn float_to_json(f: f64) -> JsonValue {
+    Number::from_f64(f).map_or(JsonValue::Null, JsonValue::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(value: &rmpv::Value) -> Vec<u8> {
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, value).unwrap();
+        buf
+    }
+
+    #[test]
+    fn auto_decodes_msgpack_maps() {
+        let bytes = encode(&rmpv::Value::Map(vec![
+            (rmpv::Value::from("id"), rmpv::Value::from(42)),
+            (rmpv::Value::from(1), rmpv::Value::from(true)),
+        ]));
+        let decoded = decode(&bytes, ValueDecoder::Auto).expect("decoded");
+        assert_eq!(decoded.compact(), r#"{"1":true,"id":42}"#);
+    }
+
+    #[test]
+    fn auto_leaves_plain_text_alone() {
+        assert!(decode(b"hello", ValueDecoder::Auto).is_none());
+        assert!(decode(b"{\"a\":1}", ValueDecoder::Auto).is_none());
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut bytes = encode(&rmpv::Value::Array(vec![rmpv::Value::from(1)]));
+        bytes.push(0xC1);
+        assert!(decode(&bytes, ValueDecoder::MessagePack).is_none());
+    }
+
+    #[test]
+    fn raw_never_decodes() {
+        let bytes = encode(&rmpv::Value::Array(vec![]));
+        assert!(decode(&bytes, ValueDecoder::Raw).is_none());
+    }
+}
@@ -0,0 +1,322 @@
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use super::latency_tracker::OperationClass;
+use super::probabilistic::{info_fields, ProbabilisticKind, ProbabilisticView};
+use super::value_decoder::{self, ValueDecoder};
+use super::value_viewer::{StringChunk, ValueViewer};
+use super::{decompress, hyperloglog, value_format};
+use crate::config::Settings;
+
+/// Quantiles listed for a t-digest, from its minimum to its maximum.
+const TDIGEST_QUANTILES: [&str; 5] = ["0", "0.5", "0.9", "0.99", "1"];
+
+/// Settings a value fetch reads, copied from the app when the fetch is requested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FetchOptions {
+    pub decoder: ValueDecoder,
+    pub large_string_threshold: usize,
+    pub string_chunk_size: usize,
+    pub collection_page_size: usize,
+}
+
+impl FetchOptions {
+    pub fn new(settings: &Settings, decoder: ValueDecoder) -> Self {
+        Self {
+            decoder,
+            large_string_threshold: settings.large_string_threshold,
+            string_chunk_size: settings.string_chunk_size,
+            collection_page_size: settings.collection_page_size,
+        }
+    }
+}
+
+/// Why a key is fetched, which decides how the value lands in the value panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValuePurpose {
+    /// Enter, a goto or a search hit: the value replaces the panel.
+    Open,
+    /// The selection came to rest on the key.
+    Preview,
+    /// Reloaded in place, keeping the sort, filter and hex view.
+    Refresh,
+    /// A watch tick: like a refresh, also keeping the scroll and flagging changed rows.
+    Watch,
+    /// Loaded for the action menu, which opens once the value is in.
+    ActionMenu,
+}
+
+/// A key read into a value panel of its own, swapped into the app when it arrives.
+pub struct FetchedValue {
+    pub key: String,
+    pub viewer: ValueViewer,
+    /// `TYPE` reply; None when the metadata could not be read.
+    pub key_type: Option<String>,
+    pub ttl: i64,
+    pub timings: Vec<(OperationClass, Duration)>,
+}
+
+/// Reads `key` the way the value panel shows it: TTL, type, size and the value itself.
+pub async fn fetch_value(mut con: MultiplexedConnection, key: String, options: FetchOptions) -> FetchedValue {
+    let mut fetch = ValueFetch {
+        viewer: ValueViewer::default(),
+        options,
+        timings: Vec::new(),
+    };
+    fetch.viewer.active_leaf_key_name = Some(key.clone());
+    let (key_type, ttl) = fetch.fetch_value_for_key(&key, &mut con).await;
+    fetch.viewer.update_current_display_value();
+    FetchedValue {
+        key,
+        viewer: fetch.viewer,
+        key_type,
+        ttl,
+        timings: fetch.timings,
+    }
+}
+
+/// Reads the `chunk` window of a large string.
+pub async fn read_string_chunk(
+    con: &mut MultiplexedConnection,
+    key_name: &str,
+    chunk: StringChunk,
+) -> Result<Vec<u8>, String> {
+    redis::cmd("GETRANGE")
+        .arg(key_name)
+        .arg(chunk.offset)
+        .arg(chunk.end().saturating_sub(1))
+        .query_async::<Vec<u8>>(con)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// A value panel being filled in off the event loop, with the time each read took.
+pub(super) struct ValueFetch {
+    pub(super) viewer: ValueViewer,
+    pub(super) options: FetchOptions,
+    timings: Vec<(OperationClass, Duration)>,
+}
+
+impl ValueFetch {
+    pub(super) fn record(&mut self, class: OperationClass, elapsed: Duration) {
+        self.timings.push((class, elapsed));
+    }
+
+    /// Returns the `TYPE` and `TTL` replies.
+    async fn fetch_value_for_key(
+        &mut self,
+        full_key_name: &str,
+        con: &mut MultiplexedConnection,
+    ) -> (Option<String>, i64) {
+        // TTL and TYPE share one round trip
+        let started = Instant::now();
+        let metadata = redis::pipe()
+            .cmd("TTL")
+            .arg(full_key_name)
+            .cmd("TYPE")
+            .arg(full_key_name)
+            .query_async::<(i64, String)>(con)
+            .await;
+        self.record(OperationClass::Metadata, started.elapsed());
+        let (ttl, type_result) = match metadata {
+            Ok((ttl, key_type)) => (ttl, Ok(key_type)),
+            Err(e) => (-2, Err(e)),
+        };
+        self.viewer.ttl_fetched_at = (ttl >= 0).then(Instant::now);
+
+        let key_type = match type_result {
+            Ok(key_type) => key_type,
+            Err(e) => {
+                self.viewer.selected_key_type = Some("error".to_string());
+                self.viewer.selected_key_value = Some(format!(
+                    "Failed to TYPE key '{}': {}",
+                    full_key_name, e
+                ));
+                return (None, ttl);
+            }
+        };
+
+        let key_type_upper = key_type.to_uppercase();
+        self.viewer.selected_key_type = Some(key_type_upper.clone());
+        // MEMORY USAGE can be disabled or ACL-restricted; the size is simply omitted then
+        self.viewer.memory_usage = redis::cmd("MEMORY")
+            .arg("USAGE")
+            .arg(full_key_name)
+            .arg("SAMPLES")
+            .arg(0)
+            .query_async::<Option<u64>>(con)
+            .await
+            .ok()
+            .flatten();
+
+        match key_type_upper.as_str() {
+            "STRING" => self.fetch_string_value(full_key_name, con).await,
+            "NONE" => {
+                self.viewer.selected_key_value = Some("(nil)".to_string());
+            }
+            "HASH" => {
+                self.fetch_and_set_hash_value(full_key_name, con).await;
+            }
+            "ZSET" => {
+                self.fetch_and_set_zset_value(full_key_name, con).await;
+            }
+            "LIST" => {
+                self.fetch_and_set_list_value(full_key_name, con).await;
+            }
+            "SET" => {
+                self.fetch_and_set_set_value(full_key_name, con).await;
+            }
+            "STREAM" => {
+                self.fetch_and_set_stream_value(full_key_name, con).await;
+            }
+            "REJSON-RL" | "JSON" => {
+                self.fetch_and_set_json_value(full_key_name, con).await;
+            }
+            other => match ProbabilisticKind::from_type(other) {
+                Some(kind) => self.fetch_and_set_probabilistic_value(full_key_name, kind, con).await,
+                None => {
+                    self.viewer.selected_key_value = Some(format!(
+                        "Key is of type '{}'. Value view for this type not yet implemented.",
+                        key_type
+                    ));
+                }
+            },
+        }
+        (Some(key_type), ttl)
+    }
+
+    async fn fetch_string_value(&mut self, full_key_name: &str, con: &mut MultiplexedConnection) {
+        let started = Instant::now();
+        let threshold = self.options.large_string_threshold;
+        if threshold > 0 {
+            if let Ok(total_len) = redis::cmd("STRLEN")
+                .arg(full_key_name)
+                .query_async::<usize>(con)
+                .await
+            {
+                if total_len > threshold {
+                    let chunk = StringChunk {
+                        total_len,
+                        offset: 0,
+                        chunk_size: self.options.string_chunk_size.max(1),
+                    };
+                    let bytes = read_string_chunk(con, full_key_name, chunk).await;
+                    self.viewer.set_string_chunk(full_key_name, chunk, bytes);
+                    self.record(OperationClass::Get, started.elapsed());
+                    return;
+                }
+            }
+        }
+
+        let result = redis::cmd("GET")
+            .arg(full_key_name)
+            .query_async::<Option<Vec<u8>>>(con)
+            .await;
+        self.record(OperationClass::Get, started.elapsed());
+        match result {
+            Ok(Some(bytes)) => match hyperloglog::parse(&bytes).filter(|_| self.options.decoder != ValueDecoder::Raw) {
+                Some(header) => self.set_hyperloglog_value(full_key_name, header, bytes, con).await,
+                None => self.set_string_value(bytes),
+            },
+            Ok(None) => {
+                self.viewer.selected_key_value = Some("(nil)".to_string());
+            }
+            Err(e) => {
+                self.viewer.selected_key_type = Some("error".to_string());
+                self.viewer.selected_key_value = Some(format!(
+                    "Failed to GET key '{}': {}",
+                    full_key_name, e
+                ));
+            }
+        }
+    }
+
+    /// Stores a fetched string, inflating compressed payloads and decoding known
+    /// encodings for display while keeping the stored bytes in `raw_value`.
+    fn set_string_value(&mut self, bytes: Vec<u8>) {
+        let mut inflated = None;
+        if self.options.decoder != ValueDecoder::Raw {
+            if let Some(output) = decompress::decompress(&bytes) {
+                self.viewer.decompressed = Some(decompress::DecompressionInfo {
+                    compression: output.compression,
+                    original_len: bytes.len(),
+                    decompressed_len: output.bytes.len(),
+                    truncated: output.truncated,
+                });
+                inflated = Some(output.bytes);
+            }
+        }
+
+        let display_bytes = inflated.as_deref().unwrap_or(&bytes);
+        self.viewer.selected_key_value = match value_decoder::decode(display_bytes, self.options.decoder) {
+            Some(decoded) => {
+                self.viewer.decoded_as = Some(decoded.label.to_string());
+                Some(decoded.pretty())
+            }
+            None => Some(value_format::format_bytes_block(display_bytes)),
+        };
+        self.viewer.raw_value = Some(bytes);
+    }
+
+    /// Shows a HyperLogLog string as its `PFCOUNT` estimate and encoding instead of the
+    /// register bytes, which stay in `raw_value` for the hex view.
+    async fn set_hyperloglog_value(
+        &mut self,
+        full_key_name: &str,
+        header: hyperloglog::HllHeader,
+        bytes: Vec<u8>,
+        con: &mut MultiplexedConnection,
+    ) {
+        let count = redis::cmd("PFCOUNT")
+            .arg(full_key_name)
+            .query_async::<u64>(con)
+            .await
+            .map_err(|e| e.to_string());
+        self.viewer.selected_key_value = Some(hyperloglog::describe(&header, bytes.len(), count));
+        self.viewer.decoded_as = Some("HyperLogLog".to_string());
+        self.viewer.hyperloglog = Some(header);
+        self.viewer.raw_value = Some(bytes);
+    }
+
+    /// Shows a Bloom or Cuckoo filter, Top-K or t-digest key as its INFO fields, plus the
+    /// heavy hitters of a Top-K and a few quantiles of a t-digest.
+    async fn fetch_and_set_probabilistic_value(
+        &mut self,
+        full_key_name: &str,
+        kind: ProbabilisticKind,
+        con: &mut MultiplexedConnection,
+    ) {
+        let info = match redis::cmd(kind.info_command()).arg(full_key_name).query_async::<Value>(con).await {
+            Ok(info) => info,
+            Err(e) => {
+                self.viewer.selected_key_value =
+                    Some(format!("Failed to {} '{}': {}", kind.info_command(), full_key_name, e));
+                return;
+            }
+        };
+        let mut view = ProbabilisticView::new(kind, &info);
+        let details = match kind {
+            ProbabilisticKind::TopK => redis::cmd("TOPK.LIST")
+                .arg(full_key_name)
+                .arg("WITHCOUNT")
+                .query_async::<Value>(con)
+                .await
+                .map(|reply| info_fields(&reply)),
+            ProbabilisticKind::TDigest => redis::cmd("TDIGEST.QUANTILE")
+                .arg(full_key_name)
+                .arg(&TDIGEST_QUANTILES)
+                .query_async::<Vec<String>>(con)
+                .await
+                .map(|values| TDIGEST_QUANTILES.iter().map(|q| q.to_string()).zip(values).collect()),
+            ProbabilisticKind::Bloom | ProbabilisticKind::Cuckoo => Ok(Vec::new()),
+        };
+        match details {
+            Ok(details) => view.details = details,
+            Err(e) => tracing::warn!("Failed to read details of '{}': {}", full_key_name, e),
+        }
+        self.viewer.selected_key_value = Some(view.text());
+        self.viewer.probabilistic = Some(view);
+    }
+}
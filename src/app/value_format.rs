@@ -21,7 +21,25 @@ pub fn format_bytes_block(bytes: &[u8]) -> String {
         return text;
     }
 
-    hex_multiline(bytes)
+    hex_multiline(bytes, 0)
+}
+
+/// Formats one GETRANGE window of a larger string. Window edges may cut through
+/// a multi-byte UTF-8 sequence, so partial characters at either end are dropped
+/// before deciding between text and hex; hex offsets are absolute.
+pub fn format_bytes_chunk(bytes: &[u8], base_offset: usize) -> String {
+    if bytes.is_empty() {
+        return "(empty)".to_string();
+    }
+
+    let trimmed = trim_partial_utf8(bytes);
+    if !trimmed.is_empty() {
+        if let Some(text) = utf8_if_printable(trimmed) {
+            return text;
+        }
+    }
+
+    hex_multiline(bytes, base_offset)
 }
 
 pub fn format_json_pretty(raw: &str) -> String {
@@ -31,6 +49,11 @@ pub fn format_json_pretty(raw: &str) -> String {
     }
 }
 
+/// Whether `bytes` would be shown as text rather than hex.
+pub fn is_printable_text(bytes: &[u8]) -> bool {
+    utf8_if_printable(bytes).is_some()
+}
+
 fn utf8_if_printable(bytes: &[u8]) -> Option<String> {
     let text = std::str::from_utf8(bytes).ok()?;
 
@@ -41,6 +64,19 @@ fn utf8_if_printable(bytes: &[u8]) -> Option<String> {
     }
 }
 
+fn trim_partial_utf8(bytes: &[u8]) -> &[u8] {
+    let leading = bytes
+        .iter()
+        .take(3)
+        .take_while(|byte| (**byte & 0b1100_0000) == 0b1000_0000)
+        .count();
+    let bytes = &bytes[leading..];
+    match std::str::from_utf8(bytes) {
+        Err(err) if err.error_len().is_none() => &bytes[..err.valid_up_to()],
+        _ => bytes,
+    }
+}
+
 fn is_printable_char(ch: char) -> bool {
     if ch.is_control() {
         matches!(ch, '\n' | '\r' | '\t')
@@ -73,13 +109,13 @@ fn hex_inline(bytes: &[u8]) -> String {
     out
 }
 
-fn hex_multiline(bytes: &[u8]) -> String {
+fn hex_multiline(bytes: &[u8], base_offset: usize) -> String {
     const LINE_BYTES: usize = 16;
     let mut out = String::new();
     let total_lines = bytes.len().div_ceil(LINE_BYTES);
 
     for (line_index, chunk) in bytes.chunks(LINE_BYTES).enumerate() {
-        let offset = line_index * LINE_BYTES;
+        let offset = base_offset + line_index * LINE_BYTES;
         write!(out, "{:08X}: ", offset).ok();
         for (idx, byte) in chunk.iter().enumerate() {
             if idx > 0 {
@@ -117,6 +153,19 @@ mod tests {
         assert_eq!(format_bytes_block(input), "hi\nthere");
     }
 
+    #[test]
+    fn format_bytes_chunk_drops_split_characters() {
+        // "é" is 0xC3 0xA9; the window starts and ends mid-character.
+        let input = [0xA9, b'a', b'b', 0xC3];
+        assert_eq!(format_bytes_chunk(&input, 10), "ab");
+    }
+
+    #[test]
+    fn format_bytes_chunk_hex_uses_absolute_offsets() {
+        let input = [0x00, 0xFF];
+        assert_eq!(format_bytes_chunk(&input, 32), "00000020: 00 FF");
+    }
+
     #[test]
     fn format_json_pretty_formats_json() {
         let raw = r#"{"a":1,"b":[true,false]}"#;
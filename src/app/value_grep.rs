@@ -0,0 +1,239 @@
+use redis::aio::MultiplexedConnection;
+use redis::Script;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::background_job::BackgroundJob;
+
+/// Strings larger than this are skipped rather than transferred or searched.
+const MAX_STRING_BYTES: usize = 1024 * 1024;
+/// Only the first this many hash fields or list elements are searched per key.
+const MAX_ELEMENTS: usize = 1000;
+/// Characters of context kept on each side of a match in its snippet.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// Checks a batch of keys server-side so values never cross the wire; returns
+/// `[key, matched_text, ...]` for the keys whose contents contain `ARGV[1]`.
+const GREP_SCRIPT: &str = r#"
+local needle, max_bytes, max_elements = ARGV[1], tonumber(ARGV[2]), tonumber(ARGV[3])
+local out = {}
+local function has(text) return string.find(text, needle, 1, true) ~= nil end
+for _, key in ipairs(KEYS) do
+  local key_type = redis.call('TYPE', key)['ok']
+  local hit = nil
+  if key_type == 'string' and redis.call('STRLEN', key) <= max_bytes then
+    local value = redis.call('GET', key)
+    if value and has(value) then hit = value end
+  elseif key_type == 'hash' and redis.call('HLEN', key) <= max_elements then
+    local fields = redis.call('HGETALL', key)
+    for i = 1, #fields, 2 do
+      if has(fields[i]) or has(fields[i + 1]) then hit = fields[i] .. ': ' .. fields[i + 1]; break end
+    end
+  elseif key_type == 'list' then
+    for _, value in ipairs(redis.call('LRANGE', key, 0, max_elements - 1)) do
+      if has(value) then hit = value; break end
+    end
+  end
+  if hit then table.insert(out, key); table.insert(out, hit) end
+end
+return out
+"#;
+
+#[derive(Debug)]
+pub enum ValueGrepEvent {
+    Progress { scanned: usize, cursor: u64 },
+    Matches(Vec<(String, String)>),
+    Done,
+    Error(String),
+}
+
+/// Background search for keys whose string, hash or list contents contain a substring.
+///
+/// Each SCAN batch is checked with a Lua script; if scripting is unavailable the
+/// same checks run client-side. Dropping the grep cancels it.
+pub struct ValueGrep {
+    pub needle: String,
+    pub scanned: usize,
+    pub cursor: u64,
+    job: BackgroundJob<ValueGrepEvent>,
+}
+
+impl ValueGrep {
+    pub fn spawn(con: MultiplexedConnection, pattern: String, needle: String, count: usize) -> Self {
+        let job = BackgroundJob::spawn(|sender| grep_values(con, pattern, needle.clone(), count, sender));
+        Self {
+            needle,
+            scanned: 0,
+            cursor: 0,
+            job,
+        }
+    }
+
+    pub fn drain(&mut self) -> Vec<ValueGrepEvent> {
+        self.job.drain()
+    }
+}
+
+async fn grep_values(
+    mut con: MultiplexedConnection,
+    pattern: String,
+    needle: String,
+    count: usize,
+    sender: UnboundedSender<ValueGrepEvent>,
+) {
+    let script = Script::new(GREP_SCRIPT);
+    let mut use_script = true;
+    let mut cursor: u64 = 0;
+    let mut scanned = 0;
+    loop {
+        let (next_cursor, keys) = match redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(count)
+            .query_async::<(u64, Vec<String>)>(&mut con)
+            .await
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                let _ = sender.send(ValueGrepEvent::Error(e.to_string()));
+                return;
+            }
+        };
+        cursor = next_cursor;
+        scanned += keys.len();
+
+        let mut hits = Vec::new();
+        if use_script && !keys.is_empty() {
+            let mut invocation = script.prepare_invoke();
+            invocation.arg(&needle).arg(MAX_STRING_BYTES).arg(MAX_ELEMENTS);
+            for key in &keys {
+                invocation.key(key);
+            }
+            match invocation.invoke_async::<Vec<Vec<u8>>>(&mut con).await {
+                Ok(flat) => hits = pair_up(flat),
+                // Scripting disabled or denied: fall back to fetching values
+                Err(_) => use_script = false,
+            }
+        }
+        if !use_script {
+            for key in &keys {
+                if let Some(hit) = grep_key_client_side(&mut con, key, &needle).await {
+                    hits.push((key.clone(), hit));
+                }
+            }
+        }
+
+        let matches = hits
+            .into_iter()
+            .map(|(key, text)| (key, snippet(&text, &needle)))
+            .collect::<Vec<_>>();
+        if !matches.is_empty() && sender.send(ValueGrepEvent::Matches(matches)).is_err() {
+            return;
+        }
+        if sender.send(ValueGrepEvent::Progress { scanned, cursor }).is_err() {
+            return;
+        }
+        if cursor == 0 {
+            let _ = sender.send(ValueGrepEvent::Done);
+            return;
+        }
+    }
+}
+
+fn pair_up(flat: Vec<Vec<u8>>) -> Vec<(String, String)> {
+    let mut iter = flat
+        .into_iter()
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+    let mut pairs = Vec::new();
+    while let (Some(key), Some(text)) = (iter.next(), iter.next()) {
+        pairs.push((key, text));
+    }
+    pairs
+}
+
+/// Client-side equivalent of `GREP_SCRIPT` for one key; errors count as no match.
+async fn grep_key_client_side(
+    con: &mut MultiplexedConnection,
+    key: &str,
+    needle: &str,
+) -> Option<String> {
+    let key_type: String = redis::cmd("TYPE").arg(key).query_async(con).await.ok()?;
+    match key_type.as_str() {
+        "string" => {
+            let len: usize = redis::cmd("STRLEN").arg(key).query_async(con).await.ok()?;
+            if len > MAX_STRING_BYTES {
+                return None;
+            }
+            let bytes: Vec<u8> = redis::cmd("GET").arg(key).query_async(con).await.ok()?;
+            let text = String::from_utf8_lossy(&bytes);
+            text.contains(needle).then(|| text.into_owned())
+        }
+        "hash" => {
+            let len: usize = redis::cmd("HLEN").arg(key).query_async(con).await.ok()?;
+            if len > MAX_ELEMENTS {
+                return None;
+            }
+            let fields: Vec<(String, String)> =
+                redis::cmd("HGETALL").arg(key).query_async(con).await.ok()?;
+            fields
+                .into_iter()
+                .find(|(field, value)| field.contains(needle) || value.contains(needle))
+                .map(|(field, value)| format!("{}: {}", field, value))
+        }
+        "list" => {
+            let items: Vec<String> = redis::cmd("LRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(MAX_ELEMENTS - 1)
+                .query_async(con)
+                .await
+                .ok()?;
+            items.into_iter().find(|item| item.contains(needle))
+        }
+        _ => None,
+    }
+}
+
+/// Single-line excerpt of `text` around the first occurrence of `needle`.
+pub fn snippet(text: &str, needle: &str) -> String {
+    let flat = text.replace(['\n', '\r', '\t'], " ");
+    let Some(start) = flat.find(needle) else {
+        return flat.chars().take(SNIPPET_CONTEXT * 2).collect();
+    };
+    let before: Vec<char> = flat[..start].chars().collect();
+    let after: Vec<char> = flat[start + needle.len()..].chars().collect();
+    let head: String = before[before.len().saturating_sub(SNIPPET_CONTEXT)..].iter().collect();
+    let tail: String = after.iter().take(SNIPPET_CONTEXT).collect();
+    format!(
+        "{}{}{}{}{}",
+        if before.len() > SNIPPET_CONTEXT { "…" } else { "" },
+        head,
+        needle,
+        tail,
+        if after.len() > SNIPPET_CONTEXT { "…" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pair_up, snippet};
+
+    #[test]
+    fn snippet_trims_context_around_match() {
+        assert_eq!(snippet("contact: a@b.io", "a@b"), "contact: a@b.io");
+        let long = format!("{}needle{}", "x".repeat(40), "y".repeat(40));
+        let excerpt = snippet(&long, "needle");
+        assert_eq!(excerpt, format!("…{}needle{}…", "x".repeat(30), "y".repeat(30)));
+        assert_eq!(snippet("line\none", "one"), "line one");
+    }
+
+    #[test]
+    fn pair_up_groups_script_reply() {
+        let flat = vec![b"k1".to_vec(), b"v1".to_vec(), b"k2".to_vec(), b"v2".to_vec()];
+        assert_eq!(
+            pair_up(flat),
+            vec![("k1".to_string(), "v1".to_string()), ("k2".to_string(), "v2".to_string())]
+        );
+    }
+}
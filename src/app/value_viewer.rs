@@ -1,5 +1,109 @@
+use std::time::Instant;
+
+use crate::app::decompress::DecompressionInfo;
+use crate::app::hex_view::HexViewState;
+use crate::app::hyperloglog::HllHeader;
+use crate::app::probabilistic::ProbabilisticView;
+use crate::app::json_highlight::{self, JsonToken};
+use crate::app::timestamp;
+use crate::app::value_format;
 use crate::app::StreamEntry;
 
+/// Window into a string value that is too large to fetch in one GET.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StringChunk {
+    pub total_len: usize,
+    pub offset: usize,
+    pub chunk_size: usize,
+}
+
+impl StringChunk {
+    /// Exclusive end of the current window, clamped to the string length.
+    pub fn end(&self) -> usize {
+        (self.offset + self.chunk_size).min(self.total_len)
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.total_len.div_ceil(self.chunk_size.max(1))
+    }
+
+    pub fn chunk_index(&self) -> usize {
+        self.offset / self.chunk_size.max(1)
+    }
+
+    pub fn has_next(&self) -> bool {
+        self.end() < self.total_len
+    }
+
+    pub fn has_previous(&self) -> bool {
+        self.offset > 0
+    }
+}
+
+/// How much of a list, sorted set or stream longer than one page has been fetched; `n`
+/// fetches the next page.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollectionPage {
+    pub loaded: usize,
+    pub total: usize,
+    pub page_size: usize,
+}
+
+impl CollectionPage {
+    /// Paging state once `loaded` of `total` elements are in, or None when nothing is left
+    /// or paging is off.
+    pub fn after(loaded: usize, total: usize, page_size: usize) -> Option<Self> {
+        (page_size > 0 && loaded < total).then_some(Self { loaded, total, page_size })
+    }
+
+    /// Elements the next page fetches.
+    pub fn next_len(&self) -> usize {
+        self.page_size.min(self.total - self.loaded)
+    }
+}
+
+/// Inclusive end index for LRANGE/ZRANGE of a page of `page_size` elements from `start`;
+/// -1 (the whole collection) when paging is off.
+pub fn page_end(start: usize, page_size: usize) -> i64 {
+    match page_size {
+        0 => -1,
+        size => (start + size - 1) as i64,
+    }
+}
+
+/// Ordering applied to already-fetched hash fields or sorted-set members.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ValueSort {
+    /// Order returned by Redis (score ascending for sorted sets).
+    #[default]
+    Natural,
+    ByField,
+    ByValue,
+    ScoreDescending,
+}
+
+impl ValueSort {
+    /// Next ordering that makes sense for `key_type`; other types only have `Natural`.
+    pub fn next_for(self, key_type: &str) -> Self {
+        match (key_type, self) {
+            ("HASH", ValueSort::Natural) => ValueSort::ByField,
+            ("HASH", ValueSort::ByField) => ValueSort::ByValue,
+            ("ZSET", ValueSort::Natural) => ValueSort::ScoreDescending,
+            _ => ValueSort::Natural,
+        }
+    }
+
+    pub fn label_for(&self, key_type: &str) -> &'static str {
+        match self {
+            ValueSort::Natural if key_type == "ZSET" => "score asc",
+            ValueSort::Natural => "natural",
+            ValueSort::ByField => "field",
+            ValueSort::ByValue => "value",
+            ValueSort::ScoreDescending => "score desc",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct ValueViewer {
     pub active_leaf_key_name: Option<String>,
@@ -11,13 +115,183 @@ pub struct ValueViewer {
     pub selected_key_value_set: Option<Vec<String>>,
     pub selected_key_value_json: Option<String>,
     pub selected_key_value_stream: Option<Vec<StreamEntry>>,
+    /// Bytes reported by `MEMORY USAGE`, when the server allows the command.
+    pub memory_usage: Option<u64>,
+    /// When the TTL of the key was read, so the panel can count it down.
+    pub ttl_fetched_at: Option<Instant>,
+    pub string_chunk: Option<StringChunk>,
+    /// Set while a list, sorted set or stream is only partly loaded.
+    pub collection_page: Option<CollectionPage>,
+    pub raw_value: Option<Vec<u8>>,
+    pub decoded_as: Option<String>,
+    pub decompressed: Option<DecompressionInfo>,
+    pub hex_view: HexViewState,
     pub current_display_value: Option<String>,
+    pub highlighted_json: Option<Vec<Vec<JsonToken>>>,
     pub displayed_value_lines: Option<Vec<String>>,
     pub selected_value_sub_index: usize,
     pub value_view_scroll: (u16, u16),
+    /// View preference that survives key changes: long lines scroll horizontally instead of wrapping.
+    pub wrap_disabled: bool,
+    /// View preference that survives key changes: prefix rows with line numbers or element indices.
+    pub line_numbers: bool,
+    pub goto_line_input: Option<String>,
+    /// Header of the value when it is a HyperLogLog.
+    pub hyperloglog: Option<HllHeader>,
+    /// Elements being typed for `PFADD`.
+    pub pfadd_input: Option<String>,
+    pub pfadd_cursor: usize,
+    /// INFO and last check of a Bloom, Cuckoo, Top-K or t-digest value.
+    pub probabilistic: Option<ProbabilisticView>,
+    /// Inputs being typed for the existence check of a RedisBloom value.
+    pub probe_input: Option<String>,
+    pub probe_cursor: usize,
+    pub sort: ValueSort,
+    /// Case-insensitive substring that collection rows must contain to be shown.
+    pub line_filter: String,
+    pub filter_input_active: bool,
+    /// View preference that survives key changes: skip the datetime shown next to epoch values.
+    pub timestamps_hidden: bool,
 }
 
 impl ValueViewer {
+    /// Absolute offset of the first byte in `raw_value`.
+    pub fn raw_base_offset(&self) -> usize {
+        self.string_chunk.map_or(0, |chunk| chunk.offset)
+    }
+
+    pub fn toggle_wrap(&mut self) {
+        self.wrap_disabled = !self.wrap_disabled;
+        self.value_view_scroll.1 = 0;
+    }
+
+    /// Scrolls the value panel; horizontal movement only applies when wrapping is off.
+    pub fn scroll(&mut self, rows: i32, columns: i32) {
+        let (y, x) = self.value_view_scroll;
+        let y = (y as i32 + rows).clamp(0, u16::MAX as i32) as u16;
+        let x = if self.wrap_disabled {
+            (x as i32 + columns).clamp(0, u16::MAX as i32) as u16
+        } else {
+            0
+        };
+        self.value_view_scroll = (y, x);
+    }
+
+    pub fn toggle_line_numbers(&mut self) {
+        self.line_numbers = !self.line_numbers;
+    }
+
+    /// Rendered rows of the current value, as used for line numbers and change tracking.
+    pub fn rows(&self) -> Vec<String> {
+        match (&self.displayed_value_lines, &self.current_display_value) {
+            (Some(lines), _) => lines.clone(),
+            (None, Some(text)) => text.lines().map(str::to_string).collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Number of rows the value panel can navigate, for list and text views alike.
+    pub fn line_count(&self) -> usize {
+        match (&self.displayed_value_lines, &self.current_display_value) {
+            (Some(lines), _) => lines.len(),
+            (None, Some(text)) => text.lines().count(),
+            (None, None) => 0,
+        }
+    }
+
+    /// Gutter label for `row`: the Redis element index for lists and sorted
+    /// sets (matching LRANGE/ZRANGE), otherwise a 1-based line number.
+    pub fn line_label(&self, row: usize) -> String {
+        if self.uses_element_index() {
+            row.to_string()
+        } else {
+            (row + 1).to_string()
+        }
+    }
+
+    fn uses_element_index(&self) -> bool {
+        self.displayed_value_lines.is_some()
+            && self.line_filter.is_empty()
+            && self.sort == ValueSort::Natural
+            && matches!(
+                self.selected_key_type.as_deref().map(str::to_uppercase).as_deref(),
+                Some("LIST") | Some("ZSET")
+            )
+    }
+
+    pub fn line_number_width(&self) -> usize {
+        self.line_label(self.line_count().saturating_sub(1)).len()
+    }
+
+    /// Moves to a 1-based line, or to an element index for lists and sorted sets.
+    pub fn goto_line(&mut self, target: usize) {
+        let count = self.line_count();
+        if count == 0 {
+            return;
+        }
+        let row = if self.uses_element_index() { target } else { target.saturating_sub(1) };
+        let row = row.min(count - 1);
+        if self.displayed_value_lines.is_some() {
+            self.selected_value_sub_index = row;
+        } else {
+            self.value_view_scroll.0 = row.min(u16::MAX as usize) as u16;
+        }
+    }
+
+    /// Whether the current value renders as rows that can be sorted or filtered.
+    pub fn has_collection_rows(&self) -> bool {
+        self.selected_key_value_hash.is_some()
+            || self.selected_key_value_zset.is_some()
+            || self.selected_key_value_list.is_some()
+            || self.selected_key_value_set.is_some()
+            || self.selected_key_value_stream.is_some()
+    }
+
+    pub fn cycle_sort(&mut self) {
+        let key_type = self.selected_key_type.as_deref().unwrap_or("").to_uppercase();
+        self.sort = self.sort.next_for(&key_type);
+        self.update_current_display_value();
+    }
+
+    pub fn toggle_timestamps(&mut self) {
+        self.timestamps_hidden = !self.timestamps_hidden;
+        self.update_current_display_value();
+    }
+
+    pub fn open_filter_prompt(&mut self) {
+        self.filter_input_active = true;
+    }
+
+    /// Applies an edited filter immediately so the rows narrow while typing.
+    pub fn set_line_filter(&mut self, filter: String) {
+        self.line_filter = filter;
+        self.update_current_display_value();
+    }
+
+    pub fn toggle_hex_view(&mut self) -> bool {
+        if self.raw_value.is_none() {
+            return false;
+        }
+        self.hex_view.toggle();
+        true
+    }
+
+    /// Shows the `chunk` window of a large string, or why it could not be read.
+    pub fn set_string_chunk(&mut self, key_name: &str, chunk: StringChunk, bytes: Result<Vec<u8>, String>) {
+        match bytes {
+            Ok(bytes) => {
+                self.string_chunk = Some(chunk);
+                self.selected_key_value = Some(value_format::format_bytes_chunk(&bytes, chunk.offset));
+                self.raw_value = Some(bytes);
+            }
+            Err(e) => {
+                self.string_chunk = None;
+                self.raw_value = None;
+                self.selected_key_value = Some(format!("Failed to GETRANGE for '{}' (string): {}", key_name, e));
+            }
+        }
+    }
+
     pub fn clear(&mut self) {
         self.active_leaf_key_name = None;
         self.selected_key_type = None;
@@ -28,10 +302,29 @@ impl ValueViewer {
         self.selected_key_value_set = None;
         self.selected_key_value_json = None;
         self.selected_key_value_stream = None;
+        self.memory_usage = None;
+        self.ttl_fetched_at = None;
+        self.string_chunk = None;
+        self.collection_page = None;
+        self.raw_value = None;
+        self.decoded_as = None;
+        self.decompressed = None;
+        self.hex_view = HexViewState::default();
         self.current_display_value = None;
+        self.highlighted_json = None;
         self.displayed_value_lines = None;
         self.selected_value_sub_index = 0;
         self.value_view_scroll = (0, 0);
+        self.goto_line_input = None;
+        self.hyperloglog = None;
+        self.pfadd_input = None;
+        self.pfadd_cursor = 0;
+        self.probabilistic = None;
+        self.probe_input = None;
+        self.probe_cursor = 0;
+        self.sort = ValueSort::Natural;
+        self.line_filter.clear();
+        self.filter_input_active = false;
     }
 
     pub fn update_current_display_value(&mut self) {
@@ -39,6 +332,15 @@ impl ValueViewer {
         self.displayed_value_lines = None;
         self.selected_value_sub_index = 0;
         self.value_view_scroll = (0, 0);
+        self.hex_view.reset();
+        let humanize = !self.timestamps_hidden;
+        let with_time = |value: &str| {
+            if humanize {
+                timestamp::annotate(value)
+            } else {
+                value.to_string()
+            }
+        };
 
         match self
             .selected_key_type
@@ -51,10 +353,16 @@ impl ValueViewer {
                     if hash_data.is_empty() {
                         self.current_display_value = Some("(empty hash)".to_string());
                     } else {
+                        let mut sorted_hash_data: Vec<&(String, String)> = hash_data.iter().collect();
+                        match self.sort {
+                            ValueSort::ByField => sorted_hash_data.sort_by(|a, b| a.0.cmp(&b.0)),
+                            ValueSort::ByValue => sorted_hash_data.sort_by(|a, b| a.1.cmp(&b.1)),
+                            _ => {}
+                        }
                         self.displayed_value_lines = Some(
-                            hash_data
+                            sorted_hash_data
                                 .iter()
-                                .map(|(k, v)| format!("{}: {}", k, v))
+                                .map(|(k, v)| format!("{}: {}", k, with_time(v)))
                                 .collect::<Vec<String>>(),
                         );
                     }
@@ -67,11 +375,15 @@ impl ValueViewer {
                     if zset_data.is_empty() {
                         self.current_display_value = Some("(empty zset)".to_string());
                     } else {
+                        let mut sorted_zset_data: Vec<&(String, f64)> = zset_data.iter().collect();
+                        if self.sort == ValueSort::ScoreDescending {
+                            sorted_zset_data.reverse();
+                        }
                         self.displayed_value_lines = Some(
-                            zset_data
+                            sorted_zset_data
                                 .iter()
                                 .map(|(member, score)| {
-                                    format!("Score: {} - Member: {}", score, member)
+                                    format!("Score: {} - Member: {}", with_time(&score.to_string()), member)
                                 })
                                 .collect::<Vec<String>>(),
                         );
@@ -89,7 +401,7 @@ impl ValueViewer {
                             list_data
                                 .iter()
                                 .enumerate()
-                                .map(|(idx, val)| format!("{}: {}", idx, val))
+                                .map(|(idx, val)| format!("{}: {}", idx, with_time(val)))
                                 .collect::<Vec<String>>(),
                         );
                     }
@@ -107,7 +419,7 @@ impl ValueViewer {
                         self.displayed_value_lines = Some(
                             sorted_set_data
                                 .iter()
-                                .map(|val| format!("- {}", val))
+                                .map(|val| format!("- {}", with_time(val)))
                                 .collect::<Vec<String>>(),
                         );
                     }
@@ -124,12 +436,17 @@ impl ValueViewer {
                     } else {
                         let mut lines: Vec<String> = Vec::new();
                         for entry in stream_entries {
-                            lines.push(format!("ID: {}", entry.id));
+                            let id = if humanize {
+                                timestamp::annotate_stream_id(&entry.id)
+                            } else {
+                                entry.id.clone()
+                            };
+                            lines.push(format!("ID: {}", id));
                             if entry.fields.is_empty() {
                                 lines.push("  (no fields)".to_string());
                             } else {
                                 for (field, value) in &entry.fields {
-                                    lines.push(format!("  {}: {}", field, value));
+                                    lines.push(format!("  {}: {}", field, with_time(value)));
                                 }
                             }
                             lines.push("---".to_string());
@@ -146,7 +463,19 @@ impl ValueViewer {
             Some("REJSON-RL") | Some("JSON") => {
                 self.current_display_value = self.selected_key_value_json.clone();
             }
-            _ => self.current_display_value = self.selected_key_value.clone(),
+            _ => self.current_display_value = self.selected_key_value.as_deref().map(with_time),
         }
+
+        if !self.line_filter.is_empty() {
+            let needle = self.line_filter.to_lowercase();
+            if let Some(lines) = &mut self.displayed_value_lines {
+                lines.retain(|line| line.to_lowercase().contains(&needle));
+            }
+        }
+
+        self.highlighted_json = match (&self.string_chunk, &self.current_display_value) {
+            (None, Some(text)) => json_highlight::highlight_json(text),
+            _ => None,
+        };
     }
 }
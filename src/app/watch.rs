@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+/// Periodic re-fetch of the viewed key, with the rows that changed on the last tick.
+#[derive(Debug, Default, Clone)]
+pub struct WatchState {
+    pub enabled: bool,
+    /// Key the last refresh was taken from, so switching keys does not flag every row.
+    pub key: Option<String>,
+    pub last_refresh: Option<Instant>,
+    pub changed_rows: HashSet<usize>,
+}
+
+impl WatchState {
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.key = None;
+        self.last_refresh = None;
+        self.changed_rows.clear();
+    }
+
+    pub fn is_due(&self, interval: Duration) -> bool {
+        self.enabled && self.last_refresh.is_none_or(|at| at.elapsed() >= interval)
+    }
+
+    /// Records a completed refresh and remembers which rows differ from `previous`.
+    pub fn record_refresh(&mut self, previous: &[String], current: &[String]) {
+        self.last_refresh = Some(Instant::now());
+        self.changed_rows = changed_rows(previous, current);
+    }
+}
+
+/// Rows of `current` that are new or differ from the row at the same position in `previous`.
+pub fn changed_rows(previous: &[String], current: &[String]) -> HashSet<usize> {
+    current
+        .iter()
+        .enumerate()
+        .filter(|(idx, row)| previous.get(*idx) != Some(*row))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn changed_rows_flags_edits_and_additions() {
+        let changed = changed_rows(&rows(&["a", "b"]), &rows(&["a", "B", "c"]));
+        assert_eq!(changed, HashSet::from([1, 2]));
+        assert!(changed_rows(&rows(&["x"]), &rows(&["x"])).is_empty());
+    }
+
+    #[test]
+    fn is_due_respects_interval() {
+        let mut watch = WatchState::default();
+        assert!(!watch.is_due(Duration::ZERO));
+        watch.toggle();
+        assert!(watch.is_due(Duration::from_secs(60)));
+        watch.record_refresh(&[], &[]);
+        assert!(!watch.is_due(Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,768 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::app_connect::{self, OpenedConnection};
+use super::app_consumer_groups::{self, GroupCommand, GroupsRead};
+use super::app_fetch::{self, MoreRows};
+use super::app_functions::{self, FunctionCall};
+use super::app_paste::{self, PastedKey};
+use super::app_redisearch::{self, SearchQuery};
+use super::key_sampler::KeySample;
+use super::notifications::Severity;
+use super::probabilistic::ProbabilisticKind;
+use super::state_action_menu::KeyAction;
+use super::state_compare::CompareDetail;
+use super::state_config_editor::ConfigParam;
+use super::state_functions::FunctionLibrary;
+use super::state_key_info::KeyInfo;
+use super::state_redisearch::{IndexInfo, SearchResults};
+use super::state_script_runner::ScriptMode;
+use super::value_fetch::{self, FetchOptions, FetchedValue, ValuePurpose};
+use super::value_viewer::{CollectionPage, StringChunk};
+use super::{
+    app_action_menu, app_clipboard, app_compare, app_config_editor, app_delete, app_doctor, app_goto_key,
+    app_hyperloglog, app_key_info, app_live_updates, app_probabilistic, app_random_sample, app_script_runner,
+};
+use crate::command::{self, CommandOutcome};
+use crate::config::ConnectionProfile;
+use crate::session::SessionState;
+
+/// Work the UI hands to the Redis worker instead of awaiting it in the event loop. Each
+/// request carries the connection it runs on.
+pub enum WorkerRequest {
+    /// `INFO` for the stats panel.
+    FetchStats(MultiplexedConnection),
+    /// Pipelined `TYPE` and `TTL` of visible keys.
+    FetchKeyTypes {
+        con: MultiplexedConnection,
+        keys: Vec<String>,
+    },
+    /// Pipelined `MEMORY USAGE` of visible keys.
+    FetchKeySizes {
+        con: MultiplexedConnection,
+        keys: Vec<String>,
+    },
+    /// Opens a connection to `profile`; the session is handed back to restore once connected.
+    Connect {
+        profile: ConnectionProfile,
+        profile_index: usize,
+        use_profile_db: bool,
+        db_override: Option<usize>,
+        db_index: usize,
+        session: Option<SessionState>,
+    },
+    FetchValue {
+        con: MultiplexedConnection,
+        key: String,
+        options: FetchOptions,
+        purpose: ValuePurpose,
+    },
+    LoadMoreValues {
+        con: MultiplexedConnection,
+        key: String,
+        key_type: String,
+        page: CollectionPage,
+        oldest: Option<String>,
+    },
+    FetchStringChunk {
+        con: MultiplexedConnection,
+        key: String,
+        chunk: StringChunk,
+    },
+    Probe {
+        con: MultiplexedConnection,
+        key: String,
+        kind: ProbabilisticKind,
+        inputs: Vec<String>,
+    },
+    PfAdd {
+        con: MultiplexedConnection,
+        key: String,
+        elements: Vec<String>,
+    },
+    GotoKey {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    /// Puts `text` on the clipboard; `message` is the toast once it is there.
+    CopyText {
+        text: String,
+        message: String,
+    },
+    CopyKeyJson {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    CopyKeyCommand {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    /// A command typed into the action menu prompt.
+    KeyCommand {
+        con: MultiplexedConnection,
+        cmd: redis::Cmd,
+        key: String,
+        action: KeyAction,
+        label: String,
+        input: String,
+    },
+    SaveValue {
+        con: MultiplexedConnection,
+        key: String,
+        command: &'static str,
+        path: PathBuf,
+    },
+    ExportKey {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    DeleteKey {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    CheckKeyspaceNotifications(MultiplexedConnection),
+    ReadPaste(MultiplexedConnection),
+    ApplyPaste {
+        con: MultiplexedConnection,
+        pipe: redis::Pipeline,
+        key: String,
+        overwrites: bool,
+    },
+    RunScript {
+        con: MultiplexedConnection,
+        script: String,
+        mode: ScriptMode,
+        keys: Vec<String>,
+        args: Vec<String>,
+    },
+    FetchDoctorReport(MultiplexedConnection),
+    FetchStatsReport(MultiplexedConnection),
+    SampleRandomKeys {
+        con: MultiplexedConnection,
+        size: usize,
+    },
+    FetchKeyInfo {
+        con: MultiplexedConnection,
+        key: String,
+    },
+    FetchConfig(MultiplexedConnection),
+    SetConfig {
+        con: MultiplexedConnection,
+        name: String,
+        value: String,
+    },
+    RewriteConfig(MultiplexedConnection),
+    FetchFunctions(MultiplexedConnection),
+    RunFunction {
+        con: MultiplexedConnection,
+        call: FunctionCall,
+    },
+    FetchSearchIndexes(MultiplexedConnection),
+    FetchSearchIndexInfo {
+        con: MultiplexedConnection,
+        index: String,
+    },
+    RunSearchQuery {
+        con: MultiplexedConnection,
+        query: SearchQuery,
+    },
+    /// Connects to the other profile of a compare; `left` is handed back with the result.
+    StartCompare {
+        left: MultiplexedConnection,
+        profile: ConnectionProfile,
+    },
+    FetchCompareDetail {
+        left: MultiplexedConnection,
+        right: MultiplexedConnection,
+        key: String,
+    },
+    FetchConsumerGroups {
+        con: MultiplexedConnection,
+        key: String,
+        selected: usize,
+    },
+    GroupCommand {
+        con: MultiplexedConnection,
+        key: String,
+        group: String,
+        command: GroupCommand,
+    },
+    /// Input typed at the command prompt.
+    RunCommand {
+        con: MultiplexedConnection,
+        input: String,
+    },
+}
+
+impl WorkerRequest {
+    fn job(&self) -> WorkerJob {
+        match self {
+            WorkerRequest::FetchStats(_) => WorkerJob::Stats,
+            WorkerRequest::FetchKeyTypes { .. } => WorkerJob::KeyTypes,
+            WorkerRequest::FetchKeySizes { .. } => WorkerJob::KeySizes,
+            WorkerRequest::Connect { .. } => WorkerJob::Connect,
+            WorkerRequest::FetchValue { .. }
+            | WorkerRequest::LoadMoreValues { .. }
+            | WorkerRequest::FetchStringChunk { .. } => WorkerJob::Value,
+            WorkerRequest::GotoKey { .. } => WorkerJob::Goto,
+            WorkerRequest::ReadPaste(_) => WorkerJob::Paste,
+            WorkerRequest::FetchDoctorReport(_) => WorkerJob::Doctor,
+            WorkerRequest::FetchStatsReport(_) => WorkerJob::StatsReport,
+            WorkerRequest::SampleRandomKeys { .. } => WorkerJob::RandomSample,
+            WorkerRequest::FetchKeyInfo { .. } => WorkerJob::KeyInfo,
+            WorkerRequest::FetchConfig(_) => WorkerJob::Config,
+            WorkerRequest::FetchFunctions(_) => WorkerJob::Functions,
+            WorkerRequest::FetchSearchIndexes(_) | WorkerRequest::FetchSearchIndexInfo { .. } => {
+                WorkerJob::SearchIndexes
+            }
+            WorkerRequest::RunSearchQuery { .. } => WorkerJob::SearchQuery,
+            WorkerRequest::StartCompare { .. } => WorkerJob::Compare,
+            WorkerRequest::FetchCompareDetail { .. } => WorkerJob::CompareDetail,
+            WorkerRequest::FetchConsumerGroups { .. } => WorkerJob::ConsumerGroups,
+            WorkerRequest::Probe { .. }
+            | WorkerRequest::PfAdd { .. }
+            | WorkerRequest::CopyText { .. }
+            | WorkerRequest::CopyKeyJson { .. }
+            | WorkerRequest::CopyKeyCommand { .. }
+            | WorkerRequest::KeyCommand { .. }
+            | WorkerRequest::SaveValue { .. }
+            | WorkerRequest::ExportKey { .. }
+            | WorkerRequest::DeleteKey { .. }
+            | WorkerRequest::CheckKeyspaceNotifications(_)
+            | WorkerRequest::ApplyPaste { .. }
+            | WorkerRequest::RunScript { .. }
+            | WorkerRequest::SetConfig { .. }
+            | WorkerRequest::RewriteConfig(_)
+            | WorkerRequest::RunFunction { .. }
+            | WorkerRequest::GroupCommand { .. }
+            | WorkerRequest::RunCommand { .. } => WorkerJob::Action,
+        }
+    }
+}
+
+/// Kinds of request. A newer request of a kind supersedes the older ones still in
+/// flight, whose results are dropped, except for actions, whose results all apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkerJob {
+    Stats,
+    KeyTypes,
+    KeySizes,
+    Connect,
+    /// Whatever fills the value panel: a key, its next page or another chunk.
+    Value,
+    Goto,
+    /// Reading the clipboard for the paste preview.
+    Paste,
+    Doctor,
+    StatsReport,
+    RandomSample,
+    KeyInfo,
+    Config,
+    Functions,
+    /// The index list of the RediSearch panel and the FT.INFO of the selected index.
+    SearchIndexes,
+    SearchQuery,
+    Compare,
+    CompareDetail,
+    ConsumerGroups,
+    /// Commands run for the user, such as writes, copies and checks.
+    Action,
+}
+
+impl WorkerJob {
+    fn latest_only(self) -> bool {
+        self != WorkerJob::Action
+    }
+}
+
+/// A finished request, applied to the app between frames.
+pub enum AppEvent {
+    Stats {
+        info: Result<String, String>,
+        elapsed: Duration,
+    },
+    KeyTypes {
+        keys: Vec<String>,
+        /// `TYPE` and `TTL` reply of each key in turn.
+        replies: Result<Vec<Value>, String>,
+        elapsed: Duration,
+    },
+    KeySizes {
+        keys: Vec<String>,
+        sizes: Vec<Option<u64>>,
+    },
+    Connected {
+        profile_index: usize,
+        session: Option<SessionState>,
+        result: Result<Box<OpenedConnection>, String>,
+    },
+    Value {
+        purpose: ValuePurpose,
+        fetched: Box<FetchedValue>,
+    },
+    MoreValues {
+        key: String,
+        page: CollectionPage,
+        rows: Result<MoreRows, String>,
+        elapsed: Duration,
+    },
+    StringChunk {
+        key: String,
+        chunk: StringChunk,
+        bytes: Result<Vec<u8>, String>,
+    },
+    Probed {
+        key: String,
+        kind: ProbabilisticKind,
+        inputs: Vec<String>,
+        reply: Result<Value, String>,
+    },
+    PfAdded {
+        key: String,
+        count: usize,
+        changed: Result<i64, String>,
+    },
+    /// The type of a key to go to; None when it does not exist.
+    GotoKey {
+        key: String,
+        found: Result<Option<String>, String>,
+    },
+    /// Outcome of work that only reports back, such as a copy or an export.
+    Toast(Severity, String),
+    KeyCommand {
+        key: String,
+        action: KeyAction,
+        label: String,
+        input: String,
+        reply: Result<Value, String>,
+    },
+    /// Bytes written to `path`, or the error.
+    SavedValue {
+        key: String,
+        path: PathBuf,
+        saved: Result<usize, String>,
+    },
+    DeletedKey {
+        key: String,
+        result: Result<String, String>,
+    },
+    PasteRead(Result<PastedKey, String>),
+    Pasted {
+        key: String,
+        overwrites: bool,
+        result: Result<(), String>,
+    },
+    ScriptRan {
+        result: String,
+        sha: Option<String>,
+    },
+    DoctorReport(Vec<String>),
+    StatsReport {
+        info: Result<String, String>,
+        elapsed: Duration,
+    },
+    RandomSample(Result<Vec<KeySample>, String>),
+    KeyInfo(Box<KeyInfo>),
+    Config(Result<Vec<ConfigParam>, String>),
+    ConfigSet {
+        name: String,
+        value: String,
+        result: Result<(), String>,
+    },
+    ConfigRewritten(Result<(), String>),
+    Functions(Result<Vec<FunctionLibrary>, String>),
+    /// What a function call or load returned, and whether a library was loaded.
+    FunctionRan {
+        result: String,
+        loaded: bool,
+    },
+    SearchIndexes(Result<Vec<String>, String>),
+    SearchIndexInfo {
+        index: String,
+        info: Result<IndexInfo, String>,
+    },
+    SearchResults {
+        query: SearchQuery,
+        results: Result<SearchResults, String>,
+    },
+    CompareConnected {
+        left: MultiplexedConnection,
+        profile: Box<ConnectionProfile>,
+        right: Result<MultiplexedConnection, String>,
+    },
+    CompareDetail(Box<CompareDetail>),
+    ConsumerGroups {
+        key: String,
+        read: Result<GroupsRead, String>,
+    },
+    /// The status line of an ack or claim, and whether it used the marked entries.
+    GroupCommandRan {
+        key: String,
+        status: String,
+        clears_marks: bool,
+    },
+    CommandRan(CommandOutcome),
+}
+
+type Ticketed<T> = (WorkerJob, u64, T);
+
+/// Redis worker of one connection attempt: requests go in over a channel, each runs on
+/// its own task, and their results come back as `AppEvent`s, so the UI never waits on
+/// them. Dropping the worker cancels whatever is in flight.
+pub struct Worker {
+    requests: UnboundedSender<Ticketed<WorkerRequest>>,
+    events: UnboundedReceiver<Ticketed<AppEvent>>,
+    /// Ticket of the latest request of each kind still in flight.
+    in_flight: HashMap<WorkerJob, u64>,
+    next_ticket: u64,
+    handle: JoinHandle<()>,
+}
+
+impl Worker {
+    pub fn spawn() -> Self {
+        let (requests, request_receiver) = mpsc::unbounded_channel();
+        let (event_sender, events) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(run(request_receiver, event_sender));
+        Self {
+            requests,
+            events,
+            in_flight: HashMap::new(),
+            next_ticket: 0,
+            handle,
+        }
+    }
+
+    pub fn is_busy(&self, job: WorkerJob) -> bool {
+        self.in_flight.contains_key(&job)
+    }
+
+    /// Queues `request` unless one of its kind is still in flight. Returns whether it was sent.
+    pub fn send(&mut self, request: WorkerRequest) -> bool {
+        if self.is_busy(request.job()) {
+            return false;
+        }
+        self.submit(request);
+        true
+    }
+
+    /// Queues `request`, superseding any request of its kind still in flight.
+    pub fn submit(&mut self, request: WorkerRequest) {
+        let job = request.job();
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        if self.requests.send((job, ticket, request)).is_ok() {
+            self.in_flight.insert(job, ticket);
+        }
+    }
+
+    /// Drains every finished request without blocking, leaving out superseded ones.
+    pub fn drain(&mut self) -> Vec<AppEvent> {
+        let mut events = Vec::new();
+        while let Ok((job, ticket, event)) = self.events.try_recv() {
+            if settle(&mut self.in_flight, job, ticket) {
+                events.push(event);
+            }
+        }
+        events
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Marks the request `ticket` of `job` finished; returns whether its result still applies.
+fn settle(in_flight: &mut HashMap<WorkerJob, u64>, job: WorkerJob, ticket: u64) -> bool {
+    if in_flight.get(&job) == Some(&ticket) {
+        in_flight.remove(&job);
+        return true;
+    }
+    !job.latest_only()
+}
+
+async fn run(mut requests: UnboundedReceiver<Ticketed<WorkerRequest>>, events: UnboundedSender<Ticketed<AppEvent>>) {
+    // Owned here so aborting the worker also aborts the requests in flight
+    let mut tasks = JoinSet::new();
+    loop {
+        tokio::select! {
+            request = requests.recv() => {
+                let Some((job, ticket, request)) = request else {
+                    return;
+                };
+                let events = events.clone();
+                tasks.spawn(async move {
+                    let _ = events.send((job, ticket, handle(request).await));
+                });
+            }
+            Some(_) = tasks.join_next(), if !tasks.is_empty() => {}
+        }
+    }
+}
+
+async fn handle(request: WorkerRequest) -> AppEvent {
+    let started = Instant::now();
+    match request {
+        WorkerRequest::FetchStats(mut con) => {
+            let info = redis::cmd("INFO").query_async::<String>(&mut con).await.map_err(|e| e.to_string());
+            AppEvent::Stats {
+                info,
+                elapsed: started.elapsed(),
+            }
+        }
+        WorkerRequest::FetchKeyTypes { mut con, keys } => {
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("TYPE").arg(key).cmd("TTL").arg(key);
+            }
+            let replies = pipe.query_async::<Vec<Value>>(&mut con).await.map_err(|e| e.to_string());
+            AppEvent::KeyTypes {
+                keys,
+                replies,
+                elapsed: started.elapsed(),
+            }
+        }
+        WorkerRequest::FetchKeySizes { mut con, keys } => {
+            let mut pipe = redis::pipe();
+            for key in &keys {
+                pipe.cmd("MEMORY").arg("USAGE").arg(key);
+            }
+            let sizes = match pipe.query_async::<Vec<Value>>(&mut con).await {
+                Ok(values) => values.into_iter().map(memory_usage).collect(),
+                Err(_) => vec![None; keys.len()],
+            };
+            AppEvent::KeySizes { keys, sizes }
+        }
+        WorkerRequest::Connect {
+            profile,
+            profile_index,
+            use_profile_db,
+            db_override,
+            db_index,
+            session,
+        } => AppEvent::Connected {
+            profile_index,
+            session,
+            result: app_connect::open_connection(&profile, use_profile_db, db_override, db_index).await,
+        },
+        WorkerRequest::FetchValue { con, key, options, purpose } => AppEvent::Value {
+            purpose,
+            fetched: Box::new(value_fetch::fetch_value(con, key, options).await),
+        },
+        WorkerRequest::LoadMoreValues {
+            con,
+            key,
+            key_type,
+            page,
+            oldest,
+        } => {
+            let rows = app_fetch::fetch_more(con, &key, &key_type, page, oldest).await;
+            AppEvent::MoreValues {
+                key,
+                page,
+                rows,
+                elapsed: started.elapsed(),
+            }
+        }
+        WorkerRequest::FetchStringChunk { mut con, key, chunk } => {
+            let bytes = value_fetch::read_string_chunk(&mut con, &key, chunk).await;
+            AppEvent::StringChunk { key, chunk, bytes }
+        }
+        WorkerRequest::Probe { con, key, kind, inputs } => {
+            let reply = app_probabilistic::probe(con, &key, kind, &inputs).await;
+            AppEvent::Probed { key, kind, inputs, reply }
+        }
+        WorkerRequest::PfAdd { con, key, elements } => {
+            let changed = app_hyperloglog::pfadd(con, &key, &elements).await;
+            AppEvent::PfAdded {
+                key,
+                count: elements.len(),
+                changed,
+            }
+        }
+        WorkerRequest::GotoKey { con, key } => {
+            let found = app_goto_key::look_up_key(con, &key).await;
+            AppEvent::GotoKey { key, found }
+        }
+        WorkerRequest::CopyText { text, message } => {
+            let (severity, message) = app_clipboard::copy_text(text, message).await;
+            AppEvent::Toast(severity, message)
+        }
+        WorkerRequest::CopyKeyJson { con, key } => {
+            let (severity, message) = app_clipboard::copy_key_json(con, key).await;
+            AppEvent::Toast(severity, message)
+        }
+        WorkerRequest::CopyKeyCommand { con, key } => {
+            let (severity, message) = app_clipboard::copy_key_command(con, key).await;
+            AppEvent::Toast(severity, message)
+        }
+        WorkerRequest::KeyCommand {
+            mut con,
+            cmd,
+            key,
+            action,
+            label,
+            input,
+        } => {
+            let reply = cmd.query_async::<Value>(&mut con).await.map_err(|e| e.to_string());
+            AppEvent::KeyCommand {
+                key,
+                action,
+                label,
+                input,
+                reply,
+            }
+        }
+        WorkerRequest::SaveValue { con, key, command, path } => {
+            let saved = app_action_menu::save_value(con, &key, command, &path).await;
+            AppEvent::SavedValue { key, path, saved }
+        }
+        WorkerRequest::ExportKey { con, key } => {
+            let (severity, message) = app_action_menu::export_key(con, &key).await;
+            AppEvent::Toast(severity, message)
+        }
+        WorkerRequest::DeleteKey { con, key } => {
+            let result = app_delete::delete_redis_key(con, &key).await;
+            AppEvent::DeletedKey { key, result }
+        }
+        WorkerRequest::CheckKeyspaceNotifications(con) => {
+            let (severity, message) = app_live_updates::check_keyspace_notifications(con).await;
+            AppEvent::Toast(severity, message)
+        }
+        WorkerRequest::ReadPaste(con) => AppEvent::PasteRead(app_paste::read_paste(con).await),
+        WorkerRequest::ApplyPaste {
+            con,
+            pipe,
+            key,
+            overwrites,
+        } => AppEvent::Pasted {
+            key,
+            overwrites,
+            result: app_paste::run_paste(con, pipe).await,
+        },
+        WorkerRequest::RunScript {
+            con,
+            script,
+            mode,
+            keys,
+            args,
+        } => {
+            let (result, sha) = app_script_runner::run_script(con, &script, mode, &keys, &args).await;
+            AppEvent::ScriptRan { result, sha }
+        }
+        WorkerRequest::FetchDoctorReport(con) => AppEvent::DoctorReport(app_doctor::doctor_report(con).await),
+        WorkerRequest::FetchStatsReport(mut con) => {
+            let info = redis::cmd("INFO").query_async::<String>(&mut con).await.map_err(|e| e.to_string());
+            AppEvent::StatsReport {
+                info,
+                elapsed: started.elapsed(),
+            }
+        }
+        WorkerRequest::SampleRandomKeys { con, size } => {
+            AppEvent::RandomSample(app_random_sample::sample_random_keys(con, size).await)
+        }
+        WorkerRequest::FetchKeyInfo { con, key } => {
+            AppEvent::KeyInfo(Box::new(app_key_info::read_key_info(con, key).await))
+        }
+        WorkerRequest::FetchConfig(con) => AppEvent::Config(app_config_editor::read_config(con).await),
+        WorkerRequest::SetConfig { mut con, name, value } => {
+            let result = redis::cmd("CONFIG")
+                .arg("SET")
+                .arg(&name)
+                .arg(&value)
+                .query_async::<()>(&mut con)
+                .await
+                .map_err(|e| e.to_string());
+            AppEvent::ConfigSet { name, value, result }
+        }
+        WorkerRequest::RewriteConfig(mut con) => {
+            let result = redis::cmd("CONFIG").arg("REWRITE").query_async::<()>(&mut con).await;
+            AppEvent::ConfigRewritten(result.map_err(|e| e.to_string()))
+        }
+        WorkerRequest::FetchFunctions(con) => AppEvent::Functions(app_functions::list_functions(con).await),
+        WorkerRequest::RunFunction { con, call } => {
+            let (result, loaded) = app_functions::run_function(con, call).await;
+            AppEvent::FunctionRan { result, loaded }
+        }
+        WorkerRequest::FetchSearchIndexes(con) => AppEvent::SearchIndexes(app_redisearch::list_indexes(con).await),
+        WorkerRequest::FetchSearchIndexInfo { con, index } => {
+            let info = app_redisearch::index_info(con, &index).await;
+            AppEvent::SearchIndexInfo { index, info }
+        }
+        WorkerRequest::RunSearchQuery { con, query } => {
+            let results = app_redisearch::search(con, &query).await;
+            AppEvent::SearchResults { query, results }
+        }
+        WorkerRequest::StartCompare { left, profile } => {
+            let right = app_compare::connect_right(&profile).await;
+            AppEvent::CompareConnected {
+                left,
+                profile: Box::new(profile),
+                right,
+            }
+        }
+        WorkerRequest::FetchCompareDetail { left, right, key } => {
+            AppEvent::CompareDetail(Box::new(app_compare::compare_detail(left, right, key).await))
+        }
+        WorkerRequest::FetchConsumerGroups { con, key, selected } => {
+            let read = app_consumer_groups::read_consumer_groups(con, &key, selected).await;
+            AppEvent::ConsumerGroups { key, read }
+        }
+        WorkerRequest::GroupCommand {
+            con,
+            key,
+            group,
+            command,
+        } => {
+            let clears_marks = command.clears_marks();
+            let status = app_consumer_groups::run_group_command(con, &key, &group, command).await;
+            AppEvent::GroupCommandRan {
+                key,
+                status,
+                clears_marks,
+            }
+        }
+        WorkerRequest::RunCommand { con, input } => AppEvent::CommandRan(command::run_input(con, &input).await),
+    }
+}
+
+/// Bytes of a `MEMORY USAGE` reply; None for a missing key or a refused command.
+fn memory_usage(value: Value) -> Option<u64> {
+    match value {
+        Value::Int(bytes) => u64::try_from(bytes).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_settle_the_job_they_answer() {
+        let mut in_flight = HashMap::from([(WorkerJob::KeySizes, 3)]);
+        assert!(settle(&mut in_flight, WorkerJob::KeySizes, 3));
+        assert!(in_flight.is_empty());
+        assert_eq!(memory_usage(Value::Int(512)), Some(512));
+        assert_eq!(memory_usage(Value::Nil), None);
+    }
+
+    #[test]
+    fn superseded_results_are_dropped_except_for_actions() {
+        let mut in_flight = HashMap::from([(WorkerJob::Value, 5), (WorkerJob::Action, 7)]);
+        assert!(!settle(&mut in_flight, WorkerJob::Value, 4));
+        assert!(settle(&mut in_flight, WorkerJob::Action, 6));
+        // The latest of each kind is still awaited
+        assert_eq!(in_flight.len(), 2);
+        assert!(settle(&mut in_flight, WorkerJob::Value, 5));
+        assert!(settle(&mut in_flight, WorkerJob::Action, 7));
+        assert!(in_flight.is_empty());
+    }
+}
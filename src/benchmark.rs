@@ -0,0 +1,245 @@
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use redis::aio::MultiplexedConnection;
+use redis::Client;
+use serde_json::json;
+
+use crate::config::ConnectionProfile;
+
+/// Prefix of every key a benchmark writes; they are unlinked when it finishes.
+const KEY_PREFIX: &str = "lazyredis:bench:";
+
+/// Keys written per pipeline when filling the keyspace for `get` and cleaning up.
+const SETUP_BATCH: usize = 1000;
+
+/// Commands a benchmark can measure.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Workload {
+    Set,
+    Get,
+    Incr,
+}
+
+impl Workload {
+    fn name(self) -> &'static str {
+        match self {
+            Workload::Set => "SET",
+            Workload::Get => "GET",
+            Workload::Incr => "INCR",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    pub workloads: Vec<Workload>,
+    /// Connections sending commands at once, one command in flight each.
+    pub concurrency: usize,
+    /// Time spent on each workload.
+    pub duration: Duration,
+    /// Bytes per `SET` value.
+    pub value_size: usize,
+    /// Distinct keys the workloads cycle through.
+    pub keyspace: usize,
+}
+
+/// Throughput and latency of one workload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadReport {
+    pub workload: Workload,
+    pub ops: usize,
+    pub errors: usize,
+    pub elapsed: Duration,
+    /// Latency percentiles and maximum, in microseconds.
+    pub p50: u64,
+    pub p95: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl WorkloadReport {
+    fn new(workload: Workload, mut latencies: Vec<u64>, errors: usize, elapsed: Duration) -> Self {
+        latencies.sort_unstable();
+        Self {
+            workload,
+            ops: latencies.len(),
+            errors,
+            elapsed,
+            p50: percentile(&latencies, 50.0),
+            p95: percentile(&latencies, 95.0),
+            p99: percentile(&latencies, 99.0),
+            max: latencies.last().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn ops_per_sec(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        self.ops as f64 / secs
+    }
+
+    /// One aligned line: operations, throughput and latencies in milliseconds.
+    pub fn line(&self) -> String {
+        let ms = |micros: u64| micros as f64 / 1000.0;
+        let mut line = format!(
+            "{:<5} {:>10} ops in {:.1}s  {:>10.0} ops/s  p50 {:.3} ms  p95 {:.3} ms  p99 {:.3} ms  max {:.3} ms",
+            self.workload.name(),
+            self.ops,
+            self.elapsed.as_secs_f64(),
+            self.ops_per_sec(),
+            ms(self.p50),
+            ms(self.p95),
+            ms(self.p99),
+            ms(self.max)
+        );
+        if self.errors > 0 {
+            line.push_str(&format!("  ({} errors)", self.errors));
+        }
+        line
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "workload": self.workload.name(),
+            "ops": self.ops,
+            "errors": self.errors,
+            "seconds": self.elapsed.as_secs_f64(),
+            "ops_per_sec": self.ops_per_sec(),
+            "latency_us": { "p50": self.p50, "p95": self.p95, "p99": self.p99, "max": self.max },
+        })
+    }
+}
+
+/// Nearest-rank percentile of ascending `sorted` values; 0 when there are none.
+fn percentile(sorted: &[u64], percent: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let rank = (percent / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Runs each workload against `profile` in turn, then removes the benchmark keys. Only
+/// dev profiles are accepted, since the benchmark writes and deletes keys.
+pub async fn run(profile: &ConnectionProfile, options: &BenchOptions) -> Result<Vec<WorkloadReport>> {
+    if !profile.dev.unwrap_or(false) {
+        return Err(anyhow!("Profile '{}' is not marked dev=true; refusing to benchmark.", profile.name));
+    }
+    if options.concurrency == 0 || options.keyspace == 0 {
+        return Err(anyhow!("--concurrency and --keyspace must be at least 1."));
+    }
+    let client = Client::open(profile.url.as_str())?;
+    let db = profile.db.unwrap_or(0);
+    let mut connections = Vec::with_capacity(options.concurrency);
+    for _ in 0..options.concurrency {
+        let mut con = client.get_multiplexed_async_connection().await?;
+        redis::cmd("SELECT").arg(db).query_async::<()>(&mut con).await?;
+        connections.push(con);
+    }
+    tracing::info!(profile = %profile.name, concurrency = options.concurrency, "benchmark started");
+
+    let value = vec![b'x'; options.value_size];
+    if options.workloads.contains(&Workload::Get) {
+        fill_keyspace(&mut connections[0], options.keyspace, &value).await?;
+    }
+    let mut reports = Vec::new();
+    for &workload in &options.workloads {
+        reports.push(run_workload(&connections, workload, options, &value).await);
+    }
+    remove_keys(&mut connections[0], options.keyspace).await?;
+    Ok(reports)
+}
+
+/// Sends `workload` from every connection at once until the duration is up.
+async fn run_workload(
+    connections: &[MultiplexedConnection],
+    workload: Workload,
+    options: &BenchOptions,
+    value: &[u8],
+) -> WorkloadReport {
+    let started = Instant::now();
+    let deadline = started + options.duration;
+    let mut workers = tokio::task::JoinSet::new();
+    for (worker, con) in connections.iter().enumerate() {
+        let (mut con, value) = (con.clone(), value.to_vec());
+        let (stride, keyspace) = (connections.len(), options.keyspace);
+        workers.spawn(async move {
+            let mut latencies = Vec::new();
+            let mut errors = 0;
+            let mut n = worker;
+            while Instant::now() < deadline {
+                let cmd = command(workload, n % keyspace, &value);
+                let sent = Instant::now();
+                match cmd.query_async::<redis::Value>(&mut con).await {
+                    Ok(_) => latencies.push(sent.elapsed().as_micros() as u64),
+                    Err(_) => errors += 1,
+                }
+                n += stride;
+            }
+            (latencies, errors)
+        });
+    }
+    let mut latencies = Vec::new();
+    let mut errors = 0;
+    while let Some(result) = workers.join_next().await {
+        match result {
+            Ok((worker_latencies, worker_errors)) => {
+                latencies.extend(worker_latencies);
+                errors += worker_errors;
+            }
+            Err(_) => errors += 1,
+        }
+    }
+    WorkloadReport::new(workload, latencies, errors, started.elapsed())
+}
+
+fn command(workload: Workload, key: usize, value: &[u8]) -> redis::Cmd {
+    match workload {
+        Workload::Set => redis::cmd("SET").arg(format!("{}{}", KEY_PREFIX, key)).arg(value).clone(),
+        Workload::Get => redis::cmd("GET").arg(format!("{}{}", KEY_PREFIX, key)).clone(),
+        Workload::Incr => redis::cmd("INCR").arg(format!("{}counter:{}", KEY_PREFIX, key)).clone(),
+    }
+}
+
+async fn fill_keyspace(con: &mut MultiplexedConnection, keyspace: usize, value: &[u8]) -> Result<()> {
+    for start in (0..keyspace).step_by(SETUP_BATCH) {
+        let mut pipe = redis::pipe();
+        for key in start..(start + SETUP_BATCH).min(keyspace) {
+            pipe.add_command(command(Workload::Set, key, value)).ignore();
+        }
+        pipe.query_async::<()>(con).await?;
+    }
+    Ok(())
+}
+
+async fn remove_keys(con: &mut MultiplexedConnection, keyspace: usize) -> Result<()> {
+    for start in (0..keyspace).step_by(SETUP_BATCH) {
+        let mut unlink = redis::cmd("UNLINK");
+        for key in start..(start + SETUP_BATCH).min(keyspace) {
+            unlink.arg(format!("{}{}", KEY_PREFIX, key)).arg(format!("{}counter:{}", KEY_PREFIX, key));
+        }
+        unlink.query_async::<()>(con).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_use_nearest_rank_percentiles() {
+        let latencies = (1..=200).rev().collect();
+        let report = WorkloadReport::new(Workload::Get, latencies, 2, Duration::from_secs(2));
+        assert_eq!((report.p50, report.p95, report.p99, report.max), (100, 190, 198, 200));
+        assert_eq!(report.ops_per_sec(), 100.0);
+        assert!(report.line().starts_with("GET          200 ops in 2.0s"), "{}", report.line());
+        assert!(report.line().ends_with("(2 errors)"));
+        assert_eq!(report.to_json()["latency_us"]["p99"], 198);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+}
@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fs, path::{Path, PathBuf}};
+
+/// Bookmarked key names per connection profile, persisted next to the config file
+/// in `lazyredis/bookmarks.toml` so the config itself is never rewritten.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Bookmarks {
+    #[serde(default)]
+    profiles: BTreeMap<String, Vec<String>>,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Bookmarks {
+    fn determine_file_path(base_path_override: Option<&Path>) -> Option<PathBuf> {
+        match base_path_override {
+            Some(base_path) => Some(base_path.to_path_buf()),
+            None => directories::BaseDirs::new().map(|base_dirs| base_dirs.config_dir().to_path_buf()),
+        }
+        .map(|base| base.join("lazyredis").join("bookmarks.toml"))
+    }
+
+    /// Reads the bookmarks file; a missing or unreadable file yields no bookmarks.
+    pub fn load(base_path_override: Option<&Path>) -> Self {
+        let path = Self::determine_file_path(base_path_override);
+        let mut bookmarks: Self = path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+        bookmarks.path = path;
+        bookmarks
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, contents).map_err(|e| e.to_string())
+    }
+
+    pub fn for_profile(&self, profile: &str) -> &[String] {
+        self.profiles.get(profile).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn contains(&self, profile: &str, key: &str) -> bool {
+        self.for_profile(profile).iter().any(|bookmark| bookmark == key)
+    }
+
+    /// Adds `key` to the profile's bookmarks or removes it if already present.
+    /// Returns whether the key is bookmarked afterwards.
+    pub fn toggle(&mut self, profile: &str, key: &str) -> bool {
+        let keys = self.profiles.entry(profile.to_string()).or_default();
+        if let Some(idx) = keys.iter().position(|bookmark| bookmark == key) {
+            keys.remove(idx);
+            if keys.is_empty() {
+                self.profiles.remove(profile);
+            }
+            return false;
+        }
+        keys.push(key.to_string());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn toggled_bookmarks_round_trip_per_profile() {
+        let dir = tempdir().unwrap();
+        let mut bookmarks = Bookmarks::load(Some(dir.path()));
+        assert!(bookmarks.for_profile("Dev").is_empty());
+
+        assert!(bookmarks.toggle("Dev", "user:1"));
+        assert!(bookmarks.toggle("Dev", "config"));
+        assert!(bookmarks.toggle("Prod", "user:1"));
+        assert!(!bookmarks.toggle("Dev", "user:1"));
+        bookmarks.save().unwrap();
+
+        let loaded = Bookmarks::load(Some(dir.path()));
+        assert_eq!(loaded.for_profile("Dev"), ["config".to_string()]);
+        assert!(loaded.contains("Prod", "user:1"));
+        assert!(!loaded.contains("Dev", "user:1"));
+    }
+}
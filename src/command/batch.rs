@@ -0,0 +1,109 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+use std::path::Path;
+
+use super::{build_cmd, render_reply};
+
+/// Outcome of running a command file: one block of output lines per executed command.
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    pub lines: Vec<String>,
+    pub executed: usize,
+    pub failed: usize,
+    /// Line number that stopped the batch, when an error ended it early.
+    pub stopped_at: Option<usize>,
+}
+
+impl BatchReport {
+    pub fn summary(&self) -> String {
+        match self.stopped_at {
+            Some(line) => format!(
+                "Stopped at line {} after {} command(s); use --force to continue past errors.",
+                line, self.executed
+            ),
+            None => format!("Ran {} command(s), {} failed.", self.executed, self.failed),
+        }
+    }
+}
+
+/// Commands of a batch file with their 1-based line numbers. Blank lines and lines
+/// starting with `#` are skipped; words are split on whitespace like the prompt does.
+pub fn parse_batch(contents: &str) -> Vec<(usize, Vec<&str>)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim_start().starts_with('#'))
+        .map(|(idx, line)| (idx + 1, line.split_whitespace().collect::<Vec<_>>()))
+        .filter(|(_, parts)| !parts.is_empty())
+        .collect()
+}
+
+/// Reads `path` and runs its commands one at a time. The first error (including an
+/// error reply) stops the batch unless `force` is set.
+pub async fn run_batch_file(
+    con: &mut MultiplexedConnection,
+    path: &Path,
+    force: bool,
+) -> Result<BatchReport, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    let mut report = BatchReport::default();
+    for (line_no, parts) in parse_batch(&contents) {
+        let reply = build_cmd(&parts).query_async::<Value>(con).await;
+        report.executed += 1;
+        report.lines.push(format!("{}: {}", line_no, parts.join(" ")));
+        let rendered = match &reply {
+            Ok(Value::ServerError(err)) => Err(err.to_string()),
+            Ok(value) => Ok(render_reply(value)),
+            Err(e) => Err(e.to_string()),
+        };
+        match rendered {
+            Ok(lines) => report.lines.extend(lines.into_iter().map(|line| format!("   {}", line))),
+            Err(e) => {
+                tracing::warn!(line = line_no, command = parts[0], "batch command failed: {}", e);
+                report.failed += 1;
+                report.lines.push(format!("   (error) {}", e));
+                if !force {
+                    report.stopped_at = Some(line_no);
+                    break;
+                }
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Splits `source <path> [--force]` prompt input; the leading `:` is optional.
+pub fn parse_source_command(input: &str) -> Option<(&str, bool)> {
+    let input = input.trim().trim_start_matches(':');
+    let (command, rest) = input.split_once(char::is_whitespace)?;
+    if !command.eq_ignore_ascii_case("source") {
+        return None;
+    }
+    let rest = rest.trim();
+    match rest.strip_suffix("--force") {
+        Some(path) if path.ends_with(char::is_whitespace) => Some((path.trim_end(), true)),
+        _ => Some((rest, false)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_batch, parse_source_command};
+
+    #[test]
+    fn batch_skips_comments_and_blank_lines() {
+        let contents = "# warm up\nSET a 1\n\n  GET a  \n";
+        assert_eq!(
+            parse_batch(contents),
+            vec![(2, vec!["SET", "a", "1"]), (4, vec!["GET", "a"])]
+        );
+    }
+
+    #[test]
+    fn source_command_takes_path_and_force_flag() {
+        assert_eq!(parse_source_command(":source ./setup.redis"), Some(("./setup.redis", false)));
+        assert_eq!(parse_source_command("SOURCE my file.txt --force"), Some(("my file.txt", true)));
+        assert_eq!(parse_source_command("GET source"), None);
+    }
+}
@@ -0,0 +1,278 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+use std::collections::HashMap;
+
+use super::resp::{map_field, pairs, value_text};
+
+/// What the prompt knows about one server command, from `COMMAND` and `COMMAND DOCS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandSpec {
+    /// Upper-case command name, e.g. `GET` or `CLIENT LIST` for container subcommands.
+    pub name: String,
+    /// Redis arity: positive is exact (including the name), negative is a minimum.
+    pub arity: i64,
+    pub summary: String,
+    /// Argument syntax such as `key [EX seconds]`.
+    pub syntax: String,
+    /// The server flags the command `write`, `may_replicate` or `admin`; commands known
+    /// only from the docs count as writing.
+    pub writes: bool,
+}
+
+impl CommandSpec {
+    pub fn arity_label(&self) -> String {
+        match self.arity {
+            0 => "args: ?".to_string(),
+            n if n < 0 => format!("args: {}+", -n - 1),
+            n => format!("args: {}", n - 1),
+        }
+    }
+}
+
+/// Loads the command table of the connected server. `COMMAND DOCS` needs Redis 7; on older
+/// servers (or when denied by ACLs) only names and arities from `COMMAND` are available.
+pub async fn fetch_command_catalog(con: &mut MultiplexedConnection) -> Vec<CommandSpec> {
+    let arities = match redis::cmd("COMMAND").query_async::<Value>(con).await {
+        Ok(reply) => parse_command_info(&reply),
+        Err(_) => return Vec::new(),
+    };
+    let docs = redis::cmd("COMMAND")
+        .arg("DOCS")
+        .query_async::<Value>(con)
+        .await
+        .map(|reply| parse_command_docs(&reply))
+        .unwrap_or_default();
+    build_catalog(arities, docs)
+}
+
+fn build_catalog(
+    arities: HashMap<String, (i64, bool)>,
+    mut docs: HashMap<String, (String, String)>,
+) -> Vec<CommandSpec> {
+    let mut catalog: Vec<CommandSpec> = arities
+        .into_iter()
+        .map(|(name, (arity, writes))| {
+            let (summary, syntax) = docs.remove(&name).unwrap_or_default();
+            CommandSpec { name, arity, summary, syntax, writes }
+        })
+        .collect();
+    // Servers before Redis 7 list subcommands (e.g. `CLIENT LIST`) only in the docs
+    catalog.extend(docs.into_iter().map(|(name, (summary, syntax))| CommandSpec {
+        name,
+        arity: 0,
+        summary,
+        syntax,
+        writes: true,
+    }));
+    catalog.sort_by(|a, b| a.name.cmp(&b.name));
+    catalog
+}
+
+/// Flags of a command that changes data or the server.
+const WRITE_FLAGS: [&str; 3] = ["write", "may_replicate", "admin"];
+
+/// `COMMAND` reply: one `[name, arity, flags, ...]` array per command, with the
+/// subcommands of containers such as `CONFIG` in the tenth field on Redis 7.
+/// Returns name -> (arity, writes), subcommands named like `CONFIG SET`.
+fn parse_command_info(reply: &Value) -> HashMap<String, (i64, bool)> {
+    let mut info = HashMap::new();
+    collect_info(reply, &mut info);
+    info
+}
+
+fn collect_info(reply: &Value, info: &mut HashMap<String, (i64, bool)>) {
+    let Value::Array(entries) = reply else {
+        return;
+    };
+    for entry in entries {
+        let Value::Array(fields) = entry else {
+            continue;
+        };
+        let Some(name) = fields.first().and_then(value_text) else {
+            continue;
+        };
+        let Some(Value::Int(arity)) = fields.get(1) else {
+            continue;
+        };
+        let writes = match fields.get(2) {
+            Some(Value::Array(flags)) | Some(Value::Set(flags)) => flags
+                .iter()
+                .filter_map(value_text)
+                .any(|flag| WRITE_FLAGS.contains(&flag.as_str())),
+            _ => false,
+        };
+        // Subcommands are reported as `config|set`
+        info.insert(name.to_uppercase().replace('|', " "), (*arity, writes));
+        if let Some(subcommands) = fields.get(9) {
+            collect_info(subcommands, info);
+        }
+    }
+}
+
+/// `COMMAND DOCS` reply: name -> doc map, as a RESP3 map or a flat RESP2 array.
+/// Returns name -> (summary, argument syntax), including container subcommands.
+fn parse_command_docs(reply: &Value) -> HashMap<String, (String, String)> {
+    let mut docs = HashMap::new();
+    collect_docs(reply, "", &mut docs);
+    docs
+}
+
+fn collect_docs(reply: &Value, parent: &str, docs: &mut HashMap<String, (String, String)>) {
+    for (name, doc) in pairs(reply) {
+        let Some(name) = value_text(name) else {
+            continue;
+        };
+        let name = name.to_uppercase();
+        // Subcommands are reported as `client|list`
+        let name = match name.split_once('|') {
+            Some((container, sub)) => format!("{} {}", container, sub),
+            None if parent.is_empty() => name,
+            None => format!("{} {}", parent, name),
+        };
+        let fields = pairs(doc);
+        let field = |wanted: &str| map_field(&fields, wanted);
+        let summary = field("summary").and_then(value_text).unwrap_or_default();
+        let syntax = field("arguments").map(arguments_syntax).unwrap_or_default();
+        if let Some(subcommands) = field("subcommands") {
+            collect_docs(subcommands, &name, docs);
+        }
+        docs.insert(name, (summary, syntax));
+    }
+}
+
+/// Renders a docs `arguments` array the way the Redis docs print command syntax.
+fn arguments_syntax(arguments: &Value) -> String {
+    let Value::Array(arguments) = arguments else {
+        return String::new();
+    };
+    arguments
+        .iter()
+        .map(argument_syntax)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn argument_syntax(argument: &Value) -> String {
+    let fields = pairs(argument);
+    let field = |wanted: &str| map_field(&fields, wanted);
+    let has_flag = |flag: &str| match field("flags") {
+        Some(Value::Array(flags)) | Some(Value::Set(flags)) => {
+            flags.iter().any(|f| value_text(f).as_deref() == Some(flag))
+        }
+        _ => false,
+    };
+    let arg_type = field("type").and_then(value_text).unwrap_or_default();
+    let name = field("name").and_then(value_text).unwrap_or_default();
+    let mut text = match arg_type.as_str() {
+        "pure-token" => String::new(),
+        "oneof" => field("arguments")
+            .map(|nested| match nested {
+                Value::Array(options) => options
+                    .iter()
+                    .map(argument_syntax)
+                    .collect::<Vec<_>>()
+                    .join("|"),
+                _ => String::new(),
+            })
+            .unwrap_or_default(),
+        "block" => field("arguments").map(arguments_syntax).unwrap_or_default(),
+        _ => name,
+    };
+    if let Some(token) = field("token").and_then(value_text) {
+        text = if text.is_empty() { token } else { format!("{} {}", token, text) };
+    }
+    if has_flag("multiple") {
+        text = format!("{} [{} ...]", text, text);
+    }
+    if has_flag("optional") {
+        text = format!("[{}]", text);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    fn arg(fields: &[(&str, Value)]) -> Value {
+        Value::Array(
+            fields
+                .iter()
+                .flat_map(|(k, v)| [bulk(k), v.clone()])
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn docs_render_argument_syntax_and_merge_arity() {
+        let set_docs = arg(&[
+            ("summary", bulk("Sets the string value of a key.")),
+            (
+                "arguments",
+                Value::Array(vec![
+                    arg(&[("name", bulk("key")), ("type", bulk("key"))]),
+                    arg(&[("name", bulk("value")), ("type", bulk("string"))]),
+                    arg(&[
+                        ("name", bulk("condition")),
+                        ("type", bulk("oneof")),
+                        ("flags", Value::Array(vec![bulk("optional")])),
+                        (
+                            "arguments",
+                            Value::Array(vec![
+                                arg(&[("name", bulk("nx")), ("type", bulk("pure-token")), ("token", bulk("NX"))]),
+                                arg(&[("name", bulk("xx")), ("type", bulk("pure-token")), ("token", bulk("XX"))]),
+                            ]),
+                        ),
+                    ]),
+                ]),
+            ),
+        ]);
+        let del_docs = arg(&[
+            ("summary", bulk("Deletes keys.")),
+            (
+                "arguments",
+                Value::Array(vec![arg(&[
+                    ("name", bulk("key")),
+                    ("type", bulk("key")),
+                    ("flags", Value::Array(vec![bulk("multiple")])),
+                ])]),
+            ),
+        ]);
+        let docs = parse_command_docs(&Value::Array(vec![bulk("set"), set_docs, bulk("del"), del_docs]));
+        let info = Value::Array(vec![
+            Value::Array(vec![bulk("set"), Value::Int(-3), Value::Array(vec![bulk("write")])]),
+            Value::Array(vec![bulk("del"), Value::Int(-2)]),
+        ]);
+        let catalog = build_catalog(parse_command_info(&info), docs);
+
+        assert_eq!(catalog[0].name, "DEL");
+        assert_eq!(catalog[0].syntax, "key [key ...]");
+        assert_eq!(catalog[1].name, "SET");
+        assert_eq!(catalog[1].syntax, "key value [NX|XX]");
+        assert_eq!(catalog[1].arity_label(), "args: 2+");
+        assert!(catalog[1].writes);
+    }
+
+    #[test]
+    fn info_flags_mark_writing_commands_and_subcommands() {
+        let subcommands = Value::Array(vec![
+            Value::Array(vec![bulk("config|get"), Value::Int(-3), Value::Array(vec![bulk("loading")])]),
+            Value::Array(vec![bulk("config|set"), Value::Int(-4), Value::Array(vec![bulk("admin")])]),
+        ]);
+        let mut config = vec![bulk("config"), Value::Int(-2), Value::Array(Vec::new())];
+        config.extend(std::iter::repeat_n(Value::Nil, 6));
+        config.push(subcommands);
+        let info = parse_command_info(&Value::Array(vec![
+            Value::Array(config),
+            Value::Array(vec![bulk("eval"), Value::Int(-3), Value::Array(vec![bulk("may_replicate")])]),
+        ]));
+        assert_eq!(info["CONFIG GET"], (-3, false));
+        assert_eq!(info["CONFIG SET"], (-4, true));
+        assert_eq!(info["EVAL"], (-3, true));
+        assert_eq!(info["CONFIG"], (-2, false));
+    }
+}
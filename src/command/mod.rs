@@ -0,0 +1,561 @@
+use redis::Value;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+use crossterm::event::KeyEvent;
+use crate::app::reproduce::{split_cli_args, CommandArgs};
+use crate::app::MultiplexedConnection;
+use crate::line_edit::{self, LineEdit};
+
+mod batch;
+mod docs;
+mod render;
+pub mod resp;
+
+pub use batch::{run_batch_file, BatchReport};
+pub use docs::{fetch_command_catalog, CommandSpec};
+pub use render::{render_raw, render_reply};
+
+/// Lines moved by one PgUp/PgDn in the result pane.
+const RESULT_PAGE_LINES: usize = 10;
+
+/// Tab-completion in progress: the text that was typed and the candidate shown.
+#[derive(Debug)]
+struct Completion {
+    stem: String,
+    index: usize,
+}
+
+#[derive(Debug)]
+pub struct CommandState {
+    pub input_buffer: String,
+    /// Byte offset of the cursor in `input_buffer`.
+    pub cursor: usize,
+    pub last_result: Option<String>,
+    /// First visible line of `last_result` in the result pane.
+    pub result_scroll: usize,
+    /// Show replies as raw RESP (type markers, exact lengths) instead of redis-cli style.
+    pub raw_output: bool,
+    replies: Vec<CommandReply>,
+    pub is_active: bool,
+    /// Commands known to the connected server, sorted by name.
+    pub catalog: Vec<CommandSpec>,
+    completion: Option<Completion>,
+}
+
+impl CommandState {
+    pub fn new() -> Self {
+        CommandState {
+            input_buffer: String::new(),
+            cursor: 0,
+            last_result: None,
+            result_scroll: 0,
+            raw_output: false,
+            replies: Vec::new(),
+            is_active: false,
+            catalog: Vec::new(),
+            completion: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.is_active = true;
+        self.input_buffer.clear();
+        self.cursor = 0;
+        self.last_result = None;
+        self.replies.clear();
+        self.result_scroll = 0;
+    }
+
+    /// Moves the result pane by `delta` lines, or by pages with `page_result`.
+    pub fn scroll_result(&mut self, delta: isize) {
+        let lines = self.last_result.as_deref().map_or(0, |result| result.lines().count());
+        self.result_scroll = self
+            .result_scroll
+            .saturating_add_signed(delta)
+            .min(lines.saturating_sub(1));
+    }
+
+    pub fn page_result(&mut self, pages: isize) {
+        self.scroll_result(pages * RESULT_PAGE_LINES as isize);
+    }
+
+    /// Writes the full result to a new timestamped file in `dir` and returns its path.
+    pub fn save_result(&self, dir: &Path) -> io::Result<PathBuf> {
+        let Some(result) = &self.last_result else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no result to save"));
+        };
+        let stamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let path = dir.join(format!("lazyredis-result-{}.txt", stamp));
+        fs::write(&path, format!("{}\n", result))?;
+        Ok(path)
+    }
+
+    pub fn close(&mut self) {
+        self.is_active = false;
+    }
+
+    /// Inserts `c` at the cursor.
+    pub fn push_char(&mut self, c: char) {
+        self.completion = None;
+        line_edit::insert(&mut self.input_buffer, &mut self.cursor, c);
+    }
+
+    /// Applies a line-editing key (cursor movement, deletion, insertion) to the input.
+    pub fn edit(&mut self, key: KeyEvent) -> LineEdit {
+        let outcome = line_edit::handle_key(&mut self.input_buffer, &mut self.cursor, key);
+        if outcome == LineEdit::Edited {
+            self.completion = None;
+        }
+        outcome
+    }
+
+    /// Start of the command currently being typed, after the last unquoted `;` or newline.
+    fn current_segment_start(&self) -> usize {
+        separator_offsets(&self.input_buffer).last().map_or(0, |idx| idx + 1)
+    }
+
+    /// Completes the command name being typed; pressing Tab again cycles through the matches.
+    pub fn complete(&mut self) {
+        let segment_start = self.current_segment_start();
+        let stem = match &self.completion {
+            Some(completion) => completion.stem.clone(),
+            None => self.input_buffer[segment_start..].trim_start().to_uppercase(),
+        };
+        // Only the command name (and a container subcommand) is completed, not arguments
+        let words = stem.split(' ').count();
+        if words > 2 {
+            return;
+        }
+        let candidates: Vec<&CommandSpec> = self
+            .catalog
+            .iter()
+            .filter(|spec| spec.name.starts_with(&stem) && spec.name.split(' ').count() == words)
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        let index = match &self.completion {
+            Some(completion) => (completion.index + 1) % candidates.len(),
+            None => 0,
+        };
+        let leading = &self.input_buffer[segment_start..];
+        let indent = leading.len() - leading.trim_start().len();
+        self.input_buffer.truncate(segment_start + indent);
+        self.input_buffer.push_str(&candidates[index].name);
+        if candidates.len() == 1 {
+            self.input_buffer.push(' ');
+        }
+        self.cursor = self.input_buffer.len();
+        self.completion = Some(Completion { stem, index });
+    }
+
+    /// Docs for the command typed so far, preferring a matching subcommand.
+    pub fn current_spec(&self) -> Option<&CommandSpec> {
+        let words: Vec<&str> = self.input_buffer[self.current_segment_start()..].split_whitespace().collect();
+        self.spec_for(&words)
+    }
+
+    fn spec_for(&self, words: &[&str]) -> Option<&CommandSpec> {
+        find_spec(&self.catalog, words)
+    }
+
+    /// What a read-only profile refuses in the input: a `source` file, whose commands are
+    /// not checked, or the first command that may write. Commands missing from the
+    /// catalog may write too.
+    pub fn read_only_refusal(&self) -> Option<String> {
+        if batch::parse_source_command(&self.input_buffer).is_some() {
+            return Some("command files".to_string());
+        }
+        read_only_refusal(&self.catalog, split_commands(&self.input_buffer).unwrap_or_default())
+    }
+
+    /// Inline hint in the style of redis-cli: argument syntax, arity and summary.
+    pub fn hint(&self) -> Option<String> {
+        let spec = self.current_spec()?;
+        let mut hint = format!("{} {}", spec.name, spec.syntax).trim_end().to_string();
+        hint.push_str(&format!(" ({})", spec.arity_label()));
+        if !spec.summary.is_empty() {
+            hint.push_str(&format!(" - {}", spec.summary));
+        }
+        Some(hint)
+    }
+
+    /// The input to run, or None with the reason in `last_result` when there is nothing to run.
+    pub fn take_input(&mut self) -> Option<String> {
+        if self.input_buffer.trim().is_empty() {
+            self.last_result = Some("Command is empty.".to_string());
+            return None;
+        }
+        if batch::parse_source_command(&self.input_buffer).is_some() {
+            return Some(self.input_buffer.clone());
+        }
+        match split_commands(&self.input_buffer) {
+            Ok(commands) if commands.is_empty() => {
+                self.last_result = Some("No command entered.".to_string());
+                return None;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.last_result = Some(e);
+                return None;
+            }
+        }
+        Some(self.input_buffer.clone())
+    }
+
+    /// Shows what a run of the input returned, from the top.
+    pub fn show_outcome(&mut self, outcome: CommandOutcome) {
+        self.result_scroll = 0;
+        match outcome {
+            CommandOutcome::Replies(replies) => {
+                self.replies = replies;
+                self.refresh_result();
+            }
+            CommandOutcome::Report(report) => {
+                self.replies.clear();
+                self.last_result = Some(report);
+            }
+        }
+    }
+
+    /// Switches between the redis-cli style rendering and the raw RESP view.
+    pub fn toggle_raw_output(&mut self) {
+        self.raw_output = !self.raw_output;
+        self.refresh_result();
+    }
+
+    /// How `last_result` renders command replies, or None for other results such as a
+    /// `source` report.
+    pub fn result_form(&self) -> Option<&'static str> {
+        if self.replies.is_empty() || self.last_result.is_none() {
+            return None;
+        }
+        Some(if self.raw_output { "raw RESP" } else { "redis-cli style" })
+    }
+
+    fn refresh_result(&mut self) {
+        if self.replies.is_empty() {
+            return;
+        }
+        let render = |reply: &CommandReply| match &reply.reply {
+            Ok(val) if self.raw_output => render_raw(val),
+            Ok(val) => render_reply(val),
+            Err(e) if self.raw_output => vec![format!("-{}", e)],
+            Err(e) => vec![format!("(error) {}", e)],
+        };
+        let lines: Vec<String> = if let [reply] = self.replies.as_slice() {
+            render(reply)
+        } else {
+            self.replies
+                .iter()
+                .enumerate()
+                .flat_map(|(idx, reply)| {
+                    std::iter::once(format!("{}) {}", idx + 1, reply.command))
+                        .chain(render(reply).into_iter().map(|line| format!("   {}", line)))
+                })
+                .collect()
+        };
+        self.last_result = Some(lines.join("\n"));
+    }
+}
+
+/// What running the prompt input returned.
+#[derive(Debug)]
+pub enum CommandOutcome {
+    Replies(Vec<CommandReply>),
+    /// The report of a `source` file.
+    Report(String),
+}
+
+/// Runs `input` as one command, or as a pipeline when it holds several commands
+/// separated by `;` or newlines; a `source` line runs the commands of its file one by one.
+pub async fn run_input(mut con: MultiplexedConnection, input: &str) -> CommandOutcome {
+    if let Some((path, force)) = batch::parse_source_command(input) {
+        return CommandOutcome::Report(match batch::run_batch_file(&mut con, Path::new(path), force).await {
+            Ok(report) => {
+                let summary = report.summary();
+                let mut lines = report.lines;
+                lines.push(summary);
+                lines.join("\n")
+            }
+            Err(e) => e,
+        });
+    }
+    let commands = match split_commands(input) {
+        Ok(commands) => commands,
+        Err(e) => return CommandOutcome::Report(e),
+    };
+    CommandOutcome::Replies(if let [parts] = commands.as_slice() {
+        let reply = build_cmd(parts).query_async::<Value>(&mut con).await;
+        vec![CommandReply::new(parts, reply)]
+    } else {
+        let mut pipe = redis::pipe();
+        pipe.ignore_errors();
+        for parts in &commands {
+            pipe.add_command(build_cmd(parts));
+        }
+        match pipe.query_async::<Vec<redis::RedisResult<Value>>>(&mut con).await {
+            Ok(replies) => commands
+                .iter()
+                .zip(replies)
+                .map(|(parts, reply)| CommandReply::new(parts, reply))
+                .collect(),
+            Err(e) => vec![CommandReply::new(&["PIPELINE"], Err(e))],
+        }
+    })
+}
+
+/// One executed command and its reply, kept so the result can be re-rendered.
+#[derive(Debug)]
+pub struct CommandReply {
+    command: String,
+    reply: Result<Value, String>,
+}
+
+impl CommandReply {
+    fn new<T: AsRef<[u8]>>(parts: &[T], reply: redis::RedisResult<Value>) -> Self {
+        let command = command_text(parts);
+        if let Err(e) = &reply {
+            tracing::warn!(command = command.split(' ').next(), "command failed: {}", e);
+        }
+        Self {
+            command,
+            reply: reply.map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Docs for `words` in `catalog`, preferring a matching subcommand.
+fn find_spec<'a, T: AsRef<[u8]>>(catalog: &'a [CommandSpec], words: &[T]) -> Option<&'a CommandSpec> {
+    let name = String::from_utf8_lossy(words.first()?.as_ref()).to_uppercase();
+    let subcommand = words
+        .get(1)
+        .map(|sub| format!("{} {}", name, String::from_utf8_lossy(sub.as_ref()).to_uppercase()));
+    subcommand
+        .and_then(|full| catalog.iter().find(|spec| spec.name == full))
+        .or_else(|| catalog.iter().find(|spec| spec.name == name))
+}
+
+/// What a read-only profile refuses among `commands`: the first one that may write.
+/// Commands missing from `catalog` may write too.
+pub fn read_only_refusal<T: AsRef<[u8]>>(
+    catalog: &[CommandSpec],
+    commands: impl IntoIterator<Item = Vec<T>>,
+) -> Option<String> {
+    commands
+        .into_iter()
+        .find(|parts| find_spec(catalog, parts).is_none_or(|spec| spec.writes))
+        .map(|parts| {
+            let name = String::from_utf8_lossy(parts[0].as_ref()).to_uppercase();
+            format!("commands that may write ({})", name)
+        })
+}
+
+const COMMAND_SEPARATORS: [u8; 2] = [b';', b'\n'];
+
+/// Byte offsets of the `;` and newlines that separate commands, skipping those inside
+/// quotes as `split_cli_args` reads them.
+fn separator_offsets(input: &str) -> Vec<usize> {
+    let bytes = input.as_bytes();
+    let mut offsets = Vec::new();
+    let mut quote = None;
+    let mut escaped = false;
+    for (idx, &byte) in bytes.iter().enumerate() {
+        match quote {
+            _ if escaped => escaped = false,
+            Some(b'"') if byte == b'\\' => escaped = true,
+            Some(b'\'') if byte == b'\\' && bytes.get(idx + 1) == Some(&b'\'') => escaped = true,
+            Some(open) if byte == open => quote = None,
+            Some(_) => {}
+            None if byte == b'"' || byte == b'\'' => quote = Some(byte),
+            None if COMMAND_SEPARATORS.contains(&byte) => offsets.push(idx),
+            None => {}
+        }
+    }
+    offsets
+}
+
+/// Splits prompt input into commands on unquoted `;` and newlines, each split into
+/// arguments the way redis-cli does.
+fn split_commands(input: &str) -> Result<Vec<CommandArgs>, String> {
+    let mut commands = Vec::new();
+    let mut start = 0;
+    for end in separator_offsets(input).into_iter().chain([input.len()]) {
+        let args = split_cli_args(&input[start..end])?;
+        if !args.is_empty() {
+            commands.push(args);
+        }
+        start = end + 1;
+    }
+    Ok(commands)
+}
+
+/// Arguments joined by spaces for the log and the result pane.
+fn command_text<T: AsRef<[u8]>>(parts: &[T]) -> String {
+    let words: Vec<_> = parts.iter().map(|part| String::from_utf8_lossy(part.as_ref())).collect();
+    words.join(" ")
+}
+
+/// Commands whose arguments are credentials and are never written to the log.
+const SECRET_COMMANDS: [&str; 3] = ["AUTH", "HELLO", "MIGRATE"];
+
+pub(crate) fn build_cmd<T: AsRef<[u8]>>(parts: &[T]) -> redis::Cmd {
+    let name = String::from_utf8_lossy(parts[0].as_ref());
+    if SECRET_COMMANDS.iter().any(|secret| secret.eq_ignore_ascii_case(&name)) {
+        tracing::info!(command = %name, "executing command (arguments redacted)");
+    } else {
+        tracing::info!(command = %name, "executing: {}", command_text(parts));
+    }
+    let mut cmd = redis::cmd(&name);
+    for arg in &parts[1..] {
+        cmd.arg(arg.as_ref());
+    }
+    cmd
+}
+
+impl Default for CommandState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_commands, CommandOutcome, CommandReply, CommandSpec, CommandState};
+    use redis::Value;
+
+    fn spec(name: &str, arity: i64) -> CommandSpec {
+        CommandSpec {
+            name: name.to_string(),
+            arity,
+            summary: String::new(),
+            syntax: "key".to_string(),
+            writes: name.starts_with("SET"),
+        }
+    }
+
+    #[test]
+    fn tab_cycles_matching_commands_and_hints_current_one() {
+        let mut state = CommandState::new();
+        state.catalog = vec![spec("GET", 2), spec("GETDEL", 2), spec("SET", -3)];
+        "ge".chars().for_each(|c| state.push_char(c));
+        state.complete();
+        assert_eq!(state.input_buffer, "GET");
+        state.complete();
+        assert_eq!(state.input_buffer, "GETDEL");
+        state.complete();
+        assert_eq!(state.input_buffer, "GET");
+
+        state.push_char(' ');
+        assert_eq!(state.hint().as_deref(), Some("GET key (args: 1)"));
+
+        state.input_buffer = "s".to_string();
+        state.complete();
+        assert_eq!(state.input_buffer, "SET ");
+    }
+
+    #[test]
+    fn result_scrolls_within_bounds_and_saves_to_file() {
+        let mut state = CommandState::new();
+        state.last_result = Some((1..=25).map(|n| n.to_string()).collect::<Vec<_>>().join("\n"));
+        state.page_result(3);
+        assert_eq!(state.result_scroll, 24);
+        state.scroll_result(-30);
+        assert_eq!(state.result_scroll, 0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = state.save_result(dir.path()).unwrap();
+        let saved = std::fs::read_to_string(path).unwrap();
+        assert!(saved.starts_with("1\n2\n") && saved.ends_with("25\n"));
+    }
+
+    #[test]
+    fn result_form_follows_the_raw_toggle() {
+        let mut state = CommandState::new();
+        state.last_result = Some("report".to_string());
+        assert_eq!(state.result_form(), None);
+
+        state.replies = vec![CommandReply::new(&["PING"], Ok(redis::Value::SimpleString("PONG".to_string())))];
+        state.refresh_result();
+        assert_eq!(state.result_form(), Some("redis-cli style"));
+        assert_eq!(state.last_result.as_deref(), Some("PONG"));
+        state.toggle_raw_output();
+        assert_eq!(state.result_form(), Some("raw RESP"));
+        assert_eq!(state.last_result.as_deref(), Some("+PONG"));
+    }
+
+    fn words(input: &str) -> Vec<Vec<String>> {
+        let commands = split_commands(input).unwrap();
+        let text = |arg: Vec<u8>| String::from_utf8(arg).unwrap();
+        commands.into_iter().map(|args| args.into_iter().map(text).collect()).collect()
+    }
+
+    #[test]
+    fn quoted_separators_stay_inside_their_argument() {
+        assert_eq!(words(r#"SET k "a;b"; GET k"#), vec![vec!["SET", "k", "a;b"], vec!["GET", "k"]]);
+        assert_eq!(
+            words(r#"EVAL "redis.call('set','a','1'); return 1" 0"#),
+            vec![vec!["EVAL", "redis.call('set','a','1'); return 1", "0"]]
+        );
+        assert_eq!(words(r#"SET k 'it\'s; fine'; SET j "\";""#).len(), 2);
+        assert!(split_commands(r#"SET k "a;b"#).is_err());
+
+        let mut state = CommandState::new();
+        state.catalog = vec![spec("GET", 2)];
+        state.input_buffer = r#"SET k "x;y" ; g"#.to_string();
+        state.complete();
+        assert_eq!(state.input_buffer, r#"SET k "x;y" ; GET "#);
+        state.input_buffer = r#"SET k "open"#.to_string();
+        assert_eq!(state.take_input(), None);
+        assert!(state.last_result.unwrap().starts_with("Unbalanced quotes"));
+    }
+
+    #[test]
+    fn input_splits_into_pipelined_commands() {
+        assert_eq!(
+            words("SET a 1; GET a\n\nDEL  a ;"),
+            vec![vec!["SET", "a", "1"], vec!["GET", "a"], vec!["DEL", "a"]]
+        );
+
+        let mut state = CommandState::new();
+        state.catalog = vec![spec("GET", 2)];
+        state.input_buffer = "SET a 1; g".to_string();
+        state.complete();
+        assert_eq!(state.input_buffer, "SET a 1; GET ");
+        assert!(state.hint().unwrap().starts_with("GET key"));
+    }
+
+    #[test]
+    fn read_only_refuses_writes_unknown_commands_and_command_files() {
+        let mut state = CommandState::new();
+        state.catalog = vec![spec("GET", 2), spec("SET", -3)];
+        let mut refusal = |input: &str| {
+            state.input_buffer = input.to_string();
+            state.read_only_refusal()
+        };
+        assert_eq!(refusal("GET a; get b"), None);
+        assert_eq!(refusal("GET a; set a 1").as_deref(), Some("commands that may write (SET)"));
+        assert_eq!(refusal("FLUSHALL").as_deref(), Some("commands that may write (FLUSHALL)"));
+        assert_eq!(refusal("source cmds.txt").as_deref(), Some("command files"));
+    }
+
+    #[test]
+    fn empty_input_is_not_run_and_outcomes_show_from_the_top() {
+        let mut state = CommandState::new();
+        state.input_buffer = " ; \n".to_string();
+        assert_eq!(state.take_input(), None);
+        assert_eq!(state.last_result.as_deref(), Some("No command entered."));
+        state.input_buffer = "source cmds.txt".to_string();
+        assert_eq!(state.take_input().as_deref(), Some("source cmds.txt"));
+
+        state.result_scroll = 4;
+        state.show_outcome(CommandOutcome::Replies(vec![CommandReply::new(&["PING"], Ok(Value::Okay))]));
+        assert_eq!((state.result_scroll, state.last_result.as_deref()), (0, Some("OK")));
+        state.show_outcome(CommandOutcome::Report("1 ok".to_string()));
+        assert_eq!(state.result_form(), None);
+        assert_eq!(state.last_result.as_deref(), Some("1 ok"));
+    }
+}
@@ -0,0 +1,192 @@
+use redis::Value;
+
+use crate::app::value_format::{format_bytes_block, is_printable_text};
+
+/// Renders a reply the way redis-cli does: numbered, indented arrays, `key => value`
+/// map rows and typed scalars. Bulk strings go through `value_format`, so binary
+/// payloads are shown as hex instead of escaped bytes.
+pub fn render_reply(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) | Value::Set(items) | Value::Push { data: items, .. } => {
+            render_items(items)
+        }
+        Value::Map(pairs) => render_map(pairs),
+        Value::Attribute { data, .. } => render_reply(data),
+        scalar => render_scalar(scalar).lines().map(str::to_string).collect(),
+    }
+}
+
+fn render_scalar(value: &Value) -> String {
+    match value {
+        Value::Nil => "(nil)".to_string(),
+        Value::Int(n) => format!("(integer) {}", n),
+        Value::BulkString(bytes) if is_printable_text(bytes) && !bytes.contains(&b'\n') => {
+            format!("\"{}\"", String::from_utf8_lossy(bytes))
+        }
+        Value::BulkString(bytes) => format_bytes_block(bytes),
+        Value::SimpleString(text) => text.clone(),
+        Value::Okay => "OK".to_string(),
+        Value::Double(n) => format!("(double) {}", n),
+        Value::Boolean(b) => format!("({})", b),
+        Value::VerbatimString { text, .. } => text.clone(),
+        Value::ServerError(err) => format!("(error) {}", err),
+        other => format!("{:?}", other),
+    }
+}
+
+fn render_items(items: &[Value]) -> Vec<String> {
+    if items.is_empty() {
+        return vec!["(empty array)".to_string()];
+    }
+    let width = items.len().to_string().len();
+    items
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, item)| prefixed(&format!("{:>width$}) ", idx + 1), render_reply(item)))
+        .collect()
+}
+
+fn render_map(pairs: &[(Value, Value)]) -> Vec<String> {
+    if pairs.is_empty() {
+        return vec!["(empty map)".to_string()];
+    }
+    let keys: Vec<String> = pairs.iter().map(|(key, _)| render_scalar(key)).collect();
+    let width = keys.iter().map(|key| key.chars().count()).max().unwrap_or(0);
+    keys.iter()
+        .zip(pairs)
+        .flat_map(|(key, (_, value))| prefixed(&format!("{:<width$} => ", key), render_reply(value)))
+        .collect()
+}
+
+/// Puts `prefix` before the first line and aligns the following lines under it.
+fn prefixed(prefix: &str, lines: Vec<String>) -> Vec<String> {
+    let indent = " ".repeat(prefix.chars().count());
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            if idx == 0 {
+                format!("{}{}", prefix, line)
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// Renders a reply as its RESP frames, one per line with nested frames indented:
+/// type markers and lengths are kept and bulk strings are shown byte-exact, with
+/// escapes for control characters and hex when they are not UTF-8.
+pub fn render_raw(value: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+    push_raw(value, 0, &mut lines);
+    lines
+}
+
+fn push_raw(value: &Value, depth: usize, lines: &mut Vec<String>) {
+    let indent = "  ".repeat(depth);
+    let aggregate = |marker: char, items: &[Value], lines: &mut Vec<String>| {
+        lines.push(format!("{}{}{}", indent, marker, items.len()));
+        for item in items {
+            push_raw(item, depth + 1, lines);
+        }
+    };
+    match value {
+        Value::Array(items) => aggregate('*', items, lines),
+        Value::Set(items) => aggregate('~', items, lines),
+        Value::Push { data, .. } => aggregate('>', data, lines),
+        Value::Map(pairs) => {
+            lines.push(format!("{}%{}", indent, pairs.len()));
+            for (key, value) in pairs {
+                push_raw(key, depth + 1, lines);
+                push_raw(value, depth + 1, lines);
+            }
+        }
+        Value::Attribute { data, attributes } => {
+            lines.push(format!("{}|{}", indent, attributes.len()));
+            for (key, value) in attributes {
+                push_raw(key, depth + 1, lines);
+                push_raw(value, depth + 1, lines);
+            }
+            push_raw(data, depth, lines);
+        }
+        Value::Nil => lines.push(format!("{}$-1", indent)),
+        Value::Int(n) => lines.push(format!("{}:{}", indent, n)),
+        Value::BulkString(bytes) => {
+            lines.push(format!("{}${} {}", indent, bytes.len(), raw_bytes(bytes)))
+        }
+        Value::SimpleString(text) => lines.push(format!("{}+{}", indent, text)),
+        Value::Okay => lines.push(format!("{}+OK", indent)),
+        Value::Double(n) => lines.push(format!("{},{}", indent, n)),
+        Value::Boolean(b) => lines.push(format!("{}#{}", indent, if *b { 't' } else { 'f' })),
+        Value::VerbatimString { format, text } => lines.push(format!(
+            "{}={} {}:{}",
+            indent,
+            text.len() + 4,
+            format,
+            raw_bytes(text.as_bytes())
+        )),
+        Value::ServerError(err) => lines.push(format!("{}-{}", indent, err)),
+        other => lines.push(format!("{}({:?}", indent, other)),
+    }
+}
+
+/// Quoted bulk string with control characters escaped, or hex for non-UTF-8 bytes.
+fn raw_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => format!("\"{}\"", text.escape_debug()),
+        Err(_) => {
+            let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+            format!("(hex) {}", hex.join(" "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_raw, render_reply};
+    use redis::Value;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn nested_arrays_are_numbered_and_indented() {
+        let reply = Value::Array(vec![
+            bulk("a"),
+            Value::Array(vec![Value::Int(1), Value::Nil]),
+            Value::Array(vec![]),
+        ]);
+        assert_eq!(
+            render_reply(&reply),
+            vec!["1) \"a\"", "2) 1) (integer) 1", "   2) (nil)", "3) (empty array)"]
+        );
+    }
+
+    #[test]
+    fn maps_align_keys_and_binary_falls_back_to_hex() {
+        let reply = Value::Map(vec![
+            (bulk("id"), Value::Int(7)),
+            (bulk("payload"), Value::BulkString(vec![0xff, 0x00])),
+        ]);
+        let lines = render_reply(&reply);
+        assert_eq!(lines[0], "\"id\"      => (integer) 7");
+        assert!(lines[1].starts_with("\"payload\" => "));
+        assert!(lines[1].contains("FF 00"), "{:?}", lines);
+    }
+
+    #[test]
+    fn raw_view_keeps_frames_and_exact_bytes() {
+        let reply = Value::Array(vec![
+            bulk("a\r\nb"),
+            Value::BulkString(vec![0xff, 0x00]),
+            Value::Int(3),
+            Value::Nil,
+        ]);
+        assert_eq!(
+            render_raw(&reply),
+            vec!["*4", "  $4 \"a\\r\\nb\"", "  $2 (hex) FF 00", "  :3", "  $-1"]
+        );
+    }
+}
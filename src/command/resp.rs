@@ -0,0 +1,27 @@
+use redis::Value;
+
+/// Key/value pairs of a RESP3 map or a flat RESP2 `[k1, v1, k2, v2, ...]` array.
+pub fn pairs(value: &Value) -> Vec<(&Value, &Value)> {
+    match value {
+        Value::Map(map) => map.iter().map(|(k, v)| (k, v)).collect(),
+        Value::Array(flat) => flat.chunks_exact(2).map(|pair| (&pair[0], &pair[1])).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Value stored under the text key `wanted` in map pairs.
+pub fn map_field<'a>(fields: &[(&'a Value, &'a Value)], wanted: &str) -> Option<&'a Value> {
+    fields
+        .iter()
+        .find(|(key, _)| value_text(key).as_deref() == Some(wanted))
+        .map(|(_, value)| *value)
+}
+
+pub fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        Value::SimpleString(text) => Some(text.clone()),
+        Value::VerbatimString { text, .. } => Some(text.clone()),
+        _ => None,
+    }
+}
@@ -1,6 +1,11 @@
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::{fs, path::{Path, PathBuf}};
+use std::{fs, ops::RangeInclusive, path::{Path, PathBuf}};
+
+use crate::keymap::KeymapPreset;
+
+/// Narrowest and widest key list, as a percentage of the screen width.
+pub const KEY_PANEL_PERCENT_RANGE: RangeInclusive<u16> = 15..=60;
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct ConnectionProfile {
@@ -9,9 +14,71 @@ pub struct ConnectionProfile {
     pub db: Option<u8>,
     pub dev: Option<bool>,
     pub color: Option<String>,
+    /// `SCAN MATCH` pattern restricting which keys are loaded, e.g. `myapp:*` (default `*`).
+    pub scan_pattern: Option<String>,
+    /// `SCAN COUNT` hint for each batch (default 1000).
+    pub scan_count: Option<usize>,
+    /// Refuse server changes such as CONFIG SET from this profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only: Option<bool>,
+    /// Replica that serves browsing reads (SCAN, GET, HGETALL, ...); writes still go to `url`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replica_url: Option<String>,
+    /// Named scan filters offered by the preset picker (`v`), as `[[connections.scan_presets]]`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scan_presets: Vec<ScanPreset>,
+}
+
+/// Saved `SCAN` filter of a profile; unset fields fall back to `*`, any type, and the
+/// profile's COUNT.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ScanPreset {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// `SCAN TYPE`, e.g. `hash` or `json`.
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
 }
 
 impl ConnectionProfile {
+    /// One-off profile for `--url`: named after the host, never dev, and read-only. The DB
+    /// comes from the URL path (`redis://host:6379/2`), or from `?db=` for a socket
+    /// (`unix:///tmp/redis.sock?db=2`).
+    pub fn from_url(url: &str) -> Result<Self, String> {
+        let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+        if !matches!(parsed.scheme(), "redis" | "rediss" | "redis+unix" | "unix") {
+            return Err(format!("Unsupported URL scheme '{}'; use redis:// or rediss://", parsed.scheme()));
+        }
+        let db = if parsed.scheme().contains("unix") {
+            parsed.query_pairs().find(|(name, _)| name == "db").map(|(_, db)| db.into_owned())
+        } else {
+            Some(parsed.path().trim_start_matches('/').to_string()).filter(|path| !path.is_empty())
+        };
+        let db = db
+            .map(|db| db.parse::<u8>().map_err(|_| format!("Invalid DB '{}' in URL", db)))
+            .transpose()?;
+        let name = match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => parsed.path().to_string(),
+        };
+        Ok(Self {
+            name,
+            url: url.to_string(),
+            db,
+            dev: Some(false),
+            color: None,
+            scan_pattern: None,
+            scan_count: None,
+            read_only: Some(true),
+            replica_url: None,
+            scan_presets: Vec::new(),
+        })
+    }
+
     pub fn resolved_color(&self) -> Color {
         self.color
             .as_deref()
@@ -55,10 +122,70 @@ fn parse_color(spec: &str) -> Color {
     }
 }
 
+/// Application-wide tunables read from the optional `[settings]` table.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    /// Strings longer than this many bytes are paged with GETRANGE instead of fetched whole.
+    pub large_string_threshold: usize,
+    /// Number of bytes fetched per GETRANGE window when paging a large string.
+    pub string_chunk_size: usize,
+    /// Milliseconds between re-fetches of the viewed key while watch mode is on.
+    pub watch_interval_ms: u64,
+    /// Subscribe to keyspace notifications and update the key tree as keys change.
+    pub live_updates: bool,
+    /// Seconds between background rescans of the key tree level being viewed; 0 turns them off.
+    pub auto_refresh_secs: u64,
+    /// Width of the key list as a percentage of the screen; Ctrl+h / Ctrl+l change and save it.
+    pub key_panel_percent: u16,
+    /// Save the profile, DB, tree level and selected key on exit and reopen them on launch.
+    pub restore_session: bool,
+    /// Characters that split keys into tree levels, highest priority first. Each key is
+    /// split on the first of them it contains.
+    pub key_delimiters: String,
+    /// Start the key panel in the expandable tree mode (`t`) instead of drilling down.
+    pub tree_view: bool,
+    /// Key binding preset: `default`, or `vim` for `gg`/`G`, `Ctrl+d`/`Ctrl+u`, `zz` and counts.
+    pub keymap: KeymapPreset,
+    /// The value panel shows the TTL in red once fewer than this many seconds are left; 0 turns it off.
+    pub ttl_warning_secs: u64,
+    /// Seconds a viewed key is shown again from memory instead of re-read; 0 always re-reads.
+    pub key_cache_secs: u64,
+    /// Milliseconds the key selection must rest before the key under it is previewed, so
+    /// holding j/k skips the keys in between; 0 previews every key passed.
+    pub preview_delay_ms: u64,
+    /// Elements of a list, sorted set or stream fetched when it is opened; `n` in the value
+    /// panel loads the next page. 0 fetches them whole.
+    pub collection_page_size: usize,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            large_string_threshold: 1024 * 1024,
+            string_chunk_size: 64 * 1024,
+            watch_interval_ms: 1000,
+            live_updates: false,
+            auto_refresh_secs: 0,
+            key_panel_percent: 30,
+            restore_session: true,
+            key_delimiters: ":".to_string(),
+            tree_view: false,
+            keymap: KeymapPreset::default(),
+            ttl_warning_secs: 60,
+            key_cache_secs: 30,
+            preview_delay_ms: 150,
+            collection_page_size: 200,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Default, PartialEq)]
 pub struct Config {
     #[serde(rename = "connections")]
     pub profiles: Vec<ConnectionProfile>,
+    #[serde(default)]
+    pub settings: Settings,
 }
 
 impl Config {
@@ -70,7 +197,13 @@ impl Config {
                 db: Some(0),
                 dev: Some(true),
                 color: Some("green".to_string()),
+                scan_pattern: None,
+                scan_count: None,
+                read_only: None,
+                replica_url: None,
+                scan_presets: Vec::new(),
             }],
+            settings: Settings::default(),
         }
     }
 
@@ -165,6 +298,58 @@ impl Config {
     pub fn load_quiet(base_path_override: Option<&Path>) -> Self {
         Self::load_internal(base_path_override, false)
     }
+
+    /// Writes `key = value` into the `[settings]` table of the config file, leaving the
+    /// rest of the file, comments included, as the user wrote it.
+    pub fn persist_setting(base_path_override: Option<&Path>, key: &str, value: &str) -> Result<(), String> {
+        let path = Self::determine_config_file_path(base_path_override)
+            .ok_or_else(|| "Could not determine config directory.".to_string())?;
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read config file at '{}': {}", path.display(), e))?;
+        fs::write(&path, set_setting(&contents, key, value))
+            .map_err(|e| format!("Failed to write config file at '{}': {}", path.display(), e))
+    }
+}
+
+/// `contents` with `key = value` set in its `[settings]` table, which is appended when
+/// missing. A trailing comment on a replaced line is kept.
+fn set_setting(contents: &str, key: &str, value: &str) -> String {
+    let setting = format!("{} = {}", key, value);
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    let Some(start) = lines.iter().position(|line| line.trim() == "[settings]") else {
+        let mut updated = contents.trim_end().to_string();
+        if !updated.is_empty() {
+            updated.push_str("\n\n");
+        }
+        updated.push_str(&format!("[settings]\n{}\n", setting));
+        return updated;
+    };
+    let end = lines[start + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |idx| start + 1 + idx);
+    let existing = (start + 1..end).find(|&idx| {
+        lines[idx]
+            .split_once('=')
+            .is_some_and(|(name, _)| name.trim() == key)
+    });
+    match existing {
+        Some(idx) => {
+            let comment = lines[idx].find('#').map(|at| lines[idx][at..].to_string());
+            lines[idx] = match comment {
+                Some(comment) => format!("{} {}", setting, comment),
+                None => setting,
+            };
+        }
+        None => {
+            let at = (start + 1..end)
+                .rev()
+                .find(|&idx| !lines[idx].trim().is_empty())
+                .map_or(start + 1, |idx| idx + 1);
+            lines.insert(at, setting);
+        }
+    }
+    lines.join("\n") + "\n"
 }
 
 #[cfg(test)]
@@ -173,6 +358,37 @@ mod tests {
     use serial_test::serial;
     use tempfile::tempdir;
 
+    #[test]
+    fn url_profiles_take_the_db_from_the_path() {
+        let profile = ConnectionProfile::from_url("redis://:secret@cache.internal:6380/2").unwrap();
+        assert_eq!(profile.name, "cache.internal:6380");
+        assert_eq!(profile.db, Some(2));
+        assert_eq!((profile.dev, profile.read_only), (Some(false), Some(true)));
+        assert_eq!(ConnectionProfile::from_url("redis://localhost").unwrap().db, None);
+        assert!(ConnectionProfile::from_url("http://localhost").is_err());
+        assert!(ConnectionProfile::from_url("redis://localhost/x").is_err());
+    }
+
+    #[test]
+    fn socket_url_profiles_take_the_db_from_the_query() {
+        let profile = ConnectionProfile::from_url("unix:///tmp/redis.sock").unwrap();
+        assert_eq!((profile.name.as_str(), profile.db), ("/tmp/redis.sock", None));
+        assert_eq!(ConnectionProfile::from_url("unix:///tmp/redis.sock?db=3").unwrap().db, Some(3));
+        assert_eq!(ConnectionProfile::from_url("redis+unix:///tmp/redis.sock?db=1").unwrap().db, Some(1));
+        assert!(ConnectionProfile::from_url("unix:///tmp/redis.sock?db=x").is_err());
+    }
+
+    #[test]
+    fn scan_presets_read_as_nested_tables() {
+        let config = "[[connections]]\nname = \"A\"\nurl = \"redis://localhost\"\n\n\
+            [[connections.scan_presets]]\nname = \"sessions\"\npattern = \"session:*\"\ntype = \"hash\"\n";
+        let parsed: Config = toml::from_str(config).unwrap();
+        let presets = &parsed.profiles[0].scan_presets;
+        assert_eq!(presets.len(), 1);
+        assert_eq!((presets[0].key_type.as_deref(), presets[0].count), (Some("hash"), None));
+        assert_eq!(toml::from_str::<Config>(&toml::to_string(&parsed).unwrap()).unwrap(), parsed);
+    }
+
     #[test]
     #[serial]
     fn load_creates_default_when_missing() {
@@ -207,10 +423,44 @@ mod tests {
                 db: Some(1),
                 dev: Some(false),
                 color: Some("red".to_string()),
+                scan_pattern: None,
+                scan_count: None,
+                read_only: None,
+                replica_url: None,
+                scan_presets: Vec::new(),
             }],
+            settings: Settings {
+                large_string_threshold: 4096,
+                ..Settings::default()
+            },
         };
         fs::write(&cfg_file, toml::to_string(&custom_cfg).unwrap()).unwrap();
         let loaded = Config::load(Some(config_base_path));
         assert_eq!(loaded, custom_cfg);
     }
+
+    #[test]
+    fn set_setting_edits_settings_table_in_place() {
+        let config = "# mine\n[[connections]]\nname = \"A\"\n\n[settings]\nwatch_interval_ms = 500\n\n[extra]\n";
+        let added = set_setting(config, "key_panel_percent", "40");
+        assert_eq!(
+            added,
+            "# mine\n[[connections]]\nname = \"A\"\n\n[settings]\nwatch_interval_ms = 500\nkey_panel_percent = 40\n\n[extra]\n"
+        );
+        let replaced = set_setting(&added.replace("= 40", "= 40 # wide"), "key_panel_percent", "25");
+        assert!(replaced.contains("key_panel_percent = 25 # wide\n"));
+        assert_eq!(
+            set_setting("[[connections]]\nname = \"A\"\n", "key_panel_percent", "35"),
+            "[[connections]]\nname = \"A\"\n\n[settings]\nkey_panel_percent = 35\n"
+        );
+    }
+
+    #[test]
+    fn settings_default_when_table_missing() {
+        let cfg: Config = toml::from_str(
+            "[[connections]]\nname = \"A\"\nurl = \"redis://localhost:6379\"\n",
+        )
+        .unwrap();
+        assert_eq!(cfg.settings, Settings::default());
+    }
 }
@@ -0,0 +1,340 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+use serde_json::{json, Map, Value as JsonValue};
+use std::time::Duration;
+
+use crate::app::redis_client::RedisClient;
+use crate::app::value_decoder::{self, ValueDecoder};
+use crate::app::{decompress, value_format};
+use crate::command::{self, build_cmd, render_raw, render_reply, CommandSpec};
+use crate::benchmark::{self, BenchOptions, Workload, WorkloadReport};
+use crate::config::{self, ConnectionProfile};
+
+/// Subcommands that print to stdout instead of opening the TUI, for shell scripts.
+#[derive(Subcommand, Debug)]
+pub enum HeadlessCommand {
+    /// List the keys matching a pattern, one per line
+    Keys {
+        /// `SCAN MATCH` glob
+        #[arg(long, default_value = "*")]
+        pattern: String,
+        /// Print a JSON array instead
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the value of a key, decoded like the value view
+    Get {
+        key: String,
+        /// Print `{"key", "type", "ttl", "value"}` as JSON instead
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run one command, e.g. `exec "HGETALL user:42"`, and print the reply like the prompt
+    Exec {
+        /// The command as one string, or as separate words to keep spaces inside arguments
+        #[arg(required = true, num_args = 1.., trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+        /// Print the reply as raw RESP instead
+        #[arg(long)]
+        raw: bool,
+    },
+    /// Measure throughput and latency of SET/GET/INCR against a dev profile
+    Bench {
+        /// Workloads to run one after another, comma-separated
+        #[arg(long, value_enum, value_delimiter = ',', default_value = "set,get,incr")]
+        workloads: Vec<Workload>,
+        /// Connections sending commands at once
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+        /// Seconds spent on each workload
+        #[arg(long, default_value_t = 5)]
+        duration: u64,
+        /// Bytes per SET value
+        #[arg(long, default_value_t = 64)]
+        value_size: usize,
+        /// Distinct keys the workloads cycle through
+        #[arg(long, default_value_t = 10_000)]
+        keyspace: usize,
+        /// Print a JSON array of results instead
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Keys fetched per `SCAN` call.
+const SCAN_BATCH: usize = 1000;
+
+/// `XRANGE` reply: entry ids with their field/value pairs.
+type StreamEntries = Vec<(String, Vec<(String, Vec<u8>)>)>;
+
+/// Connects to the named (or first) profile, runs `command` and prints the result. An
+/// error reply from `exec` is an error too, so the process exits with a failure status.
+pub async fn run(command: HeadlessCommand, profile_name: Option<&str>) -> Result<()> {
+    match command {
+        HeadlessCommand::Bench { workloads, concurrency, duration, value_size, keyspace, json } => {
+            let options = BenchOptions {
+                workloads,
+                concurrency,
+                duration: Duration::from_secs(duration),
+                value_size,
+                keyspace,
+            };
+            let reports = benchmark::run(&find_profile(profile_name)?, &options).await?;
+            if json {
+                let results: Vec<JsonValue> = reports.iter().map(WorkloadReport::to_json).collect();
+                println!("{}", serde_json::to_string(&results)?);
+            } else {
+                reports.iter().for_each(|report| println!("{}", report.line()));
+            }
+        }
+        HeadlessCommand::Exec { command, raw } => {
+            let profile = find_profile(profile_name)?;
+            let mut con = connect_profile(&profile).await?;
+            let words = command_words(&command);
+            if words.is_empty() {
+                return Err(anyhow!("Command is empty."));
+            }
+            if profile.read_only.unwrap_or(false) {
+                let catalog = command::fetch_command_catalog(&mut con).await;
+                if let Some(refused) = exec_refusal(&profile, &catalog, &words) {
+                    return Err(anyhow!(refused));
+                }
+            }
+            let reply: Value = build_cmd(&words).query_async(&mut con).await?;
+            let lines = if raw { render_raw(&reply) } else { render_reply(&reply) };
+            if matches!(reply, Value::ServerError(_)) {
+                return Err(anyhow!("{}", lines.join("\n")));
+            }
+            lines.iter().for_each(|line| println!("{}", line));
+        }
+        HeadlessCommand::Keys { pattern, json } => {
+            let mut con = connect(profile_name).await?;
+            let keys = scan_keys(&mut con, &pattern).await?;
+            if json {
+                println!("{}", serde_json::to_string(&keys)?);
+            } else {
+                keys.iter().for_each(|key| println!("{}", key));
+            }
+        }
+        HeadlessCommand::Get { key, json } => {
+            let mut con = connect(profile_name).await?;
+            let document = key_document(&mut con, &key).await?;
+            if json {
+                println!("{}", serde_json::to_string(&document)?);
+            } else {
+                println!("{}", plain_text(&document["value"]));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The named profile, or the first one.
+fn find_profile(profile_name: Option<&str>) -> Result<ConnectionProfile> {
+    let app_config = config::Config::load_quiet(None);
+    match profile_name {
+        Some(name) => app_config.profiles.into_iter().find(|p| p.name == name),
+        None => app_config.profiles.into_iter().next(),
+    }
+    .ok_or_else(|| anyhow!("Profile '{}' not found in configuration.", profile_name.unwrap_or("Default")))
+}
+
+/// Primary connection to the profile, on the profile's DB.
+pub async fn connect(profile_name: Option<&str>) -> Result<MultiplexedConnection> {
+    connect_profile(&find_profile(profile_name)?).await
+}
+
+async fn connect_profile(profile: &ConnectionProfile) -> Result<MultiplexedConnection> {
+    let mut client = RedisClient::new();
+    client.connect_to_profile(profile, true, None).await?;
+    client.connection.ok_or_else(|| anyhow!("Not connected"))
+}
+
+/// Why a read-only `profile` refuses `words`, like the command prompt does.
+fn exec_refusal(profile: &ConnectionProfile, catalog: &[CommandSpec], words: &[&str]) -> Option<String> {
+    let refused = command::read_only_refusal(catalog, [words.to_vec()])?;
+    Some(format!("Profile '{}' is read-only; {} are disabled.", profile.name, refused))
+}
+
+async fn scan_keys(con: &mut MultiplexedConnection, pattern: &str) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut cursor: u64 = 0;
+    loop {
+        let (next_cursor, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(SCAN_BATCH)
+            .query_async(con)
+            .await?;
+        keys.extend(batch);
+        if next_cursor == 0 {
+            break;
+        }
+        cursor = next_cursor;
+    }
+    keys.sort();
+    keys.dedup();
+    Ok(keys)
+}
+
+/// `key` as `get --json` prints it, and the key action menu exports it: its name, type,
+/// TTL and value.
+pub async fn key_document(con: &mut MultiplexedConnection, key: &str) -> Result<JsonValue> {
+    let key_type: String = redis::cmd("TYPE").arg(key).query_async(con).await?;
+    if key_type == "none" {
+        return Err(anyhow!("Key '{}' not found.", key));
+    }
+    let value = fetch_value(con, key, &key_type).await?;
+    let ttl: i64 = redis::cmd("TTL").arg(key).query_async(con).await?;
+    Ok(json!({ "key": key, "type": key_type, "ttl": ttl, "value": value }))
+}
+
+/// The whole value of `key` as JSON: strings as text (or their decoded form), hashes as
+/// objects, lists and sets as arrays, sorted sets and streams as arrays of objects.
+async fn fetch_value(con: &mut MultiplexedConnection, key: &str, key_type: &str) -> Result<JsonValue> {
+    Ok(match key_type {
+        "string" => {
+            let bytes: Vec<u8> = redis::cmd("GET").arg(key).query_async(con).await?;
+            bytes_to_json(&bytes)
+        }
+        "hash" => {
+            let fields: Vec<(Vec<u8>, Vec<u8>)> = redis::cmd("HGETALL").arg(key).query_async(con).await?;
+            let mut map = Map::new();
+            for (field, value) in fields {
+                map.insert(bytes_to_text(&field), bytes_to_json(&value));
+            }
+            JsonValue::Object(map)
+        }
+        "list" => {
+            let items: Vec<Vec<u8>> = redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query_async(con).await?;
+            JsonValue::Array(items.iter().map(|item| bytes_to_json(item)).collect())
+        }
+        "set" => {
+            let mut members: Vec<Vec<u8>> = redis::cmd("SMEMBERS").arg(key).query_async(con).await?;
+            members.sort();
+            JsonValue::Array(members.iter().map(|member| bytes_to_json(member)).collect())
+        }
+        "zset" => {
+            let members: Vec<(Vec<u8>, f64)> = redis::cmd("ZRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(-1)
+                .arg("WITHSCORES")
+                .query_async(con)
+                .await?;
+            let rows = members
+                .iter()
+                .map(|(member, score)| json!({ "member": bytes_to_json(member), "score": score }));
+            JsonValue::Array(rows.collect())
+        }
+        "stream" => {
+            let entries: StreamEntries =
+                redis::cmd("XRANGE").arg(key).arg("-").arg("+").query_async(con).await?;
+            let rows = entries.into_iter().map(|(id, fields)| {
+                let fields: Map<String, JsonValue> = fields
+                    .into_iter()
+                    .map(|(field, value)| (field, bytes_to_json(&value)))
+                    .collect();
+                json!({ "id": id, "fields": fields })
+            });
+            JsonValue::Array(rows.collect())
+        }
+        "ReJSON-RL" => {
+            let raw: String = redis::cmd("JSON.GET").arg(key).query_async(con).await?;
+            serde_json::from_str(&raw).unwrap_or(JsonValue::String(raw))
+        }
+        other => {
+            let reply: Value = redis::cmd("DUMP").arg(key).query_async(con).await?;
+            let dump = match reply {
+                Value::BulkString(bytes) => value_format::format_bytes_inline(&bytes),
+                _ => String::new(),
+            };
+            json!({ "unsupported_type": other, "dump": dump })
+        }
+    })
+}
+
+/// Words of an `exec` command: a single argument is split on whitespace, several are
+/// taken as they are.
+fn command_words(command: &[String]) -> Vec<&str> {
+    match command {
+        [single] => single.split_whitespace().collect(),
+        words => words.iter().map(String::as_str).collect(),
+    }
+}
+
+/// A stored value decoded like the value view does: compressed payloads are inflated and
+/// MessagePack containers become JSON; other bytes become text, or hex. A payload too large
+/// to inflate whole is exported as stored rather than cut short.
+fn bytes_to_json(bytes: &[u8]) -> JsonValue {
+    let inflated = decompress::decompress(bytes)
+        .filter(|output| !output.truncated)
+        .map(|output| output.bytes);
+    let bytes = inflated.as_deref().unwrap_or(bytes);
+    if let Some(decoded) = value_decoder::decode(bytes, ValueDecoder::Auto) {
+        return decoded.json;
+    }
+    JsonValue::String(bytes_to_text(bytes))
+}
+
+/// UTF-8 as it is, anything else as hex.
+fn bytes_to_text(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => value_format::format_bytes_inline(bytes),
+    }
+}
+
+/// Plain-text output: strings as they are, anything structured as pretty JSON.
+fn plain_text(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text.clone(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_bytes_decode_like_the_value_view() {
+        assert_eq!(bytes_to_json(b"hello"), JsonValue::String("hello".to_string()));
+        // fixmap {"a": 1}
+        assert_eq!(bytes_to_json(&[0x81, 0xa1, b'a', 0x01]), json!({ "a": 1 }));
+        assert_eq!(bytes_to_json(&[0xff, 0x00]), JsonValue::String("FF 00".to_string()));
+        assert_eq!(plain_text(&json!("text")), "text");
+        assert_eq!(plain_text(&json!([1])), "[\n  1\n]");
+    }
+
+    #[test]
+    fn exec_takes_one_string_or_separate_words() {
+        let one = vec!["HGETALL  user:42".to_string()];
+        assert_eq!(command_words(&one), ["HGETALL", "user:42"]);
+        let words = vec!["SET".to_string(), "greeting".to_string(), "hello world".to_string()];
+        assert_eq!(command_words(&words), ["SET", "greeting", "hello world"]);
+        assert!(command_words(&["  ".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn exec_refuses_writes_on_read_only_profiles() {
+        let profile = ConnectionProfile::from_url("redis://cache.internal").unwrap();
+        let get = CommandSpec {
+            name: "GET".to_string(),
+            arity: 2,
+            summary: String::new(),
+            syntax: "key".to_string(),
+            writes: false,
+        };
+        let catalog = [get];
+        assert_eq!(exec_refusal(&profile, &catalog, &["get", "foo"]), None);
+        assert_eq!(
+            exec_refusal(&profile, &catalog, &["DEL", "foo"]).as_deref(),
+            Some("Profile 'cache.internal' is read-only; commands that may write (DEL) are disabled.")
+        );
+    }
+}
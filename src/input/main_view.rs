@@ -0,0 +1,215 @@
+//! The key list, value and database panes once no modal or prompt is open.
+
+use crossterm::event::KeyCode;
+
+use crate::app;
+use crate::keymap::{Action, KeyContext};
+
+const VALUE_NAVIGATION_PAGE_SIZE: usize = 10;
+const VALUE_HORIZONTAL_SCROLL_STEP: i32 = 8;
+
+/// Keymap contexts for the main view: the focused pane's bindings, then the global ones.
+pub(super) fn main_view_contexts(app: &app::App) -> Vec<KeyContext> {
+    if app.is_key_view_focused {
+        vec![KeyContext::KeyView, KeyContext::Global]
+    } else if app.is_value_view_focused {
+        vec![KeyContext::ValueView, KeyContext::Global]
+    } else {
+        vec![KeyContext::Global]
+    }
+}
+
+/// Runs a main-view action resolved through the keymap, `count` times for movements when a
+/// vim-style count was typed. Quitting and opening the command prompt need the terminal,
+/// so `handle_key` reports those to the event loop instead.
+pub(super) fn handle_main_view_action(app: &mut app::App, action: Action, count: Option<usize>) {
+    // A breadcrumb selection only lasts until Enter or any other key
+    if !matches!(action, Action::BreadcrumbLeft | Action::BreadcrumbRight | Action::Activate) {
+        app.breadcrumb_cursor = None;
+    }
+    match action {
+        Action::Quit | Action::CommandPrompt => {}
+        Action::Help => app.help.open(),
+        Action::Down => {
+            let count = count.unwrap_or(1);
+            if app.is_value_view_focused {
+                (0..count).for_each(|_| app.select_next_value_item());
+            } else if app.is_key_view_focused {
+                if app.tree_view.is_active && !app.search_state.is_active {
+                    app.move_tree_selection(count as isize);
+                } else {
+                    (0..count).for_each(|_| app.next_key_in_view());
+                }
+                app.schedule_auto_preview();
+            } else {
+                app.next_db();
+            }
+        }
+        Action::Up => {
+            let count = count.unwrap_or(1);
+            if app.is_value_view_focused {
+                (0..count).for_each(|_| app.select_previous_value_item());
+            } else if app.is_key_view_focused {
+                if app.tree_view.is_active && !app.search_state.is_active {
+                    app.move_tree_selection(-(count as isize));
+                } else {
+                    (0..count).for_each(|_| app.previous_key_in_view());
+                }
+                app.schedule_auto_preview();
+            } else {
+                app.previous_db();
+            }
+        }
+        Action::Activate => {
+            if app.search_state.is_active {
+                app.activate_selected_filtered_key();
+            } else if app.is_key_view_focused {
+                let handled = app.jump_to_breadcrumb_cursor() || (app.tree_view.is_active && app.toggle_tree_folder());
+                if !handled {
+                    app.activate_selected_key();
+                }
+            } else if !app.is_value_view_focused {
+                app.trigger_apply_selected_db();
+            }
+        }
+        Action::FocusNext => app.cycle_focus_forward(),
+        Action::FocusPrevious => app.cycle_focus_backward(),
+        Action::FocusDb => app.focus_db(),
+        Action::FocusKeys => app.focus_keys(),
+        Action::FocusValues => app.focus_values(),
+        Action::NarrowKeys => app.resize_key_panel(false),
+        Action::WidenKeys => app.resize_key_panel(true),
+        Action::FilterValues if app.value_viewer.has_collection_rows() => app.value_viewer.open_filter_prompt(),
+        Action::Search | Action::FilterValues => app.enter_search_mode(),
+        Action::GotoKey => app.open_goto_key_prompt(),
+        Action::Profiles => app.toggle_profile_selector(),
+        Action::Stats => app.toggle_stats_view(),
+        Action::DoctorReport if app.show_stats => app.open_doctor_report(),
+        Action::StatsReport if app.show_stats => app.open_stats_report(),
+        Action::CopyKeyName => app::app_clipboard::copy_selected_key_name_to_clipboard(app),
+        Action::CopyValue => app::app_clipboard::copy_selected_key_value_to_clipboard(app),
+        Action::CopyJson => app::app_clipboard::copy_key_as_json_to_clipboard(app),
+        Action::CopyCommand => app::app_clipboard::copy_key_as_command_to_clipboard(app),
+        Action::Paste => app.open_paste(),
+        Action::HexView => app.toggle_hex_view(),
+        Action::CycleDecoder => app.cycle_value_decoder(),
+        Action::ToggleWrap => app.value_viewer.toggle_wrap(),
+        Action::Watch => app.toggle_watch(),
+        Action::StreamFollow => app.toggle_stream_follow(),
+        Action::StreamPause => app.toggle_stream_pause(),
+        Action::ConsumerGroups => app.open_consumer_groups(),
+        Action::KeyInfo => app.open_key_info(),
+        Action::KeySizes => app.toggle_key_sizes(),
+        Action::ScanPattern => app.open_scan_prompt(),
+        Action::ScanPresets => app.open_scan_presets(),
+        Action::RefreshKeys => app.refresh_key_tree(),
+        Action::LiveUpdates => app.toggle_live_updates(),
+        Action::LoadMore if app.value_viewer.collection_page.is_some() => {
+            app.load_more_collection();
+        }
+        Action::LoadMore => app.notifications.info("The whole value is already loaded."),
+        Action::ScriptRunner => app.open_script_runner(),
+        Action::Functions => app.open_functions(),
+        Action::RediSearch => app.open_redisearch(),
+        Action::PubSub => app.open_pubsub(),
+        Action::Monitor => app.open_monitor(),
+        Action::ConfigEditor => app.open_config_editor(),
+        Action::LatencyPanel => app.open_latency_panel(),
+        Action::Analysis => app.open_analysis(app::state_analysis::AnalysisView::BigKeys),
+        Action::RandomSample => app.open_random_sample(),
+        Action::Snapshots => app.open_snapshots(),
+        Action::Compare => app.open_compare(),
+        Action::ToggleBookmark => app.toggle_bookmark(),
+        Action::Bookmarks => app.toggle_bookmarks_panel(),
+        Action::Timestamps => app.value_viewer.toggle_timestamps(),
+        Action::CycleSort if app.value_viewer.has_collection_rows() => app.value_viewer.cycle_sort(),
+        Action::LineNumbers => app.value_viewer.toggle_line_numbers(),
+        Action::NextChunk if app.value_viewer.string_chunk.is_some() => {
+            app.page_string_chunk(true);
+        }
+        Action::PreviousChunk if app.value_viewer.string_chunk.is_some() => {
+            app.page_string_chunk(false);
+        }
+        Action::ExtendSelectionDown => app.next_key_in_view_with_shift(),
+        Action::ExtendSelectionUp => app.previous_key_in_view_with_shift(),
+        Action::ToggleSelection => app.toggle_current_selection(),
+        Action::Delete => app.initiate_delete_selected_item(),
+        Action::ParentFolder if app.tree_view.is_active => app.select_tree_parent(),
+        Action::ParentFolder => app.navigate_key_tree_up(),
+        Action::BreadcrumbLeft => app.move_breadcrumb_cursor(-1),
+        Action::BreadcrumbRight => app.move_breadcrumb_cursor(1),
+        Action::ToggleTreeView => app.toggle_tree_view(),
+        Action::TreeExpand if app.tree_view.is_active => app.tree_expand(),
+        Action::TreeExpand => app.activate_selected_key(),
+        Action::TreeCollapse if app.tree_view.is_active => app.tree_collapse(),
+        Action::TreeCollapse => app.navigate_key_tree_up(),
+        Action::Top => app.select_list_top(),
+        Action::Bottom => app.select_list_bottom(count),
+        Action::HalfPageDown => app.move_list_half_page(true),
+        Action::HalfPageUp => app.move_list_half_page(false),
+        Action::CenterSelection => app.center_list_selection(),
+        Action::Escape => {
+            if app.bulk_delete.is_some() {
+                app.cancel_bulk_delete();
+            } else if app.prefix_walk.is_some() {
+                app.stop_prefix_walk();
+            } else if app.key_scan.is_some() {
+                app.cancel_key_scan();
+            } else if !app.selected_indices.is_empty() {
+                app.clear_multi_selection();
+            } else {
+                app.navigate_to_key_tree_root();
+            }
+        }
+        Action::GotoLine => app.open_goto_line_prompt(),
+        Action::PfAdd => app.open_pfadd_prompt(),
+        Action::ActionMenu => app.open_action_menu(),
+        Action::Probe => app.open_probe_prompt(),
+        Action::ScrollLeft => app.value_viewer.scroll(0, -VALUE_HORIZONTAL_SCROLL_STEP),
+        Action::ScrollRight => app.value_viewer.scroll(0, VALUE_HORIZONTAL_SCROLL_STEP),
+        Action::PageDown => app.select_page_down_value_item(VALUE_NAVIGATION_PAGE_SIZE),
+        Action::PageUp => app.select_page_up_value_item(VALUE_NAVIGATION_PAGE_SIZE),
+        _ => {}
+    }
+}
+
+/// Navigation inside the hex viewer. Returns false for keys it does not own so
+/// the normal bindings (quit, focus, copy, ...) keep working.
+pub(super) fn handle_hex_view_key(app: &mut app::App, code: KeyCode) -> bool {
+    if app.value_viewer.hex_view.goto_input.is_some() {
+        if code == KeyCode::Enter {
+            app.submit_hex_goto();
+            return true;
+        }
+        let hex_view = &mut app.value_viewer.hex_view;
+        match code {
+            KeyCode::Esc => hex_view.goto_input = None,
+            KeyCode::Backspace => {
+                hex_view.goto_input.as_mut().map(String::pop);
+            }
+            KeyCode::Char(c) => hex_view.goto_input.get_or_insert_with(String::new).push(c),
+            _ => {}
+        }
+        return true;
+    }
+
+    let len = app.value_viewer.raw_value.as_ref().map_or(0, Vec::len);
+    let hex_view = &mut app.value_viewer.hex_view;
+    let row = app::hex_view::HEX_ROW_BYTES as isize;
+
+    match code {
+        KeyCode::Left | KeyCode::Char('h') => hex_view.move_cursor(-1, len),
+        KeyCode::Right | KeyCode::Char('l') => hex_view.move_cursor(1, len),
+        KeyCode::Up | KeyCode::Char('k') => hex_view.move_cursor(-row, len),
+        KeyCode::Down | KeyCode::Char('j') => hex_view.move_cursor(row, len),
+        KeyCode::PageUp => hex_view.move_cursor(-row * VALUE_NAVIGATION_PAGE_SIZE as isize, len),
+        KeyCode::PageDown => hex_view.move_cursor(row * VALUE_NAVIGATION_PAGE_SIZE as isize, len),
+        KeyCode::Home => hex_view.jump_to(0, len),
+        KeyCode::End => hex_view.jump_to(len, len),
+        KeyCode::Char('v') => hex_view.toggle_selection(),
+        KeyCode::Char('g') => hex_view.goto_input = Some(String::new()),
+        KeyCode::Esc if hex_view.selection_anchor.is_some() => hex_view.selection_anchor = None,
+        _ => return false,
+    }
+    true
+}
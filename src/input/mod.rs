@@ -0,0 +1,116 @@
+//! Key handling for the TUI: routes each key press to the open modal, prompt or panel,
+//! or through the keymap to a main-view action.
+
+mod main_view;
+mod panels;
+mod prompts;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app;
+use crate::keymap::Action;
+
+/// What the event loop has to do after a key, beyond the state changes made on `app`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyOutcome {
+    Handled,
+    Quit,
+    /// The command prompt opened and needs the terminal cursor.
+    ShowCursor,
+    /// The command prompt closed.
+    HideCursor,
+}
+
+/// Handles one key press. The innermost open modal or prompt owns the key; otherwise it
+/// is resolved through the keymap for the focused pane.
+pub fn handle_key(app: &mut app::App, key: KeyEvent) -> KeyOutcome {
+    if app.profile_state.is_active {
+        return panels::handle_profile_selector_key(app, key.code);
+    } else if app.help.is_active {
+        panels::handle_help_key(app, key.code);
+    } else if app.bookmarks_panel.is_active {
+        return panels::handle_bookmarks_key(app, key.code);
+    } else if app.delete_dialog.show_confirmation_dialog {
+        panels::handle_delete_dialog_key(app, key.code);
+    } else if app.key_info.is_some() {
+        // Any key dismisses the metadata popup
+        app.close_key_info();
+    } else if app.action_menu.is_active {
+        panels::handle_action_menu_key(app, key);
+    } else if app.paste_preview.is_active {
+        panels::handle_paste_preview_key(app, key.code);
+    } else if app.folder_copy.is_active {
+        panels::handle_folder_copy_key(app, key.code);
+    } else if app.scan_presets.is_active {
+        panels::handle_scan_presets_key(app, key.code);
+    } else if app.script_runner.is_active {
+        panels::handle_script_runner_key(app, key);
+    } else if app.functions.is_active {
+        panels::handle_functions_key(app, key);
+    } else if app.redisearch.is_active {
+        panels::handle_redisearch_key(app, key);
+    } else if app.pubsub.is_active {
+        panels::handle_pubsub_key(app, key);
+    } else if app.monitor.is_active {
+        panels::handle_monitor_key(app, key);
+    } else if app.config_editor.is_active {
+        panels::handle_config_editor_key(app, key);
+    } else if app.doctor_report.is_active {
+        panels::handle_doctor_report_key(app, key.code);
+    } else if app.stats_report.is_active {
+        panels::handle_stats_report_key(app, key.code);
+    } else if app.analysis.is_active {
+        panels::handle_analysis_key(app, key.code);
+    } else if app.compare.is_active {
+        panels::handle_compare_key(app, key);
+    } else if app.snapshots.is_active {
+        panels::handle_snapshots_key(app, key);
+    } else if app.random_sample.is_active {
+        panels::handle_random_sample_key(app, key.code);
+    } else if app.latency_panel.is_active {
+        if matches!(key.code, KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('H')) {
+            app.latency_panel.close();
+        }
+    } else if app.consumer_groups.is_active {
+        panels::handle_consumer_group_key(app, key.code);
+    } else if app.command_state.is_active {
+        if app.keymap.is(Action::CommandClose, key) {
+            app.close_command_prompt();
+            return KeyOutcome::HideCursor;
+        }
+        prompts::handle_command_prompt_key(app, key);
+    } else if app.search_state.is_active {
+        prompts::handle_search_key(app, key);
+    } else if app.value_viewer.filter_input_active {
+        prompts::handle_value_filter_key(app, key.code);
+    } else if app.value_viewer.goto_line_input.is_some() {
+        prompts::handle_goto_line_key(app, key.code);
+    } else if app.value_viewer.pfadd_input.is_some() {
+        prompts::handle_pfadd_key(app, key);
+    } else if app.value_viewer.probe_input.is_some() {
+        prompts::handle_probe_key(app, key);
+    } else if app.scan_prompt.is_some() {
+        prompts::handle_scan_prompt_key(app, key.code);
+    } else if app.goto_key_prompt.is_some() {
+        prompts::handle_goto_key_prompt_key(app, key.code);
+    } else if app.keymap.is(Action::GotoKey, key) {
+        app.open_goto_key_prompt();
+    } else if app.is_value_view_focused
+        && app.value_viewer.hex_view.enabled
+        && main_view::handle_hex_view_key(app, key.code)
+    {
+        // Consumed by the hex viewer
+    } else if let Some((action, count)) =
+        app.keymap.resolve(&main_view::main_view_contexts(app), key, &mut app.pending_keys)
+    {
+        match action {
+            Action::Quit => return KeyOutcome::Quit,
+            Action::CommandPrompt => {
+                app.open_command_prompt();
+                return KeyOutcome::ShowCursor;
+            }
+            action => main_view::handle_main_view_action(app, action, count),
+        }
+    }
+    KeyOutcome::Handled
+}
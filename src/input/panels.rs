@@ -0,0 +1,499 @@
+//! Modal panels and dialogs; each owns every key while it is open.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::KeyOutcome;
+use crate::app;
+use crate::line_edit::{self, LineEdit};
+
+pub(super) fn handle_profile_selector_key(app: &mut app::App, code: KeyCode) -> KeyOutcome {
+    match code {
+        KeyCode::Char('q') => return KeyOutcome::Quit,
+        KeyCode::Char('p') | KeyCode::Esc => app.toggle_profile_selector(),
+        KeyCode::Char('j') | KeyCode::Down => app.next_profile_in_list(),
+        KeyCode::Char('k') | KeyCode::Up => app.previous_profile_in_list(),
+        KeyCode::Enter => {
+            app.select_profile_and_connect();
+        }
+        _ => {}
+    }
+    KeyOutcome::Handled
+}
+
+pub(super) fn handle_bookmarks_key(app: &mut app::App, code: KeyCode) -> KeyOutcome {
+    match code {
+        KeyCode::Char('q') => return KeyOutcome::Quit,
+        KeyCode::Char('b') | KeyCode::Esc => app.toggle_bookmarks_panel(),
+        KeyCode::Char('j') | KeyCode::Down => app.next_bookmark(),
+        KeyCode::Char('k') | KeyCode::Up => app.previous_bookmark(),
+        KeyCode::Char('d') => app.remove_selected_bookmark(),
+        KeyCode::Enter => app.open_selected_bookmark(),
+        _ => {}
+    }
+    KeyOutcome::Handled
+}
+
+pub(super) fn handle_delete_dialog_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => {
+            app.confirm_delete_item();
+        }
+        // Esc first stops a preview scan, keeping what it counted
+        KeyCode::Esc if app.delete_preview_running() => {
+            app.stop_prefix_walk();
+        }
+        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => app.cancel_delete_item(),
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            app.confirm_delete_item();
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') if app.delete_can_preview() => {
+            app.preview_delete_item();
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn handle_doctor_report_key(app: &mut app::App, code: KeyCode) {
+    let report = &mut app.doctor_report;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => report.is_active = false,
+        KeyCode::Char('r') => app.open_doctor_report(),
+        KeyCode::Down | KeyCode::Char('j') => report.scroll_by(1),
+        KeyCode::Up | KeyCode::Char('k') => report.scroll_by(-1),
+        KeyCode::PageDown => report.scroll_by(10),
+        KeyCode::PageUp => report.scroll_by(-10),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_stats_report_key(app: &mut app::App, code: KeyCode) {
+    let report = &mut app.stats_report;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => report.is_active = false,
+        KeyCode::Char('r') => app.open_stats_report(),
+        KeyCode::Char('y') => app::app_clipboard::copy_stats_report_to_clipboard(app),
+        KeyCode::Char('s') => app.save_stats_report(),
+        KeyCode::Down | KeyCode::Char('j') => report.scroll_by(1),
+        KeyCode::Up | KeyCode::Char('k') => report.scroll_by(-1),
+        KeyCode::PageDown => report.scroll_by(10),
+        KeyCode::PageUp => report.scroll_by(-10),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_analysis_key(app: &mut app::App, code: KeyCode) {
+    use app::state_analysis::AnalysisView;
+
+    let view = app.analysis.view;
+    let by_prefix = matches!(view, AnalysisView::MemoryByPrefix | AnalysisView::TtlDistribution);
+    match code {
+        // Esc first stops a running walk, keeping its partial reports
+        KeyCode::Esc if app.analysis.stop() => {}
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.analysis.is_active = false;
+            app.analysis.stop();
+            app.analysis.hot_keys.stop_monitor();
+        }
+        KeyCode::Tab => app.analysis.view = app.analysis.view.next(),
+        KeyCode::Enter if !by_prefix => app.open_selected_analysis_key(),
+        KeyCode::Char('s') if view == AnalysisView::HotKeys => app.start_hot_key_monitor(),
+        KeyCode::Char('+') | KeyCode::Right if by_prefix => app.analysis.change_depth(1),
+        KeyCode::Char('-') | KeyCode::Left if by_prefix => app.analysis.change_depth(-1),
+        KeyCode::Char('x') if view == AnalysisView::MemoryByPrefix => app.export_prefix_memory(),
+        KeyCode::Char('r') => app.restart_analysis(),
+        KeyCode::Down | KeyCode::Char('j') => app.analysis.select(1),
+        KeyCode::Up | KeyCode::Char('k') => app.analysis.select(-1),
+        KeyCode::PageDown => app.analysis.select(10),
+        KeyCode::PageUp => app.analysis.select(-10),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_random_sample_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => app.random_sample.is_active = false,
+        KeyCode::Enter => app.open_selected_random_key(),
+        KeyCode::Char('r') => app.request_random_sample(),
+        KeyCode::Char('+') | KeyCode::Right => app.random_sample.change_size(1),
+        KeyCode::Char('-') | KeyCode::Left => app.random_sample.change_size(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.random_sample.select(1),
+        KeyCode::Up | KeyCode::Char('k') => app.random_sample.select(-1),
+        KeyCode::PageDown => app.random_sample.select(10),
+        KeyCode::PageUp => app.random_sample.select(-10),
+        _ => {}
+    }
+}
+
+/// Keys for the help overlay: scrolling, and anything that closes it.
+pub(super) fn handle_help_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => app.help.close(),
+        KeyCode::Down | KeyCode::Char('j') => app.help.scroll(1),
+        KeyCode::Up | KeyCode::Char('k') => app.help.scroll(-1),
+        KeyCode::PageDown => app.help.scroll(10),
+        KeyCode::PageUp => app.help.scroll(-10),
+        _ => {}
+    }
+}
+
+/// Keys for the consumer group inspector; it is modal, so every key is consumed.
+pub(super) fn handle_consumer_group_key(app: &mut app::App, code: KeyCode) {
+    use app::state_consumer_groups::GroupPrompt;
+
+    let state = &mut app.consumer_groups;
+    if state.prompt.is_some() {
+        match code {
+            KeyCode::Enter => app.submit_consumer_group_prompt(),
+            KeyCode::Esc => state.prompt = None,
+            KeyCode::Backspace => {
+                state.input.pop();
+            }
+            KeyCode::Char(c) => state.input.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    state.status = None;
+    match code {
+        KeyCode::Esc | KeyCode::Char('C') | KeyCode::Char('q') => state.close(),
+        KeyCode::Left | KeyCode::Char('h') => {
+            state.select_group(false);
+            app.fetch_consumer_groups();
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            state.select_group(true);
+            app.fetch_consumer_groups();
+        }
+        KeyCode::Down | KeyCode::Char('j') => state.select_pending(true),
+        KeyCode::Up | KeyCode::Char('k') => state.select_pending(false),
+        KeyCode::Char(' ') => state.toggle_mark(),
+        KeyCode::Char('a') => app.ack_pending_entries(),
+        KeyCode::Char('c') => state.open_prompt(GroupPrompt::Claim),
+        KeyCode::Char('A') => state.open_prompt(GroupPrompt::AutoClaim),
+        KeyCode::Char('r') => app.fetch_consumer_groups(),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_script_runner_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_script_runner::ScriptField;
+
+    let state = &mut app.script_runner;
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => state.is_active = false,
+        KeyCode::Tab => state.next_field(),
+        KeyCode::Char('r') if ctrl => app.run_script(),
+        KeyCode::Char('t') if ctrl => state.toggle_mode(),
+        KeyCode::PageUp => state.result_scroll = state.result_scroll.saturating_sub(10),
+        KeyCode::PageDown => state.result_scroll += 10,
+        KeyCode::Enter if state.field == ScriptField::Script => state.insert_newline(),
+        KeyCode::Enter if state.field == ScriptField::File => state.load_file(),
+        KeyCode::Enter => app.run_script(),
+        _ => {
+            let (text, cursor) = state.focused_mut();
+            line_edit::handle_key(text, cursor, key);
+        }
+    }
+}
+
+pub(super) fn handle_action_menu_key(app: &mut app::App, key: KeyEvent) {
+    let state = &mut app.action_menu;
+    if state.prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => state.prompt = None,
+            KeyCode::Enter => app.submit_key_action_prompt(),
+            _ => {
+                line_edit::handle_key(&mut state.input, &mut state.cursor, key);
+            }
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('a') => state.close(),
+        KeyCode::Down | KeyCode::Char('j') => state.select(true),
+        KeyCode::Up | KeyCode::Char('k') => state.select(false),
+        KeyCode::Enter | KeyCode::Char(' ') => app.choose_key_action(),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_paste_preview_key(app: &mut app::App, code: KeyCode) {
+    let state = &mut app.paste_preview;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') => state.close(),
+        KeyCode::Enter | KeyCode::Char('y') => app.confirm_paste(),
+        KeyCode::Down | KeyCode::Char('j') => state.scroll_by(1),
+        KeyCode::Up | KeyCode::Char('k') => state.scroll_by(-1),
+        KeyCode::PageDown => state.scroll_by(10),
+        KeyCode::PageUp => state.scroll_by(-10),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_folder_copy_key(app: &mut app::App, code: KeyCode) {
+    let state = &mut app.folder_copy;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') => state.close(),
+        KeyCode::Down | KeyCode::Char('j') => state.select(true),
+        KeyCode::Up | KeyCode::Char('k') => state.select(false),
+        KeyCode::Enter => app::app_clipboard::copy_folder_to_clipboard(app),
+        // A second `y` copies the prefix, so `yy` works from the key list
+        KeyCode::Char(c @ ('1' | '2' | 'y')) => {
+            state.selected = if c == '2' { 1 } else { 0 };
+            app::app_clipboard::copy_folder_to_clipboard(app);
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn handle_scan_presets_key(app: &mut app::App, code: KeyCode) {
+    let state = &mut app.scan_presets;
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => state.close(),
+        KeyCode::Down | KeyCode::Char('j') => state.select(true),
+        KeyCode::Up | KeyCode::Char('k') => state.select(false),
+        KeyCode::Enter => app.apply_scan_preset(None),
+        KeyCode::Char(c @ '1'..='9') => app.apply_scan_preset(Some(c as usize - '1' as usize)),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_functions_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_functions::FunctionPrompt;
+
+    let state = &mut app.functions;
+    if state.prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => state.prompt = None,
+            KeyCode::Enter => app.submit_function_prompt(),
+            _ => {
+                line_edit::handle_key(&mut state.input, &mut state.cursor, key);
+            }
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => state.close(),
+        KeyCode::Down | KeyCode::Char('j') => state.select(true),
+        KeyCode::Up | KeyCode::Char('k') => state.select(false),
+        KeyCode::Char('r') => app.fetch_functions(),
+        KeyCode::Char('c') | KeyCode::Enter => state.open_prompt(FunctionPrompt::Call),
+        KeyCode::Char('l') => state.open_prompt(FunctionPrompt::Load),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_redisearch_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_redisearch::SearchMode;
+
+    let state = &mut app.redisearch;
+    if state.editing {
+        match key.code {
+            KeyCode::Esc => state.editing = false,
+            KeyCode::Enter => app.submit_search_query(),
+            _ => {
+                line_edit::handle_key(&mut state.input, &mut state.cursor, key);
+            }
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => state.close(),
+        KeyCode::Tab if state.results.is_some() => state.results_focused = !state.results_focused,
+        KeyCode::Down | KeyCode::Char('j') if state.results_focused => state.select_row(true),
+        KeyCode::Up | KeyCode::Char('k') if state.results_focused => state.select_row(false),
+        KeyCode::Down | KeyCode::Char('j') => app.select_search_index(true),
+        KeyCode::Up | KeyCode::Char('k') => app.select_search_index(false),
+        KeyCode::Enter if state.results_focused => app.open_search_document(),
+        KeyCode::Char('/') | KeyCode::Char('s') | KeyCode::Enter => state.open_prompt(SearchMode::Search),
+        KeyCode::Char('a') => state.open_prompt(SearchMode::Aggregate),
+        KeyCode::Char('n') | KeyCode::PageDown => app.search_page(true),
+        KeyCode::Char('N') | KeyCode::PageUp => app.search_page(false),
+        KeyCode::Char('r') => app.fetch_search_indexes(),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_pubsub_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_pubsub::PubSubPrompt;
+
+    let state = &mut app.pubsub;
+    if state.prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => state.prompt = None,
+            KeyCode::Enter => app.submit_pubsub_prompt(),
+            _ => {
+                line_edit::handle_key(&mut state.input, &mut state.cursor, key);
+            }
+        }
+        return;
+    }
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => state.is_active = false,
+        KeyCode::Char('s') => state.open_prompt(PubSubPrompt::Subscribe),
+        KeyCode::Char('/') => state.open_prompt(PubSubPrompt::Filter),
+        KeyCode::Char('p') | KeyCode::Char(' ') => state.toggle_pause(),
+        KeyCode::Char('c') => {
+            state.messages.clear();
+            state.scroll = 0;
+        }
+        KeyCode::Char('x') => app.unsubscribe(),
+        KeyCode::Up | KeyCode::Char('k') => state.scroll_by(1),
+        KeyCode::Down | KeyCode::Char('j') => state.scroll_by(-1),
+        KeyCode::PageUp => state.scroll_by(10),
+        KeyCode::PageDown => state.scroll_by(-10),
+        KeyCode::End | KeyCode::Char('G') => state.scroll = 0,
+        _ => {}
+    }
+}
+
+pub(super) fn handle_monitor_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_monitor::MonitorPrompt;
+
+    let state = &mut app.monitor;
+    if state.prompt.is_some() {
+        match key.code {
+            KeyCode::Esc => state.prompt = None,
+            KeyCode::Enter => state.submit_prompt(),
+            _ => {
+                line_edit::handle_key(&mut state.input, &mut state.cursor, key);
+            }
+        }
+        return;
+    }
+    match key.code {
+        // MONITOR is expensive, so it never keeps running behind a closed panel
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.session = None;
+            state.is_active = false;
+        }
+        KeyCode::Enter | KeyCode::Char('s') => app.toggle_monitor_session(),
+        KeyCode::Char('p') | KeyCode::Char(' ') => state.toggle_pause(),
+        KeyCode::Char('a') => state.open_prompt(MonitorPrompt::Client),
+        KeyCode::Char('/') => state.open_prompt(MonitorPrompt::Command),
+        KeyCode::Char('c') => {
+            state.entries.clear();
+            state.scroll = 0;
+        }
+        KeyCode::Up | KeyCode::Char('k') => state.scroll_by(1),
+        KeyCode::Down | KeyCode::Char('j') => state.scroll_by(-1),
+        KeyCode::PageUp => state.scroll_by(10),
+        KeyCode::PageDown => state.scroll_by(-10),
+        KeyCode::End | KeyCode::Char('G') => state.scroll = 0,
+        _ => {}
+    }
+}
+
+pub(super) fn handle_compare_key(app: &mut app::App, key: KeyEvent) {
+    use app::state_compare::CompareStage;
+
+    let state = &mut app.compare;
+    match state.stage {
+        CompareStage::PickProfile => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => state.is_active = false,
+            KeyCode::Down | KeyCode::Char('j') => app.select_compare_profile(true),
+            KeyCode::Up | KeyCode::Char('k') => app.select_compare_profile(false),
+            KeyCode::Enter => app.confirm_compare_profile(),
+            _ => {}
+        },
+        CompareStage::Pattern => match key.code {
+            KeyCode::Esc => state.stage = CompareStage::PickProfile,
+            KeyCode::Enter => app.submit_compare_pattern(),
+            _ => {
+                line_edit::handle_key(&mut state.pattern, &mut state.pattern_cursor, key);
+            }
+        },
+        CompareStage::Results if state.detail.is_some() => match key.code {
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => state.detail = None,
+            KeyCode::Down | KeyCode::Char('j') => state.select(1),
+            KeyCode::Up | KeyCode::Char('k') => state.select(-1),
+            KeyCode::PageDown => state.select(10),
+            KeyCode::PageUp => state.select(-10),
+            _ => {}
+        },
+        CompareStage::Results => match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                state.reset_results();
+                state.right = None;
+                state.is_active = false;
+            }
+            KeyCode::Enter => app.request_compare_detail(),
+            KeyCode::Char('r') => app.submit_compare_pattern(),
+            KeyCode::Down | KeyCode::Char('j') => state.select(1),
+            KeyCode::Up | KeyCode::Char('k') => state.select(-1),
+            KeyCode::PageDown => state.select(10),
+            KeyCode::PageUp => state.select(-10),
+            _ => {}
+        },
+    }
+}
+
+pub(super) fn handle_snapshots_key(app: &mut app::App, key: KeyEvent) {
+    let state = &mut app.snapshots;
+    if let Some(name) = &mut state.name_input {
+        match key.code {
+            KeyCode::Esc => state.name_input = None,
+            KeyCode::Enter => app.submit_snapshot_name(),
+            _ => {
+                line_edit::handle_key(name, &mut state.name_cursor, key);
+            }
+        }
+        return;
+    }
+    if state.diff.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => state.diff = None,
+            KeyCode::Enter => app.open_selected_diff_key(),
+            KeyCode::Down | KeyCode::Char('j') => state.select(1),
+            KeyCode::Up | KeyCode::Char('k') => state.select(-1),
+            KeyCode::PageDown => state.select(10),
+            KeyCode::PageUp => state.select(-10),
+            _ => {}
+        }
+        return;
+    }
+    match key.code {
+        // Closing abandons a capture or comparison still walking the keyspace
+        KeyCode::Esc | KeyCode::Char('q') => {
+            state.job = None;
+            state.is_active = false;
+        }
+        KeyCode::Char('n') => app.start_snapshot_name(),
+        KeyCode::Enter => app.compare_selected_snapshot(),
+        KeyCode::Down | KeyCode::Char('j') => state.select(1),
+        KeyCode::Up | KeyCode::Char('k') => state.select(-1),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_config_editor_key(app: &mut app::App, key: KeyEvent) {
+    let state = &mut app.config_editor;
+    if let Some(value) = &mut state.edit {
+        match key.code {
+            KeyCode::Esc => state.edit = None,
+            KeyCode::Enter => app.submit_config_edit(),
+            _ => {
+                line_edit::handle_key(value, &mut state.edit_cursor, key);
+            }
+        }
+        return;
+    }
+    if line_edit::handle_key(&mut state.filter, &mut state.filter_cursor, key) == LineEdit::Edited {
+        state.apply_filter();
+        return;
+    }
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => state.is_active = false,
+        KeyCode::Down => state.select(1),
+        KeyCode::Up => state.select(-1),
+        KeyCode::PageDown => state.select(10),
+        KeyCode::PageUp => state.select(-10),
+        KeyCode::Enter => app.start_config_edit(),
+        KeyCode::Char('s') if ctrl => app.request_config_rewrite(),
+        KeyCode::Char('r') if ctrl => app.fetch_config(),
+        _ => {}
+    }
+}
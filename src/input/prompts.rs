@@ -0,0 +1,136 @@
+//! Single-line prompts and the search and command inputs.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app;
+use crate::keymap::{Action, KeyContext};
+use crate::line_edit::{self, LineEdit};
+
+/// Editing the value-panel row filter; Enter keeps it, Esc clears it.
+pub(super) fn handle_value_filter_key(app: &mut app::App, code: KeyCode) {
+    let viewer = &mut app.value_viewer;
+    match code {
+        KeyCode::Enter => viewer.filter_input_active = false,
+        KeyCode::Esc => {
+            viewer.filter_input_active = false;
+            viewer.set_line_filter(String::new());
+        }
+        KeyCode::Backspace => {
+            let mut filter = viewer.line_filter.clone();
+            filter.pop();
+            viewer.set_line_filter(filter);
+        }
+        KeyCode::Char(c) => {
+            let filter = format!("{}{}", viewer.line_filter, c);
+            viewer.set_line_filter(filter);
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn handle_goto_line_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.submit_goto_line(),
+        KeyCode::Esc => app.value_viewer.goto_line_input = None,
+        KeyCode::Backspace => {
+            app.value_viewer.goto_line_input.as_mut().map(String::pop);
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            app.value_viewer.goto_line_input.get_or_insert_with(String::new).push(c);
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn handle_pfadd_key(app: &mut app::App, key: KeyEvent) {
+    let viewer = &mut app.value_viewer;
+    match key.code {
+        KeyCode::Enter => app.submit_pfadd(),
+        KeyCode::Esc => viewer.pfadd_input = None,
+        _ => {
+            let Some(input) = viewer.pfadd_input.as_mut() else {
+                return;
+            };
+            line_edit::handle_key(input, &mut viewer.pfadd_cursor, key);
+        }
+    }
+}
+
+pub(super) fn handle_probe_key(app: &mut app::App, key: KeyEvent) {
+    let viewer = &mut app.value_viewer;
+    match key.code {
+        KeyCode::Enter => app.submit_probe(),
+        KeyCode::Esc => viewer.probe_input = None,
+        _ => {
+            let Some(input) = viewer.probe_input.as_mut() else {
+                return;
+            };
+            line_edit::handle_key(input, &mut viewer.probe_cursor, key);
+        }
+    }
+}
+
+pub(super) fn handle_search_key(app: &mut app::App, key: KeyEvent) {
+    let search = &mut app.search_state;
+    match line_edit::handle_key(&mut search.query, &mut search.query_cursor, key) {
+        LineEdit::Edited => app.update_filtered_keys(),
+        LineEdit::Moved => {}
+        LineEdit::Ignored => match app.keymap.action(&[KeyContext::Search], key) {
+            Some(Action::SearchExit) => app.exit_search_mode(),
+            Some(Action::SearchMode) => app.toggle_search_mode(),
+            Some(Action::SearchSubmit) if app.search_state.needs_server_search() => app.start_server_search(),
+            Some(Action::SearchSubmit) => {
+                app.activate_selected_filtered_key();
+            }
+            Some(Action::SearchDown) => app.select_next_filtered_key(),
+            Some(Action::SearchUp) => app.select_previous_filtered_key(),
+            _ => {}
+        },
+    }
+}
+
+pub(super) fn handle_command_prompt_key(app: &mut app::App, key: KeyEvent) {
+    if app.command_state.edit(key) != LineEdit::Ignored {
+        return;
+    }
+    match app.keymap.action(&[KeyContext::CommandPrompt], key) {
+        Some(Action::CommandRaw) => app.command_state.toggle_raw_output(),
+        Some(Action::CommandCopy) => {
+            app::app_clipboard::copy_command_result_to_clipboard(app);
+        }
+        Some(Action::CommandSave) => app.save_command_result(),
+        Some(Action::CommandComplete) => app.command_state.complete(),
+        Some(Action::CommandPageUp) => app.command_state.page_result(-1),
+        Some(Action::CommandPageDown) => app.command_state.page_result(1),
+        Some(Action::CommandScrollUp) => app.command_state.scroll_result(-1),
+        Some(Action::CommandScrollDown) => app.command_state.scroll_result(1),
+        // Alt+Enter starts another line of a multi-command pipeline
+        Some(Action::CommandNewLine) => app.command_state.push_char('\n'),
+        Some(Action::CommandRun) => app.execute_command_input(),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_scan_prompt_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.submit_scan_prompt(),
+        KeyCode::Esc => app.scan_prompt = None,
+        KeyCode::Backspace => {
+            app.scan_prompt.as_mut().map(String::pop);
+        }
+        KeyCode::Char(c) => app.scan_prompt.get_or_insert_with(String::new).push(c),
+        _ => {}
+    }
+}
+
+pub(super) fn handle_goto_key_prompt_key(app: &mut app::App, code: KeyCode) {
+    match code {
+        KeyCode::Enter => app.submit_goto_key_prompt(),
+        KeyCode::Esc => app.goto_key_prompt = None,
+        KeyCode::Backspace => {
+            app.goto_key_prompt.as_mut().map(String::pop);
+        }
+        KeyCode::Char(c) => app.goto_key_prompt.get_or_insert_with(String::new).push(c),
+        _ => {}
+    }
+}
@@ -0,0 +1,637 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Binding set chosen with the `keymap` setting.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeymapPreset {
+    #[default]
+    Default,
+    /// Adds `gg` / `G`, `Ctrl+d` / `Ctrl+u`, `zz` and count prefixes (`5j`). Digits become
+    /// counts, so panels are focused with Tab, and the key size column moves to `zs`.
+    Vim,
+}
+
+/// Where a binding applies. The focused pane's context is consulted before `Global`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Global,
+    KeyView,
+    ValueView,
+    Search,
+    CommandPrompt,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 5] = [
+        KeyContext::Global,
+        KeyContext::KeyView,
+        KeyContext::ValueView,
+        KeyContext::Search,
+        KeyContext::CommandPrompt,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyContext::Global => "General",
+            KeyContext::KeyView => "Key list",
+            KeyContext::ValueView => "Value view",
+            KeyContext::Search => "Search (/)",
+            KeyContext::CommandPrompt => "Command prompt (:)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Help,
+    Up,
+    Down,
+    Activate,
+    FocusNext,
+    FocusPrevious,
+    FocusDb,
+    FocusKeys,
+    FocusValues,
+    NarrowKeys,
+    WidenKeys,
+    Search,
+    GotoKey,
+    CommandPrompt,
+    Profiles,
+    Stats,
+    DoctorReport,
+    StatsReport,
+    CopyKeyName,
+    CopyValue,
+    CopyJson,
+    CopyCommand,
+    Paste,
+    HexView,
+    CycleDecoder,
+    ToggleWrap,
+    Watch,
+    StreamFollow,
+    StreamPause,
+    ConsumerGroups,
+    KeyInfo,
+    KeySizes,
+    ScanPattern,
+    ScanPresets,
+    RefreshKeys,
+    LiveUpdates,
+    ScriptRunner,
+    Functions,
+    RediSearch,
+    PubSub,
+    Monitor,
+    ConfigEditor,
+    LatencyPanel,
+    Analysis,
+    RandomSample,
+    Snapshots,
+    Compare,
+    ActionMenu,
+    ToggleBookmark,
+    Bookmarks,
+    Timestamps,
+    CycleSort,
+    LineNumbers,
+    NextChunk,
+    PreviousChunk,
+    ExtendSelectionDown,
+    ExtendSelectionUp,
+    ToggleSelection,
+    Delete,
+    ParentFolder,
+    BreadcrumbLeft,
+    BreadcrumbRight,
+    ToggleTreeView,
+    TreeExpand,
+    TreeCollapse,
+    Top,
+    Bottom,
+    HalfPageDown,
+    HalfPageUp,
+    CenterSelection,
+    Escape,
+    FilterValues,
+    GotoLine,
+    PfAdd,
+    Probe,
+    LoadMore,
+    ScrollLeft,
+    ScrollRight,
+    PageDown,
+    PageUp,
+    SearchExit,
+    SearchMode,
+    SearchSubmit,
+    SearchDown,
+    SearchUp,
+    CommandRun,
+    CommandNewLine,
+    CommandClose,
+    CommandComplete,
+    CommandRaw,
+    CommandCopy,
+    CommandSave,
+    CommandScrollUp,
+    CommandScrollDown,
+    CommandPageUp,
+    CommandPageDown,
+}
+
+impl Action {
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Help => "Show this help",
+            Action::Up => "Move up in the focused list",
+            Action::Down => "Move down in the focused list",
+            Action::Activate => "Open the selected DB, folder or key",
+            Action::FocusNext => "Focus the next panel",
+            Action::FocusPrevious => "Focus the previous panel",
+            Action::FocusDb => "Focus the DB list",
+            Action::FocusKeys => "Focus the key list",
+            Action::FocusValues => "Focus the value view",
+            Action::NarrowKeys => "Narrow the key list",
+            Action::WidenKeys => "Widen the key list",
+            Action::Search => "Search keys",
+            Action::GotoKey => "Jump to a key by name",
+            Action::CommandPrompt => "Open the command prompt",
+            Action::Profiles => "Open the profile selector",
+            Action::Stats => "Toggle the server stats panel",
+            Action::DoctorReport => "Doctor report (stats panel open)",
+            Action::StatsReport => "Stats report to copy or save (stats panel open)",
+            Action::CopyKeyName => "Copy the key name, or a folder's prefix or keys",
+            Action::CopyValue => "Copy the value",
+            Action::CopyJson => "Copy the key as JSON (type, TTL and value)",
+            Action::CopyCommand => "Copy redis-cli commands that recreate the key",
+            Action::Paste => "Create a key from the clipboard (copied with J or c)",
+            Action::HexView => "Toggle the hex viewer",
+            Action::CycleDecoder => "Cycle the value decoder",
+            Action::ToggleWrap => "Toggle line wrapping",
+            Action::Watch => "Toggle watch mode",
+            Action::StreamFollow => "Follow a stream",
+            Action::StreamPause => "Pause the stream tail",
+            Action::ConsumerGroups => "Stream consumer groups",
+            Action::KeyInfo => "Key details",
+            Action::KeySizes => "Toggle the key size column",
+            Action::ScanPattern => "Change the SCAN pattern and count",
+            Action::ScanPresets => "Apply a saved scan filter preset",
+            Action::RefreshKeys => "Rescan the current level",
+            Action::LiveUpdates => "Toggle live key updates",
+            Action::ScriptRunner => "Lua script runner",
+            Action::Functions => "Redis functions",
+            Action::RediSearch => "RediSearch indexes and queries",
+            Action::PubSub => "Pub/Sub",
+            Action::Monitor => "MONITOR",
+            Action::ConfigEditor => "Server CONFIG editor",
+            Action::LatencyPanel => "Latency panel",
+            Action::Analysis => "Keyspace analysis",
+            Action::RandomSample => "Random key sample",
+            Action::Snapshots => "Keyspace snapshots",
+            Action::Compare => "Compare keyspaces across profiles",
+            Action::ActionMenu => "Actions for the selected key",
+            Action::ToggleBookmark => "Bookmark the selected key",
+            Action::Bookmarks => "Bookmarks",
+            Action::Timestamps => "Toggle timestamp annotations",
+            Action::CycleSort => "Cycle the collection sort order",
+            Action::LineNumbers => "Toggle line numbers",
+            Action::NextChunk => "Next chunk of a large string",
+            Action::PreviousChunk => "Previous chunk of a large string",
+            Action::ExtendSelectionDown => "Extend the selection down",
+            Action::ExtendSelectionUp => "Extend the selection up",
+            Action::ToggleSelection => "Mark or unmark the selected key",
+            Action::Delete => "Delete the selected key(s) or folder",
+            Action::ParentFolder => "Go up one folder",
+            Action::BreadcrumbLeft => "Select the parent level in the breadcrumb bar (Enter opens it)",
+            Action::BreadcrumbRight => "Select the next level in the breadcrumb bar",
+            Action::ToggleTreeView => "Toggle the expandable tree view of the key list",
+            Action::TreeExpand => "Expand the folder or open the key (tree view)",
+            Action::TreeCollapse => "Collapse the folder or select its parent (tree view)",
+            Action::Top => "Jump to the top of the focused list",
+            Action::Bottom => "Jump to the bottom of the focused list, or to the row given as a count (12G)",
+            Action::HalfPageDown => "Half a page down",
+            Action::HalfPageUp => "Half a page up",
+            Action::CenterSelection => "Scroll the selection to the middle of the list",
+            Action::Escape => "Cancel the scan, clear marks, or go to the root",
+            Action::FilterValues => "Filter the collection rows",
+            Action::GotoLine => "Go to a line",
+            Action::PfAdd => "PFADD elements to a HyperLogLog (dev profiles)",
+            Action::Probe => "Check items against a Bloom/Cuckoo filter, Top-K or t-digest",
+            Action::LoadMore => "Load the next page of a long list, sorted set or stream",
+            Action::ScrollLeft => "Scroll left",
+            Action::ScrollRight => "Scroll right",
+            Action::PageDown => "Page down",
+            Action::PageUp => "Page up",
+            Action::SearchExit => "Leave search",
+            Action::SearchMode => "Cycle fuzzy, regex, server and value search",
+            Action::SearchSubmit => "Open the match, or start a server search",
+            Action::SearchDown => "Next match",
+            Action::SearchUp => "Previous match",
+            Action::CommandRun => "Run the command(s)",
+            Action::CommandNewLine => "Add another pipelined command",
+            Action::CommandClose => "Close the prompt",
+            Action::CommandComplete => "Complete the command name",
+            Action::CommandRaw => "Toggle raw RESP output",
+            Action::CommandCopy => "Copy the result",
+            Action::CommandSave => "Save the result to a file",
+            Action::CommandScrollUp => "Scroll the result up",
+            Action::CommandScrollDown => "Scroll the result down",
+            Action::CommandPageUp => "Page the result up",
+            Action::CommandPageDown => "Page the result down",
+        }
+    }
+}
+
+/// One key chord, optionally preceded by a leader character (`gg`). Shift is implied by
+/// the character for `Char` keys, so it is not stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+    pub leader: Option<char>,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers, leader: None }
+    }
+
+    /// Two-key sequence: `leader`, then `c`.
+    pub const fn sequence(leader: char, c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::NONE,
+            leader: Some(leader),
+        }
+    }
+
+    pub fn of(key: KeyEvent) -> Self {
+        match key.code {
+            KeyCode::Tab if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                Self::new(KeyCode::BackTab, key.modifiers - KeyModifiers::SHIFT)
+            }
+            KeyCode::Char(_) | KeyCode::BackTab => Self::new(key.code, key.modifiers - KeyModifiers::SHIFT),
+            _ => Self::new(key.code, key.modifiers),
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(leader) = self.leader {
+            write!(f, "{}", leader)?;
+        }
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// Every binding the main view, search and command prompt react to. Dispatch and the
+/// help overlay both read it, so the cheat sheet cannot drift from the real keys.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(KeyContext, KeyBinding, Action)>,
+    /// Whether digits typed before a key are a repeat count.
+    counts: bool,
+}
+
+/// Count and leader typed so far towards a vim-style command such as `12j` or `gg`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PendingKeys {
+    count: Option<usize>,
+    leader: Option<char>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Action::*;
+        use KeyContext::{Global, KeyView, ValueView};
+        const NONE: KeyModifiers = KeyModifiers::NONE;
+        const CTRL: KeyModifiers = KeyModifiers::CONTROL;
+        let char = |c| KeyBinding::new(KeyCode::Char(c), NONE);
+        let ctrl = |c| KeyBinding::new(KeyCode::Char(c), CTRL);
+        let key = |code| KeyBinding::new(code, NONE);
+        let shift = |code| KeyBinding::new(code, KeyModifiers::SHIFT);
+
+        let bindings = vec![
+            (Global, char('?'), Help),
+            (Global, char('q'), Quit),
+            (Global, char('j'), Down),
+            (Global, key(KeyCode::Down), Down),
+            (Global, char('k'), Up),
+            (Global, key(KeyCode::Up), Up),
+            (Global, key(KeyCode::Enter), Activate),
+            (Global, key(KeyCode::Tab), FocusNext),
+            (Global, key(KeyCode::BackTab), FocusPrevious),
+            (Global, char('1'), FocusDb),
+            (Global, char('2'), FocusKeys),
+            (Global, char('3'), FocusValues),
+            (Global, ctrl('h'), NarrowKeys),
+            (Global, ctrl('l'), WidenKeys),
+            (Global, char('/'), Search),
+            (Global, ctrl('g'), GotoKey),
+            (Global, char(':'), CommandPrompt),
+            (Global, char('p'), Profiles),
+            (Global, char('s'), Stats),
+            (Global, char('R'), DoctorReport),
+            (Global, char('I'), StatsReport),
+            (Global, char('y'), CopyKeyName),
+            (Global, char('Y'), CopyValue),
+            (Global, char('J'), CopyJson),
+            (Global, char('c'), CopyCommand),
+            (Global, char('V'), Paste),
+            (Global, char('x'), HexView),
+            (Global, char('D'), CycleDecoder),
+            (Global, char('W'), ToggleWrap),
+            (Global, char('w'), Watch),
+            (Global, char('f'), StreamFollow),
+            (Global, char('F'), StreamPause),
+            (Global, char('C'), ConsumerGroups),
+            (Global, char('i'), KeyInfo),
+            (Global, char('z'), KeySizes),
+            (Global, char('M'), ScanPattern),
+            (Global, char('v'), ScanPresets),
+            (Global, key(KeyCode::F(5)), RefreshKeys),
+            (Global, char('K'), LiveUpdates),
+            (Global, char('L'), ScriptRunner),
+            (Global, char('U'), Functions),
+            (Global, char('Q'), RediSearch),
+            (Global, char('P'), PubSub),
+            (Global, char('O'), Monitor),
+            (Global, char('E'), ConfigEditor),
+            (Global, char('H'), LatencyPanel),
+            (Global, char('B'), Analysis),
+            (Global, char('S'), RandomSample),
+            (Global, char('Z'), Snapshots),
+            (Global, char('X'), Compare),
+            (Global, char('a'), ActionMenu),
+            (Global, char('m'), ToggleBookmark),
+            (Global, char('b'), Bookmarks),
+            (Global, char('T'), Timestamps),
+            (Global, char('o'), CycleSort),
+            (Global, char('#'), LineNumbers),
+            (Global, char(']'), NextChunk),
+            (Global, char('['), PreviousChunk),
+            (Global, char('t'), ToggleTreeView),
+            (KeyView, shift(KeyCode::Down), ExtendSelectionDown),
+            (KeyView, shift(KeyCode::Up), ExtendSelectionUp),
+            (KeyView, ctrl(' '), ToggleSelection),
+            (KeyView, char('d'), Delete),
+            (KeyView, key(KeyCode::Backspace), ParentFolder),
+            (KeyView, key(KeyCode::Left), BreadcrumbLeft),
+            (KeyView, key(KeyCode::Right), BreadcrumbRight),
+            (KeyView, char('l'), TreeExpand),
+            (KeyView, char('h'), TreeCollapse),
+            (KeyView, key(KeyCode::Esc), Escape),
+            (ValueView, char('/'), FilterValues),
+            (ValueView, char('G'), GotoLine),
+            (ValueView, char('A'), PfAdd),
+            (ValueView, char('e'), Probe),
+            (ValueView, char('n'), LoadMore),
+            (ValueView, key(KeyCode::Left), ScrollLeft),
+            (ValueView, key(KeyCode::Right), ScrollRight),
+            (ValueView, key(KeyCode::PageDown), PageDown),
+            (ValueView, key(KeyCode::PageUp), PageUp),
+            (KeyContext::Search, key(KeyCode::Esc), SearchExit),
+            (KeyContext::Search, key(KeyCode::Tab), SearchMode),
+            (KeyContext::Search, key(KeyCode::Enter), SearchSubmit),
+            (KeyContext::Search, key(KeyCode::Down), SearchDown),
+            (KeyContext::Search, key(KeyCode::Up), SearchUp),
+            (KeyContext::CommandPrompt, key(KeyCode::Enter), CommandRun),
+            (KeyContext::CommandPrompt, KeyBinding::new(KeyCode::Enter, KeyModifiers::ALT), CommandNewLine),
+            (KeyContext::CommandPrompt, key(KeyCode::Esc), CommandClose),
+            (KeyContext::CommandPrompt, key(KeyCode::Tab), CommandComplete),
+            (KeyContext::CommandPrompt, ctrl('r'), CommandRaw),
+            (KeyContext::CommandPrompt, ctrl('y'), CommandCopy),
+            (KeyContext::CommandPrompt, ctrl('s'), CommandSave),
+            (KeyContext::CommandPrompt, key(KeyCode::Up), CommandScrollUp),
+            (KeyContext::CommandPrompt, key(KeyCode::Down), CommandScrollDown),
+            (KeyContext::CommandPrompt, key(KeyCode::PageUp), CommandPageUp),
+            (KeyContext::CommandPrompt, key(KeyCode::PageDown), CommandPageDown),
+        ];
+        Self { bindings, counts: false }
+    }
+}
+
+impl Keymap {
+    pub fn new(preset: KeymapPreset) -> Self {
+        let mut keymap = Self::default();
+        if preset == KeymapPreset::Vim {
+            keymap.add_vim_bindings();
+        }
+        keymap
+    }
+
+    fn add_vim_bindings(&mut self) {
+        use Action::*;
+        use KeyContext::{Global, ValueView};
+        let char = |c| KeyBinding::new(KeyCode::Char(c), KeyModifiers::NONE);
+        let ctrl = |c| KeyBinding::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        // Digits start a count, `z` leads `zz`, and `G` in the value view goes to the
+        // bottom; a count before it still goes to a line
+        self.bindings.retain(|(context, binding, action)| {
+            !matches!(binding.code, KeyCode::Char('0'..='9'))
+                && *action != KeySizes
+                && !(*context == ValueView && *action == GotoLine)
+        });
+        self.bindings.extend([
+            (Global, KeyBinding::sequence('g', 'g'), Top),
+            (Global, char('G'), Bottom),
+            (Global, ctrl('d'), HalfPageDown),
+            (Global, ctrl('u'), HalfPageUp),
+            (Global, KeyBinding::sequence('z', 'z'), CenterSelection),
+            (Global, KeyBinding::sequence('z', 's'), KeySizes),
+        ]);
+        self.counts = true;
+    }
+
+    /// Feeds `key` into the vim-style `pending` count and leader. Returns the action once a
+    /// binding is complete, with the count typed before it.
+    pub fn resolve(
+        &self,
+        contexts: &[KeyContext],
+        key: KeyEvent,
+        pending: &mut PendingKeys,
+    ) -> Option<(Action, Option<usize>)> {
+        let mut binding = KeyBinding::of(key);
+        let plain_char = match binding.code {
+            KeyCode::Char(c) if binding.modifiers.is_empty() => Some(c),
+            _ => None,
+        };
+        if pending.leader.is_none() && self.counts {
+            let digit = plain_char.and_then(|c| c.to_digit(10)).map(|digit| digit as usize);
+            if let Some(digit) = digit.filter(|&digit| digit > 0 || pending.count.is_some()) {
+                pending.count = Some(pending.count.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+                return None;
+            }
+        }
+        match pending.leader.take() {
+            Some(leader) => binding.leader = Some(leader),
+            None => {
+                if let Some(c) = plain_char.filter(|&c| self.is_leader(contexts, c)) {
+                    pending.leader = Some(c);
+                    return None;
+                }
+            }
+        }
+        let count = pending.count.take();
+        self.find(contexts, binding).map(|action| (action, count))
+    }
+
+    fn is_leader(&self, contexts: &[KeyContext], c: char) -> bool {
+        self.bindings
+            .iter()
+            .any(|(context, binding, _)| binding.leader == Some(c) && contexts.contains(context))
+    }
+
+    fn find(&self, contexts: &[KeyContext], binding: KeyBinding) -> Option<Action> {
+        contexts.iter().find_map(|context| {
+            self.bindings
+                .iter()
+                .find(|(bound_context, bound, _)| bound_context == context && *bound == binding)
+                .map(|(_, _, action)| *action)
+        })
+    }
+
+    /// Action bound to `key`, trying `contexts` in order.
+    pub fn action(&self, contexts: &[KeyContext], key: KeyEvent) -> Option<Action> {
+        self.find(contexts, KeyBinding::of(key))
+    }
+
+    /// Whether `key` is one of the bindings of `action`.
+    pub fn is(&self, action: Action, key: KeyEvent) -> bool {
+        let binding = KeyBinding::of(key);
+        self.bindings
+            .iter()
+            .any(|(_, bound, bound_action)| *bound_action == action && *bound == binding)
+    }
+
+    /// The first key bound to `action`, as shown in help.
+    pub fn binding_label(&self, action: Action) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|(_, _, bound)| *bound == action)
+            .map(|(_, binding, _)| binding.to_string())
+    }
+
+    /// Keys and description of each action bound in `context`, in binding order, with
+    /// all keys of an action on one row (`j / ↓`).
+    pub fn help_rows(&self, context: KeyContext) -> Vec<(String, &'static str)> {
+        let mut rows: Vec<(Action, Vec<String>)> = Vec::new();
+        for (bound_context, binding, action) in &self.bindings {
+            if *bound_context != context {
+                continue;
+            }
+            match rows.iter_mut().find(|(existing, _)| existing == action) {
+                Some((_, keys)) => keys.push(binding.to_string()),
+                None => rows.push((*action, vec![binding.to_string()])),
+            }
+        }
+        rows.into_iter()
+            .map(|(action, keys)| (keys.join(" / "), action.description()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn focused_context_wins_over_global() {
+        let keymap = Keymap::default();
+        let slash = press(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&[KeyContext::Global], slash), Some(Action::Search));
+        assert_eq!(
+            keymap.action(&[KeyContext::ValueView, KeyContext::Global], slash),
+            Some(Action::FilterValues)
+        );
+        // Uppercase letters arrive with Shift held
+        let upper = press(KeyCode::Char('Y'), KeyModifiers::SHIFT);
+        assert_eq!(keymap.action(&[KeyContext::Global], upper), Some(Action::CopyValue));
+        let shift_down = press(KeyCode::Down, KeyModifiers::SHIFT);
+        assert_eq!(keymap.action(&[KeyContext::Global], shift_down), None);
+        assert!(keymap.is(Action::GotoKey, press(KeyCode::Char('g'), KeyModifiers::CONTROL)));
+        // Loading more of a value does not shadow a global key
+        let load_more = press(KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&[KeyContext::ValueView, KeyContext::Global], load_more), Some(Action::LoadMore));
+        assert_eq!(keymap.action(&[KeyContext::Global], load_more), None);
+    }
+
+    #[test]
+    fn no_key_is_bound_twice_in_a_context() {
+        for keymap in [Keymap::default(), Keymap::new(KeymapPreset::Vim)] {
+            for (idx, (context, binding, _)) in keymap.bindings.iter().enumerate() {
+                let duplicate = keymap.bindings[idx + 1..]
+                    .iter()
+                    .any(|(other_context, other, _)| other_context == context && other == binding);
+                assert!(!duplicate, "{} bound twice in {:?}", binding, context);
+            }
+        }
+    }
+
+    #[test]
+    fn vim_preset_reads_counts_and_sequences() {
+        let keymap = Keymap::new(KeymapPreset::Vim);
+        let contexts = [KeyContext::KeyView, KeyContext::Global];
+        let mut pending = PendingKeys::default();
+        let mut feed = |c: char| keymap.resolve(&contexts, press(KeyCode::Char(c), KeyModifiers::NONE), &mut pending);
+        assert_eq!(feed('1'), None);
+        assert_eq!(feed('2'), None);
+        assert_eq!(feed('j'), Some((Action::Down, Some(12))));
+        assert_eq!(feed('g'), None);
+        assert_eq!(feed('g'), Some((Action::Top, None)));
+        assert_eq!(feed('z'), None);
+        assert_eq!(feed('z'), Some((Action::CenterSelection, None)));
+        assert_eq!(feed('G'), Some((Action::Bottom, None)));
+        // An unknown sequence is dropped rather than run as its second key
+        assert_eq!(feed('g'), None);
+        assert_eq!(feed('j'), None);
+        assert_eq!(feed('j'), Some((Action::Down, None)));
+
+        let rows = keymap.help_rows(KeyContext::Global);
+        assert!(rows.contains(&("gg".to_string(), "Jump to the top of the focused list")));
+        let ctrl_d = press(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(Keymap::default().resolve(&contexts, ctrl_d, &mut PendingKeys::default()), None);
+    }
+
+    #[test]
+    fn help_rows_group_keys_of_an_action() {
+        let rows = Keymap::default().help_rows(KeyContext::Global);
+        assert!(rows.contains(&("j / ↓".to_string(), "Move down in the focused list")));
+        assert!(rows.contains(&("Ctrl+h".to_string(), "Narrow the key list")));
+        assert!(rows.contains(&("Shift+Tab".to_string(), "Focus the previous panel")));
+        assert_eq!(Keymap::default().binding_label(Action::PfAdd).as_deref(), Some("A"));
+        assert_eq!(Keymap::default().binding_label(Action::CommandRun).as_deref(), Some("Enter"));
+    }
+}
@@ -0,0 +1,154 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// What a key did to a prompt's text and cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEdit {
+    /// The text changed.
+    Edited,
+    /// Only the cursor moved.
+    Moved,
+    /// The key is not a line-editing key; the caller may handle it.
+    Ignored,
+}
+
+/// Applies a line-editing key to `text` with the cursor at byte offset `cursor`:
+/// insertion at the cursor, Left/Right/Home/End (and Ctrl+A/Ctrl+E), Backspace/Delete,
+/// Ctrl+W to delete the previous word and Ctrl+U to delete to the start of the line.
+pub fn handle_key(text: &mut String, cursor: &mut usize, key: KeyEvent) -> LineEdit {
+    *cursor = clamp_cursor(text, *cursor);
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('a') if ctrl => move_to(cursor, line_start(text, *cursor)),
+        KeyCode::Char('e') if ctrl => move_to(cursor, line_end(text, *cursor)),
+        KeyCode::Char('w') if ctrl => delete_range(text, cursor, word_start(text, *cursor)),
+        KeyCode::Char('u') if ctrl => delete_range(text, cursor, line_start(text, *cursor)),
+        KeyCode::Char(_) if ctrl => LineEdit::Ignored,
+        KeyCode::Char(c) => {
+            insert(text, cursor, c);
+            LineEdit::Edited
+        }
+        KeyCode::Backspace => delete_range(text, cursor, prev_boundary(text, *cursor)),
+        KeyCode::Delete => {
+            let end = next_boundary(text, *cursor);
+            if end == *cursor {
+                return LineEdit::Ignored;
+            }
+            text.replace_range(*cursor..end, "");
+            LineEdit::Edited
+        }
+        KeyCode::Left => move_to(cursor, prev_boundary(text, *cursor)),
+        KeyCode::Right => move_to(cursor, next_boundary(text, *cursor)),
+        KeyCode::Home => move_to(cursor, line_start(text, *cursor)),
+        KeyCode::End => move_to(cursor, line_end(text, *cursor)),
+        _ => LineEdit::Ignored,
+    }
+}
+
+/// Inserts `c` at the cursor and moves the cursor past it.
+pub fn insert(text: &mut String, cursor: &mut usize, c: char) {
+    *cursor = clamp_cursor(text, *cursor);
+    text.insert(*cursor, c);
+    *cursor += c.len_utf8();
+}
+
+/// Keeps a cursor inside `text` and on a char boundary after the text was replaced.
+pub fn clamp_cursor(text: &str, cursor: usize) -> usize {
+    let mut cursor = cursor.min(text.len());
+    while !text.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    cursor
+}
+
+fn move_to(cursor: &mut usize, target: usize) -> LineEdit {
+    if *cursor == target {
+        return LineEdit::Ignored;
+    }
+    *cursor = target;
+    LineEdit::Moved
+}
+
+/// Deletes between `start` and the cursor, leaving the cursor at `start`.
+fn delete_range(text: &mut String, cursor: &mut usize, start: usize) -> LineEdit {
+    if start == *cursor {
+        return LineEdit::Ignored;
+    }
+    text.replace_range(start..*cursor, "");
+    *cursor = start;
+    LineEdit::Edited
+}
+
+fn prev_boundary(text: &str, cursor: usize) -> usize {
+    text[..cursor].char_indices().next_back().map_or(0, |(idx, _)| idx)
+}
+
+fn next_boundary(text: &str, cursor: usize) -> usize {
+    text[cursor..].chars().next().map_or(cursor, |c| cursor + c.len_utf8())
+}
+
+fn line_start(text: &str, cursor: usize) -> usize {
+    text[..cursor].rfind('\n').map_or(0, |idx| idx + 1)
+}
+
+fn line_end(text: &str, cursor: usize) -> usize {
+    text[cursor..].find('\n').map_or(text.len(), |idx| cursor + idx)
+}
+
+/// Start of the word before the cursor, skipping whitespace first like readline.
+fn word_start(text: &str, cursor: usize) -> usize {
+    let before = text[..cursor].trim_end();
+    before
+        .rfind(char::is_whitespace)
+        .map_or(0, |idx| idx + before[idx..].chars().next().map_or(1, char::len_utf8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut text = "SET key vaue".to_string();
+        let mut cursor = text.len();
+        handle_key(&mut text, &mut cursor, key(KeyCode::Left));
+        handle_key(&mut text, &mut cursor, key(KeyCode::Left));
+        assert_eq!(handle_key(&mut text, &mut cursor, key(KeyCode::Char('l'))), LineEdit::Edited);
+        assert_eq!(text, "SET key value");
+
+        handle_key(&mut text, &mut cursor, key(KeyCode::Home));
+        handle_key(&mut text, &mut cursor, key(KeyCode::Delete));
+        assert_eq!(text, "ET key value");
+        handle_key(&mut text, &mut cursor, key(KeyCode::Char('G')));
+        assert_eq!(text, "GET key value");
+
+        handle_key(&mut text, &mut cursor, key(KeyCode::End));
+        assert_eq!(handle_key(&mut text, &mut cursor, key(KeyCode::Right)), LineEdit::Ignored);
+        handle_key(&mut text, &mut cursor, ctrl('w'));
+        assert_eq!(text, "GET key ");
+        handle_key(&mut text, &mut cursor, ctrl('w'));
+        assert_eq!(text, "GET ");
+        handle_key(&mut text, &mut cursor, ctrl('u'));
+        assert_eq!((text.as_str(), cursor), ("", 0));
+    }
+
+    #[test]
+    fn cursor_respects_multibyte_chars_and_lines() {
+        let mut text = "é\nab".to_string();
+        let mut cursor = 1;
+        assert_eq!(clamp_cursor(&text, cursor), 0);
+        cursor = text.len();
+        handle_key(&mut text, &mut cursor, key(KeyCode::Home));
+        assert_eq!(cursor, 3);
+        handle_key(&mut text, &mut cursor, key(KeyCode::Left));
+        handle_key(&mut text, &mut cursor, key(KeyCode::Backspace));
+        assert_eq!(text, "\nab");
+    }
+}
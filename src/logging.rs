@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+
+/// Daily log files kept in the log directory before the oldest is deleted.
+const MAX_LOG_FILES: usize = 7;
+
+/// Verbosity chosen with `--log-level`.
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    fn filter(self) -> LevelFilter {
+        match self {
+            LogLevel::Off => LevelFilter::OFF,
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// `lazyredis/logs` next to the config file.
+pub fn log_dir(base_path_override: Option<&Path>) -> Option<PathBuf> {
+    match base_path_override {
+        Some(base_path) => Some(base_path.to_path_buf()),
+        None => directories::BaseDirs::new().map(|base_dirs| base_dirs.config_dir().to_path_buf()),
+    }
+    .map(|base| base.join("lazyredis").join("logs"))
+}
+
+/// `lazyredis.<date>.log` files in `dir`, rotated daily. The directory must exist before
+/// the appender looks for old files to prune, or it reports that on stderr.
+fn file_appender(dir: &Path) -> Result<RollingFileAppender, String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("lazyredis")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir)
+        .map_err(|e| e.to_string())
+}
+
+fn subscriber(
+    level: LogLevel,
+    writer: tracing_appender::non_blocking::NonBlocking,
+) -> impl tracing::Subscriber + Send + Sync {
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_max_level(level.filter())
+        .finish()
+}
+
+/// Sends log events to the rotating file only, so nothing reaches the terminal while the
+/// TUI owns it. Events are written until the returned guard is dropped.
+pub fn init(level: LogLevel, base_path_override: Option<&Path>) -> Result<Option<WorkerGuard>, String> {
+    if level == LogLevel::Off {
+        return Ok(None);
+    }
+    let Some(dir) = log_dir(base_path_override) else {
+        return Ok(None);
+    };
+    let (writer, guard) = tracing_appender::non_blocking(file_appender(&dir)?);
+    tracing::subscriber::set_global_default(subscriber(level, writer)).map_err(|e| e.to_string())?;
+    Ok(Some(guard))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn events_at_or_above_the_level_reach_the_log_file() {
+        let base = tempdir().unwrap();
+        let dir = log_dir(Some(base.path())).unwrap();
+        let (writer, guard) = tracing_appender::non_blocking(file_appender(&dir).unwrap());
+        tracing::subscriber::with_default(subscriber(LogLevel::Warn, writer), || {
+            tracing::error!("connection refused");
+            tracing::info!("executed GET");
+        });
+        drop(guard);
+
+        let file = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap();
+        let name = file.file_name().into_string().unwrap();
+        assert!(name.starts_with("lazyredis.") && name.ends_with(".log"), "{}", name);
+        let contents = std::fs::read_to_string(file.path()).unwrap();
+        assert!(contents.contains("connection refused"));
+        assert!(!contents.contains("executed GET"));
+    }
+}
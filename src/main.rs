@@ -1,50 +1,118 @@
 pub mod app;
+pub mod bookmarks;
+pub mod session;
+pub mod snapshot;
 pub mod ui;
 pub mod config;
+pub mod line_edit;
 pub mod seed;
 pub mod search;
 pub mod command;
+pub mod keymap;
+pub mod logging;
+pub mod headless;
+pub mod benchmark;
+pub mod input;
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyCode, KeyEventKind, KeyModifiers},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
+    layout::Rect,
     Terminal,
 };
-use std::{io, time::Duration};
+use std::{io::{self, Write}, path::{Path, PathBuf}, time::{Duration, Instant}};
 use anyhow::Result;
 use clap::Parser;
 use redis::Client;
 use url::Url;
+use app::delete_preview::{glob_base, DeletePreview};
+use app::key_delimiters::KeyDelimiters;
 
 /// A simple TUI for Redis
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct CliArgs {
-    /// Profile name to connect on startup, or to select for seeding/purging
-    #[arg(long, value_name = "PROFILE")]
+    #[command(subcommand)]
+    command: Option<headless::HeadlessCommand>,
+
+    /// Profile name to connect on startup or for a subcommand, or to select for seeding/purging
+    #[arg(long, value_name = "PROFILE", global = true)]
     profile: Option<String>,
 
+    /// Open the TUI on this Redis URL (e.g. redis://host:6379/2) as a read-only, non-dev
+    /// connection, ignoring the configured profiles
+    #[arg(long, value_name = "URL", conflicts_with_all = ["profile", "seed", "seed_file", "seed_size", "seed_only", "purge", "pattern", "dry_run", "exec_file"])]
+    url: Option<String>,
+
     /// Seed the Redis instance with test data
     #[arg(long)]
     seed: bool,
 
+    /// Seed from a TOML or JSON spec of key patterns, types, counts and TTLs instead
+    #[arg(long, value_name = "FILE", conflicts_with = "purge")]
+    seed_file: Option<PathBuf>,
+
+    /// Size of the built-in dataset to seed (implies --seed)
+    #[arg(long, value_enum, value_name = "SIZE", conflicts_with_all = ["seed_file", "purge"])]
+    seed_size: Option<seed::SeedSize>,
+
+    /// Seed only these kinds of keys, comma-separated (implies --seed)
+    #[arg(long, value_enum, value_name = "GROUPS", value_delimiter = ',', conflicts_with = "purge")]
+    seed_only: Vec<seed::SeedGroup>,
+
     /// Purge (delete) all keys in the Redis instance
     #[arg(long)]
     purge: bool,
-}
 
-// Add a page size constant for value navigation
-const VALUE_NAVIGATION_PAGE_SIZE: usize = 10;
+    /// With --purge, delete only the keys matching this glob (SCAN + UNLINK) instead of
+    /// flushing the database
+    #[arg(long, value_name = "GLOB", requires = "purge")]
+    pattern: Option<String>,
+
+    /// With --purge, report how many keys would be deleted per prefix and delete nothing
+    #[arg(long, requires = "purge")]
+    dry_run: bool,
+
+    /// Run the Redis commands in FILE (one per line) against the profile and exit
+    #[arg(long, value_name = "FILE")]
+    exec_file: Option<PathBuf>,
+
+    /// With --exec-file, keep going after a command fails
+    #[arg(long, requires = "exec_file")]
+    force: bool,
+
+    /// Detail written to the log files in the lazyredis/logs config directory
+    #[arg(long, value_enum, default_value_t = logging::LogLevel::Warn)]
+    log_level: logging::LogLevel,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = CliArgs::parse();
+    let _log_guard = logging::init(args.log_level, None).unwrap_or_else(|e| {
+        eprintln!("Logging disabled: {}", e);
+        None
+    });
+    tracing::info!("lazyredis {} starting", env!("CARGO_PKG_VERSION"));
 
-    if args.seed || args.purge {
+    let seeding = args.seed || args.seed_file.is_some() || args.seed_size.is_some() || !args.seed_only.is_empty();
+    if seeding || args.purge {
+        let seed_options = seed::SeedOptions {
+            size: args.seed_size.unwrap_or_default(),
+            only: args.seed_only.clone(),
+        };
+        let seed_spec = match args.seed_file.as_deref().map(seed::SeedSpec::load) {
+            Some(Ok(spec)) => Some(spec),
+            Some(Err(e)) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            None => None,
+        };
         let action = if args.purge { "purge" } else { "seed" };
         let verb = if args.purge { "Purging" } else { "Seeding" };
         let noun = if args.purge { "keys" } else { "with test data" };
@@ -92,17 +160,41 @@ async fn main() -> Result<()> {
                 profile.url,
                 action
             );
-            if args.purge {
+            if args.dry_run {
+                let delimiters = KeyDelimiters::new(&app_config.settings.key_delimiters);
+                let pattern = args.pattern.as_deref();
+                if let Err(e) = preview_purge(&profile.url, profile.db.unwrap_or(0), pattern, &delimiters).await {
+                    eprintln!("Error scanning Redis for profile '{}': {}", profile.name, e);
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            if let Some(pattern) = args.pattern.as_deref().filter(|_| args.purge) {
+                println!(
+                    "This will delete all keys matching '{}' in database {} on {}.",
+                    pattern,
+                    profile.db.unwrap_or(0),
+                    profile.url
+                );
+            } else if args.purge {
                 println!(
                     "This will delete ALL KEYS in database {} on {}.",
                     profile.db.unwrap_or(0),
                     profile.url
                 );
+            } else if let Some(spec) = &seed_spec {
+                println!(
+                    "This will delete ALL KEYS in database {} on {} and add {} key(s) from the seed spec.",
+                    profile.db.unwrap_or(0),
+                    profile.url,
+                    spec.total_keys(&seed_options)
+                );
             } else {
                 println!(
-                    "This will delete ALL KEYS in database {} on {} and add a large amount of test data.",
+                    "This will delete ALL KEYS in database {} on {} and add the {:?} test dataset.",
                     profile.db.unwrap_or(0),
-                    profile.url
+                    profile.url,
+                    seed_options.size
                 );
             }
             println!("Are you sure you want to proceed? (yes/no)");
@@ -114,12 +206,12 @@ async fn main() -> Result<()> {
             }
 
             if args.purge {
-                match purge_redis_data(&profile.url, profile.db.unwrap_or(0)).await {
+                match purge_redis_data(&profile.url, profile.db.unwrap_or(0), args.pattern.as_deref()).await {
                     Ok(_) => println!("Redis purged successfully for profile '{}'.", profile.name),
                     Err(e) => eprintln!("Error purging Redis for profile '{}': {}", profile.name, e),
                 }
             } else {
-                match seed::seed_redis_data(&profile.url, profile.db.unwrap_or(0)).await {
+                match seed::seed_redis_data(&profile.url, profile.db.unwrap_or(0), seed_spec.as_ref(), &seed_options).await {
                     Ok(_) => println!("Redis seeded successfully for profile '{}'.", profile.name),
                     Err(e) => eprintln!("Error seeding Redis for profile '{}': {}", profile.name, e),
                 }
@@ -133,13 +225,46 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    if let Some(command) = args.command {
+        if let Err(e) = headless::run(command, args.profile.as_deref()).await {
+            tracing::error!("{}", e);
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(path) = &args.exec_file {
+        return exec_file(args.profile.as_deref(), path, args.force).await;
+    }
+
+    let url_profile = match args.url.as_deref().map(config::ConnectionProfile::from_url) {
+        Some(Ok(profile)) => Some(profile),
+        Some(Err(e)) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        None => None,
+    };
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let app_config_tui = config::Config::load_quiet(None);
+    let mut app_config_tui = config::Config::load_quiet(None);
+    // An ad-hoc URL replaces the profiles and leaves the saved session alone
+    let restore_session = app_config_tui.settings.restore_session && url_profile.is_none();
+    if let Some(profile) = url_profile {
+        app_config_tui.profiles = vec![profile];
+    }
+    let session = restore_session.then(|| session::SessionState::load(None));
+    // Without --profile, reopen the profile used last time if it still exists
+    let session_profile = session
+        .as_ref()
+        .and_then(|session| session.profile.as_deref())
+        .and_then(|name| app_config_tui.profiles.iter().find(|p| p.name == name));
     let (initial_url, initial_profile_name) = if let Some(profile_name) = &args.profile {
         match app_config_tui.profiles.iter().find(|p| &p.name == profile_name) {
             Some(p) => (p.url.clone(), p.name.clone()),
@@ -148,15 +273,24 @@ async fn main() -> Result<()> {
                 std::process::exit(1);
             }
         }
+    } else if let Some(p) = session_profile {
+        (p.url.clone(), p.name.clone())
     } else {
         (
             app_config_tui.profiles.first().map_or("redis://127.0.0.1:6379".to_string(), |p| p.url.clone()),
             app_config_tui.profiles.first().map_or("Default".to_string(), |p| p.name.clone()),
         )
     };
-    let app = app::App::new(&initial_url, &initial_profile_name, app_config_tui.profiles.clone());
+    let mut app = app::App::new(
+        &initial_url,
+        &initial_profile_name,
+        app_config_tui.profiles.clone(),
+        app_config_tui.settings.clone(),
+        bookmarks::Bookmarks::load(None),
+    );
+    app.restore_session = session;
 
-    let res = run_app(&mut terminal, app).await;
+    let res = run_app(&mut terminal, &mut app).await;
 
     disable_raw_mode()?;
     execute!(
@@ -166,15 +300,97 @@ async fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    // Saved once the terminal is back, so a failure is printed where the user sees it
+    if restore_session {
+        if let Err(e) = app.session_state().save(None) {
+            eprintln!("Failed to save session state: {}", e);
+        }
+    }
+
     if let Err(err) = res {
+        tracing::error!("exited with an error: {:?}", err);
         println!("{:?}", err);
     }
     
     Ok(())
 }
 
-/// Purge (flush) all keys in the specified Redis database
-async fn purge_redis_data(redis_url: &str, db_index: u8) -> Result<()> {
+/// Runs a command file against the named (or first) profile, printing each reply.
+/// Exits with status 1 when any command failed.
+async fn exec_file(profile_name: Option<&str>, path: &Path, force: bool) -> Result<()> {
+    let app_config = config::Config::load_quiet(None);
+    let profile = match profile_name {
+        Some(name) => app_config.profiles.iter().find(|p| p.name == name),
+        None => app_config.profiles.first(),
+    };
+    let Some(profile) = profile else {
+        eprintln!("Profile '{}' not found in configuration.", profile_name.unwrap_or("Default"));
+        std::process::exit(1);
+    };
+    if profile.read_only.unwrap_or(false) {
+        eprintln!("Profile '{}' is read-only; --exec-file is disabled.", profile.name);
+        std::process::exit(1);
+    }
+
+    let client = Client::open(profile.url.as_str())?;
+    let mut con = client.get_multiplexed_async_connection().await?;
+    redis::cmd("SELECT").arg(profile.db.unwrap_or(0)).query_async::<()>(&mut con).await?;
+
+    let report = command::run_batch_file(&mut con, path, force)
+        .await
+        .map_err(anyhow::Error::msg)?;
+    for line in &report.lines {
+        println!("{}", line);
+    }
+    println!("{}", report.summary());
+    if report.failed > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Keys fetched per `SCAN` and unlinked per `UNLINK` by a pattern purge.
+const PURGE_BATCH: usize = 1000;
+
+/// Prefixes listed by `--dry-run` before the rest are summed up.
+const PREVIEW_GROUPS: usize = 20;
+
+/// `--purge --dry-run`: counts the keys a purge would delete per prefix, deleting nothing.
+async fn preview_purge(redis_url: &str, db_index: u8, pattern: Option<&str>, delimiters: &KeyDelimiters) -> Result<()> {
+    let client = Client::open(redis_url)?;
+    let mut con = client.get_multiplexed_async_connection().await?;
+    redis::cmd("SELECT").arg(db_index).query_async::<()>(&mut con).await?;
+
+    let pattern = pattern.unwrap_or("*");
+    let mut preview = DeletePreview::new(glob_base(pattern, delimiters));
+    let mut cursor: u64 = 0;
+    loop {
+        let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(PURGE_BATCH)
+            .query_async(&mut con)
+            .await?;
+        keys.iter().for_each(|key| preview.add(key, delimiters));
+        if next_cursor == 0 {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    println!(
+        "Dry run: would delete {} key(s) matching '{}' in database {}:",
+        preview.total, pattern, db_index
+    );
+    preview.lines(PREVIEW_GROUPS).iter().for_each(|line| println!("  {}", line));
+    println!("Nothing was deleted.");
+    Ok(())
+}
+
+/// Flushes the DB or, with `pattern`, unlinks only the keys matching it batch by batch.
+async fn purge_redis_data(redis_url: &str, db_index: u8, pattern: Option<&str>) -> Result<()> {
     println!("Connecting to {} (DB {}) to purge keys...", redis_url, db_index);
     let client = Client::open(redis_url)?;
     let mut con = client.get_multiplexed_async_connection().await?;
@@ -182,6 +398,33 @@ async fn purge_redis_data(redis_url: &str, db_index: u8) -> Result<()> {
     redis::cmd("SELECT").arg(db_index).query_async::<()>(&mut con).await?;
     println!("Selected database {}.", db_index);
 
+    if let Some(pattern) = pattern {
+        println!("Deleting keys matching '{}' in database {}...", pattern, db_index);
+        let mut cursor: u64 = 0;
+        let mut deleted: u64 = 0;
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(pattern)
+                .arg("COUNT")
+                .arg(PURGE_BATCH)
+                .query_async(&mut con)
+                .await?;
+            if !keys.is_empty() {
+                deleted += redis::cmd("UNLINK").arg(&keys).query_async::<u64>(&mut con).await?;
+                print!("\rDeleted {} key(s)...", deleted);
+                io::stdout().flush()?;
+            }
+            if next_cursor == 0 {
+                break;
+            }
+            cursor = next_cursor;
+        }
+        println!("\rDeleted {} key(s) matching '{}'.", deleted, pattern);
+        return Ok(());
+    }
+
     println!("Purging database {}...", db_index);
     redis::cmd("FLUSHDB").query_async::<()>(&mut con).await?;
     println!("Database {} purged.", db_index);
@@ -189,253 +432,65 @@ async fn purge_redis_data(redis_url: &str, db_index: u8) -> Result<()> {
     Ok(())
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: app::App) -> io::Result<()> {
-    // Trigger initial connect, status will be set by this sync call
-    app.trigger_initial_connect(); 
-    // First draw will show "Preparing initial connection..."
-    terminal.draw(|f| ui::ui(f, &app))?; 
-    // Removed: app.initial_connect_and_fetch().await; We handle this in the loop now
+async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut app::App) -> io::Result<()> {
+    // The worker connects while the first frame shows "Connecting to ..."
+    app.trigger_initial_connect();
+    terminal.draw(|f| ui::ui(f, app))?;
 
     loop {
-        // Handle pending asynchronous operations if any
-        // We clone it because the execute methods take `&mut self` and might clear it.
-        let mut did_async_op = false;
-        let operation_to_execute = app.pending_operation.take();
-        if let Some(operation_to_execute) = operation_to_execute {
-            match operation_to_execute {
-                app::PendingOperation::InitialConnect => {
-                    app.execute_initial_connect().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::ApplySelectedDb => {
-                    app.execute_apply_selected_db().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::SelectProfileAndConnect => {
-                    app.select_profile_and_connect().await; 
-                    did_async_op = true;
-                }
-                app::PendingOperation::ConfirmDeleteItem => {
-                    app.confirm_delete_item().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::ExecuteCommand => {
-                    app.execute_command_input().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::ActivateSelectedKey => {
-                    app.activate_selected_key().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::CopyKeyNameToClipboard => {
-                    crate::app::app_clipboard::copy_selected_key_name_to_clipboard(&mut app).await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::CopyKeyValueToClipboard => {
-                    crate::app::app_clipboard::copy_selected_key_value_to_clipboard(&mut app).await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::ActivateSelectedFilteredKey => {
-                    app.activate_selected_filtered_key().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::FetchRedisStats => {
-                    app.execute_fetch_redis_stats().await;
-                    did_async_op = true;
-                }
-                app::PendingOperation::AutoPreviewCurrentKey => {
-                    app.auto_preview_current_key().await;
-                    did_async_op = true;
-                }
-            }
-        }
-        if did_async_op {
-            continue;
-        }
+        app.poll_worker();
+        app.poll_stream_tail();
+        app.poll_pubsub();
+        app.poll_live_updates();
+        app.poll_monitor();
+        app.poll_latency_panel();
+        app.poll_analysis();
+        app.poll_snapshots();
+        app.poll_compare();
+        app.poll_key_scan();
+        app.poll_bulk_delete();
+        app.poll_prefix_walk();
+        app.poll_tree_refresh();
+        app.poll_ttl_countdown();
+        app.poll_search_scan();
+        app.notifications.expire(Instant::now());
+        app.poll_value_grep();
+        app.poll_search_job();
 
-        // Auto-refresh stats if needed
-        if app.should_refresh_stats() {
-            app.trigger_fetch_redis_stats();
-            continue;
+        // Stats, key types and sizes are read by the worker while the loop keeps drawing
+        app.request_background_reads();
+        if app.take_due_auto_preview() {
+            app.auto_preview_current_key();
+        }
+        if app.should_refresh_watch() {
+            app.refresh_watched_key();
         }
-        terminal.draw(|f| ui::ui(f, &app))?;
+        let size = terminal.size()?;
+        app.sync_list_scroll(&ui::panel_areas(Rect::new(0, 0, size.width, size.height), app));
+        terminal.draw(|f| ui::ui(f, app))?;
 
         // Now handle events in a separate block (mutable borrow)
-        if event::poll(Duration::from_millis(100))? {
-            if let CEvent::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press { 
-                    app.clipboard_status = None; 
-
-                    // Only process key events if no async operation is pending
-                    // This prevents inputs from interfering with an ongoing async task's state changes
-                    // or triggering new operations while one is in progress.
-                    if app.pending_operation.is_none() {
-                        if app.profile_state.is_active {
-                            match key.code {
-                                KeyCode::Char('q') => return Ok(()),
-                                KeyCode::Char('p') | KeyCode::Esc => app.toggle_profile_selector(),
-                                KeyCode::Char('j') | KeyCode::Down => app.next_profile_in_list(),
-                                KeyCode::Char('k') | KeyCode::Up => app.previous_profile_in_list(),
-                                KeyCode::Enter => {
-                                    app.pending_operation = Some(app::PendingOperation::SelectProfileAndConnect);
-                                }
-                                _ => {}
-                            }
-                        } else if app.delete_dialog.show_confirmation_dialog {
-                            match key.code {
-                                KeyCode::Enter => {
-                                    app.pending_operation = Some(app::PendingOperation::ConfirmDeleteItem);
-                                }
-                                KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('N') => app.cancel_delete_item(),
-                                KeyCode::Char('y') | KeyCode::Char('Y') => {
-                                    app.pending_operation = Some(app::PendingOperation::ConfirmDeleteItem);
-                                }
-                                _ => {}
-                            }
-                        } else if app.command_state.is_active {
-                            match key.code {
-                                KeyCode::Esc => {
-                                    app.close_command_prompt();
-                                    terminal.hide_cursor()?;
-                                }
-                                KeyCode::Backspace => {
-                                    app.command_state.input_buffer.pop();
-                                }
-                                KeyCode::Char(c) => {
-                                    app.command_state.input_buffer.push(c);
-                                }
-                                KeyCode::Enter => {
-                                    app.pending_operation = Some(app::PendingOperation::ExecuteCommand);
-                                }
-                                _ => {}
-                            }
-                        } else if app.search_state.is_active {
-                            match key.code {
-                                KeyCode::Char(c) => {
-                                    app.search_state.query.push(c);
-                                    app.update_filtered_keys();
-                                }
-                                KeyCode::Backspace => {
-                                    app.search_state.query.pop();
-                                    app.update_filtered_keys();
-                                }
-                                KeyCode::Esc => {
-                                    app.exit_search_mode();
-                                }
-                                KeyCode::Enter => {
-                                    app.pending_operation = Some(app::PendingOperation::ActivateSelectedFilteredKey);
-                                }
-                                KeyCode::Down => {
-                                    app.select_next_filtered_key();
-                                }
-                                KeyCode::Up => {
-                                    app.select_previous_filtered_key();
-                                }
-                                _ => {}
-                            }
-                        } else if (key.code == KeyCode::Tab
-                            && key.modifiers.contains(KeyModifiers::SHIFT))
-                            || key.code == KeyCode::BackTab
-                        {
-                            app.cycle_focus_backward();
-                        } else {
-                            match key.code {
-                                KeyCode::Char('q') => return Ok(()),
-                                KeyCode::Char('/') => {
-                                    app.enter_search_mode();
-                                }
-                                KeyCode::Char('p') => app.toggle_profile_selector(),
-                                KeyCode::Char('s') => app.toggle_stats_view(),
-                                KeyCode::Tab => app.cycle_focus_forward(),
-                                KeyCode::Char('1') => app.focus_db(),
-                                KeyCode::Char('2') => app.focus_keys(),
-                                KeyCode::Char('3') => app.focus_values(),
-                                KeyCode::Char('y') => app.pending_operation = Some(app::PendingOperation::CopyKeyNameToClipboard),
-                                KeyCode::Char('Y') => app.pending_operation = Some(app::PendingOperation::CopyKeyValueToClipboard),
-                                    KeyCode::Char('d') => {
-                                        if app.is_key_view_focused {
-                                            app.initiate_delete_selected_item(); // This is sync, sets up dialog
-                                        }
-                                    }
-                                    KeyCode::Char(':') => {
-                                        app.open_command_prompt(); // Sync
-                                        terminal.show_cursor()?;
-                                    }
-                                    KeyCode::Char('j') | KeyCode::Down => {
-                                        if key.modifiers == crossterm::event::KeyModifiers::SHIFT {
-                                            if app.is_key_view_focused {
-                                                app.next_key_in_view_with_shift();
-                                            }
-                                        } else if app.is_value_view_focused {
-                                            app.select_next_value_item();
-                                        } else if app.is_key_view_focused {
-                                            app.next_key_in_view();
-                                            app.pending_operation = Some(app::PendingOperation::AutoPreviewCurrentKey);
-                                        } else {
-                                            app.next_db();
-                                        }
-                                    }
-                                    KeyCode::Char('k') | KeyCode::Up => {
-                                        if key.modifiers == crossterm::event::KeyModifiers::SHIFT {
-                                            if app.is_key_view_focused {
-                                                app.previous_key_in_view_with_shift();
-                                            }
-                                        } else if app.is_value_view_focused {
-                                            app.select_previous_value_item();
-                                        } else if app.is_key_view_focused {
-                                            app.previous_key_in_view();
-                                            app.pending_operation = Some(app::PendingOperation::AutoPreviewCurrentKey);
-                                        } else {
-                                            app.previous_db();
-                                        }
-                                    }
-                                    KeyCode::PageDown => { 
-                                        if app.is_value_view_focused {
-                                            app.select_page_down_value_item(VALUE_NAVIGATION_PAGE_SIZE);
-                                        }
-                                    }
-                                    KeyCode::PageUp => { 
-                                        if app.is_value_view_focused {
-                                            app.select_page_up_value_item(VALUE_NAVIGATION_PAGE_SIZE);
-                                        }
-                                    }
-                                    KeyCode::Enter => {
-                                        if app.search_state.is_active {
-                                            app.pending_operation = Some(app::PendingOperation::ActivateSelectedFilteredKey);
-                                        } else if app.is_key_view_focused {
-                                            app.pending_operation = Some(app::PendingOperation::ActivateSelectedKey);
-                                        } else if !app.is_value_view_focused && !app.is_key_view_focused {
-                                            app.trigger_apply_selected_db();
-                                        } else if !app.is_value_view_focused {
-                                            app.is_key_view_focused = true;
-                                            app.is_value_view_focused = false;
-                                        }
-                                    }
-                                    KeyCode::Backspace => { 
-                                        if app.is_key_view_focused {
-                                            app.navigate_key_tree_up();
-                                        }
-                                    }
-                                    KeyCode::Esc => {
-                                        if app.is_key_view_focused {
-                                            if !app.selected_indices.is_empty() {
-                                                app.clear_multi_selection();
-                                            } else {
-                                                app.navigate_to_key_tree_root();
-                                            }
-                                        }
-                                    }
-                                    KeyCode::Char(' ') => {
-                                        if key.modifiers == crossterm::event::KeyModifiers::CONTROL && app.is_key_view_focused {
-                                            app.toggle_current_selection();
-                                        }
-                                    }
-                                    _ => {}
-                                }
-                            }
-                    } // End of if app.pending_operation.is_none()
+        // Wake up in time for a scheduled preview
+        let wait = app.preview_due.map_or(Duration::from_millis(100), |due| {
+            due.saturating_duration_since(Instant::now()).min(Duration::from_millis(100))
+        });
+        if event::poll(wait)? {
+            let event = event::read()?;
+            if let CEvent::Mouse(mouse) = event {
+                let size = terminal.size()?;
+                let areas = ui::panel_areas(Rect::new(0, 0, size.width, size.height), app);
+                app.handle_mouse(mouse, &areas);
+            } else if let CEvent::Key(key) = event {
+                // Redis work runs on the worker, so keys are handled even while it is busy
+                if key.kind == KeyEventKind::Press {
+                    match input::handle_key(app, key) {
+                        input::KeyOutcome::Handled => {}
+                        input::KeyOutcome::Quit => return Ok(()),
+                        input::KeyOutcome::ShowCursor => terminal.show_cursor()?,
+                        input::KeyOutcome::HideCursor => terminal.hide_cursor()?,
+                    }
                 }
             }
         }
     }
-} 
+}
@@ -1,13 +1,51 @@
 use crate::app::{KeyTreeNode};
+use crate::app::key_delimiters::KeyDelimiters;
 use fuzzy_matcher::FuzzyMatcher; // Added import
+use regex::Regex;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Fuzzy and regex searches list at most this many of the best matches.
+pub const MAX_LOCAL_RESULTS: usize = 500;
+/// Keys matched between checks for a cancelled background search.
+const CANCEL_CHECK_INTERVAL: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Fuzzy matching over the keys loaded so far.
+    #[default]
+    Fuzzy,
+    /// Regular expression over the keys loaded so far.
+    Regex,
+    /// `SCAN MATCH <query>` against the server.
+    Server,
+    /// Keys whose string, hash or list contents contain the query.
+    Values,
+}
 
 #[derive(Debug)]
 pub struct SearchState {
     pub is_active: bool,
     pub query: String,
+    /// Byte offset of the cursor in `query`.
+    pub query_cursor: usize,
     pub filtered_keys: Vec<String>,
     pub selected_index: usize,
+    pub mode: SearchMode,
+    /// Pattern the current server-side results were scanned for.
+    pub server_query: Option<String>,
+    /// Problem with the current query (bad regex, unknown type), shown in the search bar.
+    pub query_error: Option<String>,
+    /// Matches held back by a `type:` filter until their type is cached.
+    pub type_pending: Vec<String>,
+    /// Matching excerpt for each value-search result.
+    pub snippets: HashMap<String, String>,
+    /// Character positions matched by the fuzzy query in each result.
+    pub match_positions: HashMap<String, Vec<usize>>,
+    /// Local matches beyond `MAX_LOCAL_RESULTS` that are not listed.
+    pub hidden_matches: usize,
 }
 
 #[derive(Debug)] // Added derive Debug for easier inspection if needed
@@ -23,14 +61,23 @@ impl SearchState {
         SearchState {
             is_active: false,
             query: String::new(),
+            query_cursor: 0,
             filtered_keys: Vec::new(),
             selected_index: 0,
+            mode: SearchMode::default(),
+            server_query: None,
+            query_error: None,
+            type_pending: Vec::new(),
+            snippets: HashMap::new(),
+            match_positions: HashMap::new(),
+            hidden_matches: 0,
         }
     }
 
     pub fn enter(&mut self) {
         self.is_active = true;
         self.query.clear();
+        self.query_cursor = 0;
         self.filtered_keys.clear();
         self.selected_index = 0;
     }
@@ -38,34 +85,87 @@ impl SearchState {
     pub fn exit(&mut self) {
         self.is_active = false;
         self.query.clear();
+        self.query_cursor = 0;
         self.filtered_keys.clear();
         self.selected_index = 0;
+        self.server_query = None;
+        self.query_error = None;
+        self.type_pending.clear();
+        self.snippets.clear();
+        self.hidden_matches = 0;
     }
 
-    pub fn update_filtered_keys(&mut self, raw_keys: &[String]) {
-        if self.query.is_empty() {
-            self.filtered_keys.clear();
-            self.selected_index = 0;
-            return;
+    /// Cycles fuzzy → regex → server → values.
+    pub fn toggle_mode(&mut self) {
+        self.mode = match self.mode {
+            SearchMode::Fuzzy => SearchMode::Regex,
+            SearchMode::Regex => SearchMode::Server,
+            SearchMode::Server => SearchMode::Values,
+            SearchMode::Values => SearchMode::Fuzzy,
+        };
+        self.snippets.clear();
+        self.filtered_keys.clear();
+        self.selected_index = 0;
+        self.server_query = None;
+        self.query_error = None;
+        self.hidden_matches = 0;
+    }
+
+    /// True when Enter should run the query on the server rather than open a result.
+    pub fn needs_server_search(&self) -> bool {
+        matches!(self.mode, SearchMode::Server | SearchMode::Values)
+            && !self.query.is_empty()
+            && self.server_query.as_deref() != Some(self.query.as_str())
+    }
+
+    pub fn begin_server_results(&mut self, pattern: String) {
+        self.server_query = Some(pattern);
+        self.filtered_keys.clear();
+        self.snippets.clear();
+        self.selected_index = 0;
+    }
+
+    /// Appends value-search hits with their snippets, keeping at most `limit` results.
+    pub fn add_value_results(&mut self, hits: Vec<(String, String)>, limit: usize) {
+        for (key, snippet) in hits {
+            if self.filtered_keys.len() >= limit {
+                return;
+            }
+            self.filtered_keys.push(key.clone());
+            self.snippets.insert(key, snippet);
         }
+    }
 
-        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
-        self.filtered_keys = raw_keys
-            .iter()
-            .filter_map(|full_key_name| {
-                matcher
-                    .fuzzy_match(full_key_name, &self.query)
-                    .map(|_score| full_key_name.clone())
-            })
-            .collect();
+    /// Appends keys from a server-side scan, keeping at most `limit` results.
+    pub fn add_server_results(&mut self, keys: Vec<String>, limit: usize) {
+        let room = limit.saturating_sub(self.filtered_keys.len());
+        self.filtered_keys.extend(keys.into_iter().take(room));
+    }
+
+    /// Fuzzy and regex queries are matched against the loaded keys; the other modes
+    /// only change when a query is submitted to the server.
+    pub fn matches_locally(&self) -> bool {
+        matches!(self.mode, SearchMode::Fuzzy | SearchMode::Regex)
+    }
 
-        if self.filtered_keys.is_empty() {
-            self.selected_index = 0;
+    pub fn update_filtered_keys(&mut self, raw_keys: &[String], type_map: &HashMap<String, String>) {
+        if !self.matches_locally() {
             return;
         }
+        let never = AtomicBool::new(false);
+        if let Some(outcome) = compute_matches(&self.query, self.mode, raw_keys, type_map, &never) {
+            self.apply_outcome(outcome);
+        }
+    }
 
+    pub fn apply_outcome(&mut self, outcome: SearchOutcome) {
+        self.filtered_keys = outcome.filtered_keys;
+        self.match_positions = outcome.match_positions;
+        self.type_pending = outcome.type_pending;
+        self.hidden_matches = outcome.hidden_matches;
+        self.query_error = outcome.query_error;
         if self.selected_index >= self.filtered_keys.len() {
-            self.selected_index = self.filtered_keys.len() - 1;
+            self.selected_index = self.filtered_keys.len().saturating_sub(1);
         }
     }
 
@@ -87,10 +187,10 @@ impl SearchState {
 
     // Takes necessary App data as read-only references or copies
     // Returns information needed by App to complete the activation
-pub fn activate_selected_filtered(&self, key_delimiter: char, key_tree: &HashMap<String, KeyTreeNode>, raw_keys: &[String]) -> Option<SearchActivationInfo> {
+pub fn activate_selected_filtered(&self, key_delimiters: &KeyDelimiters, key_tree: &HashMap<String, KeyTreeNode>, raw_keys: &[String]) -> Option<SearchActivationInfo> {
         if self.selected_index < self.filtered_keys.len() {
             let full_key_path = self.filtered_keys[self.selected_index].clone();
-            let path_segments: Vec<String> = full_key_path.split(key_delimiter).map(|s| s.to_string()).collect();
+            let path_segments: Vec<String> = key_delimiters.split(&full_key_path).into_iter().map(|s| s.to_string()).collect();
 
             if path_segments.is_empty() {
                 return None; // Activation failed or not possible
@@ -117,8 +217,8 @@ pub fn activate_selected_filtered(&self, key_delimiter: char, key_tree: &HashMap
             
             // Additional check: Even if not a KeyTreeNode::Folder, if other keys start with this path + delimiter, treat as folder
             if !is_folder_in_tree {
-                let prefix_to_check = format!("{}{}", full_key_path, key_delimiter);
-                if raw_keys.iter().any(|k| k.starts_with(&prefix_to_check)) {
+                let prefix_to_check = format!("{}{}", key_delimiters.canonical(&full_key_path), key_delimiters.primary());
+                if raw_keys.iter().any(|k| key_delimiters.canonical(k).starts_with(&prefix_to_check)) {
                     is_folder_in_tree = true;
                 }
             }
@@ -134,6 +234,179 @@ pub fn activate_selected_filtered(&self, key_delimiter: char, key_tree: &HashMap
     }
 }
 
+/// Result of matching a query against the loaded keys.
+#[derive(Debug, Default)]
+pub struct SearchOutcome {
+    pub filtered_keys: Vec<String>,
+    pub match_positions: HashMap<String, Vec<usize>>,
+    pub type_pending: Vec<String>,
+    pub hidden_matches: usize,
+    pub query_error: Option<String>,
+}
+
+/// Matches a fuzzy or regex `query` against `raw_keys`, giving up with `None` once
+/// `cancelled` is set so a stale search stops early.
+pub fn compute_matches(
+    query: &str,
+    mode: SearchMode,
+    raw_keys: &[String],
+    type_map: &HashMap<String, String>,
+    cancelled: &AtomicBool,
+) -> Option<SearchOutcome> {
+    let mut outcome = SearchOutcome::default();
+    let (type_name, text) = split_type_filter(query);
+    let key_type = match type_name.map(redis_type_name).transpose() {
+        Ok(key_type) => key_type,
+        Err(e) => {
+            outcome.query_error = Some(e);
+            return Some(outcome);
+        }
+    };
+    // Regex mode uses the whole text; in fuzzy mode a `re:` prefix opts in
+    let regex_query = match mode {
+        SearchMode::Regex => Some(text.as_str()),
+        _ => text.strip_prefix("re:"),
+    };
+    let pattern = regex_query.unwrap_or(&text);
+    if pattern.is_empty() && key_type.is_none() {
+        return Some(outcome);
+    }
+
+    let mut matches: Vec<String> = if pattern.is_empty() {
+        raw_keys.to_vec()
+    } else if regex_query.is_some() {
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                // The last line of the error carries the reason, e.g. "unclosed group"
+                let message = e.to_string();
+                let reason = message.lines().last().unwrap_or_default();
+                outcome.query_error = Some(reason.trim_start_matches("error: ").to_string());
+                return Some(outcome);
+            }
+        };
+        let mut matches = Vec::new();
+        for chunk in raw_keys.chunks(CANCEL_CHECK_INTERVAL) {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            matches.extend(chunk.iter().filter(|key| regex.is_match(key)).cloned());
+        }
+        matches
+    } else {
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        let mut scored: Vec<(i64, &String)> = Vec::new();
+        for chunk in raw_keys.chunks(CANCEL_CHECK_INTERVAL) {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+            scored.extend(chunk.iter().filter_map(|full_key_name| {
+                matcher
+                    .fuzzy_match(full_key_name, pattern)
+                    .map(|score| (score, full_key_name))
+            }));
+        }
+        // Best matches first; equal scores stay alphabetical
+        scored.sort_by(|(a_score, a_key), (b_score, b_key)| {
+            b_score.cmp(a_score).then_with(|| a_key.cmp(b_key))
+        });
+        scored.into_iter().map(|(_, key)| key.clone()).collect()
+    };
+
+    // Keys whose type is not cached yet are held back until the App fetches it
+    if let Some(key_type) = key_type {
+        outcome.type_pending = matches
+            .iter()
+            .filter(|key| !type_map.contains_key(*key))
+            .cloned()
+            .collect();
+        matches.retain(|key| type_map.get(key).is_some_and(|t| t == key_type));
+    }
+    outcome.hidden_matches = matches.len().saturating_sub(MAX_LOCAL_RESULTS);
+    matches.truncate(MAX_LOCAL_RESULTS);
+    // Highlight positions are only computed for the results that are shown
+    if regex_query.is_none() && !pattern.is_empty() {
+        let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+        for key in &matches {
+            if let Some((_score, positions)) = matcher.fuzzy_indices(key, pattern) {
+                outcome.match_positions.insert(key.clone(), positions);
+            }
+        }
+    }
+    outcome.filtered_keys = matches;
+    Some(outcome)
+}
+
+/// A fuzzy or regex search running on the blocking pool. Dropping it cancels the search.
+pub struct SearchJob {
+    cancelled: Arc<AtomicBool>,
+    receiver: oneshot::Receiver<Option<SearchOutcome>>,
+}
+
+impl SearchJob {
+    pub fn spawn(
+        query: String,
+        mode: SearchMode,
+        raw_keys: Arc<Vec<String>>,
+        type_map: Arc<HashMap<String, String>>,
+    ) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = oneshot::channel();
+        let flag = Arc::clone(&cancelled);
+        tokio::task::spawn_blocking(move || {
+            let _ = sender.send(compute_matches(&query, mode, &raw_keys, &type_map, &flag));
+        });
+        Self { cancelled, receiver }
+    }
+
+    /// The outcome once the search has finished.
+    pub fn poll(&mut self) -> Option<SearchOutcome> {
+        self.receiver.try_recv().ok().flatten()
+    }
+}
+
+impl Drop for SearchJob {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Splits a `type:<name>` token out of a query, returning it and the remaining text.
+pub fn split_type_filter(query: &str) -> (Option<&str>, String) {
+    split_prefixed_token(query, "type:")
+}
+
+/// Splits the first whitespace-separated token starting with `prefix` out of a query.
+pub fn split_prefixed_token<'a>(query: &'a str, prefix: &str) -> (Option<&'a str>, String) {
+    let mut value = None;
+    let mut rest = Vec::new();
+    for token in query.split(' ') {
+        match token.strip_prefix(prefix) {
+            Some(found) if value.is_none() => value = Some(found),
+            _ => rest.push(token),
+        }
+    }
+    (value, rest.join(" ").trim().to_string())
+}
+
+/// Maps a user-facing type name to what `TYPE` returns.
+pub fn redis_type_name(name: &str) -> Result<&'static str, String> {
+    match name.to_ascii_lowercase().as_str() {
+        "string" | "str" => Ok("string"),
+        "hash" => Ok("hash"),
+        "list" => Ok("list"),
+        "set" => Ok("set"),
+        "zset" => Ok("zset"),
+        "stream" => Ok("stream"),
+        "json" | "rejson-rl" => Ok("ReJSON-RL"),
+        "bloom" | "mbbloom--" => Ok("MBbloom--"),
+        "cuckoo" | "mbbloomcf" => Ok("MBbloomCF"),
+        "topk" | "topk-type" => Ok("TopK-TYPE"),
+        "tdigest" | "tdis-type" => Ok("TDIS-TYPE"),
+        other => Err(format!("unknown type '{}'", other)),
+    }
+}
+
 impl Default for SearchState {
     fn default() -> Self {
         Self::new()
@@ -156,7 +429,7 @@ mod tests {
         let raw_keys = vec!["foo:bar".to_string()];
 
         let info = state
-            .activate_selected_filtered(':', &key_tree, &raw_keys)
+            .activate_selected_filtered(&KeyDelimiters::default(), &key_tree, &raw_keys)
             .expect("activation");
 
         assert!(info.is_folder);
@@ -178,9 +451,85 @@ mod tests {
         let raw_keys = vec!["alpha".to_string()];
 
         let info = state
-            .activate_selected_filtered(':', &key_tree, &raw_keys)
+            .activate_selected_filtered(&KeyDelimiters::default(), &key_tree, &raw_keys)
             .expect("activation");
 
         assert!(!info.is_folder);
     }
+
+    #[test]
+    fn server_mode_waits_for_submitted_query() {
+        let mut state = SearchState::new();
+        state.mode = SearchMode::Server;
+        state.query = "user:*".to_string();
+        state.update_filtered_keys(&["user:1".to_string()], &HashMap::new());
+        assert!(state.filtered_keys.is_empty());
+        assert!(state.needs_server_search());
+
+        state.begin_server_results("user:*".to_string());
+        state.add_server_results(vec!["user:1".to_string(), "user:2".to_string()], 1);
+        assert_eq!(state.filtered_keys, vec!["user:1".to_string()]);
+        assert!(!state.needs_server_search());
+    }
+
+    #[test]
+    fn regex_queries_filter_and_report_errors() {
+        let keys = vec!["user:1".to_string(), "user:22".to_string(), "order:1".to_string()];
+        let mut state = SearchState::new();
+        state.query = r"re:^user:\d{2}$".to_string();
+        state.update_filtered_keys(&keys, &HashMap::new());
+        assert_eq!(state.filtered_keys, vec!["user:22".to_string()]);
+
+        state.mode = SearchMode::Regex;
+        state.query = "(user".to_string();
+        state.update_filtered_keys(&keys, &HashMap::new());
+        assert!(state.filtered_keys.is_empty());
+        assert!(state.query_error.is_some());
+
+        state.query = ":1$".to_string();
+        state.update_filtered_keys(&keys, &HashMap::new());
+        assert_eq!(state.filtered_keys.len(), 2);
+        assert!(state.query_error.is_none());
+    }
+
+    #[test]
+    fn type_filter_keeps_matching_types_and_defers_unknown() {
+        let keys = vec!["user:1".to_string(), "user:2".to_string(), "user:3".to_string()];
+        let type_map = HashMap::from([
+            ("user:1".to_string(), "hash".to_string()),
+            ("user:2".to_string(), "string".to_string()),
+        ]);
+        let mut state = SearchState::new();
+        state.query = "type:hash user".to_string();
+        state.update_filtered_keys(&keys, &type_map);
+        assert_eq!(state.filtered_keys, vec!["user:1".to_string()]);
+        assert_eq!(state.type_pending, vec!["user:3".to_string()]);
+
+        state.query = "type:blob".to_string();
+        state.update_filtered_keys(&keys, &type_map);
+        assert!(state.filtered_keys.is_empty());
+        assert_eq!(state.query_error.as_deref(), Some("unknown type 'blob'"));
+    }
+
+    #[test]
+    fn fuzzy_matches_record_char_positions() {
+        let keys = vec!["user:profile".to_string()];
+        let mut state = SearchState::new();
+        state.query = "upr".to_string();
+        state.update_filtered_keys(&keys, &HashMap::new());
+        assert_eq!(state.match_positions.get("user:profile"), Some(&vec![0, 5, 6]));
+    }
+
+    #[test]
+    fn fuzzy_results_are_ranked_and_capped() {
+        let mut keys: Vec<String> =
+            (0..MAX_LOCAL_RESULTS + 5).map(|i| format!("x:u:s:e:r:{}", i)).collect();
+        keys.push("user".to_string());
+        let mut state = SearchState::new();
+        state.query = "user".to_string();
+        state.update_filtered_keys(&keys, &HashMap::new());
+        assert_eq!(state.filtered_keys[0], "user");
+        assert_eq!(state.filtered_keys.len(), MAX_LOCAL_RESULTS);
+        assert_eq!(state.hidden_matches, 6);
+    }
 }
@@ -0,0 +1,280 @@
+use redis::Cmd;
+
+use super::{SeedGroup, SeedSize};
+
+/// One part of the built-in dataset: `count` items, each written by `commands`.
+pub(super) struct Section {
+    pub group: SeedGroup,
+    pub label: &'static str,
+    pub count: usize,
+    pub commands: fn(usize) -> Vec<Cmd>,
+}
+
+/// Nested keys per `seed:level1:<i>` folder: 20 `level2` folders of 10 keys.
+const NESTED_PER_LEVEL1: usize = 200;
+
+/// Samples per seeded time series, one a minute.
+const TIME_SERIES_SAMPLES: usize = 1000;
+
+/// First sample timestamp of the seeded time series (2023-11-14, in milliseconds).
+const TIME_SERIES_START_MS: usize = 1_700_000_000_000;
+
+/// Items added to each seeded bloom filter.
+const BLOOM_ITEMS: usize = 500;
+
+/// The built-in dataset at `size`: bulk keys of every type, a few hand-written samples
+/// of each type, then keys of the module types (seeded only where the module is loaded).
+pub(super) fn sections(size: SeedSize) -> Vec<Section> {
+    use SeedGroup::*;
+    vec![
+        section(Strings, "simple keys", size.keys(1000), simple_key),
+        section(Strings, "nested keys", size.keys(50 * NESTED_PER_LEVEL1), nested_key),
+        section(Strings, "keys with / . - delimiters", size.keys(100), delimited_keys),
+        section(Hashes, "large hashes", size.collections(50), large_hash),
+        section(Lists, "large lists", size.collections(50), large_list),
+        section(Sets, "large sets", size.collections(50), large_set),
+        section(Zsets, "large zsets", size.collections(50), large_zset),
+        section(Streams, "large streams", size.collections(10), large_stream),
+        section(Strings, "sample strings", 1, |_| sample_strings()),
+        section(Hashes, "sample hashes", 1, |_| sample_hashes()),
+        section(Lists, "sample lists", 1, |_| sample_lists()),
+        section(Sets, "sample sets", 1, |_| sample_sets()),
+        section(Zsets, "sample zsets", 1, |_| sample_zsets()),
+        section(Streams, "sample streams", 1, |_| sample_streams()),
+        section(Json, "JSON documents", size.collections(50), json_document),
+        section(Timeseries, "time series", size.collections(10), time_series),
+        section(Bloom, "bloom filters", size.collections(10), bloom_filter),
+    ]
+}
+
+fn section(group: SeedGroup, label: &'static str, count: usize, commands: fn(usize) -> Vec<Cmd>) -> Section {
+    Section {
+        group,
+        label,
+        count,
+        commands,
+    }
+}
+
+fn set(key: String, value: String) -> Cmd {
+    redis::cmd("SET").arg(key).arg(value).clone()
+}
+
+fn simple_key(i: usize) -> Vec<Cmd> {
+    vec![set(format!("seed:simple:{}", i), format!("Simple value {}", i))]
+}
+
+fn nested_key(n: usize) -> Vec<Cmd> {
+    let (i, j, k) = (n / NESTED_PER_LEVEL1, n % NESTED_PER_LEVEL1 / 10, n % 10);
+    let key = format!("seed:level1:{}:level2:{}:key:{}", i, j, k);
+    let value = format!("Value for {}", key);
+    vec![set(key, value)]
+}
+
+fn delimited_keys(i: usize) -> Vec<Cmd> {
+    vec![
+        set(format!("seed/path/num_{}", i), format!("Path value {}", i)),
+        set(format!("seed.dot.num_{}", i), format!("Dot value {}", i)),
+        set(format!("seed-dash-num_{}", i), format!("Dash value {}", i)),
+    ]
+}
+
+fn large_hash(i: usize) -> Vec<Cmd> {
+    let mut cmd = redis::cmd("HSET");
+    cmd.arg(format!("seed:large_hash:{}", i));
+    for j in 0..200 {
+        cmd.arg(format!("field_{}", j)).arg(format!("value_for_hash_{}_field_{}", i, j));
+    }
+    vec![cmd]
+}
+
+fn large_list(i: usize) -> Vec<Cmd> {
+    let mut cmd = redis::cmd("RPUSH");
+    cmd.arg(format!("seed:large_list:{}", i));
+    for j in 0..500 {
+        cmd.arg(format!("list_{}_item_{}", i, j));
+    }
+    vec![cmd]
+}
+
+fn large_set(i: usize) -> Vec<Cmd> {
+    let mut cmd = redis::cmd("SADD");
+    cmd.arg(format!("seed:large_set:{}", i));
+    for j in 0..300 {
+        cmd.arg(format!("set_{}_member_{}", i, j));
+    }
+    vec![cmd]
+}
+
+fn large_zset(i: usize) -> Vec<Cmd> {
+    let mut cmd = redis::cmd("ZADD");
+    cmd.arg(format!("seed:large_zset:{}", i));
+    for j in 0..400 {
+        cmd.arg(j * 10).arg(format!("zset_{}_member_{}", i, j));
+    }
+    vec![cmd]
+}
+
+fn large_stream(i: usize) -> Vec<Cmd> {
+    (0..1000)
+        .map(|j| {
+            redis::cmd("XADD")
+                .arg(format!("seed:large_stream:{}", i))
+                .arg("*")
+                .arg("event_id")
+                .arg(format!("{}-{}", i, j))
+                .arg("sensor_id")
+                .arg(format!("sensor_{}", i % 5))
+                .arg("timestamp")
+                .arg(j * 1000)
+                .arg("payload")
+                .arg(format!("Some data payload for event {}-{}, could be JSON or any string.", i, j))
+                .clone()
+        })
+        .collect()
+}
+
+fn sample_strings() -> Vec<Cmd> {
+    vec![
+        redis::cmd("SET").arg("seed:string").arg("Hello from LazyRedis Seeder!").clone(),
+        redis::cmd("SET")
+            .arg("seed:another_string")
+            .arg("This string is a bit longer and might require scrolling to see fully in the value panel if it is narrow enough.")
+            .clone(),
+    ]
+}
+
+fn sample_hashes() -> Vec<Cmd> {
+    vec![
+        redis::cmd("HSET")
+            .arg("seed:hash")
+            .arg(&["field1", "Value1", "field2", "Another Value"])
+            .arg("long_field_name_for_testing_wrapping")
+            .arg("This value is also quite long to test how wrapping behaves in the TUI for hash values.")
+            .clone(),
+        redis::cmd("HSET").arg("seed:empty_hash").arg("placeholder_field").arg("placeholder_value").clone(),
+        redis::cmd("HDEL").arg("seed:empty_hash").arg("placeholder_field").clone(),
+    ]
+}
+
+fn sample_lists() -> Vec<Cmd> {
+    vec![
+        redis::cmd("RPUSH")
+            .arg("seed:list")
+            .arg(&["Item 1", "Item 2", "Item 3", "Yet another item", "And one more for good measure"])
+            .clone(),
+        redis::cmd("RPUSH").arg("seed:empty_list").arg("placeholder").clone(),
+        redis::cmd("LPOP").arg("seed:empty_list").clone(),
+    ]
+}
+
+fn sample_sets() -> Vec<Cmd> {
+    vec![
+        redis::cmd("SADD")
+            .arg("seed:set")
+            .arg(&["MemberA", "MemberB", "MemberC", "MemberD", "MemberE", "MemberA"])
+            .clone(),
+        redis::cmd("SADD").arg("seed:empty_set").arg("placeholder").clone(),
+        redis::cmd("SREM").arg("seed:empty_set").arg("placeholder").clone(),
+    ]
+}
+
+fn sample_zsets() -> Vec<Cmd> {
+    let mut zset = redis::cmd("ZADD");
+    zset.arg("seed:zset");
+    for (score, member) in [
+        (10, "Ten"),
+        (1, "One"),
+        (30, "Thirty"),
+        (20, "Twenty"),
+        (5, "Five"),
+        (100, "One Hundred"),
+        (15, "Fifteen"),
+    ] {
+        zset.arg(score).arg(member);
+    }
+    vec![
+        zset,
+        redis::cmd("ZADD").arg("seed:empty_zset").arg(1).arg("placeholder").clone(),
+        redis::cmd("ZREM").arg("seed:empty_zset").arg("placeholder").clone(),
+    ]
+}
+
+fn sample_streams() -> Vec<Cmd> {
+    [
+        &[("fieldA", "valueA1"), ("fieldB", "valueB1")][..],
+        &[("sensor-id", "1234"), ("temperature", "19.8")],
+        &[("message", "Hello World"), ("user", "Alice"), ("timestamp", "1678886400000")],
+    ]
+    .iter()
+    .map(|fields| {
+        let mut cmd = redis::cmd("XADD");
+        cmd.arg("seed:stream").arg("*");
+        for (field, value) in fields.iter() {
+            cmd.arg(*field).arg(*value);
+        }
+        cmd
+    })
+    .collect()
+}
+
+fn json_document(i: usize) -> Vec<Cmd> {
+    let document = serde_json::json!({
+        "id": i,
+        "name": format!("Product {}", i),
+        "price": (i % 100) as f64 + 0.99,
+        "in_stock": !i.is_multiple_of(3),
+        "tags": ["seed", format!("category_{}", i % 5)],
+        "dimensions": { "width": 10 + i % 7, "height": 20 + i % 11, "unit": "cm" },
+    });
+    vec![redis::cmd("JSON.SET")
+        .arg(format!("seed:json:{}", i))
+        .arg("$")
+        .arg(document.to_string())
+        .clone()]
+}
+
+fn time_series(i: usize) -> Vec<Cmd> {
+    let key = format!("seed:timeseries:{}", i);
+    let mut create = redis::cmd("TS.CREATE");
+    create.arg(&key).arg("LABELS").arg("sensor").arg(format!("sensor_{}", i)).arg("kind").arg("temperature");
+    let mut samples = redis::cmd("TS.MADD");
+    for j in 0..TIME_SERIES_SAMPLES {
+        let celsius = 18.0 + ((i + j) % 60) as f64 / 10.0;
+        samples.arg(&key).arg(TIME_SERIES_START_MS + j * 60_000).arg(celsius);
+    }
+    vec![create, samples]
+}
+
+fn bloom_filter(i: usize) -> Vec<Cmd> {
+    let key = format!("seed:bloom:{}", i);
+    let mut add = redis::cmd("BF.MADD");
+    add.arg(&key);
+    for j in 0..BLOOM_ITEMS {
+        add.arg(format!("user_{}_{}@example.com", i, j));
+    }
+    vec![redis::cmd("BF.RESERVE").arg(&key).arg(0.01).arg(BLOOM_ITEMS * 2).clone(), add]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_scale_the_bulk_sections() {
+        let count = |size, label: &str| {
+            sections(size)
+                .into_iter()
+                .find(|section| section.label == label)
+                .map(|section| section.count)
+        };
+        assert_eq!(count(SeedSize::Small, "nested keys"), Some(500));
+        assert_eq!(count(SeedSize::Medium, "nested keys"), Some(10_000));
+        assert_eq!(count(SeedSize::Large, "nested keys"), Some(1_000_000));
+        assert_eq!(count(SeedSize::Small, "large streams"), Some(1));
+        assert_eq!(count(SeedSize::Large, "sample zsets"), Some(1));
+
+        let last = String::from_utf8(nested_key(9_999)[0].get_packed_command()).unwrap();
+        assert!(last.contains("seed:level1:49:level2:19:key:9"), "{}", last);
+    }
+}
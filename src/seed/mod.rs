@@ -0,0 +1,177 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use futures_util::stream::{self, StreamExt};
+use redis::{aio::MultiplexedConnection, Client, Cmd};
+use std::io::Write;
+
+mod dataset;
+mod modules;
+mod spec;
+
+pub use spec::SeedSpec;
+
+/// Commands queued in one pipeline before it is sent.
+const PIPELINE_COMMANDS: usize = 1000;
+
+/// Pipelines in flight at once on the connection.
+const SEED_CONCURRENCY: usize = 8;
+
+/// How much of the built-in dataset `--seed-size` writes.
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SeedSize {
+    /// About 600 keys, for CI fixtures
+    Small,
+    /// About 11,500 keys
+    #[default]
+    Medium,
+    /// About 1.1 million keys, for performance testing
+    Large,
+}
+
+impl SeedSize {
+    /// Number of plain keys for a section with `count` of them at the medium size.
+    fn keys(self, count: usize) -> usize {
+        match self {
+            SeedSize::Small => (count / 20).max(1),
+            SeedSize::Medium => count,
+            SeedSize::Large => count * 100,
+        }
+    }
+
+    /// Number of large collections for a section with `count` of them at the medium size;
+    /// they grow slower than plain keys to keep the large size within a laptop's memory.
+    fn collections(self, count: usize) -> usize {
+        match self {
+            SeedSize::Small => (count / 10).max(1),
+            SeedSize::Medium => count,
+            SeedSize::Large => count * 10,
+        }
+    }
+}
+
+/// Kinds of data `--seed-only` can limit seeding to.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedGroup {
+    Strings,
+    Hashes,
+    Lists,
+    Sets,
+    Zsets,
+    Streams,
+    /// RedisJSON documents
+    Json,
+    /// RedisTimeSeries series
+    Timeseries,
+    /// RedisBloom filters
+    Bloom,
+}
+
+impl SeedGroup {
+    /// Name `MODULE LIST` reports for the module keys of this group need.
+    fn module(self) -> Option<&'static str> {
+        match self {
+            SeedGroup::Json => Some("ReJSON"),
+            SeedGroup::Timeseries => Some("timeseries"),
+            SeedGroup::Bloom => Some("bf"),
+            _ => None,
+        }
+    }
+}
+
+/// Size and groups chosen on the command line.
+#[derive(Debug, Default, Clone)]
+pub struct SeedOptions {
+    pub size: SeedSize,
+    /// Groups to seed; empty seeds all of them.
+    pub only: Vec<SeedGroup>,
+}
+
+impl SeedOptions {
+    pub fn includes(&self, group: SeedGroup) -> bool {
+        self.only.is_empty() || self.only.contains(&group)
+    }
+}
+
+/// Flushes the DB, then seeds it from `spec` or, without one, the built-in test dataset,
+/// limited to the groups in `options`.
+pub async fn seed_redis_data(
+    redis_url: &str,
+    db_index: u8,
+    spec: Option<&SeedSpec>,
+    options: &SeedOptions,
+) -> Result<()> {
+    println!("Connecting to {} (DB {}) to seed data...", redis_url, db_index);
+    let client = Client::open(redis_url)?;
+    let mut con: MultiplexedConnection = client.get_multiplexed_async_connection().await?;
+
+    redis::cmd("SELECT").arg(db_index).query_async::<()>(&mut con).await?;
+    println!("Selected database {}.", db_index);
+
+    println!("Flushing database {}...", db_index);
+    redis::cmd("FLUSHDB").query_async::<()>(&mut con).await?;
+    println!("Database {} flushed.", db_index);
+
+    if let Some(spec) = spec {
+        for entry in spec.keys.iter().filter(|entry| options.includes(entry.key_type.group())) {
+            write_keys(&con, &entry.pattern, entry.count, |i| entry.commands(i)).await?;
+        }
+        println!("Finished seeding {} key(s) from the spec.", spec.total_keys(options));
+        return Ok(());
+    }
+
+    let loaded_modules = modules::loaded_modules(&mut con).await;
+    println!("Seeding the {:?} dataset...", options.size);
+    for section in dataset::sections(options.size) {
+        if !options.includes(section.group) {
+            continue;
+        }
+        if let Some(module) = section.group.module().filter(|module| !modules::contains(&loaded_modules, module)) {
+            println!("Skipping {}: the {} module is not loaded.", section.label, module);
+            continue;
+        }
+        write_keys(&con, section.label, section.count, section.commands).await?;
+    }
+    println!("Finished seeding data.");
+    Ok(())
+}
+
+/// Writes items `0..count`, each with the commands `commands` returns for it. The
+/// commands are pipelined with several pipelines in flight, and progress is printed as
+/// they complete. An item's commands always share a pipeline, so they run in order.
+async fn write_keys<F>(con: &MultiplexedConnection, label: &str, count: usize, commands: F) -> Result<()>
+where
+    F: Fn(usize) -> Vec<Cmd>,
+{
+    let mut next = 0;
+    let pipelines = std::iter::from_fn(|| {
+        if next >= count {
+            return None;
+        }
+        let start = next;
+        let mut pipe = redis::pipe();
+        let mut queued = 0;
+        while next < count && queued < PIPELINE_COMMANDS {
+            for cmd in commands(next) {
+                pipe.add_command(cmd).ignore();
+                queued += 1;
+            }
+            next += 1;
+        }
+        Some((pipe, next - start))
+    });
+    let mut writes = stream::iter(pipelines)
+        .map(|(pipe, items)| {
+            let mut con = con.clone();
+            async move { pipe.query_async::<()>(&mut con).await.map(|_| items) }
+        })
+        .buffer_unordered(SEED_CONCURRENCY);
+
+    let mut done = 0;
+    while let Some(written) = writes.next().await {
+        done += written?;
+        print!("\r{}: {}/{}", label, done, count);
+        std::io::stdout().flush()?;
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use redis::aio::MultiplexedConnection;
+use redis::Value;
+
+use crate::command::resp::{map_field, pairs, value_text};
+
+/// Names of the modules the server has loaded; empty when `MODULE LIST` is unavailable.
+pub(super) async fn loaded_modules(con: &mut MultiplexedConnection) -> Vec<String> {
+    match redis::cmd("MODULE").arg("LIST").query_async::<Value>(con).await {
+        Ok(reply) => module_names(&reply),
+        Err(e) => {
+            tracing::warn!("MODULE LIST failed, seeding without module types: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Whether `module` is among `loaded`. Module names are matched case-insensitively.
+pub(super) fn contains(loaded: &[String], module: &str) -> bool {
+    loaded.iter().any(|name| name.eq_ignore_ascii_case(module))
+}
+
+/// The `name` of each module in a `MODULE LIST` reply.
+fn module_names(reply: &Value) -> Vec<String> {
+    let Value::Array(modules) = reply else {
+        return Vec::new();
+    };
+    modules
+        .iter()
+        .filter_map(|module| map_field(&pairs(module), "name").and_then(value_text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(text: &str) -> Value {
+        Value::BulkString(text.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn module_names_are_read_from_resp2_and_resp3_replies() {
+        let reply = Value::Array(vec![
+            Value::Array(vec![bulk("name"), bulk("ReJSON"), bulk("ver"), Value::Int(20_000)]),
+            Value::Map(vec![(Value::SimpleString("name".to_string()), bulk("bf"))]),
+        ]);
+        let names = module_names(&reply);
+        assert_eq!(names, ["ReJSON", "bf"]);
+        assert!(contains(&names, "rejson"));
+        assert!(!contains(&names, "timeseries"));
+        assert!(module_names(&Value::Nil).is_empty());
+    }
+}
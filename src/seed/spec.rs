@@ -0,0 +1,228 @@
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{SeedGroup, SeedOptions};
+
+/// Domain-shaped test data read from `--seed-file`: each entry creates `count` keys of
+/// one type from templates, where `{i}` is the key number and `{j}` the element number.
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct SeedSpec {
+    pub keys: Vec<KeySpec>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SeedType {
+    #[default]
+    String,
+    Hash,
+    List,
+    Set,
+    Zset,
+    Stream,
+}
+
+impl SeedType {
+    /// The `--seed-only` group entries of this type belong to.
+    pub fn group(self) -> SeedGroup {
+        match self {
+            SeedType::String => SeedGroup::Strings,
+            SeedType::Hash => SeedGroup::Hashes,
+            SeedType::List => SeedGroup::Lists,
+            SeedType::Set => SeedGroup::Sets,
+            SeedType::Zset => SeedGroup::Zsets,
+            SeedType::Stream => SeedGroup::Streams,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct KeySpec {
+    /// Key name template, e.g. `user:{i}`.
+    pub pattern: String,
+    #[serde(rename = "type", default)]
+    pub key_type: SeedType,
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// String value template.
+    pub value: Option<String>,
+    /// Hash fields, or the fields of each stream entry, as name -> value template.
+    #[serde(default)]
+    pub fields: BTreeMap<String, String>,
+    /// Elements per list, set, sorted set or stream.
+    #[serde(default = "default_items")]
+    pub items: usize,
+    /// List, set or sorted set element template.
+    pub item: Option<String>,
+    /// Sorted set score template; defaults to the element number.
+    pub score: Option<String>,
+    /// Expiry in seconds.
+    pub ttl: Option<u64>,
+}
+
+fn default_count() -> usize {
+    1
+}
+
+fn default_items() -> usize {
+    10
+}
+
+impl SeedSpec {
+    /// Reads a `.json` spec, or TOML for any other extension.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read '{}': {}", path.display(), e))?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let spec: SeedSpec = if is_json {
+            serde_json::from_str(&contents).map_err(|e| anyhow!("Invalid seed spec '{}': {}", path.display(), e))?
+        } else {
+            toml::from_str(&contents).map_err(|e| anyhow!("Invalid seed spec '{}': {}", path.display(), e))?
+        };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<()> {
+        for entry in &self.keys {
+            if entry.count > 1 && !entry.pattern.contains("{i}") {
+                return Err(anyhow!("Pattern '{}' needs {{i}} to create {} distinct keys", entry.pattern, entry.count));
+            }
+            if matches!(entry.key_type, SeedType::Hash | SeedType::Stream) && entry.fields.is_empty() {
+                return Err(anyhow!("Pattern '{}' needs at least one entry in fields", entry.pattern));
+            }
+            if let Some(score) = &entry.score {
+                render(score, 0, 0)
+                    .parse::<f64>()
+                    .map_err(|_| anyhow!("Score '{}' of '{}' is not a number", score, entry.pattern))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keys the entries selected by `options` create.
+    pub fn total_keys(&self, options: &SeedOptions) -> usize {
+        self.keys
+            .iter()
+            .filter(|entry| options.includes(entry.key_type.group()))
+            .map(|entry| entry.count)
+            .sum()
+    }
+}
+
+impl KeySpec {
+    /// Commands creating key number `i`, expiry included.
+    pub(super) fn commands(&self, i: usize) -> Vec<redis::Cmd> {
+        let key = render(&self.pattern, i, 0);
+        let item = |j| render(self.item.as_deref().unwrap_or("item {j}"), i, j);
+        let mut commands = Vec::new();
+        match self.key_type {
+            SeedType::String => {
+                let value = render(self.value.as_deref().unwrap_or("value {i}"), i, 0);
+                commands.push(redis::cmd("SET").arg(&key).arg(value).clone());
+            }
+            SeedType::Hash => {
+                let mut cmd = redis::cmd("HSET");
+                cmd.arg(&key);
+                for (field, value) in &self.fields {
+                    cmd.arg(render(field, i, 0)).arg(render(value, i, 0));
+                }
+                commands.push(cmd);
+            }
+            SeedType::List | SeedType::Set => {
+                let name = if self.key_type == SeedType::List { "RPUSH" } else { "SADD" };
+                let mut cmd = redis::cmd(name);
+                cmd.arg(&key);
+                (0..self.items).for_each(|j| {
+                    cmd.arg(item(j));
+                });
+                commands.push(cmd);
+            }
+            SeedType::Zset => {
+                let mut cmd = redis::cmd("ZADD");
+                cmd.arg(&key);
+                for j in 0..self.items {
+                    let score = self.score.as_deref().map_or(j.to_string(), |score| render(score, i, j));
+                    cmd.arg(score).arg(item(j));
+                }
+                commands.push(cmd);
+            }
+            SeedType::Stream => {
+                for j in 0..self.items {
+                    let mut cmd = redis::cmd("XADD");
+                    cmd.arg(&key).arg("*");
+                    for (field, value) in &self.fields {
+                        cmd.arg(render(field, i, j)).arg(render(value, i, j));
+                    }
+                    commands.push(cmd);
+                }
+            }
+        }
+        if let Some(ttl) = self.ttl {
+            commands.push(redis::cmd("EXPIRE").arg(&key).arg(ttl).clone());
+        }
+        commands
+    }
+}
+
+/// Fills `{i}` (key number) and `{j}` (element number) into `template`.
+fn render(template: &str, i: usize, j: usize) -> String {
+    template.replace("{i}", &i.to_string()).replace("{j}", &j.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(toml: &str) -> Result<SeedSpec> {
+        let spec: SeedSpec = toml::from_str(toml)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    #[test]
+    fn spec_entries_expand_into_commands() {
+        let spec = spec(
+            r#"
+            [[keys]]
+            pattern = "user:{i}"
+            type = "hash"
+            count = 3
+            ttl = 60
+            fields = { name = "User {i}", email = "user{i}@example.com" }
+
+            [[keys]]
+            pattern = "leaderboard"
+            type = "zset"
+            items = 2
+            item = "player:{j}"
+            score = "1{j}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(spec.total_keys(&SeedOptions::default()), 4);
+        let zsets = SeedOptions { only: vec![SeedGroup::Zsets], ..Default::default() };
+        assert_eq!(spec.total_keys(&zsets), 1);
+
+        let packed = |cmd: &redis::Cmd| String::from_utf8_lossy(&cmd.get_packed_command()).replace("\r\n", " ");
+        let user = spec.keys[0].commands(2);
+        assert_eq!(user.len(), 2);
+        let hset = packed(&user[0]);
+        assert!(hset.contains("HSET $6 user:2 $5 email $17 user2@example.com $4 name $6 User 2"), "{}", hset);
+        assert!(packed(&user[1]).contains("EXPIRE $6 user:2 $2 60"));
+        let zadd = packed(&spec.keys[1].commands(0)[0]);
+        assert!(zadd.contains("$2 10 $8 player:0 $2 11 $8 player:1"), "{}", zadd);
+    }
+
+    #[test]
+    fn invalid_specs_are_rejected_before_seeding() {
+        assert!(spec("[[keys]]\npattern = \"same\"\ncount = 2").is_err());
+        assert!(spec("[[keys]]\npattern = \"h\"\ntype = \"hash\"").is_err());
+        assert!(spec("[[keys]]\npattern = \"z\"\ntype = \"zset\"\nscore = \"x{j}\"").is_err());
+        assert!(spec("[[keys]]\npattern = \"k\"\ntpye = \"set\"").is_err());
+        assert_eq!(spec("[[keys]]\npattern = \"k\"").unwrap().keys[0].key_type, SeedType::String);
+    }
+}
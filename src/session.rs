@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::{fs, path::{Path, PathBuf}};
+
+/// Where the browser was left on exit, persisted in `lazyredis/state.toml` next to the
+/// config file and restored on the next launch unless `restore_session` is turned off.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct SessionState {
+    /// Name of the connection profile that was active.
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub db: usize,
+    /// Folder segments of the key tree level being viewed.
+    #[serde(default)]
+    pub breadcrumb: Vec<String>,
+    /// Full name of the selected key, when a key (not a folder) was selected.
+    pub selected_key: Option<String>,
+}
+
+impl SessionState {
+    fn determine_file_path(base_path_override: Option<&Path>) -> Option<PathBuf> {
+        match base_path_override {
+            Some(base_path) => Some(base_path.to_path_buf()),
+            None => directories::BaseDirs::new().map(|base_dirs| base_dirs.config_dir().to_path_buf()),
+        }
+        .map(|base| base.join("lazyredis").join("state.toml"))
+    }
+
+    /// Reads the state file; a missing or unreadable file yields an empty state.
+    pub fn load(base_path_override: Option<&Path>) -> Self {
+        Self::determine_file_path(base_path_override)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, base_path_override: Option<&Path>) -> Result<(), String> {
+        let Some(path) = Self::determine_file_path(base_path_override) else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let contents = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, contents).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn session_state_round_trips() {
+        let dir = tempdir().unwrap();
+        assert_eq!(SessionState::load(Some(dir.path())).profile, None);
+
+        let state = SessionState {
+            profile: Some("Dev".to_string()),
+            db: 3,
+            breadcrumb: vec!["user".to_string(), "42".to_string()],
+            selected_key: Some("user:42:name".to_string()),
+        };
+        state.save(Some(dir.path())).unwrap();
+
+        let loaded = SessionState::load(Some(dir.path()));
+        assert_eq!(loaded, state);
+    }
+}
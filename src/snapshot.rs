@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::app::key_sampler::KeySample;
+
+/// What a snapshot remembers about one key.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    pub key_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+}
+
+impl From<&KeySample> for SnapshotEntry {
+    fn from(sample: &KeySample) -> Self {
+        Self {
+            key_type: sample.key_type.clone(),
+            memory: sample.memory,
+            length: sample.length,
+        }
+    }
+}
+
+/// Key names, types and sizes captured from one DB, stored as
+/// `lazyredis/snapshots/<name>.json` next to the config file.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct KeyspaceSnapshot {
+    pub name: String,
+    pub profile: String,
+    pub db: usize,
+    pub pattern: String,
+    /// Unix milliseconds.
+    pub taken_at: i64,
+    pub keys: BTreeMap<String, SnapshotEntry>,
+}
+
+/// A saved snapshot as listed in the snapshots panel, without its keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotSummary {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+pub fn snapshot_dir(base_path_override: Option<&Path>) -> Option<PathBuf> {
+    match base_path_override {
+        Some(base_path) => Some(base_path.to_path_buf()),
+        None => directories::BaseDirs::new().map(|base_dirs| base_dirs.config_dir().to_path_buf()),
+    }
+    .map(|base| base.join("lazyredis").join("snapshots"))
+}
+
+impl KeyspaceSnapshot {
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, String> {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        let path = dir.join(format!("{}.json", file_stem(&self.name)));
+        let contents = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(&path, contents).map_err(|e| e.to_string())?;
+        Ok(path)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Saved snapshots by name; a missing directory yields none.
+pub fn list_snapshots(dir: &Path) -> Vec<SnapshotSummary> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<SnapshotSummary> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            Some(SnapshotSummary { name, path })
+        })
+        .collect();
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    snapshots
+}
+
+/// Names are used as file names, so path separators and the like become `_`.
+fn file_stem(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '_' })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl DiffKind {
+    pub fn marker(&self) -> char {
+        match self {
+            DiffKind::Added => '+',
+            DiffKind::Removed => '-',
+            DiffKind::Changed => '~',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRow {
+    pub kind: DiffKind,
+    pub key: String,
+    pub before: Option<SnapshotEntry>,
+    pub after: Option<SnapshotEntry>,
+}
+
+/// Keys added, removed, or whose type or size changed between `before` and `after`, by key name.
+pub fn diff(before: &BTreeMap<String, SnapshotEntry>, after: &BTreeMap<String, SnapshotEntry>) -> Vec<DiffRow> {
+    let mut rows: Vec<DiffRow> = before
+        .iter()
+        .filter_map(|(key, old)| match after.get(key) {
+            None => Some(DiffRow {
+                kind: DiffKind::Removed,
+                key: key.clone(),
+                before: Some(old.clone()),
+                after: None,
+            }),
+            Some(new) if new != old => Some(DiffRow {
+                kind: DiffKind::Changed,
+                key: key.clone(),
+                before: Some(old.clone()),
+                after: Some(new.clone()),
+            }),
+            Some(_) => None,
+        })
+        .collect();
+    rows.extend(after.iter().filter(|(key, _)| !before.contains_key(*key)).map(|(key, new)| DiffRow {
+        kind: DiffKind::Added,
+        key: key.clone(),
+        before: None,
+        after: Some(new.clone()),
+    }));
+    rows.sort_by(|a, b| a.key.cmp(&b.key));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(key_type: &str, length: u64) -> SnapshotEntry {
+        SnapshotEntry {
+            key_type: key_type.to_string(),
+            memory: Some(length * 8),
+            length: Some(length),
+        }
+    }
+
+    #[test]
+    fn snapshots_round_trip_and_diff() {
+        let dir = tempdir().unwrap();
+        let before = KeyspaceSnapshot {
+            name: "before/migration".to_string(),
+            profile: "Dev".to_string(),
+            db: 0,
+            pattern: "*".to_string(),
+            taken_at: 1,
+            keys: BTreeMap::from([
+                ("kept".to_string(), entry("string", 3)),
+                ("gone".to_string(), entry("hash", 2)),
+                ("grown".to_string(), entry("list", 1)),
+            ]),
+        };
+        let path = before.save(dir.path()).unwrap();
+        assert_eq!(path.file_name().unwrap(), "before_migration.json");
+        assert_eq!(KeyspaceSnapshot::load(&path).unwrap(), before);
+        assert_eq!(list_snapshots(dir.path())[0].name, "before_migration");
+
+        let after = BTreeMap::from([
+            ("kept".to_string(), entry("string", 3)),
+            ("grown".to_string(), entry("list", 5)),
+            ("new".to_string(), entry("set", 1)),
+        ]);
+        let rows = diff(&before.keys, &after);
+        let summary: Vec<(DiffKind, &str)> = rows.iter().map(|row| (row.kind, row.key.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![(DiffKind::Removed, "gone"), (DiffKind::Changed, "grown"), (DiffKind::Added, "new")]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_action_menu::ActionMenuState;
+use crate::keymap::Keymap;
+
+pub(super) fn draw_action_menu_modal(f: &mut Frame, state: &ActionMenuState, keymap: &Keymap) {
+    let area = centered_rect(50, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Actions: {} ({}) (j/k: select, Enter: run, Esc: close)", state.key, state.key_type))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(inner);
+
+    let width = state
+        .actions
+        .iter()
+        .map(|action| action.label(&state.key_type).len())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = state
+        .actions
+        .iter()
+        .map(|action| {
+            let shortcut = action
+                .shortcut()
+                .and_then(|shortcut| keymap.binding_label(shortcut))
+                .unwrap_or_default();
+            ListItem::new(format!("{:<width$}  {}", action.label(&state.key_type), shortcut, width = width))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected));
+    f.render_stateful_widget(list, rows[0], &mut list_state);
+
+    let (title, border) = match state.prompt.and_then(|action| action.prompt(&state.key_type)) {
+        Some(hint) => (hint, Color::Yellow),
+        None => ("Input", Color::DarkGray),
+    };
+    let input = Paragraph::new(state.input.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(input, rows[1]);
+    if state.prompt.is_some() {
+        let col = state.input[..state.cursor.min(state.input.len())].chars().count() as u16;
+        let x = (rows[1].x + 1 + col).min(rows[1].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[1].y + 1));
+    }
+}
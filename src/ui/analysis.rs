@@ -0,0 +1,239 @@
+use std::time::Instant;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs, Wrap},
+    Frame,
+};
+
+use super::{centered_rect, format_count};
+use crate::app::key_sampler::KeySample;
+use crate::app::redis_stats::format_bytes;
+use crate::app::state_analysis::{
+    AnalysisState, AnalysisView, BigKeys, HotKeySource, HotKeys, PrefixMemory, TtlBucket, TtlDistribution,
+    HOT_KEY_MONITOR_WINDOW,
+};
+
+pub(super) fn draw_analysis_modal(f: &mut Frame, state: &AnalysisState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let (progress, escape) = if state.sampler.is_some() {
+        (format!("scanning... {} keys", format_count(state.scanned)), "stop")
+    } else if state.stopped {
+        (format!("stopped after {} keys", format_count(state.scanned)), "close")
+    } else {
+        (format!("{} keys scanned", format_count(state.scanned)), "close")
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Keyspace analysis [{}] (Tab: switch view, r: rescan, Esc: {})", progress, escape))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let selected_tab = AnalysisView::ALL.iter().position(|view| *view == state.view).unwrap_or(0);
+    let tabs = Tabs::new(AnalysisView::ALL.iter().map(|view| view.label()))
+        .select(selected_tab)
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    f.render_widget(tabs, rows[0]);
+
+    match state.view {
+        AnalysisView::BigKeys => draw_big_keys(f, rows[1], &state.big_keys),
+        AnalysisView::MemoryByPrefix => draw_prefix_memory(f, rows[1], &state.prefix_memory, state.prefix_depth),
+        AnalysisView::TtlDistribution => draw_ttl_distribution(f, rows[1], &state.ttl_distribution, state.prefix_depth),
+        AnalysisView::HotKeys => draw_hot_keys(f, rows[1], &state.hot_keys, state.sampler.is_some()),
+    }
+
+    if let Some(error) = &state.error {
+        f.render_widget(Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)), rows[2]);
+    }
+}
+
+pub(super) fn highlighted_list<'a>(items: Vec<ListItem<'a>>, title: String) -> List<'a> {
+    List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+}
+
+/// `type  key  memory  length` columns of a sampled key, shared with the random key sampler.
+pub(super) fn key_sample_spans(sample: &KeySample, key_width: usize) -> Vec<Span<'static>> {
+    let memory = sample.memory.map_or("?".to_string(), format_bytes);
+    let length = match (sample.key_type.as_str(), sample.length) {
+        (_, None) => String::new(),
+        ("string", Some(bytes)) => format!("{} bytes", format_count(bytes as usize)),
+        (_, Some(items)) => format!("{} items", format_count(items as usize)),
+    };
+    vec![
+        Span::styled(format!("{:<7}", sample.key_type), Style::default().fg(Color::Magenta)),
+        Span::raw(format!("{:<width$}  ", sample.key, width = key_width)),
+        Span::styled(format!("{:>10}", memory), Style::default().fg(Color::Yellow)),
+        Span::styled(format!("  {}", length), Style::default().fg(Color::DarkGray)),
+    ]
+}
+
+pub(super) fn key_width<'a>(keys: impl Iterator<Item = &'a str>) -> usize {
+    keys.map(|key| key.chars().count()).max().unwrap_or(0).min(60)
+}
+
+fn draw_big_keys(f: &mut Frame, area: Rect, big_keys: &BigKeys) {
+    let samples = big_keys.rows();
+    let width = key_width(samples.iter().map(|sample| sample.key.as_str()));
+    let items: Vec<ListItem> = samples
+        .iter()
+        .map(|sample| ListItem::new(Line::from(key_sample_spans(sample, width))))
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(big_keys.selected));
+    }
+    let list = highlighted_list(items, "Type / key / MEMORY USAGE / length (Enter: go to key)".to_string());
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn draw_prefix_memory(f: &mut Frame, area: Rect, memory: &PrefixMemory, depth: usize) {
+    let ranked = memory.ranked(depth);
+    let total = memory.total_bytes().max(1);
+    let name_width = ranked.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).min(60);
+    let items: Vec<ListItem> = ranked
+        .iter()
+        .map(|(name, usage)| {
+            let share = usage.bytes as f64 * 100.0 / total as f64;
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{:<width$}  ", name, width = name_width)),
+                Span::styled(format!("{:>10}", format_bytes(usage.bytes)), Style::default().fg(Color::Yellow)),
+                Span::styled(format!("  {:>5.1}%", share), Style::default().fg(Color::Green)),
+                Span::styled(
+                    format!("  {} keys", format_count(usage.keys as usize)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(memory.selected));
+    }
+    let title = format!(
+        "Prefix / MEMORY USAGE / share / keys (depth {}, +/-: change depth, x: export CSV)",
+        depth
+    );
+    let list = highlighted_list(items, title);
+    f.render_stateful_widget(list, area, &mut list_state);
+}
+
+fn draw_ttl_distribution(f: &mut Frame, area: Rect, distribution: &TtlDistribution, depth: usize) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(8), Constraint::Min(3)])
+        .split(area);
+
+    let bars: Vec<Bar> = TtlBucket::ALL
+        .iter()
+        .map(|bucket| {
+            let color = if *bucket == TtlBucket::NoExpiry { Color::Red } else { Color::Cyan };
+            Bar::default()
+                .label(Line::from(bucket.label()))
+                .value(distribution.overall.count(*bucket))
+                .style(Style::default().fg(color))
+        })
+        .collect();
+    let chart = BarChart::default()
+        .block(Block::default().borders(Borders::ALL).title("All sampled keys"))
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(9)
+        .bar_gap(2);
+    f.render_widget(chart, rows[0]);
+
+    let ranked = distribution.ranked(depth);
+    let name_width = ranked.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).clamp(6, 60);
+    let items: Vec<ListItem> = ranked
+        .iter()
+        .map(|(name, histogram)| {
+            let mut spans = vec![Span::raw(format!("{:<width$}", name, width = name_width))];
+            for bucket in TtlBucket::ALL {
+                let count = histogram.count(bucket);
+                let style = match bucket {
+                    TtlBucket::NoExpiry if count > 0 => Style::default().fg(Color::Red),
+                    _ if count == 0 => Style::default().fg(Color::DarkGray),
+                    _ => Style::default(),
+                };
+                spans.push(Span::styled(format!("{:>11}", format_count(count as usize)), style));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(distribution.selected));
+    }
+    let header: String = TtlBucket::ALL.iter().map(|bucket| format!("{:>11}", bucket.label())).collect();
+    let title = format!(
+        "{:<width$}{} (depth {}, +/-: change depth)",
+        "Prefix",
+        header,
+        depth,
+        width = name_width
+    );
+    let list = highlighted_list(items, title);
+    f.render_stateful_widget(list, rows[1], &mut list_state);
+}
+
+fn draw_hot_keys(f: &mut Frame, area: Rect, hot_keys: &HotKeys, scanning: bool) {
+    let title = match hot_keys.source() {
+        Some(HotKeySource::Lfu) => "Key / OBJECT FREQ (Enter: go to key)".to_string(),
+        Some(HotKeySource::Monitor) => match hot_keys.monitor_until {
+            Some(until) => format!(
+                "Key / accesses (sampling MONITOR, {}s left)",
+                until.saturating_duration_since(Instant::now()).as_secs() + 1
+            ),
+            None => "Key / accesses in the MONITOR sample (Enter: go to key, s: sample again)".to_string(),
+        },
+        None => "Hot keys".to_string(),
+    };
+
+    let rows = hot_keys.rows();
+    if rows.is_empty() && !scanning && hot_keys.monitor.is_none() {
+        let hint = format!(
+            "No LFU counters were reported: OBJECT FREQ needs an LFU maxmemory-policy such as allkeys-lfu.\n\n\
+             Press s to count key accesses with MONITOR for {}s instead. MONITOR slows the server down.",
+            HOT_KEY_MONITOR_WINDOW.as_secs()
+        );
+        let paragraph = Paragraph::new(hint)
+            .wrap(Wrap { trim: true })
+            .block(Block::default().borders(Borders::ALL).title(title));
+        f.render_widget(paragraph, area);
+        return;
+    }
+
+    let key_width = rows.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0).min(60);
+    let items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .map(|(rank, (key, count))| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:>3}. ", rank + 1), Style::default().fg(Color::DarkGray)),
+                Span::raw(format!("{:<width$}  ", key, width = key_width)),
+                Span::styled(format!("{:>10}", format_count(*count as usize)), Style::default().fg(Color::Yellow)),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(hot_keys.selected));
+    }
+    let list = highlighted_list(items, title);
+    f.render_stateful_widget(list, area, &mut list_state);
+}
@@ -0,0 +1,42 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::App;
+
+pub(super) fn draw_bookmarks_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let bookmarks = app.current_bookmarks();
+    let items: Vec<ListItem> = if bookmarks.is_empty() {
+        vec![ListItem::new("No bookmarks yet - press 'm' on a key to add one")
+            .style(Style::default().fg(Color::DarkGray))]
+    } else {
+        bookmarks.iter().map(|key| ListItem::new(key.as_str())).collect()
+    };
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Bookmarks (Enter: open, d: remove, b/Esc: close)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    let mut list_state = ListState::default();
+    if !bookmarks.is_empty() {
+        list_state.select(Some(app.bookmarks_panel.selected_index));
+    }
+    f.render_stateful_widget(list, area, &mut list_state);
+}
@@ -0,0 +1,175 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::analysis::highlighted_list;
+use super::{centered_rect, format_count};
+use crate::app::keyspace_compare::CompareStatus;
+use crate::app::state_compare::{CompareDetail, CompareStage, CompareState};
+use crate::app::App;
+
+pub(super) fn draw_compare_modal(f: &mut Frame, app: &App) {
+    let state = &app.compare;
+    let area = centered_rect(90, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let title = match state.stage {
+        CompareStage::PickProfile => "Compare keyspaces: choose the other profile (Enter: next, Esc: close)".to_string(),
+        CompareStage::Pattern => "Compare keyspaces: SCAN pattern for both sides (Enter: compare, Esc: back)".to_string(),
+        CompareStage::Results => match &state.detail {
+            Some(detail) => format!("{} (Esc: back)", detail.key),
+            None => format!(
+                "{} vs {} [{}] (Enter: compare values, r: rerun, Esc: close)",
+                state.left_label, state.right_label, state.pattern
+            ),
+        },
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(inner);
+
+    match state.stage {
+        CompareStage::PickProfile | CompareStage::Pattern => draw_setup(f, app, rows[0], rows[1]),
+        CompareStage::Results => {
+            match &state.detail {
+                Some(detail) => draw_detail(f, state, detail, rows[0]),
+                None => draw_results(f, state, rows[0]),
+            }
+            draw_summary(f, state, rows[1]);
+        }
+    }
+}
+
+fn draw_setup(f: &mut Frame, app: &App, list_area: Rect, input_area: Rect) {
+    let state = &app.compare;
+    let items: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(idx, profile)| {
+            let mut spans = vec![Span::raw(format!("{}  ", profile.name))];
+            spans.push(Span::styled(profile.url.clone(), Style::default().fg(Color::DarkGray)));
+            if idx == app.current_profile_index {
+                spans.push(Span::styled("  (current)", Style::default().fg(Color::Yellow)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(state.profile_selected));
+    }
+    let title = format!("Compare {} DB {} with", app.current_profile_name(), app.selected_db_index);
+    f.render_stateful_widget(highlighted_list(items, title), list_area, &mut list_state);
+
+    if state.stage == CompareStage::Pattern {
+        let input = Paragraph::new(state.pattern.as_str()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("SCAN MATCH pattern")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(input, input_area);
+        let col = state.pattern[..state.pattern_cursor.min(state.pattern.len())].chars().count() as u16;
+        let x = (input_area.x + 1 + col).min(input_area.right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, input_area.y + 1));
+    } else if let Some(error) = &state.error {
+        f.render_widget(Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)), input_area);
+    }
+}
+
+fn draw_results(f: &mut Frame, state: &CompareState, area: Rect) {
+    let items: Vec<ListItem> = state
+        .rows
+        .iter()
+        .map(|row| {
+            let (marker, detail, color) = match &row.status {
+                CompareStatus::OnlyLeft => ("<", format!("only in {}", state.left_label), Color::Red),
+                CompareStatus::OnlyRight => (">", format!("only in {}", state.right_label), Color::Green),
+                CompareStatus::TypeMismatch { left, right } => ("T", format!("type {} vs {}", left, right), Color::Magenta),
+                CompareStatus::ValueMismatch => ("~", "values differ".to_string(), Color::Yellow),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::raw(format!("{}  ", row.key)),
+                Span::styled(detail, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    let title = format!("{} differing keys", format_count(state.rows.len()));
+    f.render_stateful_widget(highlighted_list(items, title), area, &mut list_state);
+}
+
+fn draw_detail(f: &mut Frame, state: &CompareState, detail: &CompareDetail, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let side = |lines: &[String], other: &[String]| -> Vec<Line<'static>> {
+        lines
+            .iter()
+            .enumerate()
+            .skip(detail.scroll)
+            .map(|(idx, line)| {
+                let style = if other.get(idx) == Some(line) {
+                    Style::default()
+                } else {
+                    Style::default().fg(Color::Yellow)
+                };
+                Line::styled(line.clone(), style)
+            })
+            .collect()
+    };
+    for (column, (label, lines, other)) in columns.iter().zip([
+        (&state.left_label, &detail.left, &detail.right),
+        (&state.right_label, &detail.right, &detail.left),
+    ]) {
+        let paragraph = Paragraph::new(side(lines, other))
+            .block(Block::default().borders(Borders::ALL).title(label.as_str()));
+        f.render_widget(paragraph, *column);
+    }
+}
+
+fn draw_summary(f: &mut Frame, state: &CompareState, area: Rect) {
+    if let Some(error) = &state.error {
+        let paragraph = Paragraph::new(error.as_str())
+            .style(Style::default().fg(Color::Red))
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(paragraph, area);
+        return;
+    }
+    let mut text = match state.scanned {
+        Some((left, right)) => format!(
+            "{} keys left, {} right | only left {} | only right {} | type mismatch {} | value mismatch {} | identical {}",
+            format_count(left),
+            format_count(right),
+            format_count(state.count(|status| *status == CompareStatus::OnlyLeft)),
+            format_count(state.count(|status| *status == CompareStatus::OnlyRight)),
+            format_count(state.count(|status| matches!(status, CompareStatus::TypeMismatch { .. }))),
+            format_count(state.count(|status| *status == CompareStatus::ValueMismatch)),
+            format_count(state.identical),
+        ),
+        None => "Scanning both sides...".to_string(),
+    };
+    if state.job.is_some() && state.scanned.is_some() {
+        text.push_str(&format!(" | comparing ({} common keys checked)", format_count(state.compared)));
+    }
+    let paragraph = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,99 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_config_editor::{known_default, ConfigEditorState};
+
+pub(super) fn draw_config_editor_modal(f: &mut Frame, state: &ConfigEditorState, read_only: bool) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let mut title = "CONFIG (type to filter, Enter: edit, Ctrl+S: CONFIG REWRITE, Ctrl+R: reload, Esc: close)".to_string();
+    if read_only {
+        title.push_str(" [read-only profile]");
+    }
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3), Constraint::Length(3), Constraint::Length(2)])
+        .split(inner);
+
+    let filter = Paragraph::new(state.filter.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Filter ({} of {})", state.filtered.len(), state.params.len())),
+    );
+    f.render_widget(filter, rows[0]);
+
+    let name_width = state.params.iter().map(|param| param.name.len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = state
+        .filtered
+        .iter()
+        .map(|&idx| {
+            let param = &state.params[idx];
+            let value_style = match param.is_modified() {
+                Some(true) => Style::default().fg(Color::Yellow),
+                _ => Style::default(),
+            };
+            let mut spans = vec![
+                Span::raw(format!("{:width$}  ", param.name, width = name_width)),
+                Span::styled(format!("{:?}", param.value), value_style),
+            ];
+            if let Some(default) = known_default(&param.name).filter(|_| param.is_modified() == Some(true)) {
+                spans.push(Span::styled(
+                    format!("  (default {:?})", default),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let has_items = !items.is_empty();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Parameter / current value (changed values in yellow)"))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    let mut list_state = ListState::default();
+    if has_items {
+        list_state.select(Some(state.selected));
+    }
+    f.render_stateful_widget(list, rows[1], &mut list_state);
+
+    let (edit_title, edit_text, border) = match (&state.edit, state.selected_param()) {
+        (Some(value), Some(param)) => (format!("CONFIG SET {} (Enter: apply, Esc: cancel)", param.name), value.as_str(), Color::Yellow),
+        _ => ("New value".to_string(), "", Color::DarkGray),
+    };
+    let edit = Paragraph::new(edit_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(edit_title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(edit, rows[2]);
+
+    let (text, cursor, row) = match &state.edit {
+        Some(value) => (value.as_str(), state.edit_cursor, rows[2]),
+        None => (state.filter.as_str(), state.filter_cursor, rows[0]),
+    };
+    let col = text[..cursor.min(text.len())].chars().count() as u16;
+    let x = (row.x + 1 + col).min(row.right().saturating_sub(2));
+    f.set_cursor_position(Position::new(x, row.y + 1));
+
+    let status = Paragraph::new(state.status.as_deref().unwrap_or("")).wrap(Wrap { trim: true });
+    f.render_widget(status, rows[3]);
+}
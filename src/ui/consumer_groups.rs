@@ -0,0 +1,99 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::App;
+
+pub(super) fn draw_consumer_group_modal(f: &mut Frame, app: &App) {
+    let state = &app.consumer_groups;
+    let area = centered_rect(80, 70, f.area());
+    f.render_widget(Clear, area);
+
+    let title = format!(
+        "Consumer Groups: {} (Esc to close)",
+        state.stream_key.as_deref().unwrap_or("")
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2), // Group tabs and summary
+            Constraint::Min(0),    // Pending entries
+            Constraint::Length(1), // Prompt or status
+            Constraint::Length(1), // Help
+        ])
+        .split(inner);
+
+    let mut tabs: Vec<Span> = Vec::new();
+    for (idx, group) in state.groups.iter().enumerate() {
+        let style = if idx == state.selected_group {
+            Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        tabs.push(Span::styled(format!(" {} ", group.name), style));
+        tabs.push(Span::raw(" "));
+    }
+    if tabs.is_empty() {
+        tabs.push(Span::styled("(no consumer groups)", Style::default().fg(Color::DarkGray)));
+    }
+    let summary = match state.current_group() {
+        Some(group) => format!(
+            "consumers: {} | pending: {} | last delivered: {} | lag: {}",
+            group.consumers,
+            group.pending,
+            group.last_delivered_id,
+            group.lag.map_or_else(|| "n/a".to_string(), |lag| lag.to_string())
+        ),
+        None => String::new(),
+    };
+    f.render_widget(Paragraph::new(vec![Line::from(tabs), Line::from(summary)]), chunks[0]);
+
+    let items: Vec<ListItem> = state
+        .pending
+        .iter()
+        .map(|entry| {
+            let mark = if state.marked.contains(&entry.id) { "*" } else { " " };
+            ListItem::new(format!(
+                "{} {:<24} {:<20} idle {:>8}ms  delivered {}",
+                mark, entry.id, entry.consumer, entry.idle_ms, entry.deliveries
+            ))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(state.selected_pending));
+    }
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::TOP).title("Pending entries"))
+        .highlight_style(Style::default().bg(Color::Yellow).fg(Color::Black).add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(list, chunks[1], &mut list_state);
+
+    let bottom = match (state.prompt, &state.status) {
+        (Some(prompt), _) => Line::from(vec![
+            Span::styled(format!("{}: ", prompt.label()), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}_", state.input)),
+        ]),
+        (None, Some(status)) => Line::from(Span::styled(status.as_str(), Style::default().fg(Color::Green))),
+        (None, None) => Line::from(""),
+    };
+    f.render_widget(Paragraph::new(bottom), chunks[2]);
+
+    let help = "h/l: group | j/k: move | Space: mark | a: XACK | c: XCLAIM | A: XAUTOCLAIM | r: refresh";
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(help, Style::default().fg(Color::DarkGray)))),
+        chunks[3],
+    );
+}
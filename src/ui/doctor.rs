@@ -0,0 +1,38 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_doctor::DoctorReportState;
+
+pub(super) fn draw_doctor_modal(f: &mut Frame, report: &DoctorReportState) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let lines: Vec<Line> = report
+        .lines
+        .iter()
+        .map(|line| {
+            if line.starts_with("== ") {
+                Line::styled(line.as_str(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            } else if line.starts_with("(error)") {
+                Line::styled(line.as_str(), Style::default().fg(Color::Red))
+            } else {
+                Line::from(line.as_str())
+            }
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Doctor report (j/k/PgUp/PgDn: scroll, r: rerun, Esc: close)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((report.scroll as u16, 0));
+    f.render_widget(paragraph, area);
+}
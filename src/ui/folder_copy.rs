@@ -0,0 +1,42 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_folder_copy::{FolderCopy, FolderCopyMenu};
+
+pub(super) fn draw_folder_copy_modal(f: &mut Frame, state: &FolderCopyMenu) {
+    let area = centered_rect(60, 20, f.area());
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = FolderCopy::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, choice)| {
+            let detail = match choice {
+                FolderCopy::Prefix => state.prefix.clone(),
+                FolderCopy::KeyNames => format!("every key under {}, one per line", state.prefix),
+            };
+            ListItem::new(format!("{} {:<9}  {}", idx + 1, choice.label(), detail))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Copy folder (1-2 or Enter: copy, y: prefix, Esc: cancel)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_functions::FunctionsState;
+
+pub(super) fn draw_functions_modal(f: &mut Frame, state: &FunctionsState) {
+    let area = centered_rect(80, 75, f.area());
+    f.render_widget(Clear, area);
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title("Functions (j/k: select, c/Enter: FCALL, l: FUNCTION LOAD, r: refresh, Esc: close)")
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(55), Constraint::Length(3), Constraint::Min(3)])
+        .split(inner);
+
+    let items: Vec<ListItem> = state
+        .functions()
+        .map(|(library, function)| {
+            let mut line = format!("{}.{} [{}]", library.name, function.name, library.engine);
+            if !function.flags.is_empty() {
+                line.push_str(&format!(" ({})", function.flags.join(", ")));
+            }
+            if !function.description.is_empty() {
+                line.push_str(&format!(" - {}", function.description));
+            }
+            ListItem::new(line)
+        })
+        .collect();
+    let has_functions = !items.is_empty();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("library.function"))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    if has_functions {
+        list_state.select(Some(state.selected));
+    }
+    f.render_stateful_widget(list, rows[0], &mut list_state);
+
+    let (title, border) = match state.prompt {
+        Some(prompt) => (prompt.label(), Color::Yellow),
+        None => ("Input", Color::DarkGray),
+    };
+    let input = Paragraph::new(state.input.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(input, rows[1]);
+    if state.prompt.is_some() {
+        let col = state.input[..state.cursor.min(state.input.len())].chars().count() as u16;
+        let x = (rows[1].x + 1 + col).min(rows[1].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[1].y + 1));
+    }
+
+    let result = state.result.as_deref().unwrap_or("");
+    let result_widget = Paragraph::new(result.lines().map(Line::from).collect::<Vec<_>>())
+        .block(Block::default().borders(Borders::ALL).title("Result"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(result_widget, rows[2]);
+}
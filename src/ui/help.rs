@@ -0,0 +1,60 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::state_help::HelpState;
+use crate::keymap::{KeyContext, Keymap};
+
+/// Keys column width, wide enough for `Ctrl+Space`-style chords.
+const KEYS_WIDTH: usize = 14;
+
+pub(super) fn draw_help_modal(f: &mut Frame, state: &HelpState, keymap: &Keymap) {
+    let area = f.area();
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Keybindings (j/k: scroll, Esc/?: close)")
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    // General bindings on the left, the context-specific ones on the right
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
+    let (general, specific): (Vec<KeyContext>, Vec<KeyContext>) = KeyContext::ALL
+        .into_iter()
+        .partition(|context| *context == KeyContext::Global);
+    let left = help_lines(keymap, &general);
+    let right = help_lines(keymap, &specific);
+    let visible = inner.height as usize;
+    let longest = left.len().max(right.len());
+    let scroll = state.scroll.min(longest.saturating_sub(visible)) as u16;
+    f.render_widget(Paragraph::new(left).scroll((scroll, 0)), columns[0]);
+    f.render_widget(Paragraph::new(right).scroll((scroll, 0)), columns[1]);
+}
+
+fn help_lines(keymap: &Keymap, contexts: &[KeyContext]) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for context in contexts {
+        if !lines.is_empty() {
+            lines.push(Line::default());
+        }
+        lines.push(Line::styled(
+            context.label(),
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+        ));
+        for (keys, description) in keymap.help_rows(*context) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {:<width$} ", keys, width = KEYS_WIDTH), Style::default().fg(Color::Yellow)),
+                Span::raw(description),
+            ]));
+        }
+    }
+    lines
+}
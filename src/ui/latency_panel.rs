@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Sparkline, Wrap},
+    Frame,
+};
+
+use super::{centered_rect, format_latency, latency_color};
+use crate::app::state_latency_panel::LatencyPanelState;
+
+/// Height of each sparkline block, including its borders.
+const GRAPH_HEIGHT: u16 = 5;
+
+pub(super) fn draw_latency_modal(f: &mut Frame, panel: &LatencyPanelState) {
+    let area = centered_rect(85, 85, f.area());
+    f.render_widget(Clear, area);
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title("Latency (PING every second, LATENCY LATEST/HISTORY every 5s; Esc: close)")
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let mut constraints = vec![Constraint::Length(GRAPH_HEIGHT)];
+    constraints.extend(panel.events.iter().map(|_| Constraint::Length(GRAPH_HEIGHT)));
+    constraints.push(Constraint::Min(0));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(inner);
+
+    let width = rows[0].width.saturating_sub(2) as usize;
+    let pings: Vec<Duration> = panel.ping_samples.iter().copied().collect();
+    let ping_title = match (pings.last(), pings.iter().max()) {
+        (Some(last), Some(max)) => format!("PING RTT: last {}, max {}", format_latency(*last), format_latency(*max)),
+        _ => "PING RTT: waiting for samples...".to_string(),
+    };
+    let color = pings.last().map_or(Color::Green, |last| latency_color(*last));
+    let recent = &pings[pings.len().saturating_sub(width)..];
+    let ping_data: Vec<u64> = recent.iter().map(|rtt| rtt.as_micros() as u64).collect();
+    let ping_graph = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(ping_title))
+        .data(&ping_data)
+        .style(Style::default().fg(color));
+    f.render_widget(ping_graph, rows[0]);
+
+    for (event, row) in panel.events.iter().zip(rows.iter().skip(1)) {
+        let latest = Duration::from_millis(event.latest_ms);
+        let samples = &event.history[event.history.len().saturating_sub(width)..];
+        let data: Vec<u64> = samples.iter().map(|(_, ms)| *ms).collect();
+        let graph = Sparkline::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "{}: latest {}ms, max {}ms ({} samples)",
+                event.name,
+                event.latest_ms,
+                event.max_ms,
+                event.history.len()
+            )))
+            .data(&data)
+            .style(Style::default().fg(latency_color(latest)));
+        f.render_widget(graph, *row);
+    }
+
+    let note = match &panel.error {
+        Some(error) => error.clone(),
+        None if panel.events.is_empty() => "No latency events recorded. The server only tracks them when \
+latency-monitor-threshold is set, e.g. CONFIG SET latency-monitor-threshold 10."
+            .to_string(),
+        None => String::new(),
+    };
+    let footer = Paragraph::new(note)
+        .style(Style::default().fg(Color::DarkGray))
+        .wrap(Wrap { trim: true });
+    f.render_widget(footer, rows[rows.len() - 1]);
+}
@@ -0,0 +1,1430 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect, Alignment, Margin, Position},
+    style::{Color, Modifier, Style},
+    widgets::{
+        Block, Borders, List, ListItem, ListState, Paragraph, Clear, Wrap, Gauge, Scrollbar,
+        ScrollbarOrientation, ScrollbarState,
+    },
+    Frame,
+    text::{Line, Span},
+};
+mod action_menu;
+mod analysis;
+mod bookmarks;
+mod compare;
+mod config_editor;
+mod consumer_groups;
+mod doctor;
+mod folder_copy;
+mod functions;
+mod help;
+mod key_info;
+mod latency_panel;
+mod monitor;
+mod paste;
+mod pubsub;
+mod random_sample;
+mod redisearch;
+mod replication;
+mod rows;
+mod scan_presets;
+mod script_runner;
+mod snapshots;
+mod stats_report;
+mod toasts;
+
+use crate::app::{App};
+use crate::app::mouse::{PanelAreas, BREADCRUMB_SEPARATOR};
+use crate::app::hex_view::{self, HexViewState, HEX_ROW_BYTES};
+use crate::app::json_highlight::{JsonToken, JsonTokenKind};
+use crate::app::latency_tracker::OperationClass;
+use crate::app::cluster::key_slot;
+use crate::app::redis_stats::{format_bytes, KeyspaceCounts};
+use crate::app::value_viewer::ValueSort;
+use rows::{gutter_width, mark_changed, with_gutter};
+use crate::search::SearchMode;
+use crate::config::ConnectionProfile;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// helper function to create a centered rect using up certain percentage of the available rect `r`
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ].as_ref())
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ].as_ref())
+        .split(popup_layout[1])[1]
+}
+
+/// Width of the server stats panel (`s`) in percent.
+const STATS_PANEL_PERCENT: u16 = 25;
+
+/// DB list and status, key/value panels and footer help, top to bottom.
+fn main_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5), // Increased height for DB list and status
+            Constraint::Min(0),    // For key/value panels
+            Constraint::Length(1), // For footer help
+        ].as_ref())
+        .split(area)
+}
+
+/// Key list, value panel and, when shown, the stats panel, left to right. The key list
+/// keeps its width when the stats panel opens; the value panel gives up the space.
+fn content_layout(area: Rect, app: &App) -> Rc<[Rect]> {
+    let keys = app.key_panel_percent();
+    let constraints = if app.show_stats {
+        vec![
+            Constraint::Percentage(keys),
+            Constraint::Percentage(100 - keys - STATS_PANEL_PERCENT),
+            Constraint::Percentage(STATS_PANEL_PERCENT),
+        ]
+    } else {
+        vec![Constraint::Percentage(keys), Constraint::Percentage(100 - keys)]
+    };
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(area)
+}
+
+/// Where `ui` draws each clickable pane and list for a terminal of size `area`.
+pub fn panel_areas(area: Rect, app: &App) -> PanelAreas {
+    let bordered = |area: Rect| Block::default().borders(Borders::ALL).inner(area);
+    let main = main_layout(area);
+    let content = content_layout(main[1], app);
+    let db_list = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(2)])
+        .split(bordered(main[0]))[0];
+    let (breadcrumb_bar, key_panel) = key_panel_with_breadcrumb(content[0], app);
+    let key_list = match scan_progress(app) {
+        Some(_) => key_list_with_gauge(key_panel)[0],
+        None => key_panel,
+    };
+    PanelAreas {
+        profile_list: app
+            .profile_state
+            .is_active
+            .then(|| bordered(centered_rect(60, 50, area))),
+        db_pane: main[0],
+        db_list,
+        keys_pane: content[0],
+        key_list: bordered(key_list),
+        breadcrumb_bar,
+        values_pane: content[1],
+        stats_pane: content.get(2).copied(),
+    }
+}
+
+/// One-line breadcrumb bar over the rest of the key panel; searching shows no bar.
+fn key_panel_with_breadcrumb(area: Rect, app: &App) -> (Option<Rect>, Rect) {
+    if app.search_state.is_active {
+        return (None, area);
+    }
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3)])
+        .split(area);
+    (Some(rows[0]), rows[1])
+}
+
+/// Key list above a one-line scan progress gauge.
+fn key_list_with_gauge(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(area)
+}
+
+pub fn ui(f: &mut Frame, app: &App) {
+    let main_layout = main_layout(f.area());
+
+    if app.profile_state.is_active {
+        // Profile selector takes over the main view
+        draw_profile_selector_modal(f, app);
+        // Still draw the footer, it sits below the area the modal covers
+        draw_footer_help(f, app, main_layout[2]);
+    } else {
+        // Normal view
+        let content_layout_chunks = content_layout(main_layout[1], app);
+
+        draw_profiles_or_db_list(f, app, main_layout[0]);
+        draw_key_list_panel(f, app, content_layout_chunks[0]);
+        
+        if app.show_stats {
+            draw_value_display_panel(f, app, content_layout_chunks[1]);
+            draw_redis_stats_panel(f, app, content_layout_chunks[2]);
+        } else {
+            draw_value_display_panel(f, app, content_layout_chunks[1]);
+        }
+        
+        draw_footer_help(f, app, main_layout[2]);
+
+        if app.delete_dialog.show_confirmation_dialog {
+            draw_delete_confirmation_dialog(f, app);
+        }
+        if app.command_state.is_active {
+            draw_command_prompt_modal(f, app);
+        }
+        if app.consumer_groups.is_active {
+            consumer_groups::draw_consumer_group_modal(f, app);
+        }
+        if let Some(info) = &app.key_info {
+            key_info::draw_key_info_modal(f, info);
+        }
+        if app.bookmarks_panel.is_active {
+            bookmarks::draw_bookmarks_modal(f, app);
+        }
+        if app.script_runner.is_active {
+            script_runner::draw_script_runner_modal(f, &app.script_runner);
+        }
+        if app.action_menu.is_active {
+            action_menu::draw_action_menu_modal(f, &app.action_menu, &app.keymap);
+        }
+        if app.scan_presets.is_active {
+            scan_presets::draw_scan_presets_modal(f, &app.scan_presets);
+        }
+        if app.paste_preview.is_active {
+            paste::draw_paste_preview_modal(f, &app.paste_preview);
+        }
+        if app.folder_copy.is_active {
+            folder_copy::draw_folder_copy_modal(f, &app.folder_copy);
+        }
+        if app.functions.is_active {
+            functions::draw_functions_modal(f, &app.functions);
+        }
+        if app.redisearch.is_active {
+            redisearch::draw_redisearch_modal(f, &app.redisearch);
+        }
+        if app.pubsub.is_active {
+            pubsub::draw_pubsub_modal(f, &app.pubsub);
+        }
+        if app.monitor.is_active {
+            monitor::draw_monitor_modal(f, &app.monitor);
+        }
+        if app.config_editor.is_active {
+            config_editor::draw_config_editor_modal(f, &app.config_editor, app.is_read_only());
+        }
+        if app.latency_panel.is_active {
+            latency_panel::draw_latency_modal(f, &app.latency_panel);
+        }
+        if app.doctor_report.is_active {
+            doctor::draw_doctor_modal(f, &app.doctor_report);
+        }
+        if app.stats_report.is_active {
+            stats_report::draw_stats_report_modal(f, &app.stats_report);
+        }
+        if app.analysis.is_active {
+            analysis::draw_analysis_modal(f, &app.analysis);
+        }
+        if app.random_sample.is_active {
+            random_sample::draw_random_sample_modal(f, &app.random_sample);
+        }
+        if app.snapshots.is_active {
+            snapshots::draw_snapshots_modal(f, &app.snapshots);
+        }
+        if app.compare.is_active {
+            compare::draw_compare_modal(f, app);
+        }
+        if app.help.is_active {
+            help::draw_help_modal(f, &app.help, &app.keymap);
+        }
+    }
+    // Toasts float above everything, just over the footer
+    let toast_area = Rect {
+        height: main_layout[2].y,
+        ..f.area()
+    };
+    toasts::draw_toasts(f, &app.notifications, toast_area);
+}
+
+fn draw_profiles_or_db_list(f: &mut Frame, app: &App, area: Rect) {
+    let is_focused = !app.is_key_view_focused && !app.is_value_view_focused;
+
+    let current_profile = app.profiles.get(app.current_profile_index);
+    let profile_name_str = current_profile.map_or("Unknown", |p| p.name.as_str());
+    let profile_color = current_profile.map_or(Color::White, |p| p.resolved_color());
+
+    let mut base_title_text = format!("1: Profile: {} - Databases / Connection", profile_name_str);
+    if let Some(reads) = read_target_label(app, current_profile) {
+        base_title_text.push_str(&format!(" | reads: {}", reads));
+    }
+    let block_title = if is_focused {
+        format!("{} [FOCUSED]", base_title_text)
+    } else {
+        base_title_text
+    };
+
+    let outer_block = Block::default()
+        .borders(Borders::ALL)
+        .title(block_title)
+        .border_style(Style::default().fg(profile_color));
+
+    f.render_widget(outer_block.clone(), area); // Render the block itself
+    let inner_area = outer_block.inner(area); // Get area inside borders
+
+    // Split inner_area for a vertical DB list and a status message area
+    let vertical_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),       // For DB List
+            Constraint::Length(2),    // For Connection Status and DB size
+        ].as_ref())
+        .split(inner_area);
+    
+    let db_list_area = vertical_chunks[0];
+    let status_area = vertical_chunks[1];
+
+    // Render DB List (Vertical)
+    let dbs: Vec<ListItem> = (0..app.db_count)
+        .map(|i| {
+            let display_text = db_label(i as usize, app.db_key_counts.as_ref());
+            let style = if i as usize == app.selected_db_index {
+                if is_focused {
+                    Style::default().fg(Color::Black).bg(Color::White) // Focused and selected
+                } else {
+                    Style::default().fg(Color::Cyan) // Selected but not focused
+                }
+            } else {
+                Style::default()
+            };
+            ListItem::new(display_text).style(style)
+        })
+        .collect();
+
+    let db_list_widget = List::new(dbs)
+        .block(Block::default()) // No title for inner list, outer block has it
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD)) // Style for when navigating, if any
+        .highlight_symbol(if is_focused { ">> " } else { "  " });
+    
+    let mut db_list_state = ListState::default();
+    if app.db_count > 0 && (app.selected_db_index as u8) < app.db_count { // Ensure index is valid
+        db_list_state.select(Some(app.selected_db_index));
+    }
+    f.render_stateful_widget(db_list_widget, db_list_area, &mut db_list_state);
+
+    // Render Status
+    let mut status_lines = vec![Line::from(app.connection_status.as_str())];
+    if let Some(size) = app.db_size {
+        status_lines.push(Line::from(db_size_label(app.selected_db_index, size, app.key_scan.is_some())));
+    }
+    let connection_status_paragraph = Paragraph::new(status_lines)
+        .style(Style::default().fg(profile_color))
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center); // Center status text
+    f.render_widget(connection_status_paragraph, status_area);
+}
+
+/// Where browsing reads go, for profiles that configure a replica.
+fn read_target_label(app: &App, profile: Option<&ConnectionProfile>) -> Option<String> {
+    match &app.redis.read_target {
+        Some(target) => Some(format!("replica {}", target)),
+        None if profile.is_some_and(|profile| profile.replica_url.is_some()) => Some("primary".to_string()),
+        None => None,
+    }
+}
+
+/// `DB 2 — 1,532,884 keys (scanning…)`, shown before the incremental SCAN catches up.
+fn db_size_label(db: usize, size: u64, scanning: bool) -> String {
+    let label = format!("DB {} — {} keys", db, format_count(size as usize));
+    if scanning {
+        format!("{} (scanning…)", label)
+    } else {
+        label
+    }
+}
+
+/// `DB 3 (12,405 keys, 301 expiring)`, or just `DB 3` while counts are unknown.
+fn db_label(db: usize, counts: Option<&HashMap<usize, KeyspaceCounts>>) -> String {
+    let Some(counts) = counts else {
+        return format!("DB {}", db);
+    };
+    match counts.get(&db) {
+        Some(db_counts) if db_counts.expires > 0 => format!(
+            "DB {} ({} keys, {} expiring)",
+            db,
+            format_count(db_counts.keys as usize),
+            format_count(db_counts.expires as usize)
+        ),
+        Some(db_counts) => format!("DB {} ({} keys)", db, format_count(db_counts.keys as usize)),
+        None => format!("DB {} (empty)", db),
+    }
+}
+
+fn format_ttl(ttl: i64) -> String {
+    match ttl {
+        -2 => "Key missing".to_string(),
+        -1 => "No Expiry".to_string(),
+        ttl if ttl < 0 => "Unknown TTL".to_string(),
+        _ => {
+            let mins = ttl / 60;
+            let secs = ttl % 60;
+            if mins > 0 {
+                format!("Expires in {}m {}s", mins, secs)
+            } else {
+                format!("Expires in {}s", secs)
+            }
+        }
+    }
+}
+
+/// Whether a TTL is below the `warning_secs` threshold; keys without an expiry never are.
+/// Seconds left of a listed key in its largest whole unit, e.g. `45s`, `12m`, `3d`.
+fn compact_ttl(secs: i64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
+fn ttl_is_expiring(ttl: i64, warning_secs: u64) -> bool {
+    ttl >= 0 && (ttl as u64) < warning_secs
+}
+
+fn key_list_title(app: &App) -> String {
+    let search = &app.search_state;
+    if search.is_active {
+        let label = search_label(search.mode);
+        return match &search.query_error {
+            Some(error) => format!("2: {}: {} [invalid: {}]", label, search.query, error),
+            None if app.search_pending() => format!("2: {}: {} [searching...]", label, search.query),
+            None => format!("2: {}: {}", label, search.query),
+        };
+    }
+    if let Some(input) = &app.scan_prompt {
+        return format!("2: Scan pattern [count]: {}_", input);
+    }
+    if let Some(input) = &app.goto_key_prompt {
+        return format!("2: Go to key: {}_", input);
+    }
+    // The breadcrumb bar under the title shows the path
+    let mut title = "2: Keys".to_string();
+    if app.scan_pattern != "*" {
+        title.push_str(&format!(" [MATCH {}]", app.scan_pattern));
+    }
+    if let Some(key_type) = app.scan_type {
+        title.push_str(&format!(" [TYPE {}]", key_type));
+    }
+    if app.live_updates.is_some() {
+        title.push_str(" [LIVE]");
+    }
+    title
+}
+
+/// Appends a prompt's `input` to `title` and returns the column of its cursor in the title.
+fn title_prompt(title: &mut String, input: &str, cursor: usize) -> usize {
+    let offset = title.chars().count() + input[..cursor.min(input.len())].chars().count();
+    title.push_str(input);
+    offset
+}
+
+fn search_label(mode: SearchMode) -> &'static str {
+    match mode {
+        SearchMode::Fuzzy => "Search Results (Global)",
+        SearchMode::Regex => "Regex Search",
+        SearchMode::Server => "Server Search (SCAN MATCH)",
+        SearchMode::Values => "Value Search ([match:<glob>] text)",
+    }
+}
+
+fn draw_key_list_panel(f: &mut Frame, app: &App, area: Rect) {
+    if app.search_state.is_active {
+        // The query is edited in the panel title, after "2: <label>: "
+        let search = &app.search_state;
+        let before_cursor = &search.query[..search.query_cursor.min(search.query.len())];
+        let offset = format!("2: {}: ", search_label(search.mode)).chars().count()
+            + before_cursor.chars().count();
+        let cursor_x = (area.x + 1 + offset as u16).min(area.right().saturating_sub(2));
+        f.set_cursor_position(Position::new(cursor_x, area.y));
+    }
+    let key_view_base_title = key_list_title(app);
+    let key_view_title = if app.is_key_view_focused {
+        format!("{} [FOCUSED]", key_view_base_title)
+    } else {
+        key_view_base_title
+    };
+    let row_width = area.width.saturating_sub(2 + 3) as usize;
+    let progress = scan_progress(app);
+    let (breadcrumb_bar, area) = key_panel_with_breadcrumb(area, app);
+    if let Some(bar) = breadcrumb_bar {
+        draw_breadcrumb_bar(f, app, bar);
+    }
+    let list_area = match progress {
+        Some(_) => key_list_with_gauge(area)[0],
+        None => area,
+    };
+    // Only rows around the viewport are built, so huge folders and search results stay fast
+    let rows = list_area.height.saturating_sub(2) as usize;
+    let (key_items, selected_key_index, list_len, window) = if app.search_state.is_active {
+        rows::search_key_items(app, row_width, rows)
+    } else if app.tree_view.is_active {
+        rows::tree_key_items(app, row_width, rows)
+    } else {
+        rows::flat_key_items(app, row_width, rows)
+    };
+    let mut list_state = ListState::default().with_offset(app.key_list_scroll.offset.saturating_sub(window.start));
+    let mut key_block = Block::default().borders(Borders::ALL).title(key_view_title);
+    if app.search_state.is_active && app.search_state.hidden_matches > 0 {
+        key_block = key_block.title_bottom(format!(
+            " {} more matches, refine the query ",
+            format_count(app.search_state.hidden_matches)
+        ));
+    }
+    if let Some(position) = list_position(list_area, selected_key_index, list_len) {
+        key_block = key_block.title_bottom(position);
+    }
+    let list_widget = List::new(key_items)
+        .block(key_block)
+        .highlight_style(
+            Style::default()
+                .bg(if app.is_key_view_focused { Color::Yellow } else { Color::DarkGray })
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(if app.is_key_view_focused { ">> " } else { "  " });
+    if window.contains(&selected_key_index) {
+        list_state.select(Some(selected_key_index - window.start));
+    }
+    f.render_stateful_widget(list_widget, list_area, &mut list_state);
+    draw_list_scrollbar(f, list_area, selected_key_index, list_len);
+    let Some((ratio, label)) = progress else {
+        return;
+    };
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(Color::Cyan).bg(Color::Black))
+        .ratio(ratio)
+        .label(label);
+    f.render_widget(gauge, key_list_with_gauge(area)[1]);
+}
+
+/// Root and folders of the current breadcrumb, the current level in bold and the level
+/// picked with Left/Right highlighted.
+fn draw_breadcrumb_bar(f: &mut Frame, app: &App, area: Rect) {
+    let labels = app.breadcrumb_labels();
+    let current = labels.len() - 1;
+    let mut spans = Vec::new();
+    for (level, label) in labels.into_iter().enumerate() {
+        if level > 0 {
+            spans.push(Span::styled(BREADCRUMB_SEPARATOR, Style::default().fg(Color::DarkGray)));
+        }
+        let style = if app.breadcrumb_cursor == Some(level) {
+            Style::default().bg(Color::Yellow).fg(Color::Black)
+        } else if level == current {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        spans.push(Span::styled(label, style));
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// `420/12,000` for the bottom border of a bordered list drawn in `area`, once it has more
+/// items than rows.
+fn list_position(area: Rect, selected: usize, len: usize) -> Option<Line<'static>> {
+    let rows = area.height.saturating_sub(2) as usize;
+    if len <= rows {
+        return None;
+    }
+    let label = format!(" {}/{} ", format_count(selected + 1), format_count(len));
+    Some(Line::from(label).right_aligned())
+}
+
+/// Scrollbar over the right border of a bordered list that has more items than rows.
+fn draw_list_scrollbar(f: &mut Frame, area: Rect, selected: usize, len: usize) {
+    let rows = area.height.saturating_sub(2) as usize;
+    if len <= rows {
+        return;
+    }
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None);
+    let mut state = ScrollbarState::new(len).position(selected).viewport_content_length(rows);
+    f.render_stateful_widget(scrollbar, area.inner(Margin::new(0, 1)), &mut state);
+}
+
+/// Gauge ratio and label for the background scan feeding the key list, or the running
+/// delete, if any.
+fn scan_progress(app: &App) -> Option<(f64, String)> {
+    if let Some(delete) = app.bulk_delete.as_ref().filter(|_| !app.search_state.is_active) {
+        let label = format!(
+            "Deleting {}: {} removed (Esc: stop)",
+            delete.label,
+            format_count(delete.deleted.max(0) as usize)
+        );
+        return Some((0.0, label));
+    }
+    if let Some(walk) = app.prefix_walk.as_ref().filter(|_| !app.search_state.is_active) {
+        let label = format!("Listing {}: {} found (Esc: stop)", walk.label, format_count(walk.found));
+        return Some((0.0, label));
+    }
+    if let Some(grep) = app.value_grep.as_ref().filter(|_| app.search_state.is_active) {
+        let label = format!(
+            "Searching values for '{}': {} keys read, cursor {} (Esc: cancel)",
+            grep.needle,
+            format_count(grep.scanned),
+            grep.cursor
+        );
+        return Some((0.0, label));
+    }
+    let scan = if app.search_state.is_active { &app.search_scan } else { &app.key_scan };
+    let scan = scan.as_ref()?;
+    let total = scan
+        .total
+        .map(|total| format!(" / {}", format_count(total as usize)))
+        .unwrap_or_default();
+    let label = format!(
+        "Scanning '{}': {}{} keys, cursor {} (Esc: cancel)",
+        scan.prefix,
+        format_count(scan.found),
+        total,
+        scan.cursor
+    );
+    Some((scan.progress().unwrap_or(0.0), label))
+}
+
+fn draw_value_display_panel(f: &mut Frame, app: &App, area: Rect) {
+    // Byte range of the TTL in the title, restyled when the key is about to expire
+    let mut expiring_ttl = None;
+    let mut value_block_title = match &app.value_viewer.active_leaf_key_name {
+        Some(name) => {
+            let ttl = app.remaining_ttl().unwrap_or(-2);
+            let head = format!(
+                "3: Value: {} ({}) | TTL: ",
+                name,
+                app.value_viewer.selected_key_type.as_deref().unwrap_or("N/A")
+            );
+            let ttl_str = format_ttl(ttl);
+            if ttl_is_expiring(ttl, app.settings.ttl_warning_secs) {
+                expiring_ttl = Some(head.len()..head.len() + ttl_str.len());
+            }
+            head + &ttl_str
+        },
+        None => "3: Value".to_string(),
+    };
+    if let Some(bytes) = app.value_viewer.memory_usage {
+        value_block_title.push_str(&format!(" | Mem: {}", format_bytes(bytes)));
+    }
+    if let (Some(cluster), Some(name)) = (&app.cluster, &app.value_viewer.active_leaf_key_name) {
+        let slot = key_slot(name.as_bytes());
+        value_block_title.push_str(&format!(" | Slot: {}", slot));
+        if let Some(node) = cluster.owner(slot) {
+            value_block_title.push_str(&format!(" @ {}", node));
+        }
+    }
+    if let Some(info) = &app.value_viewer.decompressed {
+        value_block_title.push_str(&format!(
+            " | {} {} -> {}",
+            info.compression.label(),
+            format_bytes(info.original_len as u64),
+            format_bytes(info.decompressed_len as u64)
+        ));
+        if info.truncated {
+            value_block_title.push_str(" (truncated)");
+        }
+    }
+    if let Some(decoded_as) = &app.value_viewer.decoded_as {
+        value_block_title.push_str(&format!(" | decoded: {}", decoded_as));
+    }
+    if let Some(chunk) = &app.value_viewer.string_chunk {
+        value_block_title.push_str(&format!(
+            " | Bytes {}-{} of {} (chunk {}/{}, [/]: page)",
+            chunk.offset,
+            chunk.end(),
+            chunk.total_len,
+            chunk.chunk_index() + 1,
+            chunk.chunk_count()
+        ));
+    }
+    if let Some(page) = &app.value_viewer.collection_page {
+        value_block_title.push_str(&format!(
+            " | Showing {} of {} — n: load more",
+            format_count(page.loaded),
+            format_count(page.total)
+        ));
+    }
+    let hex_bytes = app
+        .value_viewer
+        .raw_value
+        .as_deref()
+        .filter(|_| app.value_viewer.hex_view.enabled);
+    if hex_bytes.is_some() {
+        let hex_state = &app.value_viewer.hex_view;
+        value_block_title.push_str(&format!(
+            " | HEX @ 0x{:X}",
+            app.value_viewer.raw_base_offset() + hex_state.cursor
+        ));
+        if let Some(input) = &hex_state.goto_input {
+            value_block_title.push_str(&format!(" | Goto offset: {}_", input));
+        }
+    }
+    if let Some(tail) = &app.stream_tail {
+        value_block_title.push_str(if tail.paused { " | PAUSED (F: resume)" } else { " | LIVE (F: pause)" });
+    }
+    if app.watch.enabled {
+        value_block_title.push_str(&format!(
+            " | WATCH every {}",
+            format_latency(Duration::from_millis(app.settings.watch_interval_ms))
+        ));
+    }
+    let viewer = &app.value_viewer;
+    if viewer.sort != ValueSort::Natural {
+        let key_type = viewer.selected_key_type.as_deref().unwrap_or("");
+        value_block_title.push_str(&format!(" | sort: {}", viewer.sort.label_for(key_type)));
+    }
+    if viewer.filter_input_active || !viewer.line_filter.is_empty() {
+        let cursor = if viewer.filter_input_active { "_" } else { "" };
+        value_block_title.push_str(&format!(
+            " | filter: {}{} ({} rows)",
+            viewer.line_filter,
+            cursor,
+            viewer.displayed_value_lines.as_ref().map_or(0, Vec::len)
+        ));
+    }
+    if let Some(input) = &app.value_viewer.goto_line_input {
+        value_block_title.push_str(&format!(" | Goto line: {}_", input));
+    }
+    // PFADD and existence-check inputs are edited in the title; the cursor sits in it
+    let mut title_cursor = None;
+    if let Some(input) = &app.value_viewer.pfadd_input {
+        value_block_title.push_str(" | PFADD: ");
+        title_cursor = Some(title_prompt(&mut value_block_title, input, app.value_viewer.pfadd_cursor));
+    }
+    if let (Some(input), Some(view)) = (&app.value_viewer.probe_input, &app.value_viewer.probabilistic) {
+        value_block_title.push_str(&format!(" | {}: ", view.kind.check_command()));
+        title_cursor = Some(title_prompt(&mut value_block_title, input, app.value_viewer.probe_cursor));
+    }
+    if let Some(offset) = title_cursor {
+        let cursor_x = (area.x + 1 + offset as u16).min(area.right().saturating_sub(2));
+        f.set_cursor_position(Position::new(cursor_x, area.y));
+    }
+    if app.is_value_view_focused {
+        value_block_title.push_str(" [FOCUSED]");
+    }
+    let value_block_title = match expiring_ttl {
+        Some(range) => Line::from(vec![
+            Span::raw(value_block_title[..range.start].to_string()),
+            Span::styled(
+                value_block_title[range.clone()].to_string(),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(value_block_title[range.end..].to_string()),
+        ]),
+        None => Line::from(value_block_title),
+    };
+    let block = Block::default().borders(Borders::ALL).title(value_block_title)
+        .border_style(if app.is_value_view_focused { Style::default().fg(Color::Cyan) } else { Style::default() });
+    if let Some(bytes) = hex_bytes {
+        let height = area.height.saturating_sub(2) as usize;
+        let lines = hex_view_lines(bytes, app.value_viewer.raw_base_offset(), &app.value_viewer.hex_view, height);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    } else if let Some(lines) = &app.value_viewer.displayed_value_lines {
+        let wrap_width = (area.width.saturating_sub(2 + 3) as usize).saturating_sub(gutter_width(&app.value_viewer));
+        let selected = app.value_viewer.selected_value_sub_index;
+        let len = lines.len();
+        let window = app.value_list_scroll.window(selected, len, area.height.saturating_sub(2) as usize);
+        let items = rows::value_items(app, lines, window.clone(), wrap_width);
+        let mut list_state = ListState::default().with_offset(app.value_list_scroll.offset.saturating_sub(window.start));
+        if window.contains(&selected) {
+            list_state.select(Some(selected - window.start));
+        }
+        let block = match list_position(area, selected, len) {
+            Some(position) => block.title_bottom(position),
+            None => block,
+        };
+        let list_widget = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(if app.is_value_view_focused { Color::Yellow } else { Color::DarkGray })
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(if app.is_value_view_focused { ">> " } else { "  " });
+        f.render_stateful_widget(list_widget, area, &mut list_state);
+        draw_list_scrollbar(f, area, selected, len);
+    } else if let Some(json_lines) = &app.value_viewer.highlighted_json {
+        let lines: Vec<Line> = json_lines
+            .iter()
+            .enumerate()
+            .map(|(row, tokens)| mark_changed(with_gutter(json_line(tokens), row, &app.value_viewer), row, app))
+            .collect();
+        let mut value_paragraph = Paragraph::new(lines).block(block).scroll(app.value_viewer.value_view_scroll);
+        if !app.value_viewer.wrap_disabled {
+            value_paragraph = value_paragraph.wrap(Wrap { trim: false });
+        }
+        f.render_widget(value_paragraph, area);
+    } else {
+        let value_display_text = app.value_viewer.current_display_value.as_deref().unwrap_or("");
+        let lines: Vec<Line> = value_display_text
+            .lines()
+            .enumerate()
+            .map(|(row, line)| mark_changed(with_gutter(Line::from(line), row, &app.value_viewer), row, app))
+            .collect();
+        let mut value_paragraph = Paragraph::new(lines)
+            .block(block)
+            .scroll(app.value_viewer.value_view_scroll);
+        if !app.value_viewer.wrap_disabled {
+            value_paragraph = value_paragraph.wrap(Wrap { trim: true });
+        }
+        f.render_widget(value_paragraph, area);
+    }
+}
+
+/// Renders the page of 16-byte rows containing the cursor, with an ASCII sidebar.
+fn hex_view_lines(bytes: &[u8], base_offset: usize, state: &HexViewState, height: usize) -> Vec<Line<'static>> {
+    let height = height.max(1);
+    let cursor_row = state.cursor / HEX_ROW_BYTES;
+    let top_row = (cursor_row / height) * height;
+    let byte_style = |position: usize| {
+        if position == state.cursor {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else if state.is_selected(position) {
+            Style::default().fg(Color::Black).bg(Color::LightBlue)
+        } else {
+            Style::default()
+        }
+    };
+
+    let mut lines = Vec::with_capacity(height);
+    for row in top_row..top_row + height {
+        let start = row * HEX_ROW_BYTES;
+        if start >= bytes.len() {
+            break;
+        }
+        let chunk = &bytes[start..(start + HEX_ROW_BYTES).min(bytes.len())];
+        let mut spans = vec![Span::styled(
+            format!("{:08X}  ", base_offset + start),
+            Style::default().fg(Color::DarkGray),
+        )];
+        for idx in 0..HEX_ROW_BYTES {
+            let separator = if idx == HEX_ROW_BYTES / 2 - 1 { "  " } else { " " };
+            match chunk.get(idx) {
+                Some(byte) => {
+                    spans.push(Span::styled(format!("{:02X}", byte), byte_style(start + idx)));
+                    spans.push(Span::raw(separator));
+                }
+                None => spans.push(Span::raw(format!("  {}", separator))),
+            }
+        }
+        spans.push(Span::styled("|", Style::default().fg(Color::DarkGray)));
+        for (idx, byte) in chunk.iter().enumerate() {
+            spans.push(Span::styled(hex_view::ascii_char(*byte).to_string(), byte_style(start + idx)));
+        }
+        spans.push(Span::styled("|", Style::default().fg(Color::DarkGray)));
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn json_line(tokens: &[JsonToken]) -> Line<'_> {
+    Line::from(
+        tokens
+            .iter()
+            .map(|token| {
+                let style = match token.kind {
+                    JsonTokenKind::Key => Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+                    JsonTokenKind::String => Style::default().fg(Color::Green),
+                    JsonTokenKind::Number => Style::default().fg(Color::LightMagenta),
+                    JsonTokenKind::Boolean => Style::default().fg(Color::Yellow),
+                    JsonTokenKind::Null => Style::default().fg(Color::DarkGray),
+                    JsonTokenKind::Punctuation | JsonTokenKind::Whitespace => Style::default(),
+                };
+                Span::styled(token.text.as_str(), style)
+            })
+            .collect::<Vec<Span>>(),
+    )
+}
+
+fn draw_footer_help(f: &mut Frame, app: &App, area: Rect) {
+    let mut help_spans = vec![
+        Span::styled("q: quit", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("?: help", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("p: profiles", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("j/k/↑/↓: nav keys/vals", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("PgUp/PgDn: page nav vals", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("Tab/S-Tab: focus", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("1/2/3: jump", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("Enter: select", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("Esc: up/root", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("y: copy name", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("Y: copy val", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("/: search", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("d: del", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("s: stats", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("x: hex", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled(format!("D: decoder ({})", app.value_decoder.label()), Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled(
+            if app.value_viewer.wrap_disabled { "W: wrap (off)" } else { "W: wrap" },
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(" | "),
+        Span::styled("#: line numbers", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("G: goto line", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled(
+            if app.watch.enabled { "w: watch (on)" } else { "w: watch" },
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(" | "),
+        Span::styled("f: follow stream", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("C: consumer groups", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("o: sort", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("T: timestamps", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("i: key info", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled(
+            if app.key_sizes.enabled { "z: sizes (on)" } else { "z: sizes" },
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw(" | "),
+        Span::styled("M: scan pattern", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("Ctrl+G: go to key", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("m: bookmark", Style::default().fg(Color::Yellow)),
+        Span::raw(" | "),
+        Span::styled("b: bookmarks", Style::default().fg(Color::Yellow)),
+    ];
+
+    if app.search_state.is_active {
+        help_spans.extend(vec![
+            Span::raw(" | "),
+            Span::styled("Esc: exit search", Style::default().fg(Color::Cyan)),
+            Span::raw(" | "),
+            Span::styled(
+                if app.search_state.needs_server_search() { "Enter: search server" } else { "Enter: activate" },
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                match app.search_state.mode {
+                    SearchMode::Fuzzy => "Tab: regex search",
+                    SearchMode::Regex => "Tab: server search",
+                    SearchMode::Server => "Tab: value search",
+                    SearchMode::Values => "Tab: fuzzy search",
+                },
+                Style::default().fg(Color::Cyan),
+            ),
+        ]);
+    } else if app.delete_dialog.show_confirmation_dialog {
+        help_spans = vec![
+            Span::styled("Confirm Deletion: ", Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD)),
+            Span::styled("[Y]es", Style::default().fg(Color::Green)),
+            Span::raw(" / "),
+            Span::styled("[N]o (Esc)", Style::default().fg(Color::Red)),
+        ];
+    } else if !app.command_state.is_active {
+        help_spans.extend(vec![
+            Span::raw(" | "),
+            Span::styled(":: cmd", Style::default().fg(Color::Cyan)),
+        ]);
+    }
+
+
+    let help_line = Line::from(help_spans);
+    let footer_paragraph = Paragraph::new(help_line)
+        .block(Block::default())
+        .alignment(Alignment::Center);
+    f.render_widget(footer_paragraph, area);
+}
+
+/// Folder rows listed in a delete preview before the rest are summed up.
+const DELETE_PREVIEW_GROUPS: usize = 8;
+
+fn draw_delete_confirmation_dialog(f: &mut Frame, app: &App) {
+    let preview = app.delete_dialog.preview.as_ref();
+    let area = centered_rect(60, if preview.is_some() { 55 } else { 25 }, f.area());
+    f.render_widget(Clear, area); // Clear the background
+
+    let item_type = if app.delete_dialog.deletion_is_folder { "folder" } else { "key" };
+    let item_name = app
+        .delete_dialog
+        .key_to_delete_display_name
+        .as_deref()
+        .unwrap_or("unknown");
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            format!("Delete {} '{}'?", item_type, item_name),
+            Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD)
+        )).alignment(Alignment::Center),
+        Line::from("").alignment(Alignment::Center),
+        if app.delete_dialog.deletion_is_folder {
+            Line::from(Span::raw(format!("This will delete the folder and ALL keys within prefix '{}'.", app.delete_dialog.prefix_to_delete.as_deref().unwrap_or("N/A")))).alignment(Alignment::Center)
+        } else {
+            Line::from(Span::raw(format!("This will permanently delete the key '{}'.", app.delete_dialog.key_to_delete_full_path.as_deref().unwrap_or(item_name)))).alignment(Alignment::Center)
+        },
+        Line::from("").alignment(Alignment::Center),
+        Line::from(Span::raw("This action CANNOT be undone.")).alignment(Alignment::Center),
+        Line::from("").alignment(Alignment::Center),
+    ];
+    if let Some(preview) = preview {
+        let total = format_count(preview.total);
+        let heading = if app.delete_preview_running() {
+            format!("Dry run: {} key(s) so far, scanning... (Esc: stop)", total)
+        } else if preview.stopped {
+            format!("Dry run stopped: at least {} key(s) would be deleted", total)
+        } else {
+            format!("Dry run: {} key(s) would be deleted", total)
+        };
+        text.push(
+            Line::from(Span::styled(
+                heading,
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ))
+            .alignment(Alignment::Center),
+        );
+        text.extend(preview.lines(DELETE_PREVIEW_GROUPS).into_iter().map(|line| Line::from(format!("  {}", line))));
+        text.push(Line::from(""));
+    }
+    let mut choices = vec![
+        Span::raw("Press "),
+        Span::styled("[Y]es", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+        Span::raw(" or "),
+        Span::styled("[N]o (Esc)", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+    ];
+    if app.delete_can_preview() && preview.is_none() {
+        choices.push(Span::raw(", "));
+        choices.push(Span::styled("[P]review", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    }
+    text.push(Line::from(choices).alignment(Alignment::Center));
+
+    let block = Block::default()
+        .title("Confirm Deletion")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false });
+
+    f.render_widget(paragraph, area);
+}
+
+fn draw_profile_selector_modal(f: &mut Frame, app: &App) {
+    // Define a centered area for the modal, e.g., 60% width, 50% height
+    let area = centered_rect(60, 50, f.area());
+    f.render_widget(Clear, area); // Clear the background
+
+    let profiles: Vec<ListItem> = app
+        .profiles
+        .iter()
+        .enumerate()
+        .map(|(idx, profile)| {
+            let item_color = profile.resolved_color();
+            let style = if idx == app.profile_state.selected_index {
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(item_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(item_color)
+            };
+            ListItem::new(format!("{} ({})", profile.name, profile.url)).style(style)
+        })
+        .collect();
+
+    let list_widget = List::new(profiles)
+        .block(Block::default().borders(Borders::ALL).title("Select Connection Profile (p/Esc to close)"))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+    
+    let mut list_state = ListState::default();
+    list_state.select(Some(app.profile_state.selected_index));
+
+    f.render_stateful_widget(list_widget, area, &mut list_state);
+}
+
+fn draw_command_prompt_modal(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 50, f.area());
+    f.render_widget(Clear, area);
+
+    // Each line of a multi-command input gets its own prompt; the first is "CMD> "
+    let command = &app.command_state;
+    let input_lines: Vec<&str> = command.input_buffer.split('\n').collect();
+    let before_cursor = &command.input_buffer[..command.cursor.min(command.input_buffer.len())];
+    let cursor_row = before_cursor.matches('\n').count() as u16;
+    let cursor_col = before_cursor.rsplit('\n').next().unwrap_or("").chars().count() as u16;
+    // Border + prompt width + text before the cursor on its line
+    let raw_cursor_x = area.x + 6 + cursor_col;
+    let max_cursor_x = area.x + area.width.saturating_sub(1);
+    let cursor_x = raw_cursor_x.min(max_cursor_x);
+    let cursor_y = area.y + 3 + cursor_row;
+
+    // Only set cursor if the command prompt is active and focused (implicitly handled by modal display)
+    f.set_cursor_position(Position::new(cursor_x, cursor_y));
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            "Custom Command Prompt - use at your own risk!",
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        ))
+        .alignment(Alignment::Center),
+        Line::from("").alignment(Alignment::Center),
+    ];
+    text.extend(input_lines.iter().enumerate().map(|(idx, line)| {
+        let prompt = if idx == 0 { "CMD> " } else { "...> " };
+        Line::from(format!("{}{}", prompt, line))
+    }));
+    text.push(Line::from(Span::styled(
+        app.command_state.hint().unwrap_or_default(),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Command Prompt (: to open, Tab to complete, ; or Alt+Enter for pipelines, Esc to close)");
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(text.len() as u16), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), sections[0]);
+
+    let output = app.command_state.last_result.as_deref().unwrap_or("");
+    let total_lines = output.lines().count();
+    let scroll = app.command_state.result_scroll.min(total_lines.saturating_sub(1));
+    let mode = if app.command_state.raw_output { "raw RESP" } else { "Ctrl+R: raw RESP" };
+    let result_title = if total_lines > sections[1].height as usize {
+        format!(
+            " Result [{}]: line {}/{} (Up/Down/PgUp/PgDn, Ctrl+Y: copy, Ctrl+S: save) ",
+            mode,
+            scroll + 1,
+            total_lines
+        )
+    } else {
+        format!(" Result [{}] (Ctrl+Y: copy, Ctrl+S: save) ", mode)
+    };
+    let result = Paragraph::new(output.lines().map(Line::from).collect::<Vec<_>>())
+        .block(Block::default().borders(Borders::TOP).title(result_title))
+        .scroll((scroll as u16, 0));
+    f.render_widget(result, sections[1]);
+}
+
+fn draw_redis_stats_panel(f: &mut Frame, app: &App, area: Rect) {
+    let title = if app.stats_auto_refresh {
+        "4: Redis Stats [Auto] (s: toggle, R: doctor report, I: report)"
+    } else {
+        "4: Redis Stats [Manual] (s: toggle, R: doctor report, I: report)"
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    if let Some(stats) = &app.redis_stats {
+        // Split the area into sections for different stat categories
+        let inner_area = block.inner(area);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(6),  // Server info
+                Constraint::Length(8),  // Memory stats
+                Constraint::Length(6),  // Client stats
+                Constraint::Length(6),  // Performance stats
+                Constraint::Length(OperationClass::ALL.len() as u16 + 2), // Client-side latency
+                Constraint::Min(0),     // Replication
+            ])
+            .split(inner_area);
+
+        // Server Information Section
+        let server_info = vec![
+            Line::from(vec![
+                Span::styled("Server: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("Redis {} ({})", stats.redis_version, stats.redis_mode)),
+            ]),
+            Line::from(vec![
+                Span::styled("Role: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} ({} slaves)", stats.role, stats.connected_slaves)),
+            ]),
+            Line::from(vec![
+                Span::styled("Uptime: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(&stats.uptime_human),
+            ]),
+            Line::from(vec![
+                Span::styled("Updated: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{:.1}s ago", stats.age().as_secs_f64())),
+            ]),
+        ];
+
+        let server_paragraph = Paragraph::new(server_info)
+            .block(Block::default().borders(Borders::ALL).title("Server").border_style(Style::default().fg(Color::Green)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(server_paragraph, sections[0]);
+
+        // Memory Section with btop-style bars
+        let memory_usage_ratio = if stats.memory_peak > 0 {
+            (stats.memory_used as f64 / stats.memory_peak as f64).min(1.0)
+        } else {
+            0.0
+        };
+
+        let memory_gauge = Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title("Memory Usage").border_style(Style::default().fg(Color::Red)))
+            .gauge_style(Style::default().fg(Color::Red).bg(Color::Black))
+            .ratio(memory_usage_ratio)
+            .label(format!("{} / {} ({:.1}%)", 
+                stats.memory_used_human, 
+                stats.memory_peak_human,
+                memory_usage_ratio * 100.0
+            ));
+        f.render_widget(memory_gauge, sections[1]);
+
+        // Client Stats
+        let client_info = vec![
+            Line::from(vec![
+                Span::styled("Connected: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(stats.connected_clients.to_string(), Style::default().fg(Color::Green)),
+            ]),
+            Line::from(vec![
+                Span::styled("Blocked: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(stats.blocked_clients.to_string(), Style::default().fg(Color::Red)),
+            ]),
+            Line::from(vec![
+                Span::styled("Hit Rate: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(format!("{:.1}%", stats.hit_rate), 
+                    if stats.hit_rate > 90.0 { Style::default().fg(Color::Green) } 
+                    else if stats.hit_rate > 70.0 { Style::default().fg(Color::Yellow) } 
+                    else { Style::default().fg(Color::Red) }
+                ),
+            ]),
+        ];
+
+        let client_paragraph = Paragraph::new(client_info)
+            .block(Block::default().borders(Borders::ALL).title("Clients").border_style(Style::default().fg(Color::Blue)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(client_paragraph, sections[2]);
+
+        // Performance Stats
+        let perf_info = vec![
+            Line::from(vec![
+                Span::styled("Ops/sec: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::styled(stats.instantaneous_ops_per_sec.to_string(), 
+                    if stats.instantaneous_ops_per_sec > 1000 { Style::default().fg(Color::Green) }
+                    else if stats.instantaneous_ops_per_sec > 100 { Style::default().fg(Color::Yellow) }
+                    else { Style::default().fg(Color::White) }
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled("Total Cmds: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format_large_number(stats.total_commands_processed)),
+            ]),
+            Line::from(vec![
+                Span::styled("CPU: ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("sys:{:.2} usr:{:.2}", stats.used_cpu_sys, stats.used_cpu_user)),
+            ]),
+        ];
+
+        let perf_paragraph = Paragraph::new(perf_info)
+            .block(Block::default().borders(Borders::ALL).title("Performance").border_style(Style::default().fg(Color::Magenta)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(perf_paragraph, sections[3]);
+
+        // Client-side latency per operation class
+        let latency_info: Vec<Line> = OperationClass::ALL
+            .iter()
+            .map(|class| {
+                let label = Span::styled(
+                    format!("{:<11}", class.label()),
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                );
+                match app.latency.summary(*class) {
+                    Some(summary) => Line::from(vec![
+                        label,
+                        Span::raw("p50 "),
+                        Span::styled(format_latency(summary.p50), Style::default().fg(latency_color(summary.p50))),
+                        Span::raw(" p99 "),
+                        Span::styled(format_latency(summary.p99), Style::default().fg(latency_color(summary.p99))),
+                        Span::styled(format!(" n={}", summary.samples), Style::default().fg(Color::DarkGray)),
+                    ]),
+                    None => Line::from(vec![label, Span::styled("-", Style::default().fg(Color::DarkGray))]),
+                }
+            })
+            .collect();
+
+        let latency_paragraph = Paragraph::new(latency_info)
+            .block(Block::default().borders(Borders::ALL).title("Client Latency").border_style(Style::default().fg(Color::LightBlue)));
+        f.render_widget(latency_paragraph, sections[4]);
+
+        replication::draw_replication_section(f, &stats.replication, sections[5]);
+
+    } else {
+        // No stats available
+        let loading_text = vec![
+            Line::from(""),
+            Line::from(Span::styled("Loading Redis stats...", Style::default().fg(Color::Yellow))).alignment(Alignment::Center),
+            Line::from(""),
+            Line::from(Span::raw("Press 's' to toggle stats view")).alignment(Alignment::Center),
+        ];
+
+        let loading_paragraph = Paragraph::new(loading_text)
+            .block(block)
+            .wrap(Wrap { trim: true });
+        f.render_widget(loading_paragraph, area);
+        return;
+    }
+
+    f.render_widget(block, area);
+}
+
+fn format_latency(latency: Duration) -> String {
+    let micros = latency.as_micros();
+    if micros >= 1_000_000 {
+        format!("{:.2}s", latency.as_secs_f64())
+    } else if micros >= 1_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{}µs", micros)
+    }
+}
+
+/// Heat color for a latency sample: green is healthy, red deserves a look.
+fn latency_color(latency: Duration) -> Color {
+    if latency < Duration::from_millis(5) {
+        Color::Green
+    } else if latency < Duration::from_millis(50) {
+        Color::Yellow
+    } else {
+        Color::Red
+    }
+}
+
+/// Integer with thousands separators, e.g. `1,204`.
+fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (idx, ch) in digits.chars().enumerate() {
+        if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn format_large_number(num: u64) -> String {
+    if num >= 1_000_000_000 {
+        format!("{:.1}B", num as f64 / 1_000_000_000.0)
+    } else if num >= 1_000_000 {
+        format!("{:.1}M", num as f64 / 1_000_000.0)
+    } else if num >= 1_000 {
+        format!("{:.1}K", num as f64 / 1_000.0)
+    } else {
+        num.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compact_ttl, db_label, db_size_label, format_count, format_latency, format_ttl, ttl_is_expiring};
+    use crate::app::redis_stats::KeyspaceCounts;
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    #[test]
+    fn db_label_shows_keyspace_counts() {
+        let counts = HashMap::from([
+            (3, KeyspaceCounts { keys: 12_405, expires: 301 }),
+            (4, KeyspaceCounts { keys: 2, expires: 0 }),
+        ]);
+        assert_eq!(db_label(3, Some(&counts)), "DB 3 (12,405 keys, 301 expiring)");
+        assert_eq!(db_label(4, Some(&counts)), "DB 4 (2 keys)");
+        assert_eq!(db_label(5, Some(&counts)), "DB 5 (empty)");
+        assert_eq!(db_label(5, None), "DB 5");
+    }
+
+    #[test]
+    fn db_size_label_marks_running_scan() {
+        assert_eq!(db_size_label(2, 1_532_884, true), "DB 2 — 1,532,884 keys (scanning…)");
+        assert_eq!(db_size_label(0, 7, false), "DB 0 — 7 keys");
+    }
+
+    #[test]
+    fn format_ttl_handles_missing() {
+        assert_eq!(format_ttl(-2), "Key missing");
+    }
+
+    #[test]
+    fn format_ttl_handles_no_expiry() {
+        assert_eq!(format_ttl(-1), "No Expiry");
+    }
+
+    #[test]
+    fn format_ttl_handles_positive() {
+        assert_eq!(format_ttl(75), "Expires in 1m 15s");
+    }
+
+    #[test]
+    fn compact_ttl_keeps_the_largest_unit() {
+        assert_eq!(compact_ttl(0), "0s");
+        assert_eq!(compact_ttl(59), "59s");
+        assert_eq!(compact_ttl(60), "1m");
+        assert_eq!(compact_ttl(7_199), "1h");
+        assert_eq!(compact_ttl(3 * 86_400 + 5), "3d");
+    }
+
+    #[test]
+    fn ttl_is_expiring_below_the_threshold() {
+        assert!(ttl_is_expiring(59, 60));
+        assert!(ttl_is_expiring(0, 60));
+        assert!(!ttl_is_expiring(60, 60));
+        assert!(!ttl_is_expiring(-1, 60));
+        assert!(!ttl_is_expiring(5, 0));
+    }
+
+    #[test]
+    fn format_latency_picks_unit() {
+        assert_eq!(format_latency(Duration::from_micros(250)), "250µs");
+        assert_eq!(format_latency(Duration::from_micros(2_500)), "2.5ms");
+        assert_eq!(format_latency(Duration::from_millis(1_500)), "1.50s");
+    }
+
+    #[test]
+    fn format_count_groups_thousands() {
+        assert_eq!(format_count(7), "7");
+        assert_eq!(format_count(1204), "1,204");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_monitor::{MonitorState, MAX_MONITOR_ENTRIES};
+use crate::app::timestamp::format_clock;
+
+const WARNING: &str = "MONITOR streams every command the server runs and can cut its throughput \
+by half or more. Avoid it on busy production servers and stop it as soon as you are done.";
+
+pub(super) fn draw_monitor_modal(f: &mut Frame, state: &MonitorState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let mut title = "MONITOR".to_string();
+    match &state.session {
+        Some(_) if state.is_paused() => title.push_str(" [paused]"),
+        Some(_) => title.push_str(" [running]"),
+        None => title.push_str(" [stopped]"),
+    }
+    if !state.client_filter.is_empty() {
+        title.push_str(&format!(" [client: {}]", state.client_filter));
+    }
+    if !state.command_filter.is_empty() {
+        title.push_str(&format!(" [command: {}]", state.command_filter));
+    }
+    title.push_str(" (Enter: start/stop, p: pause, a: client filter, /: command filter, c: clear, Esc: stop and close)");
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Red));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(3), Constraint::Length(3)])
+        .split(inner);
+
+    let warning = Paragraph::new(WARNING)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .wrap(Wrap { trim: true });
+    f.render_widget(warning, rows[0]);
+
+    let entries: Vec<_> = state.visible_entries().collect();
+    let height = rows[1].height.saturating_sub(2) as usize;
+    let end = entries.len().saturating_sub(state.scroll);
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = entries[start..end]
+        .iter()
+        .map(|entry| {
+            Line::from(vec![
+                Span::styled(format_clock(entry.timestamp), Style::default().fg(Color::DarkGray)),
+                Span::raw(" "),
+                Span::styled(format!("[{} {}]", entry.db, entry.client), Style::default().fg(Color::Cyan)),
+                Span::raw(" "),
+                Span::raw(entry.arguments.clone()),
+            ])
+        })
+        .collect();
+    let position = if state.scroll == 0 {
+        "following".to_string()
+    } else {
+        format!("{} newer below", state.scroll)
+    };
+    let list = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(format!(
+        "Commands {} of last {} ({})",
+        entries.len(),
+        MAX_MONITOR_ENTRIES,
+        position
+    )));
+    f.render_widget(list, rows[1]);
+
+    let (title, border) = match state.prompt {
+        Some(prompt) => (prompt.label(), Color::Yellow),
+        None => ("Filter", Color::DarkGray),
+    };
+    let input = Paragraph::new(state.input.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(input, rows[2]);
+    if state.prompt.is_some() {
+        let col = state.input[..state.cursor.min(state.input.len())].chars().count() as u16;
+        let x = (rows[2].x + 1 + col).min(rows[2].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[2].y + 1));
+    }
+}
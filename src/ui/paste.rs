@@ -0,0 +1,40 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_paste::PastePreviewState;
+
+pub(super) fn draw_paste_preview_modal(f: &mut Frame, state: &PastePreviewState) {
+    let area = centered_rect(80, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let header = match &state.existing_type {
+        Some(key_type) => Line::styled(
+            format!("Overwrites the existing {} key '{}'", key_type, state.key),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ),
+        None => Line::styled(
+            format!("Creates the new key '{}'", state.key),
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        ),
+    };
+    let mut lines = vec![header, Line::from("")];
+    lines.extend(state.lines.iter().skip(state.scroll).map(|line| Line::from(line.as_str())));
+    let border = if state.existing_type.is_some() { Color::Red } else { Color::Yellow };
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    "Paste from clipboard: {} command(s) (Enter/y: run, j/k: scroll, Esc: cancel)",
+                    state.commands.len()
+                ))
+                .border_style(Style::default().fg(border)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_pubsub::PubSubState;
+use crate::app::timestamp::format_clock;
+
+pub(super) fn draw_pubsub_modal(f: &mut Frame, state: &PubSubState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let subscriptions = state.listener.as_ref().map_or_else(
+        || "not subscribed".to_string(),
+        |listener| [listener.channels.as_slice(), listener.patterns.as_slice()].concat().join(" "),
+    );
+    let mut title = format!("Pub/Sub [{}]", subscriptions);
+    if state.is_paused() {
+        title.push_str(" [paused]");
+    }
+    if !state.filter.is_empty() {
+        title.push_str(&format!(" [filter: {}]", state.filter));
+    }
+    title.push_str(" (s: subscribe, /: filter, p: pause, c: clear, x: unsubscribe, Esc: close)");
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(inner);
+
+    let messages: Vec<_> = state.visible_messages().collect();
+    let height = rows[0].height.saturating_sub(2) as usize;
+    let end = messages.len().saturating_sub(state.scroll);
+    let start = end.saturating_sub(height);
+    let lines: Vec<Line> = messages[start..end]
+        .iter()
+        .map(|message| {
+            let mut spans = vec![
+                Span::styled(format_clock(message.received_at), Style::default().fg(Color::DarkGray)),
+                Span::raw(" "),
+                Span::styled(message.channel.clone(), Style::default().fg(Color::Yellow)),
+            ];
+            if let Some(pattern) = &message.pattern {
+                spans.push(Span::styled(format!(" ({})", pattern), Style::default().fg(Color::DarkGray)));
+            }
+            spans.push(Span::raw(format!(" {}", message.payload)));
+            Line::from(spans)
+        })
+        .collect();
+    let position = if state.scroll == 0 {
+        "following".to_string()
+    } else {
+        format!("{} newer below", state.scroll)
+    };
+    let list = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Messages {} ({})", messages.len(), position)),
+    );
+    f.render_widget(list, rows[0]);
+
+    let (title, border) = match state.prompt {
+        Some(prompt) => (prompt.label(), Color::Yellow),
+        None => ("Input", Color::DarkGray),
+    };
+    let input = Paragraph::new(state.input.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(input, rows[1]);
+    if state.prompt.is_some() {
+        let col = state.input[..state.cursor.min(state.input.len())].chars().count() as u16;
+        let x = (rows[1].x + 1 + col).min(rows[1].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[1].y + 1));
+    }
+}
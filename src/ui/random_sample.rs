@@ -0,0 +1,59 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::analysis::{highlighted_list, key_sample_spans, key_width};
+use super::{centered_rect, format_ttl};
+use crate::app::state_random_sample::RandomSampleState;
+
+pub(super) fn draw_random_sample_modal(f: &mut Frame, state: &RandomSampleState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let progress = if state.loading {
+        "sampling...".to_string()
+    } else {
+        format!("{} distinct keys", state.samples.len())
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Random keys [{} RANDOMKEY, {}] (Enter: go to key, r: resample, +/-: sample size, Esc: close)",
+            state.size, progress
+        ))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let width = key_width(state.samples.iter().map(|sample| sample.key.as_str()));
+    let items: Vec<ListItem> = state
+        .samples
+        .iter()
+        .map(|sample| {
+            let mut spans = key_sample_spans(sample, width);
+            if let Some(ttl) = sample.ttl {
+                spans.push(Span::styled(format!("  {}", format_ttl(ttl as i64)), Style::default().fg(Color::Blue)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(state.selected));
+    }
+    let list = highlighted_list(items, "Type / key / MEMORY USAGE / length / TTL".to_string());
+    f.render_stateful_widget(list, rows[0], &mut list_state);
+
+    if let Some(error) = &state.error {
+        f.render_widget(Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)), rows[1]);
+    }
+}
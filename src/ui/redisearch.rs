@@ -0,0 +1,147 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_redisearch::RediSearchState;
+
+fn pad_cells(cells: &[String], widths: &[usize]) -> String {
+    cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+        .collect::<Vec<_>>()
+        .join("  ")
+}
+
+fn focus_border(focused: bool) -> Style {
+    Style::default().fg(if focused { Color::Cyan } else { Color::DarkGray })
+}
+
+pub(super) fn draw_redisearch_modal(f: &mut Frame, state: &RediSearchState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title("RediSearch (j/k: select, s: FT.SEARCH, a: FT.AGGREGATE, Tab: focus, n/N: page, Enter: open document, r: refresh, Esc: close)")
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(30), Constraint::Length(3), Constraint::Min(5)])
+        .split(inner);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(rows[0]);
+
+    let indexes: Vec<ListItem> = state.indexes.iter().map(|name| ListItem::new(name.as_str())).collect();
+    let has_indexes = !indexes.is_empty();
+    let list = List::new(indexes)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Indexes")
+                .border_style(focus_border(!state.results_focused)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    if has_indexes {
+        list_state.select(Some(state.selected_index));
+    }
+    f.render_stateful_widget(list, top[0], &mut list_state);
+
+    let mut info_lines = Vec::new();
+    if let Some(info) = &state.info {
+        info_lines.push(Line::from(format!("Documents: {}  Type: {}", info.num_docs, info.key_type)));
+        info_lines.push(Line::from(format!("Prefixes: {}", info.prefixes.join(", "))));
+        for (attribute, kind) in &info.attributes {
+            info_lines.push(Line::from(format!("  {:<24} {}", attribute, kind)));
+        }
+    }
+    let info = Paragraph::new(info_lines)
+        .block(Block::default().borders(Borders::ALL).title("FT.INFO"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(info, top[1]);
+
+    let (title, border) = if state.editing {
+        (state.mode.label(), Color::Yellow)
+    } else {
+        ("Query (s or a to edit)", Color::DarkGray)
+    };
+    let shown = match (&state.query, state.editing) {
+        (Some((_, query)), false) => query.as_str(),
+        _ => state.input.as_str(),
+    };
+    let input = Paragraph::new(shown).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(border)),
+    );
+    f.render_widget(input, rows[1]);
+    if state.editing {
+        let col = state.input[..state.cursor.min(state.input.len())].chars().count() as u16;
+        let x = (rows[1].x + 1 + col).min(rows[1].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[1].y + 1));
+    }
+
+    let results_block = |title: String| {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(focus_border(state.results_focused))
+    };
+    let Some(results) = state.results.as_ref().filter(|results| !results.rows.is_empty()) else {
+        let message = state.message.as_deref().unwrap_or("");
+        let paragraph = Paragraph::new(message.lines().map(Line::from).collect::<Vec<_>>())
+            .block(results_block("Results".to_string()))
+            .wrap(Wrap { trim: false });
+        f.render_widget(paragraph, rows[2]);
+        return;
+    };
+
+    let (columns, widths, cells) = results.table();
+    let first = state.offset + 1;
+    let last = state.offset + results.rows.len();
+    let command = state.query.as_ref().map_or("", |(mode, _)| mode.command());
+    let more = if state.has_next_page() { ", n: next page" } else { "" };
+    let title = format!("{} {}-{} of {}{}", command, first, last, results.total, more);
+    let block = results_block(title);
+    let table_area = block.inner(rows[2]);
+    f.render_widget(block, rows[2]);
+
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(1)])
+        .split(table_area);
+    let header = Paragraph::new(format!("   {}", pad_cells(&columns, &widths)))
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    f.render_widget(header, parts[0]);
+
+    let items: Vec<ListItem> = cells.iter().map(|row| ListItem::new(pad_cells(row, &widths))).collect();
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected_row));
+    f.render_stateful_widget(list, parts[1], &mut list_state);
+}
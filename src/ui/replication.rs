@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::redis_stats::format_bytes;
+use crate::app::replication::ReplicationInfo;
+
+/// Master/replica topology with link state and per-replica lag.
+pub(super) fn draw_replication_section(f: &mut Frame, replication: &ReplicationInfo, area: Rect) {
+    let label = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+
+    if let Some(master) = &replication.master {
+        let (status, color) = if master.sync_in_progress {
+            ("syncing".to_string(), Color::Yellow)
+        } else if master.is_up() {
+            match master.last_io_seconds_ago {
+                Some(seconds) if seconds >= 0 => (format!("up, last IO {}s ago", seconds), Color::Green),
+                _ => ("up".to_string(), Color::Green),
+            }
+        } else {
+            match master.link_down_since_seconds {
+                Some(seconds) => (format!("{} for {}s", master.link_status, seconds), Color::Red),
+                None => (master.link_status.clone(), Color::Red),
+            }
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Master: ", label),
+            Span::raw(format!("{}:{} ", master.host, master.port)),
+            Span::styled(status, Style::default().fg(color)),
+        ]));
+    }
+
+    lines.push(Line::from(vec![
+        Span::styled(if replication.is_replica() { "This replica: " } else { "This server: " }, label),
+        Span::raw(format!("{} offset {}", replication.role, replication.master_repl_offset)),
+    ]));
+
+    if replication.replicas.is_empty() {
+        lines.push(Line::from(Span::styled("No connected replicas", Style::default().fg(Color::DarkGray))));
+    }
+    for replica in &replication.replicas {
+        let behind = replication.offset_lag(replica);
+        let state_color = if replica.is_online() { Color::Green } else { Color::Yellow };
+        let lag_color = if replica.lag > 10 || behind > 1024 * 1024 {
+            Color::Red
+        } else if replica.lag > 1 || behind > 0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        lines.push(Line::from(vec![
+            Span::raw(format!(" └ {}:{} ", replica.ip, replica.port)),
+            Span::styled(replica.state.clone(), Style::default().fg(state_color)),
+            Span::styled(
+                format!(" lag {}s, {} behind", replica.lag, format_bytes(behind)),
+                Style::default().fg(lag_color),
+            ),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Replication")
+            .border_style(Style::default().fg(Color::LightMagenta)),
+    );
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,328 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+};
+use std::ops::Range;
+
+use super::{compact_ttl, format_count, ttl_is_expiring};
+use crate::app::redis_stats::format_bytes;
+use crate::app::value_viewer::ValueViewer;
+use crate::app::App;
+
+/// Key list rows around the viewport, with the index of the selected row, the row
+/// count and the range the items cover.
+pub(super) type KeyItems = (Vec<ListItem<'static>>, usize, usize, Range<usize>);
+
+/// Rows of the search results around the viewport, with matched characters highlighted
+/// and the value snippet of full-text matches after the key.
+pub(super) fn search_key_items(app: &App, row_width: usize, rows: usize) -> KeyItems {
+    let keys = &app.search_state.filtered_keys;
+    let selected = app.search_state.selected_index;
+    let window = app.key_list_scroll.window(selected, keys.len(), rows);
+    let items = keys[window.clone()]
+        .iter()
+        .map(|full_key_name| {
+            let label = match app.search_state.snippets.get(full_key_name) {
+                Some(snippet) => format!("{} — {}", full_key_name, snippet),
+                None => full_key_name.clone(),
+            };
+            let matched = app
+                .search_state
+                .match_positions
+                .get(full_key_name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            ListItem::new(key_row_line(label, matched, Some(full_key_name), app, row_width))
+        })
+        .collect();
+    (items, selected, keys.len(), window)
+}
+
+/// Rows of the current level around the viewport: folders with their key count, and
+/// marked rows in bold cyan.
+pub(super) fn flat_key_items(app: &App, row_width: usize, rows: usize) -> KeyItems {
+    let keys = &app.visible_keys_in_current_view;
+    let selected = app.selected_visible_key_index;
+    let window = app.key_list_scroll.window(selected, keys.len(), rows);
+    let items = keys[window.clone()]
+        .iter()
+        .zip(window.clone())
+        .map(|((name, is_folder), index)| {
+            let label = if *is_folder {
+                let count = app.folder_key_count(name.trim_end_matches('/'));
+                format!("{} ({})", name, format_count(count))
+            } else {
+                name.clone()
+            };
+            let display_name = if app.selected_indices.contains(&index) {
+                format!("● {}", label)
+            } else {
+                label
+            };
+            let full_name = if *is_folder { None } else { app.leaf_full_name(name) };
+            let item = ListItem::new(key_row_line(display_name, &[], full_name.as_ref(), app, row_width));
+            if app.selected_indices.contains(&index) {
+                item.style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            } else {
+                item
+            }
+        })
+        .collect();
+    (items, selected, keys.len(), window)
+}
+
+/// Rows of the expandable tree around the viewport, indented by depth, with the index of
+/// the selected row, the row count and the range the items cover. Marks belong to the
+/// current level, so only its rows can show them.
+pub(super) fn tree_key_items(app: &App, row_width: usize, rows: usize) -> KeyItems {
+    let tree_rows = app.tree_rows();
+    let selected = app.tree_selected_row(&tree_rows);
+    let window = app.key_list_scroll.window(selected, tree_rows.len(), rows);
+    let items = tree_rows[window.clone()]
+        .iter()
+        .map(|row| {
+            let indent = "  ".repeat(row.depth());
+            let marked = row.parents == app.current_breadcrumb
+                && app
+                    .visible_keys_in_current_view
+                    .iter()
+                    .position(|(name, _)| *name == row.name)
+                    .is_some_and(|index| app.selected_indices.contains(&index));
+            let mark = if marked { "● " } else { "" };
+            let (label, full_name) = if row.is_folder {
+                let marker = if row.expanded { "▾" } else { "▸" };
+                let count = format_count(app.tree_folder_key_count(row));
+                (format!("{}{}{} {} ({})", indent, mark, marker, row.name, count), None)
+            } else {
+                (format!("{}{}  {}", indent, mark, row.name), app.tree_leaf_full_name(row))
+            };
+            let item = ListItem::new(key_row_line(label, &[], full_name.as_ref(), app, row_width));
+            if marked {
+                item.style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            } else {
+                item
+            }
+        })
+        .collect();
+    (items, selected, tree_rows.len(), window)
+}
+
+/// Builds a key list row: a type badge for leaves whose type is known, the
+/// label, and right-aligned the TTL countdown of expiring keys and a `MEMORY USAGE`
+/// figure when the size column is on.
+fn key_row_line(
+    label: String,
+    matched: &[usize],
+    full_key_name: Option<&String>,
+    app: &App,
+    width: usize,
+) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut used = label.chars().count();
+    if let Some(key_type) = full_key_name.and_then(|key| app.type_map.get(key)) {
+        let (badge, color) = type_badge(key_type);
+        spans.push(Span::styled(
+            format!("{} ", badge),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        used += badge.len() + 1;
+    }
+    spans.extend(highlight_chars(label, matched));
+
+    let mut trailing: Vec<(String, Color)> = Vec::new();
+    if let Some(ttl) = full_key_name.and_then(|key| app.listed_ttl(key)).filter(|ttl| *ttl >= 0) {
+        let color = if ttl_is_expiring(ttl, app.settings.ttl_warning_secs) { Color::Red } else { Color::DarkGray };
+        trailing.push((compact_ttl(ttl), color));
+    }
+    let size = full_key_name
+        .filter(|_| app.key_sizes.enabled)
+        .and_then(|key| app.key_sizes.get(key));
+    if let Some(bytes) = size {
+        let color = if bytes >= 1024 * 1024 {
+            Color::Red
+        } else if bytes >= 100 * 1024 {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        };
+        trailing.push((format_bytes(bytes), color));
+    }
+    if !trailing.is_empty() {
+        let trailing_width = trailing.iter().map(|(text, _)| text.chars().count()).sum::<usize>() + trailing.len() - 1;
+        spans.push(Span::raw(" ".repeat(width.saturating_sub(used + trailing_width).max(1))));
+        for (idx, (text, color)) in trailing.into_iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(text, Style::default().fg(color)));
+        }
+    }
+    Line::from(spans)
+}
+
+/// Splits `text` into spans with the characters at `matched` positions highlighted.
+fn highlight_chars(text: String, matched: &[usize]) -> Vec<Span<'static>> {
+    if matched.is_empty() {
+        return vec![Span::raw(text)];
+    }
+    let highlight = Style::default().fg(Color::LightRed).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+    for (idx, ch) in text.chars().enumerate() {
+        let is_matched = matched.contains(&idx);
+        if is_matched != run_matched && !run.is_empty() {
+            let style = if run_matched { highlight } else { Style::default() };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_matched = is_matched;
+        run.push(ch);
+    }
+    let style = if run_matched { highlight } else { Style::default() };
+    spans.push(Span::styled(run, style));
+    spans
+}
+
+/// Three-letter badge and colour for a Redis `TYPE` reply.
+fn type_badge(key_type: &str) -> (&'static str, Color) {
+    match key_type.to_lowercase().as_str() {
+        "string" => ("STR", Color::Green),
+        "hash" => ("HSH", Color::Magenta),
+        "list" => ("LST", Color::Blue),
+        "set" => ("SET", Color::Cyan),
+        "zset" => ("ZST", Color::Yellow),
+        "stream" => ("STM", Color::Red),
+        "rejson-rl" | "json" => ("JSN", Color::LightGreen),
+        "mbbloom--" => ("BLM", Color::LightBlue),
+        "mbbloomcf" => ("CKO", Color::LightBlue),
+        "topk-type" => ("TPK", Color::LightBlue),
+        "tdis-type" => ("TDG", Color::LightBlue),
+        _ => ("???", Color::DarkGray),
+    }
+}
+
+/// Rows of a collection value inside `window`, tinted when they changed on the last
+/// watch refresh.
+pub(super) fn value_items<'a>(
+    app: &App,
+    lines: &'a [String],
+    window: Range<usize>,
+    wrap_width: usize,
+) -> Vec<ListItem<'a>> {
+    lines[window.clone()]
+        .iter()
+        .zip(window)
+        .map(|(line, row)| {
+            let item = value_list_item(row, line, &app.value_viewer, wrap_width);
+            if app.watch.changed_rows.contains(&row) {
+                item.style(watch_changed_style())
+            } else {
+                item
+            }
+        })
+        .collect()
+}
+
+/// Builds a list row for a collection element: wrapped across several lines,
+/// or shifted by the horizontal scroll offset when wrapping is off.
+fn value_list_item<'a>(row: usize, line: &'a str, viewer: &ValueViewer, wrap_width: usize) -> ListItem<'a> {
+    if viewer.wrap_disabled {
+        let offset = viewer.value_view_scroll.1 as usize;
+        let shifted = line.char_indices().nth(offset).map_or("", |(idx, _)| &line[idx..]);
+        return ListItem::new(with_gutter(Line::from(shifted), row, viewer));
+    }
+    let lines: Vec<Line> = wrap_chars(line, wrap_width)
+        .into_iter()
+        .enumerate()
+        .map(|(idx, part)| {
+            let line = Line::from(part);
+            match idx {
+                0 => with_gutter(line, row, viewer),
+                _ => with_blank_gutter(line, viewer),
+            }
+        })
+        .collect();
+    ListItem::new(lines)
+}
+
+fn watch_changed_style() -> Style {
+    Style::default().bg(Color::Rgb(60, 60, 0))
+}
+
+/// Tints rows that changed on the last watch refresh.
+pub(super) fn mark_changed<'a>(line: Line<'a>, row: usize, app: &App) -> Line<'a> {
+    if app.watch.changed_rows.contains(&row) {
+        line.patch_style(watch_changed_style())
+    } else {
+        line
+    }
+}
+
+/// Columns taken by the line-number gutter, including its separator.
+pub(super) fn gutter_width(viewer: &ValueViewer) -> usize {
+    if viewer.line_numbers {
+        viewer.line_number_width() + 3
+    } else {
+        0
+    }
+}
+
+pub(super) fn with_gutter<'a>(line: Line<'a>, row: usize, viewer: &ValueViewer) -> Line<'a> {
+    if !viewer.line_numbers {
+        return line;
+    }
+    let label = format!("{:>width$} │ ", viewer.line_label(row), width = viewer.line_number_width());
+    prepend_span(line, Span::styled(label, Style::default().fg(Color::DarkGray)))
+}
+
+fn with_blank_gutter<'a>(line: Line<'a>, viewer: &ValueViewer) -> Line<'a> {
+    if !viewer.line_numbers {
+        return line;
+    }
+    let blank = format!("{:>width$} │ ", "", width = viewer.line_number_width());
+    prepend_span(line, Span::styled(blank, Style::default().fg(Color::DarkGray)))
+}
+
+fn prepend_span<'a>(line: Line<'a>, span: Span<'a>) -> Line<'a> {
+    let mut spans = Vec::with_capacity(line.spans.len() + 1);
+    spans.push(span);
+    spans.extend(line.spans);
+    Line::from(spans)
+}
+
+/// Splits `text` into rows of at most `width` characters.
+fn wrap_chars(text: &str, width: usize) -> Vec<String> {
+    if width == 0 || text.chars().count() <= width {
+        return vec![text.to_string()];
+    }
+    let chars: Vec<char> = text.chars().collect();
+    chars.chunks(width).map(|chunk| chunk.iter().collect()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{highlight_chars, type_badge, wrap_chars};
+
+    #[test]
+    fn wrap_chars_splits_on_character_boundaries() {
+        assert_eq!(wrap_chars("short", 10), vec!["short"]);
+        assert_eq!(wrap_chars("héllowörld", 4), vec!["héll", "owör", "ld"]);
+    }
+
+    #[test]
+    fn type_badge_covers_module_and_unknown_types() {
+        assert_eq!(type_badge("ReJSON-RL").0, "JSN");
+        assert_eq!(type_badge("zset").0, "ZST");
+        assert_eq!(type_badge("MBbloom--").0, "BLM");
+        assert_eq!(type_badge("TSDB-TYPE").0, "???");
+    }
+
+    #[test]
+    fn highlight_chars_groups_matched_runs() {
+        let spans = highlight_chars("user:1".to_string(), &[0, 1, 5]);
+        let texts: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(texts, vec!["us", "er:", "1"]);
+        assert_ne!(spans[0].style, spans[1].style);
+    }
+}
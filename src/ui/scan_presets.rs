@@ -0,0 +1,52 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_scan_presets::ScanPresetPicker;
+
+pub(super) fn draw_scan_presets_modal(f: &mut Frame, state: &ScanPresetPicker) {
+    let area = centered_rect(60, 50, f.area());
+    f.render_widget(Clear, area);
+
+    let width = state.presets.iter().map(|preset| preset.name.chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = state
+        .presets
+        .iter()
+        .enumerate()
+        .map(|(idx, preset)| {
+            let digit = if idx < 9 { (idx + 1).to_string() } else { " ".to_string() };
+            let mut line = format!(
+                "{} {:<width$}  MATCH {}",
+                digit,
+                preset.name,
+                preset.pattern.as_deref().unwrap_or("*"),
+                width = width
+            );
+            if let Some(key_type) = &preset.key_type {
+                line.push_str(&format!("  TYPE {}", key_type));
+            }
+            line.push_str(&format!("  COUNT {}", preset.count.unwrap_or(state.default_count)));
+            ListItem::new(line)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Scan filters (1-9 or Enter: apply, j/k: select, Esc: close)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+    let mut list_state = ListState::default();
+    list_state.select(Some(state.selected));
+    f.render_stateful_widget(list, area, &mut list_state);
+}
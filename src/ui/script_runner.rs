@@ -0,0 +1,83 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_script_runner::{ScriptField, ScriptMode, ScriptRunnerState};
+
+pub(super) fn draw_script_runner_modal(f: &mut Frame, state: &ScriptRunnerState) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let mode = match state.mode {
+        ScriptMode::Eval => "EVAL",
+        ScriptMode::EvalSha => "SCRIPT LOAD + EVALSHA",
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Lua Script Runner [{}] (Tab: next field, Ctrl+R: run, Ctrl+T: EVAL/EVALSHA, Esc: close)",
+            mode
+        ))
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(45),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(3),
+        ])
+        .split(inner);
+
+    let fields = [
+        (ScriptField::Script, &state.script),
+        (ScriptField::Keys, &state.keys),
+        (ScriptField::Args, &state.args),
+        (ScriptField::File, &state.file),
+    ];
+    for ((field, text), row) in fields.into_iter().zip(rows.iter()) {
+        draw_field(f, state, field, text, *row);
+    }
+
+    let result = state.result.as_deref().unwrap_or("");
+    let total_lines = result.lines().count();
+    let scroll = state.result_scroll.min(total_lines.saturating_sub(1));
+    let result_widget = Paragraph::new(result.lines().map(Line::from).collect::<Vec<_>>())
+        .block(Block::default().borders(Borders::ALL).title("Result (PgUp/PgDn)"))
+        .scroll((scroll as u16, 0));
+    f.render_widget(result_widget, rows[4]);
+}
+
+fn draw_field(f: &mut Frame, state: &ScriptRunnerState, field: ScriptField, text: &str, area: Rect) {
+    let focused = state.field == field;
+    let border = if focused { Color::Yellow } else { Color::DarkGray };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(field.label())
+        .border_style(Style::default().fg(border));
+
+    let mut scroll = 0;
+    if focused {
+        let before_cursor = &text[..state.cursor.min(text.len())];
+        let row = before_cursor.matches('\n').count() as u16;
+        let col = before_cursor.rsplit('\n').next().unwrap_or("").chars().count() as u16;
+        // Keep the cursor line in view for scripts taller than the box
+        let visible_rows = area.height.saturating_sub(2).max(1);
+        scroll = row.saturating_sub(visible_rows - 1);
+        let x = (area.x + 1 + col).min(area.right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, area.y + 1 + row - scroll));
+    }
+    let paragraph = Paragraph::new(text.lines().map(Line::from).collect::<Vec<_>>())
+        .block(block)
+        .scroll((scroll, 0));
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,120 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Position},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::analysis::highlighted_list;
+use super::{centered_rect, format_count};
+use crate::app::redis_stats::format_bytes;
+use crate::app::state_snapshots::{SnapshotPurpose, SnapshotsState};
+use crate::snapshot::{DiffKind, SnapshotEntry};
+
+pub(super) fn draw_snapshots_modal(f: &mut Frame, state: &SnapshotsState) {
+    let area = centered_rect(85, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let title = match &state.diff {
+        Some(diff) => format!("Live keyspace vs snapshot '{}' (Enter: go to key, Esc: back)", diff.against),
+        None => "Keyspace snapshots (n: new snapshot, Enter: diff against live keys, Esc: close)".to_string(),
+    };
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = outer.inner(area);
+    f.render_widget(outer, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .split(inner);
+
+    let (items, selected, list_title): (Vec<ListItem>, usize, String) = match &state.diff {
+        Some(diff) => {
+            let items = diff
+                .rows
+                .iter()
+                .map(|row| {
+                    let color = match row.kind {
+                        DiffKind::Added => Color::Green,
+                        DiffKind::Removed => Color::Red,
+                        DiffKind::Changed => Color::Yellow,
+                    };
+                    let detail = match (&row.before, &row.after) {
+                        (Some(before), Some(after)) => format!("{} -> {}", describe(before), describe(after)),
+                        (Some(entry), None) | (None, Some(entry)) => describe(entry),
+                        (None, None) => String::new(),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{} ", row.kind.marker()), Style::default().fg(color)),
+                        Span::raw(format!("{}  ", row.key)),
+                        Span::styled(detail, Style::default().fg(Color::DarkGray)),
+                    ]))
+                })
+                .collect();
+            (items, diff.selected, format!("{} differences", format_count(diff.rows.len())))
+        }
+        None => {
+            let items = state
+                .snapshots
+                .iter()
+                .map(|snapshot| ListItem::new(snapshot.name.as_str()))
+                .collect();
+            let location = state.dir.as_ref().map_or("no config directory".to_string(), |dir| dir.display().to_string());
+            (items, state.selected, format!("Saved snapshots in {}", location))
+        }
+    };
+    let mut list_state = ListState::default();
+    if !items.is_empty() {
+        list_state.select(Some(selected));
+    }
+    f.render_stateful_widget(highlighted_list(items, list_title), rows[0], &mut list_state);
+
+    if let Some(name) = &state.name_input {
+        let input = Paragraph::new(name.as_str()).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Snapshot name (Enter: capture, Esc: cancel)")
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(input, rows[1]);
+        let col = name[..state.name_cursor.min(name.len())].chars().count() as u16;
+        let x = (rows[1].x + 1 + col).min(rows[1].right().saturating_sub(2));
+        f.set_cursor_position(Position::new(x, rows[1].y + 1));
+        return;
+    }
+
+    let (status, color) = match (&state.job, &state.status) {
+        (Some(job), _) => {
+            let action = match &job.purpose {
+                SnapshotPurpose::Capture(name) => format!("Capturing '{}'", name),
+                SnapshotPurpose::Compare(snapshot) => format!("Comparing with '{}'", snapshot.name),
+            };
+            (format!("{}... {} keys", action, format_count(job.keys.len())), Color::Yellow)
+        }
+        (None, Some(status)) => (status.clone(), Color::White),
+        (None, None) => (String::new(), Color::White),
+    };
+    let status = Paragraph::new(status)
+        .style(Style::default().fg(color))
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::DarkGray)));
+    f.render_widget(status, rows[1]);
+}
+
+/// `hash, 12 items, 1.2 KB`
+fn describe(entry: &SnapshotEntry) -> String {
+    let mut parts = vec![entry.key_type.clone()];
+    if let Some(length) = entry.length {
+        parts.push(match entry.key_type.as_str() {
+            "string" => format!("{} bytes", format_count(length as usize)),
+            _ => format!("{} items", format_count(length as usize)),
+        });
+    }
+    if let Some(memory) = entry.memory {
+        parts.push(format_bytes(memory));
+    }
+    parts.join(", ")
+}
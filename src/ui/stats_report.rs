@@ -0,0 +1,40 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::app::state_stats_report::StatsReportState;
+
+pub(super) fn draw_stats_report_modal(f: &mut Frame, state: &StatsReportState) {
+    let area = centered_rect(80, 80, f.area());
+    f.render_widget(Clear, area);
+
+    let lines: Vec<Line> = state
+        .report
+        .lines()
+        .map(|line| {
+            if line.starts_with("# Redis stats") {
+                Line::styled(line, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            } else if line.starts_with("## ") {
+                Line::styled(line, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+            } else if line.starts_with("(error)") {
+                Line::styled(line, Style::default().fg(Color::Red))
+            } else {
+                Line::from(line)
+            }
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Stats report (y: copy, s: save, r: refresh, j/k/PgUp/PgDn: scroll, Esc: close)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((state.scroll as u16, 0));
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,55 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::notifications::{Notifications, Severity};
+
+/// Widest a toast gets; longer messages wrap onto extra lines.
+const TOAST_MAX_WIDTH: u16 = 60;
+/// Lines of text a single toast may wrap onto before it is cut off.
+const TOAST_MAX_LINES: u16 = 3;
+
+fn severity_style(severity: Severity) -> (Color, &'static str) {
+    match severity {
+        Severity::Info => (Color::LightCyan, "Info"),
+        Severity::Success => (Color::Green, "Done"),
+        Severity::Error => (Color::Red, "Error"),
+    }
+}
+
+/// Stacks the live toasts in the bottom-right corner of `area`, newest at the bottom.
+pub(super) fn draw_toasts(f: &mut Frame, notifications: &Notifications, area: Rect) {
+    let width = TOAST_MAX_WIDTH.min(area.width.saturating_sub(2));
+    let inner_width = width.saturating_sub(2);
+    if inner_width == 0 {
+        return;
+    }
+    let mut bottom = area.bottom();
+    let toasts: Vec<_> = notifications.toasts().collect();
+    for toast in toasts.into_iter().rev() {
+        let chars = toast.message.chars().count() as u16;
+        let lines = chars.div_ceil(inner_width).clamp(1, TOAST_MAX_LINES);
+        let height = lines + 2;
+        if bottom < area.y + height {
+            break;
+        }
+        bottom -= height;
+        let toast_area = Rect::new(area.right() - width - 1, bottom, width, height);
+        let (color, title) = severity_style(toast.severity);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(color));
+        f.render_widget(Clear, toast_area);
+        f.render_widget(
+            Paragraph::new(toast.message.as_str())
+                .style(Style::default().fg(color))
+                .block(block)
+                .wrap(Wrap { trim: true }),
+            toast_area,
+        );
+    }
+}